@@ -0,0 +1,903 @@
+use crate::error::Error;
+use crate::hart::offsets::{GAS, MEMORY, MEMORY_SIZE, NATIVE_OFFSETS, PC, REGISTERS};
+use crate::instruction::RiscVInstruction;
+use crate::profile::Profile;
+use crate::program::{NO_ENTRY, Program, Translation};
+use crate::register::Register;
+
+/// An AArch64 general-purpose register. Register 31 is the zero register or `sp`,
+/// depending on the instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct X(pub(crate) u32);
+
+/// The first argument and return value register.
+const X0: X = X(0);
+/// The second argument register.
+const X1: X = X(1);
+/// The pc of the instruction native code exits at.
+const EXIT_PC: X = X(9);
+/// Scratch registers, which native code does not preserve between instructions.
+const T0: X = X(10);
+const T1: X = X(11);
+const T2: X = X(12);
+const T3: X = X(13);
+/// The [`Hart`](crate::hart::Hart) of the call, for as long as native code runs.
+const HART: X = X(19);
+/// The start of guest memory.
+const MEMORY_BASE: X = X(20);
+/// The size of guest memory.
+const MEMORY_END: X = X(21);
+/// The gas remaining.
+const GAS_LEFT: X = X(22);
+/// The native offsets of the instructions.
+const OFFSETS: X = X(23);
+/// The start of the native code.
+const CODE: X = X(24);
+const FP: X = X(29);
+const LR: X = X(30);
+/// The zero register, or the stack pointer for loads, stores and immediate additions.
+const ZR: X = X(31);
+const SP: X = X(31);
+
+/// A condition of a conditional branch or select.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Condition {
+    Eq = 0x0,
+    Ne = 0x1,
+    Hs = 0x2,
+    Lo = 0x3,
+    Hi = 0x8,
+    Ls = 0x9,
+    Ge = 0xa,
+    Lt = 0xb,
+}
+
+impl Condition {
+    /// Returns the condition that holds exactly when this one does not.
+    fn invert(self) -> Condition {
+        match self {
+            Condition::Eq => Condition::Ne,
+            Condition::Ne => Condition::Eq,
+            Condition::Hs => Condition::Lo,
+            Condition::Lo => Condition::Hs,
+            Condition::Hi => Condition::Ls,
+            Condition::Ls => Condition::Hi,
+            Condition::Ge => Condition::Lt,
+            Condition::Lt => Condition::Ge,
+        }
+    }
+}
+
+/// The largest distance of an unconditional branch, in either direction.
+const BRANCH_RANGE: usize = 1 << 27;
+
+/// An encoder of AArch64 instructions.
+///
+/// Every method appends one instruction. `wide` selects the 64-bit form of an
+/// instruction that has both; the 32-bit form zero-extends its result to 64 bits.
+#[derive(Debug, Default)]
+pub(crate) struct Emitter {
+    pub(crate) code: Vec<u8>,
+}
+
+impl Emitter {
+    /// Returns the offset of the next instruction.
+    pub(crate) fn position(&self) -> usize {
+        self.code.len()
+    }
+
+    /// Appends an instruction word.
+    pub(crate) fn emit(&mut self, word: u32) {
+        self.code.extend(word.to_le_bytes());
+    }
+
+    /// Replaces the instruction word at `position`.
+    fn patch(&mut self, position: usize, word: u32) {
+        self.code[position..position + 4].copy_from_slice(&word.to_le_bytes());
+    }
+
+    /// The distance from the next instruction to `target`, in instructions.
+    fn distance(&self, target: usize) -> u32 {
+        ((target as i64 - self.position() as i64) / 4) as u32
+    }
+
+    /// Selects the 64-bit form of an instruction.
+    fn sf(wide: bool) -> u32 {
+        (wide as u32) << 31
+    }
+
+    /// `op rd, rn, rm` for the data-processing (register) instructions.
+    fn three(&mut self, base: u32, wide: bool, rd: X, rn: X, rm: X) {
+        self.emit(base | Self::sf(wide) | rm.0 << 16 | rn.0 << 5 | rd.0);
+    }
+
+    pub(crate) fn add(&mut self, wide: bool, rd: X, rn: X, rm: X) {
+        self.three(0x0b00_0000, wide, rd, rn, rm);
+    }
+
+    /// `add rd, rn, rm, lsl #shift`
+    pub(crate) fn add_shifted(&mut self, wide: bool, rd: X, rn: X, rm: X, shift: u32) {
+        self.three(0x0b00_0000 | shift << 10, wide, rd, rn, rm);
+    }
+
+    pub(crate) fn sub(&mut self, wide: bool, rd: X, rn: X, rm: X) {
+        self.three(0x4b00_0000, wide, rd, rn, rm);
+    }
+
+    /// `cmp rn, rm`
+    pub(crate) fn cmp(&mut self, wide: bool, rn: X, rm: X) {
+        self.three(0x6b00_0000, wide, ZR, rn, rm);
+    }
+
+    pub(crate) fn and(&mut self, wide: bool, rd: X, rn: X, rm: X) {
+        self.three(0x0a00_0000, wide, rd, rn, rm);
+    }
+
+    pub(crate) fn orr(&mut self, wide: bool, rd: X, rn: X, rm: X) {
+        self.three(0x2a00_0000, wide, rd, rn, rm);
+    }
+
+    pub(crate) fn eor(&mut self, wide: bool, rd: X, rn: X, rm: X) {
+        self.three(0x4a00_0000, wide, rd, rn, rm);
+    }
+
+    pub(crate) fn lslv(&mut self, wide: bool, rd: X, rn: X, rm: X) {
+        self.three(0x1ac0_2000, wide, rd, rn, rm);
+    }
+
+    pub(crate) fn lsrv(&mut self, wide: bool, rd: X, rn: X, rm: X) {
+        self.three(0x1ac0_2400, wide, rd, rn, rm);
+    }
+
+    pub(crate) fn asrv(&mut self, wide: bool, rd: X, rn: X, rm: X) {
+        self.three(0x1ac0_2800, wide, rd, rn, rm);
+    }
+
+    pub(crate) fn sdiv(&mut self, wide: bool, rd: X, rn: X, rm: X) {
+        self.three(0x1ac0_0c00, wide, rd, rn, rm);
+    }
+
+    pub(crate) fn udiv(&mut self, wide: bool, rd: X, rn: X, rm: X) {
+        self.three(0x1ac0_0800, wide, rd, rn, rm);
+    }
+
+    /// `madd rd, rn, rm, ra`, or `mul rd, rn, rm` with `ra` = `ZR`.
+    pub(crate) fn madd(&mut self, wide: bool, rd: X, rn: X, rm: X, ra: X) {
+        self.three(0x1b00_0000 | ra.0 << 10, wide, rd, rn, rm);
+    }
+
+    /// `msub rd, rn, rm, ra`: `rd = ra - rn * rm`.
+    pub(crate) fn msub(&mut self, wide: bool, rd: X, rn: X, rm: X, ra: X) {
+        self.three(0x1b00_8000 | ra.0 << 10, wide, rd, rn, rm);
+    }
+
+    /// `smull xd, wn, wm`
+    pub(crate) fn smull(&mut self, rd: X, rn: X, rm: X) {
+        self.three(0x1b20_7c00, true, rd, rn, rm);
+    }
+
+    /// `umull xd, wn, wm`
+    pub(crate) fn umull(&mut self, rd: X, rn: X, rm: X) {
+        self.three(0x1ba0_7c00, true, rd, rn, rm);
+    }
+
+    pub(crate) fn smulh(&mut self, rd: X, rn: X, rm: X) {
+        self.three(0x1b40_7c00, true, rd, rn, rm);
+    }
+
+    pub(crate) fn umulh(&mut self, rd: X, rn: X, rm: X) {
+        self.three(0x1bc0_7c00, true, rd, rn, rm);
+    }
+
+    /// `csinv rd, rn, rm, cond`: `rd = cond ? rn : !rm`.
+    pub(crate) fn csinv(&mut self, wide: bool, rd: X, rn: X, rm: X, cond: Condition) {
+        self.three(0x5a80_0000 | (cond as u32) << 12, wide, rd, rn, rm);
+    }
+
+    /// `cset rd, cond`
+    pub(crate) fn cset(&mut self, wide: bool, rd: X, cond: Condition) {
+        let cond = cond.invert() as u32;
+        self.three(0x1a80_0400 | cond << 12, wide, rd, ZR, ZR);
+    }
+
+    /// `op rd, rn, #imm` for the add and subtract (immediate) instructions.
+    fn immediate(&mut self, base: u32, wide: bool, rd: X, rn: X, imm: u32) {
+        debug_assert!(imm < 0x1000);
+        self.emit(base | Self::sf(wide) | imm << 10 | rn.0 << 5 | rd.0);
+    }
+
+    pub(crate) fn add_imm(&mut self, wide: bool, rd: X, rn: X, imm: u32) {
+        self.immediate(0x1100_0000, wide, rd, rn, imm);
+    }
+
+    pub(crate) fn sub_imm(&mut self, wide: bool, rd: X, rn: X, imm: u32) {
+        self.immediate(0x5100_0000, wide, rd, rn, imm);
+    }
+
+    /// `cmp rn, #imm`
+    pub(crate) fn cmp_imm(&mut self, wide: bool, rn: X, imm: u32) {
+        self.immediate(0x7100_0000, wide, ZR, rn, imm);
+    }
+
+    /// `cmn rn, #imm`
+    pub(crate) fn cmn_imm(&mut self, wide: bool, rn: X, imm: u32) {
+        self.immediate(0x3100_0000, wide, ZR, rn, imm);
+    }
+
+    /// Adds a signed immediate that fits in 12 bits and a sign.
+    fn add_signed(&mut self, wide: bool, rd: X, rn: X, imm: i16) {
+        match imm < 0 {
+            true => self.sub_imm(wide, rd, rn, imm.unsigned_abs() as u32),
+            false => self.add_imm(wide, rd, rn, imm as u32),
+        }
+    }
+
+    /// `and rd, rn, #~1`, clearing the lowest bit.
+    pub(crate) fn clear_bit0(&mut self, wide: bool, rd: X, rn: X) {
+        let (n, immr, imms) = match wide {
+            true => (1, 63, 62),
+            false => (0, 31, 30),
+        };
+        self.emit(
+            0x1200_0000 | Self::sf(wide) | n << 22 | immr << 16 | imms << 10 | rn.0 << 5 | rd.0,
+        );
+    }
+
+    /// `op rd, rn, #immr, #imms` for the bitfield move instructions.
+    fn bitfield(&mut self, base: u32, wide: bool, rd: X, rn: X, immr: u32, imms: u32) {
+        let n = (wide as u32) << 22;
+        self.emit(base | Self::sf(wide) | n | immr << 16 | imms << 10 | rn.0 << 5 | rd.0);
+    }
+
+    pub(crate) fn ubfm(&mut self, wide: bool, rd: X, rn: X, immr: u32, imms: u32) {
+        self.bitfield(0x5300_0000, wide, rd, rn, immr, imms);
+    }
+
+    pub(crate) fn sbfm(&mut self, wide: bool, rd: X, rn: X, immr: u32, imms: u32) {
+        self.bitfield(0x1300_0000, wide, rd, rn, immr, imms);
+    }
+
+    /// `lsl rd, rn, #shift`
+    pub(crate) fn lsl(&mut self, wide: bool, rd: X, rn: X, shift: u32) {
+        let size = if wide { 64 } else { 32 };
+        self.ubfm(wide, rd, rn, (size - shift) % size, size - 1 - shift);
+    }
+
+    /// `lsr rd, rn, #shift`
+    pub(crate) fn lsr(&mut self, wide: bool, rd: X, rn: X, shift: u32) {
+        let size = if wide { 64 } else { 32 };
+        self.ubfm(wide, rd, rn, shift, size - 1);
+    }
+
+    /// `asr rd, rn, #shift`
+    pub(crate) fn asr(&mut self, wide: bool, rd: X, rn: X, shift: u32) {
+        let size = if wide { 64 } else { 32 };
+        self.sbfm(wide, rd, rn, shift, size - 1);
+    }
+
+    /// `sxtw xd, wn`
+    pub(crate) fn sxtw(&mut self, rd: X, rn: X) {
+        self.sbfm(true, rd, rn, 0, 31);
+    }
+
+    /// `op rd, #imm16, lsl #(16 * hw)` for the move wide instructions.
+    fn move_wide(&mut self, base: u32, wide: bool, rd: X, imm: u16, hw: u32) {
+        self.emit(base | Self::sf(wide) | hw << 21 | u32::from(imm) << 5 | rd.0);
+    }
+
+    pub(crate) fn movz(&mut self, wide: bool, rd: X, imm: u16, hw: u32) {
+        self.move_wide(0x5280_0000, wide, rd, imm, hw);
+    }
+
+    pub(crate) fn movk(&mut self, wide: bool, rd: X, imm: u16, hw: u32) {
+        self.move_wide(0x7280_0000, wide, rd, imm, hw);
+    }
+
+    pub(crate) fn movn(&mut self, wide: bool, rd: X, imm: u16, hw: u32) {
+        self.move_wide(0x1280_0000, wide, rd, imm, hw);
+    }
+
+    /// Moves `value` into `rd` with as few move wide instructions as possible.
+    pub(crate) fn mov_imm(&mut self, wide: bool, rd: X, value: u64) {
+        let count = if wide { 4 } else { 2 };
+        let halves: Vec<u16> = (0..count).map(|hw| (value >> (16 * hw)) as u16).collect();
+        let ones = halves.iter().filter(|half| **half == u16::MAX).count();
+        let zeros = halves.iter().filter(|half| **half == 0).count();
+
+        // Start from all ones with `movn` if fewer halves then need to be changed.
+        let skip = if ones > zeros { u16::MAX } else { 0 };
+        let first = halves.iter().position(|half| *half != skip).unwrap_or(0);
+        match skip {
+            u16::MAX => self.movn(wide, rd, !halves[first], first as u32),
+            _ => self.movz(wide, rd, halves[first], first as u32),
+        }
+        for (hw, half) in halves.iter().enumerate().skip(first + 1) {
+            if *half != skip {
+                self.movk(wide, rd, *half, hw as u32);
+            }
+        }
+    }
+
+    /// `adr rd, target`
+    pub(crate) fn adr(&mut self, rd: X, target: usize) {
+        let offset = (target as i64 - self.position() as i64) as u32;
+        self.emit(0x1000_0000 | (offset & 0x3) << 29 | (offset >> 2 & 0x7_ffff) << 5 | rd.0);
+    }
+
+    /// `b target`
+    pub(crate) fn b(&mut self, target: usize) {
+        let distance = self.distance(target);
+        self.emit(0x1400_0000 | distance & 0x3ff_ffff);
+    }
+
+    /// `b.cond target`
+    pub(crate) fn b_cond(&mut self, cond: Condition, target: usize) {
+        let distance = self.distance(target);
+        self.emit(0x5400_0000 | (distance & 0x7_ffff) << 5 | cond as u32);
+    }
+
+    /// `cbnz xt, target`
+    pub(crate) fn cbnz(&mut self, rt: X, target: usize) {
+        let distance = self.distance(target);
+        self.emit(0xb500_0000 | (distance & 0x7_ffff) << 5 | rt.0);
+    }
+
+    pub(crate) fn br(&mut self, rn: X) {
+        self.emit(0xd61f_0000 | rn.0 << 5);
+    }
+
+    pub(crate) fn ret(&mut self) {
+        self.emit(0xd65f_03c0);
+    }
+
+    /// `ldr xt, [xn, #offset]`
+    pub(crate) fn ldr(&mut self, rt: X, rn: X, offset: usize) {
+        debug_assert!(offset % 8 == 0 && offset / 8 < 0x1000);
+        self.emit(0xf940_0000 | (offset as u32 / 8) << 10 | rn.0 << 5 | rt.0);
+    }
+
+    /// `str xt, [xn, #offset]`
+    pub(crate) fn str(&mut self, rt: X, rn: X, offset: usize) {
+        debug_assert!(offset % 8 == 0 && offset / 8 < 0x1000);
+        self.emit(0xf900_0000 | (offset as u32 / 8) << 10 | rn.0 << 5 | rt.0);
+    }
+
+    /// `ldr wt, [xn]`
+    pub(crate) fn ldr_w(&mut self, rt: X, rn: X) {
+        self.emit(0xb940_0000 | rn.0 << 5 | rt.0);
+    }
+
+    /// A load or store of `rt` at `[xn, xm]`, with `base` selecting the width,
+    /// direction and extension.
+    pub(crate) fn indexed(&mut self, base: u32, rt: X, rn: X, rm: X) {
+        self.emit(base | rm.0 << 16 | rn.0 << 5 | rt.0);
+    }
+
+    /// `stp xt1, xt2, [xn, #offset]!`
+    pub(crate) fn stp_pre(&mut self, rt1: X, rt2: X, rn: X, offset: i32) {
+        self.pair(0xa980_0000, rt1, rt2, rn, offset);
+    }
+
+    /// `stp xt1, xt2, [xn, #offset]`
+    pub(crate) fn stp(&mut self, rt1: X, rt2: X, rn: X, offset: i32) {
+        self.pair(0xa900_0000, rt1, rt2, rn, offset);
+    }
+
+    /// `ldp xt1, xt2, [xn, #offset]`
+    pub(crate) fn ldp(&mut self, rt1: X, rt2: X, rn: X, offset: i32) {
+        self.pair(0xa940_0000, rt1, rt2, rn, offset);
+    }
+
+    /// `ldp xt1, xt2, [xn], #offset`
+    pub(crate) fn ldp_post(&mut self, rt1: X, rt2: X, rn: X, offset: i32) {
+        self.pair(0xa8c0_0000, rt1, rt2, rn, offset);
+    }
+
+    fn pair(&mut self, base: u32, rt1: X, rt2: X, rn: X, offset: i32) {
+        let imm = (offset / 8) as u32 & 0x7f;
+        self.emit(base | imm << 15 | rt2.0 << 10 | rn.0 << 5 | rt1.0);
+    }
+}
+
+/// The `indexed` encodings of the loads, by width in bytes and whether they sign-extend
+/// to 32 or 64 bits.
+fn load_encoding(width: u8, signed: bool, wide: bool) -> u32 {
+    match (width, signed, wide) {
+        (1, false, _) => 0x3860_6800,
+        (1, true, false) => 0x38e0_6800,
+        (1, true, true) => 0x38a0_6800,
+        (2, false, _) => 0x7860_6800,
+        (2, true, false) => 0x78e0_6800,
+        (2, true, true) => 0x78a0_6800,
+        (4, true, true) => 0xb8a0_6800,
+        (4, _, _) => 0xb860_6800,
+        _ => 0xf860_6800,
+    }
+}
+
+/// The `indexed` encodings of the stores, by width in bytes.
+fn store_encoding(width: u8) -> u32 {
+    match width {
+        1 => 0x3820_6800,
+        2 => 0x7820_6800,
+        4 => 0xb820_6800,
+        _ => 0xf820_6800,
+    }
+}
+
+/// The byte offset of the stack frame of the entry point.
+const FRAME_SIZE: i32 = 64;
+
+/// A translator of RISC-V programs to AArch64 code.
+///
+/// Guest registers live in the hart, and each instruction loads its operands into
+/// `T0` to `T3`. While native code runs, the callee-saved registers x19 to x24 hold
+/// `HART`, `MEMORY_BASE`, `MEMORY_END`, `GAS_LEFT`, `OFFSETS` and `CODE`. The entry
+/// point saves them with the frame pointer and link register in a frame of
+/// `FRAME_SIZE` bytes, which the shared exit sequence restores.
+struct Translator<'a> {
+    program: &'a Program,
+    emitter: Emitter,
+    /// Whether the guest has 64-bit registers.
+    wide: bool,
+    /// The offset of the shared exit sequence.
+    exit: usize,
+    /// The native offset of every instruction, indexed by address / 2.
+    offsets: Vec<u32>,
+    /// Branches to patch once every instruction has an offset, with the guest
+    /// address they branch to.
+    fixups: Vec<(usize, u64)>,
+}
+
+/// Translates `program` to AArch64 code following the protocol of
+/// [`Entry`](crate::hart::Entry).
+///
+/// # Errors
+///
+/// - `Error::InvalidCodeSize` if the native code is too large to branch across.
+pub(crate) fn translate(program: &Program, profile: Profile) -> Result<Translation, Error> {
+    let mut translator = Translator {
+        program,
+        emitter: Emitter::default(),
+        wide: profile.xlen() == 64,
+        exit: 0,
+        offsets: vec![NO_ENTRY; program.len() as usize / 2],
+        fixups: vec![],
+    };
+
+    translator.entry();
+    for (pc, instruction, length) in program.iter() {
+        translator.offsets[pc as usize / 2] = translator.emitter.position() as u32;
+        translator.instruction(pc, instruction, length);
+    }
+    // Falling off the end of the code exits with the pc past its last instruction.
+    translator.stub(program.len());
+
+    if translator.emitter.position() >= BRANCH_RANGE {
+        return Err(Error::InvalidCodeSize);
+    }
+    for (position, target) in std::mem::take(&mut translator.fixups) {
+        let target = translator.offsets[target as usize / 2] as usize;
+        let distance = ((target as i64 - position as i64) / 4) as u32;
+        translator
+            .emitter
+            .patch(position, 0x1400_0000 | distance & 0x3ff_ffff);
+    }
+
+    Ok(Translation {
+        code: translator.emitter.code,
+        offsets: translator.offsets,
+    })
+}
+
+impl Translator<'_> {
+    /// Emits the entry point and the exit sequence it shares with every stub.
+    fn entry(&mut self) {
+        let e = &mut self.emitter;
+        e.stp_pre(FP, LR, SP, -FRAME_SIZE);
+        e.add_imm(true, FP, SP, 0);
+        e.stp(HART, MEMORY_BASE, SP, 16);
+        e.stp(MEMORY_END, GAS_LEFT, SP, 32);
+        e.stp(OFFSETS, CODE, SP, 48);
+        e.add_imm(true, HART, X0, 0);
+        e.ldr(MEMORY_BASE, HART, MEMORY);
+        e.ldr(MEMORY_END, HART, MEMORY_SIZE);
+        e.ldr(GAS_LEFT, HART, GAS);
+        e.ldr(OFFSETS, HART, NATIVE_OFFSETS);
+        e.adr(CODE, 0);
+        e.br(X1);
+
+        self.exit = e.position();
+        e.str(EXIT_PC, HART, PC);
+        e.str(GAS_LEFT, HART, GAS);
+        e.ldp(OFFSETS, CODE, SP, 48);
+        e.ldp(MEMORY_END, GAS_LEFT, SP, 32);
+        e.ldp(HART, MEMORY_BASE, SP, 16);
+        e.ldp_post(FP, LR, SP, FRAME_SIZE);
+        e.ret();
+    }
+
+    /// Emits a stub that exits to Rust at `pc`.
+    fn stub(&mut self, pc: u64) {
+        self.emitter.movz(false, EXIT_PC, pc as u16, 0);
+        self.emitter.movk(false, EXIT_PC, (pc >> 16) as u16, 1);
+        self.emitter.b(self.exit);
+    }
+
+    /// Emits the native code of `instruction`, located at `pc`.
+    fn instruction(&mut self, pc: u64, instruction: RiscVInstruction, length: u8) {
+        let start = self.emitter.position();
+        let stub = start + 4;
+        let body = stub + 12;
+
+        self.emitter.cbnz(GAS_LEFT, body);
+        self.stub(pc);
+        if !self.body(pc, instruction, length, stub) {
+            self.emitter.code.truncate(start);
+            self.stub(pc);
+        }
+    }
+
+    /// Emits the body of `instruction`, which exits through `stub` to trap. Returns
+    /// `false` if native code does not implement the instruction.
+    fn body(&mut self, pc: u64, instruction: RiscVInstruction, length: u8, stub: usize) -> bool {
+        use RiscVInstruction::*;
+
+        let wide = self.wide;
+        let next = self.wrap(pc + u64::from(length));
+
+        match instruction {
+            Add { rd, rs1, rs2 } => self.register(rd, rs1, rs2, |e, a, b| e.add(wide, a, a, b)),
+            Sub { rd, rs1, rs2 } => self.register(rd, rs1, rs2, |e, a, b| e.sub(wide, a, a, b)),
+            Xor { rd, rs1, rs2 } => self.register(rd, rs1, rs2, |e, a, b| e.eor(wide, a, a, b)),
+            Or { rd, rs1, rs2 } => self.register(rd, rs1, rs2, |e, a, b| e.orr(wide, a, a, b)),
+            And { rd, rs1, rs2 } => self.register(rd, rs1, rs2, |e, a, b| e.and(wide, a, a, b)),
+            Sll { rd, rs1, rs2 } => self.register(rd, rs1, rs2, |e, a, b| e.lslv(wide, a, a, b)),
+            Srl { rd, rs1, rs2 } => self.register(rd, rs1, rs2, |e, a, b| e.lsrv(wide, a, a, b)),
+            Sra { rd, rs1, rs2 } => self.register(rd, rs1, rs2, |e, a, b| e.asrv(wide, a, a, b)),
+            Slt { rd, rs1, rs2 } => self.register(rd, rs1, rs2, |e, a, b| {
+                e.cmp(wide, a, b);
+                e.cset(wide, a, Condition::Lt);
+            }),
+            Sltu { rd, rs1, rs2 } => self.register(rd, rs1, rs2, |e, a, b| {
+                e.cmp(wide, a, b);
+                e.cset(wide, a, Condition::Lo);
+            }),
+            Mul { rd, rs1, rs2 } => {
+                self.register(rd, rs1, rs2, |e, a, b| e.madd(wide, a, a, b, ZR));
+            }
+            Mulh { rd, rs1, rs2 } if wide => {
+                self.register(rd, rs1, rs2, |e, a, b| e.smulh(a, a, b));
+            }
+            Mulh { rd, rs1, rs2 } => self.register(rd, rs1, rs2, |e, a, b| {
+                e.smull(a, a, b);
+                e.lsr(true, a, a, 32);
+            }),
+            Mulhu { rd, rs1, rs2 } if wide => {
+                self.register(rd, rs1, rs2, |e, a, b| e.umulh(a, a, b));
+            }
+            Mulhu { rd, rs1, rs2 } => self.register(rd, rs1, rs2, |e, a, b| {
+                e.umull(a, a, b);
+                e.lsr(true, a, a, 32);
+            }),
+            // The product of a signed and an unsigned 32-bit value fits in 64 bits.
+            Mulhsu { rd, rs1, rs2 } if !wide => self.register(rd, rs1, rs2, |e, a, b| {
+                e.sxtw(a, a);
+                e.madd(true, a, a, b, ZR);
+                e.lsr(true, a, a, 32);
+            }),
+            Div { rd, rs1, rs2 } => {
+                self.register(rd, rs1, rs2, |e, a, b| divide(e, wide, true, a, b))
+            }
+            Divu { rd, rs1, rs2 } => {
+                self.register(rd, rs1, rs2, |e, a, b| divide(e, wide, false, a, b));
+            }
+            Rem { rd, rs1, rs2 } => {
+                self.register(rd, rs1, rs2, |e, a, b| remainder(e, wide, true, a, b));
+            }
+            Remu { rd, rs1, rs2 } => {
+                self.register(rd, rs1, rs2, |e, a, b| remainder(e, wide, false, a, b));
+            }
+            Addw { rd, rs1, rs2 } => self.word(rd, rs1, rs2, |e, a, b| e.add(false, a, a, b)),
+            Subw { rd, rs1, rs2 } => self.word(rd, rs1, rs2, |e, a, b| e.sub(false, a, a, b)),
+            Sllw { rd, rs1, rs2 } => self.word(rd, rs1, rs2, |e, a, b| e.lslv(false, a, a, b)),
+            Srlw { rd, rs1, rs2 } => self.word(rd, rs1, rs2, |e, a, b| e.lsrv(false, a, a, b)),
+            Sraw { rd, rs1, rs2 } => self.word(rd, rs1, rs2, |e, a, b| e.asrv(false, a, a, b)),
+            Mulw { rd, rs1, rs2 } => {
+                self.word(rd, rs1, rs2, |e, a, b| e.madd(false, a, a, b, ZR));
+            }
+            Divw { rd, rs1, rs2 } => {
+                self.word(rd, rs1, rs2, |e, a, b| divide(e, false, true, a, b))
+            }
+            Divuw { rd, rs1, rs2 } => {
+                self.word(rd, rs1, rs2, |e, a, b| divide(e, false, false, a, b));
+            }
+            Remw { rd, rs1, rs2 } => {
+                self.word(rd, rs1, rs2, |e, a, b| remainder(e, false, true, a, b));
+            }
+            Remuw { rd, rs1, rs2 } => {
+                self.word(rd, rs1, rs2, |e, a, b| remainder(e, false, false, a, b));
+            }
+            Addi { rd, rs1, imm } => self.unary(rd, rs1, |e, a| e.add_signed(wide, a, a, imm)),
+            Xori { rd, rs1, imm } => {
+                self.immediate(rd, rs1, imm, |e, a, b| e.eor(wide, a, a, b));
+            }
+            Ori { rd, rs1, imm } => self.immediate(rd, rs1, imm, |e, a, b| e.orr(wide, a, a, b)),
+            Andi { rd, rs1, imm } => {
+                self.immediate(rd, rs1, imm, |e, a, b| e.and(wide, a, a, b));
+            }
+            Slti { rd, rs1, imm } => self.immediate(rd, rs1, imm, |e, a, b| {
+                e.cmp(wide, a, b);
+                e.cset(wide, a, Condition::Lt);
+            }),
+            Sltiu { rd, rs1, imm } => self.immediate(rd, rs1, imm, |e, a, b| {
+                e.cmp(wide, a, b);
+                e.cset(wide, a, Condition::Lo);
+            }),
+            Slli { rd, rs1, imm } => {
+                let shift = self.shamt(imm);
+                self.unary(rd, rs1, |e, a| e.lsl(wide, a, a, shift));
+            }
+            Srli { rd, rs1, imm } => {
+                let shift = self.shamt(imm);
+                self.unary(rd, rs1, |e, a| e.lsr(wide, a, a, shift));
+            }
+            Srai { rd, rs1, imm } => {
+                let shift = self.shamt(imm);
+                self.unary(rd, rs1, |e, a| e.asr(wide, a, a, shift));
+            }
+            Addiw { rd, rs1, imm } => self.unary(rd, rs1, |e, a| {
+                e.add_signed(false, a, a, imm);
+                e.sxtw(a, a);
+            }),
+            Slliw { rd, rs1, imm } => self.unary(rd, rs1, |e, a| {
+                e.lsl(false, a, a, imm as u32 & 0x1f);
+                e.sxtw(a, a);
+            }),
+            Srliw { rd, rs1, imm } => self.unary(rd, rs1, |e, a| {
+                e.lsr(false, a, a, imm as u32 & 0x1f);
+                e.sxtw(a, a);
+            }),
+            Sraiw { rd, rs1, imm } => self.unary(rd, rs1, |e, a| {
+                e.asr(false, a, a, imm as u32 & 0x1f);
+                e.sxtw(a, a);
+            }),
+            Lui { rd, imm } => self.constant(rd, imm as i64 as u64),
+            Auipc { rd, imm } => self.constant(rd, pc.wrapping_add(imm as i64 as u64)),
+            Jal { rd, imm } => {
+                let target = self.wrap(pc.wrapping_add(imm as i64 as u64));
+                if self.program.get(target).is_none() {
+                    return false;
+                }
+
+                self.charge();
+                self.link(rd, next);
+                self.jump(target);
+            }
+            Jalr { rd, rs1, imm } => self.jalr(rd, rs1, imm, next, stub),
+            Beq { rs1, rs2, imm } => return self.branch(pc, rs1, rs2, imm, Condition::Eq),
+            Bne { rs1, rs2, imm } => return self.branch(pc, rs1, rs2, imm, Condition::Ne),
+            Blt { rs1, rs2, imm } => return self.branch(pc, rs1, rs2, imm, Condition::Lt),
+            Bge { rs1, rs2, imm } => return self.branch(pc, rs1, rs2, imm, Condition::Ge),
+            Bltu { rs1, rs2, imm } => return self.branch(pc, rs1, rs2, imm, Condition::Lo),
+            Bgeu { rs1, rs2, imm } => return self.branch(pc, rs1, rs2, imm, Condition::Hs),
+            Lb { rd, rs1, imm }
+            | Lh { rd, rs1, imm }
+            | Lw { rd, rs1, imm }
+            | Lbu { rd, rs1, imm }
+            | Lhu { rd, rs1, imm }
+            | Ld { rd, rs1, imm }
+            | Lwu { rd, rs1, imm } => {
+                let access = instruction.memory_access().expect("loads access memory");
+                self.address(rs1, imm, access.width, stub);
+                self.charge();
+                let encoding = load_encoding(access.width, access.signed, wide);
+                self.emitter.indexed(encoding, T1, MEMORY_BASE, T0);
+                self.store(rd, T1);
+            }
+            Sb { rs1, rs2, imm }
+            | Sh { rs1, rs2, imm }
+            | Sw { rs1, rs2, imm }
+            | Sd { rs1, rs2, imm } => {
+                let access = instruction.memory_access().expect("stores access memory");
+                self.address(rs1, imm, access.width, stub);
+                self.load(T1, rs2);
+                self.charge();
+                self.emitter
+                    .indexed(store_encoding(access.width), T1, MEMORY_BASE, T0);
+            }
+            Fence { .. } | FenceTso | FenceI => self.charge(),
+            _ => return false,
+        }
+
+        true
+    }
+
+    /// Loads the value of `register` into `x`.
+    fn load(&mut self, x: X, register: Register) {
+        let offset = REGISTERS + 8 * register.number() as usize;
+        self.emitter.ldr(x, HART, offset);
+    }
+
+    /// Stores `x` to `register`, unless it is `x0`.
+    fn store(&mut self, register: Register, x: X) {
+        if register != Register::ZERO {
+            let offset = REGISTERS + 8 * register.number() as usize;
+            self.emitter.str(x, HART, offset);
+        }
+    }
+
+    /// Charges the gas of the instruction.
+    fn charge(&mut self) {
+        self.emitter.sub_imm(true, GAS_LEFT, GAS_LEFT, 1);
+    }
+
+    /// Emits an instruction of two source registers, computed by `op` into its first
+    /// operand.
+    fn register(
+        &mut self,
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+        op: impl FnOnce(&mut Emitter, X, X),
+    ) {
+        self.charge();
+        self.load(T0, rs1);
+        self.load(T1, rs2);
+        op(&mut self.emitter, T0, T1);
+        self.store(rd, T0);
+    }
+
+    /// Emits a word instruction, which sign-extends the 32-bit result of `op`.
+    fn word(
+        &mut self,
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+        op: impl FnOnce(&mut Emitter, X, X),
+    ) {
+        self.register(rd, rs1, rs2, |e, a, b| {
+            op(e, a, b);
+            e.sxtw(a, a);
+        });
+    }
+
+    /// Emits an instruction of one source register, computed by `op` in place.
+    fn unary(&mut self, rd: Register, rs1: Register, op: impl FnOnce(&mut Emitter, X)) {
+        self.charge();
+        self.load(T0, rs1);
+        op(&mut self.emitter, T0);
+        self.store(rd, T0);
+    }
+
+    /// Emits an instruction of a source register and an immediate, which `op` finds
+    /// sign-extended in its second operand.
+    fn immediate(
+        &mut self,
+        rd: Register,
+        rs1: Register,
+        imm: i16,
+        op: impl FnOnce(&mut Emitter, X, X),
+    ) {
+        let value = self.wrap(imm as i64 as u64);
+        self.charge();
+        self.load(T0, rs1);
+        self.emitter.mov_imm(self.wide, T1, value);
+        op(&mut self.emitter, T0, T1);
+        self.store(rd, T0);
+    }
+
+    /// Emits an instruction that writes a value known at translation time.
+    fn constant(&mut self, rd: Register, value: u64) {
+        self.charge();
+        self.link(rd, self.wrap(value));
+    }
+
+    /// Writes `value` to `rd`, as the link of a jump does.
+    fn link(&mut self, rd: Register, value: u64) {
+        if rd != Register::ZERO {
+            self.emitter.mov_imm(self.wide, T2, value);
+            self.store(rd, T2);
+        }
+    }
+
+    /// Emits a branch to the native code of the instruction at `target`.
+    fn jump(&mut self, target: u64) {
+        self.fixups.push((self.emitter.position(), target));
+        self.emitter.b(0);
+    }
+
+    /// Emits a conditional branch that is taken if `rs1` and `rs2` satisfy `cond`.
+    /// Returns `false` if the target is not an instruction.
+    fn branch(&mut self, pc: u64, rs1: Register, rs2: Register, imm: i16, cond: Condition) -> bool {
+        let target = self.wrap(pc.wrapping_add(imm as i64 as u64));
+        if self.program.get(target).is_none() {
+            return false;
+        }
+
+        self.charge();
+        self.load(T0, rs1);
+        self.load(T1, rs2);
+        self.emitter.cmp(self.wide, T0, T1);
+        let skip = self.emitter.position() + 8;
+        self.emitter.b_cond(cond.invert(), skip);
+        self.jump(target);
+
+        true
+    }
+
+    /// Emits `jalr`, which looks up the native code of its target in the offsets.
+    /// Targets outside of the code, such as the return address of the call, and in
+    /// the middle of an instruction exit through `stub`.
+    fn jalr(&mut self, rd: Register, rs1: Register, imm: i16, next: u64, stub: usize) {
+        let wide = self.wide;
+        let e = &mut self.emitter;
+        let offset = REGISTERS + 8 * rs1.number() as usize;
+        e.ldr(T0, HART, offset);
+        e.add_signed(wide, T0, T0, imm);
+        e.clear_bit0(wide, T0, T0);
+        e.mov_imm(true, T1, self.program.len());
+        e.cmp(true, T0, T1);
+        e.b_cond(Condition::Hs, stub);
+        e.add_shifted(true, T3, OFFSETS, T0, 1);
+        e.ldr_w(T1, T3);
+        e.cmn_imm(false, T1, 1);
+        e.b_cond(Condition::Eq, stub);
+
+        self.link(rd, next);
+        self.charge();
+        self.emitter.add(true, T1, CODE, T1);
+        self.emitter.br(T1);
+    }
+
+    /// Computes the address `rs1 + imm` of a memory access into `T0`, and exits
+    /// through `stub` unless all `width` bytes from it are within memory.
+    fn address(&mut self, rs1: Register, imm: i16, width: u8, stub: usize) {
+        let wide = self.wide;
+        self.load(T0, rs1);
+        let e = &mut self.emitter;
+        e.add_signed(wide, T0, T0, imm);
+        e.cmp(true, T0, MEMORY_END);
+        e.b_cond(Condition::Hs, stub);
+        e.add_imm(true, T1, T0, u32::from(width));
+        e.cmp(true, T1, MEMORY_END);
+        e.b_cond(Condition::Hi, stub);
+    }
+
+    /// The shift amount of an immediate shift.
+    fn shamt(&self, imm: i16) -> u32 {
+        match self.wide {
+            true => imm as u32 & 0x3f,
+            false => imm as u32 & 0x1f,
+        }
+    }
+
+    /// Truncates `value` to the register width of the guest.
+    fn wrap(&self, value: u64) -> u64 {
+        match self.wide {
+            true => value,
+            false => value as u32 as u64,
+        }
+    }
+}
+
+/// Emits `div` or `divu` of `a` by `b` into `a`. Division by zero yields all ones,
+/// where AArch64 yields zero.
+fn divide(e: &mut Emitter, wide: bool, signed: bool, a: X, b: X) {
+    match signed {
+        true => e.sdiv(wide, T2, a, b),
+        false => e.udiv(wide, T2, a, b),
+    }
+    e.cmp_imm(wide, b, 0);
+    e.csinv(wide, a, T2, ZR, Condition::Ne);
+}
+
+/// Emits `rem` or `remu` of `a` by `b` into `a`. AArch64 division by zero yields zero,
+/// so the remainder is the dividend, as in RISC-V.
+fn remainder(e: &mut Emitter, wide: bool, signed: bool, a: X, b: X) {
+    match signed {
+        true => e.sdiv(wide, T2, a, b),
+        false => e.udiv(wide, T2, a, b),
+    }
+    e.msub(wide, a, T2, b, a);
+}
@@ -0,0 +1,1347 @@
+use crate::csr;
+use crate::custom::{CustomOpcode, CustomOperands};
+use crate::error::{AssembleError, AssembleErrorKind, Error};
+use crate::float::RoundingMode;
+use crate::instruction::{FENCE_SET_LETTERS, RiscVInstruction};
+use crate::pseudo::{Pseudo, branch_offset, jump_offset, split_offset};
+use crate::register::{FloatRegister, Register};
+use std::collections::HashMap;
+
+/// Starts a comment that runs to the end of the line.
+const COMMENT: char = '#';
+
+/// An instruction produced by the assembler, along with where it was placed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AssembledInstruction {
+    /// The byte offset of the instruction from the start of the program.
+    pub offset: usize,
+    /// The assembled instruction.
+    pub instruction: RiscVInstruction,
+}
+
+/// A program assembled by [`assemble`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Assembly {
+    /// The encoded program, ready to pass to `Module::set_riscv_code`.
+    pub bytes: Vec<u8>,
+    /// Every instruction in program order. Pseudo-instructions appear as the
+    /// instructions they expand to, and data directives do not appear at all.
+    pub instructions: Vec<AssembledInstruction>,
+}
+
+/// Assembles RISC-V assembly text into instructions and their encoding.
+///
+/// Each line holds any number of `label:` definitions followed by an optional
+/// instruction or data directive, and `#` starts a comment. The syntax is the one
+/// printed by `Display` and by the pseudo-instruction printer:
+///
+/// - Registers by number (`x10`) or ABI name (`a0`, `fp`).
+/// - Loads and stores as `lw a0, 8(sp)`.
+/// - CSRs by name (`cycle`, `fcsr`) or 12-bit number (`0x340`).
+/// - `lui` and `auipc` take the 20-bit upper immediate, as in `lui a0, 0x12345`.
+/// - Branch and jump targets are a label, or a number giving the offset from the
+///   instruction itself.
+/// - The pseudo-instructions `nop`, `li`, `la`, `mv`, `not`, `neg`, `seqz`, `snez`,
+///   `sltz`, `sgtz`, `beqz`, `bnez`, `blez`, `bgez`, `bltz`, `bgtz`, `bgt`, `ble`,
+///   `bgtu`, `bleu`, `j`, `jal target`, `jr`, `jalr rs`, `ret`, `call`, `tail`,
+///   `csrr`, `csrw`, and the counter reads `rdcycle`, `rdtime` and `rdinstret` along
+///   with their `h` forms.
+/// - The Zba, Zbb and Zbs bit-manipulation instructions, such as `sh1add`, `andn`,
+///   `clz`, `rori`, `rev8` and `bseti`, and the Zknh hash instructions, such as
+///   `sha256sig0` and `sha512sum0r`, and the Zicsr instructions `csrrw`, `csrrs`,
+///   `csrrc`, `csrrwi`, `csrrsi` and `csrrci`. They are always accepted; whether a
+///   module may use them is up to the engine configuration.
+/// - The A atomic instructions `lr.w rd, (rs1)`, `sc.w rd, rs2, (rs1)` and the
+///   atomic memory operations such as `amoadd.w rd, rs2, (rs1)`, each with an
+///   optional `.aq`, `.rl` or `.aqrl` suffix.
+/// - The F and D floating-point instructions, such as `flw fa0, 8(sp)`,
+///   `fmadd.d fa0, fa1, fa2, fa3` and `fcvt.w.s a0, fa0, rtz`, with floating-point
+///   registers by number (`f10`) or ABI name (`fa0`) and an optional trailing
+///   rounding mode that defaults to `dyn`.
+/// - The RV64I and RV64M instructions, such as `ld`, `sd`, `addiw` and `mulw`, and
+///   shift amounts of up to 63 for `slli`, `srli` and `srai`. Like the extensions,
+///   whether a module may use them is up to the engine configuration.
+/// - `fence` with a predecessor and successor set such as `rw, w`, or without
+///   operands for `iorw, iorw`, along with `fence.tso` and the Zifencei `fence.i`.
+/// - Custom instructions in the custom-0 (`0x0b`) and custom-1 (`0x2b`) opcode
+///   spaces, as `.insn r 0x0b, funct3, funct7, rd, rs1, rs2` or
+///   `.insn i 0x2b, funct3, rd, rs1, imm`. Whether they are registered is up to the
+///   engine configuration.
+/// - The data directives `.word`, `.half` and `.byte`, taking numbers or labels.
+///
+/// Every instruction is emitted in its 32-bit form. Data is not padded, so an
+/// instruction following an odd number of `.byte`s is misaligned.
+///
+/// # Errors
+///
+/// Returns an `AssembleError` with the line and column of the first problem.
+pub fn assemble(source: &str) -> Result<Assembly, AssembleError> {
+    // The first pass only lays out the program. Every label resolves to the current
+    // offset, and instruction sizes never depend on the value of a label.
+    let mut statements = vec![];
+    let mut labels = HashMap::new();
+    let mut offset = 0;
+
+    for (index, text) in source.lines().enumerate() {
+        let (defined, statement) = parse_line(text, index + 1)?;
+
+        for label in defined {
+            if labels.insert(label.text, offset).is_some() {
+                return Err(label.error(AssembleErrorKind::DuplicateLabel));
+            }
+        }
+
+        if let Some(statement) = statement {
+            let layout = Assembler {
+                labels: None,
+                pc: offset,
+            };
+            let size = layout.statement_size(&statement)?;
+            statements.push((offset, statement));
+            offset += size;
+        }
+    }
+
+    let mut bytes = Vec::with_capacity(offset);
+    let mut instructions = vec![];
+
+    for (offset, statement) in &statements {
+        let assembler = Assembler {
+            labels: Some(&labels),
+            pc: *offset,
+        };
+
+        match statement {
+            Statement::Instruction { mnemonic, operands } => {
+                for instruction in assembler.instruction(mnemonic, operands)? {
+                    let word = instruction
+                        .encode()
+                        .map_err(|_| mnemonic.error(AssembleErrorKind::InvalidImmediate))?;
+                    instructions.push(AssembledInstruction {
+                        offset: bytes.len(),
+                        instruction,
+                    });
+                    bytes.extend(word.to_le_bytes());
+                }
+            }
+            Statement::Data { width, values } => {
+                for value in values {
+                    let value = assembler.data(value, *width)?;
+                    bytes.extend(&value.to_le_bytes()[..*width]);
+                }
+            }
+        }
+    }
+
+    Ok(Assembly {
+        bytes,
+        instructions,
+    })
+}
+
+/// A piece of source text and the position it was found at.
+#[derive(Debug, Clone, Copy)]
+struct Token<'a> {
+    text: &'a str,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Token<'a> {
+    /// The part of the token starting at byte `start` and ending at byte `end`,
+    /// with surrounding whitespace removed.
+    fn slice(&self, start: usize, end: usize) -> Token<'a> {
+        let text = &self.text[start..end];
+        let trimmed = text.trim_start();
+        let leading = &self.text[..start + text.len() - trimmed.len()];
+
+        Token {
+            text: trimmed.trim_end(),
+            line: self.line,
+            column: self.column + leading.chars().count(),
+        }
+    }
+
+    fn error(&self, kind: AssembleErrorKind) -> AssembleError {
+        AssembleError {
+            line: self.line,
+            column: self.column,
+            kind,
+        }
+    }
+}
+
+/// A line of source with its labels removed.
+enum Statement<'a> {
+    Instruction {
+        mnemonic: Token<'a>,
+        operands: Vec<Token<'a>>,
+    },
+    Data {
+        width: usize,
+        values: Vec<Token<'a>>,
+    },
+}
+
+/// Whether `text` can be used as a label.
+fn is_identifier(text: &str) -> bool {
+    let mut chars = text.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_' || c == '.')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.' || c == '$')
+}
+
+/// Splits a line into the labels it defines and the statement that follows them.
+fn parse_line(
+    text: &str,
+    line: usize,
+) -> Result<(Vec<Token<'_>>, Option<Statement<'_>>), AssembleError> {
+    let code = match text.find(COMMENT) {
+        Some(end) => &text[..end],
+        None => text,
+    };
+    let whole = Token {
+        text: code,
+        line,
+        column: 1,
+    };
+
+    let mut labels = vec![];
+    let mut start = 0;
+
+    loop {
+        start = code.len() - code[start..].trim_start().len();
+        let rest = &code[start..];
+        let word_end = rest
+            .find(|c: char| c.is_whitespace() || c == ':')
+            .unwrap_or(rest.len());
+
+        if rest[word_end..].starts_with(':') {
+            let label = whole.slice(start, start + word_end);
+            if !is_identifier(label.text) {
+                return Err(label.error(AssembleErrorKind::InvalidLabel));
+            }
+            labels.push(label);
+            start += word_end + 1;
+        } else {
+            break;
+        }
+    }
+
+    if start == code.len() {
+        return Ok((labels, None));
+    }
+
+    let mnemonic_end = code[start..]
+        .find(char::is_whitespace)
+        .map_or(code.len(), |end| start + end);
+    let mnemonic = whole.slice(start, mnemonic_end);
+
+    let mut operands = vec![];
+    if !code[mnemonic_end..].trim().is_empty() {
+        let mut operand_start = mnemonic_end;
+        for piece in code[mnemonic_end..].split(',') {
+            let operand = whole.slice(operand_start, operand_start + piece.len());
+            if operand.text.is_empty() {
+                return Err(operand.error(AssembleErrorKind::InvalidOperands));
+            }
+            operands.push(operand);
+            operand_start += piece.len() + 1;
+        }
+    }
+
+    let is_data = matches!(mnemonic.text, ".word" | ".half" | ".byte");
+    if is_data && operands.is_empty() {
+        return Err(mnemonic.error(AssembleErrorKind::InvalidOperands));
+    }
+
+    let statement = match mnemonic.text {
+        ".word" => Statement::Data {
+            width: 4,
+            values: operands,
+        },
+        ".half" => Statement::Data {
+            width: 2,
+            values: operands,
+        },
+        ".byte" => Statement::Data {
+            width: 1,
+            values: operands,
+        },
+        ".insn" => Statement::Instruction { mnemonic, operands },
+        directive if directive.starts_with('.') => {
+            return Err(mnemonic.error(AssembleErrorKind::UnknownDirective));
+        }
+        _ => Statement::Instruction { mnemonic, operands },
+    };
+
+    Ok((labels, Some(statement)))
+}
+
+type RType = fn(Register, Register, Register) -> RiscVInstruction;
+type IType = fn(Register, Register, i16) -> RiscVInstruction;
+type BType = fn(Register, Register, i16) -> RiscVInstruction;
+type UType = fn(Register, i32) -> RiscVInstruction;
+
+fn r_type(mnemonic: &str) -> Option<RType> {
+    let constructor: RType = match mnemonic {
+        "add" => |rd, rs1, rs2| RiscVInstruction::Add { rd, rs1, rs2 },
+        "sub" => |rd, rs1, rs2| RiscVInstruction::Sub { rd, rs1, rs2 },
+        "xor" => |rd, rs1, rs2| RiscVInstruction::Xor { rd, rs1, rs2 },
+        "or" => |rd, rs1, rs2| RiscVInstruction::Or { rd, rs1, rs2 },
+        "and" => |rd, rs1, rs2| RiscVInstruction::And { rd, rs1, rs2 },
+        "sll" => |rd, rs1, rs2| RiscVInstruction::Sll { rd, rs1, rs2 },
+        "srl" => |rd, rs1, rs2| RiscVInstruction::Srl { rd, rs1, rs2 },
+        "sra" => |rd, rs1, rs2| RiscVInstruction::Sra { rd, rs1, rs2 },
+        "slt" => |rd, rs1, rs2| RiscVInstruction::Slt { rd, rs1, rs2 },
+        "sltu" => |rd, rs1, rs2| RiscVInstruction::Sltu { rd, rs1, rs2 },
+        "mul" => |rd, rs1, rs2| RiscVInstruction::Mul { rd, rs1, rs2 },
+        "mulh" => |rd, rs1, rs2| RiscVInstruction::Mulh { rd, rs1, rs2 },
+        "mulhsu" => |rd, rs1, rs2| RiscVInstruction::Mulhsu { rd, rs1, rs2 },
+        "mulhu" => |rd, rs1, rs2| RiscVInstruction::Mulhu { rd, rs1, rs2 },
+        "div" => |rd, rs1, rs2| RiscVInstruction::Div { rd, rs1, rs2 },
+        "divu" => |rd, rs1, rs2| RiscVInstruction::Divu { rd, rs1, rs2 },
+        "rem" => |rd, rs1, rs2| RiscVInstruction::Rem { rd, rs1, rs2 },
+        "remu" => |rd, rs1, rs2| RiscVInstruction::Remu { rd, rs1, rs2 },
+        "addw" => |rd, rs1, rs2| RiscVInstruction::Addw { rd, rs1, rs2 },
+        "subw" => |rd, rs1, rs2| RiscVInstruction::Subw { rd, rs1, rs2 },
+        "sllw" => |rd, rs1, rs2| RiscVInstruction::Sllw { rd, rs1, rs2 },
+        "srlw" => |rd, rs1, rs2| RiscVInstruction::Srlw { rd, rs1, rs2 },
+        "sraw" => |rd, rs1, rs2| RiscVInstruction::Sraw { rd, rs1, rs2 },
+        "mulw" => |rd, rs1, rs2| RiscVInstruction::Mulw { rd, rs1, rs2 },
+        "divw" => |rd, rs1, rs2| RiscVInstruction::Divw { rd, rs1, rs2 },
+        "divuw" => |rd, rs1, rs2| RiscVInstruction::Divuw { rd, rs1, rs2 },
+        "remw" => |rd, rs1, rs2| RiscVInstruction::Remw { rd, rs1, rs2 },
+        "remuw" => |rd, rs1, rs2| RiscVInstruction::Remuw { rd, rs1, rs2 },
+        "sh1add" => |rd, rs1, rs2| RiscVInstruction::Sh1add { rd, rs1, rs2 },
+        "sh2add" => |rd, rs1, rs2| RiscVInstruction::Sh2add { rd, rs1, rs2 },
+        "sh3add" => |rd, rs1, rs2| RiscVInstruction::Sh3add { rd, rs1, rs2 },
+        "andn" => |rd, rs1, rs2| RiscVInstruction::Andn { rd, rs1, rs2 },
+        "orn" => |rd, rs1, rs2| RiscVInstruction::Orn { rd, rs1, rs2 },
+        "xnor" => |rd, rs1, rs2| RiscVInstruction::Xnor { rd, rs1, rs2 },
+        "max" => |rd, rs1, rs2| RiscVInstruction::Max { rd, rs1, rs2 },
+        "maxu" => |rd, rs1, rs2| RiscVInstruction::Maxu { rd, rs1, rs2 },
+        "min" => |rd, rs1, rs2| RiscVInstruction::Min { rd, rs1, rs2 },
+        "minu" => |rd, rs1, rs2| RiscVInstruction::Minu { rd, rs1, rs2 },
+        "rol" => |rd, rs1, rs2| RiscVInstruction::Rol { rd, rs1, rs2 },
+        "ror" => |rd, rs1, rs2| RiscVInstruction::Ror { rd, rs1, rs2 },
+        "bclr" => |rd, rs1, rs2| RiscVInstruction::Bclr { rd, rs1, rs2 },
+        "bext" => |rd, rs1, rs2| RiscVInstruction::Bext { rd, rs1, rs2 },
+        "binv" => |rd, rs1, rs2| RiscVInstruction::Binv { rd, rs1, rs2 },
+        "bset" => |rd, rs1, rs2| RiscVInstruction::Bset { rd, rs1, rs2 },
+        "sha512sum0r" => |rd, rs1, rs2| RiscVInstruction::Sha512sum0r { rd, rs1, rs2 },
+        "sha512sum1r" => |rd, rs1, rs2| RiscVInstruction::Sha512sum1r { rd, rs1, rs2 },
+        "sha512sig0l" => |rd, rs1, rs2| RiscVInstruction::Sha512sig0l { rd, rs1, rs2 },
+        "sha512sig0h" => |rd, rs1, rs2| RiscVInstruction::Sha512sig0h { rd, rs1, rs2 },
+        "sha512sig1l" => |rd, rs1, rs2| RiscVInstruction::Sha512sig1l { rd, rs1, rs2 },
+        "sha512sig1h" => |rd, rs1, rs2| RiscVInstruction::Sha512sig1h { rd, rs1, rs2 },
+        _ => return None,
+    };
+
+    Some(constructor)
+}
+
+fn i_type(mnemonic: &str) -> Option<IType> {
+    let constructor: IType = match mnemonic {
+        "addi" => |rd, rs1, imm| RiscVInstruction::Addi { rd, rs1, imm },
+        "xori" => |rd, rs1, imm| RiscVInstruction::Xori { rd, rs1, imm },
+        "ori" => |rd, rs1, imm| RiscVInstruction::Ori { rd, rs1, imm },
+        "andi" => |rd, rs1, imm| RiscVInstruction::Andi { rd, rs1, imm },
+        "slti" => |rd, rs1, imm| RiscVInstruction::Slti { rd, rs1, imm },
+        "sltiu" => |rd, rs1, imm| RiscVInstruction::Sltiu { rd, rs1, imm },
+        "addiw" => |rd, rs1, imm| RiscVInstruction::Addiw { rd, rs1, imm },
+        _ => return None,
+    };
+
+    Some(constructor)
+}
+
+/// Shifts by an immediate, along with their largest shift amount.
+///
+/// `slli`, `srli` and `srai` shift by up to 63 on RV64I; the rest only ever shift
+/// 32-bit values.
+fn shift(mnemonic: &str) -> Option<(IType, i64)> {
+    let (constructor, max): (IType, i64) = match mnemonic {
+        "slli" => (|rd, rs1, imm| RiscVInstruction::Slli { rd, rs1, imm }, 63),
+        "srli" => (|rd, rs1, imm| RiscVInstruction::Srli { rd, rs1, imm }, 63),
+        "srai" => (|rd, rs1, imm| RiscVInstruction::Srai { rd, rs1, imm }, 63),
+        "slliw" => (|rd, rs1, imm| RiscVInstruction::Slliw { rd, rs1, imm }, 31),
+        "srliw" => (|rd, rs1, imm| RiscVInstruction::Srliw { rd, rs1, imm }, 31),
+        "sraiw" => (|rd, rs1, imm| RiscVInstruction::Sraiw { rd, rs1, imm }, 31),
+        "rori" => (|rd, rs1, imm| RiscVInstruction::Rori { rd, rs1, imm }, 31),
+        "bclri" => (|rd, rs1, imm| RiscVInstruction::Bclri { rd, rs1, imm }, 31),
+        "bexti" => (|rd, rs1, imm| RiscVInstruction::Bexti { rd, rs1, imm }, 31),
+        "binvi" => (|rd, rs1, imm| RiscVInstruction::Binvi { rd, rs1, imm }, 31),
+        "bseti" => (|rd, rs1, imm| RiscVInstruction::Bseti { rd, rs1, imm }, 31),
+        _ => return None,
+    };
+
+    Some((constructor, max))
+}
+
+/// Instructions taking `rd, rs1`.
+fn unary(mnemonic: &str) -> Option<fn(Register, Register) -> RiscVInstruction> {
+    let constructor: fn(Register, Register) -> RiscVInstruction = match mnemonic {
+        "clz" => |rd, rs1| RiscVInstruction::Clz { rd, rs1 },
+        "ctz" => |rd, rs1| RiscVInstruction::Ctz { rd, rs1 },
+        "cpop" => |rd, rs1| RiscVInstruction::Cpop { rd, rs1 },
+        "sext.b" => |rd, rs1| RiscVInstruction::SextB { rd, rs1 },
+        "sext.h" => |rd, rs1| RiscVInstruction::SextH { rd, rs1 },
+        "zext.h" => |rd, rs1| RiscVInstruction::ZextH { rd, rs1 },
+        "orc.b" => |rd, rs1| RiscVInstruction::OrcB { rd, rs1 },
+        "rev8" => |rd, rs1| RiscVInstruction::Rev8 { rd, rs1 },
+        "sha256sig0" => |rd, rs1| RiscVInstruction::Sha256sig0 { rd, rs1 },
+        "sha256sig1" => |rd, rs1| RiscVInstruction::Sha256sig1 { rd, rs1 },
+        "sha256sum0" => |rd, rs1| RiscVInstruction::Sha256sum0 { rd, rs1 },
+        "sha256sum1" => |rd, rs1| RiscVInstruction::Sha256sum1 { rd, rs1 },
+        _ => return None,
+    };
+
+    Some(constructor)
+}
+
+fn load(mnemonic: &str) -> Option<IType> {
+    let constructor: IType = match mnemonic {
+        "lb" => |rd, rs1, imm| RiscVInstruction::Lb { rd, rs1, imm },
+        "lh" => |rd, rs1, imm| RiscVInstruction::Lh { rd, rs1, imm },
+        "lw" => |rd, rs1, imm| RiscVInstruction::Lw { rd, rs1, imm },
+        "lbu" => |rd, rs1, imm| RiscVInstruction::Lbu { rd, rs1, imm },
+        "lhu" => |rd, rs1, imm| RiscVInstruction::Lhu { rd, rs1, imm },
+        "ld" => |rd, rs1, imm| RiscVInstruction::Ld { rd, rs1, imm },
+        "lwu" => |rd, rs1, imm| RiscVInstruction::Lwu { rd, rs1, imm },
+        _ => return None,
+    };
+
+    Some(constructor)
+}
+
+/// Stores take `rs1, rs2, imm`, the order of their fields.
+fn store(mnemonic: &str) -> Option<BType> {
+    let constructor: BType = match mnemonic {
+        "sb" => |rs1, rs2, imm| RiscVInstruction::Sb { rs1, rs2, imm },
+        "sh" => |rs1, rs2, imm| RiscVInstruction::Sh { rs1, rs2, imm },
+        "sw" => |rs1, rs2, imm| RiscVInstruction::Sw { rs1, rs2, imm },
+        "sd" => |rs1, rs2, imm| RiscVInstruction::Sd { rs1, rs2, imm },
+        _ => return None,
+    };
+
+    Some(constructor)
+}
+
+/// Branches, including the pseudo-branches that swap their operands.
+///
+/// The boolean is true for the swapped forms `bgt`, `ble`, `bgtu` and `bleu`.
+fn branch(mnemonic: &str) -> Option<(BType, bool)> {
+    let blt: BType = |rs1, rs2, imm| RiscVInstruction::Blt { rs1, rs2, imm };
+    let bge: BType = |rs1, rs2, imm| RiscVInstruction::Bge { rs1, rs2, imm };
+    let bltu: BType = |rs1, rs2, imm| RiscVInstruction::Bltu { rs1, rs2, imm };
+    let bgeu: BType = |rs1, rs2, imm| RiscVInstruction::Bgeu { rs1, rs2, imm };
+
+    let branch = match mnemonic {
+        "beq" => (
+            (|rs1, rs2, imm| RiscVInstruction::Beq { rs1, rs2, imm }) as BType,
+            false,
+        ),
+        "bne" => (
+            (|rs1, rs2, imm| RiscVInstruction::Bne { rs1, rs2, imm }) as BType,
+            false,
+        ),
+        "blt" => (blt, false),
+        "bge" => (bge, false),
+        "bltu" => (bltu, false),
+        "bgeu" => (bgeu, false),
+        "bgt" => (blt, true),
+        "ble" => (bge, true),
+        "bgtu" => (bltu, true),
+        "bleu" => (bgeu, true),
+        _ => return None,
+    };
+
+    Some(branch)
+}
+
+fn upper(mnemonic: &str) -> Option<UType> {
+    let constructor: UType = match mnemonic {
+        "lui" => |rd, imm| RiscVInstruction::Lui { rd, imm },
+        "auipc" => |rd, imm| RiscVInstruction::Auipc { rd, imm },
+        _ => return None,
+    };
+
+    Some(constructor)
+}
+
+/// A constructor of an atomic instruction taking `rd, rs2, rs1, aq, rl`.
+type AtomicConstructor = fn(Register, Register, Register, bool, bool) -> RiscVInstruction;
+
+/// Atomic instructions taking `rd, rs2, (rs1)`, with their `aq` and `rl` bits.
+fn atomic(mnemonic: &str) -> Option<(AtomicConstructor, bool, bool)> {
+    let (mnemonic, aq, rl) = ordering(mnemonic);
+    let constructor: AtomicConstructor = match mnemonic {
+        "sc.w" => |rd, rs2, rs1, aq, rl| RiscVInstruction::ScW {
+            rd,
+            rs1,
+            rs2,
+            aq,
+            rl,
+        },
+        "amoswap.w" => |rd, rs2, rs1, aq, rl| RiscVInstruction::AmoswapW {
+            rd,
+            rs1,
+            rs2,
+            aq,
+            rl,
+        },
+        "amoadd.w" => |rd, rs2, rs1, aq, rl| RiscVInstruction::AmoaddW {
+            rd,
+            rs1,
+            rs2,
+            aq,
+            rl,
+        },
+        "amoxor.w" => |rd, rs2, rs1, aq, rl| RiscVInstruction::AmoxorW {
+            rd,
+            rs1,
+            rs2,
+            aq,
+            rl,
+        },
+        "amoand.w" => |rd, rs2, rs1, aq, rl| RiscVInstruction::AmoandW {
+            rd,
+            rs1,
+            rs2,
+            aq,
+            rl,
+        },
+        "amoor.w" => |rd, rs2, rs1, aq, rl| RiscVInstruction::AmoorW {
+            rd,
+            rs1,
+            rs2,
+            aq,
+            rl,
+        },
+        "amomin.w" => |rd, rs2, rs1, aq, rl| RiscVInstruction::AmominW {
+            rd,
+            rs1,
+            rs2,
+            aq,
+            rl,
+        },
+        "amomax.w" => |rd, rs2, rs1, aq, rl| RiscVInstruction::AmomaxW {
+            rd,
+            rs1,
+            rs2,
+            aq,
+            rl,
+        },
+        "amominu.w" => |rd, rs2, rs1, aq, rl| RiscVInstruction::AmominuW {
+            rd,
+            rs1,
+            rs2,
+            aq,
+            rl,
+        },
+        "amomaxu.w" => |rd, rs2, rs1, aq, rl| RiscVInstruction::AmomaxuW {
+            rd,
+            rs1,
+            rs2,
+            aq,
+            rl,
+        },
+        _ => return None,
+    };
+
+    Some((constructor, aq, rl))
+}
+
+/// Whether the mnemonic is `lr.w`, returning its `aq` and `rl` bits.
+fn load_reserved(mnemonic: &str) -> Option<(bool, bool)> {
+    match ordering(mnemonic) {
+        ("lr.w", aq, rl) => Some((aq, rl)),
+        _ => None,
+    }
+}
+
+/// Splits the `.aq`, `.rl` or `.aqrl` suffix off an atomic mnemonic.
+fn ordering(mnemonic: &str) -> (&str, bool, bool) {
+    for (suffix, aq, rl) in [
+        (".aqrl", true, true),
+        (".aq", true, false),
+        (".rl", false, true),
+    ] {
+        if let Some(mnemonic) = mnemonic.strip_suffix(suffix) {
+            return (mnemonic, aq, rl);
+        }
+    }
+
+    (mnemonic, false, false)
+}
+
+/// CSR instructions taking `rd, csr, rs1`.
+fn csr_register(mnemonic: &str) -> Option<fn(Register, u16, Register) -> RiscVInstruction> {
+    let constructor: fn(Register, u16, Register) -> RiscVInstruction = match mnemonic {
+        "csrrw" => |rd, csr, rs1| RiscVInstruction::Csrrw { rd, rs1, csr },
+        "csrrs" => |rd, csr, rs1| RiscVInstruction::Csrrs { rd, rs1, csr },
+        "csrrc" => |rd, csr, rs1| RiscVInstruction::Csrrc { rd, rs1, csr },
+        _ => return None,
+    };
+
+    Some(constructor)
+}
+
+/// CSR instructions taking `rd, csr, imm`.
+fn csr_immediate(mnemonic: &str) -> Option<fn(Register, u16, u8) -> RiscVInstruction> {
+    let constructor: fn(Register, u16, u8) -> RiscVInstruction = match mnemonic {
+        "csrrwi" => |rd, csr, imm| RiscVInstruction::Csrrwi { rd, imm, csr },
+        "csrrsi" => |rd, csr, imm| RiscVInstruction::Csrrsi { rd, imm, csr },
+        "csrrci" => |rd, csr, imm| RiscVInstruction::Csrrci { rd, imm, csr },
+        _ => return None,
+    };
+
+    Some(constructor)
+}
+
+type FloatLoad = fn(FloatRegister, Register, i16) -> RiscVInstruction;
+type FloatStore = fn(Register, FloatRegister, i16) -> RiscVInstruction;
+type Fused = fn(
+    FloatRegister,
+    FloatRegister,
+    FloatRegister,
+    FloatRegister,
+    RoundingMode,
+) -> RiscVInstruction;
+type FloatArithmetic =
+    fn(FloatRegister, FloatRegister, FloatRegister, RoundingMode) -> RiscVInstruction;
+type FloatRType = fn(FloatRegister, FloatRegister, FloatRegister) -> RiscVInstruction;
+type FloatUnary = fn(FloatRegister, FloatRegister, RoundingMode) -> RiscVInstruction;
+type FloatCompare = fn(Register, FloatRegister, FloatRegister) -> RiscVInstruction;
+type FloatToInt = fn(Register, FloatRegister, RoundingMode) -> RiscVInstruction;
+type IntToFloat = fn(FloatRegister, Register, RoundingMode) -> RiscVInstruction;
+type FloatToX = fn(Register, FloatRegister) -> RiscVInstruction;
+
+/// Floating-point loads.
+fn float_load(mnemonic: &str) -> Option<FloatLoad> {
+    let constructor: FloatLoad = match mnemonic {
+        "flw" => |rd, rs1, imm| RiscVInstruction::Flw { rd, rs1, imm },
+        "fld" => |rd, rs1, imm| RiscVInstruction::Fld { rd, rs1, imm },
+        _ => return None,
+    };
+
+    Some(constructor)
+}
+
+/// Floating-point stores take `rs1, rs2, imm`, the order of their fields.
+fn float_store(mnemonic: &str) -> Option<FloatStore> {
+    let constructor: FloatStore = match mnemonic {
+        "fsw" => |rs1, rs2, imm| RiscVInstruction::Fsw { rs1, rs2, imm },
+        "fsd" => |rs1, rs2, imm| RiscVInstruction::Fsd { rs1, rs2, imm },
+        _ => return None,
+    };
+
+    Some(constructor)
+}
+
+/// Fused multiply-add instructions taking `rd, rs1, rs2, rs3` and a rounding mode.
+fn fused(mnemonic: &str) -> Option<Fused> {
+    let constructor: Fused = match mnemonic {
+        "fmadd.s" => |rd, rs1, rs2, rs3, rm| RiscVInstruction::FmaddS {
+            rd,
+            rs1,
+            rs2,
+            rs3,
+            rm,
+        },
+        "fmsub.s" => |rd, rs1, rs2, rs3, rm| RiscVInstruction::FmsubS {
+            rd,
+            rs1,
+            rs2,
+            rs3,
+            rm,
+        },
+        "fnmsub.s" => |rd, rs1, rs2, rs3, rm| RiscVInstruction::FnmsubS {
+            rd,
+            rs1,
+            rs2,
+            rs3,
+            rm,
+        },
+        "fnmadd.s" => |rd, rs1, rs2, rs3, rm| RiscVInstruction::FnmaddS {
+            rd,
+            rs1,
+            rs2,
+            rs3,
+            rm,
+        },
+        "fmadd.d" => |rd, rs1, rs2, rs3, rm| RiscVInstruction::FmaddD {
+            rd,
+            rs1,
+            rs2,
+            rs3,
+            rm,
+        },
+        "fmsub.d" => |rd, rs1, rs2, rs3, rm| RiscVInstruction::FmsubD {
+            rd,
+            rs1,
+            rs2,
+            rs3,
+            rm,
+        },
+        "fnmsub.d" => |rd, rs1, rs2, rs3, rm| RiscVInstruction::FnmsubD {
+            rd,
+            rs1,
+            rs2,
+            rs3,
+            rm,
+        },
+        "fnmadd.d" => |rd, rs1, rs2, rs3, rm| RiscVInstruction::FnmaddD {
+            rd,
+            rs1,
+            rs2,
+            rs3,
+            rm,
+        },
+        _ => return None,
+    };
+
+    Some(constructor)
+}
+
+/// Floating-point instructions taking `rd, rs1, rs2` and a rounding mode.
+fn float_arithmetic(mnemonic: &str) -> Option<FloatArithmetic> {
+    let constructor: FloatArithmetic = match mnemonic {
+        "fadd.s" => |rd, rs1, rs2, rm| RiscVInstruction::FaddS { rd, rs1, rs2, rm },
+        "fsub.s" => |rd, rs1, rs2, rm| RiscVInstruction::FsubS { rd, rs1, rs2, rm },
+        "fmul.s" => |rd, rs1, rs2, rm| RiscVInstruction::FmulS { rd, rs1, rs2, rm },
+        "fdiv.s" => |rd, rs1, rs2, rm| RiscVInstruction::FdivS { rd, rs1, rs2, rm },
+        "fadd.d" => |rd, rs1, rs2, rm| RiscVInstruction::FaddD { rd, rs1, rs2, rm },
+        "fsub.d" => |rd, rs1, rs2, rm| RiscVInstruction::FsubD { rd, rs1, rs2, rm },
+        "fmul.d" => |rd, rs1, rs2, rm| RiscVInstruction::FmulD { rd, rs1, rs2, rm },
+        "fdiv.d" => |rd, rs1, rs2, rm| RiscVInstruction::FdivD { rd, rs1, rs2, rm },
+        _ => return None,
+    };
+
+    Some(constructor)
+}
+
+/// Floating-point instructions taking `rd, rs1, rs2` without a rounding mode.
+fn float_r_type(mnemonic: &str) -> Option<FloatRType> {
+    let constructor: FloatRType = match mnemonic {
+        "fsgnj.s" => |rd, rs1, rs2| RiscVInstruction::FsgnjS { rd, rs1, rs2 },
+        "fsgnjn.s" => |rd, rs1, rs2| RiscVInstruction::FsgnjnS { rd, rs1, rs2 },
+        "fsgnjx.s" => |rd, rs1, rs2| RiscVInstruction::FsgnjxS { rd, rs1, rs2 },
+        "fmin.s" => |rd, rs1, rs2| RiscVInstruction::FminS { rd, rs1, rs2 },
+        "fmax.s" => |rd, rs1, rs2| RiscVInstruction::FmaxS { rd, rs1, rs2 },
+        "fsgnj.d" => |rd, rs1, rs2| RiscVInstruction::FsgnjD { rd, rs1, rs2 },
+        "fsgnjn.d" => |rd, rs1, rs2| RiscVInstruction::FsgnjnD { rd, rs1, rs2 },
+        "fsgnjx.d" => |rd, rs1, rs2| RiscVInstruction::FsgnjxD { rd, rs1, rs2 },
+        "fmin.d" => |rd, rs1, rs2| RiscVInstruction::FminD { rd, rs1, rs2 },
+        "fmax.d" => |rd, rs1, rs2| RiscVInstruction::FmaxD { rd, rs1, rs2 },
+        _ => return None,
+    };
+
+    Some(constructor)
+}
+
+/// Floating-point instructions taking `rd, rs1` and a rounding mode.
+fn float_unary(mnemonic: &str) -> Option<FloatUnary> {
+    let constructor: FloatUnary = match mnemonic {
+        "fsqrt.s" => |rd, rs1, rm| RiscVInstruction::FsqrtS { rd, rs1, rm },
+        "fsqrt.d" => |rd, rs1, rm| RiscVInstruction::FsqrtD { rd, rs1, rm },
+        "fcvt.s.d" => |rd, rs1, rm| RiscVInstruction::FcvtSD { rd, rs1, rm },
+        "fcvt.d.s" => |rd, rs1, rm| RiscVInstruction::FcvtDS { rd, rs1, rm },
+        _ => return None,
+    };
+
+    Some(constructor)
+}
+
+/// Comparisons taking an integer `rd` and floating-point `rs1, rs2`.
+fn float_compare(mnemonic: &str) -> Option<FloatCompare> {
+    let constructor: FloatCompare = match mnemonic {
+        "feq.s" => |rd, rs1, rs2| RiscVInstruction::FeqS { rd, rs1, rs2 },
+        "flt.s" => |rd, rs1, rs2| RiscVInstruction::FltS { rd, rs1, rs2 },
+        "fle.s" => |rd, rs1, rs2| RiscVInstruction::FleS { rd, rs1, rs2 },
+        "feq.d" => |rd, rs1, rs2| RiscVInstruction::FeqD { rd, rs1, rs2 },
+        "flt.d" => |rd, rs1, rs2| RiscVInstruction::FltD { rd, rs1, rs2 },
+        "fle.d" => |rd, rs1, rs2| RiscVInstruction::FleD { rd, rs1, rs2 },
+        _ => return None,
+    };
+
+    Some(constructor)
+}
+
+/// Conversions taking an integer `rd`, a floating-point `rs1` and a rounding mode.
+fn float_to_int(mnemonic: &str) -> Option<FloatToInt> {
+    let constructor: FloatToInt = match mnemonic {
+        "fcvt.w.s" => |rd, rs1, rm| RiscVInstruction::FcvtWS { rd, rs1, rm },
+        "fcvt.wu.s" => |rd, rs1, rm| RiscVInstruction::FcvtWuS { rd, rs1, rm },
+        "fcvt.w.d" => |rd, rs1, rm| RiscVInstruction::FcvtWD { rd, rs1, rm },
+        "fcvt.wu.d" => |rd, rs1, rm| RiscVInstruction::FcvtWuD { rd, rs1, rm },
+        _ => return None,
+    };
+
+    Some(constructor)
+}
+
+/// Conversions taking a floating-point `rd`, an integer `rs1` and a rounding mode.
+fn int_to_float(mnemonic: &str) -> Option<IntToFloat> {
+    let constructor: IntToFloat = match mnemonic {
+        "fcvt.s.w" => |rd, rs1, rm| RiscVInstruction::FcvtSW { rd, rs1, rm },
+        "fcvt.s.wu" => |rd, rs1, rm| RiscVInstruction::FcvtSWu { rd, rs1, rm },
+        "fcvt.d.w" => |rd, rs1, rm| RiscVInstruction::FcvtDW { rd, rs1, rm },
+        "fcvt.d.wu" => |rd, rs1, rm| RiscVInstruction::FcvtDWu { rd, rs1, rm },
+        _ => return None,
+    };
+
+    Some(constructor)
+}
+
+/// Instructions taking an integer `rd` and a floating-point `rs1`.
+fn float_to_x(mnemonic: &str) -> Option<FloatToX> {
+    let constructor: FloatToX = match mnemonic {
+        "fmv.x.w" => |rd, rs1| RiscVInstruction::FmvXW { rd, rs1 },
+        "fclass.s" => |rd, rs1| RiscVInstruction::FclassS { rd, rs1 },
+        "fclass.d" => |rd, rs1| RiscVInstruction::FclassD { rd, rs1 },
+        _ => return None,
+    };
+
+    Some(constructor)
+}
+
+/// Pseudo-instructions taking `rd` that read a counter CSR.
+fn counter_pseudo(mnemonic: &str) -> Option<fn(Register) -> Pseudo> {
+    let constructor: fn(Register) -> Pseudo = match mnemonic {
+        "rdcycle" => |rd| Pseudo::Rdcycle { rd },
+        "rdcycleh" => |rd| Pseudo::Rdcycleh { rd },
+        "rdtime" => |rd| Pseudo::Rdtime { rd },
+        "rdtimeh" => |rd| Pseudo::Rdtimeh { rd },
+        "rdinstret" => |rd| Pseudo::Rdinstret { rd },
+        "rdinstreth" => |rd| Pseudo::Rdinstreth { rd },
+        _ => return None,
+    };
+
+    Some(constructor)
+}
+
+/// Pseudo-instructions taking `rd, rs`.
+fn register_pseudo(mnemonic: &str) -> Option<fn(Register, Register) -> Pseudo> {
+    let constructor: fn(Register, Register) -> Pseudo = match mnemonic {
+        "mv" => |rd, rs| Pseudo::Mv { rd, rs },
+        "not" => |rd, rs| Pseudo::Not { rd, rs },
+        "neg" => |rd, rs| Pseudo::Neg { rd, rs },
+        "seqz" => |rd, rs| Pseudo::Seqz { rd, rs },
+        "snez" => |rd, rs| Pseudo::Snez { rd, rs },
+        "sltz" => |rd, rs| Pseudo::Sltz { rd, rs },
+        "sgtz" => |rd, rs| Pseudo::Sgtz { rd, rs },
+        _ => return None,
+    };
+
+    Some(constructor)
+}
+
+/// Pseudo-instructions taking `rs, target`.
+fn branch_pseudo(mnemonic: &str) -> Option<fn(Register, i32) -> Pseudo> {
+    let constructor: fn(Register, i32) -> Pseudo = match mnemonic {
+        "beqz" => |rs, offset| Pseudo::Beqz { rs, offset },
+        "bnez" => |rs, offset| Pseudo::Bnez { rs, offset },
+        "blez" => |rs, offset| Pseudo::Blez { rs, offset },
+        "bgez" => |rs, offset| Pseudo::Bgez { rs, offset },
+        "bltz" => |rs, offset| Pseudo::Bltz { rs, offset },
+        "bgtz" => |rs, offset| Pseudo::Bgtz { rs, offset },
+        _ => return None,
+    };
+
+    Some(constructor)
+}
+
+/// Resolves operands of a statement placed at `pc`.
+struct Assembler<'a, 'b> {
+    /// Every label of the program, or `None` while the program is being laid out.
+    labels: Option<&'b HashMap<&'a str, usize>>,
+    pc: usize,
+}
+
+impl<'a> Assembler<'a, '_> {
+    /// Returns the number of bytes a statement assembles to.
+    fn statement_size(&self, statement: &Statement<'a>) -> Result<usize, AssembleError> {
+        match statement {
+            Statement::Instruction { mnemonic, operands } => {
+                Ok(self.instruction(mnemonic, operands)?.len() * 4)
+            }
+            Statement::Data { width, values } => Ok(width * values.len()),
+        }
+    }
+
+    /// Assembles one instruction or pseudo-instruction.
+    fn instruction(
+        &self,
+        mnemonic: &Token<'a>,
+        operands: &[Token<'a>],
+    ) -> Result<Vec<RiscVInstruction>, AssembleError> {
+        let text = mnemonic.text;
+
+        let instruction = if let Some(build) = r_type(text) {
+            let [rd, rs1, rs2] = expect(mnemonic, operands)?;
+            build(register(rd)?, register(rs1)?, register(rs2)?)
+        } else if let Some(build) = i_type(text) {
+            let [rd, rs1, imm] = expect(mnemonic, operands)?;
+            build(
+                register(rd)?,
+                register(rs1)?,
+                immediate(imm, -2048, 2047)? as i16,
+            )
+        } else if let Some((build, max)) = shift(text) {
+            let [rd, rs1, shamt] = expect(mnemonic, operands)?;
+            build(
+                register(rd)?,
+                register(rs1)?,
+                immediate(shamt, 0, max)? as i16,
+            )
+        } else if let Some(build) = unary(text) {
+            let [rd, rs1] = expect(mnemonic, operands)?;
+            build(register(rd)?, register(rs1)?)
+        } else if let Some(build) = load(text) {
+            let [rd, address] = expect(mnemonic, operands)?;
+            let rd = register(rd)?;
+            let (imm, rs1) = memory(address)?;
+            build(rd, rs1, imm)
+        } else if let Some(build) = store(text) {
+            let [rs2, address] = expect(mnemonic, operands)?;
+            let rs2 = register(rs2)?;
+            let (imm, rs1) = memory(address)?;
+            build(rs1, rs2, imm)
+        } else if let Some((build, swapped)) = branch(text) {
+            let [rs1, rs2, target] = expect(mnemonic, operands)?;
+            let (rs1, rs2) = (register(rs1)?, register(rs2)?);
+            let imm = self.target(target, branch_offset)?;
+            if swapped {
+                build(rs2, rs1, imm)
+            } else {
+                build(rs1, rs2, imm)
+            }
+        } else if let Some(build) = upper(text) {
+            let [rd, imm] = expect(mnemonic, operands)?;
+            let rd = register(rd)?;
+            // Negative values are accepted as the sign-extended 20-bit field.
+            build(rd, (immediate(imm, -0x80000, 0xfffff)? as i32) << 12)
+        } else if let Some((build, aq, rl)) = atomic(text) {
+            let [rd, rs2, address] = expect(mnemonic, operands)?;
+            build(
+                register(rd)?,
+                register(rs2)?,
+                atomic_address(address)?,
+                aq,
+                rl,
+            )
+        } else if let Some((aq, rl)) = load_reserved(text) {
+            let [rd, address] = expect(mnemonic, operands)?;
+            let rd = register(rd)?;
+            let rs1 = atomic_address(address)?;
+            RiscVInstruction::LrW { rd, rs1, aq, rl }
+        } else if let Some(build) = csr_register(text) {
+            let [rd, number, rs1] = expect(mnemonic, operands)?;
+            build(register(rd)?, csr(number)?, register(rs1)?)
+        } else if let Some(build) = csr_immediate(text) {
+            let [rd, number, imm] = expect(mnemonic, operands)?;
+            build(register(rd)?, csr(number)?, immediate(imm, 0, 31)? as u8)
+        } else if let Some(build) = float_load(text) {
+            let [rd, address] = expect(mnemonic, operands)?;
+            let rd = float_register(rd)?;
+            let (imm, rs1) = memory(address)?;
+            build(rd, rs1, imm)
+        } else if let Some(build) = float_store(text) {
+            let [rs2, address] = expect(mnemonic, operands)?;
+            let rs2 = float_register(rs2)?;
+            let (imm, rs1) = memory(address)?;
+            build(rs1, rs2, imm)
+        } else if let Some(build) = fused(text) {
+            let ([rd, rs1, rs2, rs3], rm) = rounding(mnemonic, operands)?;
+            build(
+                float_register(rd)?,
+                float_register(rs1)?,
+                float_register(rs2)?,
+                float_register(rs3)?,
+                rm,
+            )
+        } else if let Some(build) = float_arithmetic(text) {
+            let ([rd, rs1, rs2], rm) = rounding(mnemonic, operands)?;
+            build(
+                float_register(rd)?,
+                float_register(rs1)?,
+                float_register(rs2)?,
+                rm,
+            )
+        } else if let Some(build) = float_r_type(text) {
+            let [rd, rs1, rs2] = expect(mnemonic, operands)?;
+            build(
+                float_register(rd)?,
+                float_register(rs1)?,
+                float_register(rs2)?,
+            )
+        } else if let Some(build) = float_unary(text) {
+            let ([rd, rs1], rm) = rounding(mnemonic, operands)?;
+            build(float_register(rd)?, float_register(rs1)?, rm)
+        } else if let Some(build) = float_compare(text) {
+            let [rd, rs1, rs2] = expect(mnemonic, operands)?;
+            build(register(rd)?, float_register(rs1)?, float_register(rs2)?)
+        } else if let Some(build) = float_to_int(text) {
+            let ([rd, rs1], rm) = rounding(mnemonic, operands)?;
+            build(register(rd)?, float_register(rs1)?, rm)
+        } else if let Some(build) = int_to_float(text) {
+            let ([rd, rs1], rm) = rounding(mnemonic, operands)?;
+            build(float_register(rd)?, register(rs1)?, rm)
+        } else if let Some(build) = float_to_x(text) {
+            let [rd, rs1] = expect(mnemonic, operands)?;
+            build(register(rd)?, float_register(rs1)?)
+        } else if let Some(build) = counter_pseudo(text) {
+            let [rd] = expect(mnemonic, operands)?;
+            return pseudo(build(register(rd)?), mnemonic);
+        } else if let Some(build) = register_pseudo(text) {
+            let [rd, rs] = expect(mnemonic, operands)?;
+            return pseudo(build(register(rd)?, register(rs)?), mnemonic);
+        } else if let Some(build) = branch_pseudo(text) {
+            let [rs, target] = expect(mnemonic, operands)?;
+            let rs = register(rs)?;
+            let offset = self.target(target, Ok)?;
+            return pseudo(build(rs, offset), target);
+        } else {
+            return self.other(mnemonic, operands);
+        };
+
+        Ok(vec![instruction])
+    }
+
+    /// Assembles the instructions and pseudo-instructions with irregular operands.
+    fn other(
+        &self,
+        mnemonic: &Token<'a>,
+        operands: &[Token<'a>],
+    ) -> Result<Vec<RiscVInstruction>, AssembleError> {
+        match (mnemonic.text, operands) {
+            (".insn", _) => Ok(vec![custom(mnemonic, operands)?]),
+            ("ecall", []) => Ok(vec![RiscVInstruction::Ecall]),
+            ("ebreak", []) => Ok(vec![RiscVInstruction::Ebreak]),
+            ("fence", []) => Ok(vec![RiscVInstruction::Fence {
+                pred: FENCE_ALL,
+                succ: FENCE_ALL,
+            }]),
+            ("fence", [pred, succ]) => Ok(vec![RiscVInstruction::Fence {
+                pred: fence_set(pred)?,
+                succ: fence_set(succ)?,
+            }]),
+            ("fence.tso", []) => Ok(vec![RiscVInstruction::FenceTso]),
+            ("fence.i", []) => Ok(vec![RiscVInstruction::FenceI]),
+            ("fmv.w.x", [rd, rs1]) => Ok(vec![RiscVInstruction::FmvWX {
+                rd: float_register(rd)?,
+                rs1: register(rs1)?,
+            }]),
+            ("nop", []) => pseudo(Pseudo::Nop, mnemonic),
+            ("ret", []) => pseudo(Pseudo::Ret, mnemonic),
+            ("li", [rd, imm]) => {
+                let rd = register(rd)?;
+                let imm = immediate(imm, i32::MIN as i64, u32::MAX as i64)? as i32;
+                pseudo(Pseudo::Li { rd, imm }, mnemonic)
+            }
+            ("la", [rd, target]) => {
+                let rd = register(rd)?;
+                let (hi, lo) = split_offset(self.target(target, Ok)?);
+                Ok(vec![
+                    RiscVInstruction::Auipc { rd, imm: hi },
+                    RiscVInstruction::Addi {
+                        rd,
+                        rs1: rd,
+                        imm: lo,
+                    },
+                ])
+            }
+            ("j", [target]) => pseudo(
+                Pseudo::J {
+                    offset: self.target(target, Ok)?,
+                },
+                target,
+            ),
+            ("jal", [target]) => pseudo(
+                Pseudo::Jal {
+                    offset: self.target(target, Ok)?,
+                },
+                target,
+            ),
+            ("jal", [rd, target]) => Ok(vec![RiscVInstruction::Jal {
+                rd: register(rd)?,
+                imm: self.target(target, jump_offset)?,
+            }]),
+            ("jr", [rs]) => pseudo(Pseudo::Jr { rs: register(rs)? }, mnemonic),
+            ("jalr", [rs]) => pseudo(Pseudo::Jalr { rs: register(rs)? }, mnemonic),
+            ("jalr", [rd, address]) => {
+                let rd = register(rd)?;
+                let (imm, rs1) = memory(address)?;
+                Ok(vec![RiscVInstruction::Jalr { rd, rs1, imm }])
+            }
+            ("jalr", [rd, rs1, imm]) => Ok(vec![RiscVInstruction::Jalr {
+                rd: register(rd)?,
+                rs1: register(rs1)?,
+                imm: immediate(imm, -2048, 2047)? as i16,
+            }]),
+            ("call", [target]) => pseudo(
+                Pseudo::Call {
+                    offset: self.target(target, Ok)?,
+                },
+                target,
+            ),
+            ("tail", [target]) => pseudo(
+                Pseudo::Tail {
+                    offset: self.target(target, Ok)?,
+                },
+                target,
+            ),
+            ("csrr", [rd, number]) => pseudo(
+                Pseudo::Csrr {
+                    rd: register(rd)?,
+                    csr: csr(number)?,
+                },
+                mnemonic,
+            ),
+            ("csrw", [number, rs]) => pseudo(
+                Pseudo::Csrw {
+                    csr: csr(number)?,
+                    rs: register(rs)?,
+                },
+                mnemonic,
+            ),
+            (
+                "ecall" | "ebreak" | "fence" | "fence.tso" | "fence.i" | "fmv.w.x" | "nop" | "ret"
+                | "li" | "la" | "j" | "jal" | "jr" | "jalr" | "call" | "tail" | "csrr" | "csrw",
+                _,
+            ) => Err(mnemonic.error(AssembleErrorKind::InvalidOperands)),
+            _ => Err(mnemonic.error(AssembleErrorKind::UnknownMnemonic)),
+        }
+    }
+
+    /// Resolves a branch or jump target to an offset from the current instruction,
+    /// and checks it with `check`.
+    fn target<T>(
+        &self,
+        token: &Token<'a>,
+        check: impl Fn(i32) -> Result<T, Error>,
+    ) -> Result<T, AssembleError> {
+        let offset = if is_identifier(token.text) {
+            match self.labels {
+                Some(labels) => {
+                    let label = labels
+                        .get(token.text)
+                        .ok_or(token.error(AssembleErrorKind::UnknownLabel))?;
+                    *label as i64 - self.pc as i64
+                }
+                None => 0,
+            }
+        } else {
+            number(token)?
+        };
+
+        i32::try_from(offset)
+            .ok()
+            .and_then(|offset| check(offset).ok())
+            .ok_or(token.error(AssembleErrorKind::InvalidImmediate))
+    }
+
+    /// Resolves a value of a data directive `width` bytes wide.
+    fn data(&self, token: &Token<'a>, width: usize) -> Result<u32, AssembleError> {
+        let value = if is_identifier(token.text) {
+            match self.labels {
+                Some(labels) => *labels
+                    .get(token.text)
+                    .ok_or(token.error(AssembleErrorKind::UnknownLabel))?
+                    as i64,
+                None => 0,
+            }
+        } else {
+            number(token)?
+        };
+
+        // Values may be given either signed or unsigned.
+        let bits = width as u32 * 8;
+        let min = -(1_i64 << (bits - 1));
+        let max = (1_i64 << bits) - 1;
+        if (min..=max).contains(&value) {
+            Ok(value as u32)
+        } else {
+            Err(token.error(AssembleErrorKind::InvalidImmediate))
+        }
+    }
+}
+
+/// Assembles the operands of `.insn`: the format, `r` or `i`, and a custom opcode,
+/// followed by the operands of that format.
+fn custom(mnemonic: &Token<'_>, operands: &[Token<'_>]) -> Result<RiscVInstruction, AssembleError> {
+    let Some((first, rest)) = operands.split_first() else {
+        return Err(mnemonic.error(AssembleErrorKind::InvalidOperands));
+    };
+    let split = first
+        .text
+        .find(char::is_whitespace)
+        .ok_or(first.error(AssembleErrorKind::InvalidOperands))?;
+    let opcode = first.slice(split, first.text.len());
+    let opcode = CustomOpcode::new(immediate(&opcode, 0, 0x7f)? as u32)
+        .ok_or(opcode.error(AssembleErrorKind::InvalidOperands))?;
+
+    let (funct3, operands) = match (&first.text[..split], rest) {
+        ("r", [funct3, funct7, rd, rs1, rs2]) => (
+            funct3,
+            CustomOperands::R {
+                funct7: immediate(funct7, 0, 0x7f)? as u8,
+                rd: register(rd)?,
+                rs1: register(rs1)?,
+                rs2: register(rs2)?,
+            },
+        ),
+        ("i", [funct3, rd, rs1, imm]) => (
+            funct3,
+            CustomOperands::I {
+                rd: register(rd)?,
+                rs1: register(rs1)?,
+                imm: immediate(imm, -2048, 2047)? as i16,
+            },
+        ),
+        _ => return Err(mnemonic.error(AssembleErrorKind::InvalidOperands)),
+    };
+
+    Ok(RiscVInstruction::Custom {
+        opcode,
+        funct3: immediate(funct3, 0, 7)? as u8,
+        operands,
+    })
+}
+
+/// Expands a pseudo-instruction, reporting an out of range offset at `token`.
+fn pseudo(pseudo: Pseudo, token: &Token<'_>) -> Result<Vec<RiscVInstruction>, AssembleError> {
+    pseudo
+        .expand()
+        .map_err(|_| token.error(AssembleErrorKind::InvalidImmediate))
+}
+
+/// Checks that an instruction has exactly `N` operands.
+fn expect<'a, 'b, const N: usize>(
+    mnemonic: &Token<'a>,
+    operands: &'b [Token<'a>],
+) -> Result<&'b [Token<'a>; N], AssembleError> {
+    operands
+        .try_into()
+        .map_err(|_| mnemonic.error(AssembleErrorKind::InvalidOperands))
+}
+
+fn register(token: &Token<'_>) -> Result<Register, AssembleError> {
+    Register::from_name(token.text).ok_or(token.error(AssembleErrorKind::UnknownRegister))
+}
+
+fn float_register(token: &Token<'_>) -> Result<FloatRegister, AssembleError> {
+    FloatRegister::from_name(token.text).ok_or(token.error(AssembleErrorKind::UnknownRegister))
+}
+
+/// Checks that a floating-point instruction has `N` operands followed by an optional
+/// rounding mode such as `rtz`, which defaults to `dyn`.
+fn rounding<'a, 'b, const N: usize>(
+    mnemonic: &Token<'a>,
+    operands: &'b [Token<'a>],
+) -> Result<(&'b [Token<'a>; N], RoundingMode), AssembleError> {
+    match operands.split_last() {
+        Some((rm, operands)) if operands.len() == N => {
+            let rm = RoundingMode::from_name(rm.text)
+                .ok_or(rm.error(AssembleErrorKind::InvalidOperands))?;
+            Ok((expect(mnemonic, operands)?, rm))
+        }
+        _ => Ok((expect(mnemonic, operands)?, RoundingMode::Dynamic)),
+    }
+}
+
+/// Parses a CSR operand, either the name of a CSR or a 12-bit number.
+fn csr(token: &Token<'_>) -> Result<u16, AssembleError> {
+    match csr::from_name(token.text) {
+        Some(number) => Ok(number),
+        None => Ok(immediate(token, 0, 0xfff)? as u16),
+    }
+}
+
+/// The predecessor and successor set of a `fence` without operands: `iorw`.
+const FENCE_ALL: u8 = 0xf;
+
+/// Parses the predecessor or successor set of a fence: the letters of `iorw` in that
+/// order, each at most once, or `0` for the empty set.
+fn fence_set(token: &Token<'_>) -> Result<u8, AssembleError> {
+    if token.text == "0" {
+        return Ok(0);
+    }
+
+    let mut set = 0;
+    let mut letters = FENCE_SET_LETTERS.iter();
+    for letter in token.text.chars() {
+        let (bit, _) = letters
+            .find(|(_, other)| *other == letter)
+            .ok_or(token.error(AssembleErrorKind::InvalidOperands))?;
+        set |= bit;
+    }
+
+    match set {
+        0 => Err(token.error(AssembleErrorKind::InvalidOperands)),
+        _ => Ok(set),
+    }
+}
+
+/// Parses a decimal, `0x` hexadecimal or `0b` binary number with an optional sign.
+fn number(token: &Token<'_>) -> Result<i64, AssembleError> {
+    let text = token.text;
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, text.strip_prefix('+').unwrap_or(text)),
+    };
+
+    let magnitude = if let Some(hex) = digits.strip_prefix("0x") {
+        i64::from_str_radix(hex, 16)
+    } else if let Some(binary) = digits.strip_prefix("0b") {
+        i64::from_str_radix(binary, 2)
+    } else {
+        digits.parse()
+    };
+
+    // Signs are handled above, so a second one is an error.
+    match magnitude {
+        Ok(magnitude) if !digits.starts_with(['-', '+']) => {
+            Ok(if negative { -magnitude } else { magnitude })
+        }
+        _ => Err(token.error(AssembleErrorKind::InvalidNumber)),
+    }
+}
+
+/// Parses a number and checks that it is within `min..=max`.
+fn immediate(token: &Token<'_>, min: i64, max: i64) -> Result<i64, AssembleError> {
+    let value = number(token)?;
+    if (min..=max).contains(&value) {
+        Ok(value)
+    } else {
+        Err(token.error(AssembleErrorKind::InvalidImmediate))
+    }
+}
+
+/// Parses the address of an atomic instruction, `(a0)` or `0(a0)`.
+fn atomic_address(token: &Token<'_>) -> Result<Register, AssembleError> {
+    match memory(token)? {
+        (0, rs1) => Ok(rs1),
+        _ => Err(token.error(AssembleErrorKind::InvalidImmediate)),
+    }
+}
+
+/// Parses a memory operand such as `8(sp)` or `(a0)`.
+fn memory(token: &Token<'_>) -> Result<(i16, Register), AssembleError> {
+    let text = token.text;
+    let (Some(open), true) = (text.find('('), text.ends_with(')')) else {
+        return Err(token.error(AssembleErrorKind::InvalidOperands));
+    };
+
+    let offset = token.slice(0, open);
+    let base = token.slice(open + 1, text.len() - 1);
+
+    let imm = if offset.text.is_empty() {
+        0
+    } else {
+        immediate(&offset, -2048, 2047)? as i16
+    };
+
+    Ok((imm, register(&base)?))
+}
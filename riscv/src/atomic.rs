@@ -0,0 +1,31 @@
+/// The reservation held by `lr.w` for a later `sc.w`.
+///
+/// The VM runs a single hart, so the atomic instructions behave the same on every
+/// backend and on every run:
+///
+/// - Every atomic memory operation is a plain read-modify-write of the word at `rs1`.
+///   No other hart can access memory in between, and `aq` and `rl` have no effect.
+/// - The address of every atomic instruction must be a multiple of 4. Other
+///   addresses trap with `Error::MisalignedAtomic`.
+/// - `lr.w` reserves its address, replacing any earlier reservation.
+/// - `sc.w` succeeds only if its address is reserved, and releases the reservation
+///   whether or not it succeeds. Ordinary stores leave the reservation in place, even
+///   when they write the reserved word.
+/// - No address is reserved when `Instance::call` starts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Reservation {
+    address: Option<u32>,
+}
+
+impl Reservation {
+    /// Reserves `address`, as `lr.w` does.
+    pub fn reserve(&mut self, address: u32) {
+        self.address = Some(address);
+    }
+
+    /// Releases the reservation and returns whether it was held on `address`, as
+    /// `sc.w` does.
+    pub fn release(&mut self, address: u32) -> bool {
+        self.address.take() == Some(address)
+    }
+}
@@ -0,0 +1,858 @@
+use crate::error::{DecodeError, DecodeErrorKind};
+use crate::extension::{Extension, Extensions};
+use crate::instruction::{RiscVInstruction, require_profile};
+use crate::profile::Profile;
+use crate::register::{FloatRegister, Register};
+
+const QUADRANT_MASK: u16 = 0x3;
+const QUADRANT_0: u16 = 0x0;
+const QUADRANT_1: u16 = 0x1;
+const QUADRANT_2: u16 = 0x2;
+
+const C_FLD_FUNCT3: u16 = 0x1;
+const C_FLW_FUNCT3: u16 = 0x3;
+const C_FSD_FUNCT3: u16 = 0x5;
+const C_FSW_FUNCT3: u16 = 0x7;
+
+const C_ADDI4SPN_FUNCT3: u16 = 0x0;
+const C_LW_FUNCT3: u16 = 0x2;
+const C_SW_FUNCT3: u16 = 0x6;
+// RV64C reuses the single-precision load and store slots for doublewords.
+const C_LD_FUNCT3: u16 = 0x3;
+const C_SD_FUNCT3: u16 = 0x7;
+
+const C_ADDI_FUNCT3: u16 = 0x0;
+const C_JAL_FUNCT3: u16 = 0x1;
+// RV64C replaces C.JAL with C.ADDIW.
+const C_ADDIW_FUNCT3: u16 = 0x1;
+const C_LI_FUNCT3: u16 = 0x2;
+const C_LUI_FUNCT3: u16 = 0x3;
+const C_MISC_ALU_FUNCT3: u16 = 0x4;
+const C_J_FUNCT3: u16 = 0x5;
+const C_BEQZ_FUNCT3: u16 = 0x6;
+const C_BNEZ_FUNCT3: u16 = 0x7;
+
+const C_SRLI_FUNCT2: u16 = 0x0;
+const C_SRAI_FUNCT2: u16 = 0x1;
+const C_ANDI_FUNCT2: u16 = 0x2;
+const C_SUB_FUNCT2: u16 = 0x0;
+const C_XOR_FUNCT2: u16 = 0x1;
+const C_OR_FUNCT2: u16 = 0x2;
+const C_AND_FUNCT2: u16 = 0x3;
+const C_SUBW_FUNCT2: u16 = 0x0;
+const C_ADDW_FUNCT2: u16 = 0x1;
+
+const C_SLLI_FUNCT3: u16 = 0x0;
+const C_LWSP_FUNCT3: u16 = 0x2;
+const C_JR_MV_ADD_FUNCT3: u16 = 0x4;
+const C_SWSP_FUNCT3: u16 = 0x6;
+const C_LDSP_FUNCT3: u16 = 0x3;
+const C_SDSP_FUNCT3: u16 = 0x7;
+
+const C_NOP: u16 = 0x0001;
+const C_EBREAK: u16 = 0x9002;
+
+const FUNCT3_SHIFT: u32 = 13;
+const SP: Register = Register::SP;
+const RA: Register = Register::RA;
+const ZERO: Register = Register::ZERO;
+
+/// Extracts bits `hi..=lo` of a compressed instruction, shifted down to bit 0.
+fn bits(half: u16, hi: u32, lo: u32) -> u32 {
+    ((half as u32) >> lo) & ((1 << (hi - lo + 1)) - 1)
+}
+
+/// Sign-extends the lowest `width` bits of `value`.
+fn sign_extend(value: u32, width: u32) -> i32 {
+    let shift = 32 - width;
+    ((value << shift) as i32) >> shift
+}
+
+/// Full register number of a 3-bit compressed register field, which maps to x8-x15.
+fn reg_prime(half: u16, lo: u32) -> Register {
+    Register::from_field(bits(half, lo + 2, lo) + 8)
+}
+
+/// Floating-point register of a 3-bit compressed register field, which maps to f8-f15.
+fn freg_prime(half: u16, lo: u32) -> FloatRegister {
+    FloatRegister::from_field(bits(half, lo + 2, lo) + 8)
+}
+
+/// Full 5-bit `rd`/`rs1` field in bits 11:7.
+fn reg_rd(half: u16) -> Register {
+    Register::from_field(bits(half, 11, 7))
+}
+
+/// Full 5-bit `rs2` field in bits 6:2.
+fn reg_rs2(half: u16) -> Register {
+    Register::from_field(bits(half, 6, 2))
+}
+
+/// The 6-bit signed immediate of the CI format: imm[5] in bit 12, imm[4:0] in bits 6:2.
+fn imm_ci(half: u16) -> i32 {
+    sign_extend((bits(half, 12, 12) << 5) | bits(half, 6, 2), 6)
+}
+
+/// The 6-bit shift amount of C.SLLI/C.SRLI/C.SRAI: shamt[5] in bit 12, shamt[4:0] in bits 6:2.
+fn shamt_ci(half: u16) -> u32 {
+    (bits(half, 12, 12) << 5) | bits(half, 6, 2)
+}
+
+/// The jump offset of C.J and C.JAL: offset[11|4|9:8|10|6|7|3:1|5] in bits 12:2.
+fn imm_cj(half: u16) -> i32 {
+    let offset = (bits(half, 12, 12) << 11)
+        | (bits(half, 11, 11) << 4)
+        | (bits(half, 10, 9) << 8)
+        | (bits(half, 8, 8) << 10)
+        | (bits(half, 7, 7) << 6)
+        | (bits(half, 6, 6) << 7)
+        | (bits(half, 5, 3) << 1)
+        | (bits(half, 2, 2) << 5);
+    sign_extend(offset, 12)
+}
+
+/// The branch offset of C.BEQZ and C.BNEZ: offset[8|4:3] in bits 12:10, offset[7:6|2:1|5] in bits 6:2.
+fn imm_cb(half: u16) -> i32 {
+    let offset = (bits(half, 12, 12) << 8)
+        | (bits(half, 11, 10) << 3)
+        | (bits(half, 6, 5) << 6)
+        | (bits(half, 4, 3) << 1)
+        | (bits(half, 2, 2) << 5);
+    sign_extend(offset, 9)
+}
+
+/// The word offset of C.LW and C.SW: uimm[5:3] in bits 12:10, uimm[2] in bit 6, uimm[6] in bit 5.
+fn uimm_cl(half: u16) -> i16 {
+    ((bits(half, 12, 10) << 3) | (bits(half, 6, 6) << 2) | (bits(half, 5, 5) << 6)) as i16
+}
+
+/// The doubleword offset of C.LD and C.SD: uimm[5:3] in bits 12:10, uimm[7:6] in bits 6:5.
+fn uimm_cl_double(half: u16) -> i16 {
+    ((bits(half, 12, 10) << 3) | (bits(half, 6, 5) << 6)) as i16
+}
+
+/// The word offset of C.LWSP and C.FLWSP: uimm[5] in bit 12, uimm[4:2] in bits 6:4,
+/// uimm[7:6] in bits 3:2.
+fn uimm_ci_word(half: u16) -> i16 {
+    ((bits(half, 12, 12) << 5) | (bits(half, 6, 4) << 2) | (bits(half, 3, 2) << 6)) as i16
+}
+
+/// The doubleword offset of C.LDSP and C.FLDSP: uimm[5] in bit 12, uimm[4:3] in bits
+/// 6:5, uimm[8:6] in bits 4:2.
+fn uimm_ci_double(half: u16) -> i16 {
+    ((bits(half, 12, 12) << 5) | (bits(half, 6, 5) << 3) | (bits(half, 4, 2) << 6)) as i16
+}
+
+/// The word offset of C.SWSP and C.FSWSP: uimm[5:2] in bits 12:9, uimm[7:6] in bits 8:7.
+fn uimm_css_word(half: u16) -> i16 {
+    ((bits(half, 12, 9) << 2) | (bits(half, 8, 7) << 6)) as i16
+}
+
+/// The doubleword offset of C.SDSP and C.FSDSP: uimm[5:3] in bits 12:10, uimm[8:6] in
+/// bits 9:7.
+fn uimm_css_double(half: u16) -> i16 {
+    ((bits(half, 12, 10) << 3) | (bits(half, 9, 7) << 6)) as i16
+}
+
+/// Decodes the RV64C encodings that differ from RV32C, or returns `None` for the
+/// encodings that the two share.
+fn decode_rv64(half: u16) -> Option<Result<RiscVInstruction, DecodeError>> {
+    let reserved = || {
+        Err(DecodeError::new(
+            half as u32,
+            DecodeErrorKind::ReservedCompressed,
+        ))
+    };
+    let funct3 = half >> FUNCT3_SHIFT;
+
+    let instruction = match (half & QUADRANT_MASK, funct3) {
+        (QUADRANT_0, C_LD_FUNCT3) => RiscVInstruction::Ld {
+            rd: reg_prime(half, 2),
+            rs1: reg_prime(half, 7),
+            imm: uimm_cl_double(half),
+        },
+        (QUADRANT_0, C_SD_FUNCT3) => RiscVInstruction::Sd {
+            rs1: reg_prime(half, 7),
+            rs2: reg_prime(half, 2),
+            imm: uimm_cl_double(half),
+        },
+        (QUADRANT_1, C_ADDIW_FUNCT3) => {
+            let rd = reg_rd(half);
+            if rd == ZERO {
+                return Some(reserved());
+            }
+            RiscVInstruction::Addiw {
+                rd,
+                rs1: rd,
+                imm: imm_ci(half) as i16,
+            }
+        }
+        (QUADRANT_1, C_MISC_ALU_FUNCT3) => {
+            let rd = reg_prime(half, 7);
+            let rs2 = reg_prime(half, 2);
+            let imm = shamt_ci(half) as i16;
+            match (bits(half, 11, 10) as u16, bits(half, 12, 12)) {
+                (C_SRLI_FUNCT2, _) => RiscVInstruction::Srli { rd, rs1: rd, imm },
+                (C_SRAI_FUNCT2, _) => RiscVInstruction::Srai { rd, rs1: rd, imm },
+                (C_ANDI_FUNCT2, _) | (_, 0) => return None,
+                // Bit 12 selects the word operations.
+                _ => match bits(half, 6, 5) as u16 {
+                    C_SUBW_FUNCT2 => RiscVInstruction::Subw { rd, rs1: rd, rs2 },
+                    C_ADDW_FUNCT2 => RiscVInstruction::Addw { rd, rs1: rd, rs2 },
+                    _ => return Some(reserved()),
+                },
+            }
+        }
+        (QUADRANT_2, C_SLLI_FUNCT3) => {
+            let rd = reg_rd(half);
+            RiscVInstruction::Slli {
+                rd,
+                rs1: rd,
+                imm: shamt_ci(half) as i16,
+            }
+        }
+        (QUADRANT_2, C_LDSP_FUNCT3) => {
+            let rd = reg_rd(half);
+            if rd == ZERO {
+                return Some(reserved());
+            }
+            RiscVInstruction::Ld {
+                rd,
+                rs1: SP,
+                imm: uimm_ci_double(half),
+            }
+        }
+        (QUADRANT_2, C_SDSP_FUNCT3) => RiscVInstruction::Sd {
+            rs1: SP,
+            rs2: reg_rs2(half),
+            imm: uimm_css_double(half),
+        },
+        _ => return None,
+    };
+
+    Some(Ok(instruction))
+}
+
+/// Decodes the compressed floating-point loads and stores of the F and D extensions
+/// enabled in `extensions`, or returns `None` for every other encoding.
+///
+/// RV64C has no single-precision forms: `decode_rv64` decodes their slots as `c.ld`,
+/// `c.sd`, `c.ldsp` and `c.sdsp`.
+fn decode_float(half: u16, extensions: Extensions) -> Option<RiscVInstruction> {
+    let single = extensions.contains(Extension::F);
+    let double = extensions.contains(Extension::D);
+    let funct3 = half >> FUNCT3_SHIFT;
+
+    let instruction = match (half & QUADRANT_MASK, funct3) {
+        (QUADRANT_0, C_FLD_FUNCT3) if double => RiscVInstruction::Fld {
+            rd: freg_prime(half, 2),
+            rs1: reg_prime(half, 7),
+            imm: uimm_cl_double(half),
+        },
+        (QUADRANT_0, C_FLW_FUNCT3) if single => RiscVInstruction::Flw {
+            rd: freg_prime(half, 2),
+            rs1: reg_prime(half, 7),
+            imm: uimm_cl(half),
+        },
+        (QUADRANT_0, C_FSD_FUNCT3) if double => RiscVInstruction::Fsd {
+            rs1: reg_prime(half, 7),
+            rs2: freg_prime(half, 2),
+            imm: uimm_cl_double(half),
+        },
+        (QUADRANT_0, C_FSW_FUNCT3) if single => RiscVInstruction::Fsw {
+            rs1: reg_prime(half, 7),
+            rs2: freg_prime(half, 2),
+            imm: uimm_cl(half),
+        },
+        (QUADRANT_2, C_FLD_FUNCT3) if double => RiscVInstruction::Fld {
+            rd: FloatRegister::from_field(bits(half, 11, 7)),
+            rs1: SP,
+            imm: uimm_ci_double(half),
+        },
+        (QUADRANT_2, C_FLW_FUNCT3) if single => RiscVInstruction::Flw {
+            rd: FloatRegister::from_field(bits(half, 11, 7)),
+            rs1: SP,
+            imm: uimm_ci_word(half),
+        },
+        (QUADRANT_2, C_FSD_FUNCT3) if double => RiscVInstruction::Fsd {
+            rs1: SP,
+            rs2: FloatRegister::from_field(bits(half, 6, 2)),
+            imm: uimm_css_double(half),
+        },
+        (QUADRANT_2, C_FSW_FUNCT3) if single => RiscVInstruction::Fsw {
+            rs1: SP,
+            rs2: FloatRegister::from_field(bits(half, 6, 2)),
+            imm: uimm_css_word(half),
+        },
+        _ => return None,
+    };
+
+    Some(instruction)
+}
+
+/// Explains why a compressed instruction in a slot not handled by `decode_compressed` was rejected.
+///
+/// The floating-point load and store slots are the same in quadrants 0 and 2.
+fn floating_point_error(half: u16) -> DecodeError {
+    let kind = match half >> FUNCT3_SHIFT {
+        C_FLD_FUNCT3 | C_FSD_FUNCT3 => DecodeErrorKind::UnsupportedExtension(Extension::D),
+        C_FLW_FUNCT3 | C_FSW_FUNCT3 => DecodeErrorKind::UnsupportedExtension(Extension::F),
+        _ => DecodeErrorKind::ReservedCompressed,
+    };
+
+    DecodeError::new(half as u32, kind)
+}
+
+/// Whether a register is one of x8-x15, reachable through a 3-bit compressed register field.
+fn is_prime(reg: Register) -> bool {
+    (8..16).contains(&reg.number())
+}
+
+/// The 3-bit compressed register field of one of x8-x15.
+fn prime(reg: Register) -> u16 {
+    (reg.number() - 8) as u16
+}
+
+/// The 5-bit register field of any register.
+fn full(reg: Register) -> u16 {
+    reg.number() as u16
+}
+
+/// Whether `imm` is a multiple of `align` within `min..=max`.
+fn fits(imm: i32, min: i32, max: i32, align: i32) -> bool {
+    (min..=max).contains(&imm) && imm % align == 0
+}
+
+/// Places bits `hi..=lo` of `value` at bit position `at` of a compressed instruction.
+fn place(value: i32, hi: u32, lo: u32, at: u32) -> u16 {
+    ((((value as u32) >> lo) & ((1 << (hi - lo + 1)) - 1)) << at) as u16
+}
+
+fn encode_ci(funct3: u16, rd: Register, imm: i32, quadrant: u16) -> u16 {
+    (funct3 << FUNCT3_SHIFT)
+        | place(imm, 5, 5, 12)
+        | (full(rd) << 7)
+        | place(imm, 4, 0, 2)
+        | quadrant
+}
+
+fn encode_cl(funct3: u16, rs1: Register, reg: Register, imm: i16) -> u16 {
+    let imm = imm as i32;
+    (funct3 << FUNCT3_SHIFT)
+        | place(imm, 5, 3, 10)
+        | (prime(rs1) << 7)
+        | place(imm, 2, 2, 6)
+        | place(imm, 6, 6, 5)
+        | (prime(reg) << 2)
+        | QUADRANT_0
+}
+
+fn encode_cj(funct3: u16, imm: i32) -> u16 {
+    (funct3 << FUNCT3_SHIFT)
+        | place(imm, 11, 11, 12)
+        | place(imm, 4, 4, 11)
+        | place(imm, 9, 8, 9)
+        | place(imm, 10, 10, 8)
+        | place(imm, 6, 6, 7)
+        | place(imm, 7, 7, 6)
+        | place(imm, 3, 1, 3)
+        | place(imm, 5, 5, 2)
+        | QUADRANT_1
+}
+
+fn encode_cb(funct3: u16, rs1: Register, imm: i16) -> u16 {
+    let imm = imm as i32;
+    (funct3 << FUNCT3_SHIFT)
+        | place(imm, 8, 8, 12)
+        | place(imm, 4, 3, 10)
+        | (prime(rs1) << 7)
+        | place(imm, 7, 6, 5)
+        | place(imm, 2, 1, 3)
+        | place(imm, 5, 5, 2)
+        | QUADRANT_1
+}
+
+fn encode_misc_alu(funct2: u16, rd: Register, imm: i32) -> u16 {
+    (C_MISC_ALU_FUNCT3 << FUNCT3_SHIFT)
+        | place(imm, 5, 5, 12)
+        | (funct2 << 10)
+        | (prime(rd) << 7)
+        | place(imm, 4, 0, 2)
+        | QUADRANT_1
+}
+
+fn encode_arith(funct2: u16, rd: Register, rs2: Register) -> u16 {
+    (C_MISC_ALU_FUNCT3 << FUNCT3_SHIFT)
+        | (0x3 << 10)
+        | (prime(rd) << 7)
+        | (funct2 << 5)
+        | (prime(rs2) << 2)
+        | QUADRANT_1
+}
+
+fn encode_cr(bit12: u16, rd: Register, rs2: Register) -> u16 {
+    (C_JR_MV_ADD_FUNCT3 << FUNCT3_SHIFT)
+        | (bit12 << 12)
+        | (full(rd) << 7)
+        | (full(rs2) << 2)
+        | QUADRANT_2
+}
+
+impl RiscVInstruction {
+    /// Decode a 16-bit compressed (RV32C) instruction for the given profile, also
+    /// decoding the given optional extensions
+    ///
+    /// [`RiscVInstruction::decode_compressed`] decodes for `Profile::Rv32i`, where
+    /// every register is available, with no optional extension. `Profile::Rv64i`
+    /// decodes the RV64C encodings instead where they differ: `c.ld`, `c.sd`, `c.ldsp`
+    /// and `c.sdsp` take the place of the single-precision loads and stores, `c.addiw`
+    /// that of `c.jal`, `c.addw` and `c.subw` are added, and shift amounts may be up
+    /// to 63.
+    ///
+    /// With the F and D extensions enabled, the compressed floating-point loads and
+    /// stores expand to `flw`, `fsw`, `fld` and `fsd`. With `Profile::Rv64i`, `c.fld`
+    /// and `c.fsd` are rejected with `DecodeErrorKind::Unimplemented` instead, like
+    /// `fld` and `fsd`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `DecodeError` holding the 16-bit parcel for the same encodings as
+    /// `decode_compressed`, except for the floating-point loads and stores of enabled
+    /// extensions, and with `DecodeErrorKind::UnsupportedRegister` if the expanded
+    /// instruction names a register outside of `profile`.
+    pub fn decode_compressed_for(
+        half: u16,
+        profile: Profile,
+        extensions: Extensions,
+    ) -> Result<RiscVInstruction, DecodeError> {
+        let rv64 = match profile.xlen() {
+            64 => decode_rv64(half),
+            _ => None,
+        };
+        let instruction = match rv64 {
+            Some(result) => result?,
+            None => match decode_float(half, extensions) {
+                Some(instruction) => instruction,
+                None => RiscVInstruction::decode_compressed(half)?,
+            },
+        };
+        // Like their 32-bit encodings, the floating-point instructions are not
+        // implemented for 64-bit registers.
+        if profile.xlen() == 64 && instruction.extension().is_some() {
+            return Err(DecodeError::new(
+                half as u32,
+                DecodeErrorKind::Unimplemented,
+            ));
+        }
+        require_profile(half as u32, instruction, profile)
+    }
+
+    /// Decode a 16-bit compressed (RV32C) instruction into a RiscVInstruction
+    ///
+    /// Compressed instructions are expanded into the equivalent 32-bit instruction, so
+    /// `c.addi x8, 1` decodes to `addi x8, x8, 1` and `c.jr x1` decodes to `jalr x0, x1, 0`.
+    ///
+    /// # Arguments
+    ///
+    /// * `half` - The 16-bit instruction parcel to decode
+    ///
+    /// # Errors
+    ///
+    /// Returns a `DecodeError` holding the 16-bit parcel for reserved encodings, the
+    /// all-zero illegal instruction and compressed floating-point loads and stores,
+    /// which need the F or D extension enabled through
+    /// [`RiscVInstruction::decode_compressed_for`].
+    pub fn decode_compressed(half: u16) -> Result<RiscVInstruction, DecodeError> {
+        let funct3 = half >> FUNCT3_SHIFT;
+
+        match half & QUADRANT_MASK {
+            QUADRANT_0 => match funct3 {
+                C_ADDI4SPN_FUNCT3 => {
+                    // nzuimm[5:4|9:6|2|3] lives in bits 12:5.
+                    let imm = (bits(half, 12, 11) << 4)
+                        | (bits(half, 10, 7) << 6)
+                        | (bits(half, 6, 6) << 2)
+                        | (bits(half, 5, 5) << 3);
+                    if imm == 0 {
+                        Err(DecodeError::new(
+                            half as u32,
+                            DecodeErrorKind::ReservedCompressed,
+                        ))
+                    } else {
+                        Ok(RiscVInstruction::Addi {
+                            rd: reg_prime(half, 2),
+                            rs1: SP,
+                            imm: imm as i16,
+                        })
+                    }
+                }
+                C_LW_FUNCT3 => Ok(RiscVInstruction::Lw {
+                    rd: reg_prime(half, 2),
+                    rs1: reg_prime(half, 7),
+                    imm: uimm_cl(half),
+                }),
+                C_SW_FUNCT3 => Ok(RiscVInstruction::Sw {
+                    rs1: reg_prime(half, 7),
+                    rs2: reg_prime(half, 2),
+                    imm: uimm_cl(half),
+                }),
+                _ => Err(floating_point_error(half)),
+            },
+            QUADRANT_1 => match funct3 {
+                C_ADDI_FUNCT3 => {
+                    let rd = reg_rd(half);
+                    Ok(RiscVInstruction::Addi {
+                        rd,
+                        rs1: rd,
+                        imm: imm_ci(half) as i16,
+                    })
+                }
+                C_JAL_FUNCT3 => Ok(RiscVInstruction::Jal {
+                    rd: RA,
+                    imm: imm_cj(half),
+                }),
+                C_LI_FUNCT3 => Ok(RiscVInstruction::Addi {
+                    rd: reg_rd(half),
+                    rs1: ZERO,
+                    imm: imm_ci(half) as i16,
+                }),
+                C_LUI_FUNCT3 => {
+                    let rd = reg_rd(half);
+                    if rd == SP {
+                        // C.ADDI16SP: nzimm[9] in bit 12, nzimm[4|6|8:7|5] in bits 6:2.
+                        let imm = (bits(half, 12, 12) << 9)
+                            | (bits(half, 6, 6) << 4)
+                            | (bits(half, 5, 5) << 6)
+                            | (bits(half, 4, 3) << 7)
+                            | (bits(half, 2, 2) << 5);
+                        if imm == 0 {
+                            Err(DecodeError::new(
+                                half as u32,
+                                DecodeErrorKind::ReservedCompressed,
+                            ))
+                        } else {
+                            Ok(RiscVInstruction::Addi {
+                                rd: SP,
+                                rs1: SP,
+                                imm: sign_extend(imm, 10) as i16,
+                            })
+                        }
+                    } else {
+                        let imm = imm_ci(half);
+                        if imm == 0 {
+                            Err(DecodeError::new(
+                                half as u32,
+                                DecodeErrorKind::ReservedCompressed,
+                            ))
+                        } else {
+                            Ok(RiscVInstruction::Lui { rd, imm: imm << 12 })
+                        }
+                    }
+                }
+                C_MISC_ALU_FUNCT3 => {
+                    let rd = reg_prime(half, 7);
+                    match bits(half, 11, 10) as u16 {
+                        C_SRLI_FUNCT2 => {
+                            let shamt = shamt_ci(half);
+                            // shamt[5] must be zero on RV32.
+                            if shamt > 31 {
+                                Err(DecodeError::new(
+                                    half as u32,
+                                    DecodeErrorKind::ReservedCompressed,
+                                ))
+                            } else {
+                                Ok(RiscVInstruction::Srli {
+                                    rd,
+                                    rs1: rd,
+                                    imm: shamt as i16,
+                                })
+                            }
+                        }
+                        C_SRAI_FUNCT2 => {
+                            let shamt = shamt_ci(half);
+                            // shamt[5] must be zero on RV32.
+                            if shamt > 31 {
+                                Err(DecodeError::new(
+                                    half as u32,
+                                    DecodeErrorKind::ReservedCompressed,
+                                ))
+                            } else {
+                                Ok(RiscVInstruction::Srai {
+                                    rd,
+                                    rs1: rd,
+                                    imm: shamt as i16,
+                                })
+                            }
+                        }
+                        C_ANDI_FUNCT2 => Ok(RiscVInstruction::Andi {
+                            rd,
+                            rs1: rd,
+                            imm: imm_ci(half) as i16,
+                        }),
+                        _ => {
+                            // Bit 12 selects the RV64-only word operations.
+                            if bits(half, 12, 12) != 0 {
+                                Err(DecodeError::new(
+                                    half as u32,
+                                    DecodeErrorKind::ReservedCompressed,
+                                ))
+                            } else {
+                                let rs2 = reg_prime(half, 2);
+                                match bits(half, 6, 5) as u16 {
+                                    C_SUB_FUNCT2 => Ok(RiscVInstruction::Sub { rd, rs1: rd, rs2 }),
+                                    C_XOR_FUNCT2 => Ok(RiscVInstruction::Xor { rd, rs1: rd, rs2 }),
+                                    C_OR_FUNCT2 => Ok(RiscVInstruction::Or { rd, rs1: rd, rs2 }),
+                                    C_AND_FUNCT2 => Ok(RiscVInstruction::And { rd, rs1: rd, rs2 }),
+                                    _ => unreachable!("All 2-bit funct2 values are handled above"),
+                                }
+                            }
+                        }
+                    }
+                }
+                C_J_FUNCT3 => Ok(RiscVInstruction::Jal {
+                    rd: ZERO,
+                    imm: imm_cj(half),
+                }),
+                C_BEQZ_FUNCT3 => Ok(RiscVInstruction::Beq {
+                    rs1: reg_prime(half, 7),
+                    rs2: ZERO,
+                    imm: imm_cb(half) as i16,
+                }),
+                C_BNEZ_FUNCT3 => Ok(RiscVInstruction::Bne {
+                    rs1: reg_prime(half, 7),
+                    rs2: ZERO,
+                    imm: imm_cb(half) as i16,
+                }),
+                _ => unreachable!("All 3-bit funct3 values are handled above"),
+            },
+            QUADRANT_2 => match funct3 {
+                C_SLLI_FUNCT3 => {
+                    let rd = reg_rd(half);
+                    let shamt = shamt_ci(half);
+                    // shamt[5] must be zero on RV32.
+                    if shamt > 31 {
+                        Err(DecodeError::new(
+                            half as u32,
+                            DecodeErrorKind::ReservedCompressed,
+                        ))
+                    } else {
+                        Ok(RiscVInstruction::Slli {
+                            rd,
+                            rs1: rd,
+                            imm: shamt as i16,
+                        })
+                    }
+                }
+                C_LWSP_FUNCT3 => {
+                    let rd = reg_rd(half);
+                    if rd == ZERO {
+                        Err(DecodeError::new(
+                            half as u32,
+                            DecodeErrorKind::ReservedCompressed,
+                        ))
+                    } else {
+                        Ok(RiscVInstruction::Lw {
+                            rd,
+                            rs1: SP,
+                            imm: uimm_ci_word(half),
+                        })
+                    }
+                }
+                C_JR_MV_ADD_FUNCT3 => {
+                    let rd = reg_rd(half);
+                    let rs2 = reg_rs2(half);
+                    match (bits(half, 12, 12), rd, rs2) {
+                        (0, ZERO, ZERO) => Err(DecodeError::new(
+                            half as u32,
+                            DecodeErrorKind::ReservedCompressed,
+                        )),
+                        (0, rs1, ZERO) => Ok(RiscVInstruction::Jalr {
+                            rd: ZERO,
+                            rs1,
+                            imm: 0,
+                        }),
+                        (0, rd, rs2) => Ok(RiscVInstruction::Add { rd, rs1: ZERO, rs2 }),
+                        (_, ZERO, ZERO) => Ok(RiscVInstruction::Ebreak),
+                        (_, rs1, ZERO) => Ok(RiscVInstruction::Jalr {
+                            rd: RA,
+                            rs1,
+                            imm: 0,
+                        }),
+                        (_, rd, rs2) => Ok(RiscVInstruction::Add { rd, rs1: rd, rs2 }),
+                    }
+                }
+                C_SWSP_FUNCT3 => Ok(RiscVInstruction::Sw {
+                    rs1: SP,
+                    rs2: reg_rs2(half),
+                    imm: uimm_css_word(half),
+                }),
+                _ => Err(floating_point_error(half)),
+            },
+            _ => Err(DecodeError::new(
+                half as u32,
+                DecodeErrorKind::UnknownOpcode,
+            )),
+        }
+    }
+
+    /// Encode this instruction into a 16-bit compressed (RV32C) instruction
+    ///
+    /// Returns `None` if the instruction has no compressed form, for example because a
+    /// register is outside of x8-x15 or an immediate does not fit. Decoding the result
+    /// with [`RiscVInstruction::decode_compressed`] always yields this instruction again.
+    ///
+    /// Only RV32C encodings are produced: the RV64I instructions are never compressed,
+    /// and `jal ra` compresses to an encoding that RV64C decodes as `c.addiw`.
+    pub fn encode_compressed(&self) -> Option<u16> {
+        let half = match *self {
+            RiscVInstruction::Addi {
+                rd: ZERO,
+                rs1: ZERO,
+                imm: 0,
+            } => C_NOP,
+            RiscVInstruction::Addi { rd, rs1, imm }
+                if rd == rs1 && fits(imm as i32, -32, 31, 1) =>
+            {
+                encode_ci(C_ADDI_FUNCT3, rd, imm as i32, QUADRANT_1)
+            }
+            RiscVInstruction::Addi { rd, rs1: ZERO, imm } if fits(imm as i32, -32, 31, 1) => {
+                encode_ci(C_LI_FUNCT3, rd, imm as i32, QUADRANT_1)
+            }
+            RiscVInstruction::Addi {
+                rd: SP,
+                rs1: SP,
+                imm,
+            } if imm != 0 && fits(imm as i32, -512, 496, 16) => {
+                let imm = imm as i32;
+                (C_LUI_FUNCT3 << FUNCT3_SHIFT)
+                    | place(imm, 9, 9, 12)
+                    | (full(SP) << 7)
+                    | place(imm, 4, 4, 6)
+                    | place(imm, 6, 6, 5)
+                    | place(imm, 8, 7, 3)
+                    | place(imm, 5, 5, 2)
+                    | QUADRANT_1
+            }
+            RiscVInstruction::Addi { rd, rs1: SP, imm }
+                if is_prime(rd) && imm != 0 && fits(imm as i32, 0, 1020, 4) =>
+            {
+                let imm = imm as i32;
+                (C_ADDI4SPN_FUNCT3 << FUNCT3_SHIFT)
+                    | place(imm, 5, 4, 11)
+                    | place(imm, 9, 6, 7)
+                    | place(imm, 2, 2, 6)
+                    | place(imm, 3, 3, 5)
+                    | (prime(rd) << 2)
+                    | QUADRANT_0
+            }
+            RiscVInstruction::Lui { rd, imm }
+                if rd != SP && imm != 0 && fits(imm, -0x20000, 0x1f000, 0x1000) =>
+            {
+                encode_ci(C_LUI_FUNCT3, rd, imm >> 12, QUADRANT_1)
+            }
+            RiscVInstruction::Srli { rd, rs1, imm }
+                if rd == rs1 && is_prime(rd) && fits(imm as i32, 0, 31, 1) =>
+            {
+                encode_misc_alu(C_SRLI_FUNCT2, rd, imm as i32)
+            }
+            RiscVInstruction::Srai { rd, rs1, imm }
+                if rd == rs1 && is_prime(rd) && fits(imm as i32, 0, 31, 1) =>
+            {
+                encode_misc_alu(C_SRAI_FUNCT2, rd, imm as i32)
+            }
+            RiscVInstruction::Andi { rd, rs1, imm }
+                if rd == rs1 && is_prime(rd) && fits(imm as i32, -32, 31, 1) =>
+            {
+                encode_misc_alu(C_ANDI_FUNCT2, rd, imm as i32)
+            }
+            RiscVInstruction::Sub { rd, rs1, rs2 }
+                if rd == rs1 && is_prime(rd) && is_prime(rs2) =>
+            {
+                encode_arith(C_SUB_FUNCT2, rd, rs2)
+            }
+            RiscVInstruction::Xor { rd, rs1, rs2 }
+                if rd == rs1 && is_prime(rd) && is_prime(rs2) =>
+            {
+                encode_arith(C_XOR_FUNCT2, rd, rs2)
+            }
+            RiscVInstruction::Or { rd, rs1, rs2 } if rd == rs1 && is_prime(rd) && is_prime(rs2) => {
+                encode_arith(C_OR_FUNCT2, rd, rs2)
+            }
+            RiscVInstruction::And { rd, rs1, rs2 }
+                if rd == rs1 && is_prime(rd) && is_prime(rs2) =>
+            {
+                encode_arith(C_AND_FUNCT2, rd, rs2)
+            }
+            RiscVInstruction::Slli { rd, rs1, imm } if rd == rs1 && fits(imm as i32, 0, 31, 1) => {
+                encode_ci(C_SLLI_FUNCT3, rd, imm as i32, QUADRANT_2)
+            }
+            RiscVInstruction::Add { rd, rs1: ZERO, rs2 } if rs2 != ZERO => encode_cr(0, rd, rs2),
+            RiscVInstruction::Add { rd, rs1, rs2 } if rd == rs1 && rs2 != ZERO => {
+                encode_cr(1, rd, rs2)
+            }
+            RiscVInstruction::Lw { rd, rs1, imm }
+                if is_prime(rd) && is_prime(rs1) && fits(imm as i32, 0, 124, 4) =>
+            {
+                encode_cl(C_LW_FUNCT3, rs1, rd, imm)
+            }
+            RiscVInstruction::Lw { rd, rs1: SP, imm }
+                if rd != ZERO && fits(imm as i32, 0, 252, 4) =>
+            {
+                let imm = imm as i32;
+                (C_LWSP_FUNCT3 << FUNCT3_SHIFT)
+                    | place(imm, 5, 5, 12)
+                    | (full(rd) << 7)
+                    | place(imm, 4, 2, 4)
+                    | place(imm, 7, 6, 2)
+                    | QUADRANT_2
+            }
+            RiscVInstruction::Sw { rs1, rs2, imm }
+                if is_prime(rs1) && is_prime(rs2) && fits(imm as i32, 0, 124, 4) =>
+            {
+                encode_cl(C_SW_FUNCT3, rs1, rs2, imm)
+            }
+            RiscVInstruction::Sw { rs1: SP, rs2, imm } if fits(imm as i32, 0, 252, 4) => {
+                let imm = imm as i32;
+                (C_SWSP_FUNCT3 << FUNCT3_SHIFT)
+                    | place(imm, 5, 2, 9)
+                    | place(imm, 7, 6, 7)
+                    | (full(rs2) << 2)
+                    | QUADRANT_2
+            }
+            RiscVInstruction::Jal { rd: ZERO, imm } if fits(imm, -2048, 2046, 2) => {
+                encode_cj(C_J_FUNCT3, imm)
+            }
+            RiscVInstruction::Jal { rd: RA, imm } if fits(imm, -2048, 2046, 2) => {
+                encode_cj(C_JAL_FUNCT3, imm)
+            }
+            RiscVInstruction::Jalr {
+                rd: ZERO,
+                rs1,
+                imm: 0,
+            } if rs1 != ZERO => encode_cr(0, rs1, ZERO),
+            RiscVInstruction::Jalr {
+                rd: RA,
+                rs1,
+                imm: 0,
+            } if rs1 != ZERO => encode_cr(1, rs1, ZERO),
+            RiscVInstruction::Beq {
+                rs1,
+                rs2: ZERO,
+                imm,
+            } if is_prime(rs1) && fits(imm as i32, -256, 254, 2) => {
+                encode_cb(C_BEQZ_FUNCT3, rs1, imm)
+            }
+            RiscVInstruction::Bne {
+                rs1,
+                rs2: ZERO,
+                imm,
+            } if is_prime(rs1) && fits(imm as i32, -256, 254, 2) => {
+                encode_cb(C_BNEZ_FUNCT3, rs1, imm)
+            }
+            RiscVInstruction::Ebreak => C_EBREAK,
+            _ => return None,
+        };
+
+        Some(half)
+    }
+}
@@ -1,5 +1,12 @@
+use crate::custom::CustomInstructions;
+use crate::extension::Extensions;
+use crate::profile::Profile;
+
 /// The multiplier for the max native code size over the riscv code size.
-const NATIVE_CODE_MULTIPLIER: usize = 4;
+const NATIVE_CODE_MULTIPLIER: usize = 64;
+/// The size of the native code that does not depend on the riscv code, such as the
+/// entry point of translated code.
+const NATIVE_CODE_OVERHEAD: usize = 0x1000;
 
 /// Configuration for a RISC-V engine.
 pub struct Config {
@@ -9,15 +16,50 @@ pub struct Config {
     /// The first argument is a slice of `u32` values from registers `a0-a7`.
     /// The second argument is a user-defined context value.
     /// The function should return a `u32` value to be placed in register `a0`.
+    /// With `Profile::Rv64i`, the handler receives the lower 32 bits of each register,
+    /// and its result is sign-extended into `a0`.
+    /// With `Profile::Rv32e`, which has no `a6` and `a7`, the slice holds `a0-a5`, then
+    /// zero, then the syscall number from `t0`.
     pub syscall: fn(args: &[u32], context: u64) -> u32,
     /// The maximum amount of memory available to an instance, in bytes.
     pub max_instance_memory: u32,
     /// The maximum size of riscv code in bytes.
     pub max_code_size: usize,
+    /// The base integer instruction set of modules.
+    ///
+    /// Code naming a register outside of the profile, such as `x16` with
+    /// `Profile::Rv32e`, is rejected when it is loaded. `Profile::Rv64i` runs 64-bit
+    /// guests, which are called with [`Instance::call64`](crate::Instance::call64).
+    pub profile: Profile,
+    /// The optional extensions that modules may use on top of the M and C extensions
+    /// and the base instruction set of `profile`.
+    ///
+    /// Code using an extension that is not enabled is rejected when it is loaded.
+    /// `Profile::Rv64i` only supports Zifencei; code using any other extension is
+    /// rejected even if it is enabled.
+    /// Enabling Zifencei makes `fence.i` a no-op: code is loaded outside of guest
+    /// memory, so there are never stale translations to invalidate. F and D run on a
+    /// bit-exact software floating-point implementation rather than the host FPU, so
+    /// results, exception flags and NaNs are the same on every host.
+    pub extensions: Extensions,
+    /// A function pointer that supplies the value of the `time` CSR.
+    ///
+    /// The engine will call this function when an instance reads `time` or `timeh`
+    /// with the Zicsr extension enabled. The argument is the same user-defined context
+    /// value passed to `syscall`. The function should return the same value for the
+    /// same context, so that guests stay deterministic.
+    pub time: fn(context: u64) -> u64,
+    /// The custom instructions registered on the engine.
+    ///
+    /// Code in the custom-0 and custom-1 opcode spaces is rejected when it is loaded
+    /// unless its encoding is registered here. Each execution of a custom instruction
+    /// calls its handler with the same user-defined context value passed to `syscall`,
+    /// and charges its gas cost instead of that of an ordinary instruction.
+    pub custom: CustomInstructions,
 }
 
 impl Config {
     pub fn max_native_code_size(&self) -> usize {
-        self.max_code_size * NATIVE_CODE_MULTIPLIER
+        self.max_code_size * NATIVE_CODE_MULTIPLIER + NATIVE_CODE_OVERHEAD
     }
 }
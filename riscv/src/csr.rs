@@ -0,0 +1,95 @@
+use std::fmt;
+
+/// The number of the `fflags` CSR, the accrued floating-point exception flags.
+pub const FFLAGS: u16 = 0x001;
+/// The number of the `frm` CSR, the dynamic floating-point rounding mode.
+pub const FRM: u16 = 0x002;
+/// The number of the `fcsr` CSR, which holds both `frm` and `fflags`.
+pub const FCSR: u16 = 0x003;
+/// The number of the `cycle` CSR, the low half of the cycle counter.
+pub const CYCLE: u16 = 0xc00;
+/// The number of the `time` CSR, the low half of the real-time counter.
+pub const TIME: u16 = 0xc01;
+/// The number of the `instret` CSR, the low half of the retired instruction counter.
+pub const INSTRET: u16 = 0xc02;
+/// The number of the `cycleh` CSR, the high half of the cycle counter.
+pub const CYCLEH: u16 = 0xc80;
+/// The number of the `timeh` CSR, the high half of the real-time counter.
+pub const TIMEH: u16 = 0xc81;
+/// The number of the `instreth` CSR, the high half of the retired instruction counter.
+pub const INSTRETH: u16 = 0xc82;
+
+/// The CSRs a guest can access, and their assembler names.
+const NAMES: [(u16, &str); 9] = [
+    (FFLAGS, "fflags"),
+    (FRM, "frm"),
+    (FCSR, "fcsr"),
+    (CYCLE, "cycle"),
+    (TIME, "time"),
+    (INSTRET, "instret"),
+    (CYCLEH, "cycleh"),
+    (TIMEH, "timeh"),
+    (INSTRETH, "instreth"),
+];
+
+/// Formats a CSR operand by its assembler name, or as a number if it has none.
+pub(crate) struct CsrName(pub(crate) u16);
+
+impl fmt::Display for CsrName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match NAMES.iter().find(|(number, _)| *number == self.0) {
+            Some((_, name)) => write!(f, "{}", name),
+            None => write!(f, "{:#x}", self.0),
+        }
+    }
+}
+
+/// Returns the number of the CSR with the given assembler name.
+pub(crate) fn from_name(name: &str) -> Option<u16> {
+    NAMES
+        .iter()
+        .find(|(_, other)| *other == name)
+        .map(|(number, _)| *number)
+}
+
+/// The values of the counter CSRs seen by an instruction that reads them.
+///
+/// Besides the floating-point CSRs held in [`Fcsr`](crate::Fcsr), the counters are the
+/// only CSRs the VM implements, and every one is derived from deterministic state so
+/// that a guest reads the same values on every run:
+///
+/// - `cycle` is the gas consumed so far. Real cycle counts depend on the host.
+/// - `time` is supplied by the host through `Config::time`.
+/// - `instret` is the number of instructions retired so far.
+///
+/// The counters are read-only. Writing one, or accessing any other CSR, traps with
+/// `Error::InvalidCsr`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Counters {
+    /// The gas consumed before the reading instruction.
+    pub gas_consumed: u64,
+    /// The time returned by the host.
+    pub time: u64,
+    /// The number of instructions retired before the reading instruction.
+    pub instret: u64,
+}
+
+impl Counters {
+    /// Returns the value of the counter CSR `csr`, or `None` if `csr` is not a counter.
+    ///
+    /// The 64-bit counters are read 32 bits at a time, the high half through the CSR
+    /// with the `h` suffix.
+    pub fn read(&self, csr: u16) -> Option<u32> {
+        let value = match csr {
+            CYCLE => self.gas_consumed,
+            TIME => self.time,
+            INSTRET => self.instret,
+            CYCLEH => self.gas_consumed >> 32,
+            TIMEH => self.time >> 32,
+            INSTRETH => self.instret >> 32,
+            _ => return None,
+        };
+
+        Some(value as u32)
+    }
+}
@@ -0,0 +1,254 @@
+use crate::error::Error;
+use crate::instruction::RiscVInstruction;
+use crate::profile::Profile;
+use crate::register::Register;
+use std::fmt;
+
+/// The major opcode of the custom-0 space.
+pub(crate) const CUSTOM_0_OPCODE: u32 = 0x0b;
+/// The major opcode of the custom-1 space.
+pub(crate) const CUSTOM_1_OPCODE: u32 = 0x2b;
+
+/// The largest value of a funct3 field.
+const MAX_FUNCT3: u8 = 0x7;
+/// The largest value of a funct7 field.
+const MAX_FUNCT7: u8 = 0x7f;
+
+/// One of the major opcodes that RISC-V reserves for custom instructions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CustomOpcode {
+    /// The custom-0 opcode, `0x0b`.
+    Custom0,
+    /// The custom-1 opcode, `0x2b`.
+    Custom1,
+}
+
+impl CustomOpcode {
+    /// Returns the 7-bit major opcode.
+    pub const fn opcode(self) -> u32 {
+        match self {
+            CustomOpcode::Custom0 => CUSTOM_0_OPCODE,
+            CustomOpcode::Custom1 => CUSTOM_1_OPCODE,
+        }
+    }
+
+    /// Returns the custom opcode with the given 7-bit major opcode, if it is one.
+    pub const fn new(opcode: u32) -> Option<CustomOpcode> {
+        match opcode {
+            CUSTOM_0_OPCODE => Some(CustomOpcode::Custom0),
+            CUSTOM_1_OPCODE => Some(CustomOpcode::Custom1),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for CustomOpcode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#04x}", self.opcode())
+    }
+}
+
+/// The encoding format of a custom instruction, which decides its operands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CustomForm {
+    /// An R-type instruction with two source registers, selected by `funct7` as well
+    /// as its opcode and funct3.
+    R { funct7: u8 },
+    /// An I-type instruction with one source register and a 12-bit immediate. It
+    /// takes up every funct7 value of its opcode and funct3.
+    I,
+}
+
+/// The operands of a decoded custom instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CustomOperands {
+    /// The operands of an R-type instruction.
+    R {
+        funct7: u8,
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+    /// The operands of an I-type instruction, with the sign-extended immediate.
+    I {
+        rd: Register,
+        rs1: Register,
+        imm: i16,
+    },
+}
+
+impl CustomOperands {
+    /// Returns the encoding format of these operands.
+    pub const fn form(self) -> CustomForm {
+        match self {
+            CustomOperands::R { funct7, .. } => CustomForm::R { funct7 },
+            CustomOperands::I { .. } => CustomForm::I,
+        }
+    }
+}
+
+/// A function implementing a custom instruction on the host.
+///
+/// The handler receives the guest registers, the decoded operands and the same
+/// user-defined context value that is passed to the syscall handler. It may read and
+/// write any register, not only the ones named by its operands.
+pub type CustomHandler =
+    fn(registers: &mut RegisterFile<'_>, operands: CustomOperands, context: u64);
+
+/// A custom instruction implemented by the host.
+#[derive(Debug, Clone, Copy)]
+pub struct CustomInstruction {
+    /// The major opcode of the instruction.
+    pub opcode: CustomOpcode,
+    /// The funct3 field of the instruction, `0` through `7`.
+    pub funct3: u8,
+    /// The encoding format of the instruction.
+    pub form: CustomForm,
+    /// The gas charged each time the instruction executes, in place of the cost of an
+    /// ordinary instruction.
+    pub gas: u32,
+    /// The function that executes the instruction.
+    pub handler: CustomHandler,
+}
+
+impl CustomInstruction {
+    /// Whether this instruction and `other` claim some of the same encodings.
+    fn overlaps(&self, other: &CustomInstruction) -> bool {
+        self.opcode == other.opcode
+            && self.funct3 == other.funct3
+            && match (self.form, other.form) {
+                (CustomForm::R { funct7 }, CustomForm::R { funct7: other }) => funct7 == other,
+                _ => true,
+            }
+    }
+
+    /// Whether a decoded instruction with these fields is an instance of this one.
+    fn matches(&self, opcode: CustomOpcode, funct3: u8, form: CustomForm) -> bool {
+        self.opcode == opcode && self.funct3 == funct3 && self.form == form
+    }
+}
+
+/// The custom instructions registered on an engine.
+#[derive(Debug, Clone, Default)]
+pub struct CustomInstructions(Vec<CustomInstruction>);
+
+impl CustomInstructions {
+    /// The empty set: every custom instruction is rejected when it is decoded.
+    pub const NONE: CustomInstructions = CustomInstructions(Vec::new());
+
+    /// Returns this set with `instruction` registered.
+    ///
+    /// # Errors
+    ///
+    /// - `Error::InvalidCustomInstruction` if `funct3` or `funct7` does not fit in its
+    ///   field, or if the instruction claims an encoding that is already registered.
+    pub fn with(mut self, instruction: CustomInstruction) -> Result<CustomInstructions, Error> {
+        let funct7_fits = match instruction.form {
+            CustomForm::R { funct7 } => funct7 <= MAX_FUNCT7,
+            CustomForm::I => true,
+        };
+
+        if instruction.funct3 > MAX_FUNCT3
+            || !funct7_fits
+            || self.0.iter().any(|other| other.overlaps(&instruction))
+        {
+            return Err(Error::InvalidCustomInstruction {
+                opcode: instruction.opcode,
+                funct3: instruction.funct3,
+            });
+        }
+
+        self.0.push(instruction);
+        Ok(self)
+    }
+
+    /// Looks up the registered instruction that a word with the given opcode, funct3
+    /// and funct7 fields decodes to.
+    pub fn lookup(
+        &self,
+        opcode: CustomOpcode,
+        funct3: u8,
+        funct7: u8,
+    ) -> Option<&CustomInstruction> {
+        self.0.iter().find(|instruction| {
+            instruction.opcode == opcode
+                && instruction.funct3 == funct3
+                && match instruction.form {
+                    CustomForm::R { funct7: registered } => registered == funct7,
+                    CustomForm::I => true,
+                }
+        })
+    }
+
+    /// Returns the registered instruction that `instruction` was decoded from, or
+    /// `None` if it is not a registered custom instruction.
+    pub fn find(&self, instruction: &RiscVInstruction) -> Option<&CustomInstruction> {
+        let RiscVInstruction::Custom {
+            opcode,
+            funct3,
+            operands,
+        } = instruction
+        else {
+            return None;
+        };
+
+        self.0
+            .iter()
+            .find(|custom| custom.matches(*opcode, *funct3, operands.form()))
+    }
+
+    /// Returns an iterator over the registered instructions, in registration order.
+    pub fn iter(&self) -> impl Iterator<Item = &CustomInstruction> {
+        self.0.iter()
+    }
+}
+
+/// The general-purpose registers of a guest, as seen by a [`CustomHandler`].
+///
+/// Values are held as 64 bits for every profile. With a 32-bit profile, reads are
+/// zero-extended and writes keep only the lower 32 bits. `x0` always reads as zero,
+/// as do registers outside of the profile, and writes to them are discarded.
+#[derive(Debug)]
+pub struct RegisterFile<'a> {
+    values: &'a mut [u64; 32],
+    profile: Profile,
+}
+
+impl<'a> RegisterFile<'a> {
+    /// Constructs a new `RegisterFile` over the given register values, indexed by
+    /// register number.
+    pub fn new(values: &'a mut [u64; 32], profile: Profile) -> Self {
+        Self { values, profile }
+    }
+
+    /// Returns the profile of the guest.
+    pub fn profile(&self) -> Profile {
+        self.profile
+    }
+
+    /// Returns the value of `register`.
+    pub fn read(&self, register: Register) -> u64 {
+        if register == Register::ZERO || !self.profile.contains(register) {
+            return 0;
+        }
+
+        self.values[register.number() as usize] & self.mask()
+    }
+
+    /// Sets the value of `register`.
+    pub fn write(&mut self, register: Register, value: u64) {
+        if register == Register::ZERO || !self.profile.contains(register) {
+            return;
+        }
+
+        self.values[register.number() as usize] = value & self.mask();
+    }
+
+    /// The bits of a value that fit in a register of the profile.
+    fn mask(&self) -> u64 {
+        match self.profile.xlen() {
+            64 => u64::MAX,
+            _ => u32::MAX as u64,
+        }
+    }
+}
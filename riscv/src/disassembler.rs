@@ -0,0 +1,185 @@
+use crate::error::DecodeError;
+use crate::extension::Extensions;
+use crate::instruction::RiscVInstruction;
+use crate::register::Syntax;
+use crate::stream::InstructionStream;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// An instruction found by [`disassemble`], along with where it was found.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DisassembledInstruction {
+    /// The byte offset of the instruction from the start of the buffer.
+    pub offset: usize,
+    /// The encoded length of the instruction in bytes: 2 or 4, or fewer for a final
+    /// truncated instruction.
+    pub length: usize,
+    /// The little-endian bytes of the instruction as they appear in the buffer.
+    pub raw: u32,
+    /// The decoded instruction, or why it could not be decoded. Compressed
+    /// instructions are expanded to their 32-bit equivalent.
+    pub instruction: Result<RiscVInstruction, DecodeError>,
+}
+
+impl DisassembledInstruction {
+    /// Returns the byte offset of the branch or `jal` target, if it lies within `0..len`.
+    fn target(&self, len: usize) -> Option<usize> {
+        let instruction = self.instruction.as_ref().ok()?;
+        let target = instruction.branch_target(self.offset as u32)? as usize;
+        (target < len).then_some(target)
+    }
+}
+
+/// A code buffer disassembled by [`disassemble`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Disassembly {
+    /// Every instruction in the buffer, in order, including those that could not
+    /// be decoded.
+    pub instructions: Vec<DisassembledInstruction>,
+    /// The synthesized label of every branch and jump target, keyed by offset.
+    ///
+    /// Labels are named `L0`, `L1`, ... in order of their offset. Only targets that
+    /// start an instruction within the buffer are labelled.
+    pub labels: BTreeMap<usize, String>,
+}
+
+/// Disassembles a little-endian buffer of mixed 16-bit and 32-bit instructions.
+///
+/// The buffer has the same layout as the one passed to `Module::set_riscv_code`.
+/// Every optional extension is decoded. Decoding never stops early: a word that
+/// cannot be decoded is kept along with its error, and decoding resumes at the
+/// next parcel.
+pub fn disassemble(code: &[u8]) -> Disassembly {
+    let instructions: Vec<_> = InstructionStream::with_extensions(code, Extensions::ALL)
+        .map(|decoded| {
+            let mut bytes = [0; 4];
+            bytes[..decoded.length]
+                .copy_from_slice(&code[decoded.offset..decoded.offset + decoded.length]);
+
+            DisassembledInstruction {
+                offset: decoded.offset,
+                length: decoded.length,
+                raw: u32::from_le_bytes(bytes),
+                instruction: decoded.instruction,
+            }
+        })
+        .collect();
+
+    let mut targets: Vec<_> = instructions
+        .iter()
+        .filter_map(|instruction| instruction.target(code.len()))
+        .filter(|target| {
+            instructions
+                .binary_search_by_key(target, |instruction| instruction.offset)
+                .is_ok()
+        })
+        .collect();
+    targets.sort_unstable();
+    targets.dedup();
+
+    let labels = targets
+        .into_iter()
+        .enumerate()
+        .map(|(index, offset)| (offset, format!("L{}", index)))
+        .collect();
+
+    Disassembly {
+        instructions,
+        labels,
+    }
+}
+
+impl Disassembly {
+    /// Returns the label synthesized for the instruction at `offset`, if any.
+    pub fn label(&self, offset: usize) -> Option<&str> {
+        self.labels.get(&offset).map(String::as_str)
+    }
+
+    /// Returns a value that formats the listing with registers named in `syntax`.
+    pub fn display(&self, syntax: Syntax) -> DisassemblyDisplay<'_> {
+        DisassemblyDisplay {
+            disassembly: self,
+            syntax,
+        }
+    }
+}
+
+/// Formats an `objdump`-style listing of a [`Disassembly`].
+///
+/// Created by [`Disassembly::display`]. Each line holds the offset in hex, the raw
+/// bytes and the instruction, and every label is printed on a line of its own before
+/// the instruction it names:
+///
+/// ```text
+/// L0:
+///        0:  fff50513  addi a0, a0, -1
+///        4:  fe051ee3  bne a0, zero, -4 <L0>
+///        8:  0000      .half 0x0000  # reserved compressed encoding
+/// ```
+///
+/// Words that cannot be decoded are printed as data directives followed by the
+/// reason, so they stand out and the listing stays valid assembler input.
+pub struct DisassemblyDisplay<'a> {
+    disassembly: &'a Disassembly,
+    syntax: Syntax,
+}
+
+impl fmt::Display for DisassemblyDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let len = self
+            .disassembly
+            .instructions
+            .last()
+            .map_or(0, |last| last.offset + last.length);
+
+        for line in &self.disassembly.instructions {
+            if let Some(label) = self.disassembly.label(line.offset) {
+                writeln!(f, "{}:", label)?;
+            }
+
+            // Two hex digits per byte, padded to the width of a full word.
+            let digits = line.length * 2;
+            write!(
+                f,
+                "{:>8x}:  {:<8}  ",
+                line.offset,
+                format!("{:0digits$x}", line.raw)
+            )?;
+
+            match &line.instruction {
+                Ok(instruction) => {
+                    write!(f, "{}", instruction.display(self.syntax))?;
+                    let label = line
+                        .target(len)
+                        .and_then(|target| self.disassembly.label(target));
+                    if let Some(label) = label {
+                        write!(f, " <{}>", label)?;
+                    }
+                }
+                Err(error) => {
+                    match line.length {
+                        4 => write!(f, ".word {:#010x}", line.raw)?,
+                        2 => write!(f, ".half {:#06x}", line.raw)?,
+                        _ => {
+                            let bytes = &line.raw.to_le_bytes()[..line.length];
+                            let bytes: Vec<_> =
+                                bytes.iter().map(|byte| format!("{:#04x}", byte)).collect();
+                            write!(f, ".byte {}", bytes.join(", "))?;
+                        }
+                    }
+                    write!(f, "  # {}", error.kind)?;
+                }
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Disassembly {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display(Syntax::Numeric).fmt(f)
+    }
+}
@@ -1,18 +1,49 @@
+use crate::custom::CustomOpcode;
+use crate::extension::Extension;
+use crate::profile::Profile;
+use crate::register::Register;
+
 /// Error type for RISC-V virtual machine operations.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum Error {
+    /// An `ebreak` instruction was executed at `pc`.
+    Breakpoint { pc: u64 },
     /// The VM failed to clear the instruction cache.
     ClearCacheFailed,
+    /// A load, store or atomic instruction accessed bytes outside of the memory of
+    /// the instance. `address` is the first byte of the access.
+    InvalidAddress { address: u64 },
     /// The code is too large.
     InvalidCodeSize,
+    /// An instruction accessed a CSR that does not exist, or wrote a read-only CSR.
+    InvalidCsr { csr: u16 },
+    /// A custom instruction has a funct3 or funct7 field that does not fit, or claims
+    /// an encoding that is already registered.
+    InvalidCustomInstruction { opcode: CustomOpcode, funct3: u8 },
     /// The engine of the module and memory are not the same.
     InvalidEngine,
+    /// An immediate value does not fit in its instruction encoding.
+    InvalidImmediate,
     /// The VM encountered an instruction that is not valid or not supported.
-    InvalidInstruction,
+    ///
+    /// `offset` is the byte offset of the instruction within the code.
+    InvalidInstruction { offset: usize, error: DecodeError },
+    /// Control transferred to `target`, where no instruction of the code starts.
+    InvalidJump { target: u64 },
+    /// An instance was called with the calling convention of a different profile, such
+    /// as `Instance::call` on an RV64I engine.
+    InvalidProfile { profile: Profile },
+    /// A register number is outside of the register file.
+    InvalidRegister,
+    /// A floating-point instruction rounded with the dynamic rounding mode while the
+    /// `frm` CSR held a reserved value.
+    InvalidRoundingMode { frm: u8 },
     /// The VM failed to allocate memory.
     MemoryAllocationFailed,
     /// The VM failed to change memory permissions.
     MemoryProtectionFailed,
+    /// An atomic instruction accessed an address that is not a multiple of 4.
+    MisalignedAtomic { address: u64 },
     /// The VM ran out of gas.
     OutOfGas,
 }
@@ -20,15 +51,173 @@ pub enum Error {
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            Error::Breakpoint { pc } => write!(f, "breakpoint at {:#x}", pc),
             Error::ClearCacheFailed => write!(f, "clear cache failed"),
+            Error::InvalidAddress { address } => {
+                write!(f, "invalid memory access at {:#x}", address)
+            }
             Error::InvalidCodeSize => write!(f, "invalid code size"),
+            Error::InvalidCsr { csr } => write!(f, "invalid access to csr {:#x}", csr),
+            Error::InvalidCustomInstruction { opcode, funct3 } => {
+                write!(f, "invalid custom instruction {} funct3 {}", opcode, funct3)
+            }
             Error::InvalidEngine => write!(f, "invalid engine"),
-            Error::InvalidInstruction => write!(f, "invalid or unsupported instruction"),
+            Error::InvalidImmediate => write!(f, "invalid immediate"),
+            Error::InvalidInstruction { offset, error } => {
+                write!(f, "invalid instruction at offset {:#x}: {}", offset, error)
+            }
+            Error::InvalidJump { target } => write!(f, "invalid jump to {:#x}", target),
+            Error::InvalidProfile { profile } => {
+                write!(f, "invalid calling convention for {}", profile)
+            }
+            Error::InvalidRegister => write!(f, "invalid register"),
+            Error::InvalidRoundingMode { frm } => write!(f, "invalid rounding mode {}", frm),
             Error::MemoryAllocationFailed => write!(f, "memory allocation failed"),
             Error::MemoryProtectionFailed => write!(f, "memory protection failed"),
+            Error::MisalignedAtomic { address } => {
+                write!(f, "misaligned atomic access at {:#x}", address)
+            }
             Error::OutOfGas => write!(f, "out of gas"),
         }
     }
 }
 
 impl std::error::Error for Error {}
+
+/// The reason an instruction could not be decoded.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DecodeErrorKind {
+    /// The opcode is not assigned to any instruction.
+    UnknownOpcode,
+    /// The funct3 field is reserved for the opcode.
+    ReservedFunct3,
+    /// The funct7 field is reserved for the opcode and funct3.
+    ReservedFunct7,
+    /// The funct12 field of a SYSTEM instruction names neither `ecall` nor `ebreak`.
+    ReservedFunct12,
+    /// A register field that the instruction requires to be zero is not,
+    /// such as `rd` or `rs1` of `ecall` and `ebreak`.
+    InvalidOperands,
+    /// The compressed encoding is reserved, or is the all-zero illegal instruction.
+    ReservedCompressed,
+    /// The instruction is valid RISC-V but is not implemented by the VM.
+    Unimplemented,
+    /// The instruction uses a custom opcode, but no custom instruction with its
+    /// encoding is registered on the engine.
+    UnregisteredCustom,
+    /// The instruction belongs to an extension the VM does not support.
+    UnsupportedExtension(Extension),
+    /// The instruction names a register outside of the register file of the profile,
+    /// such as `x16` in RV32E.
+    UnsupportedRegister {
+        register: Register,
+        profile: Profile,
+    },
+    /// The code ends in the middle of the instruction.
+    Truncated,
+}
+
+/// Error type for instructions that cannot be decoded.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct DecodeError {
+    /// The raw instruction word, or the 16-bit parcel of a compressed instruction.
+    ///
+    /// For truncated instructions this holds the bytes that were available.
+    pub word: u32,
+    /// Why the word could not be decoded.
+    pub kind: DecodeErrorKind,
+}
+
+impl DecodeError {
+    pub(crate) fn new(word: u32, kind: DecodeErrorKind) -> Self {
+        Self { word, kind }
+    }
+}
+
+impl std::fmt::Display for DecodeErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecodeErrorKind::UnknownOpcode => write!(f, "unknown opcode"),
+            DecodeErrorKind::ReservedFunct3 => write!(f, "reserved funct3"),
+            DecodeErrorKind::ReservedFunct7 => write!(f, "reserved funct7"),
+            DecodeErrorKind::ReservedFunct12 => write!(f, "reserved funct12"),
+            DecodeErrorKind::InvalidOperands => write!(f, "invalid operands"),
+            DecodeErrorKind::ReservedCompressed => write!(f, "reserved compressed encoding"),
+            DecodeErrorKind::Unimplemented => write!(f, "unimplemented instruction"),
+            DecodeErrorKind::UnregisteredCustom => write!(f, "unregistered custom instruction"),
+            DecodeErrorKind::UnsupportedExtension(extension) => {
+                write!(f, "unsupported extension {}", extension)
+            }
+            DecodeErrorKind::UnsupportedRegister { register, profile } => {
+                write!(f, "register {} is not available in {}", register, profile)
+            }
+            DecodeErrorKind::Truncated => write!(f, "truncated instruction"),
+        }
+    }
+}
+
+impl std::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (0x{:08x})", self.kind, self.word)
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// The reason a line of assembly could not be assembled.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum AssembleErrorKind {
+    /// The mnemonic names neither an instruction nor a pseudo-instruction.
+    UnknownMnemonic,
+    /// The directive is not one of `.word`, `.half` or `.byte`.
+    UnknownDirective,
+    /// The operand is not a numeric or ABI register name.
+    UnknownRegister,
+    /// The operand refers to a label that is not defined anywhere.
+    UnknownLabel,
+    /// The label is defined more than once.
+    DuplicateLabel,
+    /// The label is not a valid identifier.
+    InvalidLabel,
+    /// The operand is not a number.
+    InvalidNumber,
+    /// The immediate or offset does not fit its instruction, or is misaligned.
+    InvalidImmediate,
+    /// The instruction has the wrong number or form of operands.
+    InvalidOperands,
+}
+
+/// Error type for assembly source that cannot be assembled.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct AssembleError {
+    /// The line of the error, starting from 1.
+    pub line: usize,
+    /// The column of the error within the line, starting from 1.
+    pub column: usize,
+    /// What is wrong at that position.
+    pub kind: AssembleErrorKind,
+}
+
+impl std::fmt::Display for AssembleErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AssembleErrorKind::UnknownMnemonic => write!(f, "unknown mnemonic"),
+            AssembleErrorKind::UnknownDirective => write!(f, "unknown directive"),
+            AssembleErrorKind::UnknownRegister => write!(f, "unknown register"),
+            AssembleErrorKind::UnknownLabel => write!(f, "unknown label"),
+            AssembleErrorKind::DuplicateLabel => write!(f, "duplicate label"),
+            AssembleErrorKind::InvalidLabel => write!(f, "invalid label"),
+            AssembleErrorKind::InvalidNumber => write!(f, "invalid number"),
+            AssembleErrorKind::InvalidImmediate => write!(f, "immediate out of range"),
+            AssembleErrorKind::InvalidOperands => write!(f, "invalid operands"),
+        }
+    }
+}
+
+impl std::fmt::Display for AssembleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.kind)
+    }
+}
+
+impl std::error::Error for AssembleError {}
@@ -0,0 +1,105 @@
+use std::fmt;
+
+/// A RISC-V standard extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Extension {
+    /// Atomic instructions.
+    A,
+    /// Double-precision floating point.
+    D,
+    /// Single-precision floating point.
+    F,
+    /// Vector instructions.
+    V,
+    /// Address generation bit-manipulation instructions.
+    Zba,
+    /// Basic bit-manipulation instructions.
+    Zbb,
+    /// Single-bit instructions.
+    Zbs,
+    /// Control and status register instructions.
+    Zicsr,
+    /// Instruction-fetch fence.
+    ///
+    /// Guests cannot write their own code, so `fence.i` is a no-op when enabled.
+    Zifencei,
+    /// NIST hash function instructions (SHA-256 and SHA-512).
+    Zknh,
+}
+
+impl Extension {
+    /// The extensions that are optional in the VM and can be enabled in a [`Extensions`] set.
+    pub const OPTIONAL: [Extension; 9] = [
+        Extension::A,
+        Extension::D,
+        Extension::F,
+        Extension::Zba,
+        Extension::Zbb,
+        Extension::Zbs,
+        Extension::Zicsr,
+        Extension::Zifencei,
+        Extension::Zknh,
+    ];
+
+    const fn bit(self) -> u32 {
+        1 << self as u32
+    }
+}
+
+impl fmt::Display for Extension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Extension::A => write!(f, "A"),
+            Extension::D => write!(f, "D"),
+            Extension::F => write!(f, "F"),
+            Extension::V => write!(f, "V"),
+            Extension::Zba => write!(f, "Zba"),
+            Extension::Zbb => write!(f, "Zbb"),
+            Extension::Zbs => write!(f, "Zbs"),
+            Extension::Zicsr => write!(f, "Zicsr"),
+            Extension::Zifencei => write!(f, "Zifencei"),
+            Extension::Zknh => write!(f, "Zknh"),
+        }
+    }
+}
+
+/// A set of extensions, such as the optional extensions an engine accepts on top of
+/// the base RV32IMC instruction set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Extensions(u32);
+
+impl Extensions {
+    /// The empty set: only the base RV32IMC instruction set.
+    pub const NONE: Extensions = Extensions(0);
+
+    /// Every optional extension the VM implements.
+    pub const ALL: Extensions = Extensions::from_slice(&Extension::OPTIONAL);
+
+    /// Constructs the set holding the given extensions.
+    pub const fn from_slice(extensions: &[Extension]) -> Extensions {
+        let mut set = Extensions::NONE;
+        let mut index = 0;
+        while index < extensions.len() {
+            set = set.with(extensions[index]);
+            index += 1;
+        }
+        set
+    }
+
+    /// Returns this set with `extension` added.
+    pub const fn with(self, extension: Extension) -> Extensions {
+        Extensions(self.0 | extension.bit())
+    }
+
+    /// Whether the set holds `extension`.
+    pub const fn contains(self, extension: Extension) -> bool {
+        self.0 & extension.bit() != 0
+    }
+}
+
+impl FromIterator<Extension> for Extensions {
+    fn from_iter<I: IntoIterator<Item = Extension>>(iter: I) -> Self {
+        iter.into_iter()
+            .fold(Extensions::NONE, |set, extension| set.with(extension))
+    }
+}
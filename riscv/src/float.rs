@@ -0,0 +1,886 @@
+use crate::csr::{FCSR, FFLAGS, FRM};
+use crate::error::Error;
+use std::cmp::Ordering;
+use std::fmt;
+
+/// A rounding mode of the F and D extensions, held in the `rm` field of an instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RoundingMode {
+    /// Round to nearest, ties to even (`rne`).
+    NearestEven,
+    /// Round towards zero (`rtz`).
+    TowardZero,
+    /// Round down, towards negative infinity (`rdn`).
+    Down,
+    /// Round up, towards positive infinity (`rup`).
+    Up,
+    /// Round to nearest, ties to max magnitude (`rmm`).
+    NearestMaxMagnitude,
+    /// Round with the mode held in the `frm` CSR (`dyn`).
+    Dynamic,
+}
+
+/// The rounding modes and their assembler names.
+const ROUNDING_MODE_NAMES: [(RoundingMode, &str); 6] = [
+    (RoundingMode::NearestEven, "rne"),
+    (RoundingMode::TowardZero, "rtz"),
+    (RoundingMode::Down, "rdn"),
+    (RoundingMode::Up, "rup"),
+    (RoundingMode::NearestMaxMagnitude, "rmm"),
+    (RoundingMode::Dynamic, "dyn"),
+];
+
+impl RoundingMode {
+    /// Decodes a 3-bit `rm` field, or returns `None` for the reserved values 5 and 6.
+    pub(crate) const fn from_field(field: u32) -> Option<RoundingMode> {
+        match field {
+            0 => Some(RoundingMode::NearestEven),
+            1 => Some(RoundingMode::TowardZero),
+            2 => Some(RoundingMode::Down),
+            3 => Some(RoundingMode::Up),
+            4 => Some(RoundingMode::NearestMaxMagnitude),
+            7 => Some(RoundingMode::Dynamic),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of the `rm` field that encodes this rounding mode.
+    pub(crate) const fn field(self) -> u32 {
+        match self {
+            RoundingMode::NearestEven => 0,
+            RoundingMode::TowardZero => 1,
+            RoundingMode::Down => 2,
+            RoundingMode::Up => 3,
+            RoundingMode::NearestMaxMagnitude => 4,
+            RoundingMode::Dynamic => 7,
+        }
+    }
+
+    /// Looks up a rounding mode by its assembler name, such as `rtz`.
+    pub(crate) fn from_name(name: &str) -> Option<RoundingMode> {
+        ROUNDING_MODE_NAMES
+            .iter()
+            .find(|(_, other)| *other == name)
+            .map(|(mode, _)| *mode)
+    }
+}
+
+impl fmt::Display for RoundingMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (_, name) = ROUNDING_MODE_NAMES
+            .iter()
+            .find(|(mode, _)| mode == self)
+            .expect("every rounding mode has a name");
+        f.write_str(name)
+    }
+}
+
+/// A set of the floating-point exception flags accrued in the `fflags` CSR.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ExceptionFlags(u8);
+
+impl ExceptionFlags {
+    /// The empty set.
+    pub const NONE: ExceptionFlags = ExceptionFlags(0);
+    /// Inexact (`NX`): the result was rounded.
+    pub const INEXACT: ExceptionFlags = ExceptionFlags(0x01);
+    /// Underflow (`UF`): the result is tiny after rounding and inexact.
+    pub const UNDERFLOW: ExceptionFlags = ExceptionFlags(0x02);
+    /// Overflow (`OF`): the rounded result is too large for the format.
+    pub const OVERFLOW: ExceptionFlags = ExceptionFlags(0x04);
+    /// Divide by zero (`DZ`): a finite non-zero value was divided by zero.
+    pub const DIVIDE_BY_ZERO: ExceptionFlags = ExceptionFlags(0x08);
+    /// Invalid operation (`NV`), such as `0 * inf` or an operand that is a signaling NaN.
+    pub const INVALID: ExceptionFlags = ExceptionFlags(0x10);
+
+    const MASK: u8 = 0x1f;
+
+    /// Constructs the set from the bits of `fflags`, ignoring any higher bits.
+    pub const fn from_bits(bits: u8) -> ExceptionFlags {
+        ExceptionFlags(bits & ExceptionFlags::MASK)
+    }
+
+    /// Returns the bits of `fflags` that hold this set.
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+
+    /// Returns this set with every flag in `other` added.
+    pub const fn with(self, other: ExceptionFlags) -> ExceptionFlags {
+        ExceptionFlags(self.0 | other.0)
+    }
+
+    /// Whether the set holds every flag in `other`.
+    pub const fn contains(self, other: ExceptionFlags) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    fn raise(&mut self, other: ExceptionFlags) {
+        *self = self.with(other);
+    }
+}
+
+/// The floating-point control and status register of the F and D extensions.
+///
+/// `fcsr` holds the dynamic rounding mode in `frm` and the accrued exception flags in
+/// `fflags`, and both fields can also be accessed as CSRs of their own. A guest's
+/// `fcsr` starts out zeroed: rounding to nearest, with no flags raised.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Fcsr {
+    /// The dynamic rounding mode, as the 3-bit value of an `rm` field.
+    ///
+    /// Any value can be written, but an instruction that rounds with `dyn` while
+    /// `frm` holds 5, 6 or 7 traps with `Error::InvalidRoundingMode`.
+    pub frm: u8,
+    /// The exception flags raised since the guest last cleared them.
+    pub fflags: ExceptionFlags,
+}
+
+impl Fcsr {
+    const FRM_MASK: u32 = 0x7;
+    const FRM_SHIFT: u32 = 5;
+
+    /// Returns the value of `fflags`, `frm` or `fcsr`, or `None` if `csr` is none of them.
+    pub fn read(&self, csr: u16) -> Option<u32> {
+        let fflags = self.fflags.bits() as u32;
+        let frm = self.frm as u32;
+
+        match csr {
+            FFLAGS => Some(fflags),
+            FRM => Some(frm),
+            FCSR => Some((frm << Fcsr::FRM_SHIFT) | fflags),
+            _ => None,
+        }
+    }
+
+    /// Writes `fflags`, `frm` or `fcsr`, ignoring the bits of `value` outside of the CSR.
+    ///
+    /// Returns `false` without writing anything if `csr` is none of them.
+    pub fn write(&mut self, csr: u16, value: u32) -> bool {
+        match csr {
+            FFLAGS => self.fflags = ExceptionFlags::from_bits(value as u8),
+            FRM => self.frm = (value & Fcsr::FRM_MASK) as u8,
+            FCSR => {
+                self.fflags = ExceptionFlags::from_bits(value as u8);
+                self.frm = ((value >> Fcsr::FRM_SHIFT) & Fcsr::FRM_MASK) as u8;
+            }
+            _ => return false,
+        }
+
+        true
+    }
+
+    /// Resolves the rounding mode of an instruction, replacing `dyn` with `frm`.
+    pub(crate) fn rounding(&self, rm: RoundingMode) -> Result<Round, Error> {
+        let rm = match rm {
+            RoundingMode::Dynamic => RoundingMode::from_field(self.frm as u32),
+            rm => Some(rm),
+        };
+
+        match rm {
+            Some(RoundingMode::NearestEven) => Ok(Round::NearestEven),
+            Some(RoundingMode::TowardZero) => Ok(Round::TowardZero),
+            Some(RoundingMode::Down) => Ok(Round::Down),
+            Some(RoundingMode::Up) => Ok(Round::Up),
+            Some(RoundingMode::NearestMaxMagnitude) => Ok(Round::NearestMaxMagnitude),
+            Some(RoundingMode::Dynamic) | None => Err(Error::InvalidRoundingMode { frm: self.frm }),
+        }
+    }
+}
+
+/// A rounding mode with `dyn` resolved, as used by the arithmetic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Round {
+    NearestEven,
+    TowardZero,
+    Down,
+    Up,
+    NearestMaxMagnitude,
+}
+
+/// Returns the single-precision value held in a 64-bit floating-point register.
+///
+/// Only a properly NaN-boxed value, whose upper 32 bits are all ones, is a valid
+/// single-precision operand. Any other register value reads as the canonical NaN.
+pub(crate) fn unbox(register: u64) -> u64 {
+    match register >> 32 {
+        0xffff_ffff => register & 0xffff_ffff,
+        _ => SINGLE.canonical_nan(),
+    }
+}
+
+/// Returns the register value that holds a single-precision value, NaN-boxed by
+/// setting the upper 32 bits.
+pub(crate) fn nan_box(single: u64) -> u64 {
+    single | 0xffff_ffff_0000_0000
+}
+
+/// A binary interchange format, described by the widths of its exponent and fraction.
+///
+/// Every operation works on the bits of a value held in the low bits of a `u64`, and
+/// computes the exact result with integer arithmetic before rounding it once, so the
+/// results never depend on the host's floating-point unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Format {
+    exponent_bits: u32,
+    fraction_bits: u32,
+}
+
+/// The single-precision format of the F extension.
+pub(crate) const SINGLE: Format = Format {
+    exponent_bits: 8,
+    fraction_bits: 23,
+};
+
+/// The double-precision format of the D extension.
+pub(crate) const DOUBLE: Format = Format {
+    exponent_bits: 11,
+    fraction_bits: 52,
+};
+
+/// An unpacked value. A finite value is `significand * 2^exponent`, with a non-zero
+/// significand.
+#[derive(Debug, Clone, Copy)]
+enum Class {
+    Zero,
+    Finite { exponent: i32, significand: u128 },
+    Infinity,
+    Nan { signaling: bool },
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Value {
+    sign: bool,
+    class: Class,
+}
+
+impl Value {
+    fn is_nan(self) -> bool {
+        matches!(self.class, Class::Nan { .. })
+    }
+
+    fn is_signaling(self) -> bool {
+        matches!(self.class, Class::Nan { signaling: true })
+    }
+}
+
+/// Shifts `significand` left so that its leading bit is bit 125, leaving room for the
+/// carry of an addition and a bit above it.
+fn normalize(exponent: i32, significand: u128) -> (i32, u128) {
+    let shift = significand.leading_zeros() as i32 - 2;
+    (exponent - shift, significand << shift)
+}
+
+/// Shifts `value` right, setting the lowest bit if any bit that was shifted out was set.
+fn shift_right_jam(value: u128, shift: u32) -> u128 {
+    match shift {
+        0 => value,
+        1..128 => (value >> shift) | ((value & ((1 << shift) - 1)) != 0) as u128,
+        _ => (value != 0) as u128,
+    }
+}
+
+/// Drops the low `shift` bits of `significand` and rounds the rest to an integer.
+///
+/// Returns the rounded integer and whether any of the dropped bits was set. A negative
+/// `shift` shifts left, which is always exact.
+fn shift_round(significand: u128, shift: i32, sign: bool, round: Round) -> (u128, bool) {
+    if shift <= 0 {
+        return (significand << -shift, false);
+    }
+
+    // Compares the dropped bits with half of the last kept bit.
+    let (kept, rest, half) = match shift {
+        1..128 => {
+            let rest = significand & ((1 << shift) - 1);
+            (significand >> shift, rest, rest.cmp(&(1 << (shift - 1))))
+        }
+        128 => (0, significand, significand.cmp(&(1 << 127))),
+        _ => (0, significand, Ordering::Less),
+    };
+
+    let inexact = rest != 0;
+    let increment = match round {
+        Round::NearestEven => {
+            half == Ordering::Greater || (half == Ordering::Equal && kept & 1 == 1)
+        }
+        Round::NearestMaxMagnitude => half != Ordering::Less,
+        Round::TowardZero => false,
+        Round::Down => inexact && sign,
+        Round::Up => inexact && !sign,
+    };
+
+    (kept + increment as u128, inexact)
+}
+
+/// Returns the integer square root of `value` and whether it is inexact.
+fn isqrt(value: u128) -> (u128, bool) {
+    let mut rest = value;
+    let mut root = 0;
+    let mut bit = 1 << 126;
+    while bit > rest {
+        bit >>= 2;
+    }
+
+    while bit != 0 {
+        if rest >= root + bit {
+            rest -= root + bit;
+            root = (root >> 1) + bit;
+        } else {
+            root >>= 1;
+        }
+        bit >>= 2;
+    }
+
+    (root, rest != 0)
+}
+
+impl Format {
+    fn bias(self) -> i32 {
+        (1 << (self.exponent_bits - 1)) - 1
+    }
+
+    /// The number of significand bits, including the implicit leading bit.
+    fn precision(self) -> i32 {
+        self.fraction_bits as i32 + 1
+    }
+
+    /// The biased exponent of infinities and NaNs.
+    fn max_exponent(self) -> u64 {
+        (1 << self.exponent_bits) - 1
+    }
+
+    /// The exponent of the lowest significand bit of a subnormal value.
+    fn min_exponent(self) -> i32 {
+        1 - self.bias() - self.fraction_bits as i32
+    }
+
+    fn fraction_mask(self) -> u64 {
+        (1 << self.fraction_bits) - 1
+    }
+
+    pub(crate) fn sign_bit(self) -> u64 {
+        1 << (self.exponent_bits + self.fraction_bits)
+    }
+
+    fn signed(self, sign: bool) -> u64 {
+        if sign { self.sign_bit() } else { 0 }
+    }
+
+    /// The canonical NaN: positive, quiet, and with an otherwise empty fraction.
+    pub(crate) fn canonical_nan(self) -> u64 {
+        (self.max_exponent() << self.fraction_bits) | (1 << (self.fraction_bits - 1))
+    }
+
+    fn zero(self, sign: bool) -> u64 {
+        self.signed(sign)
+    }
+
+    fn infinity(self, sign: bool) -> u64 {
+        self.signed(sign) | (self.max_exponent() << self.fraction_bits)
+    }
+
+    /// The largest finite value.
+    fn max_finite(self, sign: bool) -> u64 {
+        self.infinity(sign) - 1
+    }
+
+    fn unpack(self, bits: u64) -> Value {
+        let sign = bits & self.sign_bit() != 0;
+        let biased = (bits >> self.fraction_bits) & self.max_exponent();
+        let fraction = bits & self.fraction_mask();
+
+        let class = match biased {
+            0 if fraction == 0 => Class::Zero,
+            0 => Class::Finite {
+                exponent: self.min_exponent(),
+                significand: fraction as u128,
+            },
+            _ if biased == self.max_exponent() && fraction == 0 => Class::Infinity,
+            _ if biased == self.max_exponent() => Class::Nan {
+                signaling: fraction >> (self.fraction_bits - 1) == 0,
+            },
+            _ => Class::Finite {
+                exponent: self.min_exponent() + biased as i32 - 1,
+                significand: (fraction | (1 << self.fraction_bits)) as u128,
+            },
+        };
+
+        Value { sign, class }
+    }
+
+    /// Rounds `significand * 2^exponent` to the format, raising the exception flags of
+    /// the rounding. `significand` must not be zero.
+    ///
+    /// Tininess is detected after rounding, as RISC-V requires.
+    fn pack(
+        self,
+        sign: bool,
+        exponent: i32,
+        significand: u128,
+        round: Round,
+        flags: &mut ExceptionFlags,
+    ) -> u64 {
+        let precision = self.precision();
+        let length = 128 - significand.leading_zeros() as i32;
+        let top = exponent + length - 1;
+        let min_normal = 1 - self.bias();
+
+        let lsb = (top - (precision - 1)).max(self.min_exponent());
+        let (mut kept, inexact) = shift_round(significand, lsb - exponent, sign, round);
+
+        // A value just below the smallest normal is tiny unless rounding it with an
+        // unbounded exponent range carries it up to the smallest normal.
+        let tiny = match top.cmp(&(min_normal - 1)) {
+            Ordering::Less => true,
+            Ordering::Greater => false,
+            Ordering::Equal => {
+                let shift = top - (precision - 1) - exponent;
+                let (unbounded, _) = shift_round(significand, shift, sign, round);
+                unbounded >> precision == 0
+            }
+        };
+
+        let mut lsb = lsb;
+        if kept >> precision != 0 {
+            kept >>= 1;
+            lsb += 1;
+        }
+
+        if inexact {
+            flags.raise(ExceptionFlags::INEXACT);
+            if tiny {
+                flags.raise(ExceptionFlags::UNDERFLOW);
+            }
+        }
+
+        let biased = match kept >> (precision - 1) {
+            0 => 0,
+            _ => (lsb + precision - 1 + self.bias()) as u64,
+        };
+
+        if biased >= self.max_exponent() {
+            flags.raise(ExceptionFlags::OVERFLOW.with(ExceptionFlags::INEXACT));
+            return match round {
+                Round::NearestEven | Round::NearestMaxMagnitude => self.infinity(sign),
+                Round::TowardZero => self.max_finite(sign),
+                Round::Down if sign => self.infinity(sign),
+                Round::Up if !sign => self.infinity(sign),
+                Round::Down | Round::Up => self.max_finite(sign),
+            };
+        }
+
+        self.signed(sign) | (biased << self.fraction_bits) | (kept as u64 & self.fraction_mask())
+    }
+
+    /// Returns the canonical NaN as the result of an operation on a NaN, raising the
+    /// invalid flag if any operand is a signaling NaN.
+    fn propagate_nan(self, operands: &[Value], flags: &mut ExceptionFlags) -> u64 {
+        if operands.iter().any(|value| value.is_signaling()) {
+            flags.raise(ExceptionFlags::INVALID);
+        }
+        self.canonical_nan()
+    }
+
+    fn invalid(self, flags: &mut ExceptionFlags) -> u64 {
+        flags.raise(ExceptionFlags::INVALID);
+        self.canonical_nan()
+    }
+
+    /// Adds two finite values exactly and rounds the sum once.
+    fn sum(
+        self,
+        (sign_a, exponent_a, significand_a): (bool, i32, u128),
+        (sign_b, exponent_b, significand_b): (bool, i32, u128),
+        round: Round,
+        flags: &mut ExceptionFlags,
+    ) -> u64 {
+        let a = (sign_a, normalize(exponent_a, significand_a));
+        let b = (sign_b, normalize(exponent_b, significand_b));
+        let ((sign_a, (exponent, a)), (sign_b, (exponent_b, b))) = match a.1.0 >= b.1.0 {
+            true => (a, b),
+            false => (b, a),
+        };
+
+        // Both significands hold at least 70 zero bits below the leading bit, so any
+        // bits shifted out of the smaller one only matter as a sticky bit.
+        let b = shift_right_jam(b, (exponent - exponent_b) as u32);
+
+        if sign_a == sign_b {
+            return self.pack(sign_a, exponent, a + b, round, flags);
+        }
+
+        match a.cmp(&b) {
+            Ordering::Greater => self.pack(sign_a, exponent, a - b, round, flags),
+            Ordering::Less => self.pack(sign_b, exponent, b - a, round, flags),
+            Ordering::Equal => self.zero(round == Round::Down),
+        }
+    }
+
+    pub(crate) fn add(self, a: u64, b: u64, round: Round, flags: &mut ExceptionFlags) -> u64 {
+        let (x, y) = (self.unpack(a), self.unpack(b));
+
+        match (x.class, y.class) {
+            (Class::Nan { .. }, _) | (_, Class::Nan { .. }) => self.propagate_nan(&[x, y], flags),
+            (Class::Infinity, Class::Infinity) if x.sign != y.sign => self.invalid(flags),
+            (Class::Infinity, _) => self.infinity(x.sign),
+            (_, Class::Infinity) => self.infinity(y.sign),
+            (Class::Zero, Class::Zero) if x.sign == y.sign => self.zero(x.sign),
+            (Class::Zero, Class::Zero) => self.zero(round == Round::Down),
+            (Class::Zero, _) => b,
+            (_, Class::Zero) => a,
+            (
+                Class::Finite {
+                    exponent: exponent_a,
+                    significand: significand_a,
+                },
+                Class::Finite {
+                    exponent: exponent_b,
+                    significand: significand_b,
+                },
+            ) => self.sum(
+                (x.sign, exponent_a, significand_a),
+                (y.sign, exponent_b, significand_b),
+                round,
+                flags,
+            ),
+        }
+    }
+
+    pub(crate) fn sub(self, a: u64, b: u64, round: Round, flags: &mut ExceptionFlags) -> u64 {
+        self.add(a, b ^ self.sign_bit(), round, flags)
+    }
+
+    pub(crate) fn mul(self, a: u64, b: u64, round: Round, flags: &mut ExceptionFlags) -> u64 {
+        let (x, y) = (self.unpack(a), self.unpack(b));
+        let sign = x.sign != y.sign;
+
+        match (x.class, y.class) {
+            (Class::Nan { .. }, _) | (_, Class::Nan { .. }) => self.propagate_nan(&[x, y], flags),
+            (Class::Infinity, Class::Zero) | (Class::Zero, Class::Infinity) => self.invalid(flags),
+            (Class::Infinity, _) | (_, Class::Infinity) => self.infinity(sign),
+            (Class::Zero, _) | (_, Class::Zero) => self.zero(sign),
+            (
+                Class::Finite {
+                    exponent: exponent_a,
+                    significand: significand_a,
+                },
+                Class::Finite {
+                    exponent: exponent_b,
+                    significand: significand_b,
+                },
+            ) => self.pack(
+                sign,
+                exponent_a + exponent_b,
+                significand_a * significand_b,
+                round,
+                flags,
+            ),
+        }
+    }
+
+    pub(crate) fn div(self, a: u64, b: u64, round: Round, flags: &mut ExceptionFlags) -> u64 {
+        let (x, y) = (self.unpack(a), self.unpack(b));
+        let sign = x.sign != y.sign;
+
+        match (x.class, y.class) {
+            (Class::Nan { .. }, _) | (_, Class::Nan { .. }) => self.propagate_nan(&[x, y], flags),
+            (Class::Infinity, Class::Infinity) | (Class::Zero, Class::Zero) => self.invalid(flags),
+            (Class::Infinity, _) => self.infinity(sign),
+            (_, Class::Infinity) | (Class::Zero, _) => self.zero(sign),
+            (_, Class::Zero) => {
+                flags.raise(ExceptionFlags::DIVIDE_BY_ZERO);
+                self.infinity(sign)
+            }
+            (
+                Class::Finite {
+                    exponent: exponent_a,
+                    significand: significand_a,
+                },
+                Class::Finite {
+                    exponent: exponent_b,
+                    significand: significand_b,
+                },
+            ) => {
+                // The quotient of a normalized dividend holds at least 72 bits, so the
+                // remainder only matters as a sticky bit below them.
+                let (exponent_a, significand_a) = normalize(exponent_a, significand_a);
+                let quotient = significand_a / significand_b;
+                let inexact = significand_a % significand_b != 0;
+                self.pack(
+                    sign,
+                    exponent_a - exponent_b - 1,
+                    (quotient << 1) | inexact as u128,
+                    round,
+                    flags,
+                )
+            }
+        }
+    }
+
+    pub(crate) fn sqrt(self, a: u64, round: Round, flags: &mut ExceptionFlags) -> u64 {
+        let x = self.unpack(a);
+
+        match x.class {
+            Class::Nan { .. } => self.propagate_nan(&[x], flags),
+            Class::Zero => a,
+            _ if x.sign => self.invalid(flags),
+            Class::Infinity => a,
+            Class::Finite {
+                exponent,
+                significand,
+            } => {
+                // Halving the exponent needs it to be even. The low bits of a
+                // normalized significand are zero, so shifting one out is exact.
+                let (mut exponent, mut significand) = normalize(exponent, significand);
+                if exponent & 1 != 0 {
+                    significand >>= 1;
+                    exponent += 1;
+                }
+
+                let (root, inexact) = isqrt(significand);
+                self.pack(
+                    false,
+                    exponent / 2 - 1,
+                    (root << 1) | inexact as u128,
+                    round,
+                    flags,
+                )
+            }
+        }
+    }
+
+    /// Computes `a * b + c` with a single rounding.
+    pub(crate) fn fused_multiply_add(
+        self,
+        a: u64,
+        b: u64,
+        c: u64,
+        round: Round,
+        flags: &mut ExceptionFlags,
+    ) -> u64 {
+        let (x, y, z) = (self.unpack(a), self.unpack(b), self.unpack(c));
+        let sign = x.sign != y.sign;
+        let infinity_times_zero = matches!(
+            (x.class, y.class),
+            (Class::Infinity, Class::Zero) | (Class::Zero, Class::Infinity)
+        );
+
+        // RISC-V raises invalid for `inf * 0` even when the addend is a quiet NaN.
+        if infinity_times_zero {
+            return self.invalid(flags);
+        }
+
+        match (x.class, y.class, z.class) {
+            (Class::Nan { .. }, _, _) | (_, Class::Nan { .. }, _) | (_, _, Class::Nan { .. }) => {
+                self.propagate_nan(&[x, y, z], flags)
+            }
+            (Class::Infinity, _, Class::Infinity) | (_, Class::Infinity, Class::Infinity)
+                if sign != z.sign =>
+            {
+                self.invalid(flags)
+            }
+            (Class::Infinity, _, _) | (_, Class::Infinity, _) => self.infinity(sign),
+            (_, _, Class::Infinity) => c,
+            (Class::Zero, _, Class::Zero) | (_, Class::Zero, Class::Zero) if sign == z.sign => {
+                self.zero(sign)
+            }
+            (Class::Zero, _, Class::Zero) | (_, Class::Zero, Class::Zero) => {
+                self.zero(round == Round::Down)
+            }
+            (Class::Zero, _, _) | (_, Class::Zero, _) => c,
+            (
+                Class::Finite {
+                    exponent: exponent_a,
+                    significand: significand_a,
+                },
+                Class::Finite {
+                    exponent: exponent_b,
+                    significand: significand_b,
+                },
+                addend,
+            ) => {
+                let product = (sign, exponent_a + exponent_b, significand_a * significand_b);
+                match addend {
+                    Class::Finite {
+                        exponent,
+                        significand,
+                    } => self.sum(product, (z.sign, exponent, significand), round, flags),
+                    _ => self.pack(product.0, product.1, product.2, round, flags),
+                }
+            }
+        }
+    }
+
+    /// Orders non-NaN values, with `-0` below `+0`.
+    fn key(self, bits: u64) -> i128 {
+        let magnitude = (bits & !self.sign_bit()) as i128;
+        match bits & self.sign_bit() {
+            0 => magnitude,
+            _ => -magnitude - 1,
+        }
+    }
+
+    fn is_zero(self, bits: u64) -> bool {
+        bits & !self.sign_bit() == 0
+    }
+
+    /// Returns the smaller or larger of two values, with `-0` below `+0`.
+    ///
+    /// If only one operand is a NaN the other is returned, and if both are the result
+    /// is the canonical NaN.
+    pub(crate) fn min_max(self, a: u64, b: u64, max: bool, flags: &mut ExceptionFlags) -> u64 {
+        let (x, y) = (self.unpack(a), self.unpack(b));
+        if x.is_signaling() || y.is_signaling() {
+            flags.raise(ExceptionFlags::INVALID);
+        }
+
+        match (x.is_nan(), y.is_nan()) {
+            (true, true) => self.canonical_nan(),
+            (true, false) => b,
+            (false, true) => a,
+            (false, false) if (self.key(a) < self.key(b)) != max => a,
+            (false, false) => b,
+        }
+    }
+
+    /// A quiet comparison: only signaling NaNs raise the invalid flag.
+    pub(crate) fn eq(self, a: u64, b: u64, flags: &mut ExceptionFlags) -> bool {
+        let (x, y) = (self.unpack(a), self.unpack(b));
+        if x.is_signaling() || y.is_signaling() {
+            flags.raise(ExceptionFlags::INVALID);
+        }
+
+        !x.is_nan() && !y.is_nan() && (a == b || (self.is_zero(a) && self.is_zero(b)))
+    }
+
+    /// A signaling comparison: any NaN raises the invalid flag.
+    pub(crate) fn lt(self, a: u64, b: u64, flags: &mut ExceptionFlags) -> bool {
+        let (x, y) = (self.unpack(a), self.unpack(b));
+        if x.is_nan() || y.is_nan() {
+            flags.raise(ExceptionFlags::INVALID);
+            return false;
+        }
+
+        !(self.is_zero(a) && self.is_zero(b)) && self.key(a) < self.key(b)
+    }
+
+    /// A signaling comparison: any NaN raises the invalid flag.
+    pub(crate) fn le(self, a: u64, b: u64, flags: &mut ExceptionFlags) -> bool {
+        let (x, y) = (self.unpack(a), self.unpack(b));
+        if x.is_nan() || y.is_nan() {
+            flags.raise(ExceptionFlags::INVALID);
+            return false;
+        }
+
+        (self.is_zero(a) && self.is_zero(b)) || self.key(a) <= self.key(b)
+    }
+
+    /// Returns the `fclass` mask of a value, with exactly one of its 10 bits set.
+    pub(crate) fn classify(self, a: u64) -> u32 {
+        let x = self.unpack(a);
+        let subnormal = (a >> self.fraction_bits) & self.max_exponent() == 0;
+
+        let bit = match x.class {
+            Class::Infinity => 0,
+            Class::Finite { .. } if !subnormal => 1,
+            Class::Finite { .. } => 2,
+            Class::Zero => 3,
+            Class::Nan { signaling: true } => return 1 << 8,
+            Class::Nan { signaling: false } => return 1 << 9,
+        };
+
+        match x.sign {
+            true => 1 << bit,
+            false => 1 << (7 - bit),
+        }
+    }
+
+    /// Converts a value to a 32-bit integer, rounding as requested.
+    ///
+    /// Out of range values raise the invalid flag and saturate. NaNs convert to the
+    /// largest integer.
+    pub(crate) fn to_int(
+        self,
+        a: u64,
+        signed: bool,
+        round: Round,
+        flags: &mut ExceptionFlags,
+    ) -> u32 {
+        let x = self.unpack(a);
+        let (min, max) = match signed {
+            true => (i32::MIN as i64, i32::MAX as i64),
+            false => (0, u32::MAX as i64),
+        };
+
+        // Any magnitude above 2^33 is out of range, so larger ones are clamped to it.
+        const LIMIT: u128 = 1 << 33;
+        let (magnitude, inexact) = match x.class {
+            Class::Nan { .. } => {
+                flags.raise(ExceptionFlags::INVALID);
+                return max as u32;
+            }
+            Class::Infinity => (LIMIT, false),
+            Class::Zero => (0, false),
+            Class::Finite {
+                exponent,
+                significand,
+            } => match exponent {
+                33.. => (LIMIT, false),
+                _ => shift_round(significand, -exponent, x.sign, round),
+            },
+        };
+
+        let magnitude = magnitude.min(LIMIT) as i64;
+        let value = if x.sign { -magnitude } else { magnitude };
+        if value < min || value > max {
+            flags.raise(ExceptionFlags::INVALID);
+            return value.clamp(min, max) as u32;
+        }
+
+        if inexact {
+            flags.raise(ExceptionFlags::INEXACT);
+        }
+        value as u32
+    }
+
+    /// Converts a 32-bit integer to a value, rounding as requested.
+    pub(crate) fn int_to_float(
+        self,
+        value: u32,
+        signed: bool,
+        round: Round,
+        flags: &mut ExceptionFlags,
+    ) -> u64 {
+        let (sign, magnitude) = match signed {
+            true => ((value as i32) < 0, (value as i32).unsigned_abs()),
+            false => (false, value),
+        };
+
+        match magnitude {
+            0 => self.zero(false),
+            _ => self.pack(sign, 0, magnitude as u128, round, flags),
+        }
+    }
+
+    /// Converts a value to the format `to`, rounding as requested.
+    pub(crate) fn convert(
+        self,
+        to: Format,
+        a: u64,
+        round: Round,
+        flags: &mut ExceptionFlags,
+    ) -> u64 {
+        let x = self.unpack(a);
+
+        match x.class {
+            Class::Nan { .. } => to.propagate_nan(&[x], flags),
+            Class::Infinity => to.infinity(x.sign),
+            Class::Zero => to.zero(x.sign),
+            Class::Finite {
+                exponent,
+                significand,
+            } => to.pack(x.sign, exponent, significand, round, flags),
+        }
+    }
+}
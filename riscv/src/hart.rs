@@ -0,0 +1,456 @@
+use crate::atomic::Reservation;
+use crate::config::Config;
+use crate::csr::{Counters, TIME, TIMEH};
+use crate::custom::RegisterFile;
+use crate::error::Error;
+use crate::extension::Extension;
+use crate::float::{Fcsr, nan_box};
+use crate::instruction::RiscVInstruction;
+use crate::metadata::AccessKind;
+use crate::profile::Profile;
+use crate::register::Register;
+use std::marker::PhantomData;
+
+/// The return address of the function an instance calls. Execution ends when control
+/// transfers to it, such as through `ret` at the end of the called function.
+///
+/// It is halfword-aligned, so that `jalr` keeps it intact, and lies outside of any
+/// code that fits in a 32-bit address space.
+pub(crate) const RETURN_ADDRESS: u64 = 0xffff_fffe;
+
+/// The byte offsets of the fields of [`Hart`] that translated native code reads and
+/// writes.
+#[cfg(any(target_arch = "aarch64", target_arch = "x86_64", test))]
+pub(crate) mod offsets {
+    use super::Hart;
+    use std::mem::offset_of;
+
+    /// The byte offset of `Hart::registers`, where native code loads and stores `x0`
+    /// through `x31`, 8 bytes each.
+    pub(crate) const REGISTERS: usize = offset_of!(Hart, registers);
+    /// The byte offset of `Hart::gas`.
+    pub(crate) const GAS: usize = offset_of!(Hart, gas);
+    /// The byte offset of `Hart::pc`.
+    pub(crate) const PC: usize = offset_of!(Hart, pc);
+    /// The byte offset of `Hart::memory`.
+    pub(crate) const MEMORY: usize = offset_of!(Hart, memory);
+    /// The byte offset of `Hart::memory_size`.
+    pub(crate) const MEMORY_SIZE: usize = offset_of!(Hart, memory_size);
+    /// The byte offset of `Hart::native_offsets`.
+    pub(crate) const NATIVE_OFFSETS: usize = offset_of!(Hart, native_offsets);
+}
+
+/// The entry point at the start of translated native code.
+///
+/// It runs native code from `target`, the native address of an instruction. The
+/// native code of every instruction starts by checking that gas remains, and has a
+/// stub that exits with the pc of the instruction. Native code takes the stub when no
+/// gas remains, before an instruction that traps, and for every instruction it does
+/// not implement, such as `ecall`, whose native code is only the stub. The exit
+/// stores the pc in `hart.pc` and the remaining gas in `hart.gas`, and returns.
+/// Native code never returns in the middle of an instruction: the instruction at
+/// `hart.pc` has not had any effect, and is executed by [`Hart::execute`] next.
+pub(crate) type Entry = unsafe extern "C" fn(hart: *mut Hart<'_>, target: *const u8);
+
+/// The state of the single hart that runs a call of an instance.
+///
+/// The fields up to `native_offsets` are read and written by translated native code
+/// through [`offsets`], and are laid out with `repr(C)` for that reason.
+#[repr(C)]
+pub(crate) struct Hart<'a> {
+    /// The integer registers, indexed by register number. With a 32-bit profile, the
+    /// values are zero-extended. `x0` is always zero.
+    pub(crate) registers: [u64; 32],
+    /// The gas remaining.
+    pub(crate) gas: u64,
+    /// The address of the next instruction.
+    pub(crate) pc: u64,
+    /// The start of guest memory.
+    memory: *mut u8,
+    /// The size of guest memory in bytes.
+    memory_size: u64,
+    /// The native offset of every instruction, as described by
+    /// [`Translation::offsets`](crate::program::Translation::offsets), or null.
+    pub(crate) native_offsets: *const u32,
+    /// The floating-point registers, indexed by register number.
+    pub(crate) float_registers: [u64; 32],
+    /// The floating-point control and status register.
+    pub(crate) fcsr: Fcsr,
+    /// The reservation of `lr.w`.
+    reservation: Reservation,
+    /// The gas available when the call started.
+    gas_limit: u64,
+    /// The gas charged by custom instructions so far.
+    custom_gas: u64,
+    /// The number of custom instructions retired so far.
+    custom_count: u64,
+    /// The user-defined context value passed to the syscall and custom handlers.
+    context: u64,
+    /// The profile of the engine.
+    profile: Profile,
+    _memory: PhantomData<&'a mut [u8]>,
+}
+
+impl<'a> Hart<'a> {
+    /// Constructs a new `Hart` about to call the function at `pc` with `arg` in `a0`.
+    ///
+    /// `ra` holds [`RETURN_ADDRESS`] and `sp` the end of memory. Every other register
+    /// is zero, and no address is reserved.
+    pub(crate) fn new(
+        profile: Profile,
+        memory: &'a mut [u8],
+        pc: u64,
+        arg: u64,
+        gas: u64,
+        context: u64,
+    ) -> Self {
+        let mut registers = [0; 32];
+        registers[Register::RA.number() as usize] = RETURN_ADDRESS;
+        registers[Register::SP.number() as usize] = memory.len() as u64;
+        registers[Register::A0.number() as usize] = arg;
+
+        Self {
+            registers,
+            gas,
+            pc,
+            memory: memory.as_mut_ptr(),
+            memory_size: memory.len() as u64,
+            native_offsets: std::ptr::null(),
+            float_registers: [0; 32],
+            fcsr: Fcsr::default(),
+            reservation: Reservation::default(),
+            gas_limit: gas,
+            custom_gas: 0,
+            custom_count: 0,
+            context,
+            profile,
+            _memory: PhantomData,
+        }
+    }
+
+    /// Executes `instruction`, which is `length` bytes long and located at `self.pc`.
+    ///
+    /// This is the reference semantics of a whole instruction, including its gas cost:
+    /// 1 for an ordinary instruction, or the cost registered for a custom one. An
+    /// instruction that returns an error has no effect, and is not charged.
+    ///
+    /// # Errors
+    ///
+    /// - `Error::OutOfGas` if less gas remains than the instruction costs.
+    /// - `Error::InvalidAddress` if a load, store or atomic instruction accesses bytes
+    ///   outside of memory.
+    /// - `Error::MisalignedAtomic` if an atomic instruction accesses an address that is
+    ///   not a multiple of 4.
+    /// - `Error::Breakpoint` for `ebreak`.
+    /// - `Error::InvalidCsr` and `Error::InvalidRoundingMode` as described by
+    ///   [`RiscVInstruction::read_csr`] and [`RiscVInstruction::evaluate_float`].
+    pub(crate) fn execute(
+        &mut self,
+        instruction: RiscVInstruction,
+        length: u8,
+        config: &Config,
+    ) -> Result<(), Error> {
+        let custom = config.custom.find(&instruction);
+        let cost = custom.map_or(1, |custom| u64::from(custom.gas));
+        if self.gas < cost {
+            return Err(Error::OutOfGas);
+        }
+
+        let [rs1, rs2] = instruction
+            .sources()
+            .map(|register| register.map_or(0, |register| self.read(register)));
+        let next = self.wrap(self.pc.wrapping_add(u64::from(length)));
+        let mut pc = next;
+
+        match instruction {
+            RiscVInstruction::Auipc { rd, imm } => {
+                self.write(rd, self.pc.wrapping_add(imm as i64 as u64));
+            }
+            RiscVInstruction::Jal { rd, imm } => {
+                pc = self.wrap(self.pc.wrapping_add(imm as i64 as u64));
+                self.write(rd, next);
+            }
+            RiscVInstruction::Jalr { rd, imm, .. } => {
+                pc = self.wrap(rs1.wrapping_add(imm as i64 as u64)) & !1;
+                self.write(rd, next);
+            }
+            RiscVInstruction::Beq { imm, .. }
+            | RiscVInstruction::Bne { imm, .. }
+            | RiscVInstruction::Blt { imm, .. }
+            | RiscVInstruction::Bge { imm, .. }
+            | RiscVInstruction::Bltu { imm, .. }
+            | RiscVInstruction::Bgeu { imm, .. } => {
+                if self.taken(instruction, rs1, rs2) {
+                    pc = self.wrap(self.pc.wrapping_add(imm as i64 as u64));
+                }
+            }
+            RiscVInstruction::Ecall => {
+                let result = (config.syscall)(&self.syscall_args(), self.context);
+                self.write(Register::A0, result as i32 as i64 as u64);
+            }
+            RiscVInstruction::Ebreak => return Err(Error::Breakpoint { pc: self.pc }),
+            RiscVInstruction::Fence { .. }
+            | RiscVInstruction::FenceTso
+            | RiscVInstruction::FenceI => {}
+            RiscVInstruction::Csrrw { rd, csr, .. }
+            | RiscVInstruction::Csrrs { rd, csr, .. }
+            | RiscVInstruction::Csrrc { rd, csr, .. } => {
+                let value = self.access_csr(instruction, csr, rs1 as u32, config)?;
+                self.write(rd, u64::from(value));
+            }
+            RiscVInstruction::Csrrwi { rd, imm, csr }
+            | RiscVInstruction::Csrrsi { rd, imm, csr }
+            | RiscVInstruction::Csrrci { rd, imm, csr } => {
+                let value = self.access_csr(instruction, csr, u32::from(imm), config)?;
+                self.write(rd, u64::from(value));
+            }
+            RiscVInstruction::Custom { operands, .. } => {
+                let handler = custom
+                    .expect("decoded custom instructions are registered")
+                    .handler;
+                let mut registers = RegisterFile::new(&mut self.registers, self.profile);
+                handler(&mut registers, operands, self.context);
+                self.custom_gas += cost;
+                self.custom_count += 1;
+            }
+            _ if instruction.memory_access().is_some() => self.access(instruction, rs1, rs2)?,
+            _ => self.compute(instruction, rs1, rs2)?,
+        }
+
+        self.gas -= cost;
+        self.pc = pc;
+
+        Ok(())
+    }
+
+    /// Executes an instruction that computes a value from its source registers alone.
+    fn compute(&mut self, instruction: RiscVInstruction, rs1: u64, rs2: u64) -> Result<(), Error> {
+        let value = match self.profile.xlen() {
+            64 => instruction.evaluate_rv64(rs1, rs2),
+            _ => instruction.evaluate(rs1 as u32, rs2 as u32).map(u64::from),
+        };
+        if let Some(value) = value {
+            if let Some(rd) = instruction.writes() {
+                self.write(rd, value);
+            }
+            return Ok(());
+        }
+
+        // An instruction with an integer source, such as `fcvt.s.w`, has no
+        // floating-point sources.
+        let mut reads = instruction
+            .float_reads()
+            .map(|register| self.float_registers[register.number() as usize]);
+        let (rs1, rs2, rs3) = match instruction.sources()[0] {
+            Some(_) => (rs1, 0, 0),
+            None => (
+                reads.next().unwrap_or(0),
+                reads.next().unwrap_or(0),
+                reads.next().unwrap_or(0),
+            ),
+        };
+
+        let value = instruction
+            .evaluate_float(rs1, rs2, rs3, &mut self.fcsr)
+            .expect("every other instruction is a floating-point instruction")?;
+        match instruction.float_writes() {
+            Some(rd) => self.float_registers[rd.number() as usize] = value,
+            None => {
+                if let Some(rd) = instruction.writes() {
+                    self.write(rd, value);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Executes a load, store or atomic instruction.
+    fn access(&mut self, instruction: RiscVInstruction, rs1: u64, rs2: u64) -> Result<(), Error> {
+        let access = instruction
+            .memory_access()
+            .expect("only memory instructions are accessed");
+        let width = u64::from(access.width);
+
+        let offset = match instruction {
+            RiscVInstruction::Lb { imm, .. }
+            | RiscVInstruction::Lh { imm, .. }
+            | RiscVInstruction::Lw { imm, .. }
+            | RiscVInstruction::Lbu { imm, .. }
+            | RiscVInstruction::Lhu { imm, .. }
+            | RiscVInstruction::Ld { imm, .. }
+            | RiscVInstruction::Lwu { imm, .. }
+            | RiscVInstruction::Sb { imm, .. }
+            | RiscVInstruction::Sh { imm, .. }
+            | RiscVInstruction::Sw { imm, .. }
+            | RiscVInstruction::Sd { imm, .. }
+            | RiscVInstruction::Flw { imm, .. }
+            | RiscVInstruction::Fld { imm, .. }
+            | RiscVInstruction::Fsw { imm, .. }
+            | RiscVInstruction::Fsd { imm, .. } => imm,
+            _ => 0,
+        };
+        let address = self.wrap(rs1.wrapping_add(offset as i64 as u64));
+
+        let atomic = matches!(access.kind, AccessKind::ReadModifyWrite)
+            || matches!(
+                instruction,
+                RiscVInstruction::LrW { .. } | RiscVInstruction::ScW { .. }
+            );
+        if atomic && address % width != 0 {
+            return Err(Error::MisalignedAtomic { address });
+        }
+
+        let end = address
+            .checked_add(width)
+            .filter(|end| *end <= self.memory_size);
+        let Some(end) = end else {
+            return Err(Error::InvalidAddress { address });
+        };
+        let (start, end) = (address as usize, end as usize);
+        // SAFETY: `memory` and `memory_size` describe the slice the hart was constructed
+        // with, which it borrows mutably for its whole lifetime.
+        let memory =
+            unsafe { std::slice::from_raw_parts_mut(self.memory, self.memory_size as usize) };
+        let bytes = &mut memory[start..end];
+
+        let mut loaded = [0; 8];
+        loaded[..bytes.len()].copy_from_slice(bytes);
+        let loaded = u64::from_le_bytes(loaded);
+
+        match instruction {
+            RiscVInstruction::LrW { rd, .. } => {
+                self.reservation.reserve(address as u32);
+                self.write(rd, loaded);
+            }
+            RiscVInstruction::ScW { rd, .. } => {
+                let stored = self.reservation.release(address as u32);
+                if stored {
+                    bytes.copy_from_slice(&rs2.to_le_bytes()[..bytes.len()]);
+                }
+                self.write(rd, u64::from(!stored));
+            }
+            RiscVInstruction::Flw { rd, .. } => {
+                self.float_registers[rd.number() as usize] = nan_box(loaded);
+            }
+            RiscVInstruction::Fld { rd, .. } => {
+                self.float_registers[rd.number() as usize] = loaded;
+            }
+            RiscVInstruction::Fsw { rs2, .. } | RiscVInstruction::Fsd { rs2, .. } => {
+                let value = self.float_registers[rs2.number() as usize];
+                bytes.copy_from_slice(&value.to_le_bytes()[..bytes.len()]);
+            }
+            _ => match access.kind {
+                AccessKind::Load => {
+                    let shift = 64 - 8 * width;
+                    let value = match access.signed {
+                        true => ((loaded << shift) as i64 >> shift) as u64,
+                        false => loaded,
+                    };
+                    if let Some(rd) = instruction.writes() {
+                        self.write(rd, value);
+                    }
+                }
+                AccessKind::Store => bytes.copy_from_slice(&rs2.to_le_bytes()[..bytes.len()]),
+                AccessKind::ReadModifyWrite => {
+                    let stored = instruction
+                        .atomic(loaded as u32, rs2 as u32)
+                        .expect("atomic memory operations are evaluated");
+                    bytes.copy_from_slice(&stored.to_le_bytes());
+                    if let Some(rd) = instruction.writes() {
+                        self.write(rd, loaded);
+                    }
+                }
+            },
+        }
+
+        Ok(())
+    }
+
+    /// Executes a CSR instruction accessing `csr` with the value of `rs1` or its
+    /// immediate as `source`, and returns the old value of the CSR.
+    fn access_csr(
+        &mut self,
+        instruction: RiscVInstruction,
+        csr: u16,
+        source: u32,
+        config: &Config,
+    ) -> Result<u32, Error> {
+        let float =
+            config.extensions.contains(Extension::F) || config.extensions.contains(Extension::D);
+        if float {
+            if let Some(value) = instruction.access_fcsr(&mut self.fcsr, source) {
+                return Ok(value);
+            }
+        }
+
+        let gas_consumed = self.gas_limit - self.gas;
+        let counters = Counters {
+            gas_consumed,
+            time: match csr {
+                TIME | TIMEH => (config.time)(self.context),
+                _ => 0,
+            },
+            instret: gas_consumed - self.custom_gas + self.custom_count,
+        };
+
+        instruction
+            .read_csr(&counters)
+            .expect("CSR instructions are read")
+    }
+
+    /// Returns the arguments of a syscall: `a0` through `a7`.
+    ///
+    /// RV32E has no `a6` and `a7`, and its calling convention passes the syscall number
+    /// in `t0` instead, which the handler receives in place of `a7`.
+    fn syscall_args(&self) -> [u32; 8] {
+        let mut args: [u32; 8] = std::array::from_fn(|index| {
+            self.registers[Register::A0.number() as usize + index] as u32
+        });
+        if self.profile == Profile::Rv32e {
+            args[6] = 0;
+            args[7] = self.read(Register::T0) as u32;
+        }
+
+        args
+    }
+
+    /// Whether a conditional branch with the source values `rs1` and `rs2` is taken.
+    fn taken(&self, instruction: RiscVInstruction, rs1: u64, rs2: u64) -> bool {
+        let signed = |value: u64| match self.profile.xlen() {
+            64 => value as i64,
+            _ => value as u32 as i32 as i64,
+        };
+
+        match instruction {
+            RiscVInstruction::Beq { .. } => rs1 == rs2,
+            RiscVInstruction::Bne { .. } => rs1 != rs2,
+            RiscVInstruction::Blt { .. } => signed(rs1) < signed(rs2),
+            RiscVInstruction::Bge { .. } => signed(rs1) >= signed(rs2),
+            RiscVInstruction::Bltu { .. } => rs1 < rs2,
+            RiscVInstruction::Bgeu { .. } => rs1 >= rs2,
+            _ => false,
+        }
+    }
+
+    /// Returns the value of `register`.
+    pub(crate) fn read(&self, register: Register) -> u64 {
+        self.registers[register.number() as usize]
+    }
+
+    /// Sets the value of `register`, truncated to the register width. Writes to `x0`
+    /// are discarded.
+    pub(crate) fn write(&mut self, register: Register, value: u64) {
+        if register != Register::ZERO {
+            self.registers[register.number() as usize] = self.wrap(value);
+        }
+    }
+
+    /// Truncates `value` to the register width of the profile.
+    fn wrap(&self, value: u64) -> u64 {
+        match self.profile.xlen() {
+            64 => value,
+            _ => value as u32 as u64,
+        }
+    }
+}
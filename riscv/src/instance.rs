@@ -1,3 +1,5 @@
+use crate::hart::{Entry, Hart, RETURN_ADDRESS};
+use crate::register::Register;
 use crate::{error::Error, memory::Memory, module::Module};
 use std::{mem, rc::Rc};
 
@@ -5,6 +7,8 @@ use std::{mem, rc::Rc};
 pub struct Instance {
     module: Box<Module>,
     memory: Box<Memory>,
+    gas: u64,
+    context: u64,
 }
 
 impl Instance {
@@ -14,7 +18,41 @@ impl Instance {
             return Err(Error::InvalidEngine);
         }
 
-        Ok(Self { module, memory })
+        Ok(Self {
+            module,
+            memory,
+            gas: 0,
+            context: 0,
+        })
+    }
+
+    /// Sets the gas available to the following calls.
+    ///
+    /// Every instruction costs 1 gas, except for custom instructions, which cost the
+    /// gas they are registered with. An instance starts with no gas.
+    pub fn set_gas(&mut self, gas: u64) {
+        self.gas = gas;
+    }
+
+    /// Returns the gas remaining after the last call.
+    pub fn gas(&self) -> u64 {
+        self.gas
+    }
+
+    /// Sets the user-defined context value passed to the syscall handler, the custom
+    /// instruction handlers and `Config::time`.
+    pub fn set_context(&mut self, context: u64) {
+        self.context = context;
+    }
+
+    /// Returns the memory of the instance.
+    pub fn memory(&self) -> &Memory {
+        &self.memory
+    }
+
+    /// Returns the memory of the instance, for the host to read and write.
+    pub fn memory_mut(&mut self) -> &mut Memory {
+        &mut self.memory
     }
 
     /// Executes the loaded RISC-V function.
@@ -23,6 +61,11 @@ impl Instance {
     /// argument `arg`, and continues until it completes, an error occurs, or
     /// gas runs out.
     ///
+    /// The function is called with `arg` in `a0`, the return address in `ra` and the
+    /// end of memory in `sp`. Every other register is zero. It completes when it
+    /// returns, and the value of `a0` is returned. The remaining gas is available from
+    /// [`Instance::gas`] whether or not the call succeeds.
+    ///
     /// # Arguments
     ///
     /// * `pc` - The program counter to start execution from.
@@ -35,7 +78,22 @@ impl Instance {
     /// # Errors
     ///
     /// - `Error::OutOfGas` if gas runs out.
+    /// - `Error::InvalidJump` if control transfers to an address where no instruction
+    ///   starts, including `pc`.
+    /// - `Error::InvalidAddress`, `Error::MisalignedAtomic`, `Error::Breakpoint`,
+    ///   `Error::InvalidCsr` and `Error::InvalidRoundingMode` if an instruction traps.
+    /// - `Error::InvalidProfile` if the engine runs 64-bit guests: use
+    ///   [`Instance::call64`] instead.
     pub fn call(&mut self, pc: u32, arg: u32) -> Result<u32, Error> {
+        let profile = self.module.engine.config().profile;
+        if profile.xlen() != 32 {
+            return Err(Error::InvalidProfile { profile });
+        }
+
+        if self.module.program.is_some() {
+            return self.run(pc, u64::from(arg)).map(|value| value as u32);
+        }
+
         unsafe {
             let native_fn_addr = (self.module.native_code_addr as *mut u8).add(pc as usize);
 
@@ -45,6 +103,95 @@ impl Instance {
         }
     }
 
+    /// Executes the loaded RISC-V function on an engine with `Profile::Rv64i`.
+    ///
+    /// This is the 64-bit counterpart of [`Instance::call`]: `arg` is passed in `a0`
+    /// with all 64 bits, and the full 64-bit value of `a0` is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `pc` - The program counter to start execution from.
+    /// * `arg` - A single 64-bit argument.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `u64` return value upon successful completion.
+    ///
+    /// # Errors
+    ///
+    /// - `Error::OutOfGas` if gas runs out.
+    /// - `Error::InvalidJump` and the traps of [`Instance::call`].
+    /// - `Error::InvalidProfile` if the engine runs 32-bit guests.
+    pub fn call64(&mut self, pc: u32, arg: u64) -> Result<u64, Error> {
+        let profile = self.module.engine.config().profile;
+        if profile.xlen() != 64 {
+            return Err(Error::InvalidProfile { profile });
+        }
+
+        if self.module.program.is_some() {
+            return self.run(pc, arg);
+        }
+
+        unsafe {
+            let native_fn_addr = (self.module.native_code_addr as *mut u8).add(pc as usize);
+
+            let func: extern "C" fn(u64) -> u64 = mem::transmute(native_fn_addr);
+
+            Ok(func(arg))
+        }
+    }
+
+    /// Runs the RISC-V code of the module from `pc` until the called function returns,
+    /// and returns the value of `a0`.
+    ///
+    /// Instructions with native code run natively, and every other instruction is
+    /// interpreted by [`Hart::execute`]. On hosts without a native backend, the whole
+    /// program is interpreted.
+    fn run(&mut self, pc: u32, arg: u64) -> Result<u64, Error> {
+        let module = &self.module;
+        let config = module.engine.config();
+        let program = module.program.as_ref().expect("RISC-V code is loaded");
+
+        let memory = self.memory.data_mut();
+        let mut hart = Hart::new(
+            config.profile,
+            memory,
+            u64::from(pc),
+            arg,
+            self.gas,
+            self.context,
+        );
+        hart.native_offsets = module.native_offsets.as_ptr();
+
+        let result = loop {
+            if hart.pc == RETURN_ADDRESS {
+                break Ok(hart.read(Register::A0));
+            }
+
+            if let Some(offset) = module.native_offset(hart.pc) {
+                // SAFETY: the native code starts with the entry point of the code
+                // translated from `program`, and `offset` is the native offset of one
+                // of its instructions.
+                unsafe {
+                    let entry: Entry = mem::transmute(module.native_code_addr);
+                    let target = (module.native_code_addr as *const u8).add(offset);
+                    entry(&mut hart, target);
+                }
+            }
+
+            // Native code exits at an instruction it does not execute itself.
+            let Some((instruction, length)) = program.get(hart.pc) else {
+                break Err(Error::InvalidJump { target: hart.pc });
+            };
+            if let Err(error) = hart.execute(instruction, length, config) {
+                break Err(error);
+            }
+        };
+
+        self.gas = hart.gas;
+        result
+    }
+
     /// Decomposes the instance back into its module and memory.
     pub fn decompose(self) -> (Box<Module>, Box<Memory>) {
         (self.module, self.memory)
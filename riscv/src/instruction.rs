@@ -1,130 +1,489 @@
+use crate::csr::CsrName;
+use crate::custom::{
+    CUSTOM_0_OPCODE, CUSTOM_1_OPCODE, CustomForm, CustomInstructions, CustomOpcode, CustomOperands,
+};
+use crate::error::{DecodeError, DecodeErrorKind, Error};
+use crate::extension::{Extension, Extensions};
+use crate::float::RoundingMode;
+use crate::profile::Profile;
+use crate::register::{FloatRegister, Register, Syntax};
 use std::fmt;
 
-/// RISC-V instruction representation for 32-bit IM (Integer + Multiplication) extension.
+/// RISC-V instruction representation for the RV32I, RV32E and RV64I base profiles.
 ///
-/// This implementation specifically targets RV32IM, which includes:
-/// - **RV32I**: Base integer instruction set (arithmetic, load/store, branch, jump)
-/// - **RV32M**: Standard extension for integer multiplication and division
+/// Every profile includes:
+/// - **I**: Base integer instruction set (arithmetic, load/store, branch, jump)
+/// - **M**: Standard extension for integer multiplication and division
+///
+/// The RV64I and RV64M instructions, such as `ld` and `addw`, are only decoded by
+/// [`RiscVInstruction::decode_for`] with `Profile::Rv64i`.
+///
+/// The A atomic instructions, the F and D floating-point extensions, the Zba, Zbb and
+/// Zbs bit-manipulation extensions, the Zknh hash extension, the Zicsr CSR
+/// instructions and the Zifencei instruction-fetch fence are optional: they are only
+/// decoded by [`RiscVInstruction::decode_with`] when enabled.
+///
+/// Compressed (C) instructions have no variants of their own. They are expanded to
+/// the equivalent 32-bit instruction by [`RiscVInstruction::decode_compressed_for`].
 ///
 /// ## Architecture
-/// - **32-bit RISC-V**: All operations are 32-bit width
-/// - **Register set**: X0-X31 (32 general-purpose registers)
-/// - **Memory**: 32-bit addressing space
+/// - **Register width**: 32 bits with `Profile::Rv32i` and `Profile::Rv32e`, 64 bits
+///   with `Profile::Rv64i`, where the word instructions operate on the lower 32 bits
+/// - **Register set**: X0-X31, or X0-X15 with `Profile::Rv32e`
+/// - **Memory**: addresses as wide as the registers, within the memory of an instance
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum RiscVInstruction {
     /// Add instruction (RV32I base instruction set)
     ///
     /// Adds the values in registers `rs1` and `rs2` and stores the result in `rd`.
     /// Performs 32-bit arithmetic addition with overflow wrapping.
-    Add { rd: u8, rs1: u8, rs2: u8 },
+    Add {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
 
     /// Subtract instruction (RV32I base instruction set)
     ///
     /// Subtracts the value in register `rs2` from register `rs1` and stores the result in `rd`.
     /// Performs 32-bit arithmetic subtraction with overflow wrapping.
-    Sub { rd: u8, rs1: u8, rs2: u8 },
+    Sub {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
 
     /// XOR instruction (RV32I base instruction set)
     ///
     /// Performs bitwise XOR between registers `rs1` and `rs2` and stores the result in `rd`.
     /// Each bit in the result is 1 if the corresponding bits in the operands are different.
-    Xor { rd: u8, rs1: u8, rs2: u8 },
+    Xor {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
 
     /// OR instruction (RV32I base instruction set)
     ///
     /// Performs bitwise OR between registers `rs1` and `rs2` and stores the result in `rd`.
     /// Each bit in the result is 1 if either corresponding bit in the operands is 1.
-    Or { rd: u8, rs1: u8, rs2: u8 },
+    Or {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+
+    /// AND instruction (RV32I base instruction set)
+    ///
+    /// Performs bitwise AND between registers `rs1` and `rs2` and stores the result in `rd`.
+    /// Each bit in the result is 1 only if both corresponding bits in the operands are 1.
+    And {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+
+    /// Shift Left Logical instruction (RV32I base instruction set)
+    ///
+    /// Shifts register `rs1` left by the amount held in the lower 5 bits of register `rs2`
+    /// and stores the result in `rd`. Zero bits are shifted in from the right.
+    Sll {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+
+    /// Shift Right Logical instruction (RV32I base instruction set)
+    ///
+    /// Shifts register `rs1` right by the amount held in the lower 5 bits of register `rs2`
+    /// and stores the result in `rd`. Zero bits are shifted in from the left.
+    Srl {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+
+    /// Shift Right Arithmetic instruction (RV32I base instruction set)
+    ///
+    /// Shifts register `rs1` right by the amount held in the lower 5 bits of register `rs2`
+    /// and stores the result in `rd`. Sign bits are shifted in from the left.
+    Sra {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+
+    /// Set Less Than instruction (RV32I base instruction set)
+    ///
+    /// Compares registers `rs1` and `rs2` using signed comparison.
+    /// Sets `rd` to 1 if `rs1` < `rs2`, otherwise sets `rd` to 0.
+    Slt {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+
+    /// Set Less Than Unsigned instruction (RV32I base instruction set)
+    ///
+    /// Compares registers `rs1` and `rs2` using unsigned comparison.
+    /// Sets `rd` to 1 if `rs1` < `rs2` (unsigned), otherwise sets `rd` to 0.
+    Sltu {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+
+    /// Multiply instruction (RV32M standard extension)
+    ///
+    /// Multiplies registers `rs1` and `rs2` and stores the lower 32 bits of the
+    /// product in `rd`. The result is the same for signed and unsigned operands.
+    Mul {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+
+    /// Multiply High instruction (RV32M standard extension)
+    ///
+    /// Multiplies registers `rs1` and `rs2` as signed values and stores the upper
+    /// 32 bits of the 64-bit product in `rd`.
+    Mulh {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+
+    /// Multiply High Signed-Unsigned instruction (RV32M standard extension)
+    ///
+    /// Multiplies register `rs1` as a signed value by register `rs2` as an unsigned
+    /// value and stores the upper 32 bits of the 64-bit product in `rd`.
+    Mulhsu {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+
+    /// Multiply High Unsigned instruction (RV32M standard extension)
+    ///
+    /// Multiplies registers `rs1` and `rs2` as unsigned values and stores the upper
+    /// 32 bits of the 64-bit product in `rd`.
+    Mulhu {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+
+    /// Divide instruction (RV32M standard extension)
+    ///
+    /// Divides register `rs1` by register `rs2` using signed division, rounding towards
+    /// zero, and stores the quotient in `rd`.
+    ///
+    /// Division never traps. Every backend must produce these results:
+    /// - Division by zero sets `rd` to -1 (all bits set).
+    /// - Overflow (`i32::MIN / -1`) sets `rd` to `i32::MIN`.
+    Div {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+
+    /// Divide Unsigned instruction (RV32M standard extension)
+    ///
+    /// Divides register `rs1` by register `rs2` using unsigned division and stores the
+    /// quotient in `rd`.
+    ///
+    /// Division never traps. Every backend must produce these results:
+    /// - Division by zero sets `rd` to `u32::MAX` (all bits set).
+    Divu {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+
+    /// Remainder instruction (RV32M standard extension)
+    ///
+    /// Computes the remainder of the signed division of register `rs1` by register `rs2`
+    /// and stores it in `rd`. The sign of the result equals the sign of the dividend.
+    ///
+    /// Division never traps. Every backend must produce these results:
+    /// - Division by zero sets `rd` to the dividend `rs1`.
+    /// - Overflow (`i32::MIN % -1`) sets `rd` to 0.
+    Rem {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+
+    /// Remainder Unsigned instruction (RV32M standard extension)
+    ///
+    /// Computes the remainder of the unsigned division of register `rs1` by register `rs2`
+    /// and stores it in `rd`.
+    ///
+    /// Division never traps. Every backend must produce these results:
+    /// - Division by zero sets `rd` to the dividend `rs1`.
+    Remu {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
 
     /// Add Immediate instruction (RV32I base instruction set)
     ///
     /// Adds the immediate value to register `rs1` and stores the result in `rd`.
     /// If `rs1 = x0`, this effectively loads the immediate value into `rd`.
-    Addi { rd: u8, rs1: u8, imm: i16 },
+    Addi {
+        rd: Register,
+        rs1: Register,
+        imm: i16,
+    },
 
     /// XOR Immediate instruction (RV32I base instruction set)
     ///
     /// Performs bitwise XOR between register `rs1` and the immediate value,
     /// storing the result in `rd`.
-    Xori { rd: u8, rs1: u8, imm: i16 },
+    Xori {
+        rd: Register,
+        rs1: Register,
+        imm: i16,
+    },
 
     /// OR Immediate instruction (RV32I base instruction set)
     ///
     /// Performs bitwise OR between register `rs1` and the immediate value,
     /// storing the result in `rd`.
-    Ori { rd: u8, rs1: u8, imm: i16 },
+    Ori {
+        rd: Register,
+        rs1: Register,
+        imm: i16,
+    },
 
     /// AND Immediate instruction (RV32I base instruction set)
     ///
     /// Performs bitwise AND between register `rs1` and the immediate value,
     /// storing the result in `rd`.
-    Andi { rd: u8, rs1: u8, imm: i16 },
+    Andi {
+        rd: Register,
+        rs1: Register,
+        imm: i16,
+    },
 
     /// Shift Left Logical Immediate instruction (RV32I base instruction set)
     ///
-    /// Shifts register `rs1` left by the immediate value (0-31 bits) and
-    /// stores the result in `rd`. Zero bits are shifted in from the right.
-    Slli { rd: u8, rs1: u8, imm: i16 },
+    /// Shifts register `rs1` left by the immediate value (0-31 bits, or 0-63 on RV64I)
+    /// and stores the result in `rd`. Zero bits are shifted in from the right.
+    Slli {
+        rd: Register,
+        rs1: Register,
+        imm: i16,
+    },
 
     /// Shift Right Logical Immediate instruction (RV32I base instruction set)
     ///
-    /// Shifts register `rs1` right by the immediate value (0-31 bits) and
-    /// stores the result in `rd`. Zero bits are shifted in from the left.
-    Srli { rd: u8, rs1: u8, imm: i16 },
+    /// Shifts register `rs1` right by the immediate value (0-31 bits, or 0-63 on RV64I)
+    /// and stores the result in `rd`. Zero bits are shifted in from the left.
+    Srli {
+        rd: Register,
+        rs1: Register,
+        imm: i16,
+    },
 
     /// Shift Right Arithmetic Immediate instruction (RV32I base instruction set)
     ///
-    /// Shifts register `rs1` right by the immediate value (0-31 bits) and
-    /// stores the result in `rd`. Sign bits are shifted in from the left.
-    Srai { rd: u8, rs1: u8, imm: i16 },
+    /// Shifts register `rs1` right by the immediate value (0-31 bits, or 0-63 on RV64I)
+    /// and stores the result in `rd`. Sign bits are shifted in from the left.
+    Srai {
+        rd: Register,
+        rs1: Register,
+        imm: i16,
+    },
 
     /// Set Less Than Immediate instruction (RV32I base instruction set)
     ///
     /// Compares register `rs1` with the immediate value using signed comparison.
     /// Sets `rd` to 1 if `rs1` < imm, otherwise sets `rd` to 0.
-    Slti { rd: u8, rs1: u8, imm: i16 },
+    Slti {
+        rd: Register,
+        rs1: Register,
+        imm: i16,
+    },
 
     /// Set Less Than Immediate Unsigned instruction (RV32I base instruction set)
     ///
     /// Compares register `rs1` with the immediate value using unsigned comparison.
     /// Sets `rd` to 1 if `rs1` < imm (unsigned), otherwise sets `rd` to 0.
-    Sltiu { rd: u8, rs1: u8, imm: i16 },
+    Sltiu {
+        rd: Register,
+        rs1: Register,
+        imm: i16,
+    },
 
     /// Load Byte instruction (RV32I base instruction set)
     ///
     /// Loads an 8-bit value from memory address `rs1 + imm` and sign-extends it to 32 bits,
     /// storing the result in `rd`.
-    Lb { rd: u8, rs1: u8, imm: i16 },
+    Lb {
+        rd: Register,
+        rs1: Register,
+        imm: i16,
+    },
 
     /// Load Halfword instruction (RV32I base instruction set)
     ///
     /// Loads a 16-bit value from memory address `rs1 + imm` and sign-extends it to 32 bits,
     /// storing the result in `rd`.
-    Lh { rd: u8, rs1: u8, imm: i16 },
+    Lh {
+        rd: Register,
+        rs1: Register,
+        imm: i16,
+    },
 
     /// Load Word instruction (RV32I base instruction set)
     ///
     /// Loads a 32-bit value from memory address `rs1 + imm` and stores it in `rd`.
     /// No sign extension is needed as it's a full 32-bit load.
-    Lw { rd: u8, rs1: u8, imm: i16 },
+    Lw {
+        rd: Register,
+        rs1: Register,
+        imm: i16,
+    },
 
     /// Load Byte Unsigned instruction (RV32I base instruction set)
     ///
     /// Loads an 8-bit value from memory address `rs1 + imm` and zero-extends it to 32 bits,
     /// storing the result in `rd`.
-    Lbu { rd: u8, rs1: u8, imm: i16 },
+    Lbu {
+        rd: Register,
+        rs1: Register,
+        imm: i16,
+    },
 
     /// Load Halfword Unsigned instruction (RV32I base instruction set)
     ///
     /// Loads a 16-bit value from memory address `rs1 + imm` and zero-extends it to 32 bits,
     /// storing the result in `rd`.
-    Lhu { rd: u8, rs1: u8, imm: i16 },
+    Lhu {
+        rd: Register,
+        rs1: Register,
+        imm: i16,
+    },
+
+    /// Store Byte instruction (RV32I base instruction set)
+    ///
+    /// Stores the lower 8 bits of register `rs2` to memory address `rs1 + imm`.
+    Sb {
+        rs1: Register,
+        rs2: Register,
+        imm: i16,
+    },
+
+    /// Store Halfword instruction (RV32I base instruction set)
+    ///
+    /// Stores the lower 16 bits of register `rs2` to memory address `rs1 + imm`.
+    Sh {
+        rs1: Register,
+        rs2: Register,
+        imm: i16,
+    },
+
+    /// Store Word instruction (RV32I base instruction set)
+    ///
+    /// Stores the full 32-bit value of register `rs2` to memory address `rs1 + imm`.
+    Sw {
+        rs1: Register,
+        rs2: Register,
+        imm: i16,
+    },
+
+    /// Branch if Equal instruction (RV32I base instruction set)
+    ///
+    /// Branches to `pc + imm` if registers `rs1` and `rs2` are equal.
+    /// The offset is a multiple of 2 in the range -4096..=4094.
+    Beq {
+        rs1: Register,
+        rs2: Register,
+        imm: i16,
+    },
+
+    /// Branch if Not Equal instruction (RV32I base instruction set)
+    ///
+    /// Branches to `pc + imm` if registers `rs1` and `rs2` are not equal.
+    /// The offset is a multiple of 2 in the range -4096..=4094.
+    Bne {
+        rs1: Register,
+        rs2: Register,
+        imm: i16,
+    },
+
+    /// Branch if Less Than instruction (RV32I base instruction set)
+    ///
+    /// Branches to `pc + imm` if register `rs1` is less than `rs2` using signed comparison.
+    /// The offset is a multiple of 2 in the range -4096..=4094.
+    Blt {
+        rs1: Register,
+        rs2: Register,
+        imm: i16,
+    },
+
+    /// Branch if Greater or Equal instruction (RV32I base instruction set)
+    ///
+    /// Branches to `pc + imm` if register `rs1` is greater than or equal to `rs2` using
+    /// signed comparison. The offset is a multiple of 2 in the range -4096..=4094.
+    Bge {
+        rs1: Register,
+        rs2: Register,
+        imm: i16,
+    },
+
+    /// Branch if Less Than Unsigned instruction (RV32I base instruction set)
+    ///
+    /// Branches to `pc + imm` if register `rs1` is less than `rs2` using unsigned comparison.
+    /// The offset is a multiple of 2 in the range -4096..=4094.
+    Bltu {
+        rs1: Register,
+        rs2: Register,
+        imm: i16,
+    },
+
+    /// Branch if Greater or Equal Unsigned instruction (RV32I base instruction set)
+    ///
+    /// Branches to `pc + imm` if register `rs1` is greater than or equal to `rs2` using
+    /// unsigned comparison. The offset is a multiple of 2 in the range -4096..=4094.
+    Bgeu {
+        rs1: Register,
+        rs2: Register,
+        imm: i16,
+    },
+
+    /// Load Upper Immediate instruction (RV32I base instruction set)
+    ///
+    /// Places the 20-bit upper immediate in the top bits of `rd`, filling the lowest
+    /// 12 bits with zeros. `imm` holds the value already shifted into place, so its
+    /// low 12 bits are always zero.
+    Lui { rd: Register, imm: i32 },
+
+    /// Add Upper Immediate to PC instruction (RV32I base instruction set)
+    ///
+    /// Adds the 20-bit upper immediate, shifted left by 12 bits, to the address of this
+    /// instruction and stores the result in `rd`. `imm` holds the value already shifted
+    /// into place, so its low 12 bits are always zero.
+    Auipc { rd: Register, imm: i32 },
+
+    /// Jump and Link instruction (RV32I base instruction set)
+    ///
+    /// Jumps to `pc + imm` and saves the return address in `rd`.
+    /// The offset is a multiple of 2 in the range -1048576..=1048574.
+    /// If `rd = x0`, the return address is discarded (simple jump).
+    Jal { rd: Register, imm: i32 },
 
     /// Jump and Link Register instruction (RV32I base instruction set)
     ///
     /// Jumps to address `rs1 + imm` and saves return address in `rd`.
     /// If `rd = x0`, the return address is discarded (simple jump).
-    Jalr { rd: u8, rs1: u8, imm: i16 },
+    Jalr {
+        rd: Register,
+        rs1: Register,
+        imm: i16,
+    },
 
     /// Environment Call instruction (RV32I base instruction set)
     ///
@@ -138,278 +497,4756 @@ pub enum RiscVInstruction {
     /// This instruction has no operands and is encoded as a specific system instruction.
     Ebreak,
 
-    /// Unsupported instruction
+    /// Fence instruction (RV32I base instruction set)
     ///
-    /// Contains the raw 32-bit instruction word for debugging purposes.
-    Unsupported(u32),
-}
+    /// Orders the memory and I/O accesses in the predecessor set `pred` before those in
+    /// the successor set `succ`. Each set holds the bits I (8), O (4), R (2) and W (1).
+    /// The VM runs a single hart with no devices, so every fence is a no-op.
+    Fence { pred: u8, succ: u8 },
 
-impl fmt::Display for RiscVInstruction {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            RiscVInstruction::Add { rd, rs1, rs2 } => {
-                write!(f, "add x{}, x{}, x{}", rd, rs1, rs2)
-            }
-            RiscVInstruction::Sub { rd, rs1, rs2 } => {
-                write!(f, "sub x{}, x{}, x{}", rd, rs1, rs2)
-            }
-            RiscVInstruction::Xor { rd, rs1, rs2 } => {
-                write!(f, "xor x{}, x{}, x{}", rd, rs1, rs2)
-            }
-            RiscVInstruction::Or { rd, rs1, rs2 } => {
-                write!(f, "or x{}, x{}, x{}", rd, rs1, rs2)
-            }
-            RiscVInstruction::Addi { rd, rs1, imm } => {
-                write!(f, "addi x{}, x{}, {}", rd, rs1, imm)
-            }
-            RiscVInstruction::Xori { rd, rs1, imm } => {
-                write!(f, "xori x{}, x{}, {}", rd, rs1, imm)
-            }
-            RiscVInstruction::Ori { rd, rs1, imm } => {
-                write!(f, "ori x{}, x{}, {}", rd, rs1, imm)
-            }
-            RiscVInstruction::Andi { rd, rs1, imm } => {
-                write!(f, "andi x{}, x{}, {}", rd, rs1, imm)
-            }
-            RiscVInstruction::Slli { rd, rs1, imm } => {
-                write!(f, "slli x{}, x{}, {}", rd, rs1, imm)
-            }
-            RiscVInstruction::Srli { rd, rs1, imm } => {
-                write!(f, "srli x{}, x{}, {}", rd, rs1, imm)
-            }
-            RiscVInstruction::Srai { rd, rs1, imm } => {
-                write!(f, "srai x{}, x{}, {}", rd, rs1, imm)
-            }
-            RiscVInstruction::Slti { rd, rs1, imm } => {
-                write!(f, "slti x{}, x{}, {}", rd, rs1, imm)
-            }
-            RiscVInstruction::Sltiu { rd, rs1, imm } => {
-                write!(f, "sltiu x{}, x{}, {}", rd, rs1, imm)
-            }
-            RiscVInstruction::Lb { rd, rs1, imm } => {
-                write!(f, "lb x{}, {}(x{})", rd, imm, rs1)
-            }
-            RiscVInstruction::Lh { rd, rs1, imm } => {
-                write!(f, "lh x{}, {}(x{})", rd, imm, rs1)
-            }
-            RiscVInstruction::Lw { rd, rs1, imm } => {
-                write!(f, "lw x{}, {}(x{})", rd, imm, rs1)
-            }
-            RiscVInstruction::Lbu { rd, rs1, imm } => {
-                write!(f, "lbu x{}, {}(x{})", rd, imm, rs1)
-            }
-            RiscVInstruction::Lhu { rd, rs1, imm } => {
-                write!(f, "lhu x{}, {}(x{})", rd, imm, rs1)
-            }
-            RiscVInstruction::Jalr { rd, rs1, imm } => {
-                write!(f, "jalr x{}, x{}, {}", rd, rs1, imm)
-            }
-            RiscVInstruction::Ecall => {
-                write!(f, "ecall")
-            }
-            RiscVInstruction::Ebreak => {
-                write!(f, "ebreak")
-            }
-            RiscVInstruction::Unsupported(opcode) => {
-                write!(f, "unsupported(0x{:08x})", opcode)
-            }
-        }
-    }
-}
+    /// Total Store Ordering Fence instruction (RV32I base instruction set)
+    ///
+    /// Orders loads before later loads and stores, and stores before later stores.
+    /// A no-op in the VM, like [`RiscVInstruction::Fence`].
+    FenceTso,
 
-const REG_OPCODE: u32 = 0x33;
-const ADD_FUNCT3: u8 = 0x0;
-const ADD_FUNCT7: u32 = 0x00;
-const SUB_FUNCT7: u32 = 0x20;
-const XOR_FUNCT3: u8 = 0x4;
-const XOR_FUNCT7: u32 = 0x00;
-const OR_FUNCT3: u8 = 0x6;
-const OR_FUNCT7: u32 = 0x00;
+    /// Instruction-Fetch Fence instruction (Zifencei standard extension)
+    ///
+    /// Makes earlier stores visible to later instruction fetches. Code is loaded
+    /// outside of guest memory and cannot be written by the guest, so translated code
+    /// never goes stale and there is nothing to invalidate: an engine either rejects
+    /// `fence.i` when loading code, or, with Zifencei enabled, runs it as a no-op.
+    FenceI,
 
-const IMM_OPCODE: u32 = 0x13;
-const ADDI_FUNCT3: u8 = 0x0;
-const SLTI_FUNCT3: u8 = 0x2;
-const SLTIU_FUNCT3: u8 = 0x3;
-const XORI_FUNCT3: u8 = 0x4;
-const ORI_FUNCT3: u8 = 0x6;
-const ANDI_FUNCT3: u8 = 0x7;
-const SLLI_FUNCT3: u8 = 0x1;
-const SRLI_FUNCT3: u8 = 0x5;
-const SLLI_FUNCT7: u32 = 0x00;
-const SRLI_FUNCT7: u32 = 0x00;
-const SRAI_FUNCT7: u32 = 0x20;
+    /// Atomic Read/Write CSR instruction (Zicsr standard extension)
+    ///
+    /// Stores the old value of CSR `csr` in `rd` and writes register `rs1` to the CSR.
+    Csrrw {
+        rd: Register,
+        rs1: Register,
+        csr: u16,
+    },
 
-const LOAD_OPCODE: u32 = 0x03;
-const LB_FUNCT3: u8 = 0x0;
-const LH_FUNCT3: u8 = 0x1;
-const LW_FUNCT3: u8 = 0x2;
-const LBU_FUNCT3: u8 = 0x4;
-const LHU_FUNCT3: u8 = 0x5;
+    /// Atomic Read and Set Bits in CSR instruction (Zicsr standard extension)
+    ///
+    /// Stores the old value of CSR `csr` in `rd` and sets the bits of the CSR that are
+    /// set in register `rs1`. The CSR is not written if `rs1` is `x0`.
+    Csrrs {
+        rd: Register,
+        rs1: Register,
+        csr: u16,
+    },
 
-const JALR_OPCODE: u32 = 0x67;
-const JALR_FUNCT3: u32 = 0x0;
+    /// Atomic Read and Clear Bits in CSR instruction (Zicsr standard extension)
+    ///
+    /// Stores the old value of CSR `csr` in `rd` and clears the bits of the CSR that are
+    /// set in register `rs1`. The CSR is not written if `rs1` is `x0`.
+    Csrrc {
+        rd: Register,
+        rs1: Register,
+        csr: u16,
+    },
 
-const SYSTEM_OPCODE: u32 = 0x73;
-const SYSTEM_FUNCT3: u32 = 0x0;
-const ECALL_IMM: u32 = 0x0;
-const EBREAK_IMM: u32 = 0x1;
+    /// Atomic Read/Write CSR Immediate instruction (Zicsr standard extension)
+    ///
+    /// Stores the old value of CSR `csr` in `rd` and writes the 5-bit unsigned
+    /// immediate to the CSR.
+    Csrrwi { rd: Register, imm: u8, csr: u16 },
 
-const OPCODE_MASK: u32 = 0x7f;
-const FUNCT3_MASK: u32 = 0x7000;
-const RD_MASK: u32 = 0xf80;
-const RS1_MASK: u32 = 0xf8000;
-const RS2_MASK: u32 = 0x1f00000;
-const IMM_I_MASK: u32 = 0xfff00000;
-const FUNCT7_MASK: u32 = 0xfe000000;
+    /// Atomic Read and Set Bits in CSR Immediate instruction (Zicsr standard extension)
+    ///
+    /// Stores the old value of CSR `csr` in `rd` and sets the bits of the CSR that are
+    /// set in the 5-bit unsigned immediate. The CSR is not written if the immediate is 0.
+    Csrrsi { rd: Register, imm: u8, csr: u16 },
 
-const FUNCT3_SHIFT: u32 = 12;
-const RD_SHIFT: u32 = 7;
-const RS1_SHIFT: u32 = 15;
-const RS2_SHIFT: u32 = 20;
-const IMM_I_SHIFT: u32 = 20;
-const FUNCT7_SHIFT: u32 = 25;
+    /// Atomic Read and Clear Bits in CSR Immediate instruction (Zicsr standard extension)
+    ///
+    /// Stores the old value of CSR `csr` in `rd` and clears the bits of the CSR that are
+    /// set in the 5-bit unsigned immediate. The CSR is not written if the immediate is 0.
+    Csrrci { rd: Register, imm: u8, csr: u16 },
 
-impl RiscVInstruction {
-    /// Decode a 32-bit instruction word into a RiscVInstruction
+    /// Shift Left by 1 and Add instruction (Zba standard extension)
     ///
-    /// # Arguments
+    /// Shifts register `rs1` left by 1 bit, adds register `rs2` and stores the result
+    /// in `rd`. Used to index arrays of 16-bit elements.
+    Sh1add {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+
+    /// Shift Left by 2 and Add instruction (Zba standard extension)
     ///
-    /// * `word` - The 32-bit instruction word to decode
-    pub fn decode(word: u32) -> RiscVInstruction {
-        let opcode = word & OPCODE_MASK;
+    /// Shifts register `rs1` left by 2 bits, adds register `rs2` and stores the result
+    /// in `rd`. Used to index arrays of 32-bit elements.
+    Sh2add {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
 
-        match opcode {
-            REG_OPCODE => {
-                let funct3 = (((word & FUNCT3_MASK) >> FUNCT3_SHIFT) & 0x7) as u8;
-                let funct7 = (word & FUNCT7_MASK) >> FUNCT7_SHIFT;
-                let rd = ((word & RD_MASK) >> RD_SHIFT) as u8;
-                let rs1 = ((word & RS1_MASK) >> RS1_SHIFT) as u8;
-                let rs2 = ((word & RS2_MASK) >> RS2_SHIFT) as u8;
+    /// Shift Left by 3 and Add instruction (Zba standard extension)
+    ///
+    /// Shifts register `rs1` left by 3 bits, adds register `rs2` and stores the result
+    /// in `rd`. Used to index arrays of 64-bit elements.
+    Sh3add {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
 
-                match funct3 {
-                    ADD_FUNCT3 => {
-                        if funct7 == ADD_FUNCT7 {
-                            RiscVInstruction::Add { rd, rs1, rs2 }
-                        } else if funct7 == SUB_FUNCT7 {
-                            RiscVInstruction::Sub { rd, rs1, rs2 }
-                        } else {
-                            RiscVInstruction::Unsupported(word)
-                        }
-                    }
-                    XOR_FUNCT3 => {
-                        if funct7 == XOR_FUNCT7 {
-                            RiscVInstruction::Xor { rd, rs1, rs2 }
-                        } else {
-                            RiscVInstruction::Unsupported(word)
-                        }
-                    }
-                    OR_FUNCT3 => {
-                        if funct7 == OR_FUNCT7 {
-                            RiscVInstruction::Or { rd, rs1, rs2 }
-                        } else {
-                            RiscVInstruction::Unsupported(word)
-                        }
-                    }
-                    _ => RiscVInstruction::Unsupported(word),
-                }
-            }
-            IMM_OPCODE => {
-                let funct3 = (((word & FUNCT3_MASK) >> FUNCT3_SHIFT) & 0x7) as u8;
-                let rd = ((word & RD_MASK) >> RD_SHIFT) as u8;
-                let rs1 = ((word & RS1_MASK) >> RS1_SHIFT) as u8;
-                let imm = ((word & IMM_I_MASK) as i32 >> IMM_I_SHIFT) as i16;
-                let funct7 = (word & FUNCT7_MASK) >> FUNCT7_SHIFT;
+    /// AND with inverted operand instruction (Zbb standard extension)
+    ///
+    /// Performs bitwise AND between register `rs1` and the bitwise inverse of
+    /// register `rs2`, storing the result in `rd`.
+    Andn {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
 
-                match funct3 {
-                    ADDI_FUNCT3 => RiscVInstruction::Addi { rd, rs1, imm },
-                    SLTI_FUNCT3 => RiscVInstruction::Slti { rd, rs1, imm },
-                    SLTIU_FUNCT3 => RiscVInstruction::Sltiu { rd, rs1, imm },
-                    SLLI_FUNCT3 => {
-                        if funct7 == SLLI_FUNCT7 {
-                            let shift_imm = imm & 0x1f;
-                            RiscVInstruction::Slli {
-                                rd,
-                                rs1,
-                                imm: shift_imm,
-                            }
-                        } else {
-                            RiscVInstruction::Unsupported(word)
-                        }
-                    }
-                    SRLI_FUNCT3 => {
-                        if funct7 == SRAI_FUNCT7 {
-                            let shift_imm = imm & 0x1f;
-                            RiscVInstruction::Srai {
-                                rd,
-                                rs1,
-                                imm: shift_imm,
-                            }
-                        } else if funct7 == SRLI_FUNCT7 {
-                            let shift_imm = imm & 0x1f;
-                            RiscVInstruction::Srli {
-                                rd,
-                                rs1,
-                                imm: shift_imm,
-                            }
-                        } else {
-                            RiscVInstruction::Unsupported(word)
-                        }
-                    }
-                    XORI_FUNCT3 => RiscVInstruction::Xori { rd, rs1, imm },
-                    ORI_FUNCT3 => RiscVInstruction::Ori { rd, rs1, imm },
-                    ANDI_FUNCT3 => RiscVInstruction::Andi { rd, rs1, imm },
-                    _ => unreachable!("All 3-bit funct3 values are handled above"),
-                }
-            }
-            LOAD_OPCODE => {
-                let funct3 = (((word & FUNCT3_MASK) >> FUNCT3_SHIFT) & 0x7) as u8;
-                let rd = ((word & RD_MASK) >> RD_SHIFT) as u8;
-                let rs1 = ((word & RS1_MASK) >> RS1_SHIFT) as u8;
-                let imm = ((word & IMM_I_MASK) as i32 >> IMM_I_SHIFT) as i16;
+    /// OR with inverted operand instruction (Zbb standard extension)
+    ///
+    /// Performs bitwise OR between register `rs1` and the bitwise inverse of
+    /// register `rs2`, storing the result in `rd`.
+    Orn {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
 
-                match funct3 {
-                    LB_FUNCT3 => RiscVInstruction::Lb { rd, rs1, imm },
-                    LH_FUNCT3 => RiscVInstruction::Lh { rd, rs1, imm },
-                    LW_FUNCT3 => RiscVInstruction::Lw { rd, rs1, imm },
-                    LBU_FUNCT3 => RiscVInstruction::Lbu { rd, rs1, imm },
-                    LHU_FUNCT3 => RiscVInstruction::Lhu { rd, rs1, imm },
-                    _ => RiscVInstruction::Unsupported(word),
-                }
-            }
-            JALR_OPCODE => {
-                let funct3 = (word & FUNCT3_MASK) >> FUNCT3_SHIFT;
-                if funct3 == JALR_FUNCT3 {
-                    let rd = ((word & RD_MASK) >> RD_SHIFT) as u8;
-                    let rs1 = ((word & RS1_MASK) >> RS1_SHIFT) as u8;
-                    let imm = ((word & IMM_I_MASK) as i32 >> IMM_I_SHIFT) as i16;
+    /// Exclusive NOR instruction (Zbb standard extension)
+    ///
+    /// Performs bitwise XOR between registers `rs1` and `rs2` and stores the bitwise
+    /// inverse of the result in `rd`.
+    Xnor {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
 
-                    RiscVInstruction::Jalr { rd, rs1, imm }
-                } else {
-                    RiscVInstruction::Unsupported(word)
-                }
-            }
-            SYSTEM_OPCODE => {
-                let funct3 = (word & FUNCT3_MASK) >> FUNCT3_SHIFT;
-                if funct3 == SYSTEM_FUNCT3 {
-                    let rd = ((word & RD_MASK) >> RD_SHIFT) as u8;
-                    let rs1 = ((word & RS1_MASK) >> RS1_SHIFT) as u8;
-                    let imm = (word & IMM_I_MASK) >> IMM_I_SHIFT;
+    /// Count Leading Zeros instruction (Zbb standard extension)
+    ///
+    /// Stores the number of zero bits above the most significant set bit of register
+    /// `rs1` in `rd`. The result is 32 if `rs1` is zero.
+    Clz { rd: Register, rs1: Register },
 
-                    // ECALL and EBREAK require rd=0 and rs1=0
-                    if rd == 0 && rs1 == 0 {
-                        match imm {
-                            ECALL_IMM => RiscVInstruction::Ecall,
-                            EBREAK_IMM => RiscVInstruction::Ebreak,
-                            _ => RiscVInstruction::Unsupported(word),
-                        }
-                    } else {
-                        RiscVInstruction::Unsupported(word)
-                    }
-                } else {
-                    RiscVInstruction::Unsupported(word)
-                }
-            }
-            _ => RiscVInstruction::Unsupported(word),
+    /// Count Trailing Zeros instruction (Zbb standard extension)
+    ///
+    /// Stores the number of zero bits below the least significant set bit of register
+    /// `rs1` in `rd`. The result is 32 if `rs1` is zero.
+    Ctz { rd: Register, rs1: Register },
+
+    /// Count Population instruction (Zbb standard extension)
+    ///
+    /// Stores the number of set bits in register `rs1` in `rd`.
+    Cpop { rd: Register, rs1: Register },
+
+    /// Maximum instruction (Zbb standard extension)
+    ///
+    /// Stores the larger of registers `rs1` and `rs2` in `rd` using signed comparison.
+    Max {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+
+    /// Maximum Unsigned instruction (Zbb standard extension)
+    ///
+    /// Stores the larger of registers `rs1` and `rs2` in `rd` using unsigned comparison.
+    Maxu {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+
+    /// Minimum instruction (Zbb standard extension)
+    ///
+    /// Stores the smaller of registers `rs1` and `rs2` in `rd` using signed comparison.
+    Min {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+
+    /// Minimum Unsigned instruction (Zbb standard extension)
+    ///
+    /// Stores the smaller of registers `rs1` and `rs2` in `rd` using unsigned comparison.
+    Minu {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+
+    /// Sign-Extend Byte instruction (Zbb standard extension)
+    ///
+    /// Sign-extends the lowest 8 bits of register `rs1` to 32 bits and stores the
+    /// result in `rd`.
+    SextB { rd: Register, rs1: Register },
+
+    /// Sign-Extend Halfword instruction (Zbb standard extension)
+    ///
+    /// Sign-extends the lowest 16 bits of register `rs1` to 32 bits and stores the
+    /// result in `rd`.
+    SextH { rd: Register, rs1: Register },
+
+    /// Zero-Extend Halfword instruction (Zbb standard extension)
+    ///
+    /// Zero-extends the lowest 16 bits of register `rs1` to 32 bits and stores the
+    /// result in `rd`.
+    ZextH { rd: Register, rs1: Register },
+
+    /// Rotate Left instruction (Zbb standard extension)
+    ///
+    /// Rotates register `rs1` left by the amount held in the lower 5 bits of register
+    /// `rs2` and stores the result in `rd`.
+    Rol {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+
+    /// Rotate Right instruction (Zbb standard extension)
+    ///
+    /// Rotates register `rs1` right by the amount held in the lower 5 bits of register
+    /// `rs2` and stores the result in `rd`.
+    Ror {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+
+    /// Rotate Right Immediate instruction (Zbb standard extension)
+    ///
+    /// Rotates register `rs1` right by the immediate value (0-31 bits) and stores the
+    /// result in `rd`.
+    Rori {
+        rd: Register,
+        rs1: Register,
+        imm: i16,
+    },
+
+    /// OR-Combine Bytes instruction (Zbb standard extension)
+    ///
+    /// Sets each byte of `rd` to all ones if the corresponding byte of register `rs1`
+    /// is non-zero, and to zero otherwise. Used to find the terminator of a string.
+    OrcB { rd: Register, rs1: Register },
+
+    /// Byte-Reverse instruction (Zbb standard extension)
+    ///
+    /// Reverses the order of the bytes in register `rs1` and stores the result in `rd`,
+    /// converting between little-endian and big-endian.
+    Rev8 { rd: Register, rs1: Register },
+
+    /// Single-Bit Clear instruction (Zbs standard extension)
+    ///
+    /// Clears the bit of register `rs1` indexed by the lower 5 bits of register `rs2`
+    /// and stores the result in `rd`.
+    Bclr {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+
+    /// Single-Bit Clear Immediate instruction (Zbs standard extension)
+    ///
+    /// Clears the bit of register `rs1` indexed by the immediate value (0-31) and
+    /// stores the result in `rd`.
+    Bclri {
+        rd: Register,
+        rs1: Register,
+        imm: i16,
+    },
+
+    /// Single-Bit Extract instruction (Zbs standard extension)
+    ///
+    /// Stores the bit of register `rs1` indexed by the lower 5 bits of register `rs2`
+    /// in the lowest bit of `rd`, clearing the other bits.
+    Bext {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+
+    /// Single-Bit Extract Immediate instruction (Zbs standard extension)
+    ///
+    /// Stores the bit of register `rs1` indexed by the immediate value (0-31) in the
+    /// lowest bit of `rd`, clearing the other bits.
+    Bexti {
+        rd: Register,
+        rs1: Register,
+        imm: i16,
+    },
+
+    /// Single-Bit Invert instruction (Zbs standard extension)
+    ///
+    /// Inverts the bit of register `rs1` indexed by the lower 5 bits of register `rs2`
+    /// and stores the result in `rd`.
+    Binv {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+
+    /// Single-Bit Invert Immediate instruction (Zbs standard extension)
+    ///
+    /// Inverts the bit of register `rs1` indexed by the immediate value (0-31) and
+    /// stores the result in `rd`.
+    Binvi {
+        rd: Register,
+        rs1: Register,
+        imm: i16,
+    },
+
+    /// Single-Bit Set instruction (Zbs standard extension)
+    ///
+    /// Sets the bit of register `rs1` indexed by the lower 5 bits of register `rs2`
+    /// and stores the result in `rd`.
+    Bset {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+
+    /// Single-Bit Set Immediate instruction (Zbs standard extension)
+    ///
+    /// Sets the bit of register `rs1` indexed by the immediate value (0-31) and stores
+    /// the result in `rd`.
+    Bseti {
+        rd: Register,
+        rs1: Register,
+        imm: i16,
+    },
+
+    /// SHA-256 Sigma0 instruction (Zknh standard extension)
+    ///
+    /// Computes the SHA-256 message schedule function σ0 of register `rs1` and stores
+    /// the result in `rd`.
+    Sha256sig0 { rd: Register, rs1: Register },
+
+    /// SHA-256 Sigma1 instruction (Zknh standard extension)
+    ///
+    /// Computes the SHA-256 message schedule function σ1 of register `rs1` and stores
+    /// the result in `rd`.
+    Sha256sig1 { rd: Register, rs1: Register },
+
+    /// SHA-256 Sum0 instruction (Zknh standard extension)
+    ///
+    /// Computes the SHA-256 compression function Σ0 of register `rs1` and stores the
+    /// result in `rd`.
+    Sha256sum0 { rd: Register, rs1: Register },
+
+    /// SHA-256 Sum1 instruction (Zknh standard extension)
+    ///
+    /// Computes the SHA-256 compression function Σ1 of register `rs1` and stores the
+    /// result in `rd`.
+    Sha256sum1 { rd: Register, rs1: Register },
+
+    /// SHA-512 Sum0 instruction (Zknh standard extension)
+    ///
+    /// Computes one half of the SHA-512 compression function Σ0. Registers `rs1` and
+    /// `rs2` hold the half being computed and the other half of the 64-bit input.
+    Sha512sum0r {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+
+    /// SHA-512 Sum1 instruction (Zknh standard extension)
+    ///
+    /// Computes one half of the SHA-512 compression function Σ1. Registers `rs1` and
+    /// `rs2` hold the half being computed and the other half of the 64-bit input.
+    Sha512sum1r {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+
+    /// SHA-512 Sigma0 low instruction (Zknh standard extension)
+    ///
+    /// Computes the low half of the SHA-512 message schedule function σ0. Registers
+    /// `rs1` and `rs2` hold the low and high halves of the 64-bit input.
+    Sha512sig0l {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+
+    /// SHA-512 Sigma0 high instruction (Zknh standard extension)
+    ///
+    /// Computes the high half of the SHA-512 message schedule function σ0. Registers
+    /// `rs1` and `rs2` hold the high and low halves of the 64-bit input.
+    Sha512sig0h {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+
+    /// SHA-512 Sigma1 low instruction (Zknh standard extension)
+    ///
+    /// Computes the low half of the SHA-512 message schedule function σ1. Registers
+    /// `rs1` and `rs2` hold the low and high halves of the 64-bit input.
+    Sha512sig1l {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+
+    /// SHA-512 Sigma1 high instruction (Zknh standard extension)
+    ///
+    /// Computes the high half of the SHA-512 message schedule function σ1. Registers
+    /// `rs1` and `rs2` hold the high and low halves of the 64-bit input.
+    Sha512sig1h {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+
+    /// Load-Reserved Word instruction (A standard extension)
+    ///
+    /// Loads the word at address `rs1` into `rd` and reserves the address for a later
+    /// [`RiscVInstruction::ScW`]. `aq` and `rl` request acquire and release ordering,
+    /// which has no effect on the VM's single hart.
+    LrW {
+        rd: Register,
+        rs1: Register,
+        aq: bool,
+        rl: bool,
+    },
+
+    /// Store-Conditional Word instruction (A standard extension)
+    ///
+    /// Stores `rs2` to the word at address `rs1` and writes 0 to `rd` if the address
+    /// is reserved by an earlier [`RiscVInstruction::LrW`], and otherwise writes 1 to
+    /// `rd` without storing. Either way the reservation is released.
+    ScW {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+        aq: bool,
+        rl: bool,
+    },
+
+    /// Atomic Swap Word instruction (A standard extension)
+    ///
+    /// Atomically loads the word at address `rs1` into `rd` and stores `rs2`.
+    AmoswapW {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+        aq: bool,
+        rl: bool,
+    },
+
+    /// Atomic Add Word instruction (A standard extension)
+    ///
+    /// Atomically loads the word at address `rs1` into `rd` and stores the sum of it and
+    /// `rs2`, wrapping on overflow.
+    AmoaddW {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+        aq: bool,
+        rl: bool,
+    },
+
+    /// Atomic XOR Word instruction (A standard extension)
+    ///
+    /// Atomically loads the word at address `rs1` into `rd` and stores the bitwise XOR of
+    /// it and `rs2`.
+    AmoxorW {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+        aq: bool,
+        rl: bool,
+    },
+
+    /// Atomic AND Word instruction (A standard extension)
+    ///
+    /// Atomically loads the word at address `rs1` into `rd` and stores the bitwise AND of
+    /// it and `rs2`.
+    AmoandW {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+        aq: bool,
+        rl: bool,
+    },
+
+    /// Atomic OR Word instruction (A standard extension)
+    ///
+    /// Atomically loads the word at address `rs1` into `rd` and stores the bitwise OR of it
+    /// and `rs2`.
+    AmoorW {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+        aq: bool,
+        rl: bool,
+    },
+
+    /// Atomic Minimum Word instruction (A standard extension)
+    ///
+    /// Atomically loads the word at address `rs1` into `rd` and stores the smaller of it
+    /// and `rs2` as signed integers.
+    AmominW {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+        aq: bool,
+        rl: bool,
+    },
+
+    /// Atomic Maximum Word instruction (A standard extension)
+    ///
+    /// Atomically loads the word at address `rs1` into `rd` and stores the larger of it and
+    /// `rs2` as signed integers.
+    AmomaxW {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+        aq: bool,
+        rl: bool,
+    },
+
+    /// Atomic Minimum Unsigned Word instruction (A standard extension)
+    ///
+    /// Atomically loads the word at address `rs1` into `rd` and stores the smaller of it
+    /// and `rs2` as unsigned integers.
+    AmominuW {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+        aq: bool,
+        rl: bool,
+    },
+
+    /// Atomic Maximum Unsigned Word instruction (A standard extension)
+    ///
+    /// Atomically loads the word at address `rs1` into `rd` and stores the larger of it and
+    /// `rs2` as unsigned integers.
+    AmomaxuW {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+        aq: bool,
+        rl: bool,
+    },
+
+    /// Floating-point Load Word instruction (F standard extension)
+    ///
+    /// Loads the single-precision value at address `rs1 + imm` into `rd`, NaN-boxing it
+    /// by setting the upper 32 bits of the register.
+    Flw {
+        rd: FloatRegister,
+        rs1: Register,
+        imm: i16,
+    },
+
+    /// Floating-point Store Word instruction (F standard extension)
+    ///
+    /// Stores the low 32 bits of `rs2` to address `rs1 + imm`.
+    Fsw {
+        rs1: Register,
+        rs2: FloatRegister,
+        imm: i16,
+    },
+
+    /// Floating-point Load Doubleword instruction (D standard extension)
+    ///
+    /// Loads the double-precision value at address `rs1 + imm` into `rd`.
+    Fld {
+        rd: FloatRegister,
+        rs1: Register,
+        imm: i16,
+    },
+
+    /// Floating-point Store Doubleword instruction (D standard extension)
+    ///
+    /// Stores `rs2` to address `rs1 + imm`.
+    Fsd {
+        rs1: Register,
+        rs2: FloatRegister,
+        imm: i16,
+    },
+
+    /// Fused Multiply-Add Single-Precision instruction (F standard extension)
+    ///
+    /// Writes `rs1 * rs2 + rs3` to `rd`, rounded once with `rm`.
+    FmaddS {
+        rd: FloatRegister,
+        rs1: FloatRegister,
+        rs2: FloatRegister,
+        rs3: FloatRegister,
+        rm: RoundingMode,
+    },
+
+    /// Fused Multiply-Subtract Single-Precision instruction (F standard extension)
+    ///
+    /// Writes `rs1 * rs2 - rs3` to `rd`, rounded once with `rm`.
+    FmsubS {
+        rd: FloatRegister,
+        rs1: FloatRegister,
+        rs2: FloatRegister,
+        rs3: FloatRegister,
+        rm: RoundingMode,
+    },
+
+    /// Fused Negated Multiply-Subtract Single-Precision instruction (F standard extension)
+    ///
+    /// Writes `-(rs1 * rs2) + rs3` to `rd`, rounded once with `rm`.
+    FnmsubS {
+        rd: FloatRegister,
+        rs1: FloatRegister,
+        rs2: FloatRegister,
+        rs3: FloatRegister,
+        rm: RoundingMode,
+    },
+
+    /// Fused Negated Multiply-Add Single-Precision instruction (F standard extension)
+    ///
+    /// Writes `-(rs1 * rs2) - rs3` to `rd`, rounded once with `rm`.
+    FnmaddS {
+        rd: FloatRegister,
+        rs1: FloatRegister,
+        rs2: FloatRegister,
+        rs3: FloatRegister,
+        rm: RoundingMode,
+    },
+
+    /// Floating-point Add Single-Precision instruction (F standard extension)
+    ///
+    /// Writes `rs1 + rs2` to `rd`, rounded with `rm`.
+    FaddS {
+        rd: FloatRegister,
+        rs1: FloatRegister,
+        rs2: FloatRegister,
+        rm: RoundingMode,
+    },
+
+    /// Floating-point Subtract Single-Precision instruction (F standard extension)
+    ///
+    /// Writes `rs1 - rs2` to `rd`, rounded with `rm`.
+    FsubS {
+        rd: FloatRegister,
+        rs1: FloatRegister,
+        rs2: FloatRegister,
+        rm: RoundingMode,
+    },
+
+    /// Floating-point Multiply Single-Precision instruction (F standard extension)
+    ///
+    /// Writes `rs1 * rs2` to `rd`, rounded with `rm`.
+    FmulS {
+        rd: FloatRegister,
+        rs1: FloatRegister,
+        rs2: FloatRegister,
+        rm: RoundingMode,
+    },
+
+    /// Floating-point Divide Single-Precision instruction (F standard extension)
+    ///
+    /// Writes `rs1 / rs2` to `rd`, rounded with `rm`.
+    FdivS {
+        rd: FloatRegister,
+        rs1: FloatRegister,
+        rs2: FloatRegister,
+        rm: RoundingMode,
+    },
+
+    /// Floating-point Square Root Single-Precision instruction (F standard extension)
+    ///
+    /// Writes the square root of `rs1` to `rd`, rounded with `rm`.
+    FsqrtS {
+        rd: FloatRegister,
+        rs1: FloatRegister,
+        rm: RoundingMode,
+    },
+
+    /// Floating-point Sign Injection Single-Precision instruction (F standard extension)
+    ///
+    /// Writes `rs1` with the sign of `rs2` to `rd`.
+    FsgnjS {
+        rd: FloatRegister,
+        rs1: FloatRegister,
+        rs2: FloatRegister,
+    },
+
+    /// Floating-point Negated Sign Injection Single-Precision instruction (F standard extension)
+    ///
+    /// Writes `rs1` with the opposite of the sign of `rs2` to `rd`.
+    FsgnjnS {
+        rd: FloatRegister,
+        rs1: FloatRegister,
+        rs2: FloatRegister,
+    },
+
+    /// Floating-point XOR Sign Injection Single-Precision instruction (F standard extension)
+    ///
+    /// Writes `rs1` with the XOR of the signs of `rs1` and `rs2` to `rd`.
+    FsgnjxS {
+        rd: FloatRegister,
+        rs1: FloatRegister,
+        rs2: FloatRegister,
+    },
+
+    /// Floating-point Minimum Single-Precision instruction (F standard extension)
+    ///
+    /// Writes the smaller of `rs1` and `rs2` to `rd`, with `-0` less than `+0`. If only
+    /// one operand is a NaN, the other is written.
+    FminS {
+        rd: FloatRegister,
+        rs1: FloatRegister,
+        rs2: FloatRegister,
+    },
+
+    /// Floating-point Maximum Single-Precision instruction (F standard extension)
+    ///
+    /// Writes the larger of `rs1` and `rs2` to `rd`, with `-0` less than `+0`. If only
+    /// one operand is a NaN, the other is written.
+    FmaxS {
+        rd: FloatRegister,
+        rs1: FloatRegister,
+        rs2: FloatRegister,
+    },
+
+    /// Floating-point Equal Single-Precision instruction (F standard extension)
+    ///
+    /// Writes 1 to the integer register `rd` if `rs1` equals `rs2`, and 0 otherwise.
+    /// Only signaling NaNs raise the invalid flag.
+    FeqS {
+        rd: Register,
+        rs1: FloatRegister,
+        rs2: FloatRegister,
+    },
+
+    /// Floating-point Less Than Single-Precision instruction (F standard extension)
+    ///
+    /// Writes 1 to the integer register `rd` if `rs1` is less than `rs2`, and 0
+    /// otherwise. Any NaN raises the invalid flag.
+    FltS {
+        rd: Register,
+        rs1: FloatRegister,
+        rs2: FloatRegister,
+    },
+
+    /// Floating-point Less Than or Equal Single-Precision instruction (F standard extension)
+    ///
+    /// Writes 1 to the integer register `rd` if `rs1` is less than or equal to `rs2`,
+    /// and 0 otherwise. Any NaN raises the invalid flag.
+    FleS {
+        rd: Register,
+        rs1: FloatRegister,
+        rs2: FloatRegister,
+    },
+
+    /// Floating-point Convert Single to Word instruction (F standard extension)
+    ///
+    /// Converts `rs1` to a signed integer, rounded with `rm`, and writes it to the
+    /// integer register `rd`. Out of range values saturate, and NaNs convert to the
+    /// largest integer.
+    FcvtWS {
+        rd: Register,
+        rs1: FloatRegister,
+        rm: RoundingMode,
+    },
+
+    /// Floating-point Convert Single to Unsigned Word instruction (F standard extension)
+    ///
+    /// Converts `rs1` to an unsigned integer, rounded with `rm`, and writes it to the
+    /// integer register `rd`. Out of range values saturate, and NaNs convert to the
+    /// largest integer.
+    FcvtWuS {
+        rd: Register,
+        rs1: FloatRegister,
+        rm: RoundingMode,
+    },
+
+    /// Floating-point Move Word to Integer instruction (F standard extension)
+    ///
+    /// Writes the low 32 bits of `rs1` to the integer register `rd` unchanged.
+    FmvXW { rd: Register, rs1: FloatRegister },
+
+    /// Floating-point Classify Single-Precision instruction (F standard extension)
+    ///
+    /// Writes a mask with one of its low 10 bits set to the integer register `rd`, to
+    /// classify `rs1` as a negative infinity, normal, subnormal or zero, a positive
+    /// zero, subnormal, normal or infinity, or a signaling or quiet NaN.
+    FclassS { rd: Register, rs1: FloatRegister },
+
+    /// Floating-point Convert Word to Single instruction (F standard extension)
+    ///
+    /// Converts the signed integer in the integer register `rs1` to a floating-point
+    /// value, rounded with `rm`, and writes it to `rd`.
+    FcvtSW {
+        rd: FloatRegister,
+        rs1: Register,
+        rm: RoundingMode,
+    },
+
+    /// Floating-point Convert Unsigned Word to Single instruction (F standard extension)
+    ///
+    /// Converts the unsigned integer in the integer register `rs1` to a floating-point
+    /// value, rounded with `rm`, and writes it to `rd`.
+    FcvtSWu {
+        rd: FloatRegister,
+        rs1: Register,
+        rm: RoundingMode,
+    },
+
+    /// Floating-point Move Word from Integer instruction (F standard extension)
+    ///
+    /// Writes the integer register `rs1` to `rd` unchanged, NaN-boxing it.
+    FmvWX { rd: FloatRegister, rs1: Register },
+
+    /// Fused Multiply-Add Double-Precision instruction (D standard extension)
+    ///
+    /// Writes `rs1 * rs2 + rs3` to `rd`, rounded once with `rm`.
+    FmaddD {
+        rd: FloatRegister,
+        rs1: FloatRegister,
+        rs2: FloatRegister,
+        rs3: FloatRegister,
+        rm: RoundingMode,
+    },
+
+    /// Fused Multiply-Subtract Double-Precision instruction (D standard extension)
+    ///
+    /// Writes `rs1 * rs2 - rs3` to `rd`, rounded once with `rm`.
+    FmsubD {
+        rd: FloatRegister,
+        rs1: FloatRegister,
+        rs2: FloatRegister,
+        rs3: FloatRegister,
+        rm: RoundingMode,
+    },
+
+    /// Fused Negated Multiply-Subtract Double-Precision instruction (D standard extension)
+    ///
+    /// Writes `-(rs1 * rs2) + rs3` to `rd`, rounded once with `rm`.
+    FnmsubD {
+        rd: FloatRegister,
+        rs1: FloatRegister,
+        rs2: FloatRegister,
+        rs3: FloatRegister,
+        rm: RoundingMode,
+    },
+
+    /// Fused Negated Multiply-Add Double-Precision instruction (D standard extension)
+    ///
+    /// Writes `-(rs1 * rs2) - rs3` to `rd`, rounded once with `rm`.
+    FnmaddD {
+        rd: FloatRegister,
+        rs1: FloatRegister,
+        rs2: FloatRegister,
+        rs3: FloatRegister,
+        rm: RoundingMode,
+    },
+
+    /// Floating-point Add Double-Precision instruction (D standard extension)
+    ///
+    /// Writes `rs1 + rs2` to `rd`, rounded with `rm`.
+    FaddD {
+        rd: FloatRegister,
+        rs1: FloatRegister,
+        rs2: FloatRegister,
+        rm: RoundingMode,
+    },
+
+    /// Floating-point Subtract Double-Precision instruction (D standard extension)
+    ///
+    /// Writes `rs1 - rs2` to `rd`, rounded with `rm`.
+    FsubD {
+        rd: FloatRegister,
+        rs1: FloatRegister,
+        rs2: FloatRegister,
+        rm: RoundingMode,
+    },
+
+    /// Floating-point Multiply Double-Precision instruction (D standard extension)
+    ///
+    /// Writes `rs1 * rs2` to `rd`, rounded with `rm`.
+    FmulD {
+        rd: FloatRegister,
+        rs1: FloatRegister,
+        rs2: FloatRegister,
+        rm: RoundingMode,
+    },
+
+    /// Floating-point Divide Double-Precision instruction (D standard extension)
+    ///
+    /// Writes `rs1 / rs2` to `rd`, rounded with `rm`.
+    FdivD {
+        rd: FloatRegister,
+        rs1: FloatRegister,
+        rs2: FloatRegister,
+        rm: RoundingMode,
+    },
+
+    /// Floating-point Square Root Double-Precision instruction (D standard extension)
+    ///
+    /// Writes the square root of `rs1` to `rd`, rounded with `rm`.
+    FsqrtD {
+        rd: FloatRegister,
+        rs1: FloatRegister,
+        rm: RoundingMode,
+    },
+
+    /// Floating-point Sign Injection Double-Precision instruction (D standard extension)
+    ///
+    /// Writes `rs1` with the sign of `rs2` to `rd`.
+    FsgnjD {
+        rd: FloatRegister,
+        rs1: FloatRegister,
+        rs2: FloatRegister,
+    },
+
+    /// Floating-point Negated Sign Injection Double-Precision instruction (D standard extension)
+    ///
+    /// Writes `rs1` with the opposite of the sign of `rs2` to `rd`.
+    FsgnjnD {
+        rd: FloatRegister,
+        rs1: FloatRegister,
+        rs2: FloatRegister,
+    },
+
+    /// Floating-point XOR Sign Injection Double-Precision instruction (D standard extension)
+    ///
+    /// Writes `rs1` with the XOR of the signs of `rs1` and `rs2` to `rd`.
+    FsgnjxD {
+        rd: FloatRegister,
+        rs1: FloatRegister,
+        rs2: FloatRegister,
+    },
+
+    /// Floating-point Minimum Double-Precision instruction (D standard extension)
+    ///
+    /// Writes the smaller of `rs1` and `rs2` to `rd`, with `-0` less than `+0`. If only
+    /// one operand is a NaN, the other is written.
+    FminD {
+        rd: FloatRegister,
+        rs1: FloatRegister,
+        rs2: FloatRegister,
+    },
+
+    /// Floating-point Maximum Double-Precision instruction (D standard extension)
+    ///
+    /// Writes the larger of `rs1` and `rs2` to `rd`, with `-0` less than `+0`. If only
+    /// one operand is a NaN, the other is written.
+    FmaxD {
+        rd: FloatRegister,
+        rs1: FloatRegister,
+        rs2: FloatRegister,
+    },
+
+    /// Floating-point Equal Double-Precision instruction (D standard extension)
+    ///
+    /// Writes 1 to the integer register `rd` if `rs1` equals `rs2`, and 0 otherwise.
+    /// Only signaling NaNs raise the invalid flag.
+    FeqD {
+        rd: Register,
+        rs1: FloatRegister,
+        rs2: FloatRegister,
+    },
+
+    /// Floating-point Less Than Double-Precision instruction (D standard extension)
+    ///
+    /// Writes 1 to the integer register `rd` if `rs1` is less than `rs2`, and 0
+    /// otherwise. Any NaN raises the invalid flag.
+    FltD {
+        rd: Register,
+        rs1: FloatRegister,
+        rs2: FloatRegister,
+    },
+
+    /// Floating-point Less Than or Equal Double-Precision instruction (D standard extension)
+    ///
+    /// Writes 1 to the integer register `rd` if `rs1` is less than or equal to `rs2`,
+    /// and 0 otherwise. Any NaN raises the invalid flag.
+    FleD {
+        rd: Register,
+        rs1: FloatRegister,
+        rs2: FloatRegister,
+    },
+
+    /// Floating-point Convert Double to Word instruction (D standard extension)
+    ///
+    /// Converts `rs1` to a signed integer, rounded with `rm`, and writes it to the
+    /// integer register `rd`. Out of range values saturate, and NaNs convert to the
+    /// largest integer.
+    FcvtWD {
+        rd: Register,
+        rs1: FloatRegister,
+        rm: RoundingMode,
+    },
+
+    /// Floating-point Convert Double to Unsigned Word instruction (D standard extension)
+    ///
+    /// Converts `rs1` to an unsigned integer, rounded with `rm`, and writes it to the
+    /// integer register `rd`. Out of range values saturate, and NaNs convert to the
+    /// largest integer.
+    FcvtWuD {
+        rd: Register,
+        rs1: FloatRegister,
+        rm: RoundingMode,
+    },
+
+    /// Floating-point Classify Double-Precision instruction (D standard extension)
+    ///
+    /// Writes a mask with one of its low 10 bits set to the integer register `rd`, to
+    /// classify `rs1` as a negative infinity, normal, subnormal or zero, a positive
+    /// zero, subnormal, normal or infinity, or a signaling or quiet NaN.
+    FclassD { rd: Register, rs1: FloatRegister },
+
+    /// Floating-point Convert Word to Double instruction (D standard extension)
+    ///
+    /// Converts the signed integer in the integer register `rs1` to a floating-point
+    /// value, rounded with `rm`, and writes it to `rd`.
+    FcvtDW {
+        rd: FloatRegister,
+        rs1: Register,
+        rm: RoundingMode,
+    },
+
+    /// Floating-point Convert Unsigned Word to Double instruction (D standard extension)
+    ///
+    /// Converts the unsigned integer in the integer register `rs1` to a floating-point
+    /// value, rounded with `rm`, and writes it to `rd`.
+    FcvtDWu {
+        rd: FloatRegister,
+        rs1: Register,
+        rm: RoundingMode,
+    },
+
+    /// Floating-point Convert Double to Single instruction (D standard extension)
+    ///
+    /// Converts `rs1` to single precision, rounded with `rm`, and writes it to `rd`.
+    FcvtSD {
+        rd: FloatRegister,
+        rs1: FloatRegister,
+        rm: RoundingMode,
+    },
+
+    /// Floating-point Convert Single to Double instruction (D standard extension)
+    ///
+    /// Converts `rs1` to double precision, which is always exact, and writes it to
+    /// `rd`.
+    FcvtDS {
+        rd: FloatRegister,
+        rs1: FloatRegister,
+        rm: RoundingMode,
+    },
+
+    /// Load Doubleword instruction (RV64I base instruction set)
+    ///
+    /// Loads a 64-bit value from memory address `rs1 + imm` and stores it in `rd`.
+    Ld {
+        rd: Register,
+        rs1: Register,
+        imm: i16,
+    },
+
+    /// Load Word Unsigned instruction (RV64I base instruction set)
+    ///
+    /// Loads a 32-bit value from memory address `rs1 + imm` and zero-extends it to 64 bits,
+    /// storing the result in `rd`.
+    Lwu {
+        rd: Register,
+        rs1: Register,
+        imm: i16,
+    },
+
+    /// Store Doubleword instruction (RV64I base instruction set)
+    ///
+    /// Stores the full 64-bit value of register `rs2` to memory address `rs1 + imm`.
+    Sd {
+        rs1: Register,
+        rs2: Register,
+        imm: i16,
+    },
+
+    /// Add Word Immediate instruction (RV64I base instruction set)
+    ///
+    /// Adds the sign-extended 12-bit immediate to the lower 32 bits of register `rs1`
+    /// and stores the 32-bit result, sign-extended to 64 bits, in `rd`.
+    Addiw {
+        rd: Register,
+        rs1: Register,
+        imm: i16,
+    },
+
+    /// Shift Left Logical Word Immediate instruction (RV64I base instruction set)
+    ///
+    /// Shifts the lower 32 bits of register `rs1` left by `imm` (0..=31) and stores the
+    /// 32-bit result, sign-extended to 64 bits, in `rd`.
+    Slliw {
+        rd: Register,
+        rs1: Register,
+        imm: i16,
+    },
+
+    /// Shift Right Logical Word Immediate instruction (RV64I base instruction set)
+    ///
+    /// Shifts the lower 32 bits of register `rs1` right by `imm` (0..=31), shifting in
+    /// zero bits, and stores the 32-bit result, sign-extended to 64 bits, in `rd`.
+    Srliw {
+        rd: Register,
+        rs1: Register,
+        imm: i16,
+    },
+
+    /// Shift Right Arithmetic Word Immediate instruction (RV64I base instruction set)
+    ///
+    /// Shifts the lower 32 bits of register `rs1` right by `imm` (0..=31), shifting in
+    /// sign bits, and stores the 32-bit result, sign-extended to 64 bits, in `rd`.
+    Sraiw {
+        rd: Register,
+        rs1: Register,
+        imm: i16,
+    },
+
+    /// Add Word instruction (RV64I base instruction set)
+    ///
+    /// Adds the lower 32 bits of registers `rs1` and `rs2` and stores the 32-bit result,
+    /// sign-extended to 64 bits, in `rd`.
+    Addw {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+
+    /// Subtract Word instruction (RV64I base instruction set)
+    ///
+    /// Subtracts the lower 32 bits of register `rs2` from those of `rs1` and stores the
+    /// 32-bit result, sign-extended to 64 bits, in `rd`.
+    Subw {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+
+    /// Shift Left Logical Word instruction (RV64I base instruction set)
+    ///
+    /// Shifts the lower 32 bits of register `rs1` left by the amount held in the lower
+    /// 5 bits of register `rs2` and stores the 32-bit result, sign-extended to 64 bits,
+    /// in `rd`.
+    Sllw {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+
+    /// Shift Right Logical Word instruction (RV64I base instruction set)
+    ///
+    /// Shifts the lower 32 bits of register `rs1` right by the amount held in the lower
+    /// 5 bits of register `rs2`, shifting in zero bits, and stores the 32-bit result,
+    /// sign-extended to 64 bits, in `rd`.
+    Srlw {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+
+    /// Shift Right Arithmetic Word instruction (RV64I base instruction set)
+    ///
+    /// Shifts the lower 32 bits of register `rs1` right by the amount held in the lower
+    /// 5 bits of register `rs2`, shifting in sign bits, and stores the 32-bit result,
+    /// sign-extended to 64 bits, in `rd`.
+    Sraw {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+
+    /// Multiply Word instruction (RV64M standard extension)
+    ///
+    /// Multiplies the lower 32 bits of registers `rs1` and `rs2` and stores the lower
+    /// 32 bits of the product, sign-extended to 64 bits, in `rd`.
+    Mulw {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+
+    /// Divide Word instruction (RV64M standard extension)
+    ///
+    /// Divides the lower 32 bits of register `rs1` by those of `rs2` as signed integers
+    /// and stores the quotient, sign-extended to 64 bits, in `rd`. Division by zero and
+    /// overflow produce the same results as `div` on 32-bit values.
+    Divw {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+
+    /// Divide Unsigned Word instruction (RV64M standard extension)
+    ///
+    /// Divides the lower 32 bits of register `rs1` by those of `rs2` as unsigned integers
+    /// and stores the quotient, sign-extended to 64 bits, in `rd`. Division by zero
+    /// produces the same result as `divu` on 32-bit values.
+    Divuw {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+
+    /// Remainder Word instruction (RV64M standard extension)
+    ///
+    /// Computes the remainder of the signed division of the lower 32 bits of register
+    /// `rs1` by those of `rs2` and stores it, sign-extended to 64 bits, in `rd`.
+    /// Division by zero and overflow produce the same results as `rem` on 32-bit values.
+    Remw {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+
+    /// Remainder Unsigned Word instruction (RV64M standard extension)
+    ///
+    /// Computes the remainder of the unsigned division of the lower 32 bits of register
+    /// `rs1` by those of `rs2` and stores it, sign-extended to 64 bits, in `rd`.
+    /// Division by zero produces the same result as `remu` on 32-bit values.
+    Remuw {
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+    },
+
+    /// Custom instruction implemented by the host
+    ///
+    /// An instruction in the custom-0 or custom-1 opcode space, only decoded by
+    /// [`RiscVInstruction::decode_custom`] when an instruction with its encoding is
+    /// registered. Its operands are interpreted by the registered handler.
+    Custom {
+        opcode: CustomOpcode,
+        funct3: u8,
+        operands: CustomOperands,
+    },
+}
+
+/// Formats an instruction with registers named in a chosen [`Syntax`].
+///
+/// Created by [`RiscVInstruction::display`].
+pub struct InstructionDisplay<'a> {
+    instruction: &'a RiscVInstruction,
+    syntax: Syntax,
+}
+
+impl fmt::Display for InstructionDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let r = |register: Register| register.name(self.syntax);
+        let fr = |register: FloatRegister| register.name(self.syntax);
+
+        match self.instruction {
+            RiscVInstruction::Add { rd, rs1, rs2 } => {
+                write!(f, "add {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Sub { rd, rs1, rs2 } => {
+                write!(f, "sub {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Xor { rd, rs1, rs2 } => {
+                write!(f, "xor {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Or { rd, rs1, rs2 } => {
+                write!(f, "or {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::And { rd, rs1, rs2 } => {
+                write!(f, "and {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Sll { rd, rs1, rs2 } => {
+                write!(f, "sll {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Srl { rd, rs1, rs2 } => {
+                write!(f, "srl {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Sra { rd, rs1, rs2 } => {
+                write!(f, "sra {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Slt { rd, rs1, rs2 } => {
+                write!(f, "slt {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Sltu { rd, rs1, rs2 } => {
+                write!(f, "sltu {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Mul { rd, rs1, rs2 } => {
+                write!(f, "mul {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Mulh { rd, rs1, rs2 } => {
+                write!(f, "mulh {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Mulhsu { rd, rs1, rs2 } => {
+                write!(f, "mulhsu {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Mulhu { rd, rs1, rs2 } => {
+                write!(f, "mulhu {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Div { rd, rs1, rs2 } => {
+                write!(f, "div {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Divu { rd, rs1, rs2 } => {
+                write!(f, "divu {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Rem { rd, rs1, rs2 } => {
+                write!(f, "rem {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Remu { rd, rs1, rs2 } => {
+                write!(f, "remu {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Addi { rd, rs1, imm } => {
+                write!(f, "addi {}, {}, {}", r(*rd), r(*rs1), imm)
+            }
+            RiscVInstruction::Xori { rd, rs1, imm } => {
+                write!(f, "xori {}, {}, {}", r(*rd), r(*rs1), imm)
+            }
+            RiscVInstruction::Ori { rd, rs1, imm } => {
+                write!(f, "ori {}, {}, {}", r(*rd), r(*rs1), imm)
+            }
+            RiscVInstruction::Andi { rd, rs1, imm } => {
+                write!(f, "andi {}, {}, {}", r(*rd), r(*rs1), imm)
+            }
+            RiscVInstruction::Slli { rd, rs1, imm } => {
+                write!(f, "slli {}, {}, {}", r(*rd), r(*rs1), imm)
+            }
+            RiscVInstruction::Srli { rd, rs1, imm } => {
+                write!(f, "srli {}, {}, {}", r(*rd), r(*rs1), imm)
+            }
+            RiscVInstruction::Srai { rd, rs1, imm } => {
+                write!(f, "srai {}, {}, {}", r(*rd), r(*rs1), imm)
+            }
+            RiscVInstruction::Slti { rd, rs1, imm } => {
+                write!(f, "slti {}, {}, {}", r(*rd), r(*rs1), imm)
+            }
+            RiscVInstruction::Sltiu { rd, rs1, imm } => {
+                write!(f, "sltiu {}, {}, {}", r(*rd), r(*rs1), imm)
+            }
+            RiscVInstruction::Lb { rd, rs1, imm } => {
+                write!(f, "lb {}, {}({})", r(*rd), imm, r(*rs1))
+            }
+            RiscVInstruction::Lh { rd, rs1, imm } => {
+                write!(f, "lh {}, {}({})", r(*rd), imm, r(*rs1))
+            }
+            RiscVInstruction::Lw { rd, rs1, imm } => {
+                write!(f, "lw {}, {}({})", r(*rd), imm, r(*rs1))
+            }
+            RiscVInstruction::Lbu { rd, rs1, imm } => {
+                write!(f, "lbu {}, {}({})", r(*rd), imm, r(*rs1))
+            }
+            RiscVInstruction::Lhu { rd, rs1, imm } => {
+                write!(f, "lhu {}, {}({})", r(*rd), imm, r(*rs1))
+            }
+            RiscVInstruction::Sb { rs1, rs2, imm } => {
+                write!(f, "sb {}, {}({})", r(*rs2), imm, r(*rs1))
+            }
+            RiscVInstruction::Sh { rs1, rs2, imm } => {
+                write!(f, "sh {}, {}({})", r(*rs2), imm, r(*rs1))
+            }
+            RiscVInstruction::Sw { rs1, rs2, imm } => {
+                write!(f, "sw {}, {}({})", r(*rs2), imm, r(*rs1))
+            }
+            RiscVInstruction::Beq { rs1, rs2, imm } => {
+                write!(f, "beq {}, {}, {}", r(*rs1), r(*rs2), imm)
+            }
+            RiscVInstruction::Bne { rs1, rs2, imm } => {
+                write!(f, "bne {}, {}, {}", r(*rs1), r(*rs2), imm)
+            }
+            RiscVInstruction::Blt { rs1, rs2, imm } => {
+                write!(f, "blt {}, {}, {}", r(*rs1), r(*rs2), imm)
+            }
+            RiscVInstruction::Bge { rs1, rs2, imm } => {
+                write!(f, "bge {}, {}, {}", r(*rs1), r(*rs2), imm)
+            }
+            RiscVInstruction::Bltu { rs1, rs2, imm } => {
+                write!(f, "bltu {}, {}, {}", r(*rs1), r(*rs2), imm)
+            }
+            RiscVInstruction::Bgeu { rs1, rs2, imm } => {
+                write!(f, "bgeu {}, {}, {}", r(*rs1), r(*rs2), imm)
+            }
+            RiscVInstruction::Lui { rd, imm } => {
+                write!(f, "lui {}, {:#x}", r(*rd), (*imm as u32) >> 12)
+            }
+            RiscVInstruction::Auipc { rd, imm } => {
+                write!(f, "auipc {}, {:#x}", r(*rd), (*imm as u32) >> 12)
+            }
+            RiscVInstruction::Jal { rd, imm } => {
+                write!(f, "jal {}, {}", r(*rd), imm)
+            }
+            RiscVInstruction::Jalr { rd, rs1, imm } => {
+                write!(f, "jalr {}, {}, {}", r(*rd), r(*rs1), imm)
+            }
+            RiscVInstruction::Ecall => {
+                write!(f, "ecall")
+            }
+            RiscVInstruction::Ebreak => {
+                write!(f, "ebreak")
+            }
+            RiscVInstruction::Fence { pred, succ } => {
+                write!(f, "fence {}, {}", FenceSet(*pred), FenceSet(*succ))
+            }
+            RiscVInstruction::FenceTso => write!(f, "fence.tso"),
+            RiscVInstruction::FenceI => write!(f, "fence.i"),
+            RiscVInstruction::Csrrw { rd, rs1, csr } => {
+                write!(f, "csrrw {}, {}, {}", r(*rd), CsrName(*csr), r(*rs1))
+            }
+            RiscVInstruction::Csrrs { rd, rs1, csr } => {
+                write!(f, "csrrs {}, {}, {}", r(*rd), CsrName(*csr), r(*rs1))
+            }
+            RiscVInstruction::Csrrc { rd, rs1, csr } => {
+                write!(f, "csrrc {}, {}, {}", r(*rd), CsrName(*csr), r(*rs1))
+            }
+            RiscVInstruction::Csrrwi { rd, imm, csr } => {
+                write!(f, "csrrwi {}, {}, {}", r(*rd), CsrName(*csr), imm)
+            }
+            RiscVInstruction::Csrrsi { rd, imm, csr } => {
+                write!(f, "csrrsi {}, {}, {}", r(*rd), CsrName(*csr), imm)
+            }
+            RiscVInstruction::Csrrci { rd, imm, csr } => {
+                write!(f, "csrrci {}, {}, {}", r(*rd), CsrName(*csr), imm)
+            }
+            RiscVInstruction::Sh1add { rd, rs1, rs2 } => {
+                write!(f, "sh1add {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Sh2add { rd, rs1, rs2 } => {
+                write!(f, "sh2add {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Sh3add { rd, rs1, rs2 } => {
+                write!(f, "sh3add {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Andn { rd, rs1, rs2 } => {
+                write!(f, "andn {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Orn { rd, rs1, rs2 } => {
+                write!(f, "orn {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Xnor { rd, rs1, rs2 } => {
+                write!(f, "xnor {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Clz { rd, rs1 } => {
+                write!(f, "clz {}, {}", r(*rd), r(*rs1))
+            }
+            RiscVInstruction::Ctz { rd, rs1 } => {
+                write!(f, "ctz {}, {}", r(*rd), r(*rs1))
+            }
+            RiscVInstruction::Cpop { rd, rs1 } => {
+                write!(f, "cpop {}, {}", r(*rd), r(*rs1))
+            }
+            RiscVInstruction::Max { rd, rs1, rs2 } => {
+                write!(f, "max {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Maxu { rd, rs1, rs2 } => {
+                write!(f, "maxu {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Min { rd, rs1, rs2 } => {
+                write!(f, "min {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Minu { rd, rs1, rs2 } => {
+                write!(f, "minu {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::SextB { rd, rs1 } => {
+                write!(f, "sext.b {}, {}", r(*rd), r(*rs1))
+            }
+            RiscVInstruction::SextH { rd, rs1 } => {
+                write!(f, "sext.h {}, {}", r(*rd), r(*rs1))
+            }
+            RiscVInstruction::ZextH { rd, rs1 } => {
+                write!(f, "zext.h {}, {}", r(*rd), r(*rs1))
+            }
+            RiscVInstruction::Rol { rd, rs1, rs2 } => {
+                write!(f, "rol {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Ror { rd, rs1, rs2 } => {
+                write!(f, "ror {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Rori { rd, rs1, imm } => {
+                write!(f, "rori {}, {}, {}", r(*rd), r(*rs1), imm)
+            }
+            RiscVInstruction::OrcB { rd, rs1 } => {
+                write!(f, "orc.b {}, {}", r(*rd), r(*rs1))
+            }
+            RiscVInstruction::Rev8 { rd, rs1 } => {
+                write!(f, "rev8 {}, {}", r(*rd), r(*rs1))
+            }
+            RiscVInstruction::Bclr { rd, rs1, rs2 } => {
+                write!(f, "bclr {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Bclri { rd, rs1, imm } => {
+                write!(f, "bclri {}, {}, {}", r(*rd), r(*rs1), imm)
+            }
+            RiscVInstruction::Bext { rd, rs1, rs2 } => {
+                write!(f, "bext {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Bexti { rd, rs1, imm } => {
+                write!(f, "bexti {}, {}, {}", r(*rd), r(*rs1), imm)
+            }
+            RiscVInstruction::Binv { rd, rs1, rs2 } => {
+                write!(f, "binv {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Binvi { rd, rs1, imm } => {
+                write!(f, "binvi {}, {}, {}", r(*rd), r(*rs1), imm)
+            }
+            RiscVInstruction::Bset { rd, rs1, rs2 } => {
+                write!(f, "bset {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Bseti { rd, rs1, imm } => {
+                write!(f, "bseti {}, {}, {}", r(*rd), r(*rs1), imm)
+            }
+            RiscVInstruction::Sha256sig0 { rd, rs1 } => {
+                write!(f, "sha256sig0 {}, {}", r(*rd), r(*rs1))
+            }
+            RiscVInstruction::Sha256sig1 { rd, rs1 } => {
+                write!(f, "sha256sig1 {}, {}", r(*rd), r(*rs1))
+            }
+            RiscVInstruction::Sha256sum0 { rd, rs1 } => {
+                write!(f, "sha256sum0 {}, {}", r(*rd), r(*rs1))
+            }
+            RiscVInstruction::Sha256sum1 { rd, rs1 } => {
+                write!(f, "sha256sum1 {}, {}", r(*rd), r(*rs1))
+            }
+            RiscVInstruction::Sha512sum0r { rd, rs1, rs2 } => {
+                write!(f, "sha512sum0r {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Sha512sum1r { rd, rs1, rs2 } => {
+                write!(f, "sha512sum1r {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Sha512sig0l { rd, rs1, rs2 } => {
+                write!(f, "sha512sig0l {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Sha512sig0h { rd, rs1, rs2 } => {
+                write!(f, "sha512sig0h {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Sha512sig1l { rd, rs1, rs2 } => {
+                write!(f, "sha512sig1l {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Sha512sig1h { rd, rs1, rs2 } => {
+                write!(f, "sha512sig1h {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::LrW { rd, rs1, aq, rl } => {
+                let ordering = AtomicOrdering(*aq, *rl);
+                write!(f, "lr.w{} {}, ({})", ordering, r(*rd), r(*rs1))
+            }
+            RiscVInstruction::ScW {
+                rd,
+                rs1,
+                rs2,
+                aq,
+                rl,
+            } => {
+                let ordering = AtomicOrdering(*aq, *rl);
+                write!(f, "sc.w{} {}, {}, ({})", ordering, r(*rd), r(*rs2), r(*rs1))
+            }
+            RiscVInstruction::AmoswapW {
+                rd,
+                rs1,
+                rs2,
+                aq,
+                rl,
+            } => {
+                let ordering = AtomicOrdering(*aq, *rl);
+                write!(
+                    f,
+                    "amoswap.w{} {}, {}, ({})",
+                    ordering,
+                    r(*rd),
+                    r(*rs2),
+                    r(*rs1)
+                )
+            }
+            RiscVInstruction::AmoaddW {
+                rd,
+                rs1,
+                rs2,
+                aq,
+                rl,
+            } => {
+                let ordering = AtomicOrdering(*aq, *rl);
+                write!(
+                    f,
+                    "amoadd.w{} {}, {}, ({})",
+                    ordering,
+                    r(*rd),
+                    r(*rs2),
+                    r(*rs1)
+                )
+            }
+            RiscVInstruction::AmoxorW {
+                rd,
+                rs1,
+                rs2,
+                aq,
+                rl,
+            } => {
+                let ordering = AtomicOrdering(*aq, *rl);
+                write!(
+                    f,
+                    "amoxor.w{} {}, {}, ({})",
+                    ordering,
+                    r(*rd),
+                    r(*rs2),
+                    r(*rs1)
+                )
+            }
+            RiscVInstruction::AmoandW {
+                rd,
+                rs1,
+                rs2,
+                aq,
+                rl,
+            } => {
+                let ordering = AtomicOrdering(*aq, *rl);
+                write!(
+                    f,
+                    "amoand.w{} {}, {}, ({})",
+                    ordering,
+                    r(*rd),
+                    r(*rs2),
+                    r(*rs1)
+                )
+            }
+            RiscVInstruction::AmoorW {
+                rd,
+                rs1,
+                rs2,
+                aq,
+                rl,
+            } => {
+                let ordering = AtomicOrdering(*aq, *rl);
+                write!(
+                    f,
+                    "amoor.w{} {}, {}, ({})",
+                    ordering,
+                    r(*rd),
+                    r(*rs2),
+                    r(*rs1)
+                )
+            }
+            RiscVInstruction::AmominW {
+                rd,
+                rs1,
+                rs2,
+                aq,
+                rl,
+            } => {
+                let ordering = AtomicOrdering(*aq, *rl);
+                write!(
+                    f,
+                    "amomin.w{} {}, {}, ({})",
+                    ordering,
+                    r(*rd),
+                    r(*rs2),
+                    r(*rs1)
+                )
+            }
+            RiscVInstruction::AmomaxW {
+                rd,
+                rs1,
+                rs2,
+                aq,
+                rl,
+            } => {
+                let ordering = AtomicOrdering(*aq, *rl);
+                write!(
+                    f,
+                    "amomax.w{} {}, {}, ({})",
+                    ordering,
+                    r(*rd),
+                    r(*rs2),
+                    r(*rs1)
+                )
+            }
+            RiscVInstruction::AmominuW {
+                rd,
+                rs1,
+                rs2,
+                aq,
+                rl,
+            } => {
+                let ordering = AtomicOrdering(*aq, *rl);
+                write!(
+                    f,
+                    "amominu.w{} {}, {}, ({})",
+                    ordering,
+                    r(*rd),
+                    r(*rs2),
+                    r(*rs1)
+                )
+            }
+            RiscVInstruction::AmomaxuW {
+                rd,
+                rs1,
+                rs2,
+                aq,
+                rl,
+            } => {
+                let ordering = AtomicOrdering(*aq, *rl);
+                write!(
+                    f,
+                    "amomaxu.w{} {}, {}, ({})",
+                    ordering,
+                    r(*rd),
+                    r(*rs2),
+                    r(*rs1)
+                )
+            }
+            RiscVInstruction::Flw { rd, rs1, imm } => {
+                write!(f, "flw {}, {}({})", fr(*rd), imm, r(*rs1))
+            }
+            RiscVInstruction::Fsw { rs1, rs2, imm } => {
+                write!(f, "fsw {}, {}({})", fr(*rs2), imm, r(*rs1))
+            }
+            RiscVInstruction::Fld { rd, rs1, imm } => {
+                write!(f, "fld {}, {}({})", fr(*rd), imm, r(*rs1))
+            }
+            RiscVInstruction::Fsd { rs1, rs2, imm } => {
+                write!(f, "fsd {}, {}({})", fr(*rs2), imm, r(*rs1))
+            }
+            RiscVInstruction::FmaddS {
+                rd,
+                rs1,
+                rs2,
+                rs3,
+                rm,
+            } => {
+                write!(
+                    f,
+                    "fmadd.s {}, {}, {}, {}{}",
+                    fr(*rd),
+                    fr(*rs1),
+                    fr(*rs2),
+                    fr(*rs3),
+                    Rounding(*rm)
+                )
+            }
+            RiscVInstruction::FmsubS {
+                rd,
+                rs1,
+                rs2,
+                rs3,
+                rm,
+            } => {
+                write!(
+                    f,
+                    "fmsub.s {}, {}, {}, {}{}",
+                    fr(*rd),
+                    fr(*rs1),
+                    fr(*rs2),
+                    fr(*rs3),
+                    Rounding(*rm)
+                )
+            }
+            RiscVInstruction::FnmsubS {
+                rd,
+                rs1,
+                rs2,
+                rs3,
+                rm,
+            } => {
+                write!(
+                    f,
+                    "fnmsub.s {}, {}, {}, {}{}",
+                    fr(*rd),
+                    fr(*rs1),
+                    fr(*rs2),
+                    fr(*rs3),
+                    Rounding(*rm)
+                )
+            }
+            RiscVInstruction::FnmaddS {
+                rd,
+                rs1,
+                rs2,
+                rs3,
+                rm,
+            } => {
+                write!(
+                    f,
+                    "fnmadd.s {}, {}, {}, {}{}",
+                    fr(*rd),
+                    fr(*rs1),
+                    fr(*rs2),
+                    fr(*rs3),
+                    Rounding(*rm)
+                )
+            }
+            RiscVInstruction::FaddS { rd, rs1, rs2, rm } => {
+                write!(
+                    f,
+                    "fadd.s {}, {}, {}{}",
+                    fr(*rd),
+                    fr(*rs1),
+                    fr(*rs2),
+                    Rounding(*rm)
+                )
+            }
+            RiscVInstruction::FsubS { rd, rs1, rs2, rm } => {
+                write!(
+                    f,
+                    "fsub.s {}, {}, {}{}",
+                    fr(*rd),
+                    fr(*rs1),
+                    fr(*rs2),
+                    Rounding(*rm)
+                )
+            }
+            RiscVInstruction::FmulS { rd, rs1, rs2, rm } => {
+                write!(
+                    f,
+                    "fmul.s {}, {}, {}{}",
+                    fr(*rd),
+                    fr(*rs1),
+                    fr(*rs2),
+                    Rounding(*rm)
+                )
+            }
+            RiscVInstruction::FdivS { rd, rs1, rs2, rm } => {
+                write!(
+                    f,
+                    "fdiv.s {}, {}, {}{}",
+                    fr(*rd),
+                    fr(*rs1),
+                    fr(*rs2),
+                    Rounding(*rm)
+                )
+            }
+            RiscVInstruction::FsqrtS { rd, rs1, rm } => {
+                write!(f, "fsqrt.s {}, {}{}", fr(*rd), fr(*rs1), Rounding(*rm))
+            }
+            RiscVInstruction::FsgnjS { rd, rs1, rs2 } => {
+                write!(f, "fsgnj.s {}, {}, {}", fr(*rd), fr(*rs1), fr(*rs2))
+            }
+            RiscVInstruction::FsgnjnS { rd, rs1, rs2 } => {
+                write!(f, "fsgnjn.s {}, {}, {}", fr(*rd), fr(*rs1), fr(*rs2))
+            }
+            RiscVInstruction::FsgnjxS { rd, rs1, rs2 } => {
+                write!(f, "fsgnjx.s {}, {}, {}", fr(*rd), fr(*rs1), fr(*rs2))
+            }
+            RiscVInstruction::FminS { rd, rs1, rs2 } => {
+                write!(f, "fmin.s {}, {}, {}", fr(*rd), fr(*rs1), fr(*rs2))
+            }
+            RiscVInstruction::FmaxS { rd, rs1, rs2 } => {
+                write!(f, "fmax.s {}, {}, {}", fr(*rd), fr(*rs1), fr(*rs2))
+            }
+            RiscVInstruction::FeqS { rd, rs1, rs2 } => {
+                write!(f, "feq.s {}, {}, {}", r(*rd), fr(*rs1), fr(*rs2))
+            }
+            RiscVInstruction::FltS { rd, rs1, rs2 } => {
+                write!(f, "flt.s {}, {}, {}", r(*rd), fr(*rs1), fr(*rs2))
+            }
+            RiscVInstruction::FleS { rd, rs1, rs2 } => {
+                write!(f, "fle.s {}, {}, {}", r(*rd), fr(*rs1), fr(*rs2))
+            }
+            RiscVInstruction::FcvtWS { rd, rs1, rm } => {
+                write!(f, "fcvt.w.s {}, {}{}", r(*rd), fr(*rs1), Rounding(*rm))
+            }
+            RiscVInstruction::FcvtWuS { rd, rs1, rm } => {
+                write!(f, "fcvt.wu.s {}, {}{}", r(*rd), fr(*rs1), Rounding(*rm))
+            }
+            RiscVInstruction::FmvXW { rd, rs1 } => {
+                write!(f, "fmv.x.w {}, {}", r(*rd), fr(*rs1))
+            }
+            RiscVInstruction::FclassS { rd, rs1 } => {
+                write!(f, "fclass.s {}, {}", r(*rd), fr(*rs1))
+            }
+            RiscVInstruction::FcvtSW { rd, rs1, rm } => {
+                write!(f, "fcvt.s.w {}, {}{}", fr(*rd), r(*rs1), Rounding(*rm))
+            }
+            RiscVInstruction::FcvtSWu { rd, rs1, rm } => {
+                write!(f, "fcvt.s.wu {}, {}{}", fr(*rd), r(*rs1), Rounding(*rm))
+            }
+            RiscVInstruction::FmvWX { rd, rs1 } => {
+                write!(f, "fmv.w.x {}, {}", fr(*rd), r(*rs1))
+            }
+            RiscVInstruction::FmaddD {
+                rd,
+                rs1,
+                rs2,
+                rs3,
+                rm,
+            } => {
+                write!(
+                    f,
+                    "fmadd.d {}, {}, {}, {}{}",
+                    fr(*rd),
+                    fr(*rs1),
+                    fr(*rs2),
+                    fr(*rs3),
+                    Rounding(*rm)
+                )
+            }
+            RiscVInstruction::FmsubD {
+                rd,
+                rs1,
+                rs2,
+                rs3,
+                rm,
+            } => {
+                write!(
+                    f,
+                    "fmsub.d {}, {}, {}, {}{}",
+                    fr(*rd),
+                    fr(*rs1),
+                    fr(*rs2),
+                    fr(*rs3),
+                    Rounding(*rm)
+                )
+            }
+            RiscVInstruction::FnmsubD {
+                rd,
+                rs1,
+                rs2,
+                rs3,
+                rm,
+            } => {
+                write!(
+                    f,
+                    "fnmsub.d {}, {}, {}, {}{}",
+                    fr(*rd),
+                    fr(*rs1),
+                    fr(*rs2),
+                    fr(*rs3),
+                    Rounding(*rm)
+                )
+            }
+            RiscVInstruction::FnmaddD {
+                rd,
+                rs1,
+                rs2,
+                rs3,
+                rm,
+            } => {
+                write!(
+                    f,
+                    "fnmadd.d {}, {}, {}, {}{}",
+                    fr(*rd),
+                    fr(*rs1),
+                    fr(*rs2),
+                    fr(*rs3),
+                    Rounding(*rm)
+                )
+            }
+            RiscVInstruction::FaddD { rd, rs1, rs2, rm } => {
+                write!(
+                    f,
+                    "fadd.d {}, {}, {}{}",
+                    fr(*rd),
+                    fr(*rs1),
+                    fr(*rs2),
+                    Rounding(*rm)
+                )
+            }
+            RiscVInstruction::FsubD { rd, rs1, rs2, rm } => {
+                write!(
+                    f,
+                    "fsub.d {}, {}, {}{}",
+                    fr(*rd),
+                    fr(*rs1),
+                    fr(*rs2),
+                    Rounding(*rm)
+                )
+            }
+            RiscVInstruction::FmulD { rd, rs1, rs2, rm } => {
+                write!(
+                    f,
+                    "fmul.d {}, {}, {}{}",
+                    fr(*rd),
+                    fr(*rs1),
+                    fr(*rs2),
+                    Rounding(*rm)
+                )
+            }
+            RiscVInstruction::FdivD { rd, rs1, rs2, rm } => {
+                write!(
+                    f,
+                    "fdiv.d {}, {}, {}{}",
+                    fr(*rd),
+                    fr(*rs1),
+                    fr(*rs2),
+                    Rounding(*rm)
+                )
+            }
+            RiscVInstruction::FsqrtD { rd, rs1, rm } => {
+                write!(f, "fsqrt.d {}, {}{}", fr(*rd), fr(*rs1), Rounding(*rm))
+            }
+            RiscVInstruction::FsgnjD { rd, rs1, rs2 } => {
+                write!(f, "fsgnj.d {}, {}, {}", fr(*rd), fr(*rs1), fr(*rs2))
+            }
+            RiscVInstruction::FsgnjnD { rd, rs1, rs2 } => {
+                write!(f, "fsgnjn.d {}, {}, {}", fr(*rd), fr(*rs1), fr(*rs2))
+            }
+            RiscVInstruction::FsgnjxD { rd, rs1, rs2 } => {
+                write!(f, "fsgnjx.d {}, {}, {}", fr(*rd), fr(*rs1), fr(*rs2))
+            }
+            RiscVInstruction::FminD { rd, rs1, rs2 } => {
+                write!(f, "fmin.d {}, {}, {}", fr(*rd), fr(*rs1), fr(*rs2))
+            }
+            RiscVInstruction::FmaxD { rd, rs1, rs2 } => {
+                write!(f, "fmax.d {}, {}, {}", fr(*rd), fr(*rs1), fr(*rs2))
+            }
+            RiscVInstruction::FeqD { rd, rs1, rs2 } => {
+                write!(f, "feq.d {}, {}, {}", r(*rd), fr(*rs1), fr(*rs2))
+            }
+            RiscVInstruction::FltD { rd, rs1, rs2 } => {
+                write!(f, "flt.d {}, {}, {}", r(*rd), fr(*rs1), fr(*rs2))
+            }
+            RiscVInstruction::FleD { rd, rs1, rs2 } => {
+                write!(f, "fle.d {}, {}, {}", r(*rd), fr(*rs1), fr(*rs2))
+            }
+            RiscVInstruction::FcvtWD { rd, rs1, rm } => {
+                write!(f, "fcvt.w.d {}, {}{}", r(*rd), fr(*rs1), Rounding(*rm))
+            }
+            RiscVInstruction::FcvtWuD { rd, rs1, rm } => {
+                write!(f, "fcvt.wu.d {}, {}{}", r(*rd), fr(*rs1), Rounding(*rm))
+            }
+            RiscVInstruction::FclassD { rd, rs1 } => {
+                write!(f, "fclass.d {}, {}", r(*rd), fr(*rs1))
+            }
+            RiscVInstruction::FcvtDW { rd, rs1, rm } => {
+                write!(f, "fcvt.d.w {}, {}{}", fr(*rd), r(*rs1), Rounding(*rm))
+            }
+            RiscVInstruction::FcvtDWu { rd, rs1, rm } => {
+                write!(f, "fcvt.d.wu {}, {}{}", fr(*rd), r(*rs1), Rounding(*rm))
+            }
+            RiscVInstruction::FcvtSD { rd, rs1, rm } => {
+                write!(f, "fcvt.s.d {}, {}{}", fr(*rd), fr(*rs1), Rounding(*rm))
+            }
+            RiscVInstruction::FcvtDS { rd, rs1, rm } => {
+                write!(f, "fcvt.d.s {}, {}{}", fr(*rd), fr(*rs1), Rounding(*rm))
+            }
+            RiscVInstruction::Ld { rd, rs1, imm } => {
+                write!(f, "ld {}, {}({})", r(*rd), imm, r(*rs1))
+            }
+            RiscVInstruction::Lwu { rd, rs1, imm } => {
+                write!(f, "lwu {}, {}({})", r(*rd), imm, r(*rs1))
+            }
+            RiscVInstruction::Sd { rs1, rs2, imm } => {
+                write!(f, "sd {}, {}({})", r(*rs2), imm, r(*rs1))
+            }
+            RiscVInstruction::Addiw { rd, rs1, imm } => {
+                write!(f, "addiw {}, {}, {}", r(*rd), r(*rs1), imm)
+            }
+            RiscVInstruction::Slliw { rd, rs1, imm } => {
+                write!(f, "slliw {}, {}, {}", r(*rd), r(*rs1), imm)
+            }
+            RiscVInstruction::Srliw { rd, rs1, imm } => {
+                write!(f, "srliw {}, {}, {}", r(*rd), r(*rs1), imm)
+            }
+            RiscVInstruction::Sraiw { rd, rs1, imm } => {
+                write!(f, "sraiw {}, {}, {}", r(*rd), r(*rs1), imm)
+            }
+            RiscVInstruction::Addw { rd, rs1, rs2 } => {
+                write!(f, "addw {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Subw { rd, rs1, rs2 } => {
+                write!(f, "subw {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Sllw { rd, rs1, rs2 } => {
+                write!(f, "sllw {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Srlw { rd, rs1, rs2 } => {
+                write!(f, "srlw {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Sraw { rd, rs1, rs2 } => {
+                write!(f, "sraw {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Mulw { rd, rs1, rs2 } => {
+                write!(f, "mulw {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Divw { rd, rs1, rs2 } => {
+                write!(f, "divw {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Divuw { rd, rs1, rs2 } => {
+                write!(f, "divuw {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Remw { rd, rs1, rs2 } => {
+                write!(f, "remw {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Remuw { rd, rs1, rs2 } => {
+                write!(f, "remuw {}, {}, {}", r(*rd), r(*rs1), r(*rs2))
+            }
+            RiscVInstruction::Custom {
+                opcode,
+                funct3,
+                operands,
+            } => match operands {
+                CustomOperands::R {
+                    funct7,
+                    rd,
+                    rs1,
+                    rs2,
+                } => write!(
+                    f,
+                    ".insn r {}, {}, {}, {}, {}, {}",
+                    opcode,
+                    funct3,
+                    funct7,
+                    r(*rd),
+                    r(*rs1),
+                    r(*rs2)
+                ),
+                CustomOperands::I { rd, rs1, imm } => write!(
+                    f,
+                    ".insn i {}, {}, {}, {}, {}",
+                    opcode,
+                    funct3,
+                    r(*rd),
+                    r(*rs1),
+                    imm
+                ),
+            },
+        }
+    }
+}
+
+/// Formats the `aq` and `rl` bits of an atomic instruction as a mnemonic suffix.
+struct AtomicOrdering(bool, bool);
+
+impl fmt::Display for AtomicOrdering {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.0, self.1) {
+            (false, false) => Ok(()),
+            (true, false) => write!(f, ".aq"),
+            (false, true) => write!(f, ".rl"),
+            (true, true) => write!(f, ".aqrl"),
+        }
+    }
+}
+
+/// Formats the rounding mode of a floating-point instruction as a trailing operand,
+/// which is omitted for `dyn`.
+struct Rounding(RoundingMode);
+
+impl fmt::Display for Rounding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            RoundingMode::Dynamic => Ok(()),
+            rm => write!(f, ", {}", rm),
+        }
+    }
+}
+
+/// Formats the predecessor or successor set of a fence as a combination of the
+/// letters `iorw`, or `0` if the set is empty.
+struct FenceSet(u8);
+
+impl fmt::Display for FenceSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0 == 0 {
+            return write!(f, "0");
+        }
+
+        for (bit, letter) in FENCE_SET_LETTERS {
+            if self.0 & bit != 0 {
+                write!(f, "{}", letter)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for RiscVInstruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display(Syntax::Numeric).fmt(f)
+    }
+}
+
+const REG_OPCODE: u32 = 0x33;
+const ADD_FUNCT3: u8 = 0x0;
+const ADD_FUNCT7: u32 = 0x00;
+const SUB_FUNCT7: u32 = 0x20;
+const XOR_FUNCT3: u8 = 0x4;
+const XOR_FUNCT7: u32 = 0x00;
+const OR_FUNCT3: u8 = 0x6;
+const OR_FUNCT7: u32 = 0x00;
+const AND_FUNCT3: u8 = 0x7;
+const AND_FUNCT7: u32 = 0x00;
+const SLL_FUNCT3: u8 = 0x1;
+const SLL_FUNCT7: u32 = 0x00;
+const SRL_FUNCT3: u8 = 0x5;
+const SRL_FUNCT7: u32 = 0x00;
+const SRA_FUNCT7: u32 = 0x20;
+const SLT_FUNCT3: u8 = 0x2;
+const SLT_FUNCT7: u32 = 0x00;
+const SLTU_FUNCT3: u8 = 0x3;
+const SLTU_FUNCT7: u32 = 0x00;
+
+const SHADD_FUNCT7: u32 = 0x10;
+const SH1ADD_FUNCT3: u8 = 0x2;
+const SH2ADD_FUNCT3: u8 = 0x4;
+const SH3ADD_FUNCT3: u8 = 0x6;
+const INVERTED_FUNCT7: u32 = 0x20;
+const ANDN_FUNCT3: u8 = 0x7;
+const ORN_FUNCT3: u8 = 0x6;
+const XNOR_FUNCT3: u8 = 0x4;
+const MINMAX_FUNCT7: u32 = 0x05;
+const MIN_FUNCT3: u8 = 0x4;
+const MINU_FUNCT3: u8 = 0x5;
+const MAX_FUNCT3: u8 = 0x6;
+const MAXU_FUNCT3: u8 = 0x7;
+const ZEXTH_FUNCT7: u32 = 0x04;
+const ZEXTH_FUNCT3: u8 = 0x4;
+const ROTATE_FUNCT7: u32 = 0x30;
+const ROL_FUNCT3: u8 = 0x1;
+const ROR_FUNCT3: u8 = 0x5;
+const BCLR_FUNCT7: u32 = 0x24;
+const BINV_FUNCT7: u32 = 0x34;
+const BSET_FUNCT7: u32 = 0x14;
+const SINGLE_BIT_FUNCT3: u8 = 0x1;
+const BEXT_FUNCT3: u8 = 0x5;
+const SHA512_FUNCT3: u8 = 0x0;
+const SHA512SUM0R_FUNCT7: u32 = 0x28;
+const SHA512SUM1R_FUNCT7: u32 = 0x29;
+const SHA512SIG0L_FUNCT7: u32 = 0x2a;
+const SHA512SIG0H_FUNCT7: u32 = 0x2e;
+const SHA512SIG1L_FUNCT7: u32 = 0x2b;
+const SHA512SIG1H_FUNCT7: u32 = 0x2f;
+
+const MULDIV_FUNCT7: u32 = 0x01;
+const MUL_FUNCT3: u8 = 0x0;
+const MULH_FUNCT3: u8 = 0x1;
+const MULHSU_FUNCT3: u8 = 0x2;
+const MULHU_FUNCT3: u8 = 0x3;
+const DIV_FUNCT3: u8 = 0x4;
+const DIVU_FUNCT3: u8 = 0x5;
+const REM_FUNCT3: u8 = 0x6;
+const REMU_FUNCT3: u8 = 0x7;
+
+const IMM_OPCODE: u32 = 0x13;
+const ADDI_FUNCT3: u8 = 0x0;
+const SLTI_FUNCT3: u8 = 0x2;
+const SLTIU_FUNCT3: u8 = 0x3;
+const XORI_FUNCT3: u8 = 0x4;
+const ORI_FUNCT3: u8 = 0x6;
+const ANDI_FUNCT3: u8 = 0x7;
+const SLLI_FUNCT3: u8 = 0x1;
+const SRLI_FUNCT3: u8 = 0x5;
+const SLLI_FUNCT7: u32 = 0x00;
+const SRLI_FUNCT7: u32 = 0x00;
+const SRAI_FUNCT7: u32 = 0x20;
+// RV64I widens the shift amount to six bits, taking the low bit of funct7.
+const WIDE_SHAMT_MASK: u32 = 0x3f00000;
+const WIDE_SHIFT_FUNCT6_SHIFT: u32 = 26;
+const SLLI_FUNCT6: u32 = 0x00;
+const SRLI_FUNCT6: u32 = 0x00;
+const SRAI_FUNCT6: u32 = 0x10;
+// The unary Zbb instructions share the rotate funct7 and are told apart by the rs2 field.
+const UNARY_FUNCT7: u32 = 0x30;
+const CLZ_RS2: u32 = 0x00;
+const CTZ_RS2: u32 = 0x01;
+const CPOP_RS2: u32 = 0x02;
+const SEXTB_RS2: u32 = 0x04;
+const SEXTH_RS2: u32 = 0x05;
+const ORCB_FUNCT7: u32 = 0x14;
+const ORCB_RS2: u32 = 0x07;
+const REV8_FUNCT7: u32 = 0x34;
+const REV8_RS2: u32 = 0x18;
+// The SHA-256 instructions are unary as well, selected by the rs2 field.
+const SHA256_FUNCT7: u32 = 0x08;
+const SHA256SUM0_RS2: u32 = 0x00;
+const SHA256SUM1_RS2: u32 = 0x01;
+const SHA256SIG0_RS2: u32 = 0x02;
+const SHA256SIG1_RS2: u32 = 0x03;
+
+const LOAD_OPCODE: u32 = 0x03;
+const LB_FUNCT3: u8 = 0x0;
+const LH_FUNCT3: u8 = 0x1;
+const LW_FUNCT3: u8 = 0x2;
+const LBU_FUNCT3: u8 = 0x4;
+const LHU_FUNCT3: u8 = 0x5;
+const LD_FUNCT3: u8 = 0x3;
+const LWU_FUNCT3: u8 = 0x6;
+
+const STORE_OPCODE: u32 = 0x23;
+const SB_FUNCT3: u8 = 0x0;
+const SH_FUNCT3: u8 = 0x1;
+const SW_FUNCT3: u8 = 0x2;
+const SD_FUNCT3: u8 = 0x3;
+
+// The word instructions of RV64I and RV64M reuse the funct3 and funct7 values of the
+// corresponding OP-IMM and OP instructions.
+const IMM_32_OPCODE: u32 = 0x1b;
+const REG_32_OPCODE: u32 = 0x3b;
+
+const BRANCH_OPCODE: u32 = 0x63;
+const BEQ_FUNCT3: u8 = 0x0;
+const BNE_FUNCT3: u8 = 0x1;
+const BLT_FUNCT3: u8 = 0x4;
+const BGE_FUNCT3: u8 = 0x5;
+const BLTU_FUNCT3: u8 = 0x6;
+const BGEU_FUNCT3: u8 = 0x7;
+
+const LUI_OPCODE: u32 = 0x37;
+const AUIPC_OPCODE: u32 = 0x17;
+
+const JAL_OPCODE: u32 = 0x6f;
+
+const JALR_OPCODE: u32 = 0x67;
+const JALR_FUNCT3: u32 = 0x0;
+
+const SYSTEM_OPCODE: u32 = 0x73;
+const SYSTEM_FUNCT3: u32 = 0x0;
+const ECALL_IMM: u32 = 0x0;
+const EBREAK_IMM: u32 = 0x1;
+const CSRRW_FUNCT3: u32 = 0x1;
+const CSRRS_FUNCT3: u32 = 0x2;
+const CSRRC_FUNCT3: u32 = 0x3;
+const CSRRWI_FUNCT3: u32 = 0x5;
+const CSRRSI_FUNCT3: u32 = 0x6;
+const CSRRCI_FUNCT3: u32 = 0x7;
+// The CSR number occupies the immediate field, and the immediate of the CSR*I
+// instructions the rs1 field.
+const CSR_MAX: u16 = 0xfff;
+const CSR_IMM_MAX: u8 = 0x1f;
+
+const MISC_MEM_OPCODE: u32 = 0x0f;
+const FENCE_FUNCT3: u32 = 0x0;
+const FENCE_I_FUNCT3: u32 = 0x1;
+const FENCE_FM_SHIFT: u32 = 28;
+const FENCE_PRED_SHIFT: u32 = 24;
+const FENCE_SUCC_SHIFT: u32 = 20;
+const FENCE_SET_MASK: u32 = 0xf;
+const FENCE_TSO_FM: u32 = 0x8;
+// FENCE.TSO always orders reads and writes before reads and writes.
+const FENCE_TSO_SET: u32 = 0x3;
+pub(crate) const FENCE_SET_LETTERS: [(u8, char); 4] =
+    [(0x8, 'i'), (0x4, 'o'), (0x2, 'r'), (0x1, 'w')];
+
+const AMO_OPCODE: u32 = 0x2f;
+const AMO_W_FUNCT3: u32 = 0x2;
+// The top five bits of funct7 select the operation, and the low two bits hold the
+// aq and rl ordering bits.
+const AMO_FUNCT5_SHIFT: u32 = 27;
+const AMO_AQ_MASK: u32 = 0x4000000;
+const AMO_RL_MASK: u32 = 0x2000000;
+const LR_FUNCT5: u32 = 0x02;
+const SC_FUNCT5: u32 = 0x03;
+const AMOSWAP_FUNCT5: u32 = 0x01;
+const AMOADD_FUNCT5: u32 = 0x00;
+const AMOXOR_FUNCT5: u32 = 0x04;
+const AMOAND_FUNCT5: u32 = 0x0c;
+const AMOOR_FUNCT5: u32 = 0x08;
+const AMOMIN_FUNCT5: u32 = 0x10;
+const AMOMAX_FUNCT5: u32 = 0x14;
+const AMOMINU_FUNCT5: u32 = 0x18;
+const AMOMAXU_FUNCT5: u32 = 0x1c;
+
+const LOAD_FP_OPCODE: u32 = 0x07;
+const STORE_FP_OPCODE: u32 = 0x27;
+const FMADD_OPCODE: u32 = 0x43;
+const FMSUB_OPCODE: u32 = 0x47;
+const FNMSUB_OPCODE: u32 = 0x4b;
+const FNMADD_OPCODE: u32 = 0x4f;
+const OP_FP_OPCODE: u32 = 0x53;
+const OP_V_OPCODE: u32 = 0x57;
+const FLW_FUNCT3: u32 = 0x2;
+const FLD_FUNCT3: u32 = 0x3;
+const FSW_FUNCT3: u32 = 0x2;
+const FSD_FUNCT3: u32 = 0x3;
+const FP_FMT_MASK: u32 = 0x6000000;
+const FP_FMT_SHIFT: u32 = 25;
+const FP_FMT_SINGLE: u32 = 0x0;
+const FP_FMT_DOUBLE: u32 = 0x1;
+const FP_FUNCT5_SHIFT: u32 = 27;
+const RS3_SHIFT: u32 = 27;
+const FADD_FUNCT5: u32 = 0x00;
+const FSUB_FUNCT5: u32 = 0x01;
+const FMUL_FUNCT5: u32 = 0x02;
+const FDIV_FUNCT5: u32 = 0x03;
+const FSGNJ_FUNCT5: u32 = 0x04;
+const FMINMAX_FUNCT5: u32 = 0x05;
+const FCVT_FMT_FUNCT5: u32 = 0x08;
+const FSQRT_FUNCT5: u32 = 0x0b;
+const FCMP_FUNCT5: u32 = 0x14;
+const FCVT_INT_FUNCT5: u32 = 0x18;
+const FCVT_FROM_INT_FUNCT5: u32 = 0x1a;
+const FMV_X_FUNCT5: u32 = 0x1c;
+const FMV_FROM_X_FUNCT5: u32 = 0x1e;
+const FSGNJ_FUNCT3: u32 = 0x0;
+const FSGNJN_FUNCT3: u32 = 0x1;
+const FSGNJX_FUNCT3: u32 = 0x2;
+const FMIN_FUNCT3: u32 = 0x0;
+const FMAX_FUNCT3: u32 = 0x1;
+const FEQ_FUNCT3: u32 = 0x2;
+const FLT_FUNCT3: u32 = 0x1;
+const FLE_FUNCT3: u32 = 0x0;
+const FMV_X_FUNCT3: u32 = 0x0;
+const FCLASS_FUNCT3: u32 = 0x1;
+/// The rs2 field of a unary OP-FP instruction that has no variants to select.
+const FP_UNARY_RS2: u32 = 0x0;
+const FCVT_W_RS2: u32 = 0x0;
+const FCVT_WU_RS2: u32 = 0x1;
+
+const OPCODE_MASK: u32 = 0x7f;
+const FUNCT3_MASK: u32 = 0x7000;
+const RD_MASK: u32 = 0xf80;
+const RS1_MASK: u32 = 0xf8000;
+const RS2_MASK: u32 = 0x1f00000;
+const IMM_I_MASK: u32 = 0xfff00000;
+const IMM_S_HIGH_MASK: u32 = 0xfe000000;
+const IMM_S_LOW_MASK: u32 = 0xf80;
+const FUNCT7_MASK: u32 = 0xfe000000;
+const IMM_U_MASK: u32 = 0xfffff000;
+const IMM_J_20_MASK: u32 = 0x80000000;
+const IMM_J_19_12_MASK: u32 = 0xff000;
+const IMM_J_11_MASK: u32 = 0x100000;
+const IMM_J_10_1_MASK: u32 = 0x7fe00000;
+const IMM_B_12_MASK: u32 = 0x80000000;
+const IMM_B_11_MASK: u32 = 0x80;
+const IMM_B_10_5_MASK: u32 = 0x7e000000;
+const IMM_B_4_1_MASK: u32 = 0xf00;
+
+const FUNCT3_SHIFT: u32 = 12;
+const RD_SHIFT: u32 = 7;
+const RS1_SHIFT: u32 = 15;
+const RS2_SHIFT: u32 = 20;
+const IMM_I_SHIFT: u32 = 20;
+const IMM_S_HIGH_SHIFT: u32 = 20;
+const IMM_S_LOW_SHIFT: u32 = 7;
+const FUNCT7_SHIFT: u32 = 25;
+const IMM_J_20_SHIFT: u32 = 11;
+const IMM_J_11_SHIFT: u32 = 9;
+const IMM_J_10_1_SHIFT: u32 = 20;
+const IMM_B_12_SHIFT: u32 = 19;
+const IMM_B_11_SHIFT: u32 = 4;
+const IMM_B_10_5_SHIFT: u32 = 20;
+const IMM_B_4_1_SHIFT: u32 = 7;
+
+/// Explains why a word with an opcode that `decode` does not handle was rejected.
+fn unknown_opcode_error(word: u32, opcode: u32) -> DecodeError {
+    let kind = match opcode {
+        OP_V_OPCODE => DecodeErrorKind::UnsupportedExtension(Extension::V),
+        CUSTOM_0_OPCODE | CUSTOM_1_OPCODE => DecodeErrorKind::UnregisteredCustom,
+        _ => DecodeErrorKind::UnknownOpcode,
+    };
+
+    DecodeError::new(word, kind)
+}
+
+/// Decodes a word for `Profile::Rv64i`.
+///
+/// The loads, stores, shifts and word instructions that RV64I adds or widens are
+/// decoded here; everything else is shared with RV32I. The optional extensions are
+/// only implemented for 32-bit registers, so apart from Zifencei they are rejected.
+fn decode_rv64(word: u32, extensions: Extensions) -> Result<RiscVInstruction, DecodeError> {
+    let opcode = word & OPCODE_MASK;
+    let funct3 = (((word & FUNCT3_MASK) >> FUNCT3_SHIFT) & 0x7) as u8;
+    let funct7 = (word & FUNCT7_MASK) >> FUNCT7_SHIFT;
+    let rd = Register::from_field((word & RD_MASK) >> RD_SHIFT);
+    let rs1 = Register::from_field((word & RS1_MASK) >> RS1_SHIFT);
+    let rs2 = Register::from_field((word & RS2_MASK) >> RS2_SHIFT);
+    let imm = ((word & IMM_I_MASK) as i32 >> IMM_I_SHIFT) as i16;
+
+    match opcode {
+        LOAD_OPCODE if funct3 == LD_FUNCT3 => return Ok(RiscVInstruction::Ld { rd, rs1, imm }),
+        LOAD_OPCODE if funct3 == LWU_FUNCT3 => return Ok(RiscVInstruction::Lwu { rd, rs1, imm }),
+        STORE_OPCODE if funct3 == SD_FUNCT3 => {
+            let imm = (((word & IMM_S_HIGH_MASK) as i32 >> IMM_S_HIGH_SHIFT)
+                | ((word & IMM_S_LOW_MASK) >> IMM_S_LOW_SHIFT) as i32) as i16;
+            return Ok(RiscVInstruction::Sd { rs1, rs2, imm });
+        }
+        IMM_OPCODE if funct3 == SLLI_FUNCT3 || funct3 == SRLI_FUNCT3 => {
+            let funct6 = word >> WIDE_SHIFT_FUNCT6_SHIFT;
+            let imm = ((word & WIDE_SHAMT_MASK) >> RS2_SHIFT) as i16;
+            match (funct3, funct6) {
+                (SLLI_FUNCT3, SLLI_FUNCT6) => return Ok(RiscVInstruction::Slli { rd, rs1, imm }),
+                (SRLI_FUNCT3, SRLI_FUNCT6) => return Ok(RiscVInstruction::Srli { rd, rs1, imm }),
+                (SRLI_FUNCT3, SRAI_FUNCT6) => return Ok(RiscVInstruction::Srai { rd, rs1, imm }),
+                _ => {}
+            }
+        }
+        IMM_32_OPCODE => return decode_imm_32(word, funct3, funct7, rd, rs1, imm),
+        REG_32_OPCODE => return decode_reg_32(word, funct3, funct7, rd, rs1, rs2),
+        _ => {}
+    }
+
+    let instruction = RiscVInstruction::decode_with(word, extensions)?;
+    match instruction.extension() {
+        Some(extension) if extension != Extension::Zifencei => {
+            Err(DecodeError::new(word, DecodeErrorKind::Unimplemented))
+        }
+        _ => Ok(instruction),
+    }
+}
+
+/// Decodes an OP-IMM-32 instruction: ADDIW, SLLIW, SRLIW or SRAIW.
+fn decode_imm_32(
+    word: u32,
+    funct3: u8,
+    funct7: u32,
+    rd: Register,
+    rs1: Register,
+    imm: i16,
+) -> Result<RiscVInstruction, DecodeError> {
+    // The word shifts keep the five-bit shift amount of RV32I.
+    let shamt = imm & 0x1f;
+    match (funct3, funct7) {
+        (ADDI_FUNCT3, _) => Ok(RiscVInstruction::Addiw { rd, rs1, imm }),
+        (SLLI_FUNCT3, SLLI_FUNCT7) => Ok(RiscVInstruction::Slliw {
+            rd,
+            rs1,
+            imm: shamt,
+        }),
+        (SRLI_FUNCT3, SRLI_FUNCT7) => Ok(RiscVInstruction::Srliw {
+            rd,
+            rs1,
+            imm: shamt,
+        }),
+        (SRLI_FUNCT3, SRAI_FUNCT7) => Ok(RiscVInstruction::Sraiw {
+            rd,
+            rs1,
+            imm: shamt,
+        }),
+        (SLLI_FUNCT3 | SRLI_FUNCT3, _) => {
+            Err(DecodeError::new(word, DecodeErrorKind::ReservedFunct7))
+        }
+        _ => Err(DecodeError::new(word, DecodeErrorKind::ReservedFunct3)),
+    }
+}
+
+/// Decodes an OP-32 instruction: one of the RV64I or RV64M word instructions.
+fn decode_reg_32(
+    word: u32,
+    funct3: u8,
+    funct7: u32,
+    rd: Register,
+    rs1: Register,
+    rs2: Register,
+) -> Result<RiscVInstruction, DecodeError> {
+    let instruction = match (funct7, funct3) {
+        (ADD_FUNCT7, ADD_FUNCT3) => RiscVInstruction::Addw { rd, rs1, rs2 },
+        (SUB_FUNCT7, ADD_FUNCT3) => RiscVInstruction::Subw { rd, rs1, rs2 },
+        (SLL_FUNCT7, SLL_FUNCT3) => RiscVInstruction::Sllw { rd, rs1, rs2 },
+        (SRL_FUNCT7, SRL_FUNCT3) => RiscVInstruction::Srlw { rd, rs1, rs2 },
+        (SRA_FUNCT7, SRL_FUNCT3) => RiscVInstruction::Sraw { rd, rs1, rs2 },
+        (MULDIV_FUNCT7, MUL_FUNCT3) => RiscVInstruction::Mulw { rd, rs1, rs2 },
+        (MULDIV_FUNCT7, DIV_FUNCT3) => RiscVInstruction::Divw { rd, rs1, rs2 },
+        (MULDIV_FUNCT7, DIVU_FUNCT3) => RiscVInstruction::Divuw { rd, rs1, rs2 },
+        (MULDIV_FUNCT7, REM_FUNCT3) => RiscVInstruction::Remw { rd, rs1, rs2 },
+        (MULDIV_FUNCT7, REMU_FUNCT3) => RiscVInstruction::Remuw { rd, rs1, rs2 },
+        (ADD_FUNCT7 | SUB_FUNCT7 | MULDIV_FUNCT7, _) => {
+            return Err(DecodeError::new(word, DecodeErrorKind::ReservedFunct3));
+        }
+        _ => return Err(DecodeError::new(word, DecodeErrorKind::ReservedFunct7)),
+    };
+
+    Ok(instruction)
+}
+
+/// Decodes a MISC-MEM instruction: FENCE, FENCE.TSO or FENCE.I.
+///
+/// The register fields and the unused immediate bits must be zero.
+fn decode_misc_mem(word: u32, extensions: Extensions) -> Result<RiscVInstruction, DecodeError> {
+    let funct3 = (word & FUNCT3_MASK) >> FUNCT3_SHIFT;
+
+    // Base implementations ignore rd, rs1 and the FENCE.I immediate, and run reserved
+    // fence modes as ordinary fences.
+    match funct3 {
+        FENCE_FUNCT3 => {
+            let fm = word >> FENCE_FM_SHIFT;
+            let pred = (word >> FENCE_PRED_SHIFT) & FENCE_SET_MASK;
+            let succ = (word >> FENCE_SUCC_SHIFT) & FENCE_SET_MASK;
+
+            match fm {
+                FENCE_TSO_FM if pred == FENCE_TSO_SET && succ == FENCE_TSO_SET => {
+                    Ok(RiscVInstruction::FenceTso)
+                }
+                _ => Ok(RiscVInstruction::Fence {
+                    pred: pred as u8,
+                    succ: succ as u8,
+                }),
+            }
+        }
+        FENCE_I_FUNCT3 => require_extension(word, RiscVInstruction::FenceI, extensions),
+        _ => Err(DecodeError::new(word, DecodeErrorKind::ReservedFunct3)),
+    }
+}
+
+/// Decodes an AMO instruction: LR.W, SC.W or one of the atomic memory operations.
+fn decode_atomic(word: u32, extensions: Extensions) -> Result<RiscVInstruction, DecodeError> {
+    let funct3 = (word & FUNCT3_MASK) >> FUNCT3_SHIFT;
+    if funct3 != AMO_W_FUNCT3 {
+        return Err(DecodeError::new(word, DecodeErrorKind::ReservedFunct3));
+    }
+
+    let rd = Register::from_field((word & RD_MASK) >> RD_SHIFT);
+    let rs1 = Register::from_field((word & RS1_MASK) >> RS1_SHIFT);
+    let rs2 = Register::from_field((word & RS2_MASK) >> RS2_SHIFT);
+    let aq = word & AMO_AQ_MASK != 0;
+    let rl = word & AMO_RL_MASK != 0;
+
+    let instruction = match word >> AMO_FUNCT5_SHIFT {
+        LR_FUNCT5 if rs2 == Register::ZERO => RiscVInstruction::LrW { rd, rs1, aq, rl },
+        LR_FUNCT5 => return Err(DecodeError::new(word, DecodeErrorKind::InvalidOperands)),
+        SC_FUNCT5 => RiscVInstruction::ScW {
+            rd,
+            rs1,
+            rs2,
+            aq,
+            rl,
+        },
+        AMOSWAP_FUNCT5 => RiscVInstruction::AmoswapW {
+            rd,
+            rs1,
+            rs2,
+            aq,
+            rl,
+        },
+        AMOADD_FUNCT5 => RiscVInstruction::AmoaddW {
+            rd,
+            rs1,
+            rs2,
+            aq,
+            rl,
+        },
+        AMOXOR_FUNCT5 => RiscVInstruction::AmoxorW {
+            rd,
+            rs1,
+            rs2,
+            aq,
+            rl,
+        },
+        AMOAND_FUNCT5 => RiscVInstruction::AmoandW {
+            rd,
+            rs1,
+            rs2,
+            aq,
+            rl,
+        },
+        AMOOR_FUNCT5 => RiscVInstruction::AmoorW {
+            rd,
+            rs1,
+            rs2,
+            aq,
+            rl,
+        },
+        AMOMIN_FUNCT5 => RiscVInstruction::AmominW {
+            rd,
+            rs1,
+            rs2,
+            aq,
+            rl,
+        },
+        AMOMAX_FUNCT5 => RiscVInstruction::AmomaxW {
+            rd,
+            rs1,
+            rs2,
+            aq,
+            rl,
+        },
+        AMOMINU_FUNCT5 => RiscVInstruction::AmominuW {
+            rd,
+            rs1,
+            rs2,
+            aq,
+            rl,
+        },
+        AMOMAXU_FUNCT5 => RiscVInstruction::AmomaxuW {
+            rd,
+            rs1,
+            rs2,
+            aq,
+            rl,
+        },
+        _ => return Err(DecodeError::new(word, DecodeErrorKind::ReservedFunct7)),
+    };
+
+    require_extension(word, instruction, extensions)
+}
+
+/// Decodes an instruction of the F or D extensions: a floating-point load or store, a
+/// fused multiply-add or an OP-FP instruction.
+///
+/// The half-precision and quad-precision formats of the fmt field are not implemented.
+fn decode_floating_point(
+    word: u32,
+    extensions: Extensions,
+) -> Result<RiscVInstruction, DecodeError> {
+    let opcode = word & OPCODE_MASK;
+    let funct3 = (word & FUNCT3_MASK) >> FUNCT3_SHIFT;
+    let fmt = (word & FP_FMT_MASK) >> FP_FMT_SHIFT;
+    let rd_field = (word & RD_MASK) >> RD_SHIFT;
+    let rs1_field = (word & RS1_MASK) >> RS1_SHIFT;
+    let rs2_field = (word & RS2_MASK) >> RS2_SHIFT;
+
+    let rd = FloatRegister::from_field(rd_field);
+    let rs1 = FloatRegister::from_field(rs1_field);
+    let rs2 = FloatRegister::from_field(rs2_field);
+    let rs3 = FloatRegister::from_field(word >> RS3_SHIFT);
+    // Conversions and moves name an integer register in place of rd or rs1.
+    let xd = Register::from_field(rd_field);
+    let xs1 = Register::from_field(rs1_field);
+
+    let error = |kind| DecodeError::new(word, kind);
+    // The rm field of an instruction that rounds takes the place of funct3, and the
+    // rounding modes 5 and 6 are reserved.
+    let rm = || RoundingMode::from_field(funct3).ok_or(error(DecodeErrorKind::ReservedFunct3));
+
+    let instruction = match opcode {
+        LOAD_FP_OPCODE => {
+            let imm = ((word & IMM_I_MASK) as i32 >> IMM_I_SHIFT) as i16;
+            match funct3 {
+                FLW_FUNCT3 => RiscVInstruction::Flw { rd, rs1: xs1, imm },
+                FLD_FUNCT3 => RiscVInstruction::Fld { rd, rs1: xs1, imm },
+                // The other widths are vector loads.
+                _ => return Err(error(DecodeErrorKind::UnsupportedExtension(Extension::V))),
+            }
+        }
+        STORE_FP_OPCODE => {
+            let imm = (((word & IMM_S_HIGH_MASK) as i32 >> IMM_S_HIGH_SHIFT)
+                | ((word & IMM_S_LOW_MASK) >> IMM_S_LOW_SHIFT) as i32) as i16;
+            match funct3 {
+                FSW_FUNCT3 => RiscVInstruction::Fsw { rs1: xs1, rs2, imm },
+                FSD_FUNCT3 => RiscVInstruction::Fsd { rs1: xs1, rs2, imm },
+                // The other widths are vector stores.
+                _ => return Err(error(DecodeErrorKind::UnsupportedExtension(Extension::V))),
+            }
+        }
+        _ if fmt != FP_FMT_SINGLE && fmt != FP_FMT_DOUBLE => {
+            return Err(error(DecodeErrorKind::Unimplemented));
+        }
+        FMADD_OPCODE | FMSUB_OPCODE | FNMSUB_OPCODE | FNMADD_OPCODE => {
+            let rm = rm()?;
+            match (opcode, fmt) {
+                (FMADD_OPCODE, FP_FMT_SINGLE) => RiscVInstruction::FmaddS {
+                    rd,
+                    rs1,
+                    rs2,
+                    rs3,
+                    rm,
+                },
+                (FMSUB_OPCODE, FP_FMT_SINGLE) => RiscVInstruction::FmsubS {
+                    rd,
+                    rs1,
+                    rs2,
+                    rs3,
+                    rm,
+                },
+                (FNMSUB_OPCODE, FP_FMT_SINGLE) => RiscVInstruction::FnmsubS {
+                    rd,
+                    rs1,
+                    rs2,
+                    rs3,
+                    rm,
+                },
+                (FNMADD_OPCODE, FP_FMT_SINGLE) => RiscVInstruction::FnmaddS {
+                    rd,
+                    rs1,
+                    rs2,
+                    rs3,
+                    rm,
+                },
+                (FMADD_OPCODE, _) => RiscVInstruction::FmaddD {
+                    rd,
+                    rs1,
+                    rs2,
+                    rs3,
+                    rm,
+                },
+                (FMSUB_OPCODE, _) => RiscVInstruction::FmsubD {
+                    rd,
+                    rs1,
+                    rs2,
+                    rs3,
+                    rm,
+                },
+                (FNMSUB_OPCODE, _) => RiscVInstruction::FnmsubD {
+                    rd,
+                    rs1,
+                    rs2,
+                    rs3,
+                    rm,
+                },
+                _ => RiscVInstruction::FnmaddD {
+                    rd,
+                    rs1,
+                    rs2,
+                    rs3,
+                    rm,
+                },
+            }
+        }
+        // The rs2 field of a unary instruction selects the operation, or the source
+        // format of a conversion between formats.
+        _ => match (word >> FP_FUNCT5_SHIFT, fmt, rs2_field, funct3) {
+            (FADD_FUNCT5, FP_FMT_SINGLE, _, _) => RiscVInstruction::FaddS {
+                rd,
+                rs1,
+                rs2,
+                rm: rm()?,
+            },
+            (FSUB_FUNCT5, FP_FMT_SINGLE, _, _) => RiscVInstruction::FsubS {
+                rd,
+                rs1,
+                rs2,
+                rm: rm()?,
+            },
+            (FMUL_FUNCT5, FP_FMT_SINGLE, _, _) => RiscVInstruction::FmulS {
+                rd,
+                rs1,
+                rs2,
+                rm: rm()?,
+            },
+            (FDIV_FUNCT5, FP_FMT_SINGLE, _, _) => RiscVInstruction::FdivS {
+                rd,
+                rs1,
+                rs2,
+                rm: rm()?,
+            },
+            (FADD_FUNCT5, FP_FMT_DOUBLE, _, _) => RiscVInstruction::FaddD {
+                rd,
+                rs1,
+                rs2,
+                rm: rm()?,
+            },
+            (FSUB_FUNCT5, FP_FMT_DOUBLE, _, _) => RiscVInstruction::FsubD {
+                rd,
+                rs1,
+                rs2,
+                rm: rm()?,
+            },
+            (FMUL_FUNCT5, FP_FMT_DOUBLE, _, _) => RiscVInstruction::FmulD {
+                rd,
+                rs1,
+                rs2,
+                rm: rm()?,
+            },
+            (FDIV_FUNCT5, FP_FMT_DOUBLE, _, _) => RiscVInstruction::FdivD {
+                rd,
+                rs1,
+                rs2,
+                rm: rm()?,
+            },
+            (FSQRT_FUNCT5, FP_FMT_SINGLE, FP_UNARY_RS2, _) => {
+                RiscVInstruction::FsqrtS { rd, rs1, rm: rm()? }
+            }
+            (FSQRT_FUNCT5, FP_FMT_DOUBLE, FP_UNARY_RS2, _) => {
+                RiscVInstruction::FsqrtD { rd, rs1, rm: rm()? }
+            }
+            (FSGNJ_FUNCT5, FP_FMT_SINGLE, _, FSGNJ_FUNCT3) => {
+                RiscVInstruction::FsgnjS { rd, rs1, rs2 }
+            }
+            (FSGNJ_FUNCT5, FP_FMT_SINGLE, _, FSGNJN_FUNCT3) => {
+                RiscVInstruction::FsgnjnS { rd, rs1, rs2 }
+            }
+            (FSGNJ_FUNCT5, FP_FMT_SINGLE, _, FSGNJX_FUNCT3) => {
+                RiscVInstruction::FsgnjxS { rd, rs1, rs2 }
+            }
+            (FSGNJ_FUNCT5, FP_FMT_DOUBLE, _, FSGNJ_FUNCT3) => {
+                RiscVInstruction::FsgnjD { rd, rs1, rs2 }
+            }
+            (FSGNJ_FUNCT5, FP_FMT_DOUBLE, _, FSGNJN_FUNCT3) => {
+                RiscVInstruction::FsgnjnD { rd, rs1, rs2 }
+            }
+            (FSGNJ_FUNCT5, FP_FMT_DOUBLE, _, FSGNJX_FUNCT3) => {
+                RiscVInstruction::FsgnjxD { rd, rs1, rs2 }
+            }
+            (FMINMAX_FUNCT5, FP_FMT_SINGLE, _, FMIN_FUNCT3) => {
+                RiscVInstruction::FminS { rd, rs1, rs2 }
+            }
+            (FMINMAX_FUNCT5, FP_FMT_SINGLE, _, FMAX_FUNCT3) => {
+                RiscVInstruction::FmaxS { rd, rs1, rs2 }
+            }
+            (FMINMAX_FUNCT5, FP_FMT_DOUBLE, _, FMIN_FUNCT3) => {
+                RiscVInstruction::FminD { rd, rs1, rs2 }
+            }
+            (FMINMAX_FUNCT5, FP_FMT_DOUBLE, _, FMAX_FUNCT3) => {
+                RiscVInstruction::FmaxD { rd, rs1, rs2 }
+            }
+            (FCVT_FMT_FUNCT5, FP_FMT_SINGLE, FP_FMT_DOUBLE, _) => {
+                RiscVInstruction::FcvtSD { rd, rs1, rm: rm()? }
+            }
+            (FCVT_FMT_FUNCT5, FP_FMT_DOUBLE, FP_FMT_SINGLE, _) => {
+                RiscVInstruction::FcvtDS { rd, rs1, rm: rm()? }
+            }
+            (FCMP_FUNCT5, FP_FMT_SINGLE, _, FEQ_FUNCT3) => {
+                RiscVInstruction::FeqS { rd: xd, rs1, rs2 }
+            }
+            (FCMP_FUNCT5, FP_FMT_SINGLE, _, FLT_FUNCT3) => {
+                RiscVInstruction::FltS { rd: xd, rs1, rs2 }
+            }
+            (FCMP_FUNCT5, FP_FMT_SINGLE, _, FLE_FUNCT3) => {
+                RiscVInstruction::FleS { rd: xd, rs1, rs2 }
+            }
+            (FCMP_FUNCT5, FP_FMT_DOUBLE, _, FEQ_FUNCT3) => {
+                RiscVInstruction::FeqD { rd: xd, rs1, rs2 }
+            }
+            (FCMP_FUNCT5, FP_FMT_DOUBLE, _, FLT_FUNCT3) => {
+                RiscVInstruction::FltD { rd: xd, rs1, rs2 }
+            }
+            (FCMP_FUNCT5, FP_FMT_DOUBLE, _, FLE_FUNCT3) => {
+                RiscVInstruction::FleD { rd: xd, rs1, rs2 }
+            }
+            (FCVT_INT_FUNCT5, FP_FMT_SINGLE, FCVT_W_RS2, _) => RiscVInstruction::FcvtWS {
+                rd: xd,
+                rs1,
+                rm: rm()?,
+            },
+            (FCVT_INT_FUNCT5, FP_FMT_SINGLE, FCVT_WU_RS2, _) => RiscVInstruction::FcvtWuS {
+                rd: xd,
+                rs1,
+                rm: rm()?,
+            },
+            (FCVT_INT_FUNCT5, FP_FMT_DOUBLE, FCVT_W_RS2, _) => RiscVInstruction::FcvtWD {
+                rd: xd,
+                rs1,
+                rm: rm()?,
+            },
+            (FCVT_INT_FUNCT5, FP_FMT_DOUBLE, FCVT_WU_RS2, _) => RiscVInstruction::FcvtWuD {
+                rd: xd,
+                rs1,
+                rm: rm()?,
+            },
+            (FCVT_FROM_INT_FUNCT5, FP_FMT_SINGLE, FCVT_W_RS2, _) => RiscVInstruction::FcvtSW {
+                rd,
+                rs1: xs1,
+                rm: rm()?,
+            },
+            (FCVT_FROM_INT_FUNCT5, FP_FMT_SINGLE, FCVT_WU_RS2, _) => RiscVInstruction::FcvtSWu {
+                rd,
+                rs1: xs1,
+                rm: rm()?,
+            },
+            (FCVT_FROM_INT_FUNCT5, FP_FMT_DOUBLE, FCVT_W_RS2, _) => RiscVInstruction::FcvtDW {
+                rd,
+                rs1: xs1,
+                rm: rm()?,
+            },
+            (FCVT_FROM_INT_FUNCT5, FP_FMT_DOUBLE, FCVT_WU_RS2, _) => RiscVInstruction::FcvtDWu {
+                rd,
+                rs1: xs1,
+                rm: rm()?,
+            },
+            (FMV_X_FUNCT5, FP_FMT_SINGLE, FP_UNARY_RS2, FMV_X_FUNCT3) => {
+                RiscVInstruction::FmvXW { rd: xd, rs1 }
+            }
+            (FMV_X_FUNCT5, FP_FMT_SINGLE, FP_UNARY_RS2, FCLASS_FUNCT3) => {
+                RiscVInstruction::FclassS { rd: xd, rs1 }
+            }
+            (FMV_X_FUNCT5, FP_FMT_DOUBLE, FP_UNARY_RS2, FCLASS_FUNCT3) => {
+                RiscVInstruction::FclassD { rd: xd, rs1 }
+            }
+            (FMV_FROM_X_FUNCT5, FP_FMT_SINGLE, FP_UNARY_RS2, FMV_X_FUNCT3) => {
+                RiscVInstruction::FmvWX { rd, rs1: xs1 }
+            }
+            // fmv.x.d and fmv.d.x only exist in RV64.
+            (FMV_X_FUNCT5 | FMV_FROM_X_FUNCT5, _, FP_UNARY_RS2, _)
+            | (FSGNJ_FUNCT5 | FMINMAX_FUNCT5 | FCMP_FUNCT5, _, _, _) => {
+                return Err(error(DecodeErrorKind::ReservedFunct3));
+            }
+            (
+                FSQRT_FUNCT5 | FCVT_FMT_FUNCT5 | FCVT_INT_FUNCT5 | FCVT_FROM_INT_FUNCT5
+                | FMV_X_FUNCT5 | FMV_FROM_X_FUNCT5,
+                _,
+                _,
+                _,
+            ) => return Err(error(DecodeErrorKind::InvalidOperands)),
+            _ => return Err(error(DecodeErrorKind::ReservedFunct7)),
+        },
+    };
+
+    require_extension(word, instruction, extensions)
+}
+
+/// Decodes a SYSTEM instruction other than ECALL/EBREAK, which is a CSR instruction
+/// unless funct3 is reserved.
+fn decode_csr(word: u32, funct3: u32) -> Result<RiscVInstruction, DecodeError> {
+    let rd = Register::from_field((word & RD_MASK) >> RD_SHIFT);
+    let field = (word & RS1_MASK) >> RS1_SHIFT;
+    let rs1 = Register::from_field(field);
+    let imm = field as u8;
+    let csr = ((word & IMM_I_MASK) >> IMM_I_SHIFT) as u16;
+
+    match funct3 {
+        CSRRW_FUNCT3 => Ok(RiscVInstruction::Csrrw { rd, rs1, csr }),
+        CSRRS_FUNCT3 => Ok(RiscVInstruction::Csrrs { rd, rs1, csr }),
+        CSRRC_FUNCT3 => Ok(RiscVInstruction::Csrrc { rd, rs1, csr }),
+        CSRRWI_FUNCT3 => Ok(RiscVInstruction::Csrrwi { rd, imm, csr }),
+        CSRRSI_FUNCT3 => Ok(RiscVInstruction::Csrrsi { rd, imm, csr }),
+        CSRRCI_FUNCT3 => Ok(RiscVInstruction::Csrrci { rd, imm, csr }),
+        _ => Err(DecodeError::new(word, DecodeErrorKind::ReservedFunct3)),
+    }
+}
+
+/// Decodes the optional extension instructions of the OP opcode, or returns `None`
+/// if the function fields name none of them.
+fn decode_optional_register(
+    funct3: u8,
+    funct7: u32,
+    rd: Register,
+    rs1: Register,
+    rs2: Register,
+) -> Option<RiscVInstruction> {
+    let instruction = match (funct7, funct3) {
+        (SHADD_FUNCT7, SH1ADD_FUNCT3) => RiscVInstruction::Sh1add { rd, rs1, rs2 },
+        (SHADD_FUNCT7, SH2ADD_FUNCT3) => RiscVInstruction::Sh2add { rd, rs1, rs2 },
+        (SHADD_FUNCT7, SH3ADD_FUNCT3) => RiscVInstruction::Sh3add { rd, rs1, rs2 },
+        (INVERTED_FUNCT7, ANDN_FUNCT3) => RiscVInstruction::Andn { rd, rs1, rs2 },
+        (INVERTED_FUNCT7, ORN_FUNCT3) => RiscVInstruction::Orn { rd, rs1, rs2 },
+        (INVERTED_FUNCT7, XNOR_FUNCT3) => RiscVInstruction::Xnor { rd, rs1, rs2 },
+        (MINMAX_FUNCT7, MIN_FUNCT3) => RiscVInstruction::Min { rd, rs1, rs2 },
+        (MINMAX_FUNCT7, MINU_FUNCT3) => RiscVInstruction::Minu { rd, rs1, rs2 },
+        (MINMAX_FUNCT7, MAX_FUNCT3) => RiscVInstruction::Max { rd, rs1, rs2 },
+        (MINMAX_FUNCT7, MAXU_FUNCT3) => RiscVInstruction::Maxu { rd, rs1, rs2 },
+        (ZEXTH_FUNCT7, ZEXTH_FUNCT3) if rs2 == Register::ZERO => {
+            RiscVInstruction::ZextH { rd, rs1 }
+        }
+        (ROTATE_FUNCT7, ROL_FUNCT3) => RiscVInstruction::Rol { rd, rs1, rs2 },
+        (ROTATE_FUNCT7, ROR_FUNCT3) => RiscVInstruction::Ror { rd, rs1, rs2 },
+        (BCLR_FUNCT7, SINGLE_BIT_FUNCT3) => RiscVInstruction::Bclr { rd, rs1, rs2 },
+        (BCLR_FUNCT7, BEXT_FUNCT3) => RiscVInstruction::Bext { rd, rs1, rs2 },
+        (BINV_FUNCT7, SINGLE_BIT_FUNCT3) => RiscVInstruction::Binv { rd, rs1, rs2 },
+        (BSET_FUNCT7, SINGLE_BIT_FUNCT3) => RiscVInstruction::Bset { rd, rs1, rs2 },
+        (SHA512SUM0R_FUNCT7, SHA512_FUNCT3) => RiscVInstruction::Sha512sum0r { rd, rs1, rs2 },
+        (SHA512SUM1R_FUNCT7, SHA512_FUNCT3) => RiscVInstruction::Sha512sum1r { rd, rs1, rs2 },
+        (SHA512SIG0L_FUNCT7, SHA512_FUNCT3) => RiscVInstruction::Sha512sig0l { rd, rs1, rs2 },
+        (SHA512SIG0H_FUNCT7, SHA512_FUNCT3) => RiscVInstruction::Sha512sig0h { rd, rs1, rs2 },
+        (SHA512SIG1L_FUNCT7, SHA512_FUNCT3) => RiscVInstruction::Sha512sig1l { rd, rs1, rs2 },
+        (SHA512SIG1H_FUNCT7, SHA512_FUNCT3) => RiscVInstruction::Sha512sig1h { rd, rs1, rs2 },
+        _ => return None,
+    };
+
+    Some(instruction)
+}
+
+/// Decodes the optional extension instructions of the OP-IMM opcode, or returns
+/// `None` if the function fields name none of them.
+///
+/// `shamt` is the rs2 field, which holds the shift amount of the immediate forms and
+/// selects the operation of the unary forms.
+fn decode_optional_immediate(
+    funct3: u8,
+    funct7: u32,
+    rd: Register,
+    rs1: Register,
+    shamt: u32,
+) -> Option<RiscVInstruction> {
+    let imm = shamt as i16;
+    let instruction = match (funct7, funct3, shamt) {
+        (UNARY_FUNCT7, SLLI_FUNCT3, CLZ_RS2) => RiscVInstruction::Clz { rd, rs1 },
+        (UNARY_FUNCT7, SLLI_FUNCT3, CTZ_RS2) => RiscVInstruction::Ctz { rd, rs1 },
+        (UNARY_FUNCT7, SLLI_FUNCT3, CPOP_RS2) => RiscVInstruction::Cpop { rd, rs1 },
+        (UNARY_FUNCT7, SLLI_FUNCT3, SEXTB_RS2) => RiscVInstruction::SextB { rd, rs1 },
+        (UNARY_FUNCT7, SLLI_FUNCT3, SEXTH_RS2) => RiscVInstruction::SextH { rd, rs1 },
+        (ROTATE_FUNCT7, SRLI_FUNCT3, _) => RiscVInstruction::Rori { rd, rs1, imm },
+        (ORCB_FUNCT7, SRLI_FUNCT3, ORCB_RS2) => RiscVInstruction::OrcB { rd, rs1 },
+        (REV8_FUNCT7, SRLI_FUNCT3, REV8_RS2) => RiscVInstruction::Rev8 { rd, rs1 },
+        (BCLR_FUNCT7, SLLI_FUNCT3, _) => RiscVInstruction::Bclri { rd, rs1, imm },
+        (BCLR_FUNCT7, SRLI_FUNCT3, _) => RiscVInstruction::Bexti { rd, rs1, imm },
+        (BINV_FUNCT7, SLLI_FUNCT3, _) => RiscVInstruction::Binvi { rd, rs1, imm },
+        (BSET_FUNCT7, SLLI_FUNCT3, _) => RiscVInstruction::Bseti { rd, rs1, imm },
+        (SHA256_FUNCT7, SLLI_FUNCT3, SHA256SUM0_RS2) => RiscVInstruction::Sha256sum0 { rd, rs1 },
+        (SHA256_FUNCT7, SLLI_FUNCT3, SHA256SUM1_RS2) => RiscVInstruction::Sha256sum1 { rd, rs1 },
+        (SHA256_FUNCT7, SLLI_FUNCT3, SHA256SIG0_RS2) => RiscVInstruction::Sha256sig0 { rd, rs1 },
+        (SHA256_FUNCT7, SLLI_FUNCT3, SHA256SIG1_RS2) => RiscVInstruction::Sha256sig1 { rd, rs1 },
+        _ => return None,
+    };
+
+    Some(instruction)
+}
+
+/// Accepts an instruction only if every register it names is part of `profile`.
+pub(crate) fn require_profile(
+    word: u32,
+    instruction: RiscVInstruction,
+    profile: Profile,
+) -> Result<RiscVInstruction, DecodeError> {
+    let outside = instruction
+        .reads()
+        .chain(instruction.writes())
+        .find(|register| !profile.contains(*register));
+
+    match outside {
+        Some(register) => Err(DecodeError::new(
+            word,
+            DecodeErrorKind::UnsupportedRegister { register, profile },
+        )),
+        None => Ok(instruction),
+    }
+}
+
+/// Accepts an instruction of an optional extension only if the extension is enabled.
+fn require_extension(
+    word: u32,
+    instruction: RiscVInstruction,
+    extensions: Extensions,
+) -> Result<RiscVInstruction, DecodeError> {
+    match instruction.extension() {
+        Some(extension) if !extensions.contains(extension) => Err(DecodeError::new(
+            word,
+            DecodeErrorKind::UnsupportedExtension(extension),
+        )),
+        _ => Ok(instruction),
+    }
+}
+
+fn encode_register(reg: Register) -> u32 {
+    reg.number() as u32
+}
+
+/// Checks that a signed immediate fits in `bits` bits and is a multiple of `align`.
+fn encode_immediate(imm: i32, bits: u32, align: i32) -> Result<u32, Error> {
+    let min = -(1 << (bits - 1));
+    let max = (1 << (bits - 1)) - 1;
+    if imm < min || imm > max || imm % align != 0 {
+        Err(Error::InvalidImmediate)
+    } else {
+        Ok(imm as u32)
+    }
+}
+
+fn encode_r(
+    opcode: u32,
+    funct3: u8,
+    funct7: u32,
+    rd: Register,
+    rs1: Register,
+    rs2: Register,
+) -> Result<u32, Error> {
+    Ok((funct7 << FUNCT7_SHIFT)
+        | (encode_register(rs2) << RS2_SHIFT)
+        | (encode_register(rs1) << RS1_SHIFT)
+        | ((funct3 as u32) << FUNCT3_SHIFT)
+        | (encode_register(rd) << RD_SHIFT)
+        | opcode)
+}
+
+fn encode_atomic(
+    funct5: u32,
+    aq: bool,
+    rl: bool,
+    rd: Register,
+    rs1: Register,
+    rs2: Register,
+) -> Result<u32, Error> {
+    let funct7 = (funct5 << 2) | ((aq as u32) << 1) | rl as u32;
+    encode_r(AMO_OPCODE, AMO_W_FUNCT3 as u8, funct7, rd, rs1, rs2)
+}
+
+/// Encodes an OP-FP instruction.
+///
+/// The register fields name integer or floating-point registers depending on the
+/// instruction, so they are passed as numbers. The rs2 field of a unary instruction
+/// selects the operation instead.
+fn encode_op_fp(
+    funct5: u32,
+    fmt: u32,
+    funct3: u32,
+    rd: u8,
+    rs1: u8,
+    rs2: u32,
+) -> Result<u32, Error> {
+    Ok((funct5 << FP_FUNCT5_SHIFT)
+        | (fmt << FP_FMT_SHIFT)
+        | (rs2 << RS2_SHIFT)
+        | ((rs1 as u32) << RS1_SHIFT)
+        | (funct3 << FUNCT3_SHIFT)
+        | ((rd as u32) << RD_SHIFT)
+        | OP_FP_OPCODE)
+}
+
+/// Encodes a fused multiply-add instruction, whose third source register takes the
+/// place of funct5.
+fn encode_r4(
+    opcode: u32,
+    fmt: u32,
+    rd: FloatRegister,
+    rs1: FloatRegister,
+    rs2: FloatRegister,
+    rs3: FloatRegister,
+    rm: RoundingMode,
+) -> Result<u32, Error> {
+    Ok(((rs3.number() as u32) << RS3_SHIFT)
+        | (fmt << FP_FMT_SHIFT)
+        | ((rs2.number() as u32) << RS2_SHIFT)
+        | ((rs1.number() as u32) << RS1_SHIFT)
+        | (rm.field() << FUNCT3_SHIFT)
+        | ((rd.number() as u32) << RD_SHIFT)
+        | opcode)
+}
+
+fn encode_fp_load(funct3: u32, rd: FloatRegister, rs1: Register, imm: i16) -> Result<u32, Error> {
+    let imm = encode_immediate(imm as i32, 12, 1)?;
+    Ok(((imm << IMM_I_SHIFT) & IMM_I_MASK)
+        | (encode_register(rs1) << RS1_SHIFT)
+        | (funct3 << FUNCT3_SHIFT)
+        | ((rd.number() as u32) << RD_SHIFT)
+        | LOAD_FP_OPCODE)
+}
+
+fn encode_fp_store(funct3: u32, rs1: Register, rs2: FloatRegister, imm: i16) -> Result<u32, Error> {
+    let imm = encode_immediate(imm as i32, 12, 1)?;
+    Ok(((imm << IMM_S_HIGH_SHIFT) & IMM_S_HIGH_MASK)
+        | ((rs2.number() as u32) << RS2_SHIFT)
+        | (encode_register(rs1) << RS1_SHIFT)
+        | (funct3 << FUNCT3_SHIFT)
+        | ((imm << IMM_S_LOW_SHIFT) & IMM_S_LOW_MASK)
+        | STORE_FP_OPCODE)
+}
+
+fn encode_i(opcode: u32, funct3: u8, rd: Register, rs1: Register, imm: i16) -> Result<u32, Error> {
+    let imm = encode_immediate(imm as i32, 12, 1)?;
+    Ok(((imm << IMM_I_SHIFT) & IMM_I_MASK)
+        | (encode_register(rs1) << RS1_SHIFT)
+        | ((funct3 as u32) << FUNCT3_SHIFT)
+        | (encode_register(rd) << RD_SHIFT)
+        | opcode)
+}
+
+fn encode_shift(
+    opcode: u32,
+    funct3: u8,
+    funct7: u32,
+    rd: Register,
+    rs1: Register,
+    shamt: i16,
+) -> Result<u32, Error> {
+    if !(0..32).contains(&shamt) {
+        return Err(Error::InvalidImmediate);
+    }
+    // The shift amount occupies the rs2 field.
+    encode_r(
+        opcode,
+        funct3,
+        funct7,
+        rd,
+        rs1,
+        Register::from_field(shamt as u32),
+    )
+}
+
+/// Encodes `slli`, `srli` or `srai`, whose shift amount may be up to 63 on RV64I.
+///
+/// Amounts of 32 and above set the low bit of funct7, which RV32I reserves.
+fn encode_wide_shift(
+    funct3: u8,
+    funct6: u32,
+    rd: Register,
+    rs1: Register,
+    shamt: i16,
+) -> Result<u32, Error> {
+    if !(0..64).contains(&shamt) {
+        return Err(Error::InvalidImmediate);
+    }
+
+    Ok((funct6 << WIDE_SHIFT_FUNCT6_SHIFT)
+        | ((shamt as u32) << RS2_SHIFT)
+        | (encode_register(rs1) << RS1_SHIFT)
+        | ((funct3 as u32) << FUNCT3_SHIFT)
+        | (encode_register(rd) << RD_SHIFT)
+        | IMM_OPCODE)
+}
+
+/// Encodes a CSR instruction, whose rs1 field holds either a register or an immediate.
+fn encode_csr(funct3: u32, rd: Register, field: u32, csr: u16) -> Result<u32, Error> {
+    if csr > CSR_MAX {
+        return Err(Error::InvalidImmediate);
+    }
+
+    Ok(((csr as u32) << IMM_I_SHIFT)
+        | (field << RS1_SHIFT)
+        | (funct3 << FUNCT3_SHIFT)
+        | (encode_register(rd) << RD_SHIFT)
+        | SYSTEM_OPCODE)
+}
+
+/// Checks that the immediate of a CSR*I instruction fits in the rs1 field.
+fn encode_csr_immediate(imm: u8) -> Result<u32, Error> {
+    if imm > CSR_IMM_MAX {
+        Err(Error::InvalidImmediate)
+    } else {
+        Ok(imm as u32)
+    }
+}
+
+/// Encodes a unary instruction whose operation is selected by the rs2 field.
+fn encode_unary(
+    opcode: u32,
+    funct3: u8,
+    funct7: u32,
+    rd: Register,
+    rs1: Register,
+    rs2: u32,
+) -> u32 {
+    (funct7 << FUNCT7_SHIFT)
+        | (rs2 << RS2_SHIFT)
+        | (encode_register(rs1) << RS1_SHIFT)
+        | ((funct3 as u32) << FUNCT3_SHIFT)
+        | (encode_register(rd) << RD_SHIFT)
+        | opcode
+}
+
+fn encode_s(funct3: u8, rs1: Register, rs2: Register, imm: i16) -> Result<u32, Error> {
+    let imm = encode_immediate(imm as i32, 12, 1)?;
+    Ok(((imm << IMM_S_HIGH_SHIFT) & IMM_S_HIGH_MASK)
+        | (encode_register(rs2) << RS2_SHIFT)
+        | (encode_register(rs1) << RS1_SHIFT)
+        | ((funct3 as u32) << FUNCT3_SHIFT)
+        | ((imm << IMM_S_LOW_SHIFT) & IMM_S_LOW_MASK)
+        | STORE_OPCODE)
+}
+
+fn encode_b(funct3: u8, rs1: Register, rs2: Register, imm: i16) -> Result<u32, Error> {
+    let imm = encode_immediate(imm as i32, 13, 2)?;
+    Ok(((imm << IMM_B_12_SHIFT) & IMM_B_12_MASK)
+        | ((imm << IMM_B_10_5_SHIFT) & IMM_B_10_5_MASK)
+        | (encode_register(rs2) << RS2_SHIFT)
+        | (encode_register(rs1) << RS1_SHIFT)
+        | ((funct3 as u32) << FUNCT3_SHIFT)
+        | ((imm << IMM_B_4_1_SHIFT) & IMM_B_4_1_MASK)
+        | ((imm >> IMM_B_11_SHIFT) & IMM_B_11_MASK)
+        | BRANCH_OPCODE)
+}
+
+fn encode_u(opcode: u32, rd: Register, imm: i32) -> Result<u32, Error> {
+    if imm as u32 & !IMM_U_MASK != 0 {
+        return Err(Error::InvalidImmediate);
+    }
+    Ok(imm as u32 | (encode_register(rd) << RD_SHIFT) | opcode)
+}
+
+fn encode_j(rd: Register, imm: i32) -> Result<u32, Error> {
+    let imm = encode_immediate(imm, 21, 2)?;
+    Ok(((imm << IMM_J_20_SHIFT) & IMM_J_20_MASK)
+        | ((imm << IMM_J_10_1_SHIFT) & IMM_J_10_1_MASK)
+        | ((imm << IMM_J_11_SHIFT) & IMM_J_11_MASK)
+        | (imm & IMM_J_19_12_MASK)
+        | (encode_register(rd) << RD_SHIFT)
+        | JAL_OPCODE)
+}
+
+impl RiscVInstruction {
+    /// Returns a value that formats this instruction with registers named in `syntax`.
+    ///
+    /// The `Display` implementation is equivalent to `display(Syntax::Numeric)`;
+    /// `display(Syntax::Abi)` prints `addi sp, sp, -16` in the style of `objdump`.
+    pub fn display(&self, syntax: Syntax) -> InstructionDisplay<'_> {
+        InstructionDisplay {
+            instruction: self,
+            syntax,
+        }
+    }
+
+    /// Decode a 32-bit instruction word into a RiscVInstruction
+    ///
+    /// Only the base instruction set is decoded. This is equivalent to
+    /// `decode_with(word, Extensions::NONE)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `word` - The 32-bit instruction word to decode
+    ///
+    /// # Errors
+    ///
+    /// Returns a `DecodeError` describing why the word is not a supported instruction.
+    pub fn decode(word: u32) -> Result<RiscVInstruction, DecodeError> {
+        RiscVInstruction::decode_with(word, Extensions::NONE)
+    }
+
+    /// Decode a 32-bit instruction word for the given profile, accepting the given
+    /// optional extensions
+    ///
+    /// [`RiscVInstruction::decode_with`] decodes for `Profile::Rv32i`, where every
+    /// register is available. `Profile::Rv64i` additionally decodes the RV64I and
+    /// RV64M instructions and six-bit shift amounts; only the Zifencei extension is
+    /// available on top of it.
+    ///
+    /// # Arguments
+    ///
+    /// * `word` - The 32-bit instruction word to decode
+    /// * `profile` - The base integer instruction set whose registers may be named
+    /// * `extensions` - The optional extensions to accept on top of the base instruction set
+    ///
+    /// # Errors
+    ///
+    /// Returns a `DecodeError` describing why the word is not a supported instruction.
+    /// An instruction naming a register outside of `profile` is rejected with
+    /// `DecodeErrorKind::UnsupportedRegister`. With `Profile::Rv64i`, an enabled
+    /// extension other than Zifencei is rejected with `DecodeErrorKind::Unimplemented`.
+    pub fn decode_for(
+        word: u32,
+        profile: Profile,
+        extensions: Extensions,
+    ) -> Result<RiscVInstruction, DecodeError> {
+        let instruction = match profile.xlen() {
+            64 => decode_rv64(word, extensions)?,
+            _ => RiscVInstruction::decode_with(word, extensions)?,
+        };
+        require_profile(word, instruction, profile)
+    }
+
+    /// Decode a 32-bit instruction word for the given profile, accepting the given
+    /// optional extensions and registered custom instructions
+    ///
+    /// Words in the custom-0 and custom-1 opcode spaces decode to
+    /// `RiscVInstruction::Custom` if an instruction with their opcode, funct3 and
+    /// funct7 fields is registered in `custom`; every other word is decoded by
+    /// [`RiscVInstruction::decode_for`].
+    ///
+    /// # Arguments
+    ///
+    /// * `word` - The 32-bit instruction word to decode
+    /// * `profile` - The base integer instruction set whose registers may be named
+    /// * `extensions` - The optional extensions to accept on top of the base instruction set
+    /// * `custom` - The custom instructions to accept
+    ///
+    /// # Errors
+    ///
+    /// Returns a `DecodeError` describing why the word is not a supported instruction.
+    /// A custom instruction that is not registered is rejected with
+    /// `DecodeErrorKind::UnregisteredCustom`.
+    pub fn decode_custom(
+        word: u32,
+        profile: Profile,
+        extensions: Extensions,
+        custom: &CustomInstructions,
+    ) -> Result<RiscVInstruction, DecodeError> {
+        let Some(opcode) = CustomOpcode::new(word & OPCODE_MASK) else {
+            return RiscVInstruction::decode_for(word, profile, extensions);
+        };
+
+        let funct3 = ((word & FUNCT3_MASK) >> FUNCT3_SHIFT) as u8;
+        let funct7 = ((word & FUNCT7_MASK) >> FUNCT7_SHIFT) as u8;
+        let rd = Register::from_field((word & RD_MASK) >> RD_SHIFT);
+        let rs1 = Register::from_field((word & RS1_MASK) >> RS1_SHIFT);
+        let rs2 = Register::from_field((word & RS2_MASK) >> RS2_SHIFT);
+
+        let operands = match custom.lookup(opcode, funct3, funct7) {
+            Some(instruction) => match instruction.form {
+                CustomForm::R { funct7 } => CustomOperands::R {
+                    funct7,
+                    rd,
+                    rs1,
+                    rs2,
+                },
+                CustomForm::I => CustomOperands::I {
+                    rd,
+                    rs1,
+                    imm: ((word & IMM_I_MASK) as i32 >> IMM_I_SHIFT) as i16,
+                },
+            },
+            None => return Err(unknown_opcode_error(word, word & OPCODE_MASK)),
+        };
+
+        let instruction = RiscVInstruction::Custom {
+            opcode,
+            funct3,
+            operands,
+        };
+        require_profile(word, instruction, profile)
+    }
+
+    /// Decode a 32-bit instruction word, accepting the given optional extensions
+    ///
+    /// # Arguments
+    ///
+    /// * `word` - The 32-bit instruction word to decode
+    /// * `extensions` - The optional extensions to accept on top of the base instruction set
+    ///
+    /// # Errors
+    ///
+    /// Returns a `DecodeError` describing why the word is not a supported instruction.
+    /// An instruction of an optional extension that is not in `extensions` is rejected
+    /// with `DecodeErrorKind::UnsupportedExtension`.
+    pub fn decode_with(word: u32, extensions: Extensions) -> Result<RiscVInstruction, DecodeError> {
+        let opcode = word & OPCODE_MASK;
+
+        match opcode {
+            REG_OPCODE => {
+                let funct3 = (((word & FUNCT3_MASK) >> FUNCT3_SHIFT) & 0x7) as u8;
+                let funct7 = (word & FUNCT7_MASK) >> FUNCT7_SHIFT;
+                let rd = Register::from_field((word & RD_MASK) >> RD_SHIFT);
+                let rs1 = Register::from_field((word & RS1_MASK) >> RS1_SHIFT);
+                let rs2 = Register::from_field((word & RS2_MASK) >> RS2_SHIFT);
+
+                if let Some(instruction) = decode_optional_register(funct3, funct7, rd, rs1, rs2) {
+                    return require_extension(word, instruction, extensions);
+                }
+
+                if funct7 == MULDIV_FUNCT7 {
+                    match funct3 {
+                        MUL_FUNCT3 => Ok(RiscVInstruction::Mul { rd, rs1, rs2 }),
+                        MULH_FUNCT3 => Ok(RiscVInstruction::Mulh { rd, rs1, rs2 }),
+                        MULHSU_FUNCT3 => Ok(RiscVInstruction::Mulhsu { rd, rs1, rs2 }),
+                        MULHU_FUNCT3 => Ok(RiscVInstruction::Mulhu { rd, rs1, rs2 }),
+                        DIV_FUNCT3 => Ok(RiscVInstruction::Div { rd, rs1, rs2 }),
+                        DIVU_FUNCT3 => Ok(RiscVInstruction::Divu { rd, rs1, rs2 }),
+                        REM_FUNCT3 => Ok(RiscVInstruction::Rem { rd, rs1, rs2 }),
+                        REMU_FUNCT3 => Ok(RiscVInstruction::Remu { rd, rs1, rs2 }),
+                        _ => unreachable!("All 3-bit funct3 values are handled above"),
+                    }
+                } else {
+                    match funct3 {
+                        ADD_FUNCT3 => {
+                            if funct7 == ADD_FUNCT7 {
+                                Ok(RiscVInstruction::Add { rd, rs1, rs2 })
+                            } else if funct7 == SUB_FUNCT7 {
+                                Ok(RiscVInstruction::Sub { rd, rs1, rs2 })
+                            } else {
+                                Err(DecodeError::new(word, DecodeErrorKind::ReservedFunct7))
+                            }
+                        }
+                        XOR_FUNCT3 => {
+                            if funct7 == XOR_FUNCT7 {
+                                Ok(RiscVInstruction::Xor { rd, rs1, rs2 })
+                            } else {
+                                Err(DecodeError::new(word, DecodeErrorKind::ReservedFunct7))
+                            }
+                        }
+                        OR_FUNCT3 => {
+                            if funct7 == OR_FUNCT7 {
+                                Ok(RiscVInstruction::Or { rd, rs1, rs2 })
+                            } else {
+                                Err(DecodeError::new(word, DecodeErrorKind::ReservedFunct7))
+                            }
+                        }
+                        AND_FUNCT3 => {
+                            if funct7 == AND_FUNCT7 {
+                                Ok(RiscVInstruction::And { rd, rs1, rs2 })
+                            } else {
+                                Err(DecodeError::new(word, DecodeErrorKind::ReservedFunct7))
+                            }
+                        }
+                        SLL_FUNCT3 => {
+                            if funct7 == SLL_FUNCT7 {
+                                Ok(RiscVInstruction::Sll { rd, rs1, rs2 })
+                            } else {
+                                Err(DecodeError::new(word, DecodeErrorKind::ReservedFunct7))
+                            }
+                        }
+                        SRL_FUNCT3 => {
+                            if funct7 == SRL_FUNCT7 {
+                                Ok(RiscVInstruction::Srl { rd, rs1, rs2 })
+                            } else if funct7 == SRA_FUNCT7 {
+                                Ok(RiscVInstruction::Sra { rd, rs1, rs2 })
+                            } else {
+                                Err(DecodeError::new(word, DecodeErrorKind::ReservedFunct7))
+                            }
+                        }
+                        SLT_FUNCT3 => {
+                            if funct7 == SLT_FUNCT7 {
+                                Ok(RiscVInstruction::Slt { rd, rs1, rs2 })
+                            } else {
+                                Err(DecodeError::new(word, DecodeErrorKind::ReservedFunct7))
+                            }
+                        }
+                        SLTU_FUNCT3 => {
+                            if funct7 == SLTU_FUNCT7 {
+                                Ok(RiscVInstruction::Sltu { rd, rs1, rs2 })
+                            } else {
+                                Err(DecodeError::new(word, DecodeErrorKind::ReservedFunct7))
+                            }
+                        }
+                        _ => unreachable!("All 3-bit funct3 values are handled above"),
+                    }
+                }
+            }
+            IMM_OPCODE => {
+                let funct3 = (((word & FUNCT3_MASK) >> FUNCT3_SHIFT) & 0x7) as u8;
+                let rd = Register::from_field((word & RD_MASK) >> RD_SHIFT);
+                let rs1 = Register::from_field((word & RS1_MASK) >> RS1_SHIFT);
+                let imm = ((word & IMM_I_MASK) as i32 >> IMM_I_SHIFT) as i16;
+                let funct7 = (word & FUNCT7_MASK) >> FUNCT7_SHIFT;
+                let shamt = (word & RS2_MASK) >> RS2_SHIFT;
+
+                if let Some(instruction) = decode_optional_immediate(funct3, funct7, rd, rs1, shamt)
+                {
+                    return require_extension(word, instruction, extensions);
+                }
+
+                match funct3 {
+                    ADDI_FUNCT3 => Ok(RiscVInstruction::Addi { rd, rs1, imm }),
+                    SLTI_FUNCT3 => Ok(RiscVInstruction::Slti { rd, rs1, imm }),
+                    SLTIU_FUNCT3 => Ok(RiscVInstruction::Sltiu { rd, rs1, imm }),
+                    SLLI_FUNCT3 => {
+                        if funct7 == SLLI_FUNCT7 {
+                            let shift_imm = imm & 0x1f;
+                            Ok(RiscVInstruction::Slli {
+                                rd,
+                                rs1,
+                                imm: shift_imm,
+                            })
+                        } else {
+                            Err(DecodeError::new(word, DecodeErrorKind::ReservedFunct7))
+                        }
+                    }
+                    SRLI_FUNCT3 => {
+                        if funct7 == SRAI_FUNCT7 {
+                            let shift_imm = imm & 0x1f;
+                            Ok(RiscVInstruction::Srai {
+                                rd,
+                                rs1,
+                                imm: shift_imm,
+                            })
+                        } else if funct7 == SRLI_FUNCT7 {
+                            let shift_imm = imm & 0x1f;
+                            Ok(RiscVInstruction::Srli {
+                                rd,
+                                rs1,
+                                imm: shift_imm,
+                            })
+                        } else {
+                            Err(DecodeError::new(word, DecodeErrorKind::ReservedFunct7))
+                        }
+                    }
+                    XORI_FUNCT3 => Ok(RiscVInstruction::Xori { rd, rs1, imm }),
+                    ORI_FUNCT3 => Ok(RiscVInstruction::Ori { rd, rs1, imm }),
+                    ANDI_FUNCT3 => Ok(RiscVInstruction::Andi { rd, rs1, imm }),
+                    _ => unreachable!("All 3-bit funct3 values are handled above"),
+                }
+            }
+            LOAD_OPCODE => {
+                let funct3 = (((word & FUNCT3_MASK) >> FUNCT3_SHIFT) & 0x7) as u8;
+                let rd = Register::from_field((word & RD_MASK) >> RD_SHIFT);
+                let rs1 = Register::from_field((word & RS1_MASK) >> RS1_SHIFT);
+                let imm = ((word & IMM_I_MASK) as i32 >> IMM_I_SHIFT) as i16;
+
+                match funct3 {
+                    LB_FUNCT3 => Ok(RiscVInstruction::Lb { rd, rs1, imm }),
+                    LH_FUNCT3 => Ok(RiscVInstruction::Lh { rd, rs1, imm }),
+                    LW_FUNCT3 => Ok(RiscVInstruction::Lw { rd, rs1, imm }),
+                    LBU_FUNCT3 => Ok(RiscVInstruction::Lbu { rd, rs1, imm }),
+                    LHU_FUNCT3 => Ok(RiscVInstruction::Lhu { rd, rs1, imm }),
+                    _ => Err(DecodeError::new(word, DecodeErrorKind::ReservedFunct3)),
+                }
+            }
+            STORE_OPCODE => {
+                let funct3 = (((word & FUNCT3_MASK) >> FUNCT3_SHIFT) & 0x7) as u8;
+                let rs1 = Register::from_field((word & RS1_MASK) >> RS1_SHIFT);
+                let rs2 = Register::from_field((word & RS2_MASK) >> RS2_SHIFT);
+                // The S-type immediate is split: imm[11:5] lives in bits 31:25 and
+                // imm[4:0] in bits 11:7. The arithmetic shift sign-extends imm[11].
+                let imm = (((word & IMM_S_HIGH_MASK) as i32 >> IMM_S_HIGH_SHIFT)
+                    | ((word & IMM_S_LOW_MASK) >> IMM_S_LOW_SHIFT) as i32)
+                    as i16;
+
+                match funct3 {
+                    SB_FUNCT3 => Ok(RiscVInstruction::Sb { rs1, rs2, imm }),
+                    SH_FUNCT3 => Ok(RiscVInstruction::Sh { rs1, rs2, imm }),
+                    SW_FUNCT3 => Ok(RiscVInstruction::Sw { rs1, rs2, imm }),
+                    _ => Err(DecodeError::new(word, DecodeErrorKind::ReservedFunct3)),
+                }
+            }
+            BRANCH_OPCODE => {
+                let funct3 = (((word & FUNCT3_MASK) >> FUNCT3_SHIFT) & 0x7) as u8;
+                let rs1 = Register::from_field((word & RS1_MASK) >> RS1_SHIFT);
+                let rs2 = Register::from_field((word & RS2_MASK) >> RS2_SHIFT);
+                // The B-type immediate is scrambled across the word and always has
+                // bit 0 clear. The arithmetic shift of bit 31 sign-extends imm[12].
+                let imm = (((word & IMM_B_12_MASK) as i32 >> IMM_B_12_SHIFT)
+                    | ((word & IMM_B_11_MASK) << IMM_B_11_SHIFT) as i32
+                    | ((word & IMM_B_10_5_MASK) >> IMM_B_10_5_SHIFT) as i32
+                    | ((word & IMM_B_4_1_MASK) >> IMM_B_4_1_SHIFT) as i32)
+                    as i16;
+
+                match funct3 {
+                    BEQ_FUNCT3 => Ok(RiscVInstruction::Beq { rs1, rs2, imm }),
+                    BNE_FUNCT3 => Ok(RiscVInstruction::Bne { rs1, rs2, imm }),
+                    BLT_FUNCT3 => Ok(RiscVInstruction::Blt { rs1, rs2, imm }),
+                    BGE_FUNCT3 => Ok(RiscVInstruction::Bge { rs1, rs2, imm }),
+                    BLTU_FUNCT3 => Ok(RiscVInstruction::Bltu { rs1, rs2, imm }),
+                    BGEU_FUNCT3 => Ok(RiscVInstruction::Bgeu { rs1, rs2, imm }),
+                    _ => Err(DecodeError::new(word, DecodeErrorKind::ReservedFunct3)),
+                }
+            }
+            LUI_OPCODE => {
+                let rd = Register::from_field((word & RD_MASK) >> RD_SHIFT);
+                let imm = (word & IMM_U_MASK) as i32;
+
+                Ok(RiscVInstruction::Lui { rd, imm })
+            }
+            AUIPC_OPCODE => {
+                let rd = Register::from_field((word & RD_MASK) >> RD_SHIFT);
+                let imm = (word & IMM_U_MASK) as i32;
+
+                Ok(RiscVInstruction::Auipc { rd, imm })
+            }
+            JAL_OPCODE => {
+                let rd = Register::from_field((word & RD_MASK) >> RD_SHIFT);
+                // The J-type immediate is scrambled across the word and always has
+                // bit 0 clear. The arithmetic shift of bit 31 sign-extends imm[20].
+                let imm = ((word & IMM_J_20_MASK) as i32 >> IMM_J_20_SHIFT)
+                    | (word & IMM_J_19_12_MASK) as i32
+                    | ((word & IMM_J_11_MASK) >> IMM_J_11_SHIFT) as i32
+                    | ((word & IMM_J_10_1_MASK) >> IMM_J_10_1_SHIFT) as i32;
+
+                Ok(RiscVInstruction::Jal { rd, imm })
+            }
+            JALR_OPCODE => {
+                let funct3 = (word & FUNCT3_MASK) >> FUNCT3_SHIFT;
+                if funct3 == JALR_FUNCT3 {
+                    let rd = Register::from_field((word & RD_MASK) >> RD_SHIFT);
+                    let rs1 = Register::from_field((word & RS1_MASK) >> RS1_SHIFT);
+                    let imm = ((word & IMM_I_MASK) as i32 >> IMM_I_SHIFT) as i16;
+
+                    Ok(RiscVInstruction::Jalr { rd, rs1, imm })
+                } else {
+                    Err(DecodeError::new(word, DecodeErrorKind::ReservedFunct3))
+                }
+            }
+            MISC_MEM_OPCODE => decode_misc_mem(word, extensions),
+            LOAD_FP_OPCODE | STORE_FP_OPCODE | FMADD_OPCODE | FMSUB_OPCODE | FNMSUB_OPCODE
+            | FNMADD_OPCODE | OP_FP_OPCODE => decode_floating_point(word, extensions),
+            AMO_OPCODE => decode_atomic(word, extensions),
+            SYSTEM_OPCODE => {
+                let funct3 = (word & FUNCT3_MASK) >> FUNCT3_SHIFT;
+                if funct3 == SYSTEM_FUNCT3 {
+                    let rd = Register::from_field((word & RD_MASK) >> RD_SHIFT);
+                    let rs1 = Register::from_field((word & RS1_MASK) >> RS1_SHIFT);
+                    let imm = (word & IMM_I_MASK) >> IMM_I_SHIFT;
+
+                    // ECALL and EBREAK require rd=0 and rs1=0
+                    if rd == Register::ZERO && rs1 == Register::ZERO {
+                        match imm {
+                            ECALL_IMM => Ok(RiscVInstruction::Ecall),
+                            EBREAK_IMM => Ok(RiscVInstruction::Ebreak),
+                            _ => Err(DecodeError::new(word, DecodeErrorKind::ReservedFunct12)),
+                        }
+                    } else {
+                        Err(DecodeError::new(word, DecodeErrorKind::InvalidOperands))
+                    }
+                } else {
+                    require_extension(word, decode_csr(word, funct3)?, extensions)
+                }
+            }
+            _ => Err(unknown_opcode_error(word, opcode)),
+        }
+    }
+
+    /// Encode this instruction into a 32-bit instruction word
+    ///
+    /// This is the exact inverse of [`RiscVInstruction::decode`]: for every word that
+    /// decodes to a supported instruction, encoding the result yields the same word.
+    /// The same holds for `decode_for` with `Profile::Rv64i`, whose shift amounts of
+    /// 32 and above are encoded as well.
+    ///
+    /// # Errors
+    ///
+    /// - `Error::InvalidImmediate` if an immediate does not fit its encoding, or is not
+    ///   a multiple of 2 for branches and jumps, or has low bits set for `lui`/`auipc`.
+    pub fn encode(&self) -> Result<u32, Error> {
+        match self {
+            RiscVInstruction::Add { rd, rs1, rs2 } => {
+                encode_r(REG_OPCODE, ADD_FUNCT3, ADD_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Sub { rd, rs1, rs2 } => {
+                encode_r(REG_OPCODE, ADD_FUNCT3, SUB_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Xor { rd, rs1, rs2 } => {
+                encode_r(REG_OPCODE, XOR_FUNCT3, XOR_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Or { rd, rs1, rs2 } => {
+                encode_r(REG_OPCODE, OR_FUNCT3, OR_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::And { rd, rs1, rs2 } => {
+                encode_r(REG_OPCODE, AND_FUNCT3, AND_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Sll { rd, rs1, rs2 } => {
+                encode_r(REG_OPCODE, SLL_FUNCT3, SLL_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Srl { rd, rs1, rs2 } => {
+                encode_r(REG_OPCODE, SRL_FUNCT3, SRL_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Sra { rd, rs1, rs2 } => {
+                encode_r(REG_OPCODE, SRL_FUNCT3, SRA_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Slt { rd, rs1, rs2 } => {
+                encode_r(REG_OPCODE, SLT_FUNCT3, SLT_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Sltu { rd, rs1, rs2 } => {
+                encode_r(REG_OPCODE, SLTU_FUNCT3, SLTU_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Mul { rd, rs1, rs2 } => {
+                encode_r(REG_OPCODE, MUL_FUNCT3, MULDIV_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Mulh { rd, rs1, rs2 } => {
+                encode_r(REG_OPCODE, MULH_FUNCT3, MULDIV_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Mulhsu { rd, rs1, rs2 } => {
+                encode_r(REG_OPCODE, MULHSU_FUNCT3, MULDIV_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Mulhu { rd, rs1, rs2 } => {
+                encode_r(REG_OPCODE, MULHU_FUNCT3, MULDIV_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Div { rd, rs1, rs2 } => {
+                encode_r(REG_OPCODE, DIV_FUNCT3, MULDIV_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Divu { rd, rs1, rs2 } => {
+                encode_r(REG_OPCODE, DIVU_FUNCT3, MULDIV_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Rem { rd, rs1, rs2 } => {
+                encode_r(REG_OPCODE, REM_FUNCT3, MULDIV_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Remu { rd, rs1, rs2 } => {
+                encode_r(REG_OPCODE, REMU_FUNCT3, MULDIV_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Addi { rd, rs1, imm } => {
+                encode_i(IMM_OPCODE, ADDI_FUNCT3, *rd, *rs1, *imm)
+            }
+            RiscVInstruction::Xori { rd, rs1, imm } => {
+                encode_i(IMM_OPCODE, XORI_FUNCT3, *rd, *rs1, *imm)
+            }
+            RiscVInstruction::Ori { rd, rs1, imm } => {
+                encode_i(IMM_OPCODE, ORI_FUNCT3, *rd, *rs1, *imm)
+            }
+            RiscVInstruction::Andi { rd, rs1, imm } => {
+                encode_i(IMM_OPCODE, ANDI_FUNCT3, *rd, *rs1, *imm)
+            }
+            RiscVInstruction::Slti { rd, rs1, imm } => {
+                encode_i(IMM_OPCODE, SLTI_FUNCT3, *rd, *rs1, *imm)
+            }
+            RiscVInstruction::Sltiu { rd, rs1, imm } => {
+                encode_i(IMM_OPCODE, SLTIU_FUNCT3, *rd, *rs1, *imm)
+            }
+            RiscVInstruction::Slli { rd, rs1, imm } => {
+                encode_wide_shift(SLLI_FUNCT3, SLLI_FUNCT6, *rd, *rs1, *imm)
+            }
+            RiscVInstruction::Srli { rd, rs1, imm } => {
+                encode_wide_shift(SRLI_FUNCT3, SRLI_FUNCT6, *rd, *rs1, *imm)
+            }
+            RiscVInstruction::Srai { rd, rs1, imm } => {
+                encode_wide_shift(SRLI_FUNCT3, SRAI_FUNCT6, *rd, *rs1, *imm)
+            }
+            RiscVInstruction::Lb { rd, rs1, imm } => {
+                encode_i(LOAD_OPCODE, LB_FUNCT3, *rd, *rs1, *imm)
+            }
+            RiscVInstruction::Lh { rd, rs1, imm } => {
+                encode_i(LOAD_OPCODE, LH_FUNCT3, *rd, *rs1, *imm)
+            }
+            RiscVInstruction::Lw { rd, rs1, imm } => {
+                encode_i(LOAD_OPCODE, LW_FUNCT3, *rd, *rs1, *imm)
+            }
+            RiscVInstruction::Lbu { rd, rs1, imm } => {
+                encode_i(LOAD_OPCODE, LBU_FUNCT3, *rd, *rs1, *imm)
+            }
+            RiscVInstruction::Lhu { rd, rs1, imm } => {
+                encode_i(LOAD_OPCODE, LHU_FUNCT3, *rd, *rs1, *imm)
+            }
+            RiscVInstruction::Sb { rs1, rs2, imm } => encode_s(SB_FUNCT3, *rs1, *rs2, *imm),
+            RiscVInstruction::Sh { rs1, rs2, imm } => encode_s(SH_FUNCT3, *rs1, *rs2, *imm),
+            RiscVInstruction::Sw { rs1, rs2, imm } => encode_s(SW_FUNCT3, *rs1, *rs2, *imm),
+            RiscVInstruction::Beq { rs1, rs2, imm } => encode_b(BEQ_FUNCT3, *rs1, *rs2, *imm),
+            RiscVInstruction::Bne { rs1, rs2, imm } => encode_b(BNE_FUNCT3, *rs1, *rs2, *imm),
+            RiscVInstruction::Blt { rs1, rs2, imm } => encode_b(BLT_FUNCT3, *rs1, *rs2, *imm),
+            RiscVInstruction::Bge { rs1, rs2, imm } => encode_b(BGE_FUNCT3, *rs1, *rs2, *imm),
+            RiscVInstruction::Bltu { rs1, rs2, imm } => encode_b(BLTU_FUNCT3, *rs1, *rs2, *imm),
+            RiscVInstruction::Bgeu { rs1, rs2, imm } => encode_b(BGEU_FUNCT3, *rs1, *rs2, *imm),
+            RiscVInstruction::Lui { rd, imm } => encode_u(LUI_OPCODE, *rd, *imm),
+            RiscVInstruction::Auipc { rd, imm } => encode_u(AUIPC_OPCODE, *rd, *imm),
+            RiscVInstruction::Jal { rd, imm } => encode_j(*rd, *imm),
+            RiscVInstruction::Jalr { rd, rs1, imm } => {
+                encode_i(JALR_OPCODE, JALR_FUNCT3 as u8, *rd, *rs1, *imm)
+            }
+            RiscVInstruction::Ecall => Ok((ECALL_IMM << IMM_I_SHIFT) | SYSTEM_OPCODE),
+            RiscVInstruction::Ebreak => Ok((EBREAK_IMM << IMM_I_SHIFT) | SYSTEM_OPCODE),
+            RiscVInstruction::Fence { pred, succ } => {
+                if *pred as u32 > FENCE_SET_MASK || *succ as u32 > FENCE_SET_MASK {
+                    return Err(Error::InvalidImmediate);
+                }
+                Ok(((*pred as u32) << FENCE_PRED_SHIFT)
+                    | ((*succ as u32) << FENCE_SUCC_SHIFT)
+                    | (FENCE_FUNCT3 << FUNCT3_SHIFT)
+                    | MISC_MEM_OPCODE)
+            }
+            RiscVInstruction::FenceTso => Ok((FENCE_TSO_FM << FENCE_FM_SHIFT)
+                | (FENCE_TSO_SET << FENCE_PRED_SHIFT)
+                | (FENCE_TSO_SET << FENCE_SUCC_SHIFT)
+                | (FENCE_FUNCT3 << FUNCT3_SHIFT)
+                | MISC_MEM_OPCODE),
+            RiscVInstruction::FenceI => Ok((FENCE_I_FUNCT3 << FUNCT3_SHIFT) | MISC_MEM_OPCODE),
+            RiscVInstruction::Csrrw { rd, rs1, csr } => {
+                encode_csr(CSRRW_FUNCT3, *rd, encode_register(*rs1), *csr)
+            }
+            RiscVInstruction::Csrrs { rd, rs1, csr } => {
+                encode_csr(CSRRS_FUNCT3, *rd, encode_register(*rs1), *csr)
+            }
+            RiscVInstruction::Csrrc { rd, rs1, csr } => {
+                encode_csr(CSRRC_FUNCT3, *rd, encode_register(*rs1), *csr)
+            }
+            RiscVInstruction::Csrrwi { rd, imm, csr } => {
+                encode_csr(CSRRWI_FUNCT3, *rd, encode_csr_immediate(*imm)?, *csr)
+            }
+            RiscVInstruction::Csrrsi { rd, imm, csr } => {
+                encode_csr(CSRRSI_FUNCT3, *rd, encode_csr_immediate(*imm)?, *csr)
+            }
+            RiscVInstruction::Csrrci { rd, imm, csr } => {
+                encode_csr(CSRRCI_FUNCT3, *rd, encode_csr_immediate(*imm)?, *csr)
+            }
+            RiscVInstruction::Sh1add { rd, rs1, rs2 } => {
+                encode_r(REG_OPCODE, SH1ADD_FUNCT3, SHADD_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Sh2add { rd, rs1, rs2 } => {
+                encode_r(REG_OPCODE, SH2ADD_FUNCT3, SHADD_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Sh3add { rd, rs1, rs2 } => {
+                encode_r(REG_OPCODE, SH3ADD_FUNCT3, SHADD_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Andn { rd, rs1, rs2 } => {
+                encode_r(REG_OPCODE, ANDN_FUNCT3, INVERTED_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Orn { rd, rs1, rs2 } => {
+                encode_r(REG_OPCODE, ORN_FUNCT3, INVERTED_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Xnor { rd, rs1, rs2 } => {
+                encode_r(REG_OPCODE, XNOR_FUNCT3, INVERTED_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Clz { rd, rs1 } => Ok(encode_unary(
+                IMM_OPCODE,
+                SLLI_FUNCT3,
+                UNARY_FUNCT7,
+                *rd,
+                *rs1,
+                CLZ_RS2,
+            )),
+            RiscVInstruction::Ctz { rd, rs1 } => Ok(encode_unary(
+                IMM_OPCODE,
+                SLLI_FUNCT3,
+                UNARY_FUNCT7,
+                *rd,
+                *rs1,
+                CTZ_RS2,
+            )),
+            RiscVInstruction::Cpop { rd, rs1 } => Ok(encode_unary(
+                IMM_OPCODE,
+                SLLI_FUNCT3,
+                UNARY_FUNCT7,
+                *rd,
+                *rs1,
+                CPOP_RS2,
+            )),
+            RiscVInstruction::Max { rd, rs1, rs2 } => {
+                encode_r(REG_OPCODE, MAX_FUNCT3, MINMAX_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Maxu { rd, rs1, rs2 } => {
+                encode_r(REG_OPCODE, MAXU_FUNCT3, MINMAX_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Min { rd, rs1, rs2 } => {
+                encode_r(REG_OPCODE, MIN_FUNCT3, MINMAX_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Minu { rd, rs1, rs2 } => {
+                encode_r(REG_OPCODE, MINU_FUNCT3, MINMAX_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::SextB { rd, rs1 } => Ok(encode_unary(
+                IMM_OPCODE,
+                SLLI_FUNCT3,
+                UNARY_FUNCT7,
+                *rd,
+                *rs1,
+                SEXTB_RS2,
+            )),
+            RiscVInstruction::SextH { rd, rs1 } => Ok(encode_unary(
+                IMM_OPCODE,
+                SLLI_FUNCT3,
+                UNARY_FUNCT7,
+                *rd,
+                *rs1,
+                SEXTH_RS2,
+            )),
+            RiscVInstruction::ZextH { rd, rs1 } => Ok(encode_unary(
+                REG_OPCODE,
+                ZEXTH_FUNCT3,
+                ZEXTH_FUNCT7,
+                *rd,
+                *rs1,
+                0,
+            )),
+            RiscVInstruction::Rol { rd, rs1, rs2 } => {
+                encode_r(REG_OPCODE, ROL_FUNCT3, ROTATE_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Ror { rd, rs1, rs2 } => {
+                encode_r(REG_OPCODE, ROR_FUNCT3, ROTATE_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Rori { rd, rs1, imm } => {
+                encode_shift(IMM_OPCODE, SRLI_FUNCT3, ROTATE_FUNCT7, *rd, *rs1, *imm)
+            }
+            RiscVInstruction::OrcB { rd, rs1 } => Ok(encode_unary(
+                IMM_OPCODE,
+                SRLI_FUNCT3,
+                ORCB_FUNCT7,
+                *rd,
+                *rs1,
+                ORCB_RS2,
+            )),
+            RiscVInstruction::Rev8 { rd, rs1 } => Ok(encode_unary(
+                IMM_OPCODE,
+                SRLI_FUNCT3,
+                REV8_FUNCT7,
+                *rd,
+                *rs1,
+                REV8_RS2,
+            )),
+            RiscVInstruction::Bclr { rd, rs1, rs2 } => {
+                encode_r(REG_OPCODE, SINGLE_BIT_FUNCT3, BCLR_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Bclri { rd, rs1, imm } => {
+                encode_shift(IMM_OPCODE, SINGLE_BIT_FUNCT3, BCLR_FUNCT7, *rd, *rs1, *imm)
+            }
+            RiscVInstruction::Bext { rd, rs1, rs2 } => {
+                encode_r(REG_OPCODE, BEXT_FUNCT3, BCLR_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Bexti { rd, rs1, imm } => {
+                encode_shift(IMM_OPCODE, BEXT_FUNCT3, BCLR_FUNCT7, *rd, *rs1, *imm)
+            }
+            RiscVInstruction::Binv { rd, rs1, rs2 } => {
+                encode_r(REG_OPCODE, SINGLE_BIT_FUNCT3, BINV_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Binvi { rd, rs1, imm } => {
+                encode_shift(IMM_OPCODE, SINGLE_BIT_FUNCT3, BINV_FUNCT7, *rd, *rs1, *imm)
+            }
+            RiscVInstruction::Bset { rd, rs1, rs2 } => {
+                encode_r(REG_OPCODE, SINGLE_BIT_FUNCT3, BSET_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Bseti { rd, rs1, imm } => {
+                encode_shift(IMM_OPCODE, SINGLE_BIT_FUNCT3, BSET_FUNCT7, *rd, *rs1, *imm)
+            }
+            RiscVInstruction::Sha256sig0 { rd, rs1 } => Ok(encode_unary(
+                IMM_OPCODE,
+                SLLI_FUNCT3,
+                SHA256_FUNCT7,
+                *rd,
+                *rs1,
+                SHA256SIG0_RS2,
+            )),
+            RiscVInstruction::Sha256sig1 { rd, rs1 } => Ok(encode_unary(
+                IMM_OPCODE,
+                SLLI_FUNCT3,
+                SHA256_FUNCT7,
+                *rd,
+                *rs1,
+                SHA256SIG1_RS2,
+            )),
+            RiscVInstruction::Sha256sum0 { rd, rs1 } => Ok(encode_unary(
+                IMM_OPCODE,
+                SLLI_FUNCT3,
+                SHA256_FUNCT7,
+                *rd,
+                *rs1,
+                SHA256SUM0_RS2,
+            )),
+            RiscVInstruction::Sha256sum1 { rd, rs1 } => Ok(encode_unary(
+                IMM_OPCODE,
+                SLLI_FUNCT3,
+                SHA256_FUNCT7,
+                *rd,
+                *rs1,
+                SHA256SUM1_RS2,
+            )),
+            RiscVInstruction::Sha512sum0r { rd, rs1, rs2 } => encode_r(
+                REG_OPCODE,
+                SHA512_FUNCT3,
+                SHA512SUM0R_FUNCT7,
+                *rd,
+                *rs1,
+                *rs2,
+            ),
+            RiscVInstruction::Sha512sum1r { rd, rs1, rs2 } => encode_r(
+                REG_OPCODE,
+                SHA512_FUNCT3,
+                SHA512SUM1R_FUNCT7,
+                *rd,
+                *rs1,
+                *rs2,
+            ),
+            RiscVInstruction::Sha512sig0l { rd, rs1, rs2 } => encode_r(
+                REG_OPCODE,
+                SHA512_FUNCT3,
+                SHA512SIG0L_FUNCT7,
+                *rd,
+                *rs1,
+                *rs2,
+            ),
+            RiscVInstruction::Sha512sig0h { rd, rs1, rs2 } => encode_r(
+                REG_OPCODE,
+                SHA512_FUNCT3,
+                SHA512SIG0H_FUNCT7,
+                *rd,
+                *rs1,
+                *rs2,
+            ),
+            RiscVInstruction::Sha512sig1l { rd, rs1, rs2 } => encode_r(
+                REG_OPCODE,
+                SHA512_FUNCT3,
+                SHA512SIG1L_FUNCT7,
+                *rd,
+                *rs1,
+                *rs2,
+            ),
+            RiscVInstruction::Sha512sig1h { rd, rs1, rs2 } => encode_r(
+                REG_OPCODE,
+                SHA512_FUNCT3,
+                SHA512SIG1H_FUNCT7,
+                *rd,
+                *rs1,
+                *rs2,
+            ),
+            RiscVInstruction::LrW { rd, rs1, aq, rl } => {
+                encode_atomic(LR_FUNCT5, *aq, *rl, *rd, *rs1, Register::ZERO)
+            }
+            RiscVInstruction::ScW {
+                rd,
+                rs1,
+                rs2,
+                aq,
+                rl,
+            } => encode_atomic(SC_FUNCT5, *aq, *rl, *rd, *rs1, *rs2),
+            RiscVInstruction::AmoswapW {
+                rd,
+                rs1,
+                rs2,
+                aq,
+                rl,
+            } => encode_atomic(AMOSWAP_FUNCT5, *aq, *rl, *rd, *rs1, *rs2),
+            RiscVInstruction::AmoaddW {
+                rd,
+                rs1,
+                rs2,
+                aq,
+                rl,
+            } => encode_atomic(AMOADD_FUNCT5, *aq, *rl, *rd, *rs1, *rs2),
+            RiscVInstruction::AmoxorW {
+                rd,
+                rs1,
+                rs2,
+                aq,
+                rl,
+            } => encode_atomic(AMOXOR_FUNCT5, *aq, *rl, *rd, *rs1, *rs2),
+            RiscVInstruction::AmoandW {
+                rd,
+                rs1,
+                rs2,
+                aq,
+                rl,
+            } => encode_atomic(AMOAND_FUNCT5, *aq, *rl, *rd, *rs1, *rs2),
+            RiscVInstruction::AmoorW {
+                rd,
+                rs1,
+                rs2,
+                aq,
+                rl,
+            } => encode_atomic(AMOOR_FUNCT5, *aq, *rl, *rd, *rs1, *rs2),
+            RiscVInstruction::AmominW {
+                rd,
+                rs1,
+                rs2,
+                aq,
+                rl,
+            } => encode_atomic(AMOMIN_FUNCT5, *aq, *rl, *rd, *rs1, *rs2),
+            RiscVInstruction::AmomaxW {
+                rd,
+                rs1,
+                rs2,
+                aq,
+                rl,
+            } => encode_atomic(AMOMAX_FUNCT5, *aq, *rl, *rd, *rs1, *rs2),
+            RiscVInstruction::AmominuW {
+                rd,
+                rs1,
+                rs2,
+                aq,
+                rl,
+            } => encode_atomic(AMOMINU_FUNCT5, *aq, *rl, *rd, *rs1, *rs2),
+            RiscVInstruction::AmomaxuW {
+                rd,
+                rs1,
+                rs2,
+                aq,
+                rl,
+            } => encode_atomic(AMOMAXU_FUNCT5, *aq, *rl, *rd, *rs1, *rs2),
+            RiscVInstruction::Flw { rd, rs1, imm } => encode_fp_load(FLW_FUNCT3, *rd, *rs1, *imm),
+            RiscVInstruction::Fsw { rs1, rs2, imm } => {
+                encode_fp_store(FSW_FUNCT3, *rs1, *rs2, *imm)
+            }
+            RiscVInstruction::Fld { rd, rs1, imm } => encode_fp_load(FLD_FUNCT3, *rd, *rs1, *imm),
+            RiscVInstruction::Fsd { rs1, rs2, imm } => {
+                encode_fp_store(FSD_FUNCT3, *rs1, *rs2, *imm)
+            }
+            RiscVInstruction::FmaddS {
+                rd,
+                rs1,
+                rs2,
+                rs3,
+                rm,
+            } => encode_r4(FMADD_OPCODE, FP_FMT_SINGLE, *rd, *rs1, *rs2, *rs3, *rm),
+            RiscVInstruction::FmsubS {
+                rd,
+                rs1,
+                rs2,
+                rs3,
+                rm,
+            } => encode_r4(FMSUB_OPCODE, FP_FMT_SINGLE, *rd, *rs1, *rs2, *rs3, *rm),
+            RiscVInstruction::FnmsubS {
+                rd,
+                rs1,
+                rs2,
+                rs3,
+                rm,
+            } => encode_r4(FNMSUB_OPCODE, FP_FMT_SINGLE, *rd, *rs1, *rs2, *rs3, *rm),
+            RiscVInstruction::FnmaddS {
+                rd,
+                rs1,
+                rs2,
+                rs3,
+                rm,
+            } => encode_r4(FNMADD_OPCODE, FP_FMT_SINGLE, *rd, *rs1, *rs2, *rs3, *rm),
+            RiscVInstruction::FaddS { rd, rs1, rs2, rm } => encode_op_fp(
+                FADD_FUNCT5,
+                FP_FMT_SINGLE,
+                rm.field(),
+                rd.number(),
+                rs1.number(),
+                rs2.number() as u32,
+            ),
+            RiscVInstruction::FsubS { rd, rs1, rs2, rm } => encode_op_fp(
+                FSUB_FUNCT5,
+                FP_FMT_SINGLE,
+                rm.field(),
+                rd.number(),
+                rs1.number(),
+                rs2.number() as u32,
+            ),
+            RiscVInstruction::FmulS { rd, rs1, rs2, rm } => encode_op_fp(
+                FMUL_FUNCT5,
+                FP_FMT_SINGLE,
+                rm.field(),
+                rd.number(),
+                rs1.number(),
+                rs2.number() as u32,
+            ),
+            RiscVInstruction::FdivS { rd, rs1, rs2, rm } => encode_op_fp(
+                FDIV_FUNCT5,
+                FP_FMT_SINGLE,
+                rm.field(),
+                rd.number(),
+                rs1.number(),
+                rs2.number() as u32,
+            ),
+            RiscVInstruction::FsqrtS { rd, rs1, rm } => encode_op_fp(
+                FSQRT_FUNCT5,
+                FP_FMT_SINGLE,
+                rm.field(),
+                rd.number(),
+                rs1.number(),
+                FP_UNARY_RS2,
+            ),
+            RiscVInstruction::FsgnjS { rd, rs1, rs2 } => encode_op_fp(
+                FSGNJ_FUNCT5,
+                FP_FMT_SINGLE,
+                FSGNJ_FUNCT3,
+                rd.number(),
+                rs1.number(),
+                rs2.number() as u32,
+            ),
+            RiscVInstruction::FsgnjnS { rd, rs1, rs2 } => encode_op_fp(
+                FSGNJ_FUNCT5,
+                FP_FMT_SINGLE,
+                FSGNJN_FUNCT3,
+                rd.number(),
+                rs1.number(),
+                rs2.number() as u32,
+            ),
+            RiscVInstruction::FsgnjxS { rd, rs1, rs2 } => encode_op_fp(
+                FSGNJ_FUNCT5,
+                FP_FMT_SINGLE,
+                FSGNJX_FUNCT3,
+                rd.number(),
+                rs1.number(),
+                rs2.number() as u32,
+            ),
+            RiscVInstruction::FminS { rd, rs1, rs2 } => encode_op_fp(
+                FMINMAX_FUNCT5,
+                FP_FMT_SINGLE,
+                FMIN_FUNCT3,
+                rd.number(),
+                rs1.number(),
+                rs2.number() as u32,
+            ),
+            RiscVInstruction::FmaxS { rd, rs1, rs2 } => encode_op_fp(
+                FMINMAX_FUNCT5,
+                FP_FMT_SINGLE,
+                FMAX_FUNCT3,
+                rd.number(),
+                rs1.number(),
+                rs2.number() as u32,
+            ),
+            RiscVInstruction::FeqS { rd, rs1, rs2 } => encode_op_fp(
+                FCMP_FUNCT5,
+                FP_FMT_SINGLE,
+                FEQ_FUNCT3,
+                rd.number(),
+                rs1.number(),
+                rs2.number() as u32,
+            ),
+            RiscVInstruction::FltS { rd, rs1, rs2 } => encode_op_fp(
+                FCMP_FUNCT5,
+                FP_FMT_SINGLE,
+                FLT_FUNCT3,
+                rd.number(),
+                rs1.number(),
+                rs2.number() as u32,
+            ),
+            RiscVInstruction::FleS { rd, rs1, rs2 } => encode_op_fp(
+                FCMP_FUNCT5,
+                FP_FMT_SINGLE,
+                FLE_FUNCT3,
+                rd.number(),
+                rs1.number(),
+                rs2.number() as u32,
+            ),
+            RiscVInstruction::FcvtWS { rd, rs1, rm } => encode_op_fp(
+                FCVT_INT_FUNCT5,
+                FP_FMT_SINGLE,
+                rm.field(),
+                rd.number(),
+                rs1.number(),
+                FCVT_W_RS2,
+            ),
+            RiscVInstruction::FcvtWuS { rd, rs1, rm } => encode_op_fp(
+                FCVT_INT_FUNCT5,
+                FP_FMT_SINGLE,
+                rm.field(),
+                rd.number(),
+                rs1.number(),
+                FCVT_WU_RS2,
+            ),
+            RiscVInstruction::FmvXW { rd, rs1 } => encode_op_fp(
+                FMV_X_FUNCT5,
+                FP_FMT_SINGLE,
+                FMV_X_FUNCT3,
+                rd.number(),
+                rs1.number(),
+                FP_UNARY_RS2,
+            ),
+            RiscVInstruction::FclassS { rd, rs1 } => encode_op_fp(
+                FMV_X_FUNCT5,
+                FP_FMT_SINGLE,
+                FCLASS_FUNCT3,
+                rd.number(),
+                rs1.number(),
+                FP_UNARY_RS2,
+            ),
+            RiscVInstruction::FcvtSW { rd, rs1, rm } => encode_op_fp(
+                FCVT_FROM_INT_FUNCT5,
+                FP_FMT_SINGLE,
+                rm.field(),
+                rd.number(),
+                rs1.number(),
+                FCVT_W_RS2,
+            ),
+            RiscVInstruction::FcvtSWu { rd, rs1, rm } => encode_op_fp(
+                FCVT_FROM_INT_FUNCT5,
+                FP_FMT_SINGLE,
+                rm.field(),
+                rd.number(),
+                rs1.number(),
+                FCVT_WU_RS2,
+            ),
+            RiscVInstruction::FmvWX { rd, rs1 } => encode_op_fp(
+                FMV_FROM_X_FUNCT5,
+                FP_FMT_SINGLE,
+                FMV_X_FUNCT3,
+                rd.number(),
+                rs1.number(),
+                FP_UNARY_RS2,
+            ),
+            RiscVInstruction::FmaddD {
+                rd,
+                rs1,
+                rs2,
+                rs3,
+                rm,
+            } => encode_r4(FMADD_OPCODE, FP_FMT_DOUBLE, *rd, *rs1, *rs2, *rs3, *rm),
+            RiscVInstruction::FmsubD {
+                rd,
+                rs1,
+                rs2,
+                rs3,
+                rm,
+            } => encode_r4(FMSUB_OPCODE, FP_FMT_DOUBLE, *rd, *rs1, *rs2, *rs3, *rm),
+            RiscVInstruction::FnmsubD {
+                rd,
+                rs1,
+                rs2,
+                rs3,
+                rm,
+            } => encode_r4(FNMSUB_OPCODE, FP_FMT_DOUBLE, *rd, *rs1, *rs2, *rs3, *rm),
+            RiscVInstruction::FnmaddD {
+                rd,
+                rs1,
+                rs2,
+                rs3,
+                rm,
+            } => encode_r4(FNMADD_OPCODE, FP_FMT_DOUBLE, *rd, *rs1, *rs2, *rs3, *rm),
+            RiscVInstruction::FaddD { rd, rs1, rs2, rm } => encode_op_fp(
+                FADD_FUNCT5,
+                FP_FMT_DOUBLE,
+                rm.field(),
+                rd.number(),
+                rs1.number(),
+                rs2.number() as u32,
+            ),
+            RiscVInstruction::FsubD { rd, rs1, rs2, rm } => encode_op_fp(
+                FSUB_FUNCT5,
+                FP_FMT_DOUBLE,
+                rm.field(),
+                rd.number(),
+                rs1.number(),
+                rs2.number() as u32,
+            ),
+            RiscVInstruction::FmulD { rd, rs1, rs2, rm } => encode_op_fp(
+                FMUL_FUNCT5,
+                FP_FMT_DOUBLE,
+                rm.field(),
+                rd.number(),
+                rs1.number(),
+                rs2.number() as u32,
+            ),
+            RiscVInstruction::FdivD { rd, rs1, rs2, rm } => encode_op_fp(
+                FDIV_FUNCT5,
+                FP_FMT_DOUBLE,
+                rm.field(),
+                rd.number(),
+                rs1.number(),
+                rs2.number() as u32,
+            ),
+            RiscVInstruction::FsqrtD { rd, rs1, rm } => encode_op_fp(
+                FSQRT_FUNCT5,
+                FP_FMT_DOUBLE,
+                rm.field(),
+                rd.number(),
+                rs1.number(),
+                FP_UNARY_RS2,
+            ),
+            RiscVInstruction::FsgnjD { rd, rs1, rs2 } => encode_op_fp(
+                FSGNJ_FUNCT5,
+                FP_FMT_DOUBLE,
+                FSGNJ_FUNCT3,
+                rd.number(),
+                rs1.number(),
+                rs2.number() as u32,
+            ),
+            RiscVInstruction::FsgnjnD { rd, rs1, rs2 } => encode_op_fp(
+                FSGNJ_FUNCT5,
+                FP_FMT_DOUBLE,
+                FSGNJN_FUNCT3,
+                rd.number(),
+                rs1.number(),
+                rs2.number() as u32,
+            ),
+            RiscVInstruction::FsgnjxD { rd, rs1, rs2 } => encode_op_fp(
+                FSGNJ_FUNCT5,
+                FP_FMT_DOUBLE,
+                FSGNJX_FUNCT3,
+                rd.number(),
+                rs1.number(),
+                rs2.number() as u32,
+            ),
+            RiscVInstruction::FminD { rd, rs1, rs2 } => encode_op_fp(
+                FMINMAX_FUNCT5,
+                FP_FMT_DOUBLE,
+                FMIN_FUNCT3,
+                rd.number(),
+                rs1.number(),
+                rs2.number() as u32,
+            ),
+            RiscVInstruction::FmaxD { rd, rs1, rs2 } => encode_op_fp(
+                FMINMAX_FUNCT5,
+                FP_FMT_DOUBLE,
+                FMAX_FUNCT3,
+                rd.number(),
+                rs1.number(),
+                rs2.number() as u32,
+            ),
+            RiscVInstruction::FeqD { rd, rs1, rs2 } => encode_op_fp(
+                FCMP_FUNCT5,
+                FP_FMT_DOUBLE,
+                FEQ_FUNCT3,
+                rd.number(),
+                rs1.number(),
+                rs2.number() as u32,
+            ),
+            RiscVInstruction::FltD { rd, rs1, rs2 } => encode_op_fp(
+                FCMP_FUNCT5,
+                FP_FMT_DOUBLE,
+                FLT_FUNCT3,
+                rd.number(),
+                rs1.number(),
+                rs2.number() as u32,
+            ),
+            RiscVInstruction::FleD { rd, rs1, rs2 } => encode_op_fp(
+                FCMP_FUNCT5,
+                FP_FMT_DOUBLE,
+                FLE_FUNCT3,
+                rd.number(),
+                rs1.number(),
+                rs2.number() as u32,
+            ),
+            RiscVInstruction::FcvtWD { rd, rs1, rm } => encode_op_fp(
+                FCVT_INT_FUNCT5,
+                FP_FMT_DOUBLE,
+                rm.field(),
+                rd.number(),
+                rs1.number(),
+                FCVT_W_RS2,
+            ),
+            RiscVInstruction::FcvtWuD { rd, rs1, rm } => encode_op_fp(
+                FCVT_INT_FUNCT5,
+                FP_FMT_DOUBLE,
+                rm.field(),
+                rd.number(),
+                rs1.number(),
+                FCVT_WU_RS2,
+            ),
+            RiscVInstruction::FclassD { rd, rs1 } => encode_op_fp(
+                FMV_X_FUNCT5,
+                FP_FMT_DOUBLE,
+                FCLASS_FUNCT3,
+                rd.number(),
+                rs1.number(),
+                FP_UNARY_RS2,
+            ),
+            RiscVInstruction::FcvtDW { rd, rs1, rm } => encode_op_fp(
+                FCVT_FROM_INT_FUNCT5,
+                FP_FMT_DOUBLE,
+                rm.field(),
+                rd.number(),
+                rs1.number(),
+                FCVT_W_RS2,
+            ),
+            RiscVInstruction::FcvtDWu { rd, rs1, rm } => encode_op_fp(
+                FCVT_FROM_INT_FUNCT5,
+                FP_FMT_DOUBLE,
+                rm.field(),
+                rd.number(),
+                rs1.number(),
+                FCVT_WU_RS2,
+            ),
+            RiscVInstruction::FcvtSD { rd, rs1, rm } => encode_op_fp(
+                FCVT_FMT_FUNCT5,
+                FP_FMT_SINGLE,
+                rm.field(),
+                rd.number(),
+                rs1.number(),
+                FP_FMT_DOUBLE,
+            ),
+            RiscVInstruction::FcvtDS { rd, rs1, rm } => encode_op_fp(
+                FCVT_FMT_FUNCT5,
+                FP_FMT_DOUBLE,
+                rm.field(),
+                rd.number(),
+                rs1.number(),
+                FP_FMT_SINGLE,
+            ),
+            RiscVInstruction::Ld { rd, rs1, imm } => {
+                encode_i(LOAD_OPCODE, LD_FUNCT3, *rd, *rs1, *imm)
+            }
+            RiscVInstruction::Lwu { rd, rs1, imm } => {
+                encode_i(LOAD_OPCODE, LWU_FUNCT3, *rd, *rs1, *imm)
+            }
+            RiscVInstruction::Sd { rs1, rs2, imm } => encode_s(SD_FUNCT3, *rs1, *rs2, *imm),
+            RiscVInstruction::Addiw { rd, rs1, imm } => {
+                encode_i(IMM_32_OPCODE, ADDI_FUNCT3, *rd, *rs1, *imm)
+            }
+            RiscVInstruction::Slliw { rd, rs1, imm } => {
+                encode_shift(IMM_32_OPCODE, SLLI_FUNCT3, SLLI_FUNCT7, *rd, *rs1, *imm)
+            }
+            RiscVInstruction::Srliw { rd, rs1, imm } => {
+                encode_shift(IMM_32_OPCODE, SRLI_FUNCT3, SRLI_FUNCT7, *rd, *rs1, *imm)
+            }
+            RiscVInstruction::Sraiw { rd, rs1, imm } => {
+                encode_shift(IMM_32_OPCODE, SRLI_FUNCT3, SRAI_FUNCT7, *rd, *rs1, *imm)
+            }
+            RiscVInstruction::Addw { rd, rs1, rs2 } => {
+                encode_r(REG_32_OPCODE, ADD_FUNCT3, ADD_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Subw { rd, rs1, rs2 } => {
+                encode_r(REG_32_OPCODE, ADD_FUNCT3, SUB_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Sllw { rd, rs1, rs2 } => {
+                encode_r(REG_32_OPCODE, SLL_FUNCT3, SLL_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Srlw { rd, rs1, rs2 } => {
+                encode_r(REG_32_OPCODE, SRL_FUNCT3, SRL_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Sraw { rd, rs1, rs2 } => {
+                encode_r(REG_32_OPCODE, SRL_FUNCT3, SRA_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Mulw { rd, rs1, rs2 } => {
+                encode_r(REG_32_OPCODE, MUL_FUNCT3, MULDIV_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Divw { rd, rs1, rs2 } => {
+                encode_r(REG_32_OPCODE, DIV_FUNCT3, MULDIV_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Divuw { rd, rs1, rs2 } => {
+                encode_r(REG_32_OPCODE, DIVU_FUNCT3, MULDIV_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Remw { rd, rs1, rs2 } => {
+                encode_r(REG_32_OPCODE, REM_FUNCT3, MULDIV_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Remuw { rd, rs1, rs2 } => {
+                encode_r(REG_32_OPCODE, REMU_FUNCT3, MULDIV_FUNCT7, *rd, *rs1, *rs2)
+            }
+            RiscVInstruction::Custom {
+                opcode,
+                funct3,
+                operands,
+            } => {
+                if *funct3 > 0x7 {
+                    return Err(Error::InvalidImmediate);
+                }
+
+                match *operands {
+                    CustomOperands::R {
+                        funct7,
+                        rd,
+                        rs1,
+                        rs2,
+                    } if funct7 <= 0x7f => {
+                        encode_r(opcode.opcode(), *funct3, funct7 as u32, rd, rs1, rs2)
+                    }
+                    CustomOperands::R { .. } => Err(Error::InvalidImmediate),
+                    CustomOperands::I { rd, rs1, imm } => {
+                        encode_i(opcode.opcode(), *funct3, rd, rs1, imm)
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the absolute target address of a conditional branch or `jal`.
+    ///
+    /// The offset of a branch or `jal` is relative to the address of the instruction
+    /// itself, so the target is `pc + imm`, wrapping around the 32-bit address space.
+    /// Returns `None` for all other instructions, including `jalr`, whose target
+    /// depends on a register value.
+    ///
+    /// # Arguments
+    ///
+    /// * `pc` - The address of this instruction
+    pub fn branch_target(&self, pc: u32) -> Option<u32> {
+        match self {
+            RiscVInstruction::Beq { imm, .. }
+            | RiscVInstruction::Bne { imm, .. }
+            | RiscVInstruction::Blt { imm, .. }
+            | RiscVInstruction::Bge { imm, .. }
+            | RiscVInstruction::Bltu { imm, .. }
+            | RiscVInstruction::Bgeu { imm, .. } => Some(pc.wrapping_add(*imm as i32 as u32)),
+            RiscVInstruction::Jal { imm, .. } => Some(pc.wrapping_add(*imm as u32)),
+            _ => None,
+        }
+    }
+
+    /// Returns the optional extension this instruction belongs to, or `None` if it is
+    /// part of the base instruction set.
+    pub fn extension(&self) -> Option<Extension> {
+        match self {
+            RiscVInstruction::Sh1add { .. }
+            | RiscVInstruction::Sh2add { .. }
+            | RiscVInstruction::Sh3add { .. } => Some(Extension::Zba),
+            RiscVInstruction::Andn { .. }
+            | RiscVInstruction::Orn { .. }
+            | RiscVInstruction::Xnor { .. }
+            | RiscVInstruction::Clz { .. }
+            | RiscVInstruction::Ctz { .. }
+            | RiscVInstruction::Cpop { .. }
+            | RiscVInstruction::Max { .. }
+            | RiscVInstruction::Maxu { .. }
+            | RiscVInstruction::Min { .. }
+            | RiscVInstruction::Minu { .. }
+            | RiscVInstruction::SextB { .. }
+            | RiscVInstruction::SextH { .. }
+            | RiscVInstruction::ZextH { .. }
+            | RiscVInstruction::Rol { .. }
+            | RiscVInstruction::Ror { .. }
+            | RiscVInstruction::Rori { .. }
+            | RiscVInstruction::OrcB { .. }
+            | RiscVInstruction::Rev8 { .. } => Some(Extension::Zbb),
+            RiscVInstruction::Bclr { .. }
+            | RiscVInstruction::Bclri { .. }
+            | RiscVInstruction::Bext { .. }
+            | RiscVInstruction::Bexti { .. }
+            | RiscVInstruction::Binv { .. }
+            | RiscVInstruction::Binvi { .. }
+            | RiscVInstruction::Bset { .. }
+            | RiscVInstruction::Bseti { .. } => Some(Extension::Zbs),
+            RiscVInstruction::Sha256sig0 { .. }
+            | RiscVInstruction::Sha256sig1 { .. }
+            | RiscVInstruction::Sha256sum0 { .. }
+            | RiscVInstruction::Sha256sum1 { .. }
+            | RiscVInstruction::Sha512sum0r { .. }
+            | RiscVInstruction::Sha512sum1r { .. }
+            | RiscVInstruction::Sha512sig0l { .. }
+            | RiscVInstruction::Sha512sig0h { .. }
+            | RiscVInstruction::Sha512sig1l { .. }
+            | RiscVInstruction::Sha512sig1h { .. } => Some(Extension::Zknh),
+            RiscVInstruction::FenceI => Some(Extension::Zifencei),
+            RiscVInstruction::LrW { .. }
+            | RiscVInstruction::ScW { .. }
+            | RiscVInstruction::AmoswapW { .. }
+            | RiscVInstruction::AmoaddW { .. }
+            | RiscVInstruction::AmoxorW { .. }
+            | RiscVInstruction::AmoandW { .. }
+            | RiscVInstruction::AmoorW { .. }
+            | RiscVInstruction::AmominW { .. }
+            | RiscVInstruction::AmomaxW { .. }
+            | RiscVInstruction::AmominuW { .. }
+            | RiscVInstruction::AmomaxuW { .. } => Some(Extension::A),
+            RiscVInstruction::Flw { .. }
+            | RiscVInstruction::Fsw { .. }
+            | RiscVInstruction::FmaddS { .. }
+            | RiscVInstruction::FmsubS { .. }
+            | RiscVInstruction::FnmsubS { .. }
+            | RiscVInstruction::FnmaddS { .. }
+            | RiscVInstruction::FaddS { .. }
+            | RiscVInstruction::FsubS { .. }
+            | RiscVInstruction::FmulS { .. }
+            | RiscVInstruction::FdivS { .. }
+            | RiscVInstruction::FsqrtS { .. }
+            | RiscVInstruction::FsgnjS { .. }
+            | RiscVInstruction::FsgnjnS { .. }
+            | RiscVInstruction::FsgnjxS { .. }
+            | RiscVInstruction::FminS { .. }
+            | RiscVInstruction::FmaxS { .. }
+            | RiscVInstruction::FeqS { .. }
+            | RiscVInstruction::FltS { .. }
+            | RiscVInstruction::FleS { .. }
+            | RiscVInstruction::FcvtWS { .. }
+            | RiscVInstruction::FcvtWuS { .. }
+            | RiscVInstruction::FmvXW { .. }
+            | RiscVInstruction::FclassS { .. }
+            | RiscVInstruction::FcvtSW { .. }
+            | RiscVInstruction::FcvtSWu { .. }
+            | RiscVInstruction::FmvWX { .. } => Some(Extension::F),
+            RiscVInstruction::Fld { .. }
+            | RiscVInstruction::Fsd { .. }
+            | RiscVInstruction::FmaddD { .. }
+            | RiscVInstruction::FmsubD { .. }
+            | RiscVInstruction::FnmsubD { .. }
+            | RiscVInstruction::FnmaddD { .. }
+            | RiscVInstruction::FaddD { .. }
+            | RiscVInstruction::FsubD { .. }
+            | RiscVInstruction::FmulD { .. }
+            | RiscVInstruction::FdivD { .. }
+            | RiscVInstruction::FsqrtD { .. }
+            | RiscVInstruction::FsgnjD { .. }
+            | RiscVInstruction::FsgnjnD { .. }
+            | RiscVInstruction::FsgnjxD { .. }
+            | RiscVInstruction::FminD { .. }
+            | RiscVInstruction::FmaxD { .. }
+            | RiscVInstruction::FeqD { .. }
+            | RiscVInstruction::FltD { .. }
+            | RiscVInstruction::FleD { .. }
+            | RiscVInstruction::FcvtWD { .. }
+            | RiscVInstruction::FcvtWuD { .. }
+            | RiscVInstruction::FclassD { .. }
+            | RiscVInstruction::FcvtDW { .. }
+            | RiscVInstruction::FcvtDWu { .. }
+            | RiscVInstruction::FcvtSD { .. }
+            | RiscVInstruction::FcvtDS { .. } => Some(Extension::D),
+            RiscVInstruction::Csrrw { .. }
+            | RiscVInstruction::Csrrs { .. }
+            | RiscVInstruction::Csrrc { .. }
+            | RiscVInstruction::Csrrwi { .. }
+            | RiscVInstruction::Csrrsi { .. }
+            | RiscVInstruction::Csrrci { .. } => Some(Extension::Zicsr),
+            _ => None,
         }
     }
 }
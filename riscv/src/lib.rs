@@ -1,64 +1,52 @@
+#[cfg(any(target_arch = "aarch64", test))]
+mod aarch64;
+mod assembler;
+mod atomic;
+mod compressed;
 mod config;
+mod csr;
+mod custom;
+mod disassembler;
 mod engine;
 mod error;
+mod extension;
+mod float;
+mod hart;
 mod instance;
 mod instruction;
 mod memory;
+mod metadata;
 mod module;
+mod profile;
+mod program;
+mod pseudo;
+mod register;
+mod semantics;
+mod stream;
 #[cfg(test)]
 mod tests;
+#[cfg(any(target_arch = "x86_64", test))]
+mod x86_64;
 
+pub use assembler::{AssembledInstruction, Assembly, assemble};
+pub use atomic::Reservation;
 pub use config::Config;
+pub use csr::Counters;
+pub use custom::{
+    CustomForm, CustomHandler, CustomInstruction, CustomInstructions, CustomOpcode, CustomOperands,
+    RegisterFile,
+};
+pub use disassembler::{DisassembledInstruction, Disassembly, DisassemblyDisplay, disassemble};
 pub use engine::Engine;
-pub use error::Error;
+pub use error::{AssembleError, AssembleErrorKind, DecodeError, DecodeErrorKind, Error};
+pub use extension::{Extension, Extensions};
+pub use float::{ExceptionFlags, Fcsr, RoundingMode};
 pub use instance::Instance;
-pub use instruction::RiscVInstruction;
+pub use instruction::{InstructionDisplay, RiscVInstruction};
 pub use memory::Memory;
+pub use metadata::{AccessKind, ControlFlow, MemoryAccess};
 pub use module::Module;
-
-/*
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test() {
-        let syscall = |_args: &[u32], _context: u64| 0;
-
-        let config = Config {
-            syscall,
-            max_instance_memory: 1024 * 1024,
-            max_code_size: 1024,
-        };
-
-        let engine = Engine::new(config);
-
-        // This is ARM64 assembly for a function that takes a u32 and returns it.
-        // fn(num: u32) -> u32 { return num; }
-        //
-        // You can get this from a compiler explorer.
-        // sub sp, sp, #16
-        // str w0, [sp, #12]
-        // ldr w0, [sp, #12]
-        // add sp, sp, #16
-        // ret
-        let mut code = vec![];
-        code.extend(0xD10043FF_u32.to_le_bytes());
-        code.extend(0xB9000FE0_u32.to_le_bytes());
-        code.extend(0xB9400FE0_u32.to_le_bytes());
-        code.extend(0x910043FF_u32.to_le_bytes());
-        code.extend(0xD65F03C0_u32.to_le_bytes());
-
-        let mut module = Module::new(engine.clone()).unwrap();
-        module.set_native_code(&code).unwrap();
-
-        let memory = Memory::new(engine.clone());
-
-        let mut instance = Instance::new(module, memory).unwrap();
-
-        let output = instance.call(0, 42).unwrap();
-
-        assert_eq!(output, 42);
-    }
-}
-*/
+pub use profile::Profile;
+pub use pseudo::{PrintedInstruction, Pseudo, PseudoDisplay, PseudoPrinter};
+pub use register::{FloatRegister, Register, Syntax};
+pub use stream::{DecodedInstruction, InstructionStream};
@@ -4,17 +4,24 @@ use std::rc::Rc;
 /// The memory of an instance.
 pub struct Memory {
     pub(crate) engine: Rc<Engine>,
-    _memory: Vec<u8>,
+    data: Vec<u8>,
 }
 
 impl Memory {
     /// Constructs a new `Memory` with the given engine.
     pub fn new(engine: Rc<Engine>) -> Box<Self> {
-        let memory = vec![0; engine.config().max_instance_memory as usize];
+        let data = vec![0; engine.config().max_instance_memory as usize];
 
-        Box::new(Self {
-            engine,
-            _memory: memory,
-        })
+        Box::new(Self { engine, data })
+    }
+
+    /// Returns the contents of the memory.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Returns the contents of the memory, for the host to read and write.
+    pub fn data_mut(&mut self) -> &mut [u8] {
+        &mut self.data
     }
 }
@@ -0,0 +1,645 @@
+use crate::custom::CustomOperands;
+use crate::instruction::RiscVInstruction;
+use crate::register::{FloatRegister, Register};
+
+/// How an instruction affects the flow of control.
+///
+/// Calls and returns are recognized by the link register hints of the RISC-V
+/// calling convention: a jump that writes `ra` or `t0` is a call, and a `jalr`
+/// that discards its link and jumps through `ra` or `t0` is a return.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ControlFlow {
+    /// Execution continues with the next instruction.
+    Sequential,
+    /// A conditional branch to a pc-relative target.
+    Branch,
+    /// An unconditional `jal` that is not a call.
+    Jump,
+    /// A `jalr` that is neither a call nor a return.
+    IndirectJump,
+    /// A `jal` that links through `ra` or `t0`.
+    Call,
+    /// A `jalr` that links through `ra` or `t0`.
+    IndirectCall,
+    /// A `jalr` that discards its link and jumps through `ra` or `t0`.
+    Return,
+    /// Control passes to the host, as for `ecall` and `ebreak`.
+    Trap,
+}
+
+impl ControlFlow {
+    /// Whether the instruction may continue anywhere other than the next instruction.
+    pub fn is_control_transfer(self) -> bool {
+        self != ControlFlow::Sequential
+    }
+}
+
+/// Whether a memory access reads or writes memory, or reads and then writes the
+/// same location as the atomic memory operations do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AccessKind {
+    Load,
+    Store,
+    ReadModifyWrite,
+}
+
+/// The memory access made by a load, store or atomic instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MemoryAccess {
+    /// Whether the access is a load or a store.
+    pub kind: AccessKind,
+    /// The number of bytes accessed: 1, 2, 4 or 8.
+    pub width: u8,
+    /// Whether a load sign-extends the value to the register width. Always `false`
+    /// for stores. `lw` is signed, since it sign-extends to 64 bits on RV64I.
+    pub signed: bool,
+}
+
+impl MemoryAccess {
+    const fn load(width: u8, signed: bool) -> Option<MemoryAccess> {
+        Some(MemoryAccess {
+            kind: AccessKind::Load,
+            width,
+            signed,
+        })
+    }
+
+    const fn store(width: u8) -> Option<MemoryAccess> {
+        Some(MemoryAccess {
+            kind: AccessKind::Store,
+            width,
+            signed: false,
+        })
+    }
+
+    const fn read_modify_write(width: u8) -> Option<MemoryAccess> {
+        Some(MemoryAccess {
+            kind: AccessKind::ReadModifyWrite,
+            width,
+            signed: false,
+        })
+    }
+}
+
+/// Whether a link register write marks a jump as a call.
+fn is_link(register: Register) -> bool {
+    register == Register::RA || register == Register::T0
+}
+
+impl RiscVInstruction {
+    /// Returns the registers this instruction reads, in operand order.
+    ///
+    /// `x0` is never reported, since reading it always yields zero. A register
+    /// named by both source operands is reported twice. Only integer registers are
+    /// reported: see [`RiscVInstruction::float_reads`] for floating-point registers.
+    /// A custom instruction reports the registers named by its operands, although
+    /// its handler may read any register.
+    pub fn reads(&self) -> impl Iterator<Item = Register> {
+        self.sources()
+            .into_iter()
+            .flatten()
+            .filter(|register| *register != Register::ZERO)
+    }
+
+    /// Returns the integer source operands `rs1` and `rs2` of this instruction, or
+    /// `None` for an operand it does not have. Unlike [`RiscVInstruction::reads`],
+    /// `x0` is included.
+    pub(crate) fn sources(&self) -> [Option<Register>; 2] {
+        match *self {
+            RiscVInstruction::Add { rs1, rs2, .. }
+            | RiscVInstruction::Sub { rs1, rs2, .. }
+            | RiscVInstruction::Xor { rs1, rs2, .. }
+            | RiscVInstruction::Or { rs1, rs2, .. }
+            | RiscVInstruction::And { rs1, rs2, .. }
+            | RiscVInstruction::Sll { rs1, rs2, .. }
+            | RiscVInstruction::Srl { rs1, rs2, .. }
+            | RiscVInstruction::Sra { rs1, rs2, .. }
+            | RiscVInstruction::Slt { rs1, rs2, .. }
+            | RiscVInstruction::Sltu { rs1, rs2, .. }
+            | RiscVInstruction::Mul { rs1, rs2, .. }
+            | RiscVInstruction::Mulh { rs1, rs2, .. }
+            | RiscVInstruction::Mulhsu { rs1, rs2, .. }
+            | RiscVInstruction::Mulhu { rs1, rs2, .. }
+            | RiscVInstruction::Div { rs1, rs2, .. }
+            | RiscVInstruction::Divu { rs1, rs2, .. }
+            | RiscVInstruction::Rem { rs1, rs2, .. }
+            | RiscVInstruction::Remu { rs1, rs2, .. }
+            | RiscVInstruction::Addw { rs1, rs2, .. }
+            | RiscVInstruction::Subw { rs1, rs2, .. }
+            | RiscVInstruction::Sllw { rs1, rs2, .. }
+            | RiscVInstruction::Srlw { rs1, rs2, .. }
+            | RiscVInstruction::Sraw { rs1, rs2, .. }
+            | RiscVInstruction::Mulw { rs1, rs2, .. }
+            | RiscVInstruction::Divw { rs1, rs2, .. }
+            | RiscVInstruction::Divuw { rs1, rs2, .. }
+            | RiscVInstruction::Remw { rs1, rs2, .. }
+            | RiscVInstruction::Remuw { rs1, rs2, .. }
+            | RiscVInstruction::Sb { rs1, rs2, .. }
+            | RiscVInstruction::Sh { rs1, rs2, .. }
+            | RiscVInstruction::Sw { rs1, rs2, .. }
+            | RiscVInstruction::Sd { rs1, rs2, .. }
+            | RiscVInstruction::Beq { rs1, rs2, .. }
+            | RiscVInstruction::Bne { rs1, rs2, .. }
+            | RiscVInstruction::Blt { rs1, rs2, .. }
+            | RiscVInstruction::Bge { rs1, rs2, .. }
+            | RiscVInstruction::Bltu { rs1, rs2, .. }
+            | RiscVInstruction::Bgeu { rs1, rs2, .. }
+            | RiscVInstruction::Sh1add { rs1, rs2, .. }
+            | RiscVInstruction::Sh2add { rs1, rs2, .. }
+            | RiscVInstruction::Sh3add { rs1, rs2, .. }
+            | RiscVInstruction::Andn { rs1, rs2, .. }
+            | RiscVInstruction::Orn { rs1, rs2, .. }
+            | RiscVInstruction::Xnor { rs1, rs2, .. }
+            | RiscVInstruction::Max { rs1, rs2, .. }
+            | RiscVInstruction::Maxu { rs1, rs2, .. }
+            | RiscVInstruction::Min { rs1, rs2, .. }
+            | RiscVInstruction::Minu { rs1, rs2, .. }
+            | RiscVInstruction::Rol { rs1, rs2, .. }
+            | RiscVInstruction::Ror { rs1, rs2, .. }
+            | RiscVInstruction::Bclr { rs1, rs2, .. }
+            | RiscVInstruction::Bext { rs1, rs2, .. }
+            | RiscVInstruction::Binv { rs1, rs2, .. }
+            | RiscVInstruction::Bset { rs1, rs2, .. }
+            | RiscVInstruction::Sha512sum0r { rs1, rs2, .. }
+            | RiscVInstruction::Sha512sum1r { rs1, rs2, .. }
+            | RiscVInstruction::Sha512sig0l { rs1, rs2, .. }
+            | RiscVInstruction::Sha512sig0h { rs1, rs2, .. }
+            | RiscVInstruction::Sha512sig1l { rs1, rs2, .. }
+            | RiscVInstruction::Sha512sig1h { rs1, rs2, .. }
+            | RiscVInstruction::ScW { rs1, rs2, .. }
+            | RiscVInstruction::AmoswapW { rs1, rs2, .. }
+            | RiscVInstruction::AmoaddW { rs1, rs2, .. }
+            | RiscVInstruction::AmoxorW { rs1, rs2, .. }
+            | RiscVInstruction::AmoandW { rs1, rs2, .. }
+            | RiscVInstruction::AmoorW { rs1, rs2, .. }
+            | RiscVInstruction::AmominW { rs1, rs2, .. }
+            | RiscVInstruction::AmomaxW { rs1, rs2, .. }
+            | RiscVInstruction::AmominuW { rs1, rs2, .. }
+            | RiscVInstruction::AmomaxuW { rs1, rs2, .. } => [Some(rs1), Some(rs2)],
+            RiscVInstruction::Addi { rs1, .. }
+            | RiscVInstruction::LrW { rs1, .. }
+            | RiscVInstruction::Xori { rs1, .. }
+            | RiscVInstruction::Ori { rs1, .. }
+            | RiscVInstruction::Andi { rs1, .. }
+            | RiscVInstruction::Slli { rs1, .. }
+            | RiscVInstruction::Srli { rs1, .. }
+            | RiscVInstruction::Srai { rs1, .. }
+            | RiscVInstruction::Slti { rs1, .. }
+            | RiscVInstruction::Sltiu { rs1, .. }
+            | RiscVInstruction::Lb { rs1, .. }
+            | RiscVInstruction::Lh { rs1, .. }
+            | RiscVInstruction::Lw { rs1, .. }
+            | RiscVInstruction::Ld { rs1, .. }
+            | RiscVInstruction::Lwu { rs1, .. }
+            | RiscVInstruction::Addiw { rs1, .. }
+            | RiscVInstruction::Slliw { rs1, .. }
+            | RiscVInstruction::Srliw { rs1, .. }
+            | RiscVInstruction::Sraiw { rs1, .. }
+            | RiscVInstruction::Lbu { rs1, .. }
+            | RiscVInstruction::Lhu { rs1, .. }
+            | RiscVInstruction::Jalr { rs1, .. }
+            | RiscVInstruction::Clz { rs1, .. }
+            | RiscVInstruction::Ctz { rs1, .. }
+            | RiscVInstruction::Cpop { rs1, .. }
+            | RiscVInstruction::SextB { rs1, .. }
+            | RiscVInstruction::SextH { rs1, .. }
+            | RiscVInstruction::ZextH { rs1, .. }
+            | RiscVInstruction::OrcB { rs1, .. }
+            | RiscVInstruction::Rev8 { rs1, .. }
+            | RiscVInstruction::Rori { rs1, .. }
+            | RiscVInstruction::Bclri { rs1, .. }
+            | RiscVInstruction::Bexti { rs1, .. }
+            | RiscVInstruction::Binvi { rs1, .. }
+            | RiscVInstruction::Bseti { rs1, .. }
+            | RiscVInstruction::Sha256sig0 { rs1, .. }
+            | RiscVInstruction::Sha256sig1 { rs1, .. }
+            | RiscVInstruction::Sha256sum0 { rs1, .. }
+            | RiscVInstruction::Sha256sum1 { rs1, .. }
+            | RiscVInstruction::Csrrw { rs1, .. }
+            | RiscVInstruction::Csrrs { rs1, .. }
+            | RiscVInstruction::Csrrc { rs1, .. }
+            | RiscVInstruction::Flw { rs1, .. }
+            | RiscVInstruction::Fsw { rs1, .. }
+            | RiscVInstruction::Fld { rs1, .. }
+            | RiscVInstruction::Fsd { rs1, .. }
+            | RiscVInstruction::FcvtSW { rs1, .. }
+            | RiscVInstruction::FcvtSWu { rs1, .. }
+            | RiscVInstruction::FmvWX { rs1, .. }
+            | RiscVInstruction::FcvtDW { rs1, .. }
+            | RiscVInstruction::FcvtDWu { rs1, .. } => [Some(rs1), None],
+            RiscVInstruction::Lui { .. }
+            | RiscVInstruction::Auipc { .. }
+            | RiscVInstruction::Jal { .. }
+            | RiscVInstruction::Ecall
+            | RiscVInstruction::Ebreak
+            | RiscVInstruction::Fence { .. }
+            | RiscVInstruction::FenceTso
+            | RiscVInstruction::FenceI
+            | RiscVInstruction::Csrrwi { .. }
+            | RiscVInstruction::Csrrsi { .. }
+            | RiscVInstruction::Csrrci { .. }
+            | RiscVInstruction::FmaddS { .. }
+            | RiscVInstruction::FmsubS { .. }
+            | RiscVInstruction::FnmsubS { .. }
+            | RiscVInstruction::FnmaddS { .. }
+            | RiscVInstruction::FaddS { .. }
+            | RiscVInstruction::FsubS { .. }
+            | RiscVInstruction::FmulS { .. }
+            | RiscVInstruction::FdivS { .. }
+            | RiscVInstruction::FsqrtS { .. }
+            | RiscVInstruction::FsgnjS { .. }
+            | RiscVInstruction::FsgnjnS { .. }
+            | RiscVInstruction::FsgnjxS { .. }
+            | RiscVInstruction::FminS { .. }
+            | RiscVInstruction::FmaxS { .. }
+            | RiscVInstruction::FeqS { .. }
+            | RiscVInstruction::FltS { .. }
+            | RiscVInstruction::FleS { .. }
+            | RiscVInstruction::FcvtWS { .. }
+            | RiscVInstruction::FcvtWuS { .. }
+            | RiscVInstruction::FmvXW { .. }
+            | RiscVInstruction::FclassS { .. }
+            | RiscVInstruction::FmaddD { .. }
+            | RiscVInstruction::FmsubD { .. }
+            | RiscVInstruction::FnmsubD { .. }
+            | RiscVInstruction::FnmaddD { .. }
+            | RiscVInstruction::FaddD { .. }
+            | RiscVInstruction::FsubD { .. }
+            | RiscVInstruction::FmulD { .. }
+            | RiscVInstruction::FdivD { .. }
+            | RiscVInstruction::FsqrtD { .. }
+            | RiscVInstruction::FsgnjD { .. }
+            | RiscVInstruction::FsgnjnD { .. }
+            | RiscVInstruction::FsgnjxD { .. }
+            | RiscVInstruction::FminD { .. }
+            | RiscVInstruction::FmaxD { .. }
+            | RiscVInstruction::FeqD { .. }
+            | RiscVInstruction::FltD { .. }
+            | RiscVInstruction::FleD { .. }
+            | RiscVInstruction::FcvtWD { .. }
+            | RiscVInstruction::FcvtWuD { .. }
+            | RiscVInstruction::FclassD { .. }
+            | RiscVInstruction::FcvtSD { .. }
+            | RiscVInstruction::FcvtDS { .. } => [None, None],
+            RiscVInstruction::Custom { operands, .. } => match operands {
+                CustomOperands::R { rs1, rs2, .. } => [Some(rs1), Some(rs2)],
+                CustomOperands::I { rs1, .. } => [Some(rs1), None],
+            },
+        }
+    }
+
+    /// Returns the register this instruction writes, if any.
+    ///
+    /// Writes to `x0` are discarded, so an instruction whose destination is `x0`
+    /// writes no register. The result of an `ecall` is placed in `a0` by the host
+    /// and is not reported here. Only integer registers are reported: see
+    /// [`RiscVInstruction::float_writes`] for floating-point registers. A custom
+    /// instruction reports its `rd`, although its handler may write any register.
+    pub fn writes(&self) -> Option<Register> {
+        let rd = match *self {
+            RiscVInstruction::Add { rd, .. }
+            | RiscVInstruction::Sub { rd, .. }
+            | RiscVInstruction::Xor { rd, .. }
+            | RiscVInstruction::Or { rd, .. }
+            | RiscVInstruction::And { rd, .. }
+            | RiscVInstruction::Sll { rd, .. }
+            | RiscVInstruction::Srl { rd, .. }
+            | RiscVInstruction::Sra { rd, .. }
+            | RiscVInstruction::Slt { rd, .. }
+            | RiscVInstruction::Sltu { rd, .. }
+            | RiscVInstruction::Mul { rd, .. }
+            | RiscVInstruction::Mulh { rd, .. }
+            | RiscVInstruction::Mulhsu { rd, .. }
+            | RiscVInstruction::Mulhu { rd, .. }
+            | RiscVInstruction::Div { rd, .. }
+            | RiscVInstruction::Divu { rd, .. }
+            | RiscVInstruction::Rem { rd, .. }
+            | RiscVInstruction::Remu { rd, .. }
+            | RiscVInstruction::Addi { rd, .. }
+            | RiscVInstruction::Xori { rd, .. }
+            | RiscVInstruction::Ori { rd, .. }
+            | RiscVInstruction::Andi { rd, .. }
+            | RiscVInstruction::Slli { rd, .. }
+            | RiscVInstruction::Srli { rd, .. }
+            | RiscVInstruction::Srai { rd, .. }
+            | RiscVInstruction::Slti { rd, .. }
+            | RiscVInstruction::Sltiu { rd, .. }
+            | RiscVInstruction::Lb { rd, .. }
+            | RiscVInstruction::Lh { rd, .. }
+            | RiscVInstruction::Lw { rd, .. }
+            | RiscVInstruction::Lbu { rd, .. }
+            | RiscVInstruction::Lhu { rd, .. }
+            | RiscVInstruction::Ld { rd, .. }
+            | RiscVInstruction::Lwu { rd, .. }
+            | RiscVInstruction::Addiw { rd, .. }
+            | RiscVInstruction::Slliw { rd, .. }
+            | RiscVInstruction::Srliw { rd, .. }
+            | RiscVInstruction::Sraiw { rd, .. }
+            | RiscVInstruction::Addw { rd, .. }
+            | RiscVInstruction::Subw { rd, .. }
+            | RiscVInstruction::Sllw { rd, .. }
+            | RiscVInstruction::Srlw { rd, .. }
+            | RiscVInstruction::Sraw { rd, .. }
+            | RiscVInstruction::Mulw { rd, .. }
+            | RiscVInstruction::Divw { rd, .. }
+            | RiscVInstruction::Divuw { rd, .. }
+            | RiscVInstruction::Remw { rd, .. }
+            | RiscVInstruction::Remuw { rd, .. }
+            | RiscVInstruction::Lui { rd, .. }
+            | RiscVInstruction::Auipc { rd, .. }
+            | RiscVInstruction::Jal { rd, .. }
+            | RiscVInstruction::Jalr { rd, .. }
+            | RiscVInstruction::Sh1add { rd, .. }
+            | RiscVInstruction::Sh2add { rd, .. }
+            | RiscVInstruction::Sh3add { rd, .. }
+            | RiscVInstruction::Andn { rd, .. }
+            | RiscVInstruction::Orn { rd, .. }
+            | RiscVInstruction::Xnor { rd, .. }
+            | RiscVInstruction::Max { rd, .. }
+            | RiscVInstruction::Maxu { rd, .. }
+            | RiscVInstruction::Min { rd, .. }
+            | RiscVInstruction::Minu { rd, .. }
+            | RiscVInstruction::Rol { rd, .. }
+            | RiscVInstruction::Ror { rd, .. }
+            | RiscVInstruction::Bclr { rd, .. }
+            | RiscVInstruction::Bext { rd, .. }
+            | RiscVInstruction::Binv { rd, .. }
+            | RiscVInstruction::Bset { rd, .. }
+            | RiscVInstruction::Clz { rd, .. }
+            | RiscVInstruction::Ctz { rd, .. }
+            | RiscVInstruction::Cpop { rd, .. }
+            | RiscVInstruction::SextB { rd, .. }
+            | RiscVInstruction::SextH { rd, .. }
+            | RiscVInstruction::ZextH { rd, .. }
+            | RiscVInstruction::OrcB { rd, .. }
+            | RiscVInstruction::Rev8 { rd, .. }
+            | RiscVInstruction::Rori { rd, .. }
+            | RiscVInstruction::Bclri { rd, .. }
+            | RiscVInstruction::Bexti { rd, .. }
+            | RiscVInstruction::Binvi { rd, .. }
+            | RiscVInstruction::Bseti { rd, .. }
+            | RiscVInstruction::Sha256sig0 { rd, .. }
+            | RiscVInstruction::Sha256sig1 { rd, .. }
+            | RiscVInstruction::Sha256sum0 { rd, .. }
+            | RiscVInstruction::Sha256sum1 { rd, .. }
+            | RiscVInstruction::Sha512sum0r { rd, .. }
+            | RiscVInstruction::Sha512sum1r { rd, .. }
+            | RiscVInstruction::Sha512sig0l { rd, .. }
+            | RiscVInstruction::Sha512sig0h { rd, .. }
+            | RiscVInstruction::Sha512sig1l { rd, .. }
+            | RiscVInstruction::Sha512sig1h { rd, .. }
+            | RiscVInstruction::Csrrw { rd, .. }
+            | RiscVInstruction::Csrrs { rd, .. }
+            | RiscVInstruction::Csrrc { rd, .. }
+            | RiscVInstruction::Csrrwi { rd, .. }
+            | RiscVInstruction::Csrrsi { rd, .. }
+            | RiscVInstruction::Csrrci { rd, .. }
+            | RiscVInstruction::LrW { rd, .. }
+            | RiscVInstruction::ScW { rd, .. }
+            | RiscVInstruction::AmoswapW { rd, .. }
+            | RiscVInstruction::AmoaddW { rd, .. }
+            | RiscVInstruction::AmoxorW { rd, .. }
+            | RiscVInstruction::AmoandW { rd, .. }
+            | RiscVInstruction::AmoorW { rd, .. }
+            | RiscVInstruction::AmominW { rd, .. }
+            | RiscVInstruction::AmomaxW { rd, .. }
+            | RiscVInstruction::AmominuW { rd, .. }
+            | RiscVInstruction::AmomaxuW { rd, .. }
+            | RiscVInstruction::FeqS { rd, .. }
+            | RiscVInstruction::FltS { rd, .. }
+            | RiscVInstruction::FleS { rd, .. }
+            | RiscVInstruction::FcvtWS { rd, .. }
+            | RiscVInstruction::FcvtWuS { rd, .. }
+            | RiscVInstruction::FmvXW { rd, .. }
+            | RiscVInstruction::FclassS { rd, .. }
+            | RiscVInstruction::FeqD { rd, .. }
+            | RiscVInstruction::FltD { rd, .. }
+            | RiscVInstruction::FleD { rd, .. }
+            | RiscVInstruction::FcvtWD { rd, .. }
+            | RiscVInstruction::FcvtWuD { rd, .. }
+            | RiscVInstruction::FclassD { rd, .. } => rd,
+            RiscVInstruction::Custom {
+                operands: CustomOperands::R { rd, .. } | CustomOperands::I { rd, .. },
+                ..
+            } => rd,
+            RiscVInstruction::Sb { .. }
+            | RiscVInstruction::Sh { .. }
+            | RiscVInstruction::Sw { .. }
+            | RiscVInstruction::Sd { .. }
+            | RiscVInstruction::Beq { .. }
+            | RiscVInstruction::Bne { .. }
+            | RiscVInstruction::Blt { .. }
+            | RiscVInstruction::Bge { .. }
+            | RiscVInstruction::Bltu { .. }
+            | RiscVInstruction::Bgeu { .. }
+            | RiscVInstruction::Ecall
+            | RiscVInstruction::Ebreak
+            | RiscVInstruction::Fence { .. }
+            | RiscVInstruction::FenceTso
+            | RiscVInstruction::FenceI
+            | RiscVInstruction::Flw { .. }
+            | RiscVInstruction::Fsw { .. }
+            | RiscVInstruction::Fld { .. }
+            | RiscVInstruction::Fsd { .. }
+            | RiscVInstruction::FmaddS { .. }
+            | RiscVInstruction::FmsubS { .. }
+            | RiscVInstruction::FnmsubS { .. }
+            | RiscVInstruction::FnmaddS { .. }
+            | RiscVInstruction::FaddS { .. }
+            | RiscVInstruction::FsubS { .. }
+            | RiscVInstruction::FmulS { .. }
+            | RiscVInstruction::FdivS { .. }
+            | RiscVInstruction::FsqrtS { .. }
+            | RiscVInstruction::FsgnjS { .. }
+            | RiscVInstruction::FsgnjnS { .. }
+            | RiscVInstruction::FsgnjxS { .. }
+            | RiscVInstruction::FminS { .. }
+            | RiscVInstruction::FmaxS { .. }
+            | RiscVInstruction::FcvtSW { .. }
+            | RiscVInstruction::FcvtSWu { .. }
+            | RiscVInstruction::FmvWX { .. }
+            | RiscVInstruction::FmaddD { .. }
+            | RiscVInstruction::FmsubD { .. }
+            | RiscVInstruction::FnmsubD { .. }
+            | RiscVInstruction::FnmaddD { .. }
+            | RiscVInstruction::FaddD { .. }
+            | RiscVInstruction::FsubD { .. }
+            | RiscVInstruction::FmulD { .. }
+            | RiscVInstruction::FdivD { .. }
+            | RiscVInstruction::FsqrtD { .. }
+            | RiscVInstruction::FsgnjD { .. }
+            | RiscVInstruction::FsgnjnD { .. }
+            | RiscVInstruction::FsgnjxD { .. }
+            | RiscVInstruction::FminD { .. }
+            | RiscVInstruction::FmaxD { .. }
+            | RiscVInstruction::FcvtDW { .. }
+            | RiscVInstruction::FcvtDWu { .. }
+            | RiscVInstruction::FcvtSD { .. }
+            | RiscVInstruction::FcvtDS { .. } => return None,
+        };
+
+        (rd != Register::ZERO).then_some(rd)
+    }
+
+    /// Returns the floating-point registers this instruction reads, in operand order.
+    ///
+    /// Unlike `x0`, `f0` is an ordinary register and is always reported. A register
+    /// named by several source operands is reported once for each.
+    pub fn float_reads(&self) -> impl Iterator<Item = FloatRegister> {
+        let sources = match *self {
+            RiscVInstruction::FmaddS { rs1, rs2, rs3, .. }
+            | RiscVInstruction::FmsubS { rs1, rs2, rs3, .. }
+            | RiscVInstruction::FnmsubS { rs1, rs2, rs3, .. }
+            | RiscVInstruction::FnmaddS { rs1, rs2, rs3, .. }
+            | RiscVInstruction::FmaddD { rs1, rs2, rs3, .. }
+            | RiscVInstruction::FmsubD { rs1, rs2, rs3, .. }
+            | RiscVInstruction::FnmsubD { rs1, rs2, rs3, .. }
+            | RiscVInstruction::FnmaddD { rs1, rs2, rs3, .. } => [Some(rs1), Some(rs2), Some(rs3)],
+            RiscVInstruction::FaddS { rs1, rs2, .. }
+            | RiscVInstruction::FsubS { rs1, rs2, .. }
+            | RiscVInstruction::FmulS { rs1, rs2, .. }
+            | RiscVInstruction::FdivS { rs1, rs2, .. }
+            | RiscVInstruction::FsgnjS { rs1, rs2, .. }
+            | RiscVInstruction::FsgnjnS { rs1, rs2, .. }
+            | RiscVInstruction::FsgnjxS { rs1, rs2, .. }
+            | RiscVInstruction::FminS { rs1, rs2, .. }
+            | RiscVInstruction::FmaxS { rs1, rs2, .. }
+            | RiscVInstruction::FeqS { rs1, rs2, .. }
+            | RiscVInstruction::FltS { rs1, rs2, .. }
+            | RiscVInstruction::FleS { rs1, rs2, .. }
+            | RiscVInstruction::FaddD { rs1, rs2, .. }
+            | RiscVInstruction::FsubD { rs1, rs2, .. }
+            | RiscVInstruction::FmulD { rs1, rs2, .. }
+            | RiscVInstruction::FdivD { rs1, rs2, .. }
+            | RiscVInstruction::FsgnjD { rs1, rs2, .. }
+            | RiscVInstruction::FsgnjnD { rs1, rs2, .. }
+            | RiscVInstruction::FsgnjxD { rs1, rs2, .. }
+            | RiscVInstruction::FminD { rs1, rs2, .. }
+            | RiscVInstruction::FmaxD { rs1, rs2, .. }
+            | RiscVInstruction::FeqD { rs1, rs2, .. }
+            | RiscVInstruction::FltD { rs1, rs2, .. }
+            | RiscVInstruction::FleD { rs1, rs2, .. } => [Some(rs1), Some(rs2), None],
+            RiscVInstruction::Fsw { rs2, .. } | RiscVInstruction::Fsd { rs2, .. } => {
+                [Some(rs2), None, None]
+            }
+            RiscVInstruction::FsqrtS { rs1, .. }
+            | RiscVInstruction::FcvtWS { rs1, .. }
+            | RiscVInstruction::FcvtWuS { rs1, .. }
+            | RiscVInstruction::FmvXW { rs1, .. }
+            | RiscVInstruction::FclassS { rs1, .. }
+            | RiscVInstruction::FsqrtD { rs1, .. }
+            | RiscVInstruction::FcvtWD { rs1, .. }
+            | RiscVInstruction::FcvtWuD { rs1, .. }
+            | RiscVInstruction::FclassD { rs1, .. }
+            | RiscVInstruction::FcvtSD { rs1, .. }
+            | RiscVInstruction::FcvtDS { rs1, .. } => [Some(rs1), None, None],
+            _ => [None, None, None],
+        };
+
+        sources.into_iter().flatten()
+    }
+
+    /// Returns the floating-point register this instruction writes, if any.
+    pub fn float_writes(&self) -> Option<FloatRegister> {
+        match *self {
+            RiscVInstruction::Flw { rd, .. }
+            | RiscVInstruction::Fld { rd, .. }
+            | RiscVInstruction::FmaddS { rd, .. }
+            | RiscVInstruction::FmsubS { rd, .. }
+            | RiscVInstruction::FnmsubS { rd, .. }
+            | RiscVInstruction::FnmaddS { rd, .. }
+            | RiscVInstruction::FaddS { rd, .. }
+            | RiscVInstruction::FsubS { rd, .. }
+            | RiscVInstruction::FmulS { rd, .. }
+            | RiscVInstruction::FdivS { rd, .. }
+            | RiscVInstruction::FsqrtS { rd, .. }
+            | RiscVInstruction::FsgnjS { rd, .. }
+            | RiscVInstruction::FsgnjnS { rd, .. }
+            | RiscVInstruction::FsgnjxS { rd, .. }
+            | RiscVInstruction::FminS { rd, .. }
+            | RiscVInstruction::FmaxS { rd, .. }
+            | RiscVInstruction::FcvtSW { rd, .. }
+            | RiscVInstruction::FcvtSWu { rd, .. }
+            | RiscVInstruction::FmvWX { rd, .. }
+            | RiscVInstruction::FmaddD { rd, .. }
+            | RiscVInstruction::FmsubD { rd, .. }
+            | RiscVInstruction::FnmsubD { rd, .. }
+            | RiscVInstruction::FnmaddD { rd, .. }
+            | RiscVInstruction::FaddD { rd, .. }
+            | RiscVInstruction::FsubD { rd, .. }
+            | RiscVInstruction::FmulD { rd, .. }
+            | RiscVInstruction::FdivD { rd, .. }
+            | RiscVInstruction::FsqrtD { rd, .. }
+            | RiscVInstruction::FsgnjD { rd, .. }
+            | RiscVInstruction::FsgnjnD { rd, .. }
+            | RiscVInstruction::FsgnjxD { rd, .. }
+            | RiscVInstruction::FminD { rd, .. }
+            | RiscVInstruction::FmaxD { rd, .. }
+            | RiscVInstruction::FcvtDW { rd, .. }
+            | RiscVInstruction::FcvtDWu { rd, .. }
+            | RiscVInstruction::FcvtSD { rd, .. }
+            | RiscVInstruction::FcvtDS { rd, .. } => Some(rd),
+            _ => None,
+        }
+    }
+
+    /// Returns how this instruction affects the flow of control.
+    pub fn control_flow(&self) -> ControlFlow {
+        match *self {
+            RiscVInstruction::Beq { .. }
+            | RiscVInstruction::Bne { .. }
+            | RiscVInstruction::Blt { .. }
+            | RiscVInstruction::Bge { .. }
+            | RiscVInstruction::Bltu { .. }
+            | RiscVInstruction::Bgeu { .. } => ControlFlow::Branch,
+            RiscVInstruction::Jal { rd, .. } if is_link(rd) => ControlFlow::Call,
+            RiscVInstruction::Jal { .. } => ControlFlow::Jump,
+            RiscVInstruction::Jalr { rd, .. } if is_link(rd) => ControlFlow::IndirectCall,
+            RiscVInstruction::Jalr {
+                rd: Register::ZERO,
+                rs1,
+                ..
+            } if is_link(rs1) => ControlFlow::Return,
+            RiscVInstruction::Jalr { .. } => ControlFlow::IndirectJump,
+            RiscVInstruction::Ecall | RiscVInstruction::Ebreak => ControlFlow::Trap,
+            _ => ControlFlow::Sequential,
+        }
+    }
+
+    /// Returns the memory access made by this instruction, or `None` if it does
+    /// not access memory.
+    ///
+    /// The address of every access is `rs1 + imm`, or `rs1` for the atomic
+    /// instructions, which have no immediate.
+    pub fn memory_access(&self) -> Option<MemoryAccess> {
+        match self {
+            RiscVInstruction::Lb { .. } => MemoryAccess::load(1, true),
+            RiscVInstruction::Lh { .. } => MemoryAccess::load(2, true),
+            RiscVInstruction::Lw { .. } => MemoryAccess::load(4, true),
+            RiscVInstruction::Lbu { .. } => MemoryAccess::load(1, false),
+            RiscVInstruction::Lhu { .. } => MemoryAccess::load(2, false),
+            RiscVInstruction::Sb { .. } => MemoryAccess::store(1),
+            RiscVInstruction::Sh { .. } => MemoryAccess::store(2),
+            RiscVInstruction::Sw { .. } => MemoryAccess::store(4),
+            RiscVInstruction::Ld { .. } => MemoryAccess::load(8, false),
+            RiscVInstruction::Lwu { .. } => MemoryAccess::load(4, false),
+            RiscVInstruction::Sd { .. } => MemoryAccess::store(8),
+            RiscVInstruction::LrW { .. } => MemoryAccess::load(4, false),
+            RiscVInstruction::ScW { .. } => MemoryAccess::store(4),
+            RiscVInstruction::Flw { .. } => MemoryAccess::load(4, false),
+            RiscVInstruction::Fld { .. } => MemoryAccess::load(8, false),
+            RiscVInstruction::Fsw { .. } => MemoryAccess::store(4),
+            RiscVInstruction::Fsd { .. } => MemoryAccess::store(8),
+            RiscVInstruction::AmoswapW { .. }
+            | RiscVInstruction::AmoaddW { .. }
+            | RiscVInstruction::AmoxorW { .. }
+            | RiscVInstruction::AmoandW { .. }
+            | RiscVInstruction::AmoorW { .. }
+            | RiscVInstruction::AmominW { .. }
+            | RiscVInstruction::AmomaxW { .. }
+            | RiscVInstruction::AmominuW { .. }
+            | RiscVInstruction::AmomaxuW { .. } => MemoryAccess::read_modify_write(4),
+            _ => None,
+        }
+    }
+}
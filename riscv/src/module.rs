@@ -1,3 +1,5 @@
+use crate::profile::Profile;
+use crate::program::{NO_ENTRY, Program, Translation};
 use crate::{engine::Engine, error::Error};
 use clear_cache::clear_cache;
 use libc::{
@@ -11,8 +13,11 @@ pub struct Module {
     pub(crate) engine: Rc<Engine>,
     pub(crate) native_code_addr: *mut c_void,
     pub(crate) native_code_size: usize,
-    _memory_ptr: Box<*mut u8>,
-    _gas: Box<u32>,
+    /// The RISC-V code loaded with `set_riscv_code`, if any.
+    pub(crate) program: Option<Program>,
+    /// The offset of the native code of every instruction of `program`, or empty if
+    /// `program` is interpreted.
+    pub(crate) native_offsets: Vec<u32>,
 }
 
 impl Module {
@@ -22,9 +27,6 @@ impl Module {
     ///
     /// - `Error::MemoryAllocationFailed` if the memory allocation fails.
     pub fn new(engine: Rc<Engine>) -> Result<Box<Self>, Error> {
-        #[cfg(not(target_arch = "aarch64"))]
-        compile_error!("This code only supports aarch64 targets.");
-
         let native_code_addr: *mut c_void;
 
         unsafe {
@@ -56,18 +58,50 @@ impl Module {
             engine,
             native_code_addr,
             native_code_size: 0,
-            _memory_ptr: Box::new(std::ptr::null_mut()),
-            _gas: Box::new(0),
+            program: None,
+            native_offsets: vec![],
         }))
     }
 
     /// Loads RISC-V executable code into the module.
     ///
+    /// On AArch64 and x86-64 hosts, the code is translated to native code. Guest
+    /// registers, gas and the base and size of instance memory live in a structure
+    /// that native code reaches through a pinned host register, and instructions that
+    /// native code does not implement, such as `ecall`, exit to the interpreter one at
+    /// a time. On other hosts, the whole program runs on the interpreter.
+    ///
     /// # Errors
     ///
-    /// - `Error::InvalidInstruction` if the code is invalid.
-    pub fn set_riscv_code(&mut self, _code: &[u8]) -> Result<(), Error> {
-        unimplemented!();
+    /// - `Error::InvalidCodeSize` if the code, or the native code it translates to, is
+    ///   too large.
+    /// - `Error::InvalidInstruction` with the offset and reason of the first instruction
+    ///   that cannot be decoded, including instructions of extensions that are not
+    ///   enabled in the engine configuration, custom instructions that are not
+    ///   registered on it, and instructions naming registers outside of its profile.
+    /// - `Error::MemoryProtectionFailed` if the memory protection fails.
+    pub fn set_riscv_code(&mut self, code: &[u8]) -> Result<(), Error> {
+        let config = self.engine.config();
+        if code.len() > config.max_code_size {
+            return Err(Error::InvalidCodeSize);
+        }
+
+        let program = Program::decode(code, config)?;
+        let native_offsets = match translate(&program, config.profile)? {
+            Some(translation) => {
+                self.set_native_code(&translation.code)?;
+                translation.offsets
+            }
+            None => {
+                self.native_code_size = 0;
+                vec![]
+            }
+        };
+
+        self.program = Some(program);
+        self.native_offsets = native_offsets;
+
+        Ok(())
     }
 
     /// Loads pre-compiled native code into the module.
@@ -116,10 +150,23 @@ impl Module {
         }
 
         self.native_code_size = code.len();
+        self.program = None;
+        self.native_offsets = vec![];
 
         Ok(())
     }
 
+    /// Returns the offset of the native code of the instruction at `pc`, or `None` if
+    /// no instruction starts there.
+    pub(crate) fn native_offset(&self, pc: u64) -> Option<usize> {
+        if pc % 2 != 0 {
+            return None;
+        }
+
+        let offset = *self.native_offsets.get(usize::try_from(pc / 2).ok()?)?;
+        (offset != NO_ENTRY).then_some(offset as usize)
+    }
+
     /// Returns a slice to the native (JIT-compiled) code.
     pub fn native_code(&self) -> &[u8] {
         unsafe {
@@ -128,6 +175,24 @@ impl Module {
     }
 }
 
+/// Translates `program` to native code for the host.
+#[cfg(target_arch = "aarch64")]
+fn translate(program: &Program, profile: Profile) -> Result<Option<Translation>, Error> {
+    crate::aarch64::translate(program, profile).map(Some)
+}
+
+/// Translates `program` to native code for the host.
+#[cfg(target_arch = "x86_64")]
+fn translate(program: &Program, profile: Profile) -> Result<Option<Translation>, Error> {
+    crate::x86_64::translate(program, profile).map(Some)
+}
+
+/// Returns `None`: the host has no native backend, and programs are interpreted.
+#[cfg(not(any(target_arch = "aarch64", target_arch = "x86_64")))]
+fn translate(_program: &Program, _profile: Profile) -> Result<Option<Translation>, Error> {
+    Ok(None)
+}
+
 impl Drop for Module {
     fn drop(&mut self) {
         unsafe {
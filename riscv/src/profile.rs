@@ -0,0 +1,52 @@
+use crate::register::Register;
+use std::fmt;
+
+/// The number of registers of the RV32E base.
+const EMBEDDED_REGISTER_COUNT: u8 = 16;
+
+/// The base integer instruction set that an engine runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Profile {
+    /// The RV32I base with 32 registers, `x0` through `x31`.
+    #[default]
+    Rv32i,
+    /// The RV32E embedded base with 16 registers, `x0` through `x15`.
+    ///
+    /// Instructions naming `x16` through `x31` are rejected when they are decoded.
+    Rv32e,
+    /// The RV64I base with 32 64-bit registers, `x0` through `x31`.
+    ///
+    /// Adds the doubleword loads and stores and the word arithmetic instructions,
+    /// such as `ld` and `addw`. Addresses are 64 bits wide, but guest memory is still
+    /// limited to `Config::max_instance_memory`.
+    Rv64i,
+}
+
+impl Profile {
+    /// Whether `register` is part of the register file of this profile.
+    pub const fn contains(self, register: Register) -> bool {
+        match self {
+            Profile::Rv32i => true,
+            Profile::Rv32e => register.number() < EMBEDDED_REGISTER_COUNT,
+            Profile::Rv64i => true,
+        }
+    }
+
+    /// The width of the registers of this profile in bits, 32 or 64.
+    pub const fn xlen(self) -> u32 {
+        match self {
+            Profile::Rv32i | Profile::Rv32e => 32,
+            Profile::Rv64i => 64,
+        }
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Profile::Rv32i => write!(f, "RV32I"),
+            Profile::Rv32e => write!(f, "RV32E"),
+            Profile::Rv64i => write!(f, "RV64I"),
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use crate::config::Config;
+use crate::error::Error;
+use crate::instruction::RiscVInstruction;
+use crate::stream::InstructionStream;
+
+/// The native offset of a halfword at which no instruction starts.
+pub(crate) const NO_ENTRY: u32 = u32::MAX;
+
+/// RISC-V code decoded for execution.
+pub(crate) struct Program {
+    /// The instruction starting at every even offset, indexed by offset / 2, along
+    /// with its length in bytes. The second half of a 32-bit instruction is `None`.
+    slots: Vec<Option<(RiscVInstruction, u8)>>,
+}
+
+impl Program {
+    /// Decodes `code` with the profile, extensions and custom instructions of `config`.
+    ///
+    /// # Errors
+    ///
+    /// - `Error::InvalidInstruction` with the offset and reason of the first instruction
+    ///   that cannot be decoded.
+    pub(crate) fn decode(code: &[u8], config: &Config) -> Result<Program, Error> {
+        let mut slots = vec![None; code.len() / 2];
+        let stream =
+            InstructionStream::with_custom(code, config.profile, config.extensions, &config.custom);
+
+        for decoded in stream {
+            match decoded.instruction {
+                Ok(instruction) => {
+                    slots[decoded.offset / 2] = Some((instruction, decoded.length as u8));
+                }
+                Err(error) => {
+                    return Err(Error::InvalidInstruction {
+                        offset: decoded.offset,
+                        error,
+                    });
+                }
+            }
+        }
+
+        Ok(Program { slots })
+    }
+
+    /// Returns the instruction starting at `pc` and its length in bytes, or `None` if
+    /// no instruction starts there.
+    pub(crate) fn get(&self, pc: u64) -> Option<(RiscVInstruction, u8)> {
+        if pc % 2 != 0 {
+            return None;
+        }
+
+        *self.slots.get(usize::try_from(pc / 2).ok()?)?
+    }
+}
+
+/// The accessors the native backends translate a program with.
+#[cfg(any(target_arch = "aarch64", target_arch = "x86_64", test))]
+impl Program {
+    /// Returns the length of the code in bytes.
+    pub(crate) fn len(&self) -> u64 {
+        self.slots.len() as u64 * 2
+    }
+
+    /// Returns every instruction in order, with its offset and length in bytes.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (u64, RiscVInstruction, u8)> {
+        self.slots.iter().enumerate().filter_map(|(index, slot)| {
+            slot.map(|(instruction, length)| (index as u64 * 2, instruction, length))
+        })
+    }
+}
+
+/// Native code translated from a [`Program`].
+pub(crate) struct Translation {
+    /// The machine code, starting with the [`Entry`](crate::hart::Entry) point.
+    pub(crate) code: Vec<u8>,
+    /// The offset within `code` of the native code of every instruction, indexed by
+    /// its address / 2, or `NO_ENTRY` if no instruction starts at the address.
+    pub(crate) offsets: Vec<u32>,
+}
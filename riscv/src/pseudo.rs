@@ -0,0 +1,562 @@
+use crate::csr::{self, CsrName};
+use crate::error::Error;
+use crate::instruction::RiscVInstruction;
+use crate::register::{Register, Syntax};
+use std::fmt;
+
+/// A standard assembler pseudo-instruction standing in for one or two real instructions.
+///
+/// Offsets are relative to the address of the first instruction, the same as the
+/// immediates of branches and jumps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pseudo {
+    /// `addi x0, x0, 0`
+    Nop,
+    /// `addi rd, x0, imm`, or `lui rd, hi` followed by `addi rd, rd, lo`
+    Li { rd: Register, imm: i32 },
+    /// `addi rd, rs, 0`
+    Mv { rd: Register, rs: Register },
+    /// `xori rd, rs, -1`
+    Not { rd: Register, rs: Register },
+    /// `sub rd, x0, rs`
+    Neg { rd: Register, rs: Register },
+    /// `sltiu rd, rs, 1`
+    Seqz { rd: Register, rs: Register },
+    /// `sltu rd, x0, rs`
+    Snez { rd: Register, rs: Register },
+    /// `slt rd, rs, x0`
+    Sltz { rd: Register, rs: Register },
+    /// `slt rd, x0, rs`
+    Sgtz { rd: Register, rs: Register },
+    /// `beq rs, x0, offset`
+    Beqz { rs: Register, offset: i32 },
+    /// `bne rs, x0, offset`
+    Bnez { rs: Register, offset: i32 },
+    /// `bge x0, rs, offset`
+    Blez { rs: Register, offset: i32 },
+    /// `bge rs, x0, offset`
+    Bgez { rs: Register, offset: i32 },
+    /// `blt rs, x0, offset`
+    Bltz { rs: Register, offset: i32 },
+    /// `blt x0, rs, offset`
+    Bgtz { rs: Register, offset: i32 },
+    /// `jal x0, offset`
+    J { offset: i32 },
+    /// `jal ra, offset`
+    Jal { offset: i32 },
+    /// `jalr x0, rs, 0`
+    Jr { rs: Register },
+    /// `jalr ra, rs, 0`
+    Jalr { rs: Register },
+    /// `jalr x0, ra, 0`
+    Ret,
+    /// `auipc ra, hi` followed by `jalr ra, ra, lo`
+    Call { offset: i32 },
+    /// `auipc t1, hi` followed by `jalr x0, t1, lo`
+    Tail { offset: i32 },
+    /// `csrrs rd, cycle, x0`
+    Rdcycle { rd: Register },
+    /// `csrrs rd, cycleh, x0`
+    Rdcycleh { rd: Register },
+    /// `csrrs rd, time, x0`
+    Rdtime { rd: Register },
+    /// `csrrs rd, timeh, x0`
+    Rdtimeh { rd: Register },
+    /// `csrrs rd, instret, x0`
+    Rdinstret { rd: Register },
+    /// `csrrs rd, instreth, x0`
+    Rdinstreth { rd: Register },
+    /// `csrrs rd, csr, x0`
+    Csrr { rd: Register, csr: u16 },
+    /// `csrrw x0, csr, rs`
+    Csrw { csr: u16, rs: Register },
+}
+
+/// Formats a pseudo-instruction with registers named in a chosen [`Syntax`].
+///
+/// Created by [`Pseudo::display`].
+pub struct PseudoDisplay<'a> {
+    pseudo: &'a Pseudo,
+    syntax: Syntax,
+}
+
+impl fmt::Display for PseudoDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let r = |register: Register| register.name(self.syntax);
+
+        match self.pseudo {
+            Pseudo::Nop => write!(f, "nop"),
+            Pseudo::Li { rd, imm } => write!(f, "li {}, {}", r(*rd), imm),
+            Pseudo::Mv { rd, rs } => write!(f, "mv {}, {}", r(*rd), r(*rs)),
+            Pseudo::Not { rd, rs } => write!(f, "not {}, {}", r(*rd), r(*rs)),
+            Pseudo::Neg { rd, rs } => write!(f, "neg {}, {}", r(*rd), r(*rs)),
+            Pseudo::Seqz { rd, rs } => write!(f, "seqz {}, {}", r(*rd), r(*rs)),
+            Pseudo::Snez { rd, rs } => write!(f, "snez {}, {}", r(*rd), r(*rs)),
+            Pseudo::Sltz { rd, rs } => write!(f, "sltz {}, {}", r(*rd), r(*rs)),
+            Pseudo::Sgtz { rd, rs } => write!(f, "sgtz {}, {}", r(*rd), r(*rs)),
+            Pseudo::Beqz { rs, offset } => write!(f, "beqz {}, {}", r(*rs), offset),
+            Pseudo::Bnez { rs, offset } => write!(f, "bnez {}, {}", r(*rs), offset),
+            Pseudo::Blez { rs, offset } => write!(f, "blez {}, {}", r(*rs), offset),
+            Pseudo::Bgez { rs, offset } => write!(f, "bgez {}, {}", r(*rs), offset),
+            Pseudo::Bltz { rs, offset } => write!(f, "bltz {}, {}", r(*rs), offset),
+            Pseudo::Bgtz { rs, offset } => write!(f, "bgtz {}, {}", r(*rs), offset),
+            Pseudo::J { offset } => write!(f, "j {}", offset),
+            Pseudo::Jal { offset } => write!(f, "jal {}", offset),
+            Pseudo::Jr { rs } => write!(f, "jr {}", r(*rs)),
+            Pseudo::Jalr { rs } => write!(f, "jalr {}", r(*rs)),
+            Pseudo::Ret => write!(f, "ret"),
+            Pseudo::Call { offset } => write!(f, "call {}", offset),
+            Pseudo::Tail { offset } => write!(f, "tail {}", offset),
+            Pseudo::Rdcycle { rd } => write!(f, "rdcycle {}", r(*rd)),
+            Pseudo::Rdcycleh { rd } => write!(f, "rdcycleh {}", r(*rd)),
+            Pseudo::Rdtime { rd } => write!(f, "rdtime {}", r(*rd)),
+            Pseudo::Rdtimeh { rd } => write!(f, "rdtimeh {}", r(*rd)),
+            Pseudo::Rdinstret { rd } => write!(f, "rdinstret {}", r(*rd)),
+            Pseudo::Rdinstreth { rd } => write!(f, "rdinstreth {}", r(*rd)),
+            Pseudo::Csrr { rd, csr } => write!(f, "csrr {}, {}", r(*rd), CsrName(*csr)),
+            Pseudo::Csrw { csr, rs } => write!(f, "csrw {}, {}", CsrName(*csr), r(*rs)),
+        }
+    }
+}
+
+impl fmt::Display for Pseudo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display(Syntax::Numeric).fmt(f)
+    }
+}
+
+impl Pseudo {
+    /// Recognizes the pseudo-instruction at the start of `instructions`.
+    ///
+    /// Two-instruction idioms such as `lui`+`addi` and `auipc`+`jalr` are preferred
+    /// over single instructions. Returns `None` if the first instruction is best shown
+    /// as itself.
+    pub fn recognize(instructions: &[RiscVInstruction]) -> Option<Pseudo> {
+        match instructions {
+            [first, second, ..] => {
+                Pseudo::from_pair(first, second).or_else(|| Pseudo::from_single(first))
+            }
+            [first] => Pseudo::from_single(first),
+            [] => None,
+        }
+    }
+
+    /// Returns the number of real instructions the pseudo-instruction stands for.
+    pub fn instruction_count(&self) -> usize {
+        match self {
+            Pseudo::Call { .. } | Pseudo::Tail { .. } => 2,
+            // `li` is recognized from either a single `addi` or a `lui`+`addi` pair.
+            Pseudo::Li { imm, .. } if !(-2048..2048).contains(imm) => 2,
+            _ => 1,
+        }
+    }
+
+    /// Returns a value that formats this pseudo-instruction with registers named in `syntax`.
+    pub fn display(&self, syntax: Syntax) -> PseudoDisplay<'_> {
+        PseudoDisplay {
+            pseudo: self,
+            syntax,
+        }
+    }
+
+    /// Expands the pseudo-instruction into the real instructions it stands for.
+    ///
+    /// A `li` value outside of the 12-bit signed range always expands to a `lui`+`addi`
+    /// pair, even when its low 12 bits are zero, so the expansion is always
+    /// `instruction_count` instructions long and is recognized as the same pseudo-instruction.
+    ///
+    /// # Errors
+    ///
+    /// - `Error::InvalidImmediate` if a branch or jump offset does not fit its instruction.
+    pub fn expand(&self) -> Result<Vec<RiscVInstruction>, Error> {
+        const ZERO: Register = Register::ZERO;
+        const RA: Register = Register::RA;
+
+        let instructions = match *self {
+            Pseudo::Nop => vec![RiscVInstruction::Addi {
+                rd: ZERO,
+                rs1: ZERO,
+                imm: 0,
+            }],
+            Pseudo::Li { rd, imm } => match i16::try_from(imm) {
+                Ok(small) if (-2048..2048).contains(&small) => vec![RiscVInstruction::Addi {
+                    rd,
+                    rs1: ZERO,
+                    imm: small,
+                }],
+                _ => {
+                    let (hi, lo) = split_offset(imm);
+                    vec![
+                        RiscVInstruction::Lui { rd, imm: hi },
+                        RiscVInstruction::Addi {
+                            rd,
+                            rs1: rd,
+                            imm: lo,
+                        },
+                    ]
+                }
+            },
+            Pseudo::Mv { rd, rs } => vec![RiscVInstruction::Addi {
+                rd,
+                rs1: rs,
+                imm: 0,
+            }],
+            Pseudo::Not { rd, rs } => vec![RiscVInstruction::Xori {
+                rd,
+                rs1: rs,
+                imm: -1,
+            }],
+            Pseudo::Neg { rd, rs } => vec![RiscVInstruction::Sub {
+                rd,
+                rs1: ZERO,
+                rs2: rs,
+            }],
+            Pseudo::Seqz { rd, rs } => vec![RiscVInstruction::Sltiu {
+                rd,
+                rs1: rs,
+                imm: 1,
+            }],
+            Pseudo::Snez { rd, rs } => vec![RiscVInstruction::Sltu {
+                rd,
+                rs1: ZERO,
+                rs2: rs,
+            }],
+            Pseudo::Sltz { rd, rs } => vec![RiscVInstruction::Slt {
+                rd,
+                rs1: rs,
+                rs2: ZERO,
+            }],
+            Pseudo::Sgtz { rd, rs } => vec![RiscVInstruction::Slt {
+                rd,
+                rs1: ZERO,
+                rs2: rs,
+            }],
+            Pseudo::Beqz { rs, offset } => vec![RiscVInstruction::Beq {
+                rs1: rs,
+                rs2: ZERO,
+                imm: branch_offset(offset)?,
+            }],
+            Pseudo::Bnez { rs, offset } => vec![RiscVInstruction::Bne {
+                rs1: rs,
+                rs2: ZERO,
+                imm: branch_offset(offset)?,
+            }],
+            Pseudo::Blez { rs, offset } => vec![RiscVInstruction::Bge {
+                rs1: ZERO,
+                rs2: rs,
+                imm: branch_offset(offset)?,
+            }],
+            Pseudo::Bgez { rs, offset } => vec![RiscVInstruction::Bge {
+                rs1: rs,
+                rs2: ZERO,
+                imm: branch_offset(offset)?,
+            }],
+            Pseudo::Bltz { rs, offset } => vec![RiscVInstruction::Blt {
+                rs1: rs,
+                rs2: ZERO,
+                imm: branch_offset(offset)?,
+            }],
+            Pseudo::Bgtz { rs, offset } => vec![RiscVInstruction::Blt {
+                rs1: ZERO,
+                rs2: rs,
+                imm: branch_offset(offset)?,
+            }],
+            Pseudo::J { offset } => vec![RiscVInstruction::Jal {
+                rd: ZERO,
+                imm: jump_offset(offset)?,
+            }],
+            Pseudo::Jal { offset } => vec![RiscVInstruction::Jal {
+                rd: RA,
+                imm: jump_offset(offset)?,
+            }],
+            Pseudo::Jr { rs } => vec![RiscVInstruction::Jalr {
+                rd: ZERO,
+                rs1: rs,
+                imm: 0,
+            }],
+            Pseudo::Jalr { rs } => vec![RiscVInstruction::Jalr {
+                rd: RA,
+                rs1: rs,
+                imm: 0,
+            }],
+            Pseudo::Ret => vec![RiscVInstruction::Jalr {
+                rd: ZERO,
+                rs1: RA,
+                imm: 0,
+            }],
+            Pseudo::Call { offset } => far_jump(RA, RA, offset),
+            Pseudo::Tail { offset } => far_jump(Register::T1, ZERO, offset),
+            Pseudo::Rdcycle { rd } => read_csr(rd, csr::CYCLE),
+            Pseudo::Rdcycleh { rd } => read_csr(rd, csr::CYCLEH),
+            Pseudo::Rdtime { rd } => read_csr(rd, csr::TIME),
+            Pseudo::Rdtimeh { rd } => read_csr(rd, csr::TIMEH),
+            Pseudo::Rdinstret { rd } => read_csr(rd, csr::INSTRET),
+            Pseudo::Rdinstreth { rd } => read_csr(rd, csr::INSTRETH),
+            Pseudo::Csrr { rd, csr } => read_csr(rd, csr),
+            Pseudo::Csrw { csr, rs } => vec![RiscVInstruction::Csrrw {
+                rd: ZERO,
+                rs1: rs,
+                csr,
+            }],
+        };
+
+        Ok(instructions)
+    }
+
+    fn from_pair(first: &RiscVInstruction, second: &RiscVInstruction) -> Option<Pseudo> {
+        match (*first, *second) {
+            (
+                RiscVInstruction::Lui { rd, imm: hi },
+                RiscVInstruction::Addi {
+                    rd: addi_rd,
+                    rs1,
+                    imm: lo,
+                },
+            ) if rd == addi_rd && rd == rs1 && rd != Register::ZERO => {
+                let imm = hi.wrapping_add(lo as i32);
+                // A value that fits in 12 bits is a single `addi`, and would not round-trip.
+                if (-2048..2048).contains(&imm) {
+                    None
+                } else {
+                    Some(Pseudo::Li { rd, imm })
+                }
+            }
+            (
+                RiscVInstruction::Auipc {
+                    rd: Register::RA,
+                    imm: hi,
+                },
+                RiscVInstruction::Jalr {
+                    rd: Register::RA,
+                    rs1: Register::RA,
+                    imm: lo,
+                },
+            ) => Some(Pseudo::Call {
+                offset: hi.wrapping_add(lo as i32),
+            }),
+            (
+                RiscVInstruction::Auipc {
+                    rd: Register::T1,
+                    imm: hi,
+                },
+                RiscVInstruction::Jalr {
+                    rd: Register::ZERO,
+                    rs1: Register::T1,
+                    imm: lo,
+                },
+            ) => Some(Pseudo::Tail {
+                offset: hi.wrapping_add(lo as i32),
+            }),
+            _ => None,
+        }
+    }
+
+    fn from_single(instruction: &RiscVInstruction) -> Option<Pseudo> {
+        const ZERO: Register = Register::ZERO;
+        const RA: Register = Register::RA;
+
+        let pseudo = match *instruction {
+            RiscVInstruction::Addi {
+                rd: ZERO,
+                rs1: ZERO,
+                imm: 0,
+            } => Pseudo::Nop,
+            RiscVInstruction::Addi { rd, rs1: ZERO, imm } => Pseudo::Li {
+                rd,
+                imm: imm as i32,
+            },
+            RiscVInstruction::Addi { rd, rs1, imm: 0 } => Pseudo::Mv { rd, rs: rs1 },
+            RiscVInstruction::Xori { rd, rs1, imm: -1 } => Pseudo::Not { rd, rs: rs1 },
+            RiscVInstruction::Sub { rd, rs1: ZERO, rs2 } => Pseudo::Neg { rd, rs: rs2 },
+            RiscVInstruction::Sltiu { rd, rs1, imm: 1 } => Pseudo::Seqz { rd, rs: rs1 },
+            RiscVInstruction::Sltu { rd, rs1: ZERO, rs2 } => Pseudo::Snez { rd, rs: rs2 },
+            RiscVInstruction::Slt { rd, rs1, rs2: ZERO } => Pseudo::Sltz { rd, rs: rs1 },
+            RiscVInstruction::Slt { rd, rs1: ZERO, rs2 } => Pseudo::Sgtz { rd, rs: rs2 },
+            RiscVInstruction::Beq {
+                rs1,
+                rs2: ZERO,
+                imm,
+            } => Pseudo::Beqz {
+                rs: rs1,
+                offset: imm as i32,
+            },
+            RiscVInstruction::Bne {
+                rs1,
+                rs2: ZERO,
+                imm,
+            } => Pseudo::Bnez {
+                rs: rs1,
+                offset: imm as i32,
+            },
+            RiscVInstruction::Bge {
+                rs1: ZERO,
+                rs2,
+                imm,
+            } => Pseudo::Blez {
+                rs: rs2,
+                offset: imm as i32,
+            },
+            RiscVInstruction::Bge {
+                rs1,
+                rs2: ZERO,
+                imm,
+            } => Pseudo::Bgez {
+                rs: rs1,
+                offset: imm as i32,
+            },
+            RiscVInstruction::Blt {
+                rs1,
+                rs2: ZERO,
+                imm,
+            } => Pseudo::Bltz {
+                rs: rs1,
+                offset: imm as i32,
+            },
+            RiscVInstruction::Blt {
+                rs1: ZERO,
+                rs2,
+                imm,
+            } => Pseudo::Bgtz {
+                rs: rs2,
+                offset: imm as i32,
+            },
+            RiscVInstruction::Jal { rd: ZERO, imm } => Pseudo::J { offset: imm },
+            RiscVInstruction::Jal { rd: RA, imm } => Pseudo::Jal { offset: imm },
+            RiscVInstruction::Jalr {
+                rd: ZERO,
+                rs1: RA,
+                imm: 0,
+            } => Pseudo::Ret,
+            RiscVInstruction::Jalr {
+                rd: ZERO,
+                rs1,
+                imm: 0,
+            } => Pseudo::Jr { rs: rs1 },
+            RiscVInstruction::Jalr {
+                rd: RA,
+                rs1,
+                imm: 0,
+            } => Pseudo::Jalr { rs: rs1 },
+            RiscVInstruction::Csrrs { rd, rs1: ZERO, csr } => match csr {
+                csr::CYCLE => Pseudo::Rdcycle { rd },
+                csr::CYCLEH => Pseudo::Rdcycleh { rd },
+                csr::TIME => Pseudo::Rdtime { rd },
+                csr::TIMEH => Pseudo::Rdtimeh { rd },
+                csr::INSTRET => Pseudo::Rdinstret { rd },
+                csr::INSTRETH => Pseudo::Rdinstreth { rd },
+                _ => Pseudo::Csrr { rd, csr },
+            },
+            RiscVInstruction::Csrrw { rd: ZERO, rs1, csr } => Pseudo::Csrw { csr, rs: rs1 },
+            _ => return None,
+        };
+
+        Some(pseudo)
+    }
+}
+
+/// Reads a CSR without writing it.
+fn read_csr(rd: Register, csr: u16) -> Vec<RiscVInstruction> {
+    vec![RiscVInstruction::Csrrs {
+        rd,
+        rs1: Register::ZERO,
+        csr,
+    }]
+}
+
+/// Splits a 32-bit value into a `lui`/`auipc` immediate and a sign-extended 12-bit low part.
+///
+/// The high part is rounded so that `hi + lo` wraps around to `value` for every input.
+pub(crate) fn split_offset(value: i32) -> (i32, i16) {
+    let lo = ((value << 20) >> 20) as i16;
+    let hi = value.wrapping_sub(lo as i32);
+    (hi, lo)
+}
+
+/// Checks that `offset` is a valid branch offset.
+pub(crate) fn branch_offset(offset: i32) -> Result<i16, Error> {
+    if (-4096..=4094).contains(&offset) && offset % 2 == 0 {
+        Ok(offset as i16)
+    } else {
+        Err(Error::InvalidImmediate)
+    }
+}
+
+/// Checks that `offset` is a valid `jal` offset.
+pub(crate) fn jump_offset(offset: i32) -> Result<i32, Error> {
+    if (-0x100000..=0xffffe).contains(&offset) && offset % 2 == 0 {
+        Ok(offset)
+    } else {
+        Err(Error::InvalidImmediate)
+    }
+}
+
+/// The `auipc`+`jalr` pair of `call` and `tail`, reaching any 32-bit offset.
+fn far_jump(scratch: Register, rd: Register, offset: i32) -> Vec<RiscVInstruction> {
+    let (hi, lo) = split_offset(offset);
+    vec![
+        RiscVInstruction::Auipc {
+            rd: scratch,
+            imm: hi,
+        },
+        RiscVInstruction::Jalr {
+            rd,
+            rs1: scratch,
+            imm: lo,
+        },
+    ]
+}
+
+/// A line of pseudo-instruction aware disassembly.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrintedInstruction {
+    /// The index of the first instruction shown by this line.
+    pub index: usize,
+    /// The number of instructions shown by this line, either 1 or 2.
+    pub count: usize,
+    /// The formatted pseudo-instruction, or the instruction itself if it has no
+    /// pseudo-instruction form.
+    pub text: String,
+}
+
+/// An iterator formatting a sequence of instructions with standard pseudo-instructions.
+///
+/// This is an opt-in alternative to the `Display` implementation of
+/// [`RiscVInstruction`], which always prints the real instruction.
+pub struct PseudoPrinter<'a> {
+    instructions: &'a [RiscVInstruction],
+    syntax: Syntax,
+    index: usize,
+}
+
+impl<'a> PseudoPrinter<'a> {
+    /// Constructs a new `PseudoPrinter` naming registers in the given syntax.
+    pub fn new(instructions: &'a [RiscVInstruction], syntax: Syntax) -> Self {
+        Self {
+            instructions,
+            syntax,
+            index: 0,
+        }
+    }
+}
+
+impl Iterator for PseudoPrinter<'_> {
+    type Item = PrintedInstruction;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.index;
+        let remaining = &self.instructions[index..];
+        let first = remaining.first()?;
+
+        let (count, text) = match Pseudo::recognize(remaining) {
+            Some(pseudo) => (
+                pseudo.instruction_count(),
+                pseudo.display(self.syntax).to_string(),
+            ),
+            None => (1, first.display(self.syntax).to_string()),
+        };
+
+        self.index += count;
+
+        Some(PrintedInstruction { index, count, text })
+    }
+}
@@ -0,0 +1,316 @@
+use crate::error::Error;
+use std::fmt;
+
+/// The number of general-purpose registers.
+const REGISTER_COUNT: u8 = 32;
+
+/// Mask of a 5-bit register field.
+const REGISTER_MASK: u32 = 0x1f;
+
+const NUMERIC_NAMES: [&str; REGISTER_COUNT as usize] = [
+    "x0", "x1", "x2", "x3", "x4", "x5", "x6", "x7", "x8", "x9", "x10", "x11", "x12", "x13", "x14",
+    "x15", "x16", "x17", "x18", "x19", "x20", "x21", "x22", "x23", "x24", "x25", "x26", "x27",
+    "x28", "x29", "x30", "x31",
+];
+
+const ABI_NAMES: [&str; REGISTER_COUNT as usize] = [
+    "zero", "ra", "sp", "gp", "tp", "t0", "t1", "t2", "s0", "s1", "a0", "a1", "a2", "a3", "a4",
+    "a5", "a6", "a7", "s2", "s3", "s4", "s5", "s6", "s7", "s8", "s9", "s10", "s11", "t3", "t4",
+    "t5", "t6",
+];
+
+const FLOAT_NUMERIC_NAMES: [&str; REGISTER_COUNT as usize] = [
+    "f0", "f1", "f2", "f3", "f4", "f5", "f6", "f7", "f8", "f9", "f10", "f11", "f12", "f13", "f14",
+    "f15", "f16", "f17", "f18", "f19", "f20", "f21", "f22", "f23", "f24", "f25", "f26", "f27",
+    "f28", "f29", "f30", "f31",
+];
+
+const FLOAT_ABI_NAMES: [&str; REGISTER_COUNT as usize] = [
+    "ft0", "ft1", "ft2", "ft3", "ft4", "ft5", "ft6", "ft7", "fs0", "fs1", "fa0", "fa1", "fa2",
+    "fa3", "fa4", "fa5", "fa6", "fa7", "fs2", "fs3", "fs4", "fs5", "fs6", "fs7", "fs8", "fs9",
+    "fs10", "fs11", "ft8", "ft9", "ft10", "ft11",
+];
+
+/// How registers are named when an instruction is formatted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Syntax {
+    /// Architectural names, such as `x1` and `x10`.
+    #[default]
+    Numeric,
+    /// ABI names as printed by `objdump`, such as `ra` and `a0`.
+    Abi,
+}
+
+/// A general-purpose register, `x0` through `x31`.
+///
+/// A `Register` is always in range: it can only be created from one of the
+/// constants below, a checked conversion from `u8`, or by name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Register(u8);
+
+impl Register {
+    pub const X0: Register = Register(0);
+    pub const X1: Register = Register(1);
+    pub const X2: Register = Register(2);
+    pub const X3: Register = Register(3);
+    pub const X4: Register = Register(4);
+    pub const X5: Register = Register(5);
+    pub const X6: Register = Register(6);
+    pub const X7: Register = Register(7);
+    pub const X8: Register = Register(8);
+    pub const X9: Register = Register(9);
+    pub const X10: Register = Register(10);
+    pub const X11: Register = Register(11);
+    pub const X12: Register = Register(12);
+    pub const X13: Register = Register(13);
+    pub const X14: Register = Register(14);
+    pub const X15: Register = Register(15);
+    pub const X16: Register = Register(16);
+    pub const X17: Register = Register(17);
+    pub const X18: Register = Register(18);
+    pub const X19: Register = Register(19);
+    pub const X20: Register = Register(20);
+    pub const X21: Register = Register(21);
+    pub const X22: Register = Register(22);
+    pub const X23: Register = Register(23);
+    pub const X24: Register = Register(24);
+    pub const X25: Register = Register(25);
+    pub const X26: Register = Register(26);
+    pub const X27: Register = Register(27);
+    pub const X28: Register = Register(28);
+    pub const X29: Register = Register(29);
+    pub const X30: Register = Register(30);
+    pub const X31: Register = Register(31);
+
+    /// Hard-wired zero.
+    pub const ZERO: Register = Register::X0;
+    /// Return address.
+    pub const RA: Register = Register::X1;
+    /// Stack pointer.
+    pub const SP: Register = Register::X2;
+    /// Global pointer.
+    pub const GP: Register = Register::X3;
+    /// Thread pointer.
+    pub const TP: Register = Register::X4;
+    pub const T0: Register = Register::X5;
+    pub const T1: Register = Register::X6;
+    pub const T2: Register = Register::X7;
+    /// Saved register, also used as the frame pointer.
+    pub const S0: Register = Register::X8;
+    /// Frame pointer, an alias of `s0`.
+    pub const FP: Register = Register::X8;
+    pub const S1: Register = Register::X9;
+    pub const A0: Register = Register::X10;
+    pub const A1: Register = Register::X11;
+    pub const A2: Register = Register::X12;
+    pub const A3: Register = Register::X13;
+    pub const A4: Register = Register::X14;
+    pub const A5: Register = Register::X15;
+    pub const A6: Register = Register::X16;
+    pub const A7: Register = Register::X17;
+    pub const S2: Register = Register::X18;
+    pub const S3: Register = Register::X19;
+    pub const S4: Register = Register::X20;
+    pub const S5: Register = Register::X21;
+    pub const S6: Register = Register::X22;
+    pub const S7: Register = Register::X23;
+    pub const S8: Register = Register::X24;
+    pub const S9: Register = Register::X25;
+    pub const S10: Register = Register::X26;
+    pub const S11: Register = Register::X27;
+    pub const T3: Register = Register::X28;
+    pub const T4: Register = Register::X29;
+    pub const T5: Register = Register::X30;
+    pub const T6: Register = Register::X31;
+
+    /// Constructs the register with the given number, or `None` if it is greater than 31.
+    pub const fn new(number: u8) -> Option<Register> {
+        if number < REGISTER_COUNT {
+            Some(Register(number))
+        } else {
+            None
+        }
+    }
+
+    /// Constructs a register from a 5-bit instruction field, ignoring any higher bits.
+    pub(crate) const fn from_field(field: u32) -> Register {
+        Register((field & REGISTER_MASK) as u8)
+    }
+
+    /// Looks up a register by its numeric (`x10`) or ABI (`a0`, `fp`) name.
+    pub fn from_name(name: &str) -> Option<Register> {
+        if name == "fp" {
+            return Some(Register::FP);
+        }
+
+        NUMERIC_NAMES
+            .iter()
+            .position(|numeric| *numeric == name)
+            .or_else(|| ABI_NAMES.iter().position(|abi| *abi == name))
+            .map(|number| Register(number as u8))
+    }
+
+    /// Returns the register number, `0` through `31`.
+    pub const fn number(self) -> u8 {
+        self.0
+    }
+
+    /// Returns the ABI name of the register, such as `ra` or `a0`.
+    ///
+    /// `x8` is named `s0` rather than `fp`, matching `objdump`.
+    pub const fn abi_name(self) -> &'static str {
+        ABI_NAMES[self.0 as usize]
+    }
+
+    /// Returns the name of the register in the given syntax.
+    pub const fn name(self, syntax: Syntax) -> &'static str {
+        match syntax {
+            Syntax::Numeric => NUMERIC_NAMES[self.0 as usize],
+            Syntax::Abi => ABI_NAMES[self.0 as usize],
+        }
+    }
+}
+
+impl TryFrom<u8> for Register {
+    type Error = Error;
+
+    fn try_from(number: u8) -> Result<Self, Self::Error> {
+        Register::new(number).ok_or(Error::InvalidRegister)
+    }
+}
+
+impl From<Register> for u8 {
+    fn from(register: Register) -> Self {
+        register.0
+    }
+}
+
+impl fmt::Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name(Syntax::Numeric))
+    }
+}
+
+/// A floating-point register, `f0` through `f31`, of the F and D extensions.
+///
+/// Like [`Register`], a `FloatRegister` is always in range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FloatRegister(u8);
+
+impl FloatRegister {
+    pub const F0: FloatRegister = FloatRegister(0);
+    pub const F1: FloatRegister = FloatRegister(1);
+    pub const F2: FloatRegister = FloatRegister(2);
+    pub const F3: FloatRegister = FloatRegister(3);
+    pub const F4: FloatRegister = FloatRegister(4);
+    pub const F5: FloatRegister = FloatRegister(5);
+    pub const F6: FloatRegister = FloatRegister(6);
+    pub const F7: FloatRegister = FloatRegister(7);
+    pub const F8: FloatRegister = FloatRegister(8);
+    pub const F9: FloatRegister = FloatRegister(9);
+    pub const F10: FloatRegister = FloatRegister(10);
+    pub const F11: FloatRegister = FloatRegister(11);
+    pub const F12: FloatRegister = FloatRegister(12);
+    pub const F13: FloatRegister = FloatRegister(13);
+    pub const F14: FloatRegister = FloatRegister(14);
+    pub const F15: FloatRegister = FloatRegister(15);
+    pub const F16: FloatRegister = FloatRegister(16);
+    pub const F17: FloatRegister = FloatRegister(17);
+    pub const F18: FloatRegister = FloatRegister(18);
+    pub const F19: FloatRegister = FloatRegister(19);
+    pub const F20: FloatRegister = FloatRegister(20);
+    pub const F21: FloatRegister = FloatRegister(21);
+    pub const F22: FloatRegister = FloatRegister(22);
+    pub const F23: FloatRegister = FloatRegister(23);
+    pub const F24: FloatRegister = FloatRegister(24);
+    pub const F25: FloatRegister = FloatRegister(25);
+    pub const F26: FloatRegister = FloatRegister(26);
+    pub const F27: FloatRegister = FloatRegister(27);
+    pub const F28: FloatRegister = FloatRegister(28);
+    pub const F29: FloatRegister = FloatRegister(29);
+    pub const F30: FloatRegister = FloatRegister(30);
+    pub const F31: FloatRegister = FloatRegister(31);
+
+    pub const FT0: FloatRegister = FloatRegister::F0;
+    pub const FT1: FloatRegister = FloatRegister::F1;
+    pub const FT2: FloatRegister = FloatRegister::F2;
+    pub const FT3: FloatRegister = FloatRegister::F3;
+    pub const FT4: FloatRegister = FloatRegister::F4;
+    pub const FT5: FloatRegister = FloatRegister::F5;
+    pub const FT6: FloatRegister = FloatRegister::F6;
+    pub const FT7: FloatRegister = FloatRegister::F7;
+    pub const FS0: FloatRegister = FloatRegister::F8;
+    pub const FS1: FloatRegister = FloatRegister::F9;
+    pub const FA0: FloatRegister = FloatRegister::F10;
+    pub const FA1: FloatRegister = FloatRegister::F11;
+    pub const FA2: FloatRegister = FloatRegister::F12;
+    pub const FA3: FloatRegister = FloatRegister::F13;
+    pub const FA4: FloatRegister = FloatRegister::F14;
+    pub const FA5: FloatRegister = FloatRegister::F15;
+    pub const FA6: FloatRegister = FloatRegister::F16;
+    pub const FA7: FloatRegister = FloatRegister::F17;
+    pub const FS2: FloatRegister = FloatRegister::F18;
+    pub const FS3: FloatRegister = FloatRegister::F19;
+    pub const FS4: FloatRegister = FloatRegister::F20;
+    pub const FS5: FloatRegister = FloatRegister::F21;
+    pub const FS6: FloatRegister = FloatRegister::F22;
+    pub const FS7: FloatRegister = FloatRegister::F23;
+    pub const FS8: FloatRegister = FloatRegister::F24;
+    pub const FS9: FloatRegister = FloatRegister::F25;
+    pub const FS10: FloatRegister = FloatRegister::F26;
+    pub const FS11: FloatRegister = FloatRegister::F27;
+    pub const FT8: FloatRegister = FloatRegister::F28;
+    pub const FT9: FloatRegister = FloatRegister::F29;
+    pub const FT10: FloatRegister = FloatRegister::F30;
+    pub const FT11: FloatRegister = FloatRegister::F31;
+
+    /// Constructs the register with the given number, or `None` if it is greater than 31.
+    pub const fn new(number: u8) -> Option<FloatRegister> {
+        if number < REGISTER_COUNT {
+            Some(FloatRegister(number))
+        } else {
+            None
+        }
+    }
+
+    /// Constructs a register from a 5-bit instruction field, ignoring any higher bits.
+    pub(crate) const fn from_field(field: u32) -> FloatRegister {
+        FloatRegister((field & REGISTER_MASK) as u8)
+    }
+
+    /// Looks up a register by its numeric (`f10`) or ABI (`fa0`) name.
+    pub fn from_name(name: &str) -> Option<FloatRegister> {
+        FLOAT_NUMERIC_NAMES
+            .iter()
+            .position(|numeric| *numeric == name)
+            .or_else(|| FLOAT_ABI_NAMES.iter().position(|abi| *abi == name))
+            .map(|number| FloatRegister(number as u8))
+    }
+
+    /// Returns the register number, `0` through `31`.
+    pub const fn number(self) -> u8 {
+        self.0
+    }
+
+    /// Returns the name of the register in the given syntax.
+    pub const fn name(self, syntax: Syntax) -> &'static str {
+        match syntax {
+            Syntax::Numeric => FLOAT_NUMERIC_NAMES[self.0 as usize],
+            Syntax::Abi => FLOAT_ABI_NAMES[self.0 as usize],
+        }
+    }
+}
+
+impl TryFrom<u8> for FloatRegister {
+    type Error = Error;
+
+    fn try_from(number: u8) -> Result<Self, Self::Error> {
+        FloatRegister::new(number).ok_or(Error::InvalidRegister)
+    }
+}
+
+impl fmt::Display for FloatRegister {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name(Syntax::Numeric))
+    }
+}
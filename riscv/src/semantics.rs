@@ -0,0 +1,467 @@
+use crate::csr::Counters;
+use crate::error::Error;
+use crate::float::{DOUBLE, ExceptionFlags, Fcsr, SINGLE, nan_box, unbox};
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+/// The shift amount of a register shift or rotate is held in the lower 5 bits.
+const SHIFT_MASK: u32 = 0x1f;
+
+/// The shift amount of an immediate shift, rotate or single-bit instruction.
+///
+/// Decoded instructions always hold an amount in `0..32`; larger values are reduced
+/// the same way as a register shift amount.
+fn immediate_shamt(imm: i16) -> u32 {
+    imm as u32 & SHIFT_MASK
+}
+
+/// The shift amount of an RV64I register or immediate shift is held in the lower 6 bits.
+const WIDE_SHIFT_MASK: u64 = 0x3f;
+
+/// Sign-extends the 32-bit result of a word instruction to 64 bits.
+fn word(value: u32) -> u64 {
+    value as i32 as i64 as u64
+}
+
+/// The RV32I or RV32M instruction that a word instruction applies to the lower 32
+/// bits of its operands, or `None` if `instruction` is not a word instruction.
+fn word_operation(instruction: RiscVInstruction) -> Option<RiscVInstruction> {
+    let operation = match instruction {
+        RiscVInstruction::Addiw { rd, rs1, imm } => RiscVInstruction::Addi { rd, rs1, imm },
+        RiscVInstruction::Slliw { rd, rs1, imm } => RiscVInstruction::Slli { rd, rs1, imm },
+        RiscVInstruction::Srliw { rd, rs1, imm } => RiscVInstruction::Srli { rd, rs1, imm },
+        RiscVInstruction::Sraiw { rd, rs1, imm } => RiscVInstruction::Srai { rd, rs1, imm },
+        RiscVInstruction::Addw { rd, rs1, rs2 } => RiscVInstruction::Add { rd, rs1, rs2 },
+        RiscVInstruction::Subw { rd, rs1, rs2 } => RiscVInstruction::Sub { rd, rs1, rs2 },
+        RiscVInstruction::Sllw { rd, rs1, rs2 } => RiscVInstruction::Sll { rd, rs1, rs2 },
+        RiscVInstruction::Srlw { rd, rs1, rs2 } => RiscVInstruction::Srl { rd, rs1, rs2 },
+        RiscVInstruction::Sraw { rd, rs1, rs2 } => RiscVInstruction::Sra { rd, rs1, rs2 },
+        RiscVInstruction::Mulw { rd, rs1, rs2 } => RiscVInstruction::Mul { rd, rs1, rs2 },
+        RiscVInstruction::Divw { rd, rs1, rs2 } => RiscVInstruction::Div { rd, rs1, rs2 },
+        RiscVInstruction::Divuw { rd, rs1, rs2 } => RiscVInstruction::Divu { rd, rs1, rs2 },
+        RiscVInstruction::Remw { rd, rs1, rs2 } => RiscVInstruction::Rem { rd, rs1, rs2 },
+        RiscVInstruction::Remuw { rd, rs1, rs2 } => RiscVInstruction::Remu { rd, rs1, rs2 },
+        _ => return None,
+    };
+
+    Some(operation)
+}
+
+impl RiscVInstruction {
+    /// Computes the value written to `rd` by an instruction that only depends on its
+    /// source registers and immediate.
+    ///
+    /// This is the reference semantics every backend must match. `rs1` and `rs2` are
+    /// the values of the source registers; an operand the instruction does not have is
+    /// ignored. Returns `None` for instructions that access memory, depend on the
+    /// program counter, or transfer control. The RV64I word instructions are only
+    /// evaluated by [`RiscVInstruction::evaluate_rv64`].
+    pub fn evaluate(&self, rs1: u32, rs2: u32) -> Option<u32> {
+        let shamt = rs2 & SHIFT_MASK;
+
+        let value = match *self {
+            RiscVInstruction::Add { .. } => rs1.wrapping_add(rs2),
+            RiscVInstruction::Sub { .. } => rs1.wrapping_sub(rs2),
+            RiscVInstruction::Xor { .. } => rs1 ^ rs2,
+            RiscVInstruction::Or { .. } => rs1 | rs2,
+            RiscVInstruction::And { .. } => rs1 & rs2,
+            RiscVInstruction::Sll { .. } => rs1 << shamt,
+            RiscVInstruction::Srl { .. } => rs1 >> shamt,
+            RiscVInstruction::Sra { .. } => ((rs1 as i32) >> shamt) as u32,
+            RiscVInstruction::Slt { .. } => ((rs1 as i32) < (rs2 as i32)) as u32,
+            RiscVInstruction::Sltu { .. } => (rs1 < rs2) as u32,
+            RiscVInstruction::Mul { .. } => rs1.wrapping_mul(rs2),
+            RiscVInstruction::Mulh { .. } => ((rs1 as i32 as i64 * rs2 as i32 as i64) >> 32) as u32,
+            RiscVInstruction::Mulhsu { .. } => ((rs1 as i32 as i64 * rs2 as i64) >> 32) as u32,
+            RiscVInstruction::Mulhu { .. } => ((rs1 as u64 * rs2 as u64) >> 32) as u32,
+            RiscVInstruction::Div { .. } => match rs2 {
+                0 => u32::MAX,
+                _ => (rs1 as i32).wrapping_div(rs2 as i32) as u32,
+            },
+            RiscVInstruction::Divu { .. } => rs1.checked_div(rs2).unwrap_or(u32::MAX),
+            RiscVInstruction::Rem { .. } => match rs2 {
+                0 => rs1,
+                _ => (rs1 as i32).wrapping_rem(rs2 as i32) as u32,
+            },
+            RiscVInstruction::Remu { .. } => rs1.checked_rem(rs2).unwrap_or(rs1),
+            RiscVInstruction::Addi { imm, .. } => rs1.wrapping_add(imm as i32 as u32),
+            RiscVInstruction::Xori { imm, .. } => rs1 ^ imm as i32 as u32,
+            RiscVInstruction::Ori { imm, .. } => rs1 | imm as i32 as u32,
+            RiscVInstruction::Andi { imm, .. } => rs1 & imm as i32 as u32,
+            RiscVInstruction::Slli { imm, .. } => rs1 << immediate_shamt(imm),
+            RiscVInstruction::Srli { imm, .. } => rs1 >> immediate_shamt(imm),
+            RiscVInstruction::Srai { imm, .. } => ((rs1 as i32) >> immediate_shamt(imm)) as u32,
+            RiscVInstruction::Slti { imm, .. } => ((rs1 as i32) < imm as i32) as u32,
+            RiscVInstruction::Sltiu { imm, .. } => (rs1 < imm as i32 as u32) as u32,
+            RiscVInstruction::Lui { imm, .. } => imm as u32,
+            RiscVInstruction::Sh1add { .. } => (rs1 << 1).wrapping_add(rs2),
+            RiscVInstruction::Sh2add { .. } => (rs1 << 2).wrapping_add(rs2),
+            RiscVInstruction::Sh3add { .. } => (rs1 << 3).wrapping_add(rs2),
+            RiscVInstruction::Andn { .. } => rs1 & !rs2,
+            RiscVInstruction::Orn { .. } => rs1 | !rs2,
+            RiscVInstruction::Xnor { .. } => !(rs1 ^ rs2),
+            RiscVInstruction::Clz { .. } => rs1.leading_zeros(),
+            RiscVInstruction::Ctz { .. } => rs1.trailing_zeros(),
+            RiscVInstruction::Cpop { .. } => rs1.count_ones(),
+            RiscVInstruction::Max { .. } => (rs1 as i32).max(rs2 as i32) as u32,
+            RiscVInstruction::Maxu { .. } => rs1.max(rs2),
+            RiscVInstruction::Min { .. } => (rs1 as i32).min(rs2 as i32) as u32,
+            RiscVInstruction::Minu { .. } => rs1.min(rs2),
+            RiscVInstruction::SextB { .. } => rs1 as i8 as i32 as u32,
+            RiscVInstruction::SextH { .. } => rs1 as i16 as i32 as u32,
+            RiscVInstruction::ZextH { .. } => rs1 as u16 as u32,
+            RiscVInstruction::Rol { .. } => rs1.rotate_left(shamt),
+            RiscVInstruction::Ror { .. } => rs1.rotate_right(shamt),
+            RiscVInstruction::Rori { imm, .. } => rs1.rotate_right(immediate_shamt(imm)),
+            RiscVInstruction::OrcB { .. } => {
+                let bytes = rs1
+                    .to_le_bytes()
+                    .map(|byte| if byte == 0 { 0 } else { 0xff });
+                u32::from_le_bytes(bytes)
+            }
+            RiscVInstruction::Rev8 { .. } => rs1.swap_bytes(),
+            RiscVInstruction::Bclr { .. } => rs1 & !(1 << shamt),
+            RiscVInstruction::Bclri { imm, .. } => rs1 & !(1 << immediate_shamt(imm)),
+            RiscVInstruction::Bext { .. } => (rs1 >> shamt) & 1,
+            RiscVInstruction::Bexti { imm, .. } => (rs1 >> immediate_shamt(imm)) & 1,
+            RiscVInstruction::Binv { .. } => rs1 ^ (1 << shamt),
+            RiscVInstruction::Binvi { imm, .. } => rs1 ^ (1 << immediate_shamt(imm)),
+            RiscVInstruction::Bset { .. } => rs1 | (1 << shamt),
+            RiscVInstruction::Bseti { imm, .. } => rs1 | (1 << immediate_shamt(imm)),
+            RiscVInstruction::Sha256sig0 { .. } => {
+                rs1.rotate_right(7) ^ rs1.rotate_right(18) ^ (rs1 >> 3)
+            }
+            RiscVInstruction::Sha256sig1 { .. } => {
+                rs1.rotate_right(17) ^ rs1.rotate_right(19) ^ (rs1 >> 10)
+            }
+            RiscVInstruction::Sha256sum0 { .. } => {
+                rs1.rotate_right(2) ^ rs1.rotate_right(13) ^ rs1.rotate_right(22)
+            }
+            RiscVInstruction::Sha256sum1 { .. } => {
+                rs1.rotate_right(6) ^ rs1.rotate_right(11) ^ rs1.rotate_right(25)
+            }
+            // The SHA-512 functions work on 64-bit values held in a pair of registers:
+            // each instruction computes one 32-bit half of the result.
+            RiscVInstruction::Sha512sum0r { .. } => {
+                (rs1 << 25) ^ (rs1 << 30) ^ (rs1 >> 28) ^ (rs2 >> 7) ^ (rs2 >> 2) ^ (rs2 << 4)
+            }
+            RiscVInstruction::Sha512sum1r { .. } => {
+                (rs1 << 23) ^ (rs1 >> 14) ^ (rs1 >> 18) ^ (rs2 >> 9) ^ (rs2 << 18) ^ (rs2 << 14)
+            }
+            RiscVInstruction::Sha512sig0l { .. } => {
+                (rs1 >> 1) ^ (rs1 >> 7) ^ (rs1 >> 8) ^ (rs2 << 31) ^ (rs2 << 25) ^ (rs2 << 24)
+            }
+            RiscVInstruction::Sha512sig0h { .. } => {
+                (rs1 >> 1) ^ (rs1 >> 7) ^ (rs1 >> 8) ^ (rs2 << 31) ^ (rs2 << 24)
+            }
+            RiscVInstruction::Sha512sig1l { .. } => {
+                (rs1 << 3) ^ (rs1 >> 6) ^ (rs1 >> 19) ^ (rs2 >> 29) ^ (rs2 << 26) ^ (rs2 << 13)
+            }
+            RiscVInstruction::Sha512sig1h { .. } => {
+                (rs1 << 3) ^ (rs1 >> 6) ^ (rs1 >> 19) ^ (rs2 >> 29) ^ (rs2 << 13)
+            }
+            _ => return None,
+        };
+
+        Some(value)
+    }
+
+    /// Computes the value written to `rd` by an instruction on RV64I, where registers
+    /// are 64 bits wide.
+    ///
+    /// Covers the same instructions as [`RiscVInstruction::evaluate`] for the base
+    /// and M instruction sets, plus the word instructions, which operate on the lower
+    /// 32 bits of their operands and sign-extend the 32-bit result. Division by zero
+    /// and overflow follow the same rules as on RV32I at each width. Returns `None`
+    /// for instructions of the optional extensions, which RV64I does not implement.
+    pub fn evaluate_rv64(&self, rs1: u64, rs2: u64) -> Option<u64> {
+        let shamt = rs2 & WIDE_SHIFT_MASK;
+        let wide_shamt = |imm: i16| imm as u64 & WIDE_SHIFT_MASK;
+        let imm64 = |imm: i16| imm as i64 as u64;
+
+        let value = match *self {
+            RiscVInstruction::Add { .. } => rs1.wrapping_add(rs2),
+            RiscVInstruction::Sub { .. } => rs1.wrapping_sub(rs2),
+            RiscVInstruction::Xor { .. } => rs1 ^ rs2,
+            RiscVInstruction::Or { .. } => rs1 | rs2,
+            RiscVInstruction::And { .. } => rs1 & rs2,
+            RiscVInstruction::Sll { .. } => rs1 << shamt,
+            RiscVInstruction::Srl { .. } => rs1 >> shamt,
+            RiscVInstruction::Sra { .. } => ((rs1 as i64) >> shamt) as u64,
+            RiscVInstruction::Slt { .. } => ((rs1 as i64) < (rs2 as i64)) as u64,
+            RiscVInstruction::Sltu { .. } => (rs1 < rs2) as u64,
+            RiscVInstruction::Mul { .. } => rs1.wrapping_mul(rs2),
+            RiscVInstruction::Mulh { .. } => {
+                ((rs1 as i64 as i128 * rs2 as i64 as i128) >> 64) as u64
+            }
+            RiscVInstruction::Mulhsu { .. } => ((rs1 as i64 as i128 * rs2 as i128) >> 64) as u64,
+            RiscVInstruction::Mulhu { .. } => ((rs1 as u128 * rs2 as u128) >> 64) as u64,
+            RiscVInstruction::Div { .. } => match rs2 {
+                0 => u64::MAX,
+                _ => (rs1 as i64).wrapping_div(rs2 as i64) as u64,
+            },
+            RiscVInstruction::Divu { .. } => rs1.checked_div(rs2).unwrap_or(u64::MAX),
+            RiscVInstruction::Rem { .. } => match rs2 {
+                0 => rs1,
+                _ => (rs1 as i64).wrapping_rem(rs2 as i64) as u64,
+            },
+            RiscVInstruction::Remu { .. } => rs1.checked_rem(rs2).unwrap_or(rs1),
+            RiscVInstruction::Addi { imm, .. } => rs1.wrapping_add(imm64(imm)),
+            RiscVInstruction::Xori { imm, .. } => rs1 ^ imm64(imm),
+            RiscVInstruction::Ori { imm, .. } => rs1 | imm64(imm),
+            RiscVInstruction::Andi { imm, .. } => rs1 & imm64(imm),
+            RiscVInstruction::Slli { imm, .. } => rs1 << wide_shamt(imm),
+            RiscVInstruction::Srli { imm, .. } => rs1 >> wide_shamt(imm),
+            RiscVInstruction::Srai { imm, .. } => ((rs1 as i64) >> wide_shamt(imm)) as u64,
+            RiscVInstruction::Slti { imm, .. } => ((rs1 as i64) < imm as i64) as u64,
+            RiscVInstruction::Sltiu { imm, .. } => (rs1 < imm64(imm)) as u64,
+            RiscVInstruction::Lui { imm, .. } => imm as i64 as u64,
+            _ => word(word_operation(*self)?.evaluate(rs1 as u32, rs2 as u32)?),
+        };
+
+        Some(value)
+    }
+
+    /// Computes the value an atomic memory operation stores back to memory.
+    ///
+    /// `loaded` is the word loaded from memory, which the instruction also writes to
+    /// `rd`, and `rs2` is the value of its source register. Returns `None` for every
+    /// other instruction, including `lr.w` and `sc.w`, whose semantics are described
+    /// by [`Reservation`](crate::Reservation).
+    pub fn atomic(&self, loaded: u32, rs2: u32) -> Option<u32> {
+        let value = match *self {
+            RiscVInstruction::AmoswapW { .. } => rs2,
+            RiscVInstruction::AmoaddW { .. } => loaded.wrapping_add(rs2),
+            RiscVInstruction::AmoxorW { .. } => loaded ^ rs2,
+            RiscVInstruction::AmoandW { .. } => loaded & rs2,
+            RiscVInstruction::AmoorW { .. } => loaded | rs2,
+            RiscVInstruction::AmominW { .. } => (loaded as i32).min(rs2 as i32) as u32,
+            RiscVInstruction::AmomaxW { .. } => (loaded as i32).max(rs2 as i32) as u32,
+            RiscVInstruction::AmominuW { .. } => loaded.min(rs2),
+            RiscVInstruction::AmomaxuW { .. } => loaded.max(rs2),
+            _ => return None,
+        };
+
+        Some(value)
+    }
+
+    /// Computes the value written to `rd` by an instruction of the F or D extensions.
+    ///
+    /// This is the reference semantics every backend must match bit for bit. The
+    /// floating-point registers are 64 bits wide, and a single-precision value is
+    /// NaN-boxed in the low half of one. `rs1`, `rs2` and `rs3` are the values of the
+    /// source registers, with an integer register zero-extended; an operand the
+    /// instruction does not have is ignored. The result is the new value of a
+    /// floating-point `rd`, or of an integer `rd` zero-extended to 64 bits.
+    ///
+    /// The rounding mode `dyn` rounds with `fcsr.frm`, and the exception flags the
+    /// instruction raises are accrued in `fcsr.fflags`. Returns `None` for every other
+    /// instruction, including the floating-point loads and stores.
+    ///
+    /// # Errors
+    ///
+    /// - `Error::InvalidRoundingMode` if the instruction rounds with `dyn` while `frm`
+    ///   holds a reserved rounding mode. `fcsr` is left unchanged.
+    pub fn evaluate_float(
+        &self,
+        rs1: u64,
+        rs2: u64,
+        rs3: u64,
+        fcsr: &mut Fcsr,
+    ) -> Option<Result<u64, Error>> {
+        let mut flags = ExceptionFlags::NONE;
+        let value = self
+            .float_result(rs1, rs2, rs3, fcsr, &mut flags)
+            .transpose()?;
+        if value.is_ok() {
+            fcsr.fflags = fcsr.fflags.with(flags);
+        }
+
+        Some(value)
+    }
+
+    /// Computes the result of [`RiscVInstruction::evaluate_float`], raising the
+    /// exception flags of the instruction in `flags`.
+    fn float_result(
+        &self,
+        rs1: u64,
+        rs2: u64,
+        rs3: u64,
+        fcsr: &Fcsr,
+        flags: &mut ExceptionFlags,
+    ) -> Result<Option<u64>, Error> {
+        // Every instruction except the moves unboxes its single-precision operands.
+        let (s1, s2, s3) = (unbox(rs1), unbox(rs2), unbox(rs3));
+        let (single_sign, double_sign) = (SINGLE.sign_bit(), DOUBLE.sign_bit());
+        let round = |rm| fcsr.rounding(rm);
+
+        let value = match *self {
+            RiscVInstruction::FmaddS { rm, .. } => {
+                nan_box(SINGLE.fused_multiply_add(s1, s2, s3, round(rm)?, flags))
+            }
+            RiscVInstruction::FmsubS { rm, .. } => {
+                let s3 = s3 ^ single_sign;
+                nan_box(SINGLE.fused_multiply_add(s1, s2, s3, round(rm)?, flags))
+            }
+            RiscVInstruction::FnmsubS { rm, .. } => {
+                let s1 = s1 ^ single_sign;
+                nan_box(SINGLE.fused_multiply_add(s1, s2, s3, round(rm)?, flags))
+            }
+            RiscVInstruction::FnmaddS { rm, .. } => {
+                let (s1, s3) = (s1 ^ single_sign, s3 ^ single_sign);
+                nan_box(SINGLE.fused_multiply_add(s1, s2, s3, round(rm)?, flags))
+            }
+            RiscVInstruction::FaddS { rm, .. } => nan_box(SINGLE.add(s1, s2, round(rm)?, flags)),
+            RiscVInstruction::FsubS { rm, .. } => nan_box(SINGLE.sub(s1, s2, round(rm)?, flags)),
+            RiscVInstruction::FmulS { rm, .. } => nan_box(SINGLE.mul(s1, s2, round(rm)?, flags)),
+            RiscVInstruction::FdivS { rm, .. } => nan_box(SINGLE.div(s1, s2, round(rm)?, flags)),
+            RiscVInstruction::FsqrtS { rm, .. } => nan_box(SINGLE.sqrt(s1, round(rm)?, flags)),
+            RiscVInstruction::FsgnjS { .. } => nan_box((s1 & !single_sign) | (s2 & single_sign)),
+            RiscVInstruction::FsgnjnS { .. } => nan_box((s1 & !single_sign) | (!s2 & single_sign)),
+            RiscVInstruction::FsgnjxS { .. } => nan_box(s1 ^ (s2 & single_sign)),
+            RiscVInstruction::FminS { .. } => nan_box(SINGLE.min_max(s1, s2, false, flags)),
+            RiscVInstruction::FmaxS { .. } => nan_box(SINGLE.min_max(s1, s2, true, flags)),
+            RiscVInstruction::FeqS { .. } => SINGLE.eq(s1, s2, flags) as u64,
+            RiscVInstruction::FltS { .. } => SINGLE.lt(s1, s2, flags) as u64,
+            RiscVInstruction::FleS { .. } => SINGLE.le(s1, s2, flags) as u64,
+            RiscVInstruction::FcvtWS { rm, .. } => {
+                SINGLE.to_int(s1, true, round(rm)?, flags) as u64
+            }
+            RiscVInstruction::FcvtWuS { rm, .. } => {
+                SINGLE.to_int(s1, false, round(rm)?, flags) as u64
+            }
+            RiscVInstruction::FmvXW { .. } => rs1 & u32::MAX as u64,
+            RiscVInstruction::FclassS { .. } => SINGLE.classify(s1) as u64,
+            RiscVInstruction::FcvtSW { rm, .. } => {
+                nan_box(SINGLE.int_to_float(rs1 as u32, true, round(rm)?, flags))
+            }
+            RiscVInstruction::FcvtSWu { rm, .. } => {
+                nan_box(SINGLE.int_to_float(rs1 as u32, false, round(rm)?, flags))
+            }
+            RiscVInstruction::FmvWX { .. } => nan_box(rs1 & u32::MAX as u64),
+            RiscVInstruction::FmaddD { rm, .. } => {
+                DOUBLE.fused_multiply_add(rs1, rs2, rs3, round(rm)?, flags)
+            }
+            RiscVInstruction::FmsubD { rm, .. } => {
+                let rs3 = rs3 ^ double_sign;
+                DOUBLE.fused_multiply_add(rs1, rs2, rs3, round(rm)?, flags)
+            }
+            RiscVInstruction::FnmsubD { rm, .. } => {
+                let rs1 = rs1 ^ double_sign;
+                DOUBLE.fused_multiply_add(rs1, rs2, rs3, round(rm)?, flags)
+            }
+            RiscVInstruction::FnmaddD { rm, .. } => {
+                let (rs1, rs3) = (rs1 ^ double_sign, rs3 ^ double_sign);
+                DOUBLE.fused_multiply_add(rs1, rs2, rs3, round(rm)?, flags)
+            }
+            RiscVInstruction::FaddD { rm, .. } => DOUBLE.add(rs1, rs2, round(rm)?, flags),
+            RiscVInstruction::FsubD { rm, .. } => DOUBLE.sub(rs1, rs2, round(rm)?, flags),
+            RiscVInstruction::FmulD { rm, .. } => DOUBLE.mul(rs1, rs2, round(rm)?, flags),
+            RiscVInstruction::FdivD { rm, .. } => DOUBLE.div(rs1, rs2, round(rm)?, flags),
+            RiscVInstruction::FsqrtD { rm, .. } => DOUBLE.sqrt(rs1, round(rm)?, flags),
+            RiscVInstruction::FsgnjD { .. } => (rs1 & !double_sign) | (rs2 & double_sign),
+            RiscVInstruction::FsgnjnD { .. } => (rs1 & !double_sign) | (!rs2 & double_sign),
+            RiscVInstruction::FsgnjxD { .. } => rs1 ^ (rs2 & double_sign),
+            RiscVInstruction::FminD { .. } => DOUBLE.min_max(rs1, rs2, false, flags),
+            RiscVInstruction::FmaxD { .. } => DOUBLE.min_max(rs1, rs2, true, flags),
+            RiscVInstruction::FeqD { .. } => DOUBLE.eq(rs1, rs2, flags) as u64,
+            RiscVInstruction::FltD { .. } => DOUBLE.lt(rs1, rs2, flags) as u64,
+            RiscVInstruction::FleD { .. } => DOUBLE.le(rs1, rs2, flags) as u64,
+            RiscVInstruction::FcvtWD { rm, .. } => {
+                DOUBLE.to_int(rs1, true, round(rm)?, flags) as u64
+            }
+            RiscVInstruction::FcvtWuD { rm, .. } => {
+                DOUBLE.to_int(rs1, false, round(rm)?, flags) as u64
+            }
+            RiscVInstruction::FclassD { .. } => DOUBLE.classify(rs1) as u64,
+            RiscVInstruction::FcvtDW { rm, .. } => {
+                DOUBLE.int_to_float(rs1 as u32, true, round(rm)?, flags)
+            }
+            RiscVInstruction::FcvtDWu { rm, .. } => {
+                DOUBLE.int_to_float(rs1 as u32, false, round(rm)?, flags)
+            }
+            RiscVInstruction::FcvtSD { rm, .. } => {
+                nan_box(DOUBLE.convert(SINGLE, rs1, round(rm)?, flags))
+            }
+            RiscVInstruction::FcvtDS { rm, .. } => SINGLE.convert(DOUBLE, s1, round(rm)?, flags),
+            _ => return Ok(None),
+        };
+
+        Ok(Some(value))
+    }
+
+    /// Accesses `fflags`, `frm` or `fcsr` with a CSR instruction, returning the value
+    /// written to `rd`.
+    ///
+    /// This is the reference semantics of the floating-point CSRs held in [`Fcsr`].
+    /// `source` is the value of `rs1`, or the immediate of `csrrwi`, `csrrsi` and
+    /// `csrrci`. As for the counters, `csrrs` and `csrrc` with `rs1` = `x0`, and
+    /// `csrrsi` and `csrrci` with an immediate of 0, only read the CSR. Returns `None`
+    /// for other instructions and other CSRs.
+    pub fn access_fcsr(&self, fcsr: &mut Fcsr, source: u32) -> Option<u32> {
+        let (csr, value) = match *self {
+            RiscVInstruction::Csrrw { csr, .. } | RiscVInstruction::Csrrwi { csr, .. } => {
+                (csr, Some(source))
+            }
+            RiscVInstruction::Csrrs { rs1, csr, .. } => {
+                (csr, (rs1 != Register::ZERO).then_some(source))
+            }
+            RiscVInstruction::Csrrsi { imm, csr, .. } => (csr, (imm != 0).then_some(source)),
+            RiscVInstruction::Csrrc { rs1, csr, .. } => {
+                (csr, (rs1 != Register::ZERO).then_some(source))
+            }
+            RiscVInstruction::Csrrci { imm, csr, .. } => (csr, (imm != 0).then_some(source)),
+            _ => return None,
+        };
+
+        let old = fcsr.read(csr)?;
+        let new = match *self {
+            RiscVInstruction::Csrrs { .. } | RiscVInstruction::Csrrsi { .. } => {
+                value.map(|value| old | value)
+            }
+            RiscVInstruction::Csrrc { .. } | RiscVInstruction::Csrrci { .. } => {
+                value.map(|value| old & !value)
+            }
+            _ => value,
+        };
+
+        if let Some(new) = new {
+            fcsr.write(csr, new);
+        }
+
+        Some(old)
+    }
+
+    /// Computes the value written to `rd` by a CSR instruction.
+    ///
+    /// This is the reference semantics of the counter CSRs described by [`Counters`].
+    /// With the F or D extension, `fflags`, `frm` and `fcsr` are accessed through
+    /// [`RiscVInstruction::access_fcsr`] instead. Returns `None` for instructions other
+    /// than the CSR instructions.
+    ///
+    /// # Errors
+    ///
+    /// - `Error::InvalidCsr` if the instruction names a CSR other than a counter, or
+    ///   writes a counter. `csrrs` and `csrrc` with `rs1` = `x0`, and `csrrsi` and
+    ///   `csrrci` with an immediate of 0, only read the CSR.
+    pub fn read_csr(&self, counters: &Counters) -> Option<Result<u32, Error>> {
+        let (csr, writes) = match *self {
+            RiscVInstruction::Csrrw { csr, .. } | RiscVInstruction::Csrrwi { csr, .. } => {
+                (csr, true)
+            }
+            RiscVInstruction::Csrrs { rs1, csr, .. } | RiscVInstruction::Csrrc { rs1, csr, .. } => {
+                (csr, rs1 != Register::ZERO)
+            }
+            RiscVInstruction::Csrrsi { imm, csr, .. }
+            | RiscVInstruction::Csrrci { imm, csr, .. } => (csr, imm != 0),
+            _ => return None,
+        };
+
+        let value = match writes {
+            true => None,
+            false => counters.read(csr),
+        };
+
+        Some(value.ok_or(Error::InvalidCsr { csr }))
+    }
+}
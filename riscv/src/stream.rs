@@ -0,0 +1,121 @@
+use crate::custom::CustomInstructions;
+use crate::error::{DecodeError, DecodeErrorKind};
+use crate::extension::Extensions;
+use crate::instruction::RiscVInstruction;
+use crate::profile::Profile;
+
+/// The lowest two bits of every 32-bit instruction are set; anything else is compressed.
+const UNCOMPRESSED_MASK: u16 = 0x3;
+
+/// An instruction decoded from a code buffer, along with where it was found.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DecodedInstruction {
+    /// The byte offset of the instruction from the start of the buffer.
+    pub offset: usize,
+    /// The encoded length of the instruction in bytes, either 2 or 4.
+    ///
+    /// A buffer that ends in the middle of an instruction yields a final
+    /// `Truncated` error whose length covers the remaining bytes.
+    pub length: usize,
+    /// The decoded instruction, or why it could not be decoded. Compressed
+    /// instructions are expanded to their 32-bit equivalent.
+    pub instruction: Result<RiscVInstruction, DecodeError>,
+}
+
+/// The error yielded for an instruction cut off by the end of the buffer.
+fn truncated(partial: u32) -> DecodeError {
+    DecodeError::new(partial, DecodeErrorKind::Truncated)
+}
+
+/// An iterator decoding a little-endian buffer of mixed 16-bit and 32-bit instructions.
+pub struct InstructionStream<'a> {
+    code: &'a [u8],
+    offset: usize,
+    profile: Profile,
+    extensions: Extensions,
+    custom: &'a CustomInstructions,
+}
+
+impl<'a> InstructionStream<'a> {
+    /// Constructs a new `InstructionStream` over the given code buffer, decoding only
+    /// the base instruction set.
+    pub fn new(code: &'a [u8]) -> Self {
+        Self::with_extensions(code, Extensions::NONE)
+    }
+
+    /// Constructs a new `InstructionStream` over the given code buffer, also decoding
+    /// the given optional extensions.
+    pub fn with_extensions(code: &'a [u8], extensions: Extensions) -> Self {
+        Self::with_profile(code, Profile::Rv32i, extensions)
+    }
+
+    /// Constructs a new `InstructionStream` over the given code buffer for the given
+    /// profile, also decoding the given optional extensions.
+    pub fn with_profile(code: &'a [u8], profile: Profile, extensions: Extensions) -> Self {
+        static NONE: CustomInstructions = CustomInstructions::NONE;
+        Self::with_custom(code, profile, extensions, &NONE)
+    }
+
+    /// Constructs a new `InstructionStream` over the given code buffer for the given
+    /// profile, also decoding the given optional extensions and custom instructions.
+    pub fn with_custom(
+        code: &'a [u8],
+        profile: Profile,
+        extensions: Extensions,
+        custom: &'a CustomInstructions,
+    ) -> Self {
+        Self {
+            code,
+            offset: 0,
+            profile,
+            extensions,
+            custom,
+        }
+    }
+}
+
+impl Iterator for InstructionStream<'_> {
+    type Item = DecodedInstruction;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let offset = self.offset;
+        let remaining = &self.code[offset..];
+
+        let (length, instruction) = match remaining {
+            [] => return None,
+            [byte] => (1, Err(truncated(*byte as u32))),
+            [b0, b1, rest @ ..] => {
+                let half = u16::from_le_bytes([*b0, *b1]);
+                if half & UNCOMPRESSED_MASK != UNCOMPRESSED_MASK {
+                    (
+                        2,
+                        RiscVInstruction::decode_compressed_for(
+                            half,
+                            self.profile,
+                            self.extensions,
+                        ),
+                    )
+                } else if let [b2, b3, ..] = rest {
+                    let word = u32::from_le_bytes([*b0, *b1, *b2, *b3]);
+                    let instruction = RiscVInstruction::decode_custom(
+                        word,
+                        self.profile,
+                        self.extensions,
+                        self.custom,
+                    );
+                    (4, instruction)
+                } else {
+                    (remaining.len(), Err(truncated(half as u32)))
+                }
+            }
+        };
+
+        self.offset += length;
+
+        Some(DecodedInstruction {
+            offset,
+            length,
+            instruction,
+        })
+    }
+}
@@ -0,0 +1,241 @@
+use crate::Config;
+use crate::aarch64::{Condition, Emitter, X, translate};
+use crate::assembler::assemble;
+use crate::custom::CustomInstructions;
+use crate::extension::Extensions;
+use crate::hart::offsets::{GAS, MEMORY, MEMORY_SIZE, NATIVE_OFFSETS, PC, REGISTERS};
+use crate::profile::Profile;
+use crate::program::{NO_ENTRY, Program};
+
+fn words(code: &[u8]) -> Vec<u32> {
+    code.chunks(4)
+        .map(|word| u32::from_le_bytes(word.try_into().unwrap()))
+        .collect()
+}
+
+fn program(profile: Profile, source: &str) -> Program {
+    let config = Config {
+        syscall: |_args, _context| 0,
+        max_instance_memory: 0x1000,
+        max_code_size: 0x1000,
+        profile,
+        extensions: Extensions::NONE,
+        time: |_context| 0,
+        custom: CustomInstructions::NONE,
+    };
+    Program::decode(&assemble(source).unwrap().bytes, &config).unwrap()
+}
+
+/// The expected words were produced by `llvm-mc -triple=aarch64 -show-encoding`.
+#[test]
+fn encodings() {
+    let (a, b, c) = (X(10), X(11), X(12));
+    let mut e = Emitter::default();
+    e.add(false, a, a, b); // add w10, w10, w11
+    e.add(true, a, a, b); // add x10, x10, x11
+    e.add_shifted(true, X(13), X(23), a, 1); // add x13, x23, x10, lsl #1
+    e.sub(false, a, a, b); // sub w10, w10, w11
+    e.cmp(true, a, b); // cmp x10, x11
+    e.cmp(false, a, b); // cmp w10, w11
+    e.and(true, a, a, b); // and x10, x10, x11
+    e.orr(false, a, a, b); // orr w10, w10, w11
+    e.eor(true, a, a, b); // eor x10, x10, x11
+    e.lslv(true, a, a, b); // lsl x10, x10, x11
+    e.lsrv(false, a, a, b); // lsr w10, w10, w11
+    e.asrv(true, a, a, b); // asr x10, x10, x11
+    e.sdiv(false, c, a, b); // sdiv w12, w10, w11
+    e.udiv(true, c, a, b); // udiv x12, x10, x11
+    e.madd(true, a, a, b, X(31)); // mul x10, x10, x11
+    e.msub(false, a, c, b, a); // msub w10, w12, w11, w10
+    e.smull(a, a, b); // smull x10, w10, w11
+    e.umull(a, a, b); // umull x10, w10, w11
+    e.smulh(a, a, b); // smulh x10, x10, x11
+    e.umulh(a, a, b); // umulh x10, x10, x11
+    e.csinv(false, a, c, X(31), Condition::Ne); // csinv w10, w12, wzr, ne
+    e.cset(false, a, Condition::Lt); // cset w10, lt
+    e.cset(true, a, Condition::Lo); // cset x10, lo
+
+    assert_eq!(
+        words(&e.code),
+        [
+            0x0b0b014a, 0x8b0b014a, 0x8b0a06ed, 0x4b0b014a, 0xeb0b015f, 0x6b0b015f, 0x8a0b014a,
+            0x2a0b014a, 0xca0b014a, 0x9acb214a, 0x1acb254a, 0x9acb294a, 0x1acb0d4c, 0x9acb094c,
+            0x9b0b7d4a, 0x1b0ba98a, 0x9b2b7d4a, 0x9bab7d4a, 0x9b4b7d4a, 0x9bcb7d4a, 0x5a9f118a,
+            0x1a9fa7ea, 0x9a9f27ea,
+        ]
+    );
+}
+
+#[test]
+fn immediate_encodings() {
+    let (a, b) = (X(10), X(11));
+    let mut e = Emitter::default();
+    e.add_imm(true, X(29), X(31), 0); // add x29, sp, #0
+    e.add_imm(false, a, a, 2047); // add w10, w10, #2047
+    e.sub_imm(true, a, a, 2048); // sub x10, x10, #2048
+    e.sub_imm(true, X(22), X(22), 1); // sub x22, x22, #1
+    e.cmp_imm(false, b, 0); // cmp w11, #0
+    e.cmn_imm(false, b, 1); // cmn w11, #1
+    e.clear_bit0(true, a, a); // and x10, x10, #0xfffffffffffffffe
+    e.clear_bit0(false, a, a); // and w10, w10, #0xfffffffe
+    e.lsl(false, a, a, 3); // lsl w10, w10, #3
+    e.lsl(true, a, a, 63); // lsl x10, x10, #63
+    e.lsr(true, a, a, 32); // lsr x10, x10, #32
+    e.asr(false, a, a, 31); // asr w10, w10, #31
+    e.sxtw(a, a); // sxtw x10, w10
+    e.movz(false, X(9), 0x1234, 0); // movz w9, #0x1234
+    e.movk(false, X(9), 0x5678, 1); // movk w9, #0x5678, lsl #16
+    e.movn(true, b, 0, 1); // movn x11, #0x0, lsl #16
+    e.movk(true, b, 0xabcd, 3); // movk x11, #0xabcd, lsl #48
+
+    assert_eq!(
+        words(&e.code),
+        [
+            0x910003fd, 0x111ffd4a, 0xd120014a, 0xd10006d6, 0x7100017f, 0x3100057f, 0x927ff94a,
+            0x121f794a, 0x531d714a, 0xd341014a, 0xd360fd4a, 0x131f7d4a, 0x93407d4a, 0x52824689,
+            0x72aacf09, 0x92a0000b, 0xf2f579ab,
+        ]
+    );
+}
+
+#[test]
+fn memory_encodings() {
+    let (a, b) = (X(10), X(11));
+    let mut e = Emitter::default();
+    e.br(b); // br x11
+    e.ret(); // ret
+    e.ldr(X(20), X(19), 272); // ldr x20, [x19, #272]
+    e.str(a, X(19), 248); // str x10, [x19, #248]
+    e.ldr_w(b, X(13)); // ldr w11, [x13]
+    for encoding in [
+        0x3860_6800, // ldrb w11, [x20, x10]
+        0x38e0_6800, // ldrsb w11, [x20, x10]
+        0x38a0_6800, // ldrsb x11, [x20, x10]
+        0x7860_6800, // ldrh w11, [x20, x10]
+        0x78e0_6800, // ldrsh w11, [x20, x10]
+        0x78a0_6800, // ldrsh x11, [x20, x10]
+        0xb860_6800, // ldr w11, [x20, x10]
+        0xb8a0_6800, // ldrsw x11, [x20, x10]
+        0xf860_6800, // ldr x11, [x20, x10]
+        0x3820_6800, // strb w11, [x20, x10]
+        0x7820_6800, // strh w11, [x20, x10]
+        0xb820_6800, // str w11, [x20, x10]
+        0xf820_6800, // str x11, [x20, x10]
+    ] {
+        e.indexed(encoding, b, X(20), a);
+    }
+    e.stp_pre(X(29), X(30), X(31), -64); // stp x29, x30, [sp, #-64]!
+    e.stp(X(19), X(20), X(31), 16); // stp x19, x20, [sp, #16]
+    e.ldp(X(23), X(24), X(31), 48); // ldp x23, x24, [sp, #48]
+    e.ldp_post(X(29), X(30), X(31), 64); // ldp x29, x30, [sp], #64
+
+    assert_eq!(
+        words(&e.code),
+        [
+            0xd61f0160, 0xd65f03c0, 0xf9408a74, 0xf9007e6a, 0xb94001ab, 0x386a6a8b, 0x38ea6a8b,
+            0x38aa6a8b, 0x786a6a8b, 0x78ea6a8b, 0x78aa6a8b, 0xb86a6a8b, 0xb8aa6a8b, 0xf86a6a8b,
+            0x382a6a8b, 0x782a6a8b, 0xb82a6a8b, 0xf82a6a8b, 0xa9bc7bfd, 0xa90153f3, 0xa94363f7,
+            0xa8c47bfd,
+        ]
+    );
+}
+
+#[test]
+fn branch_encodings() {
+    let mut e = Emitter::default();
+    e.emit(0xd503201f); // nop
+    e.b(0); // b #-4
+    e.b_cond(Condition::Hs, 12); // b.hs #4
+    e.cbnz(X(22), 24); // cbnz x22, #12
+    e.adr(X(24), 0); // adr x24, #-16
+
+    assert_eq!(
+        words(&e.code),
+        [0xd503201f, 0x17ffffff, 0x54000022, 0xb5000076, 0x10ffff98]
+    );
+}
+
+#[test]
+fn move_immediate() {
+    let moves = |wide, value| {
+        let mut e = Emitter::default();
+        e.mov_imm(wide, X(10), value);
+        words(&e.code)
+    };
+
+    // movz w10, #0
+    assert_eq!(moves(false, 0), [0x5280000a]);
+    // movz w10, #0x5678, lsl #16
+    assert_eq!(moves(false, 0x5678_0000), [0x52aacf0a]);
+    // movn w10, #0x1
+    assert_eq!(moves(false, 0xffff_fffe), [0x1280002a]);
+    // movn x10, #0x7ff
+    assert_eq!(moves(true, -2048_i64 as u64), [0x9280ffea]);
+    // movz x10, #0x1234; movk x10, #0xabcd, lsl #48
+    assert_eq!(moves(true, 0xabcd_0000_0000_1234), [0xd282468a, 0xf2f579aa]);
+}
+
+#[test]
+fn hart_layout() {
+    assert_eq!(REGISTERS, 0);
+    assert_eq!(GAS, 256);
+    assert_eq!(PC, 264);
+    assert_eq!(MEMORY, 272);
+    assert_eq!(MEMORY_SIZE, 280);
+    assert_eq!(NATIVE_OFFSETS, 288);
+}
+
+#[test]
+fn offsets() {
+    let program = program(Profile::Rv32i, "addi a0, a0, 1\n.half 0x0001\nret");
+    let translation = translate(&program, Profile::Rv32i).unwrap();
+
+    let offsets = &translation.offsets;
+    assert_eq!(offsets.len(), 5);
+    assert_ne!(offsets[0], NO_ENTRY);
+    assert_eq!(offsets[1], NO_ENTRY);
+    assert!(offsets[2] > offsets[0]);
+    assert!(offsets[3] > offsets[2]);
+    assert_eq!(offsets[4], NO_ENTRY);
+
+    // The code starts with the entry point.
+    let code = words(&translation.code);
+    assert_eq!(code[0], 0xa9bc7bfd); // stp x29, x30, [sp, #-64]!
+    // Every instruction starts by checking for gas, jumping over its stub.
+    for offset in [offsets[0], offsets[2], offsets[3]] {
+        assert_eq!(code[offset as usize / 4], 0xb5000096); // cbnz x22, #16
+    }
+}
+
+#[test]
+fn instructions_executed_by_the_engine() {
+    let program = program(Profile::Rv32i, "ecall\nebreak\nj 0x100");
+    let translation = translate(&program, Profile::Rv32i).unwrap();
+    let code = words(&translation.code);
+
+    for (index, pc) in [(0, 0), (2, 4), (4, 8)] {
+        let offset = translation.offsets[index] as usize / 4;
+        // movz w9, #pc; movk w9, #0, lsl #16; b exit
+        assert_eq!(code[offset], 0x52800009 | pc << 5);
+        assert_eq!(code[offset + 1], 0x72a00009);
+        assert_eq!(code[offset + 2] & 0xfc000000, 0x14000000);
+    }
+}
+
+#[test]
+fn branches_link_to_their_targets() {
+    let program = program(Profile::Rv32i, "loop:\naddi a0, a0, -1\nbnez a0, loop\nret");
+    let translation = translate(&program, Profile::Rv32i).unwrap();
+    let code = words(&translation.code);
+
+    let (target, branch) = (translation.offsets[0], translation.offsets[2]);
+    let end = translation.offsets[4] as usize / 4;
+    let jump = code[branch as usize / 4..end]
+        .iter()
+        .rposition(|word| word & 0xfc000000 == 0x14000000)
+        .unwrap()
+        + branch as usize / 4;
+
+    let distance = (code[jump] << 6) as i32 >> 6;
+    assert_eq!(jump as i32 + distance, target as i32 / 4);
+}
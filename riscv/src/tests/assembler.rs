@@ -0,0 +1,613 @@
+use crate::assembler::{AssembledInstruction, assemble};
+use crate::error::{AssembleError, AssembleErrorKind};
+use crate::instruction::RiscVInstruction;
+use crate::pseudo::PseudoPrinter;
+use crate::register::{Register, Syntax};
+
+/// Assembles `source` and returns its little-endian instruction words.
+fn words(source: &str) -> Vec<u32> {
+    assemble(source)
+        .unwrap()
+        .bytes
+        .chunks(4)
+        .map(|word| u32::from_le_bytes(word.try_into().unwrap()))
+        .collect()
+}
+
+/// Assembles `source` and returns the error.
+fn error(source: &str) -> (usize, usize, AssembleErrorKind) {
+    let AssembleError { line, column, kind } = assemble(source).unwrap_err();
+    (line, column, kind)
+}
+
+#[test]
+fn empty() {
+    let assembly = assemble("").unwrap();
+    assert!(assembly.bytes.is_empty());
+    assert!(assembly.instructions.is_empty());
+}
+
+#[test]
+fn register_type() {
+    assert_eq!(words("add x1, x2, x3"), [0x003100b3]);
+    assert_eq!(words("sub x1, x2, x3"), [0x403100b3]);
+    assert_eq!(words("mul x1, x2, x3"), [0x023100b3]);
+}
+
+#[test]
+fn bit_manipulation() {
+    assert_eq!(words("sh1add x1, x2, x3"), [0x203120b3]);
+    assert_eq!(words("andn ra, sp, gp"), [0x403170b3]);
+    assert_eq!(words("clz x1, x2"), [0x60011093]);
+    assert_eq!(words("zext.h x1, x2"), [0x080140b3]);
+    assert_eq!(words("rori x1, x2, 7"), [0x60715093]);
+    assert_eq!(words("rev8 x1, x2"), [0x69815093]);
+    assert_eq!(words("bexti x1, x2, 7"), [0x48715093]);
+    assert_eq!(words("bset x1, x2, x3"), [0x283110b3]);
+}
+
+#[test]
+fn hash_functions() {
+    assert_eq!(words("sha256sig0 x1, x2"), [0x10211093]);
+    assert_eq!(words("sha256sum1 ra, sp"), [0x10111093]);
+    assert_eq!(words("sha512sum0r x1, x2, x3"), [0x503100b3]);
+    assert_eq!(words("sha512sig1h x1, x2, x3"), [0x5e3100b3]);
+}
+
+#[test]
+fn csr_instructions() {
+    assert_eq!(words("csrrw x1, 0x340, x2"), [0x340110f3]);
+    assert_eq!(words("csrrc x1, 4095, x31"), [0xffffb0f3]);
+    assert_eq!(words("csrrwi ra, 0x340, 31"), [0x340fd0f3]);
+    assert_eq!(words("csrrsi ra, instreth, 1"), [0xc820e0f3]);
+    assert_eq!(words("rdcycle a0"), [0xc0002573]);
+    assert_eq!(words("rdtime a0"), [0xc0102573]);
+    assert_eq!(words("csrw 0x340, a1"), [0x34059073]);
+    assert_eq!(words("csrr a0, cycle"), words("rdcycle a0"));
+}
+
+#[test]
+fn atomics() {
+    assert_eq!(words("lr.w a0, (a1)"), [0x1005a52f]);
+    assert_eq!(words("lr.w.aqrl ra, 0(sp)"), [0x160120af]);
+    assert_eq!(words("sc.w a0, a2, (a1)"), [0x18c5a52f]);
+    assert_eq!(words("sc.w.rl a0, a2, (a1)"), [0x1ac5a52f]);
+    assert_eq!(words("amoswap.w.aq a0, a2, (a1)"), [0x0cc5a52f]);
+    assert_eq!(words("amoadd.w a0, a2, (a1)"), [0x00c5a52f]);
+    assert_eq!(words("amoand.w a0, a2, (a1)"), [0x60c5a52f]);
+    assert_eq!(words("amomaxu.w.aqrl t6, t6, (t6)"), [0xe7ffafaf]);
+}
+
+#[test]
+fn floating_point() {
+    assert_eq!(words("flw fa0, 8(a1)"), [0x0085a507]);
+    assert_eq!(words("fsd f12, 8(x11)"), [0x00c5b427]);
+    assert_eq!(words("fadd.s fa0, fa1, fa2"), [0x00c5f553]);
+    assert_eq!(words("fadd.s fa0, fa1, fa2, dyn"), [0x00c5f553]);
+    assert_eq!(words("fadd.s fa0, fa1, fa2, rne"), [0x00c58553]);
+    assert_eq!(words("fmadd.d f10, f11, f12, f13, rmm"), [0x6ac5c543]);
+    assert_eq!(words("fsqrt.d fa0, fa1, rdn"), [0x5a05a553]);
+    assert_eq!(words("fsgnjx.s fa0, fa1, fa2"), [0x20c5a553]);
+    assert_eq!(words("flt.d a0, fa1, fa2"), [0xa2c59553]);
+    assert_eq!(words("fcvt.w.s a0, fa1, rtz"), [0xc0059553]);
+    assert_eq!(words("fcvt.d.wu fa0, a1"), [0xd215f553]);
+    assert_eq!(words("fmv.x.w a0, fa1"), [0xe0058553]);
+    assert_eq!(words("fmv.w.x fa0, a1"), [0xf0058553]);
+    assert_eq!(words("fclass.d a0, fa1"), [0xe2059553]);
+    assert_eq!(words("csrrs a0, fcsr, zero"), [0x00302573]);
+}
+
+#[test]
+fn fences() {
+    assert_eq!(words("fence"), [0x0ff0000f]);
+    assert_eq!(words("fence iorw, iorw"), [0x0ff0000f]);
+    assert_eq!(words("fence rw, w"), [0x0310000f]);
+    assert_eq!(words("fence 0, o"), [0x0040000f]);
+    assert_eq!(words("fence.tso"), [0x8330000f]);
+    assert_eq!(words("fence.i"), [0x0000100f]);
+}
+
+#[test]
+fn abi_register_names() {
+    assert_eq!(words("add ra, sp, gp"), [0x003100b3]);
+    assert_eq!(words("add a0, a1, a2"), words("add x10, x11, x12"));
+    assert_eq!(words("add s0, fp, zero"), words("add x8, x8, x0"));
+}
+
+#[test]
+fn immediate_type() {
+    assert_eq!(words("addi x10, x0, 100"), [0x06400513]);
+    assert_eq!(words("addi sp, sp, -16"), [0xff010113]);
+    assert_eq!(words("xori a0, a1, 0x7ff"), [0x7ff5c513]);
+    assert_eq!(words("slli x1, x1, 31"), [0x01f09093]);
+}
+
+#[test]
+fn rv64_instructions() {
+    assert_eq!(words("ld ra, -2048(t6)"), [0x800fb083]);
+    assert_eq!(words("lwu a0, 2047(a1)"), [0x7ff5e503]);
+    assert_eq!(words("sd a0, -8(sp)"), [0xfea13c23]);
+    assert_eq!(words("addiw a0, a1, -1"), [0xfff5851b]);
+    assert_eq!(words("sraiw a0, a1, 31"), [0x41f5d51b]);
+    assert_eq!(words("subw a0, a1, a2"), [0x40c5853b]);
+    assert_eq!(words("remuw a0, a1, a2"), [0x02c5f53b]);
+    assert_eq!(words("slli a0, a1, 63"), [0x03f59513]);
+    assert_eq!(words("srai a0, a1, 63"), [0x43f5d513]);
+}
+
+#[test]
+fn loads_and_stores() {
+    assert_eq!(words("lw a0, 8(sp)"), [0x00812503]);
+    assert_eq!(words("lw a0, (sp)"), [0x00012503]);
+    assert_eq!(words("lbu a0, -1(a1)"), [0xfff5c503]);
+    assert_eq!(words("sw a0, 12(sp)"), [0x00a12623]);
+    assert_eq!(words("sb x2, -2048(x1)"), [0x80208023]);
+}
+
+#[test]
+fn upper_immediates() {
+    assert_eq!(words("lui x1, 0x12345"), [0x123450b7]);
+    assert_eq!(words("auipc ra, 0x1"), [0x00001097]);
+    assert_eq!(words("lui x1, 0xfffff"), [0xfffff0b7]);
+    assert_eq!(words("lui x1, -1"), [0xfffff0b7]);
+}
+
+#[test]
+fn system() {
+    assert_eq!(words("ecall\nebreak"), [0x00000073, 0x00100073]);
+}
+
+#[test]
+fn numeric_offsets() {
+    // Numbers are offsets from the instruction, as printed by Display.
+    assert_eq!(words("beq x1, x2, 8"), [0x00208463]);
+    assert_eq!(words("jal x1, -2"), [0xfffff0ef]);
+    assert_eq!(words("jalr x0, x1, 0"), [0x00008067]);
+    assert_eq!(words("jalr ra, 4(a0)"), [0x004500e7]);
+}
+
+#[test]
+fn labels() {
+    let source = "
+        start:
+            addi a0, a0, -1
+            bnez a0, start
+            beq a0, a1, end
+            nop
+        end: ret
+    ";
+
+    assert_eq!(
+        words(source),
+        [
+            0xfff50513, // addi a0, a0, -1
+            0xfe051ee3, // bne a0, x0, -4
+            0x00b50463, // beq a0, a1, 8
+            0x00000013, // nop
+            0x00008067, // ret
+        ]
+    );
+}
+
+#[test]
+fn several_labels_on_one_line() {
+    assert_eq!(
+        words("j b\na: b:c: nop"),
+        [
+            0x0040006f, // jal x0, 4
+            0x00000013, // nop
+        ]
+    );
+}
+
+#[test]
+fn comments_and_blank_lines() {
+    let source = "
+        # A comment on its own line
+
+        add x1, x2, x3 # and a trailing one
+    ";
+    assert_eq!(words(source), [0x003100b3]);
+}
+
+#[test]
+fn pseudo_instructions() {
+    let source = "
+        nop
+        li a0, 5
+        mv a1, a0
+        not a2, a1
+        neg a3, a2
+        seqz a4, a3
+        snez a5, a4
+        sltz t0, a5
+        sgtz t1, t0
+        jr t1
+        jalr t1
+        ret
+    ";
+
+    let assembly = assemble(source).unwrap();
+    let instructions: Vec<_> = assembly
+        .instructions
+        .iter()
+        .map(|assembled| assembled.instruction)
+        .collect();
+    let printed: Vec<_> = PseudoPrinter::new(&instructions, Syntax::Abi)
+        .map(|printed| printed.text)
+        .collect();
+
+    assert_eq!(
+        printed,
+        [
+            "nop",
+            "li a0, 5",
+            "mv a1, a0",
+            "not a2, a1",
+            "neg a3, a2",
+            "seqz a4, a3",
+            "snez a5, a4",
+            "sltz t0, a5",
+            "sgtz t1, t0",
+            "jr t1",
+            "jalr t1",
+            "ret",
+        ]
+    );
+}
+
+#[test]
+fn li_large() {
+    assert_eq!(
+        words("li a0, 0x12345678"),
+        [
+            0x12345537, // lui a0, 0x12345
+            0x67850513, // addi a0, a0, 0x678
+        ]
+    );
+
+    // The low part is negative, so the upper part is rounded up.
+    assert_eq!(
+        words("li a0, 0x12345800"),
+        [
+            0x12346537, // lui a0, 0x12346
+            0x80050513, // addi a0, a0, -2048
+        ]
+    );
+
+    assert_eq!(words("li a0, -1"), [0xfff00513]);
+    assert_eq!(words("li a0, 0xffffffff"), [0xfff00513]);
+}
+
+#[test]
+fn branch_pseudo_instructions() {
+    let source = "
+        top:
+        beqz a0, top
+        bnez a0, top
+        blez a0, top
+        bgez a0, top
+        bltz a0, top
+        bgtz a0, top
+        bgt a0, a1, top
+        ble a0, a1, top
+        bgtu a0, a1, top
+        bleu a0, a1, top
+    ";
+
+    let instructions: Vec<_> = assemble(source)
+        .unwrap()
+        .instructions
+        .iter()
+        .map(|assembled| format!("{}", assembled.instruction.display(Syntax::Abi)))
+        .collect();
+
+    assert_eq!(
+        instructions,
+        [
+            "beq a0, zero, 0",
+            "bne a0, zero, -4",
+            "bge zero, a0, -8",
+            "bge a0, zero, -12",
+            "blt a0, zero, -16",
+            "blt zero, a0, -20",
+            "blt a1, a0, -24",
+            "bge a1, a0, -28",
+            "bltu a1, a0, -32",
+            "bgeu a1, a0, -36",
+        ]
+    );
+}
+
+#[test]
+fn jumps_and_calls() {
+    let source = "
+        call function
+        tail function
+        j function
+        jal function
+        function:
+        ret
+    ";
+
+    assert_eq!(
+        words(source),
+        [
+            0x00000097, // auipc ra, 0
+            0x018080e7, // jalr ra, ra, 24
+            0x00000317, // auipc t1, 0
+            0x01030067, // jalr x0, t1, 16
+            0x0080006f, // jal x0, 8
+            0x004000ef, // jal ra, 4
+            0x00008067, // ret
+        ]
+    );
+}
+
+#[test]
+fn far_call() {
+    let assembly = assemble("call 0x12345800").unwrap();
+    let printed: Vec<_> = assembly
+        .instructions
+        .iter()
+        .map(|assembled| format!("{}", assembled.instruction))
+        .collect();
+
+    assert_eq!(printed, ["auipc x1, 0x12346", "jalr x1, x1, -2048"]);
+}
+
+#[test]
+fn load_address() {
+    let source = "
+        nop
+        la a0, data
+        ret
+        data: .word 42
+    ";
+
+    assert_eq!(
+        words(source),
+        [
+            0x00000013, // nop
+            0x00000517, // auipc a0, 0
+            0x00c50513, // addi a0, a0, 12
+            0x00008067, // ret
+            42,
+        ]
+    );
+}
+
+#[test]
+fn data_directives() {
+    let assembly = assemble(".byte 1, 0xff, -1\n.half 0x1234\n.word -2, label\nlabel:").unwrap();
+
+    assert_eq!(
+        assembly.bytes,
+        [
+            0x01, 0xff, 0xff, // .byte
+            0x34, 0x12, // .half
+            0xfe, 0xff, 0xff, 0xff, // .word -2
+            0x0d, 0x00, 0x00, 0x00, // .word label
+        ]
+    );
+    assert!(assembly.instructions.is_empty());
+}
+
+#[test]
+fn instruction_offsets() {
+    let assembly = assemble(".word 0\nli a0, 0x10000\nret").unwrap();
+
+    assert_eq!(
+        assembly.instructions,
+        [
+            AssembledInstruction {
+                offset: 4,
+                instruction: RiscVInstruction::Lui {
+                    rd: Register::A0,
+                    imm: 0x10000,
+                },
+            },
+            AssembledInstruction {
+                offset: 8,
+                instruction: RiscVInstruction::Addi {
+                    rd: Register::A0,
+                    rs1: Register::A0,
+                    imm: 0,
+                },
+            },
+            AssembledInstruction {
+                offset: 12,
+                instruction: RiscVInstruction::Jalr {
+                    rd: Register::ZERO,
+                    rs1: Register::RA,
+                    imm: 0,
+                },
+            },
+        ]
+    );
+}
+
+#[test]
+fn display_round_trip() {
+    // Every decoded instruction assembles back from its Display output.
+    let samples = [
+        0x003100b3, 0x403100b3, 0x023140b3, 0x06400513, 0xfff5c513, 0x41f0d093, 0x0031b093,
+        0x00812503, 0xfff5c503, 0x00a12623, 0xfe20fce3, 0x123450b7, 0xfffff097, 0xfffff0ef,
+        0x004500e7, 0x00000073, 0x00100073,
+    ];
+
+    for word in samples {
+        let instruction = RiscVInstruction::decode(word).unwrap();
+        for syntax in [Syntax::Numeric, Syntax::Abi] {
+            let text = instruction.display(syntax).to_string();
+            assert_eq!(words(&text), [word], "{} did not assemble", text);
+        }
+    }
+}
+
+#[test]
+fn unknown_mnemonic() {
+    assert_eq!(
+        error("nop\n  frobnicate a0"),
+        (2, 3, AssembleErrorKind::UnknownMnemonic)
+    );
+}
+
+#[test]
+fn unknown_register() {
+    assert_eq!(
+        error("add a0, a8, a1"),
+        (1, 9, AssembleErrorKind::UnknownRegister)
+    );
+    assert_eq!(
+        error("lw a0, 4(x32)"),
+        (1, 10, AssembleErrorKind::UnknownRegister)
+    );
+    assert_eq!(
+        error("fadd.s fa0, a1, fa2"),
+        (1, 13, AssembleErrorKind::UnknownRegister)
+    );
+    assert_eq!(
+        error("feq.s fa0, fa1, fa2"),
+        (1, 7, AssembleErrorKind::UnknownRegister)
+    );
+}
+
+#[test]
+fn unknown_label() {
+    assert_eq!(
+        error("nop\nj nowhere"),
+        (2, 3, AssembleErrorKind::UnknownLabel)
+    );
+}
+
+#[test]
+fn duplicate_label() {
+    assert_eq!(
+        error("here: nop\n here: nop"),
+        (2, 2, AssembleErrorKind::DuplicateLabel)
+    );
+}
+
+#[test]
+fn invalid_label() {
+    assert_eq!(error("1abc: nop"), (1, 1, AssembleErrorKind::InvalidLabel));
+}
+
+#[test]
+fn invalid_number() {
+    assert_eq!(
+        error("addi a0, a0, 0xzz"),
+        (1, 14, AssembleErrorKind::InvalidNumber)
+    );
+    assert_eq!(
+        error("addi a0, a0, --1"),
+        (1, 14, AssembleErrorKind::InvalidNumber)
+    );
+}
+
+#[test]
+fn immediate_out_of_range() {
+    assert_eq!(
+        error("addi a0, a0, 2048"),
+        (1, 14, AssembleErrorKind::InvalidImmediate)
+    );
+    assert_eq!(
+        error("slli a0, a0, 64"),
+        (1, 14, AssembleErrorKind::InvalidImmediate)
+    );
+    assert_eq!(
+        error("slliw a0, a0, 32"),
+        (1, 15, AssembleErrorKind::InvalidImmediate)
+    );
+    assert_eq!(
+        error("rori a0, a0, 32"),
+        (1, 14, AssembleErrorKind::InvalidImmediate)
+    );
+    assert_eq!(
+        error("lui a0, 0x100000"),
+        (1, 9, AssembleErrorKind::InvalidImmediate)
+    );
+    assert_eq!(
+        error(".byte 256"),
+        (1, 7, AssembleErrorKind::InvalidImmediate)
+    );
+    assert_eq!(
+        error("csrr a0, 0x1000"),
+        (1, 10, AssembleErrorKind::InvalidImmediate)
+    );
+    assert_eq!(
+        error("csrrwi a0, cycle, 32"),
+        (1, 19, AssembleErrorKind::InvalidImmediate)
+    );
+    assert_eq!(
+        error("amoadd.w a0, a2, 4(a1)"),
+        (1, 18, AssembleErrorKind::InvalidImmediate)
+    );
+}
+
+#[test]
+fn branch_out_of_range() {
+    assert_eq!(
+        error("beq a0, a1, 4096"),
+        (1, 13, AssembleErrorKind::InvalidImmediate)
+    );
+    assert_eq!(
+        error("beqz a0, 3"),
+        (1, 10, AssembleErrorKind::InvalidImmediate)
+    );
+
+    let far = format!(
+        "beq a0, a1, far\n.word {}\nfar: nop",
+        vec!["0"; 1024].join(", ")
+    );
+    assert_eq!(error(&far), (1, 13, AssembleErrorKind::InvalidImmediate));
+}
+
+#[test]
+fn wrong_operands() {
+    assert_eq!(
+        error("add a0, a1"),
+        (1, 1, AssembleErrorKind::InvalidOperands)
+    );
+    assert_eq!(error("ret a0"), (1, 1, AssembleErrorKind::InvalidOperands));
+    assert_eq!(
+        error("add a0, , a1"),
+        (1, 9, AssembleErrorKind::InvalidOperands)
+    );
+    assert_eq!(
+        error("lw a0, 8"),
+        (1, 8, AssembleErrorKind::InvalidOperands)
+    );
+    assert_eq!(error(".word"), (1, 1, AssembleErrorKind::InvalidOperands));
+    assert_eq!(
+        error("fence wr, rw"),
+        (1, 7, AssembleErrorKind::InvalidOperands)
+    );
+    assert_eq!(
+        error("fence.i a0"),
+        (1, 1, AssembleErrorKind::InvalidOperands)
+    );
+    assert_eq!(
+        error("fadd.s fa0, fa1, fa2, up"),
+        (1, 23, AssembleErrorKind::InvalidOperands)
+    );
+    assert_eq!(
+        error("fsgnj.s fa0, fa1, fa2, rtz"),
+        (1, 1, AssembleErrorKind::InvalidOperands)
+    );
+}
+
+#[test]
+fn unknown_directive() {
+    assert_eq!(
+        error("  .align 4"),
+        (1, 3, AssembleErrorKind::UnknownDirective)
+    );
+}
+
+#[test]
+fn error_display() {
+    let error = assemble("nop\nadd a0, a1, q1").unwrap_err();
+    assert_eq!(format!("{}", error), "2:13: unknown register");
+}
@@ -0,0 +1,40 @@
+use crate::atomic::Reservation;
+
+#[test]
+fn store_conditional_after_load_reserved() {
+    let mut reservation = Reservation::default();
+    reservation.reserve(0x100);
+
+    assert!(reservation.release(0x100));
+    // The reservation is released by the first store-conditional.
+    assert!(!reservation.release(0x100));
+}
+
+#[test]
+fn store_conditional_without_reservation() {
+    let mut reservation = Reservation::default();
+
+    assert!(!reservation.release(0x100));
+}
+
+#[test]
+fn store_conditional_to_another_address() {
+    let mut reservation = Reservation::default();
+    reservation.reserve(0x100);
+
+    assert!(!reservation.release(0x104));
+    assert!(!reservation.release(0x100));
+}
+
+#[test]
+fn load_reserved_replaces_the_reservation() {
+    let mut reservation = Reservation::default();
+    reservation.reserve(0x100);
+    reservation.reserve(0x200);
+
+    assert!(!reservation.release(0x100));
+
+    reservation.reserve(0x100);
+    reservation.reserve(0x200);
+    assert!(reservation.release(0x200));
+}
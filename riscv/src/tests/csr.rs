@@ -0,0 +1,26 @@
+use crate::csr::{CYCLE, CYCLEH, Counters, INSTRET, INSTRETH, TIME, TIMEH};
+
+#[test]
+fn counters() {
+    let counters = Counters {
+        gas_consumed: 0x0000_0001_8000_0000,
+        time: 0x1234_5678_9abc_def0,
+        instret: 42,
+    };
+
+    assert_eq!(counters.read(CYCLE), Some(0x8000_0000));
+    assert_eq!(counters.read(CYCLEH), Some(0x0000_0001));
+    assert_eq!(counters.read(TIME), Some(0x9abc_def0));
+    assert_eq!(counters.read(TIMEH), Some(0x1234_5678));
+    assert_eq!(counters.read(INSTRET), Some(42));
+    assert_eq!(counters.read(INSTRETH), Some(0));
+}
+
+#[test]
+fn other_csrs() {
+    let counters = Counters::default();
+
+    // mscratch, a machine-mode CSR, and the hpmcounter3 user counter.
+    assert_eq!(counters.read(0x340), None);
+    assert_eq!(counters.read(0xc03), None);
+}
@@ -0,0 +1,407 @@
+use crate::assembler::assemble;
+use crate::custom::{
+    CustomForm, CustomInstruction, CustomInstructions, CustomOpcode, CustomOperands, RegisterFile,
+};
+use crate::error::{DecodeError, DecodeErrorKind, Error};
+use crate::extension::{Extension, Extensions};
+use crate::instruction::RiscVInstruction;
+use crate::profile::Profile;
+use crate::register::{Register, Syntax};
+use crate::stream::{DecodedInstruction, InstructionStream};
+
+/// Multiplies `rs1` by `rs2` into the register pair starting at `rd`.
+fn widening_multiply(registers: &mut RegisterFile<'_>, operands: CustomOperands, _context: u64) {
+    if let CustomOperands::R { rd, rs1, rs2, .. } = operands {
+        let product = registers.read(rs1) as u128 * registers.read(rs2) as u128;
+        let high = Register::new(rd.number() + 1).unwrap();
+        registers.write(rd, product as u64);
+        registers.write(high, (product >> 64) as u64);
+    }
+}
+
+/// Adds the immediate and the context to `rs1`.
+fn add_context(registers: &mut RegisterFile<'_>, operands: CustomOperands, context: u64) {
+    if let CustomOperands::I { rd, rs1, imm } = operands {
+        let value = registers.read(rs1).wrapping_add(imm as u64);
+        registers.write(rd, value.wrapping_add(context));
+    }
+}
+
+fn r_type(opcode: CustomOpcode, funct3: u8, funct7: u8) -> CustomInstruction {
+    CustomInstruction {
+        opcode,
+        funct3,
+        form: CustomForm::R { funct7 },
+        gas: 10,
+        handler: widening_multiply,
+    }
+}
+
+fn i_type(opcode: CustomOpcode, funct3: u8) -> CustomInstruction {
+    CustomInstruction {
+        opcode,
+        funct3,
+        form: CustomForm::I,
+        gas: 2,
+        handler: add_context,
+    }
+}
+
+fn registry() -> CustomInstructions {
+    CustomInstructions::NONE
+        .with(r_type(CustomOpcode::Custom0, 1, 2))
+        .unwrap()
+        .with(i_type(CustomOpcode::Custom1, 3))
+        .unwrap()
+}
+
+fn decode(word: u32) -> Result<RiscVInstruction, DecodeError> {
+    RiscVInstruction::decode_custom(word, Profile::Rv32i, Extensions::NONE, &registry())
+}
+
+#[test]
+fn opcodes() {
+    assert_eq!(CustomOpcode::new(0x0b), Some(CustomOpcode::Custom0));
+    assert_eq!(CustomOpcode::new(0x2b), Some(CustomOpcode::Custom1));
+    assert_eq!(CustomOpcode::new(0x33), None);
+    assert_eq!(CustomOpcode::Custom1.opcode(), 0x2b);
+    assert_eq!(CustomOpcode::Custom0.to_string(), "0x0b");
+}
+
+#[test]
+fn decode_registered() {
+    assert_eq!(
+        decode(0x04c5950b), // .insn r 0x0b, 1, 2, x10, x11, x12
+        Ok(RiscVInstruction::Custom {
+            opcode: CustomOpcode::Custom0,
+            funct3: 1,
+            operands: CustomOperands::R {
+                funct7: 2,
+                rd: Register::X10,
+                rs1: Register::X11,
+                rs2: Register::X12,
+            },
+        })
+    );
+    assert_eq!(
+        decode(0xffb5b52b), // .insn i 0x2b, 3, x10, x11, -5
+        Ok(RiscVInstruction::Custom {
+            opcode: CustomOpcode::Custom1,
+            funct3: 3,
+            operands: CustomOperands::I {
+                rd: Register::X10,
+                rs1: Register::X11,
+                imm: -5,
+            },
+        })
+    );
+}
+
+#[test]
+fn decode_unregistered() {
+    for word in [
+        0x04c5850b, // funct3 0 of custom-0
+        0x06c5950b, // funct7 3 of custom-0 funct3 1
+        0xffb5b50b, // the I-type instruction under custom-0
+        0x04c5952b, // the R-type instruction under custom-1
+    ] {
+        assert_eq!(
+            decode(word),
+            Err(DecodeError {
+                word,
+                kind: DecodeErrorKind::UnregisteredCustom,
+            })
+        );
+    }
+
+    // Without a registry, custom instructions are never decoded.
+    assert_eq!(
+        RiscVInstruction::decode(0x04c5950b),
+        Err(DecodeError {
+            word: 0x04c5950b,
+            kind: DecodeErrorKind::UnregisteredCustom,
+        })
+    );
+}
+
+#[test]
+fn decode_other_instructions() {
+    assert_eq!(
+        decode(0x003100b3), // add x1, x2, x3
+        Ok(RiscVInstruction::Add {
+            rd: Register::X1,
+            rs1: Register::X2,
+            rs2: Register::X3,
+        })
+    );
+    assert_eq!(
+        decode(0x403170b3), // andn x1, x2, x3
+        Err(DecodeError {
+            word: 0x403170b3,
+            kind: DecodeErrorKind::UnsupportedExtension(Extension::Zbb),
+        })
+    );
+}
+
+#[test]
+fn decode_checks_profile() {
+    let word = 0x7ff8078b; // .insn i 0x0b, 0, x15, x16, 2047
+    let custom = CustomInstructions::NONE
+        .with(i_type(CustomOpcode::Custom0, 0))
+        .unwrap();
+
+    assert_eq!(
+        RiscVInstruction::decode_custom(word, Profile::Rv32e, Extensions::NONE, &custom),
+        Err(DecodeError {
+            word,
+            kind: DecodeErrorKind::UnsupportedRegister {
+                register: Register::X16,
+                profile: Profile::Rv32e,
+            },
+        })
+    );
+    assert!(
+        RiscVInstruction::decode_custom(word, Profile::Rv64i, Extensions::NONE, &custom).is_ok()
+    );
+}
+
+#[test]
+fn encode_round_trip() {
+    for word in [0x04c5950b, 0xffb5b52b] {
+        assert_eq!(decode(word).unwrap().encode(), Ok(word));
+    }
+
+    let instruction = RiscVInstruction::Custom {
+        opcode: CustomOpcode::Custom0,
+        funct3: 7,
+        operands: CustomOperands::R {
+            funct7: 0x7f,
+            rd: Register::X1,
+            rs1: Register::X2,
+            rs2: Register::X3,
+        },
+    };
+    assert_eq!(instruction.encode(), Ok(0xfe31708b));
+}
+
+#[test]
+fn encode_out_of_range() {
+    let r = |funct3, funct7| RiscVInstruction::Custom {
+        opcode: CustomOpcode::Custom0,
+        funct3,
+        operands: CustomOperands::R {
+            funct7,
+            rd: Register::X1,
+            rs1: Register::X2,
+            rs2: Register::X3,
+        },
+    };
+    let i = |imm| RiscVInstruction::Custom {
+        opcode: CustomOpcode::Custom1,
+        funct3: 0,
+        operands: CustomOperands::I {
+            rd: Register::X1,
+            rs1: Register::X2,
+            imm,
+        },
+    };
+
+    assert_eq!(r(8, 0).encode(), Err(Error::InvalidImmediate));
+    assert_eq!(r(0, 0x80).encode(), Err(Error::InvalidImmediate));
+    assert_eq!(i(2048).encode(), Err(Error::InvalidImmediate));
+}
+
+#[test]
+fn display() {
+    let r = decode(0x04c5950b).unwrap();
+    let i = decode(0xffb5b52b).unwrap();
+
+    assert_eq!(r.to_string(), ".insn r 0x0b, 1, 2, x10, x11, x12");
+    assert_eq!(i.to_string(), ".insn i 0x2b, 3, x10, x11, -5");
+    assert_eq!(
+        r.display(Syntax::Abi).to_string(),
+        ".insn r 0x0b, 1, 2, a0, a1, a2"
+    );
+}
+
+#[test]
+fn assemble_display() {
+    for word in [0x04c5950b, 0xffb5b52b, 0xfe31708b] {
+        let instruction = RiscVInstruction::decode_custom(
+            word,
+            Profile::Rv32i,
+            Extensions::NONE,
+            &registry()
+                .with(r_type(CustomOpcode::Custom0, 7, 0x7f))
+                .unwrap(),
+        )
+        .unwrap();
+        let assembly = assemble(&instruction.to_string()).unwrap();
+        assert_eq!(assembly.bytes, word.to_le_bytes());
+    }
+}
+
+#[test]
+fn assemble_errors() {
+    for source in [
+        ".insn r 0x33, 0, 0, a0, a1, a2", // not a custom opcode
+        ".insn r 0x0b, 8, 0, a0, a1, a2",
+        ".insn r 0x0b, 0, 128, a0, a1, a2",
+        ".insn i 0x0b, 0, a0, a1, 2048",
+        ".insn r 0x0b, 0, a0, a1, a2",
+        ".insn s 0x0b, 0, a0, 0(a1)",
+        ".insn",
+    ] {
+        assert!(assemble(source).is_err(), "{}", source);
+    }
+}
+
+#[test]
+fn metadata() {
+    let r = decode(0x04c5950b).unwrap();
+    let i = decode(0xffb5b52b).unwrap();
+
+    assert_eq!(
+        r.reads().collect::<Vec<_>>(),
+        [Register::X11, Register::X12]
+    );
+    assert_eq!(r.writes(), Some(Register::X10));
+    assert_eq!(i.reads().collect::<Vec<_>>(), [Register::X11]);
+    assert_eq!(i.writes(), Some(Register::X10));
+    assert_eq!(r.extension(), None);
+    assert_eq!(r.memory_access(), None);
+}
+
+#[test]
+fn registration_conflicts() {
+    let custom = registry();
+
+    for instruction in [
+        r_type(CustomOpcode::Custom0, 1, 2),
+        i_type(CustomOpcode::Custom0, 1),
+        r_type(CustomOpcode::Custom1, 3, 0),
+        i_type(CustomOpcode::Custom1, 3),
+    ] {
+        assert_eq!(
+            custom.clone().with(instruction).map(|_| ()),
+            Err(Error::InvalidCustomInstruction {
+                opcode: instruction.opcode,
+                funct3: instruction.funct3,
+            })
+        );
+    }
+
+    // Neighbouring encodings are still free.
+    assert!(
+        custom
+            .clone()
+            .with(r_type(CustomOpcode::Custom0, 1, 3))
+            .is_ok()
+    );
+    assert!(
+        custom
+            .clone()
+            .with(r_type(CustomOpcode::Custom1, 2, 0))
+            .is_ok()
+    );
+}
+
+#[test]
+fn registration_field_ranges() {
+    assert!(
+        CustomInstructions::NONE
+            .with(r_type(CustomOpcode::Custom0, 8, 0))
+            .is_err()
+    );
+    assert!(
+        CustomInstructions::NONE
+            .with(r_type(CustomOpcode::Custom0, 0, 0x80))
+            .is_err()
+    );
+}
+
+#[test]
+fn find_registered() {
+    let custom = registry();
+    let found = custom.find(&decode(0xffb5b52b).unwrap()).unwrap();
+
+    assert_eq!(found.opcode, CustomOpcode::Custom1);
+    assert_eq!(found.gas, 2);
+    assert!(custom.find(&RiscVInstruction::Ecall).is_none());
+    assert_eq!(custom.iter().count(), 2);
+}
+
+#[test]
+fn handler() {
+    let custom = registry();
+    let instruction = decode(0x04c5950b).unwrap();
+    let RiscVInstruction::Custom { operands, .. } = instruction else {
+        unreachable!();
+    };
+
+    let mut values = [0; 32];
+    values[11] = u64::MAX;
+    values[12] = 3;
+    let mut registers = RegisterFile::new(&mut values, Profile::Rv64i);
+    (custom.find(&instruction).unwrap().handler)(&mut registers, operands, 0);
+
+    assert_eq!(values[10], u64::MAX - 2);
+    assert_eq!(values[11], 2);
+}
+
+#[test]
+fn register_file_rv32() {
+    let mut values = [0; 32];
+    values[5] = 0x1_2345_6789;
+    let mut registers = RegisterFile::new(&mut values, Profile::Rv32e);
+
+    assert_eq!(registers.profile(), Profile::Rv32e);
+    assert_eq!(registers.read(Register::X5), 0x2345_6789);
+
+    registers.write(Register::X6, u64::MAX);
+    registers.write(Register::X0, 1);
+    registers.write(Register::X16, 1);
+
+    assert_eq!(registers.read(Register::X6), 0xffff_ffff);
+    assert_eq!(registers.read(Register::X0), 0);
+    assert_eq!(registers.read(Register::X16), 0);
+    assert_eq!(values[6], 0xffff_ffff);
+    assert_eq!(values[16], 0);
+}
+
+#[test]
+fn register_file_rv64() {
+    let mut values = [0; 32];
+    values[0] = 7;
+    let mut registers = RegisterFile::new(&mut values, Profile::Rv64i);
+
+    registers.write(Register::X31, u64::MAX);
+
+    assert_eq!(registers.read(Register::X31), u64::MAX);
+    assert_eq!(registers.read(Register::X0), 0);
+}
+
+#[test]
+fn stream() {
+    let custom = registry();
+    let mut code = vec![];
+    code.extend(0x0001_u16.to_le_bytes()); // c.nop
+    code.extend(0xffb5b52b_u32.to_le_bytes()); // .insn i 0x2b, 3, x10, x11, -5
+    code.extend(0x04c5850b_u32.to_le_bytes()); // unregistered
+
+    let decoded: Vec<_> =
+        InstructionStream::with_custom(&code, Profile::Rv32i, Extensions::NONE, &custom).collect();
+
+    assert_eq!(decoded[1].instruction, decode(0xffb5b52b));
+    assert_eq!(
+        decoded[2],
+        DecodedInstruction {
+            offset: 6,
+            length: 4,
+            instruction: Err(DecodeError {
+                word: 0x04c5850b,
+                kind: DecodeErrorKind::UnregisteredCustom,
+            }),
+        }
+    );
+}
@@ -0,0 +1,184 @@
+use crate::assembler::assemble;
+use crate::disassembler::{DisassembledInstruction, disassemble};
+use crate::error::{DecodeError, DecodeErrorKind};
+use crate::instruction::RiscVInstruction;
+use crate::register::{Register, Syntax};
+
+#[test]
+fn empty() {
+    let disassembly = disassemble(&[]);
+    assert!(disassembly.instructions.is_empty());
+    assert!(disassembly.labels.is_empty());
+    assert_eq!(disassembly.to_string(), "");
+}
+
+#[test]
+fn offsets_and_raw_words() {
+    let mut code = vec![];
+    code.extend(0x003100b3_u32.to_le_bytes()); // add x1, x2, x3
+    code.extend(0x1141_u16.to_le_bytes()); // c.addi x2, -16
+
+    let disassembly = disassemble(&code);
+
+    assert_eq!(
+        disassembly.instructions,
+        vec![
+            DisassembledInstruction {
+                offset: 0,
+                length: 4,
+                raw: 0x003100b3,
+                instruction: Ok(RiscVInstruction::Add {
+                    rd: Register::X1,
+                    rs1: Register::X2,
+                    rs2: Register::X3,
+                }),
+            },
+            DisassembledInstruction {
+                offset: 4,
+                length: 2,
+                raw: 0x1141,
+                instruction: Ok(RiscVInstruction::Addi {
+                    rd: Register::X2,
+                    rs1: Register::X2,
+                    imm: -16,
+                }),
+            },
+        ]
+    );
+    assert_eq!(
+        disassembly.to_string(),
+        "       0:  003100b3  add x1, x2, x3\n       4:  1141      addi x2, x2, -16\n"
+    );
+}
+
+#[test]
+fn backward_branch_label() {
+    let code = assemble("loop:\naddi a0, a0, -1\nbnez a0, loop\n")
+        .unwrap()
+        .bytes;
+
+    let disassembly = disassemble(&code);
+
+    assert_eq!(disassembly.label(0), Some("L0"));
+    assert_eq!(
+        disassembly.display(Syntax::Abi).to_string(),
+        "L0:\n       0:  fff50513  addi a0, a0, -1\n       4:  fe051ee3  bne a0, zero, -4 <L0>\n"
+    );
+}
+
+#[test]
+fn labels_in_offset_order() {
+    let source = "
+        beqz a0, done
+        jal ra, func
+    done:
+        ret
+    func:
+        j done
+    ";
+    let code = assemble(source).unwrap().bytes;
+
+    let disassembly = disassemble(&code);
+
+    assert_eq!(
+        disassembly.labels.into_iter().collect::<Vec<_>>(),
+        [(8, "L0".to_string()), (12, "L1".to_string())]
+    );
+}
+
+#[test]
+fn shared_target_has_one_label() {
+    let source = "
+    top:
+        beqz a0, top
+        bnez a1, top
+        j top
+    ";
+    let code = assemble(source).unwrap().bytes;
+
+    let disassembly = disassemble(&code);
+
+    assert_eq!(disassembly.labels.len(), 1);
+    assert_eq!(disassembly.label(0), Some("L0"));
+}
+
+#[test]
+fn target_outside_buffer_is_not_labelled() {
+    let code = assemble("j 64\nbeqz a0, -8").unwrap().bytes;
+
+    let disassembly = disassemble(&code);
+
+    assert!(disassembly.labels.is_empty());
+    assert_eq!(
+        disassembly.to_string(),
+        "       0:  0400006f  jal x0, 64\n       4:  fe050ce3  beq x10, x0, -8\n"
+    );
+}
+
+#[test]
+fn target_inside_instruction_is_not_labelled() {
+    // jal x0, 2 jumps into the middle of itself.
+    let code = 0x0020006f_u32.to_le_bytes();
+
+    let disassembly = disassemble(&code);
+
+    assert!(disassembly.labels.is_empty());
+}
+
+#[test]
+fn compressed_branch_label() {
+    let mut code = vec![];
+    code.extend(0x0001_u16.to_le_bytes()); // c.nop
+    code.extend(0xdd7d_u16.to_le_bytes()); // c.beqz x10, -2
+
+    let disassembly = disassemble(&code);
+
+    assert_eq!(
+        disassembly.to_string(),
+        "L0:\n       0:  0001      addi x0, x0, 0\n       2:  dd7d      beq x10, x0, -2 <L0>\n"
+    );
+}
+
+#[test]
+fn undecodable_words_are_marked() {
+    let mut code = vec![];
+    code.extend(0x0000007f_u32.to_le_bytes()); // unknown opcode
+    code.extend(0x0000_u16.to_le_bytes()); // illegal compressed instruction
+    code.extend(0x00000013_u32.to_le_bytes()); // addi x0, x0, 0
+
+    let disassembly = disassemble(&code);
+
+    assert_eq!(
+        disassembly.instructions[0].instruction,
+        Err(DecodeError {
+            word: 0x0000007f,
+            kind: DecodeErrorKind::UnknownOpcode,
+        })
+    );
+    assert_eq!(
+        disassembly.to_string(),
+        concat!(
+            "       0:  0000007f  .word 0x0000007f  # unknown opcode\n",
+            "       4:  0000      .half 0x0000  # reserved compressed encoding\n",
+            "       6:  00000013  addi x0, x0, 0\n",
+        )
+    );
+}
+
+#[test]
+fn truncated_tail() {
+    let mut code = vec![];
+    code.extend(0x00000013_u32.to_le_bytes()); // addi x0, x0, 0
+    code.extend([0x13, 0x00, 0x00]); // the first three bytes of another addi
+
+    let disassembly = disassemble(&code);
+
+    assert_eq!(disassembly.instructions[1].length, 3);
+    assert_eq!(
+        disassembly.to_string(),
+        concat!(
+            "       0:  00000013  addi x0, x0, 0\n",
+            "       4:  000013    .byte 0x13, 0x00, 0x00  # truncated instruction\n",
+        )
+    );
+}
@@ -0,0 +1,442 @@
+use crate::csr::{FCSR, FFLAGS, FRM};
+use crate::error::Error;
+use crate::float::{DOUBLE, ExceptionFlags, Fcsr, Round, RoundingMode, SINGLE, nan_box, unbox};
+
+/// A small deterministic generator so that sampled operands are the same on every run.
+fn next_random(state: &mut u64) -> u64 {
+    *state = state
+        .wrapping_mul(6364136223846793005)
+        .wrapping_add(1442695040888963407);
+    *state
+}
+
+/// Single-precision operands: random bit patterns mixed with zeros, infinities,
+/// NaNs, subnormals and the extremes of the normal range.
+fn single_operands() -> Vec<f32> {
+    let mut operands = vec![
+        0.0,
+        -0.0,
+        1.0,
+        -1.5,
+        f32::INFINITY,
+        f32::NEG_INFINITY,
+        f32::NAN,
+        f32::MIN_POSITIVE,
+        f32::from_bits(1),
+        f32::from_bits(0x807f_ffff),
+        f32::MAX,
+        f32::MIN,
+    ];
+
+    let mut state = 1;
+    for _ in 0..48 {
+        let bits = next_random(&mut state) >> 32;
+        operands.push(f32::from_bits(bits as u32));
+    }
+
+    operands
+}
+
+/// The double-precision counterpart of [`single_operands`].
+fn double_operands() -> Vec<f64> {
+    let mut operands = vec![
+        0.0,
+        -0.0,
+        1.0,
+        -1.5,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+        f64::NAN,
+        f64::MIN_POSITIVE,
+        f64::from_bits(1),
+        f64::from_bits(0x800f_ffff_ffff_ffff),
+        f64::MAX,
+        f64::MIN,
+    ];
+
+    let mut state = 2;
+    for _ in 0..48 {
+        operands.push(f64::from_bits(next_random(&mut state)));
+    }
+
+    operands
+}
+
+/// Asserts that a single-precision result matches the host, which rounds to nearest.
+/// NaN results must be the canonical NaN, whatever NaN the host produced.
+fn assert_single(result: u64, expected: f32, operation: &str) {
+    let expected = if expected.is_nan() {
+        SINGLE.canonical_nan()
+    } else {
+        expected.to_bits() as u64
+    };
+
+    assert_eq!(result, expected, "{}", operation);
+}
+
+/// The double-precision counterpart of [`assert_single`].
+fn assert_double(result: u64, expected: f64, operation: &str) {
+    let expected = if expected.is_nan() {
+        DOUBLE.canonical_nan()
+    } else {
+        expected.to_bits()
+    };
+
+    assert_eq!(result, expected, "{}", operation);
+}
+
+#[test]
+fn single_matches_host() {
+    let operands = single_operands();
+    let mut flags = ExceptionFlags::NONE;
+    let flags = &mut flags;
+    let round = Round::NearestEven;
+
+    for &a in &operands {
+        let x = a.to_bits() as u64;
+        assert_single(SINGLE.sqrt(x, round, flags), a.sqrt(), &format!("sqrt {a}"));
+
+        for &b in &operands {
+            let y = b.to_bits() as u64;
+            assert_single(SINGLE.add(x, y, round, flags), a + b, &format!("{a} + {b}"));
+            assert_single(SINGLE.sub(x, y, round, flags), a - b, &format!("{a} - {b}"));
+            assert_single(SINGLE.mul(x, y, round, flags), a * b, &format!("{a} * {b}"));
+            assert_single(SINGLE.div(x, y, round, flags), a / b, &format!("{a} / {b}"));
+
+            for &c in operands.iter().step_by(7) {
+                let z = c.to_bits() as u64;
+                assert_single(
+                    SINGLE.fused_multiply_add(x, y, z, round, flags),
+                    a.mul_add(b, c),
+                    &format!("{a} * {b} + {c}"),
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn double_matches_host() {
+    let operands = double_operands();
+    let mut flags = ExceptionFlags::NONE;
+    let flags = &mut flags;
+    let round = Round::NearestEven;
+
+    for &a in &operands {
+        let x = a.to_bits();
+        assert_double(DOUBLE.sqrt(x, round, flags), a.sqrt(), &format!("sqrt {a}"));
+
+        for &b in &operands {
+            let y = b.to_bits();
+            assert_double(DOUBLE.add(x, y, round, flags), a + b, &format!("{a} + {b}"));
+            assert_double(DOUBLE.sub(x, y, round, flags), a - b, &format!("{a} - {b}"));
+            assert_double(DOUBLE.mul(x, y, round, flags), a * b, &format!("{a} * {b}"));
+            assert_double(DOUBLE.div(x, y, round, flags), a / b, &format!("{a} / {b}"));
+
+            for &c in operands.iter().step_by(7) {
+                assert_double(
+                    DOUBLE.fused_multiply_add(x, y, c.to_bits(), round, flags),
+                    a.mul_add(b, c),
+                    &format!("{a} * {b} + {c}"),
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn conversions_match_host() {
+    let mut flags = ExceptionFlags::NONE;
+    let flags = &mut flags;
+    let round = Round::NearestEven;
+
+    for a in double_operands() {
+        let single = DOUBLE.convert(SINGLE, a.to_bits(), round, flags);
+        assert_single(single, a as f32, &format!("{a} as f32"));
+    }
+
+    for a in single_operands() {
+        let double = SINGLE.convert(DOUBLE, a.to_bits() as u64, round, flags);
+        assert_double(double, a as f64, &format!("{a} as f64"));
+    }
+
+    let mut state = 3;
+    for _ in 0..1000 {
+        let value = (next_random(&mut state) >> 32) as u32;
+        assert_single(
+            SINGLE.int_to_float(value, true, round, flags),
+            value as i32 as f32,
+            &format!("{value} as i32 as f32"),
+        );
+        assert_single(
+            SINGLE.int_to_float(value, false, round, flags),
+            value as f32,
+            &format!("{value} as f32"),
+        );
+        assert_double(
+            DOUBLE.int_to_float(value, true, round, flags),
+            value as i32 as f64,
+            &format!("{value} as i32 as f64"),
+        );
+    }
+}
+
+#[test]
+fn directed_rounding() {
+    let third = |round| {
+        let (one, three) = (1.0_f32.to_bits() as u64, 3.0_f32.to_bits() as u64);
+        SINGLE.div(one, three, round, &mut ExceptionFlags::default())
+    };
+
+    let nearest = 1.0_f32 / 3.0;
+    assert_eq!(third(Round::NearestEven), nearest.to_bits() as u64);
+    assert_eq!(third(Round::NearestMaxMagnitude), nearest.to_bits() as u64);
+    // 1/3 rounds up to nearest, so rounding down gives the previous value.
+    assert_eq!(third(Round::Up), nearest.to_bits() as u64);
+    assert_eq!(third(Round::Down), nearest.to_bits() as u64 - 1);
+    assert_eq!(third(Round::TowardZero), nearest.to_bits() as u64 - 1);
+
+    // Ties: 1 + 2^-24 is halfway between 1 and the next single.
+    let one = 1.0_f32.to_bits() as u64;
+    let half_ulp = (2.0_f32).powi(-24).to_bits() as u64;
+    let mut flags = ExceptionFlags::NONE;
+    let flags = &mut flags;
+    assert_eq!(SINGLE.add(one, half_ulp, Round::NearestEven, flags), one);
+    assert_eq!(
+        SINGLE.add(one, half_ulp, Round::NearestMaxMagnitude, flags),
+        one + 1
+    );
+}
+
+#[test]
+fn overflow_by_rounding_mode() {
+    let max = f64::MAX.to_bits();
+    let overflow = |round| {
+        let mut flags = ExceptionFlags::NONE;
+        let result = DOUBLE.mul(max, 2.0_f64.to_bits(), round, &mut flags);
+        assert_eq!(
+            flags,
+            ExceptionFlags::OVERFLOW.with(ExceptionFlags::INEXACT)
+        );
+        result
+    };
+
+    assert_eq!(overflow(Round::NearestEven), f64::INFINITY.to_bits());
+    assert_eq!(
+        overflow(Round::NearestMaxMagnitude),
+        f64::INFINITY.to_bits()
+    );
+    assert_eq!(overflow(Round::Up), f64::INFINITY.to_bits());
+    assert_eq!(overflow(Round::TowardZero), max);
+    assert_eq!(overflow(Round::Down), max);
+}
+
+#[test]
+fn exception_flags() {
+    let one = 1.0_f32.to_bits() as u64;
+    let zero = 0.0_f32.to_bits() as u64;
+    let infinity = f32::INFINITY.to_bits() as u64;
+    let signaling = 0x7f80_0001;
+    let round = Round::NearestEven;
+
+    let flags_of = |operation: &dyn Fn(&mut ExceptionFlags) -> u64| {
+        let mut flags = ExceptionFlags::NONE;
+        operation(&mut flags);
+        flags
+    };
+
+    assert_eq!(
+        flags_of(&|flags| SINGLE.add(one, one, round, flags)),
+        ExceptionFlags::NONE
+    );
+    assert_eq!(
+        flags_of(&|flags| SINGLE.div(one, zero, round, flags)),
+        ExceptionFlags::DIVIDE_BY_ZERO
+    );
+    assert_eq!(
+        flags_of(&|flags| SINGLE.mul(zero, infinity, round, flags)),
+        ExceptionFlags::INVALID
+    );
+    assert_eq!(
+        flags_of(&|flags| SINGLE.add(signaling, one, round, flags)),
+        ExceptionFlags::INVALID
+    );
+    assert_eq!(
+        flags_of(&|flags| SINGLE.div(one, 3.0_f32.to_bits() as u64, round, flags)),
+        ExceptionFlags::INEXACT
+    );
+    // The smallest normal squared is tiny and inexact.
+    let min = f32::MIN_POSITIVE.to_bits() as u64;
+    assert_eq!(
+        flags_of(&|flags| SINGLE.mul(min, min, round, flags)),
+        ExceptionFlags::UNDERFLOW.with(ExceptionFlags::INEXACT)
+    );
+    // Exact subnormal results do not underflow.
+    assert_eq!(
+        flags_of(&|flags| SINGLE.sub(min, 1, round, flags)),
+        ExceptionFlags::NONE
+    );
+}
+
+#[test]
+fn comparisons() {
+    let one = 1.0_f64.to_bits();
+    let quiet = f64::NAN.to_bits();
+    let signaling = 0x7ff0_0000_0000_0001;
+    let mut flags = ExceptionFlags::NONE;
+
+    assert!(DOUBLE.eq(0.0_f64.to_bits(), (-0.0_f64).to_bits(), &mut flags));
+    assert!(!DOUBLE.lt(0.0_f64.to_bits(), (-0.0_f64).to_bits(), &mut flags));
+    assert!(DOUBLE.le(one, one, &mut flags));
+    assert_eq!(flags, ExceptionFlags::NONE);
+
+    // Quiet NaNs only raise the invalid flag for the ordered comparisons.
+    assert!(!DOUBLE.eq(quiet, one, &mut flags));
+    assert_eq!(flags, ExceptionFlags::NONE);
+    assert!(!DOUBLE.lt(quiet, one, &mut flags));
+    assert_eq!(flags, ExceptionFlags::INVALID);
+
+    let mut flags = ExceptionFlags::NONE;
+    assert!(!DOUBLE.eq(signaling, one, &mut flags));
+    assert_eq!(flags, ExceptionFlags::INVALID);
+}
+
+#[test]
+fn min_max() {
+    let one = 1.0_f32.to_bits() as u64;
+    let zero = 0.0_f32.to_bits() as u64;
+    let negative_zero = (-0.0_f32).to_bits() as u64;
+    let quiet = SINGLE.canonical_nan();
+    let mut flags = ExceptionFlags::NONE;
+
+    assert_eq!(
+        SINGLE.min_max(zero, negative_zero, false, &mut flags),
+        negative_zero
+    );
+    assert_eq!(SINGLE.min_max(negative_zero, zero, true, &mut flags), zero);
+    assert_eq!(SINGLE.min_max(quiet, one, false, &mut flags), one);
+    assert_eq!(SINGLE.min_max(quiet, quiet, true, &mut flags), quiet);
+    assert_eq!(flags, ExceptionFlags::NONE);
+
+    assert_eq!(SINGLE.min_max(0x7f80_0001, one, true, &mut flags), one);
+    assert_eq!(flags, ExceptionFlags::INVALID);
+}
+
+#[test]
+fn classify() {
+    let cases = [
+        (f64::NEG_INFINITY, 1 << 0),
+        (-1.0, 1 << 1),
+        (-f64::from_bits(1), 1 << 2),
+        (-0.0, 1 << 3),
+        (0.0, 1 << 4),
+        (f64::from_bits(1), 1 << 5),
+        (1.0, 1 << 6),
+        (f64::INFINITY, 1 << 7),
+        (f64::from_bits(0x7ff0_0000_0000_0001), 1 << 8),
+        (f64::NAN, 1 << 9),
+    ];
+
+    for (value, class) in cases {
+        assert_eq!(DOUBLE.classify(value.to_bits()), class, "{}", value);
+    }
+}
+
+#[test]
+fn integer_conversion_saturates() {
+    let round = Round::TowardZero;
+    let convert = |value: f32, signed| {
+        let mut flags = ExceptionFlags::NONE;
+        let result = SINGLE.to_int(value.to_bits() as u64, signed, round, &mut flags);
+        (result, flags)
+    };
+
+    assert_eq!(
+        convert(-2.5, true),
+        (-2_i32 as u32, ExceptionFlags::INEXACT)
+    );
+    assert_eq!(convert(3e9, false), (3_000_000_000, ExceptionFlags::NONE));
+    assert_eq!(
+        convert(3e9, true),
+        (i32::MAX as u32, ExceptionFlags::INVALID)
+    );
+    assert_eq!(
+        convert(-3e9, true),
+        (i32::MIN as u32, ExceptionFlags::INVALID)
+    );
+    assert_eq!(convert(-1.0, false), (0, ExceptionFlags::INVALID));
+    // -0.5 truncates to zero, which is in range even for unsigned results.
+    assert_eq!(convert(-0.5, false), (0, ExceptionFlags::INEXACT));
+    assert_eq!(
+        convert(f32::NAN, true),
+        (i32::MAX as u32, ExceptionFlags::INVALID)
+    );
+    assert_eq!(
+        convert(f32::NAN, false),
+        (u32::MAX, ExceptionFlags::INVALID)
+    );
+    assert_eq!(
+        convert(f32::NEG_INFINITY, true),
+        (i32::MIN as u32, ExceptionFlags::INVALID)
+    );
+}
+
+#[test]
+fn nan_boxing() {
+    let one = 1.0_f32.to_bits() as u64;
+
+    assert_eq!(nan_box(one), 0xffff_ffff_3f80_0000);
+    assert_eq!(unbox(nan_box(one)), one);
+    // A single-precision value that is not NaN-boxed reads as the canonical NaN.
+    assert_eq!(unbox(one), SINGLE.canonical_nan());
+    assert_eq!(unbox(1.0_f64.to_bits()), SINGLE.canonical_nan());
+}
+
+#[test]
+fn fcsr_fields() {
+    let mut fcsr = Fcsr::default();
+    assert_eq!(fcsr.read(FCSR), Some(0));
+
+    assert!(fcsr.write(FRM, 0xff));
+    assert!(fcsr.write(FFLAGS, 0xff));
+    assert_eq!(fcsr.frm, 0x7);
+    assert_eq!(fcsr.fflags, ExceptionFlags::from_bits(0x1f));
+    assert_eq!(fcsr.read(FCSR), Some(0xff));
+
+    assert!(fcsr.write(FCSR, 0x123));
+    assert_eq!(fcsr.read(FRM), Some(0x1));
+    assert_eq!(fcsr.read(FFLAGS), Some(0x3));
+
+    assert_eq!(fcsr.read(0xc00), None);
+    assert!(!fcsr.write(0xc00, 0));
+}
+
+#[test]
+fn dynamic_rounding_mode() {
+    let mut fcsr = Fcsr::default();
+    assert_eq!(fcsr.rounding(RoundingMode::Dynamic), Ok(Round::NearestEven));
+
+    fcsr.frm = 1;
+    assert_eq!(fcsr.rounding(RoundingMode::Dynamic), Ok(Round::TowardZero));
+    assert_eq!(fcsr.rounding(RoundingMode::Up), Ok(Round::Up));
+
+    for frm in 5..8 {
+        fcsr.frm = frm;
+        assert_eq!(
+            fcsr.rounding(RoundingMode::Dynamic),
+            Err(Error::InvalidRoundingMode { frm })
+        );
+        // A static rounding mode does not read frm.
+        assert_eq!(fcsr.rounding(RoundingMode::Down), Ok(Round::Down));
+    }
+}
+
+#[test]
+fn flags() {
+    let flags = ExceptionFlags::INEXACT.with(ExceptionFlags::OVERFLOW);
+
+    assert_eq!(flags.bits(), 0x05);
+    assert!(flags.contains(ExceptionFlags::OVERFLOW));
+    assert!(!flags.contains(ExceptionFlags::INVALID));
+    assert_eq!(ExceptionFlags::from_bits(0xff).bits(), 0x1f);
+}
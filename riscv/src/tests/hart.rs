@@ -0,0 +1,357 @@
+use crate::Config;
+use crate::assembler::assemble;
+use crate::custom::{
+    CustomForm, CustomInstruction, CustomInstructions, CustomOpcode, CustomOperands, RegisterFile,
+};
+use crate::error::Error;
+use crate::extension::{Extension, Extensions};
+use crate::hart::{Hart, RETURN_ADDRESS};
+use crate::profile::Profile;
+use crate::program::Program;
+use crate::register::Register;
+
+fn syscall(args: &[u32], context: u64) -> u32 {
+    args.iter().sum::<u32>() + context as u32
+}
+
+fn time(context: u64) -> u64 {
+    context * 1000
+}
+
+fn config(profile: Profile, extensions: Extensions) -> Config {
+    Config {
+        syscall,
+        max_instance_memory: 0x100,
+        max_code_size: 0x1000,
+        profile,
+        extensions,
+        time,
+        custom: CustomInstructions::NONE,
+    }
+}
+
+/// The outcome of running a function to completion on a hart.
+struct Outcome {
+    result: Result<(), Error>,
+    registers: [u64; 32],
+    gas: u64,
+    memory: Vec<u8>,
+}
+
+impl Outcome {
+    fn a0(&self) -> u64 {
+        self.registers[Register::A0.number() as usize]
+    }
+}
+
+/// Executes the function at the start of `source` one instruction at a time, until it
+/// returns or traps.
+fn run(config: &Config, source: &str, arg: u64, gas: u64) -> Outcome {
+    let program = Program::decode(&assemble(source).unwrap().bytes, config).unwrap();
+    let mut memory = vec![0; config.max_instance_memory as usize];
+    let mut hart = Hart::new(config.profile, &mut memory, 0, arg, gas, 7);
+
+    let result = loop {
+        if hart.pc == RETURN_ADDRESS {
+            break Ok(());
+        }
+        let Some((instruction, length)) = program.get(hart.pc) else {
+            break Err(Error::InvalidJump { target: hart.pc });
+        };
+        if let Err(error) = hart.execute(instruction, length, config) {
+            break Err(error);
+        }
+    };
+
+    let (registers, gas) = (hart.registers, hart.gas);
+    Outcome {
+        result,
+        registers,
+        gas,
+        memory,
+    }
+}
+
+fn rv32(source: &str, arg: u64) -> Outcome {
+    run(&config(Profile::Rv32i, Extensions::NONE), source, arg, 1000)
+}
+
+#[test]
+fn arithmetic() {
+    let outcome = rv32("addi a0, a0, -3\nslli a0, a0, 4\nret", 1);
+
+    assert_eq!(outcome.result, Ok(()));
+    assert_eq!(outcome.a0(), 0xffff_ffe0);
+    assert_eq!(outcome.gas, 997);
+}
+
+#[test]
+fn x0_is_never_written() {
+    let outcome = rv32("addi zero, a0, 1\nmv a0, zero\nret", 5);
+
+    assert_eq!(outcome.a0(), 0);
+    assert_eq!(outcome.registers[0], 0);
+}
+
+#[test]
+fn loop_with_branches() {
+    let source = "
+        li a1, 0
+    loop:
+        add a1, a1, a0
+        addi a0, a0, -1
+        bnez a0, loop
+        mv a0, a1
+        ret
+    ";
+
+    assert_eq!(rv32(source, 10).a0(), 55);
+}
+
+#[test]
+fn calls_link_and_return() {
+    let source = "
+        mv s0, ra
+        jal double
+        jal double
+        mv ra, s0
+        ret
+    double:
+        add a0, a0, a0
+        ret
+    ";
+
+    assert_eq!(rv32(source, 3).a0(), 12);
+}
+
+#[test]
+fn jalr_clears_the_lowest_bit() {
+    let outcome = rv32("auipc a1, 0\njalr a1, 13(a1)\nebreak\nret", 0);
+
+    assert_eq!(outcome.result, Ok(()));
+    assert_eq!(outcome.registers[Register::A1.number() as usize], 8);
+}
+
+#[test]
+fn jump_to_the_middle_of_an_instruction() {
+    let outcome = rv32("j 2\nret", 0);
+
+    assert_eq!(outcome.result, Err(Error::InvalidJump { target: 2 }));
+}
+
+#[test]
+fn falling_off_the_end() {
+    let outcome = rv32("nop", 0);
+
+    assert_eq!(outcome.result, Err(Error::InvalidJump { target: 4 }));
+}
+
+#[test]
+fn loads_and_stores() {
+    let source = "
+        li a1, 0x80
+        sw a0, 0(a1)
+        lb a2, 3(a1)
+        lbu a3, 3(a1)
+        lh a4, 2(a1)
+        lhu a5, 2(a1)
+        sb a0, 8(a1)
+        lw a0, 8(a1)
+        ret
+    ";
+    let outcome = rv32(source, 0x8765_4321);
+    let register = |register: Register| outcome.registers[register.number() as usize];
+
+    assert_eq!(outcome.result, Ok(()));
+    assert_eq!(register(Register::A2), 0xffff_ff87);
+    assert_eq!(register(Register::A3), 0x87);
+    assert_eq!(register(Register::A4), 0xffff_8765);
+    assert_eq!(register(Register::A5), 0x8765);
+    assert_eq!(outcome.a0(), 0x21);
+    assert_eq!(outcome.memory[0x80..0x84], [0x21, 0x43, 0x65, 0x87]);
+}
+
+#[test]
+fn access_outside_of_memory() {
+    // The last word of memory is accessible, but not one byte further.
+    let outcome = rv32("sw a0, 0xfc(zero)\nlw a1, 0xfd(zero)\nret", 1);
+    assert_eq!(outcome.result, Err(Error::InvalidAddress { address: 0xfd }));
+    assert_eq!(outcome.memory[0xfc], 1);
+    // The trapping instruction is not charged.
+    assert_eq!(outcome.gas, 999);
+
+    // Addresses wrap around the 32-bit address space.
+    let outcome = rv32("lb a1, -1(zero)\nret", 0);
+    assert_eq!(
+        outcome.result,
+        Err(Error::InvalidAddress {
+            address: 0xffff_ffff
+        })
+    );
+}
+
+#[test]
+fn out_of_gas() {
+    let config = config(Profile::Rv32i, Extensions::NONE);
+
+    let outcome = run(&config, "addi a0, a0, 1\naddi a0, a0, 1\nret", 0, 2);
+    assert_eq!(outcome.result, Err(Error::OutOfGas));
+    assert_eq!(outcome.a0(), 2);
+    assert_eq!(outcome.gas, 0);
+
+    let outcome = run(&config, "addi a0, a0, 1\naddi a0, a0, 1\nret", 0, 3);
+    assert_eq!(outcome.result, Ok(()));
+    assert_eq!(outcome.gas, 0);
+}
+
+#[test]
+fn ecall_calls_the_syscall_handler() {
+    let outcome = rv32("li a1, 2\nli a7, 3\necall\nret", 1);
+
+    // The handler sums its arguments and adds the context.
+    assert_eq!(outcome.a0(), 1 + 2 + 3 + 7);
+}
+
+#[test]
+fn rv32e_ecall_passes_the_syscall_number_in_t0() {
+    let config = config(Profile::Rv32e, Extensions::NONE);
+    let outcome = run(&config, "li a1, 2\nli t0, 3\necall\nret", 1, 100);
+
+    assert_eq!(outcome.a0(), 1 + 2 + 3 + 7);
+}
+
+#[test]
+fn ebreak_traps() {
+    let outcome = rv32("nop\nebreak", 0);
+
+    assert_eq!(outcome.result, Err(Error::Breakpoint { pc: 4 }));
+}
+
+#[test]
+fn counters() {
+    let config = config(Profile::Rv32i, Extensions::from_slice(&[Extension::Zicsr]));
+    let source = "
+        nop
+        csrr a1, instret
+        csrr a2, cycle
+        csrr a3, time
+        ret
+    ";
+    let outcome = run(&config, source, 0, 1000);
+    let register = |register: Register| outcome.registers[register.number() as usize];
+
+    assert_eq!(register(Register::A1), 1);
+    assert_eq!(register(Register::A2), 2);
+    assert_eq!(register(Register::A3), 7000);
+
+    let outcome = run(&config, "csrw cycle, a0\nret", 0, 1000);
+    assert_eq!(outcome.result, Err(Error::InvalidCsr { csr: 0xc00 }));
+}
+
+#[test]
+fn atomics() {
+    let config = config(Profile::Rv32i, Extensions::from_slice(&[Extension::A]));
+    let source = "
+        li a1, 0x40
+        sw a0, 0(a1)
+        li a2, 5
+        amoadd.w a3, a2, (a1)
+        lr.w a4, (a1)
+        sc.w a5, a2, (a1)
+        sc.w a6, a2, (a1)
+        lw a0, 0(a1)
+        ret
+    ";
+    let outcome = run(&config, source, 10, 1000);
+    let register = |register: Register| outcome.registers[register.number() as usize];
+
+    assert_eq!(register(Register::A3), 10);
+    assert_eq!(register(Register::A4), 15);
+    assert_eq!(register(Register::A5), 0);
+    assert_eq!(register(Register::A6), 1);
+    assert_eq!(outcome.a0(), 5);
+
+    let outcome = run(&config, "li a1, 0x42\nlr.w a0, (a1)\nret", 0, 1000);
+    assert_eq!(
+        outcome.result,
+        Err(Error::MisalignedAtomic { address: 0x42 })
+    );
+}
+
+#[test]
+fn floating_point() {
+    let config = config(Profile::Rv32i, Extensions::from_slice(&[Extension::F]));
+    let source = "
+        fcvt.s.w fa0, a0
+        fadd.s fa1, fa0, fa0
+        fsw fa1, 0x10(zero)
+        flw fa2, 0x10(zero)
+        fcvt.w.s a0, fa2
+        ret
+    ";
+    let outcome = run(&config, source, 21, 1000);
+
+    assert_eq!(outcome.a0(), 42);
+    assert_eq!(outcome.memory[0x10..0x14], 42.0_f32.to_le_bytes());
+}
+
+fn swap(registers: &mut RegisterFile<'_>, operands: CustomOperands, context: u64) {
+    let CustomOperands::R { rd, rs1, rs2, .. } = operands else {
+        return;
+    };
+    let (a, b) = (registers.read(rs1), registers.read(rs2));
+    registers.write(rd, a + context);
+    registers.write(rs1, b);
+}
+
+#[test]
+fn custom_instructions_charge_their_gas() {
+    let mut config = config(Profile::Rv32i, Extensions::from_slice(&[Extension::Zicsr]));
+    config.custom = CustomInstructions::NONE
+        .with(CustomInstruction {
+            opcode: CustomOpcode::Custom0,
+            funct3: 0,
+            form: CustomForm::R { funct7: 0 },
+            gas: 10,
+            handler: swap,
+        })
+        .unwrap();
+    let source = "
+        li a1, 4
+        .insn r 0x0b, 0, 0, a2, a0, a1
+        csrr a3, instret
+        csrr a4, cycle
+        ret
+    ";
+    let outcome = run(&config, source, 3, 1000);
+    let register = |register: Register| outcome.registers[register.number() as usize];
+
+    assert_eq!(outcome.a0(), 4);
+    assert_eq!(register(Register::A2), 3 + 7);
+    assert_eq!(register(Register::A3), 2);
+    assert_eq!(register(Register::A4), 12);
+    assert_eq!(outcome.gas, 1000 - 14);
+}
+
+#[test]
+fn rv64_registers() {
+    let config = config(Profile::Rv64i, Extensions::NONE);
+    let source = "
+        li a1, -1
+        sw a1, 0(zero)
+        lw a2, 0(zero)
+        lwu a3, 0(zero)
+        addiw a4, a1, 0
+        srli a1, a1, 1
+        addw a0, a0, a0
+        ret
+    ";
+    let outcome = run(&config, source, 0x4000_0000, 1000);
+    let register = |register: Register| outcome.registers[register.number() as usize];
+
+    assert_eq!(register(Register::A1), u64::MAX >> 1);
+    assert_eq!(register(Register::A2), u64::MAX);
+    assert_eq!(register(Register::A3), 0xffff_ffff);
+    assert_eq!(register(Register::A4), u64::MAX);
+    assert_eq!(outcome.a0(), 0xffff_ffff_8000_0000);
+}
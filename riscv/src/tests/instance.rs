@@ -0,0 +1,131 @@
+use crate::assembler::assemble;
+use crate::custom::CustomInstructions;
+use crate::error::Error;
+use crate::extension::Extensions;
+use crate::profile::Profile;
+use crate::{Config, Engine, Instance, Memory, Module};
+use std::rc::Rc;
+
+fn syscall(args: &[u32], context: u64) -> u32 {
+    args.iter().sum::<u32>() + context as u32
+}
+
+fn time(_context: u64) -> u64 {
+    0
+}
+
+fn engine_for(profile: Profile) -> Rc<Engine> {
+    Engine::new(Config {
+        syscall,
+        max_instance_memory: 0x10000,
+        max_code_size: 0x100,
+        profile,
+        extensions: Extensions::NONE,
+        time,
+        custom: CustomInstructions::NONE,
+    })
+}
+
+fn instance_for(profile: Profile) -> Instance {
+    let engine = engine_for(profile);
+    let module = Module::new(engine.clone()).unwrap();
+    let memory = Memory::new(engine);
+    Instance::new(module, memory).unwrap()
+}
+
+#[test]
+fn call_rejects_rv64() {
+    let mut instance = instance_for(Profile::Rv64i);
+    assert_eq!(
+        instance.call(0, 0),
+        Err(Error::InvalidProfile {
+            profile: Profile::Rv64i
+        })
+    );
+}
+
+#[test]
+fn call64_rejects_rv32() {
+    for profile in [Profile::Rv32i, Profile::Rv32e] {
+        let mut instance = instance_for(profile);
+        assert_eq!(
+            instance.call64(0, 0),
+            Err(Error::InvalidProfile { profile })
+        );
+    }
+}
+
+fn instance_with_code(profile: Profile, source: &str) -> Instance {
+    let engine = engine_for(profile);
+    let mut module = Module::new(engine.clone()).unwrap();
+    module
+        .set_riscv_code(&assemble(source).unwrap().bytes)
+        .unwrap();
+    let memory = Memory::new(engine);
+
+    let mut instance = Instance::new(module, memory).unwrap();
+    instance.set_gas(1000);
+    instance
+}
+
+#[test]
+fn call_runs_riscv_code() {
+    let source = "
+        li a1, 0
+    loop:
+        add a1, a1, a0
+        addi a0, a0, -1
+        bnez a0, loop
+        mv a0, a1
+        ret
+    ";
+    let mut instance = instance_with_code(Profile::Rv32i, source);
+
+    assert_eq!(instance.call(0, 10), Ok(55));
+    assert_eq!(instance.gas(), 1000 - 1 - 3 * 10 - 2);
+}
+
+#[test]
+fn call64_runs_riscv_code() {
+    let mut instance = instance_with_code(Profile::Rv64i, "slli a0, a0, 32\naddw a1, a0, a0\nret");
+
+    assert_eq!(instance.call64(0, 3), Ok(3 << 32));
+}
+
+#[test]
+fn call_traps() {
+    let mut instance = instance_with_code(Profile::Rv32i, "lw a0, 0(a0)\nret");
+
+    assert_eq!(
+        instance.call(0, 0x10000),
+        Err(Error::InvalidAddress { address: 0x10000 })
+    );
+    assert_eq!(instance.gas(), 1000);
+    assert_eq!(instance.call(2, 0), Err(Error::InvalidJump { target: 2 }));
+}
+
+#[test]
+fn call_runs_out_of_gas() {
+    let mut instance = instance_with_code(Profile::Rv32i, "loop:\nj loop");
+
+    assert_eq!(instance.call(0, 0), Err(Error::OutOfGas));
+    assert_eq!(instance.gas(), 0);
+}
+
+#[test]
+fn memory_is_shared_with_the_host() {
+    let mut instance = instance_with_code(Profile::Rv32i, "lw a1, 0(a0)\nsw a1, 4(a0)\nret");
+    instance.memory_mut().data_mut()[0x20..0x24].copy_from_slice(&[1, 2, 3, 4]);
+
+    assert_eq!(instance.call(0, 0x20), Ok(0x20));
+    assert_eq!(instance.memory().data()[0x24..0x28], [1, 2, 3, 4]);
+}
+
+#[test]
+fn syscalls_receive_the_context() {
+    let mut instance = instance_with_code(Profile::Rv32i, "li a7, 2\necall\naddi a0, a0, 1\nret");
+    instance.set_context(40);
+
+    assert_eq!(instance.call(0, 0), Ok(43));
+    assert_eq!(instance.gas(), 1000 - 4);
+}
@@ -1,15 +1,17 @@
+use crate::error::{DecodeError, DecodeErrorKind};
 use crate::instruction::RiscVInstruction;
+use crate::register::Register;
 
 #[test]
 fn basic() {
     let add_x1_x2_x3 = 0x003100b3;
-    let decoded = RiscVInstruction::decode(add_x1_x2_x3);
+    let decoded = RiscVInstruction::decode(add_x1_x2_x3).unwrap();
 
     match decoded {
         RiscVInstruction::Add { rd, rs1, rs2 } => {
-            assert_eq!(rd, 1);
-            assert_eq!(rs1, 2);
-            assert_eq!(rs2, 3);
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X3);
         }
         _ => panic!("Expected ADD instruction"),
     }
@@ -18,13 +20,13 @@ fn basic() {
 #[test]
 fn min_rd() {
     let add_x0_x1_x2 = 0x00208033;
-    let decoded = RiscVInstruction::decode(add_x0_x1_x2);
+    let decoded = RiscVInstruction::decode(add_x0_x1_x2).unwrap();
 
     match decoded {
         RiscVInstruction::Add { rd, rs1, rs2 } => {
-            assert_eq!(rd, 0);
-            assert_eq!(rs1, 1);
-            assert_eq!(rs2, 2);
+            assert_eq!(rd, Register::X0);
+            assert_eq!(rs1, Register::X1);
+            assert_eq!(rs2, Register::X2);
         }
         _ => panic!("Expected ADD instruction"),
     }
@@ -33,13 +35,13 @@ fn min_rd() {
 #[test]
 fn max_rd() {
     let add_x31_x1_x2 = 0x00208033 | (31 << 7);
-    let decoded = RiscVInstruction::decode(add_x31_x1_x2);
+    let decoded = RiscVInstruction::decode(add_x31_x1_x2).unwrap();
 
     match decoded {
         RiscVInstruction::Add { rd, rs1, rs2 } => {
-            assert_eq!(rd, 31);
-            assert_eq!(rs1, 1);
-            assert_eq!(rs2, 2);
+            assert_eq!(rd, Register::X31);
+            assert_eq!(rs1, Register::X1);
+            assert_eq!(rs2, Register::X2);
         }
         _ => panic!("Expected ADD instruction"),
     }
@@ -48,13 +50,13 @@ fn max_rd() {
 #[test]
 fn min_rs1() {
     let add_x1_x0_x2 = 0x00200033 | (1 << 7);
-    let decoded = RiscVInstruction::decode(add_x1_x0_x2);
+    let decoded = RiscVInstruction::decode(add_x1_x0_x2).unwrap();
 
     match decoded {
         RiscVInstruction::Add { rd, rs1, rs2 } => {
-            assert_eq!(rd, 1);
-            assert_eq!(rs1, 0);
-            assert_eq!(rs2, 2);
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X0);
+            assert_eq!(rs2, Register::X2);
         }
         _ => panic!("Expected ADD instruction"),
     }
@@ -63,13 +65,13 @@ fn min_rs1() {
 #[test]
 fn max_rs1() {
     let add_x1_x31_x2 = 0x002f8033 | (1 << 7);
-    let decoded = RiscVInstruction::decode(add_x1_x31_x2);
+    let decoded = RiscVInstruction::decode(add_x1_x31_x2).unwrap();
 
     match decoded {
         RiscVInstruction::Add { rd, rs1, rs2 } => {
-            assert_eq!(rd, 1);
-            assert_eq!(rs1, 31);
-            assert_eq!(rs2, 2);
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X31);
+            assert_eq!(rs2, Register::X2);
         }
         _ => panic!("Expected ADD instruction"),
     }
@@ -78,13 +80,13 @@ fn max_rs1() {
 #[test]
 fn min_rs2() {
     let add_x1_x2_x0 = 0x00010033 | (1 << 7);
-    let decoded = RiscVInstruction::decode(add_x1_x2_x0);
+    let decoded = RiscVInstruction::decode(add_x1_x2_x0).unwrap();
 
     match decoded {
         RiscVInstruction::Add { rd, rs1, rs2 } => {
-            assert_eq!(rd, 1);
-            assert_eq!(rs1, 2);
-            assert_eq!(rs2, 0);
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X0);
         }
         _ => panic!("Expected ADD instruction"),
     }
@@ -93,13 +95,13 @@ fn min_rs2() {
 #[test]
 fn max_rs2() {
     let add_x1_x2_x31 = 0x01f10033 | (1 << 7);
-    let decoded = RiscVInstruction::decode(add_x1_x2_x31);
+    let decoded = RiscVInstruction::decode(add_x1_x2_x31).unwrap();
 
     match decoded {
         RiscVInstruction::Add { rd, rs1, rs2 } => {
-            assert_eq!(rd, 1);
-            assert_eq!(rs1, 2);
-            assert_eq!(rs2, 31);
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X31);
         }
         _ => panic!("Expected ADD instruction"),
     }
@@ -108,13 +110,13 @@ fn max_rs2() {
 #[test]
 fn all_max_values() {
     let add_x31_x31_x31 = 0x01ff8fb3;
-    let decoded = RiscVInstruction::decode(add_x31_x31_x31);
+    let decoded = RiscVInstruction::decode(add_x31_x31_x31).unwrap();
 
     match decoded {
         RiscVInstruction::Add { rd, rs1, rs2 } => {
-            assert_eq!(rd, 31);
-            assert_eq!(rs1, 31);
-            assert_eq!(rs2, 31);
+            assert_eq!(rd, Register::X31);
+            assert_eq!(rs1, Register::X31);
+            assert_eq!(rs2, Register::X31);
         }
         _ => panic!("Expected ADD instruction"),
     }
@@ -127,9 +129,10 @@ fn invalid_funct7_should_be_unsupported() {
     let decoded = RiscVInstruction::decode(invalid_add);
 
     match decoded {
-        RiscVInstruction::Unsupported(word) => {
+        Err(DecodeError { word, kind }) => {
             assert_eq!(word, 0x203100b3);
+            assert_eq!(kind, DecodeErrorKind::ReservedFunct7);
         }
-        _ => panic!("Expected unsupported instruction"),
+        _ => panic!("Expected decode error"),
     }
 }
@@ -0,0 +1,138 @@
+use crate::error::{DecodeError, DecodeErrorKind};
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn basic() {
+    let and_x1_x2_x3 = 0x003170b3;
+    let decoded = RiscVInstruction::decode(and_x1_x2_x3).unwrap();
+
+    match decoded {
+        RiscVInstruction::And { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X3);
+        }
+        _ => panic!("Expected AND instruction"),
+    }
+}
+
+#[test]
+fn min_rd() {
+    let and_x0_x1_x2 = 0x0020f033;
+    let decoded = RiscVInstruction::decode(and_x0_x1_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::And { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X0);
+            assert_eq!(rs1, Register::X1);
+            assert_eq!(rs2, Register::X2);
+        }
+        _ => panic!("Expected AND instruction"),
+    }
+}
+
+#[test]
+fn max_rd() {
+    let and_x31_x1_x2 = 0x0020f033 | (31 << 7);
+    let decoded = RiscVInstruction::decode(and_x31_x1_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::And { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X31);
+            assert_eq!(rs1, Register::X1);
+            assert_eq!(rs2, Register::X2);
+        }
+        _ => panic!("Expected AND instruction"),
+    }
+}
+
+#[test]
+fn min_rs1() {
+    let and_x1_x0_x2 = 0x002070b3;
+    let decoded = RiscVInstruction::decode(and_x1_x0_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::And { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X0);
+            assert_eq!(rs2, Register::X2);
+        }
+        _ => panic!("Expected AND instruction"),
+    }
+}
+
+#[test]
+fn max_rs1() {
+    let and_x1_x31_x2 = 0x002ff0b3;
+    let decoded = RiscVInstruction::decode(and_x1_x31_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::And { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X31);
+            assert_eq!(rs2, Register::X2);
+        }
+        _ => panic!("Expected AND instruction"),
+    }
+}
+
+#[test]
+fn min_rs2() {
+    let and_x1_x2_x0 = 0x000170b3;
+    let decoded = RiscVInstruction::decode(and_x1_x2_x0).unwrap();
+
+    match decoded {
+        RiscVInstruction::And { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X0);
+        }
+        _ => panic!("Expected AND instruction"),
+    }
+}
+
+#[test]
+fn max_rs2() {
+    let and_x1_x2_x31 = 0x01f170b3;
+    let decoded = RiscVInstruction::decode(and_x1_x2_x31).unwrap();
+
+    match decoded {
+        RiscVInstruction::And { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X31);
+        }
+        _ => panic!("Expected AND instruction"),
+    }
+}
+
+#[test]
+fn all_max_values() {
+    let and_x31_x31_x31 = 0x01ffffb3;
+    let decoded = RiscVInstruction::decode(and_x31_x31_x31).unwrap();
+
+    match decoded {
+        RiscVInstruction::And { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X31);
+            assert_eq!(rs1, Register::X31);
+            assert_eq!(rs2, Register::X31);
+        }
+        _ => panic!("Expected AND instruction"),
+    }
+}
+
+#[test]
+fn invalid_funct7_should_be_unsupported() {
+    // AND with invalid funct7 (0x40 instead of 0x00)
+    let invalid_and = 0x803170b3;
+    let decoded = RiscVInstruction::decode(invalid_and);
+
+    match decoded {
+        Err(DecodeError { word, kind }) => {
+            assert_eq!(word, 0x803170b3);
+            assert_eq!(kind, DecodeErrorKind::ReservedFunct7);
+        }
+        _ => panic!("Expected decode error"),
+    }
+}
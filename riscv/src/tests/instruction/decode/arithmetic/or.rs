@@ -1,15 +1,18 @@
+use crate::error::{DecodeError, DecodeErrorKind};
+use crate::extension::Extension;
 use crate::instruction::RiscVInstruction;
+use crate::register::Register;
 
 #[test]
 fn basic() {
     let or_x1_x2_x3 = 0x003160b3;
-    let decoded = RiscVInstruction::decode(or_x1_x2_x3);
+    let decoded = RiscVInstruction::decode(or_x1_x2_x3).unwrap();
 
     match decoded {
         RiscVInstruction::Or { rd, rs1, rs2 } => {
-            assert_eq!(rd, 1);
-            assert_eq!(rs1, 2);
-            assert_eq!(rs2, 3);
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X3);
         }
         _ => panic!("Expected OR instruction"),
     }
@@ -18,13 +21,13 @@ fn basic() {
 #[test]
 fn min_rd() {
     let or_x0_x1_x2 = 0x0020e033;
-    let decoded = RiscVInstruction::decode(or_x0_x1_x2);
+    let decoded = RiscVInstruction::decode(or_x0_x1_x2).unwrap();
 
     match decoded {
         RiscVInstruction::Or { rd, rs1, rs2 } => {
-            assert_eq!(rd, 0);
-            assert_eq!(rs1, 1);
-            assert_eq!(rs2, 2);
+            assert_eq!(rd, Register::X0);
+            assert_eq!(rs1, Register::X1);
+            assert_eq!(rs2, Register::X2);
         }
         _ => panic!("Expected OR instruction"),
     }
@@ -33,13 +36,13 @@ fn min_rd() {
 #[test]
 fn max_rd() {
     let or_x31_x1_x2 = 0x0020e033 | (31 << 7);
-    let decoded = RiscVInstruction::decode(or_x31_x1_x2);
+    let decoded = RiscVInstruction::decode(or_x31_x1_x2).unwrap();
 
     match decoded {
         RiscVInstruction::Or { rd, rs1, rs2 } => {
-            assert_eq!(rd, 31);
-            assert_eq!(rs1, 1);
-            assert_eq!(rs2, 2);
+            assert_eq!(rd, Register::X31);
+            assert_eq!(rs1, Register::X1);
+            assert_eq!(rs2, Register::X2);
         }
         _ => panic!("Expected OR instruction"),
     }
@@ -48,13 +51,13 @@ fn max_rd() {
 #[test]
 fn min_rs1() {
     let or_x1_x0_x2 = 0x00206033 | (1 << 7);
-    let decoded = RiscVInstruction::decode(or_x1_x0_x2);
+    let decoded = RiscVInstruction::decode(or_x1_x0_x2).unwrap();
 
     match decoded {
         RiscVInstruction::Or { rd, rs1, rs2 } => {
-            assert_eq!(rd, 1);
-            assert_eq!(rs1, 0);
-            assert_eq!(rs2, 2);
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X0);
+            assert_eq!(rs2, Register::X2);
         }
         _ => panic!("Expected OR instruction"),
     }
@@ -63,13 +66,13 @@ fn min_rs1() {
 #[test]
 fn max_rs1() {
     let or_x1_x31_x2 = 0x002fe033 | (1 << 7);
-    let decoded = RiscVInstruction::decode(or_x1_x31_x2);
+    let decoded = RiscVInstruction::decode(or_x1_x31_x2).unwrap();
 
     match decoded {
         RiscVInstruction::Or { rd, rs1, rs2 } => {
-            assert_eq!(rd, 1);
-            assert_eq!(rs1, 31);
-            assert_eq!(rs2, 2);
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X31);
+            assert_eq!(rs2, Register::X2);
         }
         _ => panic!("Expected OR instruction"),
     }
@@ -78,13 +81,13 @@ fn max_rs1() {
 #[test]
 fn min_rs2() {
     let or_x1_x2_x0 = 0x00016033 | (1 << 7);
-    let decoded = RiscVInstruction::decode(or_x1_x2_x0);
+    let decoded = RiscVInstruction::decode(or_x1_x2_x0).unwrap();
 
     match decoded {
         RiscVInstruction::Or { rd, rs1, rs2 } => {
-            assert_eq!(rd, 1);
-            assert_eq!(rs1, 2);
-            assert_eq!(rs2, 0);
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X0);
         }
         _ => panic!("Expected OR instruction"),
     }
@@ -93,13 +96,13 @@ fn min_rs2() {
 #[test]
 fn max_rs2() {
     let or_x1_x2_x31 = 0x01f16033 | (1 << 7);
-    let decoded = RiscVInstruction::decode(or_x1_x2_x31);
+    let decoded = RiscVInstruction::decode(or_x1_x2_x31).unwrap();
 
     match decoded {
         RiscVInstruction::Or { rd, rs1, rs2 } => {
-            assert_eq!(rd, 1);
-            assert_eq!(rs1, 2);
-            assert_eq!(rs2, 31);
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X31);
         }
         _ => panic!("Expected OR instruction"),
     }
@@ -108,13 +111,13 @@ fn max_rs2() {
 #[test]
 fn all_max_values() {
     let or_x31_x31_x31 = 0x01ffefb3;
-    let decoded = RiscVInstruction::decode(or_x31_x31_x31);
+    let decoded = RiscVInstruction::decode(or_x31_x31_x31).unwrap();
 
     match decoded {
         RiscVInstruction::Or { rd, rs1, rs2 } => {
-            assert_eq!(rd, 31);
-            assert_eq!(rs1, 31);
-            assert_eq!(rs2, 31);
+            assert_eq!(rd, Register::X31);
+            assert_eq!(rs1, Register::X31);
+            assert_eq!(rs2, Register::X31);
         }
         _ => panic!("Expected OR instruction"),
     }
@@ -122,14 +125,15 @@ fn all_max_values() {
 
 #[test]
 fn invalid_funct7_should_be_unsupported() {
-    // OR with invalid funct7 (0x20 instead of 0x00)
+    // OR with funct7 0x10 instead of 0x00 is sh3add, which needs Zba
     let invalid_or = 0x203160b3;
     let decoded = RiscVInstruction::decode(invalid_or);
 
     match decoded {
-        RiscVInstruction::Unsupported(word) => {
+        Err(DecodeError { word, kind }) => {
             assert_eq!(word, 0x203160b3);
+            assert_eq!(kind, DecodeErrorKind::UnsupportedExtension(Extension::Zba));
         }
-        _ => panic!("Expected unsupported instruction"),
+        _ => panic!("Expected decode error"),
     }
 }
@@ -0,0 +1,138 @@
+use crate::error::{DecodeError, DecodeErrorKind};
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn basic() {
+    let sltu_x1_x2_x3 = 0x003130b3;
+    let decoded = RiscVInstruction::decode(sltu_x1_x2_x3).unwrap();
+
+    match decoded {
+        RiscVInstruction::Sltu { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X3);
+        }
+        _ => panic!("Expected SLTU instruction"),
+    }
+}
+
+#[test]
+fn min_rd() {
+    let sltu_x0_x1_x2 = 0x0020b033;
+    let decoded = RiscVInstruction::decode(sltu_x0_x1_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::Sltu { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X0);
+            assert_eq!(rs1, Register::X1);
+            assert_eq!(rs2, Register::X2);
+        }
+        _ => panic!("Expected SLTU instruction"),
+    }
+}
+
+#[test]
+fn max_rd() {
+    let sltu_x31_x1_x2 = 0x0020b033 | (31 << 7);
+    let decoded = RiscVInstruction::decode(sltu_x31_x1_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::Sltu { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X31);
+            assert_eq!(rs1, Register::X1);
+            assert_eq!(rs2, Register::X2);
+        }
+        _ => panic!("Expected SLTU instruction"),
+    }
+}
+
+#[test]
+fn min_rs1() {
+    let sltu_x1_x0_x2 = 0x002030b3;
+    let decoded = RiscVInstruction::decode(sltu_x1_x0_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::Sltu { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X0);
+            assert_eq!(rs2, Register::X2);
+        }
+        _ => panic!("Expected SLTU instruction"),
+    }
+}
+
+#[test]
+fn max_rs1() {
+    let sltu_x1_x31_x2 = 0x002fb0b3;
+    let decoded = RiscVInstruction::decode(sltu_x1_x31_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::Sltu { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X31);
+            assert_eq!(rs2, Register::X2);
+        }
+        _ => panic!("Expected SLTU instruction"),
+    }
+}
+
+#[test]
+fn min_rs2() {
+    let sltu_x1_x2_x0 = 0x000130b3;
+    let decoded = RiscVInstruction::decode(sltu_x1_x2_x0).unwrap();
+
+    match decoded {
+        RiscVInstruction::Sltu { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X0);
+        }
+        _ => panic!("Expected SLTU instruction"),
+    }
+}
+
+#[test]
+fn max_rs2() {
+    let sltu_x1_x2_x31 = 0x01f130b3;
+    let decoded = RiscVInstruction::decode(sltu_x1_x2_x31).unwrap();
+
+    match decoded {
+        RiscVInstruction::Sltu { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X31);
+        }
+        _ => panic!("Expected SLTU instruction"),
+    }
+}
+
+#[test]
+fn all_max_values() {
+    let sltu_x31_x31_x31 = 0x01ffbfb3;
+    let decoded = RiscVInstruction::decode(sltu_x31_x31_x31).unwrap();
+
+    match decoded {
+        RiscVInstruction::Sltu { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X31);
+            assert_eq!(rs1, Register::X31);
+            assert_eq!(rs2, Register::X31);
+        }
+        _ => panic!("Expected SLTU instruction"),
+    }
+}
+
+#[test]
+fn invalid_funct7_should_be_unsupported() {
+    // SLTU with invalid funct7 (0x40 instead of 0x00)
+    let invalid_sltu = 0x803130b3;
+    let decoded = RiscVInstruction::decode(invalid_sltu);
+
+    match decoded {
+        Err(DecodeError { word, kind }) => {
+            assert_eq!(word, 0x803130b3);
+            assert_eq!(kind, DecodeErrorKind::ReservedFunct7);
+        }
+        _ => panic!("Expected decode error"),
+    }
+}
@@ -0,0 +1,138 @@
+use crate::error::{DecodeError, DecodeErrorKind};
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn basic() {
+    let sra_x1_x2_x3 = 0x403150b3;
+    let decoded = RiscVInstruction::decode(sra_x1_x2_x3).unwrap();
+
+    match decoded {
+        RiscVInstruction::Sra { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X3);
+        }
+        _ => panic!("Expected SRA instruction"),
+    }
+}
+
+#[test]
+fn min_rd() {
+    let sra_x0_x1_x2 = 0x4020d033;
+    let decoded = RiscVInstruction::decode(sra_x0_x1_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::Sra { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X0);
+            assert_eq!(rs1, Register::X1);
+            assert_eq!(rs2, Register::X2);
+        }
+        _ => panic!("Expected SRA instruction"),
+    }
+}
+
+#[test]
+fn max_rd() {
+    let sra_x31_x1_x2 = 0x4020d033 | (31 << 7);
+    let decoded = RiscVInstruction::decode(sra_x31_x1_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::Sra { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X31);
+            assert_eq!(rs1, Register::X1);
+            assert_eq!(rs2, Register::X2);
+        }
+        _ => panic!("Expected SRA instruction"),
+    }
+}
+
+#[test]
+fn min_rs1() {
+    let sra_x1_x0_x2 = 0x402050b3;
+    let decoded = RiscVInstruction::decode(sra_x1_x0_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::Sra { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X0);
+            assert_eq!(rs2, Register::X2);
+        }
+        _ => panic!("Expected SRA instruction"),
+    }
+}
+
+#[test]
+fn max_rs1() {
+    let sra_x1_x31_x2 = 0x402fd0b3;
+    let decoded = RiscVInstruction::decode(sra_x1_x31_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::Sra { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X31);
+            assert_eq!(rs2, Register::X2);
+        }
+        _ => panic!("Expected SRA instruction"),
+    }
+}
+
+#[test]
+fn min_rs2() {
+    let sra_x1_x2_x0 = 0x400150b3;
+    let decoded = RiscVInstruction::decode(sra_x1_x2_x0).unwrap();
+
+    match decoded {
+        RiscVInstruction::Sra { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X0);
+        }
+        _ => panic!("Expected SRA instruction"),
+    }
+}
+
+#[test]
+fn max_rs2() {
+    let sra_x1_x2_x31 = 0x41f150b3;
+    let decoded = RiscVInstruction::decode(sra_x1_x2_x31).unwrap();
+
+    match decoded {
+        RiscVInstruction::Sra { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X31);
+        }
+        _ => panic!("Expected SRA instruction"),
+    }
+}
+
+#[test]
+fn all_max_values() {
+    let sra_x31_x31_x31 = 0x41ffdfb3;
+    let decoded = RiscVInstruction::decode(sra_x31_x31_x31).unwrap();
+
+    match decoded {
+        RiscVInstruction::Sra { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X31);
+            assert_eq!(rs1, Register::X31);
+            assert_eq!(rs2, Register::X31);
+        }
+        _ => panic!("Expected SRA instruction"),
+    }
+}
+
+#[test]
+fn invalid_funct7_should_be_unsupported() {
+    // SRA with invalid funct7 (0x40 instead of 0x20)
+    let invalid_sra = 0x803150b3;
+    let decoded = RiscVInstruction::decode(invalid_sra);
+
+    match decoded {
+        Err(DecodeError { word, kind }) => {
+            assert_eq!(word, 0x803150b3);
+            assert_eq!(kind, DecodeErrorKind::ReservedFunct7);
+        }
+        _ => panic!("Expected decode error"),
+    }
+}
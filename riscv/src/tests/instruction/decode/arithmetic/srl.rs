@@ -0,0 +1,138 @@
+use crate::error::{DecodeError, DecodeErrorKind};
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn basic() {
+    let srl_x1_x2_x3 = 0x003150b3;
+    let decoded = RiscVInstruction::decode(srl_x1_x2_x3).unwrap();
+
+    match decoded {
+        RiscVInstruction::Srl { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X3);
+        }
+        _ => panic!("Expected SRL instruction"),
+    }
+}
+
+#[test]
+fn min_rd() {
+    let srl_x0_x1_x2 = 0x0020d033;
+    let decoded = RiscVInstruction::decode(srl_x0_x1_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::Srl { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X0);
+            assert_eq!(rs1, Register::X1);
+            assert_eq!(rs2, Register::X2);
+        }
+        _ => panic!("Expected SRL instruction"),
+    }
+}
+
+#[test]
+fn max_rd() {
+    let srl_x31_x1_x2 = 0x0020d033 | (31 << 7);
+    let decoded = RiscVInstruction::decode(srl_x31_x1_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::Srl { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X31);
+            assert_eq!(rs1, Register::X1);
+            assert_eq!(rs2, Register::X2);
+        }
+        _ => panic!("Expected SRL instruction"),
+    }
+}
+
+#[test]
+fn min_rs1() {
+    let srl_x1_x0_x2 = 0x002050b3;
+    let decoded = RiscVInstruction::decode(srl_x1_x0_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::Srl { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X0);
+            assert_eq!(rs2, Register::X2);
+        }
+        _ => panic!("Expected SRL instruction"),
+    }
+}
+
+#[test]
+fn max_rs1() {
+    let srl_x1_x31_x2 = 0x002fd0b3;
+    let decoded = RiscVInstruction::decode(srl_x1_x31_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::Srl { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X31);
+            assert_eq!(rs2, Register::X2);
+        }
+        _ => panic!("Expected SRL instruction"),
+    }
+}
+
+#[test]
+fn min_rs2() {
+    let srl_x1_x2_x0 = 0x000150b3;
+    let decoded = RiscVInstruction::decode(srl_x1_x2_x0).unwrap();
+
+    match decoded {
+        RiscVInstruction::Srl { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X0);
+        }
+        _ => panic!("Expected SRL instruction"),
+    }
+}
+
+#[test]
+fn max_rs2() {
+    let srl_x1_x2_x31 = 0x01f150b3;
+    let decoded = RiscVInstruction::decode(srl_x1_x2_x31).unwrap();
+
+    match decoded {
+        RiscVInstruction::Srl { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X31);
+        }
+        _ => panic!("Expected SRL instruction"),
+    }
+}
+
+#[test]
+fn all_max_values() {
+    let srl_x31_x31_x31 = 0x01ffdfb3;
+    let decoded = RiscVInstruction::decode(srl_x31_x31_x31).unwrap();
+
+    match decoded {
+        RiscVInstruction::Srl { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X31);
+            assert_eq!(rs1, Register::X31);
+            assert_eq!(rs2, Register::X31);
+        }
+        _ => panic!("Expected SRL instruction"),
+    }
+}
+
+#[test]
+fn invalid_funct7_should_be_unsupported() {
+    // SRL with invalid funct7 (0x40 instead of 0x00)
+    let invalid_srl = 0x803150b3;
+    let decoded = RiscVInstruction::decode(invalid_srl);
+
+    match decoded {
+        Err(DecodeError { word, kind }) => {
+            assert_eq!(word, 0x803150b3);
+            assert_eq!(kind, DecodeErrorKind::ReservedFunct7);
+        }
+        _ => panic!("Expected decode error"),
+    }
+}
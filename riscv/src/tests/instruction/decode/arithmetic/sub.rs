@@ -1,15 +1,16 @@
 use crate::instruction::RiscVInstruction;
+use crate::register::Register;
 
 #[test]
 fn basic() {
     let sub_x1_x2_x3 = 0x403100b3;
-    let decoded = RiscVInstruction::decode(sub_x1_x2_x3);
+    let decoded = RiscVInstruction::decode(sub_x1_x2_x3).unwrap();
 
     match decoded {
         RiscVInstruction::Sub { rd, rs1, rs2 } => {
-            assert_eq!(rd, 1);
-            assert_eq!(rs1, 2);
-            assert_eq!(rs2, 3);
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X3);
         }
         _ => panic!("Expected SUB instruction"),
     }
@@ -18,13 +19,13 @@ fn basic() {
 #[test]
 fn min_rd() {
     let sub_x0_x1_x2 = 0x40208033;
-    let decoded = RiscVInstruction::decode(sub_x0_x1_x2);
+    let decoded = RiscVInstruction::decode(sub_x0_x1_x2).unwrap();
 
     match decoded {
         RiscVInstruction::Sub { rd, rs1, rs2 } => {
-            assert_eq!(rd, 0);
-            assert_eq!(rs1, 1);
-            assert_eq!(rs2, 2);
+            assert_eq!(rd, Register::X0);
+            assert_eq!(rs1, Register::X1);
+            assert_eq!(rs2, Register::X2);
         }
         _ => panic!("Expected SUB instruction"),
     }
@@ -33,13 +34,13 @@ fn min_rd() {
 #[test]
 fn max_rd() {
     let sub_x31_x1_x2 = 0x40208033 | (31 << 7);
-    let decoded = RiscVInstruction::decode(sub_x31_x1_x2);
+    let decoded = RiscVInstruction::decode(sub_x31_x1_x2).unwrap();
 
     match decoded {
         RiscVInstruction::Sub { rd, rs1, rs2 } => {
-            assert_eq!(rd, 31);
-            assert_eq!(rs1, 1);
-            assert_eq!(rs2, 2);
+            assert_eq!(rd, Register::X31);
+            assert_eq!(rs1, Register::X1);
+            assert_eq!(rs2, Register::X2);
         }
         _ => panic!("Expected SUB instruction"),
     }
@@ -48,13 +49,13 @@ fn max_rd() {
 #[test]
 fn min_rs1() {
     let sub_x1_x0_x2 = 0x40200033 | (1 << 7);
-    let decoded = RiscVInstruction::decode(sub_x1_x0_x2);
+    let decoded = RiscVInstruction::decode(sub_x1_x0_x2).unwrap();
 
     match decoded {
         RiscVInstruction::Sub { rd, rs1, rs2 } => {
-            assert_eq!(rd, 1);
-            assert_eq!(rs1, 0);
-            assert_eq!(rs2, 2);
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X0);
+            assert_eq!(rs2, Register::X2);
         }
         _ => panic!("Expected SUB instruction"),
     }
@@ -63,13 +64,13 @@ fn min_rs1() {
 #[test]
 fn max_rs1() {
     let sub_x1_x31_x2 = 0x402f8033 | (1 << 7);
-    let decoded = RiscVInstruction::decode(sub_x1_x31_x2);
+    let decoded = RiscVInstruction::decode(sub_x1_x31_x2).unwrap();
 
     match decoded {
         RiscVInstruction::Sub { rd, rs1, rs2 } => {
-            assert_eq!(rd, 1);
-            assert_eq!(rs1, 31);
-            assert_eq!(rs2, 2);
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X31);
+            assert_eq!(rs2, Register::X2);
         }
         _ => panic!("Expected SUB instruction"),
     }
@@ -78,13 +79,13 @@ fn max_rs1() {
 #[test]
 fn min_rs2() {
     let sub_x1_x2_x0 = 0x40010033 | (1 << 7);
-    let decoded = RiscVInstruction::decode(sub_x1_x2_x0);
+    let decoded = RiscVInstruction::decode(sub_x1_x2_x0).unwrap();
 
     match decoded {
         RiscVInstruction::Sub { rd, rs1, rs2 } => {
-            assert_eq!(rd, 1);
-            assert_eq!(rs1, 2);
-            assert_eq!(rs2, 0);
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X0);
         }
         _ => panic!("Expected SUB instruction"),
     }
@@ -93,13 +94,13 @@ fn min_rs2() {
 #[test]
 fn max_rs2() {
     let sub_x1_x2_x31 = 0x41f10033 | (1 << 7);
-    let decoded = RiscVInstruction::decode(sub_x1_x2_x31);
+    let decoded = RiscVInstruction::decode(sub_x1_x2_x31).unwrap();
 
     match decoded {
         RiscVInstruction::Sub { rd, rs1, rs2 } => {
-            assert_eq!(rd, 1);
-            assert_eq!(rs1, 2);
-            assert_eq!(rs2, 31);
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X31);
         }
         _ => panic!("Expected SUB instruction"),
     }
@@ -108,13 +109,13 @@ fn max_rs2() {
 #[test]
 fn all_max_values() {
     let sub_x31_x31_x31 = 0x41ff8fb3;
-    let decoded = RiscVInstruction::decode(sub_x31_x31_x31);
+    let decoded = RiscVInstruction::decode(sub_x31_x31_x31).unwrap();
 
     match decoded {
         RiscVInstruction::Sub { rd, rs1, rs2 } => {
-            assert_eq!(rd, 31);
-            assert_eq!(rs1, 31);
-            assert_eq!(rs2, 31);
+            assert_eq!(rd, Register::X31);
+            assert_eq!(rs1, Register::X31);
+            assert_eq!(rs2, Register::X31);
         }
         _ => panic!("Expected SUB instruction"),
     }
@@ -124,14 +125,14 @@ fn all_max_values() {
 fn invalid_funct7_should_be_unsupported() {
     // SUB with invalid funct7 (0x00 instead of 0x20)
     let invalid_sub = 0x003100b3;
-    let decoded = RiscVInstruction::decode(invalid_sub);
+    let decoded = RiscVInstruction::decode(invalid_sub).unwrap();
 
     match decoded {
         RiscVInstruction::Add { rd, rs1, rs2 } => {
             // This should decode as ADD, not SUB
-            assert_eq!(rd, 1);
-            assert_eq!(rs1, 2);
-            assert_eq!(rs2, 3);
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X3);
         }
         _ => panic!("Expected ADD instruction (not SUB)"),
     }
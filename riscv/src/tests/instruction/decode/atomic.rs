@@ -0,0 +1,229 @@
+use crate::error::{DecodeError, DecodeErrorKind};
+use crate::extension::{Extension, Extensions};
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+const RD: Register = Register::X10;
+const RS1: Register = Register::X11;
+const RS2: Register = Register::X12;
+const A: Extensions = Extensions::NONE.with(Extension::A);
+
+/// Every A instruction with rd = x10, rs1 = x11, rs2 = x12 and no ordering bits.
+fn samples() -> Vec<(u32, RiscVInstruction)> {
+    vec![
+        (
+            0x1005a52f,
+            RiscVInstruction::LrW {
+                rd: RD,
+                rs1: RS1,
+                aq: false,
+                rl: false,
+            },
+        ),
+        (
+            0x18c5a52f,
+            RiscVInstruction::ScW {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+                aq: false,
+                rl: false,
+            },
+        ),
+        (
+            0x08c5a52f,
+            RiscVInstruction::AmoswapW {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+                aq: false,
+                rl: false,
+            },
+        ),
+        (
+            0x00c5a52f,
+            RiscVInstruction::AmoaddW {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+                aq: false,
+                rl: false,
+            },
+        ),
+        (
+            0x20c5a52f,
+            RiscVInstruction::AmoxorW {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+                aq: false,
+                rl: false,
+            },
+        ),
+        (
+            0x60c5a52f,
+            RiscVInstruction::AmoandW {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+                aq: false,
+                rl: false,
+            },
+        ),
+        (
+            0x40c5a52f,
+            RiscVInstruction::AmoorW {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+                aq: false,
+                rl: false,
+            },
+        ),
+        (
+            0x80c5a52f,
+            RiscVInstruction::AmominW {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+                aq: false,
+                rl: false,
+            },
+        ),
+        (
+            0xa0c5a52f,
+            RiscVInstruction::AmomaxW {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+                aq: false,
+                rl: false,
+            },
+        ),
+        (
+            0xc0c5a52f,
+            RiscVInstruction::AmominuW {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+                aq: false,
+                rl: false,
+            },
+        ),
+        (
+            0xe0c5a52f,
+            RiscVInstruction::AmomaxuW {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+                aq: false,
+                rl: false,
+            },
+        ),
+    ]
+}
+
+#[test]
+fn enabled() {
+    for (word, instruction) in samples() {
+        assert_eq!(
+            RiscVInstruction::decode_with(word, A),
+            Ok(instruction),
+            "0x{:08x}",
+            word
+        );
+        assert_eq!(instruction.extension(), Some(Extension::A));
+    }
+}
+
+#[test]
+fn disabled() {
+    for (word, _) in samples() {
+        assert_eq!(
+            RiscVInstruction::decode(word),
+            Err(DecodeError::new(
+                word,
+                DecodeErrorKind::UnsupportedExtension(Extension::A)
+            ))
+        );
+    }
+}
+
+#[test]
+fn ordering_bits() {
+    let amomaxu_w_aqrl = 0xe7ffafaf;
+    let decoded = RiscVInstruction::decode_with(amomaxu_w_aqrl, A).unwrap();
+
+    match decoded {
+        RiscVInstruction::AmomaxuW {
+            rd,
+            rs1,
+            rs2,
+            aq,
+            rl,
+        } => {
+            assert_eq!(rd, Register::X31);
+            assert_eq!(rs1, Register::X31);
+            assert_eq!(rs2, Register::X31);
+            assert!(aq);
+            assert!(rl);
+        }
+        _ => panic!("Expected AMOMAXU.W instruction"),
+    }
+
+    let sc_w_rl = 0x1ac5a52f;
+    let decoded = RiscVInstruction::decode_with(sc_w_rl, A).unwrap();
+
+    match decoded {
+        RiscVInstruction::ScW { aq, rl, .. } => {
+            assert!(!aq);
+            assert!(rl);
+        }
+        _ => panic!("Expected SC.W instruction"),
+    }
+}
+
+#[test]
+fn lr_with_non_zero_rs2() {
+    // LR.W with rs2 = x12
+    let lr_with_rs2 = 0x10c5a52f;
+    let decoded = RiscVInstruction::decode_with(lr_with_rs2, A);
+
+    match decoded {
+        Err(DecodeError { word, kind }) => {
+            assert_eq!(word, lr_with_rs2);
+            assert_eq!(kind, DecodeErrorKind::InvalidOperands);
+        }
+        _ => panic!("Expected decode error"),
+    }
+}
+
+#[test]
+fn doubleword_funct3() {
+    // AMOADD.D, which only exists in RV64A.
+    let amoadd_d = 0x00c5b52f;
+    let decoded = RiscVInstruction::decode_with(amoadd_d, A);
+
+    match decoded {
+        Err(DecodeError { word, kind }) => {
+            assert_eq!(word, amoadd_d);
+            assert_eq!(kind, DecodeErrorKind::ReservedFunct3);
+        }
+        _ => panic!("Expected decode error"),
+    }
+}
+
+#[test]
+fn unassigned_funct5() {
+    // funct5 0b00101 is not assigned to any AMO.
+    let unassigned = 0x28c5a52f;
+    let decoded = RiscVInstruction::decode_with(unassigned, A);
+
+    match decoded {
+        Err(DecodeError { word, kind }) => {
+            assert_eq!(word, unassigned);
+            assert_eq!(kind, DecodeErrorKind::ReservedFunct7);
+        }
+        _ => panic!("Expected decode error"),
+    }
+}
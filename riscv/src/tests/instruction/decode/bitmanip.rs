@@ -0,0 +1,348 @@
+use crate::error::{DecodeError, DecodeErrorKind};
+use crate::extension::{Extension, Extensions};
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+const RD: Register = Register::X1;
+const RS1: Register = Register::X2;
+const RS2: Register = Register::X3;
+
+/// Every Zba, Zbb and Zbs instruction with rd = x1, rs1 = x2, rs2 = x3 and a shift
+/// amount of 7, along with the extension it belongs to.
+fn samples() -> Vec<(u32, RiscVInstruction, Extension)> {
+    vec![
+        (
+            0x203120b3,
+            RiscVInstruction::Sh1add {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            Extension::Zba,
+        ),
+        (
+            0x203140b3,
+            RiscVInstruction::Sh2add {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            Extension::Zba,
+        ),
+        (
+            0x203160b3,
+            RiscVInstruction::Sh3add {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            Extension::Zba,
+        ),
+        (
+            0x403170b3,
+            RiscVInstruction::Andn {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            Extension::Zbb,
+        ),
+        (
+            0x403160b3,
+            RiscVInstruction::Orn {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            Extension::Zbb,
+        ),
+        (
+            0x403140b3,
+            RiscVInstruction::Xnor {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            Extension::Zbb,
+        ),
+        (
+            0x60011093,
+            RiscVInstruction::Clz { rd: RD, rs1: RS1 },
+            Extension::Zbb,
+        ),
+        (
+            0x60111093,
+            RiscVInstruction::Ctz { rd: RD, rs1: RS1 },
+            Extension::Zbb,
+        ),
+        (
+            0x60211093,
+            RiscVInstruction::Cpop { rd: RD, rs1: RS1 },
+            Extension::Zbb,
+        ),
+        (
+            0x0a3160b3,
+            RiscVInstruction::Max {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            Extension::Zbb,
+        ),
+        (
+            0x0a3170b3,
+            RiscVInstruction::Maxu {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            Extension::Zbb,
+        ),
+        (
+            0x0a3140b3,
+            RiscVInstruction::Min {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            Extension::Zbb,
+        ),
+        (
+            0x0a3150b3,
+            RiscVInstruction::Minu {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            Extension::Zbb,
+        ),
+        (
+            0x60411093,
+            RiscVInstruction::SextB { rd: RD, rs1: RS1 },
+            Extension::Zbb,
+        ),
+        (
+            0x60511093,
+            RiscVInstruction::SextH { rd: RD, rs1: RS1 },
+            Extension::Zbb,
+        ),
+        (
+            0x080140b3,
+            RiscVInstruction::ZextH { rd: RD, rs1: RS1 },
+            Extension::Zbb,
+        ),
+        (
+            0x603110b3,
+            RiscVInstruction::Rol {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            Extension::Zbb,
+        ),
+        (
+            0x603150b3,
+            RiscVInstruction::Ror {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            Extension::Zbb,
+        ),
+        (
+            0x60715093,
+            RiscVInstruction::Rori {
+                rd: RD,
+                rs1: RS1,
+                imm: 7,
+            },
+            Extension::Zbb,
+        ),
+        (
+            0x28715093,
+            RiscVInstruction::OrcB { rd: RD, rs1: RS1 },
+            Extension::Zbb,
+        ),
+        (
+            0x69815093,
+            RiscVInstruction::Rev8 { rd: RD, rs1: RS1 },
+            Extension::Zbb,
+        ),
+        (
+            0x483110b3,
+            RiscVInstruction::Bclr {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            Extension::Zbs,
+        ),
+        (
+            0x48711093,
+            RiscVInstruction::Bclri {
+                rd: RD,
+                rs1: RS1,
+                imm: 7,
+            },
+            Extension::Zbs,
+        ),
+        (
+            0x483150b3,
+            RiscVInstruction::Bext {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            Extension::Zbs,
+        ),
+        (
+            0x48715093,
+            RiscVInstruction::Bexti {
+                rd: RD,
+                rs1: RS1,
+                imm: 7,
+            },
+            Extension::Zbs,
+        ),
+        (
+            0x683110b3,
+            RiscVInstruction::Binv {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            Extension::Zbs,
+        ),
+        (
+            0x68711093,
+            RiscVInstruction::Binvi {
+                rd: RD,
+                rs1: RS1,
+                imm: 7,
+            },
+            Extension::Zbs,
+        ),
+        (
+            0x283110b3,
+            RiscVInstruction::Bset {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            Extension::Zbs,
+        ),
+        (
+            0x28711093,
+            RiscVInstruction::Bseti {
+                rd: RD,
+                rs1: RS1,
+                imm: 7,
+            },
+            Extension::Zbs,
+        ),
+    ]
+}
+
+#[test]
+fn enabled() {
+    for (word, instruction, extension) in samples() {
+        assert_eq!(
+            RiscVInstruction::decode_with(word, Extensions::ALL),
+            Ok(instruction),
+            "0x{:08x}",
+            word
+        );
+        assert_eq!(
+            RiscVInstruction::decode_with(word, Extensions::NONE.with(extension)),
+            Ok(instruction),
+            "0x{:08x}",
+            word
+        );
+        assert_eq!(instruction.extension(), Some(extension));
+    }
+}
+
+#[test]
+fn disabled() {
+    for (word, _, extension) in samples() {
+        assert_eq!(
+            RiscVInstruction::decode(word),
+            Err(DecodeError {
+                word,
+                kind: DecodeErrorKind::UnsupportedExtension(extension),
+            })
+        );
+    }
+}
+
+#[test]
+fn other_extensions_do_not_enable() {
+    for (word, _, extension) in samples() {
+        let others: Extensions = Extension::OPTIONAL
+            .into_iter()
+            .filter(|other| *other != extension)
+            .collect();
+
+        assert_eq!(
+            RiscVInstruction::decode_with(word, others),
+            Err(DecodeError {
+                word,
+                kind: DecodeErrorKind::UnsupportedExtension(extension),
+            })
+        );
+    }
+}
+
+#[test]
+fn base_instructions_need_no_extension() {
+    let add_x1_x2_x3 = 0x003100b3;
+    let decoded = RiscVInstruction::decode_with(add_x1_x2_x3, Extensions::ALL).unwrap();
+
+    assert_eq!(decoded.extension(), None);
+    assert_eq!(RiscVInstruction::decode(add_x1_x2_x3), Ok(decoded));
+}
+
+#[test]
+fn max_shift_amount() {
+    let rori_x1_x2_31 = 0x61f15093;
+    let decoded = RiscVInstruction::decode_with(rori_x1_x2_31, Extensions::ALL);
+
+    assert_eq!(
+        decoded,
+        Ok(RiscVInstruction::Rori {
+            rd: RD,
+            rs1: RS1,
+            imm: 31,
+        })
+    );
+}
+
+#[test]
+fn shift_amount_out_of_range() {
+    // RORI with bit 5 of the shift amount set, which is reserved on RV32.
+    let rori_x1_x2_32 = 0x62015093;
+    let decoded = RiscVInstruction::decode_with(rori_x1_x2_32, Extensions::ALL);
+
+    match decoded {
+        Err(DecodeError { word, kind }) => {
+            assert_eq!(word, 0x62015093);
+            assert_eq!(kind, DecodeErrorKind::ReservedFunct7);
+        }
+        _ => panic!("Expected decode error"),
+    }
+}
+
+#[test]
+fn unary_with_unknown_rs2() {
+    // The unary Zbb instructions select the operation through rs2; 3 is unassigned.
+    let unassigned = 0x60311093;
+    let decoded = RiscVInstruction::decode_with(unassigned, Extensions::ALL);
+
+    match decoded {
+        Err(DecodeError { word, kind }) => {
+            assert_eq!(word, 0x60311093);
+            assert_eq!(kind, DecodeErrorKind::ReservedFunct7);
+        }
+        _ => panic!("Expected decode error"),
+    }
+}
@@ -0,0 +1,200 @@
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn basic() {
+    let bgeu_x1_x2_8 = 0x0020f463;
+    let decoded = RiscVInstruction::decode(bgeu_x1_x2_8).unwrap();
+
+    match decoded {
+        RiscVInstruction::Bgeu { rs1, rs2, imm } => {
+            assert_eq!(rs1, Register::X1);
+            assert_eq!(rs2, Register::X2);
+            assert_eq!(imm, 8);
+        }
+        _ => panic!("Expected BGEU instruction"),
+    }
+}
+
+#[test]
+fn min_rs1() {
+    let bgeu_x0_x1_8 = 0x00107463;
+    let decoded = RiscVInstruction::decode(bgeu_x0_x1_8).unwrap();
+
+    match decoded {
+        RiscVInstruction::Bgeu { rs1, rs2, imm } => {
+            assert_eq!(rs1, Register::X0);
+            assert_eq!(rs2, Register::X1);
+            assert_eq!(imm, 8);
+        }
+        _ => panic!("Expected BGEU instruction"),
+    }
+}
+
+#[test]
+fn max_rs1() {
+    let bgeu_x31_x1_8 = 0x001ff463;
+    let decoded = RiscVInstruction::decode(bgeu_x31_x1_8).unwrap();
+
+    match decoded {
+        RiscVInstruction::Bgeu { rs1, rs2, imm } => {
+            assert_eq!(rs1, Register::X31);
+            assert_eq!(rs2, Register::X1);
+            assert_eq!(imm, 8);
+        }
+        _ => panic!("Expected BGEU instruction"),
+    }
+}
+
+#[test]
+fn min_rs2() {
+    let bgeu_x1_x0_8 = 0x0000f463;
+    let decoded = RiscVInstruction::decode(bgeu_x1_x0_8).unwrap();
+
+    match decoded {
+        RiscVInstruction::Bgeu { rs1, rs2, imm } => {
+            assert_eq!(rs1, Register::X1);
+            assert_eq!(rs2, Register::X0);
+            assert_eq!(imm, 8);
+        }
+        _ => panic!("Expected BGEU instruction"),
+    }
+}
+
+#[test]
+fn max_rs2() {
+    let bgeu_x1_x31_8 = 0x01f0f463;
+    let decoded = RiscVInstruction::decode(bgeu_x1_x31_8).unwrap();
+
+    match decoded {
+        RiscVInstruction::Bgeu { rs1, rs2, imm } => {
+            assert_eq!(rs1, Register::X1);
+            assert_eq!(rs2, Register::X31);
+            assert_eq!(imm, 8);
+        }
+        _ => panic!("Expected BGEU instruction"),
+    }
+}
+
+#[test]
+fn negative_imm() {
+    let bgeu_x1_x2_neg8 = 0xfe20fce3;
+    let decoded = RiscVInstruction::decode(bgeu_x1_x2_neg8).unwrap();
+
+    match decoded {
+        RiscVInstruction::Bgeu { rs1, rs2, imm } => {
+            assert_eq!(rs1, Register::X1);
+            assert_eq!(rs2, Register::X2);
+            assert_eq!(imm, -8);
+        }
+        _ => panic!("Expected BGEU instruction"),
+    }
+}
+
+#[test]
+fn zero_imm() {
+    let bgeu_x1_x2_0 = 0x0020f063;
+    let decoded = RiscVInstruction::decode(bgeu_x1_x2_0).unwrap();
+
+    match decoded {
+        RiscVInstruction::Bgeu { rs1, rs2, imm } => {
+            assert_eq!(rs1, Register::X1);
+            assert_eq!(rs2, Register::X2);
+            assert_eq!(imm, 0);
+        }
+        _ => panic!("Expected BGEU instruction"),
+    }
+}
+
+#[test]
+fn max_positive_imm() {
+    let bgeu_x1_x2_4094 = 0x7e20ffe3;
+    let decoded = RiscVInstruction::decode(bgeu_x1_x2_4094).unwrap();
+
+    match decoded {
+        RiscVInstruction::Bgeu { rs1, rs2, imm } => {
+            assert_eq!(rs1, Register::X1);
+            assert_eq!(rs2, Register::X2);
+            assert_eq!(imm, 4094);
+        }
+        _ => panic!("Expected BGEU instruction"),
+    }
+}
+
+#[test]
+fn min_negative_imm() {
+    let bgeu_x1_x2_neg4096 = 0x8020f063;
+    let decoded = RiscVInstruction::decode(bgeu_x1_x2_neg4096).unwrap();
+
+    match decoded {
+        RiscVInstruction::Bgeu { rs1, rs2, imm } => {
+            assert_eq!(rs1, Register::X1);
+            assert_eq!(rs2, Register::X2);
+            assert_eq!(imm, -4096);
+        }
+        _ => panic!("Expected BGEU instruction"),
+    }
+}
+
+#[test]
+fn neg_two_imm() {
+    let bgeu_x1_x2_neg2 = 0xfe20ffe3;
+    let decoded = RiscVInstruction::decode(bgeu_x1_x2_neg2).unwrap();
+
+    match decoded {
+        RiscVInstruction::Bgeu { rs1, rs2, imm } => {
+            assert_eq!(rs1, Register::X1);
+            assert_eq!(rs2, Register::X2);
+            assert_eq!(imm, -2);
+        }
+        _ => panic!("Expected BGEU instruction"),
+    }
+}
+
+#[test]
+fn imm_bit_11() {
+    // imm[11] is encoded in bit 7
+    let bgeu_x1_x2_2048 = 0x0020f0e3;
+    let decoded = RiscVInstruction::decode(bgeu_x1_x2_2048).unwrap();
+
+    match decoded {
+        RiscVInstruction::Bgeu { rs1, rs2, imm } => {
+            assert_eq!(rs1, Register::X1);
+            assert_eq!(rs2, Register::X2);
+            assert_eq!(imm, 2048);
+        }
+        _ => panic!("Expected BGEU instruction"),
+    }
+}
+
+#[test]
+fn imm_bits_4_1() {
+    // imm[4:1] is encoded in bits 11:8
+    let bgeu_x1_x2_30 = 0x0020ff63;
+    let decoded = RiscVInstruction::decode(bgeu_x1_x2_30).unwrap();
+
+    match decoded {
+        RiscVInstruction::Bgeu { rs1, rs2, imm } => {
+            assert_eq!(rs1, Register::X1);
+            assert_eq!(rs2, Register::X2);
+            assert_eq!(imm, 30);
+        }
+        _ => panic!("Expected BGEU instruction"),
+    }
+}
+
+#[test]
+fn imm_bits_10_5() {
+    // imm[10:5] is encoded in bits 30:25
+    let bgeu_x1_x2_2016 = 0x7e20f063;
+    let decoded = RiscVInstruction::decode(bgeu_x1_x2_2016).unwrap();
+
+    match decoded {
+        RiscVInstruction::Bgeu { rs1, rs2, imm } => {
+            assert_eq!(rs1, Register::X1);
+            assert_eq!(rs2, Register::X2);
+            assert_eq!(imm, 2016);
+        }
+        _ => panic!("Expected BGEU instruction"),
+    }
+}
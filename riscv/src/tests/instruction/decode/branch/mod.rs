@@ -0,0 +1,6 @@
+mod beq;
+mod bge;
+mod bgeu;
+mod blt;
+mod bltu;
+mod bne;
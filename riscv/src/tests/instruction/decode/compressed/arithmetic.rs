@@ -0,0 +1,448 @@
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn c_addi() {
+    // c.addi x8, 1 => addi x8, x8, 1
+    let decoded = RiscVInstruction::decode_compressed(0x0405).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Addi {
+            rd: Register::X8,
+            rs1: Register::X8,
+            imm: 1
+        }
+    );
+}
+
+#[test]
+fn c_addi_negative() {
+    // c.addi x2, -16 => addi x2, x2, -16
+    let decoded = RiscVInstruction::decode_compressed(0x1141).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Addi {
+            rd: Register::X2,
+            rs1: Register::X2,
+            imm: -16
+        }
+    );
+}
+
+#[test]
+fn c_addi_max() {
+    // c.addi x31, 31 => addi x31, x31, 31
+    let decoded = RiscVInstruction::decode_compressed(0x0ffd).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Addi {
+            rd: Register::X31,
+            rs1: Register::X31,
+            imm: 31
+        }
+    );
+}
+
+#[test]
+fn c_addi_min() {
+    // c.addi x31, -32 => addi x31, x31, -32
+    let decoded = RiscVInstruction::decode_compressed(0x1f81).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Addi {
+            rd: Register::X31,
+            rs1: Register::X31,
+            imm: -32
+        }
+    );
+}
+
+#[test]
+fn c_nop() {
+    // c.nop => addi x0, x0, 0
+    let decoded = RiscVInstruction::decode_compressed(0x0001).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Addi {
+            rd: Register::X0,
+            rs1: Register::X0,
+            imm: 0
+        }
+    );
+}
+
+#[test]
+fn c_li() {
+    // c.li x10, 0 => addi x10, x0, 0
+    let decoded = RiscVInstruction::decode_compressed(0x4501).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Addi {
+            rd: Register::X10,
+            rs1: Register::X0,
+            imm: 0
+        }
+    );
+}
+
+#[test]
+fn c_li_negative() {
+    // c.li x15, -1 => addi x15, x0, -1
+    let decoded = RiscVInstruction::decode_compressed(0x57fd).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Addi {
+            rd: Register::X15,
+            rs1: Register::X0,
+            imm: -1
+        }
+    );
+}
+
+#[test]
+fn c_addi16sp() {
+    // c.addi16sp -48 => addi x2, x2, -48
+    let decoded = RiscVInstruction::decode_compressed(0x7179).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Addi {
+            rd: Register::X2,
+            rs1: Register::X2,
+            imm: -48
+        }
+    );
+}
+
+#[test]
+fn c_addi16sp_max() {
+    // c.addi16sp 496 => addi x2, x2, 496
+    let decoded = RiscVInstruction::decode_compressed(0x617d).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Addi {
+            rd: Register::X2,
+            rs1: Register::X2,
+            imm: 496
+        }
+    );
+}
+
+#[test]
+fn c_addi16sp_min() {
+    // c.addi16sp -512 => addi x2, x2, -512
+    let decoded = RiscVInstruction::decode_compressed(0x7101).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Addi {
+            rd: Register::X2,
+            rs1: Register::X2,
+            imm: -512
+        }
+    );
+}
+
+#[test]
+fn c_addi4spn() {
+    // c.addi4spn x8, 16 => addi x8, x2, 16
+    let decoded = RiscVInstruction::decode_compressed(0x0800).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Addi {
+            rd: Register::X8,
+            rs1: Register::X2,
+            imm: 16
+        }
+    );
+}
+
+#[test]
+fn c_addi4spn_max() {
+    // c.addi4spn x15, 1020 => addi x15, x2, 1020
+    let decoded = RiscVInstruction::decode_compressed(0x1ffc).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Addi {
+            rd: Register::X15,
+            rs1: Register::X2,
+            imm: 1020
+        }
+    );
+}
+
+#[test]
+fn c_lui() {
+    // c.lui x10, 0x1 => lui x10, 0x1
+    let decoded = RiscVInstruction::decode_compressed(0x6505).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Lui {
+            rd: Register::X10,
+            imm: 0x1000
+        }
+    );
+}
+
+#[test]
+fn c_lui_negative() {
+    // c.lui x10, 0xfffff => lui x10, 0xfffff
+    let decoded = RiscVInstruction::decode_compressed(0x757d).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Lui {
+            rd: Register::X10,
+            imm: -0x1000
+        }
+    );
+}
+
+#[test]
+fn c_lui_max() {
+    // c.lui x31, 0x1f => lui x31, 0x1f
+    let decoded = RiscVInstruction::decode_compressed(0x6ffd).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Lui {
+            rd: Register::X31,
+            imm: 0x1f000
+        }
+    );
+}
+
+#[test]
+fn c_lui_min() {
+    // c.lui x31, 0xfffe0 => lui x31, 0xfffe0
+    let decoded = RiscVInstruction::decode_compressed(0x7f81).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Lui {
+            rd: Register::X31,
+            imm: -0x20000
+        }
+    );
+}
+
+#[test]
+fn c_srli() {
+    // c.srli x8, 3 => srli x8, x8, 3
+    let decoded = RiscVInstruction::decode_compressed(0x800d).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Srli {
+            rd: Register::X8,
+            rs1: Register::X8,
+            imm: 3
+        }
+    );
+}
+
+#[test]
+fn c_srli_max() {
+    // c.srli x15, 31 => srli x15, x15, 31
+    let decoded = RiscVInstruction::decode_compressed(0x83fd).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Srli {
+            rd: Register::X15,
+            rs1: Register::X15,
+            imm: 31
+        }
+    );
+}
+
+#[test]
+fn c_srai() {
+    // c.srai x9, 5 => srai x9, x9, 5
+    let decoded = RiscVInstruction::decode_compressed(0x8495).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Srai {
+            rd: Register::X9,
+            rs1: Register::X9,
+            imm: 5
+        }
+    );
+}
+
+#[test]
+fn c_andi() {
+    // c.andi x10, -1 => andi x10, x10, -1
+    let decoded = RiscVInstruction::decode_compressed(0x997d).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Andi {
+            rd: Register::X10,
+            rs1: Register::X10,
+            imm: -1
+        }
+    );
+}
+
+#[test]
+fn c_andi_positive() {
+    // c.andi x10, 15 => andi x10, x10, 15
+    let decoded = RiscVInstruction::decode_compressed(0x893d).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Andi {
+            rd: Register::X10,
+            rs1: Register::X10,
+            imm: 15
+        }
+    );
+}
+
+#[test]
+fn c_sub() {
+    // c.sub x8, x9 => sub x8, x8, x9
+    let decoded = RiscVInstruction::decode_compressed(0x8c05).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Sub {
+            rd: Register::X8,
+            rs1: Register::X8,
+            rs2: Register::X9
+        }
+    );
+}
+
+#[test]
+fn c_xor() {
+    // c.xor x10, x11 => xor x10, x10, x11
+    let decoded = RiscVInstruction::decode_compressed(0x8d2d).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Xor {
+            rd: Register::X10,
+            rs1: Register::X10,
+            rs2: Register::X11
+        }
+    );
+}
+
+#[test]
+fn c_or() {
+    // c.or x14, x15 => or x14, x14, x15
+    let decoded = RiscVInstruction::decode_compressed(0x8f5d).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Or {
+            rd: Register::X14,
+            rs1: Register::X14,
+            rs2: Register::X15
+        }
+    );
+}
+
+#[test]
+fn c_and() {
+    // c.and x15, x8 => and x15, x15, x8
+    let decoded = RiscVInstruction::decode_compressed(0x8fe1).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::And {
+            rd: Register::X15,
+            rs1: Register::X15,
+            rs2: Register::X8
+        }
+    );
+}
+
+#[test]
+fn c_slli() {
+    // c.slli x10, 2 => slli x10, x10, 2
+    let decoded = RiscVInstruction::decode_compressed(0x050a).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Slli {
+            rd: Register::X10,
+            rs1: Register::X10,
+            imm: 2
+        }
+    );
+}
+
+#[test]
+fn c_slli_max() {
+    // c.slli x31, 31 => slli x31, x31, 31
+    let decoded = RiscVInstruction::decode_compressed(0x0ffe).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Slli {
+            rd: Register::X31,
+            rs1: Register::X31,
+            imm: 31
+        }
+    );
+}
+
+#[test]
+fn c_mv() {
+    // c.mv x10, x11 => add x10, x0, x11
+    let decoded = RiscVInstruction::decode_compressed(0x852e).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Add {
+            rd: Register::X10,
+            rs1: Register::X0,
+            rs2: Register::X11
+        }
+    );
+}
+
+#[test]
+fn c_add() {
+    // c.add x10, x11 => add x10, x10, x11
+    let decoded = RiscVInstruction::decode_compressed(0x952e).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Add {
+            rd: Register::X10,
+            rs1: Register::X10,
+            rs2: Register::X11
+        }
+    );
+}
+
+#[test]
+fn c_add_max() {
+    // c.add x31, x31 => add x31, x31, x31
+    let decoded = RiscVInstruction::decode_compressed(0x9ffe).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Add {
+            rd: Register::X31,
+            rs1: Register::X31,
+            rs2: Register::X31
+        }
+    );
+}
@@ -0,0 +1,107 @@
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn c_beqz() {
+    // c.beqz x8, 8 => beq x8, x0, 8
+    let decoded = RiscVInstruction::decode_compressed(0xc401).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Beq {
+            rs1: Register::X8,
+            rs2: Register::X0,
+            imm: 8
+        }
+    );
+}
+
+#[test]
+fn c_beqz_negative() {
+    // c.beqz x10, -4 => beq x10, x0, -4
+    let decoded = RiscVInstruction::decode_compressed(0xdd75).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Beq {
+            rs1: Register::X10,
+            rs2: Register::X0,
+            imm: -4
+        }
+    );
+}
+
+#[test]
+fn c_beqz_max() {
+    // c.beqz x15, 254 => beq x15, x0, 254
+    let decoded = RiscVInstruction::decode_compressed(0xcffd).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Beq {
+            rs1: Register::X15,
+            rs2: Register::X0,
+            imm: 254
+        }
+    );
+}
+
+#[test]
+fn c_beqz_min() {
+    // c.beqz x15, -256 => beq x15, x0, -256
+    let decoded = RiscVInstruction::decode_compressed(0xd381).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Beq {
+            rs1: Register::X15,
+            rs2: Register::X0,
+            imm: -256
+        }
+    );
+}
+
+#[test]
+fn c_bnez() {
+    // c.bnez x9, 16 => bne x9, x0, 16
+    let decoded = RiscVInstruction::decode_compressed(0xe881).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Bne {
+            rs1: Register::X9,
+            rs2: Register::X0,
+            imm: 16
+        }
+    );
+}
+
+#[test]
+fn c_bnez_negative() {
+    // c.bnez x11, -2 => bne x11, x0, -2
+    let decoded = RiscVInstruction::decode_compressed(0xfdfd).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Bne {
+            rs1: Register::X11,
+            rs2: Register::X0,
+            imm: -2
+        }
+    );
+}
+
+#[test]
+fn c_bnez_scrambled_bits() {
+    // c.bnez x8, 0xaa: every offset bit lands in its own position
+    let decoded = RiscVInstruction::decode_compressed(0xe44d).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Bne {
+            rs1: Register::X8,
+            rs2: Register::X0,
+            imm: 170
+        }
+    );
+}
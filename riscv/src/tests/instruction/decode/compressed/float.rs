@@ -0,0 +1,138 @@
+use crate::error::{DecodeError, DecodeErrorKind};
+use crate::extension::{Extension, Extensions};
+use crate::instruction::RiscVInstruction;
+use crate::profile::Profile;
+use crate::register::{FloatRegister, Register};
+
+const FD: Extensions = Extensions::from_slice(&[Extension::F, Extension::D]);
+
+fn decode(half: u16, extensions: Extensions) -> Result<RiscVInstruction, DecodeError> {
+    RiscVInstruction::decode_compressed_for(half, Profile::Rv32i, extensions)
+}
+
+#[test]
+fn c_flw() {
+    // c.flw f10, 4(x11) => flw f10, 4(x11)
+    assert_eq!(
+        decode(0x61c8, FD),
+        Ok(RiscVInstruction::Flw {
+            rd: FloatRegister::F10,
+            rs1: Register::X11,
+            imm: 4
+        })
+    );
+}
+
+#[test]
+fn c_fsw() {
+    // c.fsw f12, 124(x9) => fsw f12, 124(x9)
+    assert_eq!(
+        decode(0xfcf0, FD),
+        Ok(RiscVInstruction::Fsw {
+            rs1: Register::X9,
+            rs2: FloatRegister::F12,
+            imm: 124
+        })
+    );
+}
+
+#[test]
+fn c_fld() {
+    // c.fld f10, 8(x11) => fld f10, 8(x11)
+    assert_eq!(
+        decode(0x2588, FD),
+        Ok(RiscVInstruction::Fld {
+            rd: FloatRegister::F10,
+            rs1: Register::X11,
+            imm: 8
+        })
+    );
+}
+
+#[test]
+fn c_fsd() {
+    // c.fsd f9, 248(x15) => fsd f9, 248(x15)
+    assert_eq!(
+        decode(0xbfe4, FD),
+        Ok(RiscVInstruction::Fsd {
+            rs1: Register::X15,
+            rs2: FloatRegister::F9,
+            imm: 248
+        })
+    );
+}
+
+#[test]
+fn c_flwsp() {
+    // c.flwsp f0, 252(x2) => flw f0, 252(x2)
+    assert_eq!(
+        decode(0x707e, FD),
+        Ok(RiscVInstruction::Flw {
+            rd: FloatRegister::F0,
+            rs1: Register::SP,
+            imm: 252
+        })
+    );
+}
+
+#[test]
+fn c_fswsp() {
+    // c.fswsp f27, 4(x2) => fsw f27, 4(x2)
+    assert_eq!(
+        decode(0xe26e, FD),
+        Ok(RiscVInstruction::Fsw {
+            rs1: Register::SP,
+            rs2: FloatRegister::F27,
+            imm: 4
+        })
+    );
+}
+
+#[test]
+fn c_fldsp() {
+    // c.fldsp f0, 504(x2) => fld f0, 504(x2)
+    assert_eq!(
+        decode(0x307e, FD),
+        Ok(RiscVInstruction::Fld {
+            rd: FloatRegister::F0,
+            rs1: Register::SP,
+            imm: 504
+        })
+    );
+}
+
+#[test]
+fn c_fsdsp() {
+    // c.fsdsp f31, 8(x2) => fsd f31, 8(x2)
+    assert_eq!(
+        decode(0xa47e, FD),
+        Ok(RiscVInstruction::Fsd {
+            rs1: Register::SP,
+            rs2: FloatRegister::F31,
+            imm: 8
+        })
+    );
+}
+
+#[test]
+fn each_precision_needs_its_extension() {
+    let f = Extensions::NONE.with(Extension::F);
+    let d = Extensions::NONE.with(Extension::D);
+
+    assert!(decode(0x61c8, f).is_ok());
+    assert_eq!(
+        decode(0x2588, f),
+        Err(DecodeError::new(
+            0x2588,
+            DecodeErrorKind::UnsupportedExtension(Extension::D)
+        ))
+    );
+    assert!(decode(0x307e, d).is_ok());
+    assert_eq!(
+        decode(0x707e, d),
+        Err(DecodeError::new(
+            0x707e,
+            DecodeErrorKind::UnsupportedExtension(Extension::F)
+        ))
+    );
+}
@@ -0,0 +1,145 @@
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn c_j() {
+    // c.j 8 => jal x0, 8
+    let decoded = RiscVInstruction::decode_compressed(0xa021).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Jal {
+            rd: Register::X0,
+            imm: 8
+        }
+    );
+}
+
+#[test]
+fn c_j_negative() {
+    // c.j -2 => jal x0, -2
+    let decoded = RiscVInstruction::decode_compressed(0xbffd).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Jal {
+            rd: Register::X0,
+            imm: -2
+        }
+    );
+}
+
+#[test]
+fn c_j_max() {
+    // c.j 2046 => jal x0, 2046
+    let decoded = RiscVInstruction::decode_compressed(0xaffd).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Jal {
+            rd: Register::X0,
+            imm: 2046
+        }
+    );
+}
+
+#[test]
+fn c_j_min() {
+    // c.j -2048 => jal x0, -2048
+    let decoded = RiscVInstruction::decode_compressed(0xb001).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Jal {
+            rd: Register::X0,
+            imm: -2048
+        }
+    );
+}
+
+#[test]
+fn c_j_scrambled_bits() {
+    // c.j 0x5aa: every offset bit lands in its own position
+    let decoded = RiscVInstruction::decode_compressed(0xa36d).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Jal {
+            rd: Register::X0,
+            imm: 1450
+        }
+    );
+}
+
+#[test]
+fn c_jal() {
+    // c.jal 16 => jal x1, 16
+    let decoded = RiscVInstruction::decode_compressed(0x2801).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Jal {
+            rd: Register::X1,
+            imm: 16
+        }
+    );
+}
+
+#[test]
+fn c_jal_negative() {
+    // c.jal -16 => jal x1, -16
+    let decoded = RiscVInstruction::decode_compressed(0x3fc5).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Jal {
+            rd: Register::X1,
+            imm: -16
+        }
+    );
+}
+
+#[test]
+fn c_jr() {
+    // c.jr x1 => jalr x0, x1, 0
+    let decoded = RiscVInstruction::decode_compressed(0x8082).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Jalr {
+            rd: Register::X0,
+            rs1: Register::X1,
+            imm: 0
+        }
+    );
+}
+
+#[test]
+fn c_jr_max() {
+    // c.jr x31 => jalr x0, x31, 0
+    let decoded = RiscVInstruction::decode_compressed(0x8f82).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Jalr {
+            rd: Register::X0,
+            rs1: Register::X31,
+            imm: 0
+        }
+    );
+}
+
+#[test]
+fn c_jalr() {
+    // c.jalr x5 => jalr x1, x5, 0
+    let decoded = RiscVInstruction::decode_compressed(0x9282).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Jalr {
+            rd: Register::X1,
+            rs1: Register::X5,
+            imm: 0
+        }
+    );
+}
@@ -0,0 +1,107 @@
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn c_lw() {
+    // c.lw x10, 0(x10) => lw x10, 0(x10)
+    let decoded = RiscVInstruction::decode_compressed(0x4108).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Lw {
+            rd: Register::X10,
+            rs1: Register::X10,
+            imm: 0
+        }
+    );
+}
+
+#[test]
+fn c_lw_offset() {
+    // c.lw x8, 4(x9) => lw x8, 4(x9)
+    let decoded = RiscVInstruction::decode_compressed(0x40c0).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Lw {
+            rd: Register::X8,
+            rs1: Register::X9,
+            imm: 4
+        }
+    );
+}
+
+#[test]
+fn c_lw_max() {
+    // c.lw x15, 124(x15) => lw x15, 124(x15)
+    let decoded = RiscVInstruction::decode_compressed(0x5ffc).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Lw {
+            rd: Register::X15,
+            rs1: Register::X15,
+            imm: 124
+        }
+    );
+}
+
+#[test]
+fn c_lw_uimm_6() {
+    // c.lw x8, 64(x8): uimm[6] is encoded in bit 5
+    let decoded = RiscVInstruction::decode_compressed(0x4020).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Lw {
+            rd: Register::X8,
+            rs1: Register::X8,
+            imm: 64
+        }
+    );
+}
+
+#[test]
+fn c_lwsp() {
+    // c.lwsp x1, 12 => lw x1, 12(x2)
+    let decoded = RiscVInstruction::decode_compressed(0x40b2).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Lw {
+            rd: Register::X1,
+            rs1: Register::X2,
+            imm: 12
+        }
+    );
+}
+
+#[test]
+fn c_lwsp_max() {
+    // c.lwsp x31, 252 => lw x31, 252(x2)
+    let decoded = RiscVInstruction::decode_compressed(0x5ffe).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Lw {
+            rd: Register::X31,
+            rs1: Register::X2,
+            imm: 252
+        }
+    );
+}
+
+#[test]
+fn c_lwsp_zero() {
+    // c.lwsp x10, 0 => lw x10, 0(x2)
+    let decoded = RiscVInstruction::decode_compressed(0x4502).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Lw {
+            rd: Register::X10,
+            rs1: Register::X2,
+            imm: 0
+        }
+    );
+}
@@ -0,0 +1,9 @@
+mod arithmetic;
+mod branch;
+mod float;
+mod jump;
+mod load;
+mod rv64;
+mod store;
+mod system;
+mod unsupported;
@@ -0,0 +1,214 @@
+use crate::error::{DecodeError, DecodeErrorKind};
+use crate::extension::{Extension, Extensions};
+use crate::instruction::RiscVInstruction;
+use crate::profile::Profile;
+use crate::register::Register;
+
+fn decode_rv64(half: u16) -> Result<RiscVInstruction, DecodeError> {
+    RiscVInstruction::decode_compressed_for(half, Profile::Rv64i, Extensions::NONE)
+}
+
+#[test]
+fn c_ld() {
+    // c.ld x10, 248(x11) => ld x10, 248(x11)
+    assert_eq!(
+        decode_rv64(0x7de8),
+        Ok(RiscVInstruction::Ld {
+            rd: Register::X10,
+            rs1: Register::X11,
+            imm: 248
+        })
+    );
+}
+
+#[test]
+fn c_sd() {
+    // c.sd x10, 8(x11) => sd x10, 8(x11)
+    assert_eq!(
+        decode_rv64(0xe588),
+        Ok(RiscVInstruction::Sd {
+            rs1: Register::X11,
+            rs2: Register::X10,
+            imm: 8
+        })
+    );
+}
+
+#[test]
+fn c_ldsp() {
+    // c.ldsp x1, 504(x2) => ld x1, 504(x2)
+    assert_eq!(
+        decode_rv64(0x70fe),
+        Ok(RiscVInstruction::Ld {
+            rd: Register::X1,
+            rs1: Register::X2,
+            imm: 504
+        })
+    );
+}
+
+#[test]
+fn c_ldsp_x0_reserved() {
+    // c.ldsp x0, 0(x2)
+    assert_eq!(
+        decode_rv64(0x6002),
+        Err(DecodeError::new(
+            0x6002,
+            DecodeErrorKind::ReservedCompressed
+        ))
+    );
+}
+
+#[test]
+fn c_sdsp() {
+    // c.sdsp x1, 8(x2) => sd x1, 8(x2)
+    assert_eq!(
+        decode_rv64(0xe406),
+        Ok(RiscVInstruction::Sd {
+            rs1: Register::X2,
+            rs2: Register::X1,
+            imm: 8
+        })
+    );
+}
+
+#[test]
+fn c_addiw() {
+    // c.addiw x10, -1 => addiw x10, x10, -1
+    assert_eq!(
+        decode_rv64(0x357d),
+        Ok(RiscVInstruction::Addiw {
+            rd: Register::X10,
+            rs1: Register::X10,
+            imm: -1
+        })
+    );
+    // The same encoding is c.jal on RV32.
+    assert!(matches!(
+        RiscVInstruction::decode_compressed(0x357d),
+        Ok(RiscVInstruction::Jal { .. })
+    ));
+}
+
+#[test]
+fn c_addiw_x0_reserved() {
+    // c.addiw x0, 1
+    assert_eq!(
+        decode_rv64(0x2005),
+        Err(DecodeError::new(
+            0x2005,
+            DecodeErrorKind::ReservedCompressed
+        ))
+    );
+}
+
+#[test]
+fn c_addw_and_c_subw() {
+    // c.addw x10, x11 => addw x10, x10, x11
+    assert_eq!(
+        decode_rv64(0x9d2d),
+        Ok(RiscVInstruction::Addw {
+            rd: Register::X10,
+            rs1: Register::X10,
+            rs2: Register::X11
+        })
+    );
+    // c.subw x8, x15 => subw x8, x8, x15
+    assert_eq!(
+        decode_rv64(0x9c1d),
+        Ok(RiscVInstruction::Subw {
+            rd: Register::X8,
+            rs1: Register::X8,
+            rs2: Register::X15
+        })
+    );
+}
+
+#[test]
+fn reserved_word_operations() {
+    // Bit 12 set with funct2 = 2 and 3
+    for half in [0x9d4d, 0x9d6d] {
+        assert_eq!(
+            decode_rv64(half),
+            Err(DecodeError::new(
+                half as u32,
+                DecodeErrorKind::ReservedCompressed
+            ))
+        );
+    }
+}
+
+#[test]
+fn wide_shifts() {
+    // c.slli x10, 63 => slli x10, x10, 63
+    assert_eq!(
+        decode_rv64(0x157e),
+        Ok(RiscVInstruction::Slli {
+            rd: Register::X10,
+            rs1: Register::X10,
+            imm: 63
+        })
+    );
+    // c.srli x10, 32 => srli x10, x10, 32
+    assert_eq!(
+        decode_rv64(0x9101),
+        Ok(RiscVInstruction::Srli {
+            rd: Register::X10,
+            rs1: Register::X10,
+            imm: 32
+        })
+    );
+    // c.srai x10, 33 => srai x10, x10, 33
+    assert_eq!(
+        decode_rv64(0x9505),
+        Ok(RiscVInstruction::Srai {
+            rd: Register::X10,
+            rs1: Register::X10,
+            imm: 33
+        })
+    );
+}
+
+#[test]
+fn shared_encodings() {
+    // c.lw, c.add, c.andi, c.sub and c.j decode the same as on RV32.
+    for half in [0x4108, 0x952e, 0x8905, 0x8d0d, 0xa001] {
+        assert_eq!(decode_rv64(half), RiscVInstruction::decode_compressed(half));
+    }
+}
+
+#[test]
+fn double_precision_slots() {
+    // c.fld f10, 0(x10) is still a D instruction.
+    assert_eq!(
+        decode_rv64(0x2108),
+        Err(DecodeError::new(
+            0x2108,
+            DecodeErrorKind::UnsupportedExtension(Extension::D)
+        ))
+    );
+
+    // c.fld f10, 0(x10) and c.fsdsp f15, 16(x2) are not implemented, like fld and fsd.
+    let d = Extensions::NONE.with(Extension::D);
+    for half in [0x2108, 0xa83e] {
+        assert_eq!(
+            RiscVInstruction::decode_compressed_for(half, Profile::Rv64i, d),
+            Err(DecodeError::new(
+                half as u32,
+                DecodeErrorKind::Unimplemented
+            ))
+        );
+    }
+}
+
+#[test]
+fn single_precision_slots_stay_doublewords() {
+    // With F enabled, c.ld x10, 248(x11) and c.sdsp x15, 16(x2) are still integer.
+    let f = Extensions::NONE.with(Extension::F);
+    for half in [0x7de8, 0xe83e] {
+        assert_eq!(
+            RiscVInstruction::decode_compressed_for(half, Profile::Rv64i, f),
+            decode_rv64(half)
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn c_sw() {
+    // c.sw x10, 0(x11) => sw x10, 0(x11)
+    let decoded = RiscVInstruction::decode_compressed(0xc188).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Sw {
+            rs1: Register::X11,
+            rs2: Register::X10,
+            imm: 0
+        }
+    );
+}
+
+#[test]
+fn c_sw_offset() {
+    // c.sw x8, 8(x9) => sw x8, 8(x9)
+    let decoded = RiscVInstruction::decode_compressed(0xc480).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Sw {
+            rs1: Register::X9,
+            rs2: Register::X8,
+            imm: 8
+        }
+    );
+}
+
+#[test]
+fn c_sw_max() {
+    // c.sw x15, 124(x15) => sw x15, 124(x15)
+    let decoded = RiscVInstruction::decode_compressed(0xdffc).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Sw {
+            rs1: Register::X15,
+            rs2: Register::X15,
+            imm: 124
+        }
+    );
+}
+
+#[test]
+fn c_swsp() {
+    // c.swsp x1, 12 => sw x1, 12(x2)
+    let decoded = RiscVInstruction::decode_compressed(0xc606).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Sw {
+            rs1: Register::X2,
+            rs2: Register::X1,
+            imm: 12
+        }
+    );
+}
+
+#[test]
+fn c_swsp_max() {
+    // c.swsp x31, 252 => sw x31, 252(x2)
+    let decoded = RiscVInstruction::decode_compressed(0xdffe).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Sw {
+            rs1: Register::X2,
+            rs2: Register::X31,
+            imm: 252
+        }
+    );
+}
+
+#[test]
+fn c_swsp_zero_register() {
+    // c.swsp x0, 0 => sw x0, 0(x2)
+    let decoded = RiscVInstruction::decode_compressed(0xc002).unwrap();
+
+    assert_eq!(
+        decoded,
+        RiscVInstruction::Sw {
+            rs1: Register::X2,
+            rs2: Register::X0,
+            imm: 0
+        }
+    );
+}
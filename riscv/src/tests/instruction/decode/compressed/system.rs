@@ -0,0 +1,9 @@
+use crate::instruction::RiscVInstruction;
+
+#[test]
+fn c_ebreak() {
+    // c.ebreak => ebreak
+    let decoded = RiscVInstruction::decode_compressed(0x9002).unwrap();
+
+    assert_eq!(decoded, RiscVInstruction::Ebreak);
+}
@@ -0,0 +1,199 @@
+use crate::error::{DecodeError, DecodeErrorKind};
+use crate::extension::Extension;
+use crate::instruction::RiscVInstruction;
+
+#[test]
+fn illegal() {
+    // The all-zero parcel is defined to be illegal
+    let decoded = RiscVInstruction::decode_compressed(0x0000);
+
+    assert_eq!(
+        decoded,
+        Err(DecodeError {
+            word: 0x0000,
+            kind: DecodeErrorKind::ReservedCompressed,
+        })
+    );
+}
+
+#[test]
+fn c_addi4spn_zero_imm() {
+    // c.addi4spn with a zero immediate is reserved
+    let decoded = RiscVInstruction::decode_compressed(0x0000);
+
+    assert_eq!(
+        decoded,
+        Err(DecodeError {
+            word: 0x0000,
+            kind: DecodeErrorKind::ReservedCompressed,
+        })
+    );
+}
+
+#[test]
+fn c_addi16sp_zero_imm() {
+    // c.addi16sp with a zero immediate is reserved
+    let decoded = RiscVInstruction::decode_compressed(0x6101);
+
+    assert_eq!(
+        decoded,
+        Err(DecodeError {
+            word: 0x6101,
+            kind: DecodeErrorKind::ReservedCompressed,
+        })
+    );
+}
+
+#[test]
+fn c_lui_zero_imm() {
+    // c.lui with a zero immediate is reserved
+    let decoded = RiscVInstruction::decode_compressed(0x6501);
+
+    assert_eq!(
+        decoded,
+        Err(DecodeError {
+            word: 0x6501,
+            kind: DecodeErrorKind::ReservedCompressed,
+        })
+    );
+}
+
+#[test]
+fn c_lwsp_zero_rd() {
+    // c.lwsp with rd=x0 is reserved
+    let decoded = RiscVInstruction::decode_compressed(0x4012);
+
+    assert_eq!(
+        decoded,
+        Err(DecodeError {
+            word: 0x4012,
+            kind: DecodeErrorKind::ReservedCompressed,
+        })
+    );
+}
+
+#[test]
+fn c_jr_zero_rs1() {
+    // c.jr with rs1=x0 is reserved
+    let decoded = RiscVInstruction::decode_compressed(0x8002);
+
+    assert_eq!(
+        decoded,
+        Err(DecodeError {
+            word: 0x8002,
+            kind: DecodeErrorKind::ReservedCompressed,
+        })
+    );
+}
+
+#[test]
+fn c_slli_shamt_5() {
+    // shamt[5] must be zero on RV32
+    let decoded = RiscVInstruction::decode_compressed(0x1502);
+
+    assert_eq!(
+        decoded,
+        Err(DecodeError {
+            word: 0x1502,
+            kind: DecodeErrorKind::ReservedCompressed,
+        })
+    );
+}
+
+#[test]
+fn c_srli_shamt_5() {
+    // shamt[5] must be zero on RV32
+    let decoded = RiscVInstruction::decode_compressed(0x9001);
+
+    assert_eq!(
+        decoded,
+        Err(DecodeError {
+            word: 0x9001,
+            kind: DecodeErrorKind::ReservedCompressed,
+        })
+    );
+}
+
+#[test]
+fn c_srai_shamt_5() {
+    // shamt[5] must be zero on RV32
+    let decoded = RiscVInstruction::decode_compressed(0x9401);
+
+    assert_eq!(
+        decoded,
+        Err(DecodeError {
+            word: 0x9401,
+            kind: DecodeErrorKind::ReservedCompressed,
+        })
+    );
+}
+
+#[test]
+fn c_subw() {
+    // c.subw is only defined on RV64
+    let decoded = RiscVInstruction::decode_compressed(0x9c05);
+
+    assert_eq!(
+        decoded,
+        Err(DecodeError {
+            word: 0x9c05,
+            kind: DecodeErrorKind::ReservedCompressed,
+        })
+    );
+}
+
+#[test]
+fn c_fld() {
+    // Compressed floating-point loads need the D extension
+    let decoded = RiscVInstruction::decode_compressed(0x2000);
+
+    assert_eq!(
+        decoded,
+        Err(DecodeError {
+            word: 0x2000,
+            kind: DecodeErrorKind::UnsupportedExtension(Extension::D),
+        })
+    );
+}
+
+#[test]
+fn c_flwsp() {
+    // Compressed floating-point loads need the F extension
+    let decoded = RiscVInstruction::decode_compressed(0x6082);
+
+    assert_eq!(
+        decoded,
+        Err(DecodeError {
+            word: 0x6082,
+            kind: DecodeErrorKind::UnsupportedExtension(Extension::F),
+        })
+    );
+}
+
+#[test]
+fn quadrant_0_reserved() {
+    // funct3=100 in quadrant 0 is reserved
+    let decoded = RiscVInstruction::decode_compressed(0x8004);
+
+    assert_eq!(
+        decoded,
+        Err(DecodeError {
+            word: 0x8004,
+            kind: DecodeErrorKind::ReservedCompressed,
+        })
+    );
+}
+
+#[test]
+fn uncompressed() {
+    // The lowest two bits mark a 32-bit instruction
+    let decoded = RiscVInstruction::decode_compressed(0x0013);
+
+    assert_eq!(
+        decoded,
+        Err(DecodeError {
+            word: 0x0013,
+            kind: DecodeErrorKind::UnknownOpcode,
+        })
+    );
+}
@@ -0,0 +1,138 @@
+use crate::error::{DecodeError, DecodeErrorKind};
+use crate::extension::{Extension, Extensions};
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+const RD: Register = Register::X1;
+const RS1: Register = Register::X2;
+const RS2: Register = Register::X3;
+
+/// Every Zknh instruction with rd = x1, rs1 = x2 and rs2 = x3.
+fn samples() -> Vec<(u32, RiscVInstruction)> {
+    vec![
+        (
+            0x10011093,
+            RiscVInstruction::Sha256sum0 { rd: RD, rs1: RS1 },
+        ),
+        (
+            0x10111093,
+            RiscVInstruction::Sha256sum1 { rd: RD, rs1: RS1 },
+        ),
+        (
+            0x10211093,
+            RiscVInstruction::Sha256sig0 { rd: RD, rs1: RS1 },
+        ),
+        (
+            0x10311093,
+            RiscVInstruction::Sha256sig1 { rd: RD, rs1: RS1 },
+        ),
+        (
+            0x503100b3,
+            RiscVInstruction::Sha512sum0r {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+        ),
+        (
+            0x523100b3,
+            RiscVInstruction::Sha512sum1r {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+        ),
+        (
+            0x543100b3,
+            RiscVInstruction::Sha512sig0l {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+        ),
+        (
+            0x5c3100b3,
+            RiscVInstruction::Sha512sig0h {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+        ),
+        (
+            0x563100b3,
+            RiscVInstruction::Sha512sig1l {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+        ),
+        (
+            0x5e3100b3,
+            RiscVInstruction::Sha512sig1h {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+        ),
+    ]
+}
+
+#[test]
+fn enabled() {
+    for (word, instruction) in samples() {
+        assert_eq!(
+            RiscVInstruction::decode_with(word, Extensions::NONE.with(Extension::Zknh)),
+            Ok(instruction),
+            "0x{:08x}",
+            word
+        );
+        assert_eq!(instruction.extension(), Some(Extension::Zknh));
+    }
+}
+
+#[test]
+fn disabled() {
+    let bitmanip = Extensions::from_slice(&[Extension::Zba, Extension::Zbb, Extension::Zbs]);
+
+    for (word, _) in samples() {
+        for extensions in [Extensions::NONE, bitmanip] {
+            assert_eq!(
+                RiscVInstruction::decode_with(word, extensions),
+                Err(DecodeError {
+                    word,
+                    kind: DecodeErrorKind::UnsupportedExtension(Extension::Zknh),
+                })
+            );
+        }
+    }
+}
+
+#[test]
+fn sha256_with_unknown_rs2() {
+    // The SHA-256 instructions select the operation through rs2; 4 is unassigned.
+    let unassigned = 0x10411093;
+    let decoded = RiscVInstruction::decode_with(unassigned, Extensions::ALL);
+
+    match decoded {
+        Err(DecodeError { word, kind }) => {
+            assert_eq!(word, 0x10411093);
+            assert_eq!(kind, DecodeErrorKind::ReservedFunct7);
+        }
+        _ => panic!("Expected decode error"),
+    }
+}
+
+#[test]
+fn sha512_with_wrong_funct3() {
+    // SHA512SUM0R with funct3 1 instead of 0.
+    let wrong_funct3 = 0x503110b3;
+    let decoded = RiscVInstruction::decode_with(wrong_funct3, Extensions::ALL);
+
+    match decoded {
+        Err(DecodeError { word, kind }) => {
+            assert_eq!(word, 0x503110b3);
+            assert_eq!(kind, DecodeErrorKind::ReservedFunct7);
+        }
+        _ => panic!("Expected decode error"),
+    }
+}
@@ -0,0 +1,151 @@
+use crate::error::{DecodeError, DecodeErrorKind};
+use crate::extension::{Extension, Extensions};
+use crate::instruction::RiscVInstruction;
+
+const ZIFENCEI: Extensions = Extensions::NONE.with(Extension::Zifencei);
+
+#[test]
+fn fence() {
+    let fence_iorw_iorw = 0x0ff0000f;
+    let decoded = RiscVInstruction::decode(fence_iorw_iorw).unwrap();
+
+    match decoded {
+        RiscVInstruction::Fence { pred, succ } => {
+            assert_eq!(pred, 0xf);
+            assert_eq!(succ, 0xf);
+        }
+        _ => panic!("Expected FENCE instruction"),
+    }
+}
+
+#[test]
+fn fence_sets() {
+    let fence_rw_w = 0x0310000f;
+    let decoded = RiscVInstruction::decode(fence_rw_w).unwrap();
+
+    match decoded {
+        RiscVInstruction::Fence { pred, succ } => {
+            assert_eq!(pred, 0x3);
+            assert_eq!(succ, 0x1);
+        }
+        _ => panic!("Expected FENCE instruction"),
+    }
+}
+
+#[test]
+fn fence_tso() {
+    let fence_tso = 0x8330000f;
+    let decoded = RiscVInstruction::decode(fence_tso).unwrap();
+
+    match decoded {
+        RiscVInstruction::FenceTso => {}
+        _ => panic!("Expected FENCE.TSO instruction"),
+    }
+}
+
+#[test]
+fn fence_ignores_registers() {
+    // FENCE rw, w with rd=1 or rs1=1, and FENCE.TSO with rd=1
+    for (word, expected) in [
+        (
+            0x0310008f,
+            RiscVInstruction::Fence {
+                pred: 0x3,
+                succ: 0x1,
+            },
+        ),
+        (
+            0x0310800f,
+            RiscVInstruction::Fence {
+                pred: 0x3,
+                succ: 0x1,
+            },
+        ),
+        (0x8330008f, RiscVInstruction::FenceTso),
+    ] {
+        assert_eq!(RiscVInstruction::decode(word), Ok(expected));
+    }
+}
+
+#[test]
+fn fence_with_reserved_fm_is_a_fence() {
+    // fm=1, and FENCE.TSO's fm with sets other than rw, rw
+    for (word, expected) in [
+        (
+            0x1ff0000f,
+            RiscVInstruction::Fence {
+                pred: 0xf,
+                succ: 0xf,
+            },
+        ),
+        (
+            0x8ff0000f,
+            RiscVInstruction::Fence {
+                pred: 0xf,
+                succ: 0xf,
+            },
+        ),
+        (
+            0x8320000f,
+            RiscVInstruction::Fence {
+                pred: 0x3,
+                succ: 0x2,
+            },
+        ),
+    ] {
+        assert_eq!(RiscVInstruction::decode(word), Ok(expected));
+    }
+}
+
+#[test]
+fn fence_i() {
+    let fence_i = 0x0000100f;
+    let decoded = RiscVInstruction::decode_with(fence_i, ZIFENCEI).unwrap();
+
+    match decoded {
+        RiscVInstruction::FenceI => {}
+        _ => panic!("Expected FENCE.I instruction"),
+    }
+}
+
+#[test]
+fn fence_i_requires_zifencei() {
+    let fence_i = 0x0000100f;
+    let decoded = RiscVInstruction::decode(fence_i);
+
+    match decoded {
+        Err(DecodeError { word, kind }) => {
+            assert_eq!(word, fence_i);
+            assert_eq!(
+                kind,
+                DecodeErrorKind::UnsupportedExtension(Extension::Zifencei)
+            );
+        }
+        _ => panic!("Expected decode error"),
+    }
+}
+
+#[test]
+fn fence_i_ignores_its_fields() {
+    // FENCE.I with imm=1, rs1=1 or rd=1
+    for word in [0x0010100f, 0x0000900f, 0x0000108f] {
+        assert_eq!(
+            RiscVInstruction::decode_with(word, ZIFENCEI),
+            Ok(RiscVInstruction::FenceI)
+        );
+    }
+}
+
+#[test]
+fn misc_mem_reserved_funct3() {
+    let reserved = 0x0000200f;
+    let decoded = RiscVInstruction::decode(reserved);
+
+    match decoded {
+        Err(DecodeError { word, kind }) => {
+            assert_eq!(word, reserved);
+            assert_eq!(kind, DecodeErrorKind::ReservedFunct3);
+        }
+        _ => panic!("Expected decode error"),
+    }
+}
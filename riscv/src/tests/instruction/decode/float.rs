@@ -0,0 +1,556 @@
+use crate::error::{DecodeError, DecodeErrorKind};
+use crate::extension::{Extension, Extensions};
+use crate::float::RoundingMode;
+use crate::instruction::RiscVInstruction;
+use crate::register::{FloatRegister, Register};
+
+const RD: Register = Register::X10;
+const RS1: Register = Register::X11;
+const FD: FloatRegister = FloatRegister::F10;
+const FS1: FloatRegister = FloatRegister::F11;
+const FS2: FloatRegister = FloatRegister::F12;
+const FS3: FloatRegister = FloatRegister::F13;
+const F: Extensions = Extensions::NONE.with(Extension::F);
+const FD_EXTENSIONS: Extensions = F.with(Extension::D);
+
+/// Every F and D instruction with the dynamic rounding mode where it has one, as
+/// encoded by LLVM. LLVM encodes the exact conversions to double with `rne`.
+fn samples() -> Vec<(u32, RiscVInstruction)> {
+    vec![
+        (
+            0x0085a507,
+            RiscVInstruction::Flw {
+                rd: FD,
+                rs1: RS1,
+                imm: 8,
+            },
+        ),
+        (
+            0x00c5a427,
+            RiscVInstruction::Fsw {
+                rs1: RS1,
+                rs2: FS2,
+                imm: 8,
+            },
+        ),
+        (
+            0x0085b507,
+            RiscVInstruction::Fld {
+                rd: FD,
+                rs1: RS1,
+                imm: 8,
+            },
+        ),
+        (
+            0x00c5b427,
+            RiscVInstruction::Fsd {
+                rs1: RS1,
+                rs2: FS2,
+                imm: 8,
+            },
+        ),
+        (
+            0x68c5f543,
+            RiscVInstruction::FmaddS {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+                rs3: FS3,
+                rm: RoundingMode::Dynamic,
+            },
+        ),
+        (
+            0x68c5f547,
+            RiscVInstruction::FmsubS {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+                rs3: FS3,
+                rm: RoundingMode::Dynamic,
+            },
+        ),
+        (
+            0x68c5f54b,
+            RiscVInstruction::FnmsubS {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+                rs3: FS3,
+                rm: RoundingMode::Dynamic,
+            },
+        ),
+        (
+            0x68c5f54f,
+            RiscVInstruction::FnmaddS {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+                rs3: FS3,
+                rm: RoundingMode::Dynamic,
+            },
+        ),
+        (
+            0x00c5f553,
+            RiscVInstruction::FaddS {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+                rm: RoundingMode::Dynamic,
+            },
+        ),
+        (
+            0x08c5f553,
+            RiscVInstruction::FsubS {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+                rm: RoundingMode::Dynamic,
+            },
+        ),
+        (
+            0x10c5f553,
+            RiscVInstruction::FmulS {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+                rm: RoundingMode::Dynamic,
+            },
+        ),
+        (
+            0x18c5f553,
+            RiscVInstruction::FdivS {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+                rm: RoundingMode::Dynamic,
+            },
+        ),
+        (
+            0x5805f553,
+            RiscVInstruction::FsqrtS {
+                rd: FD,
+                rs1: FS1,
+                rm: RoundingMode::Dynamic,
+            },
+        ),
+        (
+            0x20c58553,
+            RiscVInstruction::FsgnjS {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+            },
+        ),
+        (
+            0x20c59553,
+            RiscVInstruction::FsgnjnS {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+            },
+        ),
+        (
+            0x20c5a553,
+            RiscVInstruction::FsgnjxS {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+            },
+        ),
+        (
+            0x28c58553,
+            RiscVInstruction::FminS {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+            },
+        ),
+        (
+            0x28c59553,
+            RiscVInstruction::FmaxS {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+            },
+        ),
+        (
+            0xa0c5a553,
+            RiscVInstruction::FeqS {
+                rd: RD,
+                rs1: FS1,
+                rs2: FS2,
+            },
+        ),
+        (
+            0xa0c59553,
+            RiscVInstruction::FltS {
+                rd: RD,
+                rs1: FS1,
+                rs2: FS2,
+            },
+        ),
+        (
+            0xa0c58553,
+            RiscVInstruction::FleS {
+                rd: RD,
+                rs1: FS1,
+                rs2: FS2,
+            },
+        ),
+        (
+            0xc005f553,
+            RiscVInstruction::FcvtWS {
+                rd: RD,
+                rs1: FS1,
+                rm: RoundingMode::Dynamic,
+            },
+        ),
+        (
+            0xc015f553,
+            RiscVInstruction::FcvtWuS {
+                rd: RD,
+                rs1: FS1,
+                rm: RoundingMode::Dynamic,
+            },
+        ),
+        (0xe0058553, RiscVInstruction::FmvXW { rd: RD, rs1: FS1 }),
+        (0xe0059553, RiscVInstruction::FclassS { rd: RD, rs1: FS1 }),
+        (
+            0xd005f553,
+            RiscVInstruction::FcvtSW {
+                rd: FD,
+                rs1: RS1,
+                rm: RoundingMode::Dynamic,
+            },
+        ),
+        (
+            0xd015f553,
+            RiscVInstruction::FcvtSWu {
+                rd: FD,
+                rs1: RS1,
+                rm: RoundingMode::Dynamic,
+            },
+        ),
+        (0xf0058553, RiscVInstruction::FmvWX { rd: FD, rs1: RS1 }),
+        (
+            0x6ac5f543,
+            RiscVInstruction::FmaddD {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+                rs3: FS3,
+                rm: RoundingMode::Dynamic,
+            },
+        ),
+        (
+            0x6ac5f547,
+            RiscVInstruction::FmsubD {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+                rs3: FS3,
+                rm: RoundingMode::Dynamic,
+            },
+        ),
+        (
+            0x6ac5f54b,
+            RiscVInstruction::FnmsubD {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+                rs3: FS3,
+                rm: RoundingMode::Dynamic,
+            },
+        ),
+        (
+            0x6ac5f54f,
+            RiscVInstruction::FnmaddD {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+                rs3: FS3,
+                rm: RoundingMode::Dynamic,
+            },
+        ),
+        (
+            0x02c5f553,
+            RiscVInstruction::FaddD {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+                rm: RoundingMode::Dynamic,
+            },
+        ),
+        (
+            0x0ac5f553,
+            RiscVInstruction::FsubD {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+                rm: RoundingMode::Dynamic,
+            },
+        ),
+        (
+            0x12c5f553,
+            RiscVInstruction::FmulD {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+                rm: RoundingMode::Dynamic,
+            },
+        ),
+        (
+            0x1ac5f553,
+            RiscVInstruction::FdivD {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+                rm: RoundingMode::Dynamic,
+            },
+        ),
+        (
+            0x5a05f553,
+            RiscVInstruction::FsqrtD {
+                rd: FD,
+                rs1: FS1,
+                rm: RoundingMode::Dynamic,
+            },
+        ),
+        (
+            0x22c58553,
+            RiscVInstruction::FsgnjD {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+            },
+        ),
+        (
+            0x22c59553,
+            RiscVInstruction::FsgnjnD {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+            },
+        ),
+        (
+            0x22c5a553,
+            RiscVInstruction::FsgnjxD {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+            },
+        ),
+        (
+            0x2ac58553,
+            RiscVInstruction::FminD {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+            },
+        ),
+        (
+            0x2ac59553,
+            RiscVInstruction::FmaxD {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+            },
+        ),
+        (
+            0xa2c5a553,
+            RiscVInstruction::FeqD {
+                rd: RD,
+                rs1: FS1,
+                rs2: FS2,
+            },
+        ),
+        (
+            0xa2c59553,
+            RiscVInstruction::FltD {
+                rd: RD,
+                rs1: FS1,
+                rs2: FS2,
+            },
+        ),
+        (
+            0xa2c58553,
+            RiscVInstruction::FleD {
+                rd: RD,
+                rs1: FS1,
+                rs2: FS2,
+            },
+        ),
+        (
+            0xc205f553,
+            RiscVInstruction::FcvtWD {
+                rd: RD,
+                rs1: FS1,
+                rm: RoundingMode::Dynamic,
+            },
+        ),
+        (
+            0xc215f553,
+            RiscVInstruction::FcvtWuD {
+                rd: RD,
+                rs1: FS1,
+                rm: RoundingMode::Dynamic,
+            },
+        ),
+        (0xe2059553, RiscVInstruction::FclassD { rd: RD, rs1: FS1 }),
+        (
+            0xd2058553,
+            RiscVInstruction::FcvtDW {
+                rd: FD,
+                rs1: RS1,
+                rm: RoundingMode::NearestEven,
+            },
+        ),
+        (
+            0xd2158553,
+            RiscVInstruction::FcvtDWu {
+                rd: FD,
+                rs1: RS1,
+                rm: RoundingMode::NearestEven,
+            },
+        ),
+        (
+            0x4015f553,
+            RiscVInstruction::FcvtSD {
+                rd: FD,
+                rs1: FS1,
+                rm: RoundingMode::Dynamic,
+            },
+        ),
+        (
+            0x42058553,
+            RiscVInstruction::FcvtDS {
+                rd: FD,
+                rs1: FS1,
+                rm: RoundingMode::NearestEven,
+            },
+        ),
+    ]
+}
+
+#[test]
+fn enabled() {
+    for (word, instruction) in samples() {
+        assert_eq!(
+            RiscVInstruction::decode_with(word, FD_EXTENSIONS),
+            Ok(instruction),
+            "0x{:08x}",
+            word
+        );
+    }
+}
+
+#[test]
+fn disabled() {
+    for (word, instruction) in samples() {
+        let extension = instruction.extension().unwrap();
+        assert_eq!(
+            RiscVInstruction::decode(word),
+            Err(DecodeError::new(
+                word,
+                DecodeErrorKind::UnsupportedExtension(extension)
+            ))
+        );
+    }
+}
+
+#[test]
+fn double_requires_d() {
+    for (word, instruction) in samples() {
+        let decoded = RiscVInstruction::decode_with(word, F);
+        match instruction.extension() {
+            Some(Extension::F) => assert_eq!(decoded, Ok(instruction)),
+            Some(Extension::D) => assert_eq!(
+                decoded,
+                Err(DecodeError::new(
+                    word,
+                    DecodeErrorKind::UnsupportedExtension(Extension::D)
+                ))
+            ),
+            other => panic!("Unexpected extension {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn rounding_modes() {
+    // fadd.s f10, f11, f12 with each rm field.
+    let fadd_s = 0x00c58553;
+    let modes = [
+        (0, RoundingMode::NearestEven),
+        (1, RoundingMode::TowardZero),
+        (2, RoundingMode::Down),
+        (3, RoundingMode::Up),
+        (4, RoundingMode::NearestMaxMagnitude),
+        (7, RoundingMode::Dynamic),
+    ];
+
+    for (field, rm) in modes {
+        let word = fadd_s | (field << 12);
+        assert_eq!(
+            RiscVInstruction::decode_with(word, F),
+            Ok(RiscVInstruction::FaddS {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+                rm
+            })
+        );
+    }
+}
+
+#[test]
+fn reserved_rounding_modes() {
+    for field in [5, 6] {
+        let word = 0x00c58553 | (field << 12);
+        assert_eq!(
+            RiscVInstruction::decode_with(word, F),
+            Err(DecodeError::new(word, DecodeErrorKind::ReservedFunct3))
+        );
+    }
+}
+
+#[test]
+fn half_and_quad_precision() {
+    // fadd.h and fadd.q f10, f11, f12.
+    for word in [0x04c5f553, 0x06c5f553] {
+        assert_eq!(
+            RiscVInstruction::decode_with(word, Extensions::ALL),
+            Err(DecodeError::new(word, DecodeErrorKind::Unimplemented))
+        );
+    }
+}
+
+#[test]
+fn invalid_funct3() {
+    // fsgnj.s with funct3 = 3.
+    let word = 0x20c5b553;
+    assert_eq!(
+        RiscVInstruction::decode_with(word, F),
+        Err(DecodeError::new(word, DecodeErrorKind::ReservedFunct3))
+    );
+}
+
+#[test]
+fn invalid_rs2() {
+    // fsqrt.s with rs2 = x1.
+    let word = 0x5815f553;
+    assert_eq!(
+        RiscVInstruction::decode_with(word, F),
+        Err(DecodeError::new(word, DecodeErrorKind::InvalidOperands))
+    );
+}
+
+#[test]
+fn unassigned_funct5() {
+    // funct5 0b00110 is not assigned to any OP-FP instruction.
+    let word = 0x30c5f553;
+    assert_eq!(
+        RiscVInstruction::decode_with(word, F),
+        Err(DecodeError::new(word, DecodeErrorKind::ReservedFunct7))
+    );
+}
@@ -1,14 +1,17 @@
+use crate::error::{DecodeError, DecodeErrorKind};
+use crate::extension::Extension;
 use crate::instruction::RiscVInstruction;
+use crate::register::Register;
 
 #[test]
 fn basic() {
     let srai_x1_x2_5 = 0x00515093 | 0x40000000;
-    let decoded = RiscVInstruction::decode(srai_x1_x2_5);
+    let decoded = RiscVInstruction::decode(srai_x1_x2_5).unwrap();
 
     match decoded {
         RiscVInstruction::Srai { rd, rs1, imm } => {
-            assert_eq!(rd, 1);
-            assert_eq!(rs1, 2);
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
             assert_eq!(imm, 5);
         }
         _ => panic!("Expected SRAI instruction"),
@@ -18,12 +21,12 @@ fn basic() {
 #[test]
 fn min_rd() {
     let srai_x0_x1_1 = 0x0010d013 | 0x40000000;
-    let decoded = RiscVInstruction::decode(srai_x0_x1_1);
+    let decoded = RiscVInstruction::decode(srai_x0_x1_1).unwrap();
 
     match decoded {
         RiscVInstruction::Srai { rd, rs1, imm } => {
-            assert_eq!(rd, 0);
-            assert_eq!(rs1, 1);
+            assert_eq!(rd, Register::X0);
+            assert_eq!(rs1, Register::X1);
             assert_eq!(imm, 1);
         }
         _ => panic!("Expected SRAI instruction"),
@@ -33,12 +36,12 @@ fn min_rd() {
 #[test]
 fn max_rd() {
     let srai_x31_x1_1 = (0x0010d013 | 0x40000000) | (31 << 7);
-    let decoded = RiscVInstruction::decode(srai_x31_x1_1);
+    let decoded = RiscVInstruction::decode(srai_x31_x1_1).unwrap();
 
     match decoded {
         RiscVInstruction::Srai { rd, rs1, imm } => {
-            assert_eq!(rd, 31);
-            assert_eq!(rs1, 1);
+            assert_eq!(rd, Register::X31);
+            assert_eq!(rs1, Register::X1);
             assert_eq!(imm, 1);
         }
         _ => panic!("Expected SRAI instruction"),
@@ -48,12 +51,12 @@ fn max_rd() {
 #[test]
 fn min_rs1() {
     let srai_x1_x0_1 = 0x00105093 | 0x40000000;
-    let decoded = RiscVInstruction::decode(srai_x1_x0_1);
+    let decoded = RiscVInstruction::decode(srai_x1_x0_1).unwrap();
 
     match decoded {
         RiscVInstruction::Srai { rd, rs1, imm } => {
-            assert_eq!(rd, 1);
-            assert_eq!(rs1, 0);
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X0);
             assert_eq!(imm, 1);
         }
         _ => panic!("Expected SRAI instruction"),
@@ -63,12 +66,12 @@ fn min_rs1() {
 #[test]
 fn max_rs1() {
     let srai_x1_x31_1 = 0x001fd093 | 0x40000000;
-    let decoded = RiscVInstruction::decode(srai_x1_x31_1);
+    let decoded = RiscVInstruction::decode(srai_x1_x31_1).unwrap();
 
     match decoded {
         RiscVInstruction::Srai { rd, rs1, imm } => {
-            assert_eq!(rd, 1);
-            assert_eq!(rs1, 31);
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X31);
             assert_eq!(imm, 1);
         }
         _ => panic!("Expected SRAI instruction"),
@@ -78,12 +81,12 @@ fn max_rs1() {
 #[test]
 fn zero_imm() {
     let srai_x1_x2_0 = 0x00015093 | 0x40000000;
-    let decoded = RiscVInstruction::decode(srai_x1_x2_0);
+    let decoded = RiscVInstruction::decode(srai_x1_x2_0).unwrap();
 
     match decoded {
         RiscVInstruction::Srai { rd, rs1, imm } => {
-            assert_eq!(rd, 1);
-            assert_eq!(rs1, 2);
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
             assert_eq!(imm, 0);
         }
         _ => panic!("Expected SRAI instruction"),
@@ -93,12 +96,12 @@ fn zero_imm() {
 #[test]
 fn max_shift_amount() {
     let srai_x1_x2_31 = 0x01f15093 | 0x40000000;
-    let decoded = RiscVInstruction::decode(srai_x1_x2_31);
+    let decoded = RiscVInstruction::decode(srai_x1_x2_31).unwrap();
 
     match decoded {
         RiscVInstruction::Srai { rd, rs1, imm } => {
-            assert_eq!(rd, 1);
-            assert_eq!(rs1, 2);
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
             assert_eq!(imm, 31);
         }
         _ => panic!("Expected SRAI instruction"),
@@ -107,13 +110,15 @@ fn max_shift_amount() {
 
 #[test]
 fn invalid_funct7() {
+    // SRAI with funct7 0x30 instead of 0x20 is rori, which needs Zbb
     let srai_with_invalid_funct7 = 0x60105093;
     let decoded = RiscVInstruction::decode(srai_with_invalid_funct7);
 
     match decoded {
-        RiscVInstruction::Unsupported(word) => {
+        Err(DecodeError { word, kind }) => {
             assert_eq!(word, 0x60105093);
+            assert_eq!(kind, DecodeErrorKind::UnsupportedExtension(Extension::Zbb));
         }
-        _ => panic!("Expected unsupported instruction for SRAI with invalid funct7"),
+        _ => panic!("Expected decode error for SRAI with invalid funct7"),
     }
 }
@@ -1,14 +1,16 @@
+use crate::error::{DecodeError, DecodeErrorKind};
 use crate::instruction::RiscVInstruction;
+use crate::register::Register;
 
 #[test]
 fn basic() {
     let srli_x1_x2_5 = 0x00515093;
-    let decoded = RiscVInstruction::decode(srli_x1_x2_5);
+    let decoded = RiscVInstruction::decode(srli_x1_x2_5).unwrap();
 
     match decoded {
         RiscVInstruction::Srli { rd, rs1, imm } => {
-            assert_eq!(rd, 1);
-            assert_eq!(rs1, 2);
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
             assert_eq!(imm, 5);
         }
         _ => panic!("Expected SRLI instruction"),
@@ -18,12 +20,12 @@ fn basic() {
 #[test]
 fn min_rd() {
     let srli_x0_x1_1 = 0x0010d013;
-    let decoded = RiscVInstruction::decode(srli_x0_x1_1);
+    let decoded = RiscVInstruction::decode(srli_x0_x1_1).unwrap();
 
     match decoded {
         RiscVInstruction::Srli { rd, rs1, imm } => {
-            assert_eq!(rd, 0);
-            assert_eq!(rs1, 1);
+            assert_eq!(rd, Register::X0);
+            assert_eq!(rs1, Register::X1);
             assert_eq!(imm, 1);
         }
         _ => panic!("Expected SRLI instruction"),
@@ -33,12 +35,12 @@ fn min_rd() {
 #[test]
 fn max_rd() {
     let srli_x31_x1_1 = 0x0010d013 | (31 << 7);
-    let decoded = RiscVInstruction::decode(srli_x31_x1_1);
+    let decoded = RiscVInstruction::decode(srli_x31_x1_1).unwrap();
 
     match decoded {
         RiscVInstruction::Srli { rd, rs1, imm } => {
-            assert_eq!(rd, 31);
-            assert_eq!(rs1, 1);
+            assert_eq!(rd, Register::X31);
+            assert_eq!(rs1, Register::X1);
             assert_eq!(imm, 1);
         }
         _ => panic!("Expected SRLI instruction"),
@@ -48,12 +50,12 @@ fn max_rd() {
 #[test]
 fn min_rs1() {
     let srli_x1_x0_1 = 0x00105093;
-    let decoded = RiscVInstruction::decode(srli_x1_x0_1);
+    let decoded = RiscVInstruction::decode(srli_x1_x0_1).unwrap();
 
     match decoded {
         RiscVInstruction::Srli { rd, rs1, imm } => {
-            assert_eq!(rd, 1);
-            assert_eq!(rs1, 0);
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X0);
             assert_eq!(imm, 1);
         }
         _ => panic!("Expected SRLI instruction"),
@@ -63,12 +65,12 @@ fn min_rs1() {
 #[test]
 fn max_rs1() {
     let srli_x1_x31_1 = 0x001fd093;
-    let decoded = RiscVInstruction::decode(srli_x1_x31_1);
+    let decoded = RiscVInstruction::decode(srli_x1_x31_1).unwrap();
 
     match decoded {
         RiscVInstruction::Srli { rd, rs1, imm } => {
-            assert_eq!(rd, 1);
-            assert_eq!(rs1, 31);
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X31);
             assert_eq!(imm, 1);
         }
         _ => panic!("Expected SRLI instruction"),
@@ -78,12 +80,12 @@ fn max_rs1() {
 #[test]
 fn zero_imm() {
     let srli_x1_x2_0 = 0x00015093;
-    let decoded = RiscVInstruction::decode(srli_x1_x2_0);
+    let decoded = RiscVInstruction::decode(srli_x1_x2_0).unwrap();
 
     match decoded {
         RiscVInstruction::Srli { rd, rs1, imm } => {
-            assert_eq!(rd, 1);
-            assert_eq!(rs1, 2);
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
             assert_eq!(imm, 0);
         }
         _ => panic!("Expected SRLI instruction"),
@@ -93,12 +95,12 @@ fn zero_imm() {
 #[test]
 fn max_shift_amount() {
     let srli_x1_x2_31 = 0x01f15093;
-    let decoded = RiscVInstruction::decode(srli_x1_x2_31);
+    let decoded = RiscVInstruction::decode(srli_x1_x2_31).unwrap();
 
     match decoded {
         RiscVInstruction::Srli { rd, rs1, imm } => {
-            assert_eq!(rd, 1);
-            assert_eq!(rs1, 2);
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
             assert_eq!(imm, 31);
         }
         _ => panic!("Expected SRLI instruction"),
@@ -111,9 +113,9 @@ fn invalid_funct7() {
     let decoded = RiscVInstruction::decode(srli_with_invalid_funct7);
 
     match decoded {
-        RiscVInstruction::Unsupported(_) => {
-            // This is expected
+        Err(DecodeError { kind, .. }) => {
+            assert_eq!(kind, DecodeErrorKind::ReservedFunct7);
         }
-        _ => panic!("Expected unsupported instruction for SRLI with invalid funct7"),
+        _ => panic!("Expected decode error for SRLI with invalid funct7"),
     }
 }
@@ -0,0 +1,159 @@
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn basic() {
+    let jal_x1_2048 = 0x001000ef;
+    let decoded = RiscVInstruction::decode(jal_x1_2048).unwrap();
+
+    match decoded {
+        RiscVInstruction::Jal { rd, imm } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(imm, 2048);
+        }
+        _ => panic!("Expected JAL instruction"),
+    }
+}
+
+#[test]
+fn min_rd() {
+    let jal_x0_8 = 0x0080006f;
+    let decoded = RiscVInstruction::decode(jal_x0_8).unwrap();
+
+    match decoded {
+        RiscVInstruction::Jal { rd, imm } => {
+            assert_eq!(rd, Register::X0);
+            assert_eq!(imm, 8);
+        }
+        _ => panic!("Expected JAL instruction"),
+    }
+}
+
+#[test]
+fn max_rd() {
+    let jal_x31_8 = 0x00800fef;
+    let decoded = RiscVInstruction::decode(jal_x31_8).unwrap();
+
+    match decoded {
+        RiscVInstruction::Jal { rd, imm } => {
+            assert_eq!(rd, Register::X31);
+            assert_eq!(imm, 8);
+        }
+        _ => panic!("Expected JAL instruction"),
+    }
+}
+
+#[test]
+fn negative_imm() {
+    let jal_x1_neg8 = 0xff9ff0ef;
+    let decoded = RiscVInstruction::decode(jal_x1_neg8).unwrap();
+
+    match decoded {
+        RiscVInstruction::Jal { rd, imm } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(imm, -8);
+        }
+        _ => panic!("Expected JAL instruction"),
+    }
+}
+
+#[test]
+fn zero_imm() {
+    let jal_x0_0 = 0x0000006f;
+    let decoded = RiscVInstruction::decode(jal_x0_0).unwrap();
+
+    match decoded {
+        RiscVInstruction::Jal { rd, imm } => {
+            assert_eq!(rd, Register::X0);
+            assert_eq!(imm, 0);
+        }
+        _ => panic!("Expected JAL instruction"),
+    }
+}
+
+#[test]
+fn max_positive_imm() {
+    let jal_x1_1048574 = 0x7ffff0ef;
+    let decoded = RiscVInstruction::decode(jal_x1_1048574).unwrap();
+
+    match decoded {
+        RiscVInstruction::Jal { rd, imm } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(imm, 1048574);
+        }
+        _ => panic!("Expected JAL instruction"),
+    }
+}
+
+#[test]
+fn min_negative_imm() {
+    let jal_x1_neg1048576 = 0x800000ef;
+    let decoded = RiscVInstruction::decode(jal_x1_neg1048576).unwrap();
+
+    match decoded {
+        RiscVInstruction::Jal { rd, imm } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(imm, -1048576);
+        }
+        _ => panic!("Expected JAL instruction"),
+    }
+}
+
+#[test]
+fn neg_two_imm() {
+    let jal_x0_neg2 = 0xfffff06f;
+    let decoded = RiscVInstruction::decode(jal_x0_neg2).unwrap();
+
+    match decoded {
+        RiscVInstruction::Jal { rd, imm } => {
+            assert_eq!(rd, Register::X0);
+            assert_eq!(imm, -2);
+        }
+        _ => panic!("Expected JAL instruction"),
+    }
+}
+
+#[test]
+fn imm_bits_10_1() {
+    // imm[10:1] is encoded in bits 30:21
+    let jal_x1_2046 = 0x7fe000ef;
+    let decoded = RiscVInstruction::decode(jal_x1_2046).unwrap();
+
+    match decoded {
+        RiscVInstruction::Jal { rd, imm } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(imm, 2046);
+        }
+        _ => panic!("Expected JAL instruction"),
+    }
+}
+
+#[test]
+fn imm_bit_11() {
+    // imm[11] is encoded in bit 20
+    let jal_x1_2048 = 0x001000ef;
+    let decoded = RiscVInstruction::decode(jal_x1_2048).unwrap();
+
+    match decoded {
+        RiscVInstruction::Jal { rd, imm } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(imm, 2048);
+        }
+        _ => panic!("Expected JAL instruction"),
+    }
+}
+
+#[test]
+fn imm_bits_19_12() {
+    // imm[19:12] is encoded in bits 19:12
+    let jal_x1_1044480 = 0x000ff0ef;
+    let decoded = RiscVInstruction::decode(jal_x1_1044480).unwrap();
+
+    match decoded {
+        RiscVInstruction::Jal { rd, imm } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(imm, 1044480);
+        }
+        _ => panic!("Expected JAL instruction"),
+    }
+}
@@ -1,14 +1,15 @@
 use crate::instruction::RiscVInstruction;
+use crate::register::Register;
 
 #[test]
 fn basic() {
     let jalr_x1_x2_4 = 0x004100e7;
-    let decoded = RiscVInstruction::decode(jalr_x1_x2_4);
+    let decoded = RiscVInstruction::decode(jalr_x1_x2_4).unwrap();
 
     match decoded {
         RiscVInstruction::Jalr { rd, rs1, imm } => {
-            assert_eq!(rd, 1);
-            assert_eq!(rs1, 2);
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
             assert_eq!(imm, 4);
         }
         _ => panic!("Expected JALR instruction"),
@@ -18,12 +19,12 @@ fn basic() {
 #[test]
 fn negative_imm() {
     let jalr_x0_x1_neg4 = 0xffc08067;
-    let decoded = RiscVInstruction::decode(jalr_x0_x1_neg4);
+    let decoded = RiscVInstruction::decode(jalr_x0_x1_neg4).unwrap();
 
     match decoded {
         RiscVInstruction::Jalr { rd, rs1, imm } => {
-            assert_eq!(rd, 0);
-            assert_eq!(rs1, 1);
+            assert_eq!(rd, Register::X0);
+            assert_eq!(rs1, Register::X1);
             assert_eq!(imm, -4);
         }
         _ => panic!("Expected JALR instruction"),
@@ -33,11 +34,11 @@ fn negative_imm() {
 #[test]
 fn min_rd() {
     let jalr_x0 = 0x00008067;
-    let decoded = RiscVInstruction::decode(jalr_x0);
+    let decoded = RiscVInstruction::decode(jalr_x0).unwrap();
     match decoded {
         RiscVInstruction::Jalr { rd, rs1, imm } => {
-            assert_eq!(rd, 0);
-            assert_eq!(rs1, 1);
+            assert_eq!(rd, Register::X0);
+            assert_eq!(rs1, Register::X1);
             assert_eq!(imm, 0);
         }
         _ => panic!("Expected JALR instruction"),
@@ -47,11 +48,11 @@ fn min_rd() {
 #[test]
 fn max_rd() {
     let jalr_x31 = 0x000080e7 | (31 << 7);
-    let decoded = RiscVInstruction::decode(jalr_x31);
+    let decoded = RiscVInstruction::decode(jalr_x31).unwrap();
     match decoded {
         RiscVInstruction::Jalr { rd, rs1, imm } => {
-            assert_eq!(rd, 31);
-            assert_eq!(rs1, 1);
+            assert_eq!(rd, Register::X31);
+            assert_eq!(rs1, Register::X1);
             assert_eq!(imm, 0);
         }
         _ => panic!("Expected JALR instruction"),
@@ -61,11 +62,11 @@ fn max_rd() {
 #[test]
 fn min_rs1() {
     let jalr_rs1_0 = 0x00000067;
-    let decoded = RiscVInstruction::decode(jalr_rs1_0);
+    let decoded = RiscVInstruction::decode(jalr_rs1_0).unwrap();
     match decoded {
         RiscVInstruction::Jalr { rd, rs1, imm } => {
-            assert_eq!(rd, 0);
-            assert_eq!(rs1, 0);
+            assert_eq!(rd, Register::X0);
+            assert_eq!(rs1, Register::X0);
             assert_eq!(imm, 0);
         }
         _ => panic!("Expected JALR instruction"),
@@ -75,11 +76,11 @@ fn min_rs1() {
 #[test]
 fn max_rs1() {
     let jalr_rs1_31 = 0x000f8067;
-    let decoded = RiscVInstruction::decode(jalr_rs1_31);
+    let decoded = RiscVInstruction::decode(jalr_rs1_31).unwrap();
     match decoded {
         RiscVInstruction::Jalr { rd, rs1, imm } => {
-            assert_eq!(rd, 0);
-            assert_eq!(rs1, 31);
+            assert_eq!(rd, Register::X0);
+            assert_eq!(rs1, Register::X31);
             assert_eq!(imm, 0);
         }
         _ => panic!("Expected JALR instruction"),
@@ -89,11 +90,11 @@ fn max_rs1() {
 #[test]
 fn zero_imm() {
     let jalr_imm_0 = 0x00008067;
-    let decoded = RiscVInstruction::decode(jalr_imm_0);
+    let decoded = RiscVInstruction::decode(jalr_imm_0).unwrap();
     match decoded {
         RiscVInstruction::Jalr { rd, rs1, imm } => {
-            assert_eq!(rd, 0);
-            assert_eq!(rs1, 1);
+            assert_eq!(rd, Register::X0);
+            assert_eq!(rs1, Register::X1);
             assert_eq!(imm, 0);
         }
         _ => panic!("Expected JALR instruction"),
@@ -103,11 +104,11 @@ fn zero_imm() {
 #[test]
 fn max_positive_imm() {
     let jalr_imm_2047 = 0x7ff08067;
-    let decoded = RiscVInstruction::decode(jalr_imm_2047);
+    let decoded = RiscVInstruction::decode(jalr_imm_2047).unwrap();
     match decoded {
         RiscVInstruction::Jalr { rd, rs1, imm } => {
-            assert_eq!(rd, 0);
-            assert_eq!(rs1, 1);
+            assert_eq!(rd, Register::X0);
+            assert_eq!(rs1, Register::X1);
             assert_eq!(imm, 2047);
         }
         _ => panic!("Expected JALR instruction"),
@@ -117,11 +118,11 @@ fn max_positive_imm() {
 #[test]
 fn min_negative_imm() {
     let jalr_imm_neg2048 = 0x80008067;
-    let decoded = RiscVInstruction::decode(jalr_imm_neg2048);
+    let decoded = RiscVInstruction::decode(jalr_imm_neg2048).unwrap();
     match decoded {
         RiscVInstruction::Jalr { rd, rs1, imm } => {
-            assert_eq!(rd, 0);
-            assert_eq!(rs1, 1);
+            assert_eq!(rd, Register::X0);
+            assert_eq!(rs1, Register::X1);
             assert_eq!(imm, -2048);
         }
         _ => panic!("Expected JALR instruction"),
@@ -131,11 +132,11 @@ fn min_negative_imm() {
 #[test]
 fn neg_one_imm() {
     let jalr_imm_neg1 = 0xfff08067;
-    let decoded = RiscVInstruction::decode(jalr_imm_neg1);
+    let decoded = RiscVInstruction::decode(jalr_imm_neg1).unwrap();
     match decoded {
         RiscVInstruction::Jalr { rd, rs1, imm } => {
-            assert_eq!(rd, 0);
-            assert_eq!(rs1, 1);
+            assert_eq!(rd, Register::X0);
+            assert_eq!(rs1, Register::X1);
             assert_eq!(imm, -1);
         }
         _ => panic!("Expected JALR instruction"),
@@ -1 +1,2 @@
+mod jal;
 mod jalr;
@@ -1,14 +1,15 @@
 use crate::instruction::RiscVInstruction;
+use crate::register::Register;
 
 #[test]
 fn basic() {
     let lhu_x1_x2_100 = 0x06415083;
-    let decoded = RiscVInstruction::decode(lhu_x1_x2_100);
+    let decoded = RiscVInstruction::decode(lhu_x1_x2_100).unwrap();
 
     match decoded {
         RiscVInstruction::Lhu { rd, rs1, imm } => {
-            assert_eq!(rd, 1);
-            assert_eq!(rs1, 2);
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
             assert_eq!(imm, 100);
         }
         _ => panic!("Expected LHU instruction"),
@@ -18,12 +19,12 @@ fn basic() {
 #[test]
 fn min_rd() {
     let lhu_x0_x1_0 = 0x0000d003;
-    let decoded = RiscVInstruction::decode(lhu_x0_x1_0);
+    let decoded = RiscVInstruction::decode(lhu_x0_x1_0).unwrap();
 
     match decoded {
         RiscVInstruction::Lhu { rd, rs1, imm } => {
-            assert_eq!(rd, 0);
-            assert_eq!(rs1, 1);
+            assert_eq!(rd, Register::X0);
+            assert_eq!(rs1, Register::X1);
             assert_eq!(imm, 0);
         }
         _ => panic!("Expected LHU instruction"),
@@ -33,12 +34,12 @@ fn min_rd() {
 #[test]
 fn max_rd() {
     let lhu_x31_x1_0 = 0x0000d003 | (31 << 7);
-    let decoded = RiscVInstruction::decode(lhu_x31_x1_0);
+    let decoded = RiscVInstruction::decode(lhu_x31_x1_0).unwrap();
 
     match decoded {
         RiscVInstruction::Lhu { rd, rs1, imm } => {
-            assert_eq!(rd, 31);
-            assert_eq!(rs1, 1);
+            assert_eq!(rd, Register::X31);
+            assert_eq!(rs1, Register::X1);
             assert_eq!(imm, 0);
         }
         _ => panic!("Expected LHU instruction"),
@@ -48,12 +49,12 @@ fn max_rd() {
 #[test]
 fn min_rs1() {
     let lhu_x1_x0_0 = 0x00005083;
-    let decoded = RiscVInstruction::decode(lhu_x1_x0_0);
+    let decoded = RiscVInstruction::decode(lhu_x1_x0_0).unwrap();
 
     match decoded {
         RiscVInstruction::Lhu { rd, rs1, imm } => {
-            assert_eq!(rd, 1);
-            assert_eq!(rs1, 0);
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X0);
             assert_eq!(imm, 0);
         }
         _ => panic!("Expected LHU instruction"),
@@ -63,12 +64,12 @@ fn min_rs1() {
 #[test]
 fn max_rs1() {
     let lhu_x1_x31_0 = 0x00005083 | (31 << 15);
-    let decoded = RiscVInstruction::decode(lhu_x1_x31_0);
+    let decoded = RiscVInstruction::decode(lhu_x1_x31_0).unwrap();
 
     match decoded {
         RiscVInstruction::Lhu { rd, rs1, imm } => {
-            assert_eq!(rd, 1);
-            assert_eq!(rs1, 31);
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X31);
             assert_eq!(imm, 0);
         }
         _ => panic!("Expected LHU instruction"),
@@ -78,12 +79,12 @@ fn max_rs1() {
 #[test]
 fn negative_imm() {
     let lhu_x1_x2_neg4 = 0xffc15083;
-    let decoded = RiscVInstruction::decode(lhu_x1_x2_neg4);
+    let decoded = RiscVInstruction::decode(lhu_x1_x2_neg4).unwrap();
 
     match decoded {
         RiscVInstruction::Lhu { rd, rs1, imm } => {
-            assert_eq!(rd, 1);
-            assert_eq!(rs1, 2);
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
             assert_eq!(imm, -4);
         }
         _ => panic!("Expected LHU instruction"),
@@ -93,12 +94,12 @@ fn negative_imm() {
 #[test]
 fn zero_imm() {
     let lhu_x1_x2_0 = 0x00015083;
-    let decoded = RiscVInstruction::decode(lhu_x1_x2_0);
+    let decoded = RiscVInstruction::decode(lhu_x1_x2_0).unwrap();
 
     match decoded {
         RiscVInstruction::Lhu { rd, rs1, imm } => {
-            assert_eq!(rd, 1);
-            assert_eq!(rs1, 2);
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
             assert_eq!(imm, 0);
         }
         _ => panic!("Expected LHU instruction"),
@@ -108,12 +109,12 @@ fn zero_imm() {
 #[test]
 fn max_positive_imm() {
     let lhu_x1_x0_2047 = 0x7ff05083;
-    let decoded = RiscVInstruction::decode(lhu_x1_x0_2047);
+    let decoded = RiscVInstruction::decode(lhu_x1_x0_2047).unwrap();
 
     match decoded {
         RiscVInstruction::Lhu { rd, rs1, imm } => {
-            assert_eq!(rd, 1);
-            assert_eq!(rs1, 0);
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X0);
             assert_eq!(imm, 2047);
         }
         _ => panic!("Expected LHU instruction"),
@@ -123,12 +124,12 @@ fn max_positive_imm() {
 #[test]
 fn min_negative_imm() {
     let lhu_x1_x0_neg2048 = 0x80005083;
-    let decoded = RiscVInstruction::decode(lhu_x1_x0_neg2048);
+    let decoded = RiscVInstruction::decode(lhu_x1_x0_neg2048).unwrap();
 
     match decoded {
         RiscVInstruction::Lhu { rd, rs1, imm } => {
-            assert_eq!(rd, 1);
-            assert_eq!(rs1, 0);
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X0);
             assert_eq!(imm, -2048);
         }
         _ => panic!("Expected LHU instruction"),
@@ -1,6 +1,18 @@
 mod arithmetic;
+mod atomic;
+mod bitmanip;
+mod branch;
+mod compressed;
+mod crypto;
+mod fence;
+mod float;
 mod immediate;
 mod jump;
 mod load;
+mod multiply;
+mod profile;
+mod rv64;
+mod store;
 mod system;
 mod unsupported;
+mod upper;
@@ -0,0 +1,8 @@
+mod div;
+mod divu;
+mod mul;
+mod mulh;
+mod mulhsu;
+mod mulhu;
+mod rem;
+mod remu;
@@ -0,0 +1,138 @@
+use crate::error::{DecodeError, DecodeErrorKind};
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn basic() {
+    let mul_x1_x2_x3 = 0x023100b3;
+    let decoded = RiscVInstruction::decode(mul_x1_x2_x3).unwrap();
+
+    match decoded {
+        RiscVInstruction::Mul { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X3);
+        }
+        _ => panic!("Expected MUL instruction"),
+    }
+}
+
+#[test]
+fn min_rd() {
+    let mul_x0_x1_x2 = 0x02208033;
+    let decoded = RiscVInstruction::decode(mul_x0_x1_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::Mul { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X0);
+            assert_eq!(rs1, Register::X1);
+            assert_eq!(rs2, Register::X2);
+        }
+        _ => panic!("Expected MUL instruction"),
+    }
+}
+
+#[test]
+fn max_rd() {
+    let mul_x31_x1_x2 = 0x02208033 | (31 << 7);
+    let decoded = RiscVInstruction::decode(mul_x31_x1_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::Mul { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X31);
+            assert_eq!(rs1, Register::X1);
+            assert_eq!(rs2, Register::X2);
+        }
+        _ => panic!("Expected MUL instruction"),
+    }
+}
+
+#[test]
+fn min_rs1() {
+    let mul_x1_x0_x2 = 0x022000b3;
+    let decoded = RiscVInstruction::decode(mul_x1_x0_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::Mul { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X0);
+            assert_eq!(rs2, Register::X2);
+        }
+        _ => panic!("Expected MUL instruction"),
+    }
+}
+
+#[test]
+fn max_rs1() {
+    let mul_x1_x31_x2 = 0x022f80b3;
+    let decoded = RiscVInstruction::decode(mul_x1_x31_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::Mul { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X31);
+            assert_eq!(rs2, Register::X2);
+        }
+        _ => panic!("Expected MUL instruction"),
+    }
+}
+
+#[test]
+fn min_rs2() {
+    let mul_x1_x2_x0 = 0x020100b3;
+    let decoded = RiscVInstruction::decode(mul_x1_x2_x0).unwrap();
+
+    match decoded {
+        RiscVInstruction::Mul { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X0);
+        }
+        _ => panic!("Expected MUL instruction"),
+    }
+}
+
+#[test]
+fn max_rs2() {
+    let mul_x1_x2_x31 = 0x03f100b3;
+    let decoded = RiscVInstruction::decode(mul_x1_x2_x31).unwrap();
+
+    match decoded {
+        RiscVInstruction::Mul { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X31);
+        }
+        _ => panic!("Expected MUL instruction"),
+    }
+}
+
+#[test]
+fn all_max_values() {
+    let mul_x31_x31_x31 = 0x03ff8fb3;
+    let decoded = RiscVInstruction::decode(mul_x31_x31_x31).unwrap();
+
+    match decoded {
+        RiscVInstruction::Mul { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X31);
+            assert_eq!(rs1, Register::X31);
+            assert_eq!(rs2, Register::X31);
+        }
+        _ => panic!("Expected MUL instruction"),
+    }
+}
+
+#[test]
+fn invalid_funct7_should_be_unsupported() {
+    // MUL with invalid funct7 (0x03 instead of 0x01)
+    let invalid_mul = 0x063100b3;
+    let decoded = RiscVInstruction::decode(invalid_mul);
+
+    match decoded {
+        Err(DecodeError { word, kind }) => {
+            assert_eq!(word, 0x063100b3);
+            assert_eq!(kind, DecodeErrorKind::ReservedFunct7);
+        }
+        _ => panic!("Expected decode error"),
+    }
+}
@@ -0,0 +1,138 @@
+use crate::error::{DecodeError, DecodeErrorKind};
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn basic() {
+    let mulh_x1_x2_x3 = 0x023110b3;
+    let decoded = RiscVInstruction::decode(mulh_x1_x2_x3).unwrap();
+
+    match decoded {
+        RiscVInstruction::Mulh { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X3);
+        }
+        _ => panic!("Expected MULH instruction"),
+    }
+}
+
+#[test]
+fn min_rd() {
+    let mulh_x0_x1_x2 = 0x02209033;
+    let decoded = RiscVInstruction::decode(mulh_x0_x1_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::Mulh { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X0);
+            assert_eq!(rs1, Register::X1);
+            assert_eq!(rs2, Register::X2);
+        }
+        _ => panic!("Expected MULH instruction"),
+    }
+}
+
+#[test]
+fn max_rd() {
+    let mulh_x31_x1_x2 = 0x02209033 | (31 << 7);
+    let decoded = RiscVInstruction::decode(mulh_x31_x1_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::Mulh { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X31);
+            assert_eq!(rs1, Register::X1);
+            assert_eq!(rs2, Register::X2);
+        }
+        _ => panic!("Expected MULH instruction"),
+    }
+}
+
+#[test]
+fn min_rs1() {
+    let mulh_x1_x0_x2 = 0x022010b3;
+    let decoded = RiscVInstruction::decode(mulh_x1_x0_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::Mulh { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X0);
+            assert_eq!(rs2, Register::X2);
+        }
+        _ => panic!("Expected MULH instruction"),
+    }
+}
+
+#[test]
+fn max_rs1() {
+    let mulh_x1_x31_x2 = 0x022f90b3;
+    let decoded = RiscVInstruction::decode(mulh_x1_x31_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::Mulh { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X31);
+            assert_eq!(rs2, Register::X2);
+        }
+        _ => panic!("Expected MULH instruction"),
+    }
+}
+
+#[test]
+fn min_rs2() {
+    let mulh_x1_x2_x0 = 0x020110b3;
+    let decoded = RiscVInstruction::decode(mulh_x1_x2_x0).unwrap();
+
+    match decoded {
+        RiscVInstruction::Mulh { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X0);
+        }
+        _ => panic!("Expected MULH instruction"),
+    }
+}
+
+#[test]
+fn max_rs2() {
+    let mulh_x1_x2_x31 = 0x03f110b3;
+    let decoded = RiscVInstruction::decode(mulh_x1_x2_x31).unwrap();
+
+    match decoded {
+        RiscVInstruction::Mulh { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X31);
+        }
+        _ => panic!("Expected MULH instruction"),
+    }
+}
+
+#[test]
+fn all_max_values() {
+    let mulh_x31_x31_x31 = 0x03ff9fb3;
+    let decoded = RiscVInstruction::decode(mulh_x31_x31_x31).unwrap();
+
+    match decoded {
+        RiscVInstruction::Mulh { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X31);
+            assert_eq!(rs1, Register::X31);
+            assert_eq!(rs2, Register::X31);
+        }
+        _ => panic!("Expected MULH instruction"),
+    }
+}
+
+#[test]
+fn invalid_funct7_should_be_unsupported() {
+    // MULH with invalid funct7 (0x03 instead of 0x01)
+    let invalid_mulh = 0x063110b3;
+    let decoded = RiscVInstruction::decode(invalid_mulh);
+
+    match decoded {
+        Err(DecodeError { word, kind }) => {
+            assert_eq!(word, 0x063110b3);
+            assert_eq!(kind, DecodeErrorKind::ReservedFunct7);
+        }
+        _ => panic!("Expected decode error"),
+    }
+}
@@ -0,0 +1,138 @@
+use crate::error::{DecodeError, DecodeErrorKind};
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn basic() {
+    let mulhsu_x1_x2_x3 = 0x023120b3;
+    let decoded = RiscVInstruction::decode(mulhsu_x1_x2_x3).unwrap();
+
+    match decoded {
+        RiscVInstruction::Mulhsu { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X3);
+        }
+        _ => panic!("Expected MULHSU instruction"),
+    }
+}
+
+#[test]
+fn min_rd() {
+    let mulhsu_x0_x1_x2 = 0x0220a033;
+    let decoded = RiscVInstruction::decode(mulhsu_x0_x1_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::Mulhsu { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X0);
+            assert_eq!(rs1, Register::X1);
+            assert_eq!(rs2, Register::X2);
+        }
+        _ => panic!("Expected MULHSU instruction"),
+    }
+}
+
+#[test]
+fn max_rd() {
+    let mulhsu_x31_x1_x2 = 0x0220a033 | (31 << 7);
+    let decoded = RiscVInstruction::decode(mulhsu_x31_x1_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::Mulhsu { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X31);
+            assert_eq!(rs1, Register::X1);
+            assert_eq!(rs2, Register::X2);
+        }
+        _ => panic!("Expected MULHSU instruction"),
+    }
+}
+
+#[test]
+fn min_rs1() {
+    let mulhsu_x1_x0_x2 = 0x022020b3;
+    let decoded = RiscVInstruction::decode(mulhsu_x1_x0_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::Mulhsu { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X0);
+            assert_eq!(rs2, Register::X2);
+        }
+        _ => panic!("Expected MULHSU instruction"),
+    }
+}
+
+#[test]
+fn max_rs1() {
+    let mulhsu_x1_x31_x2 = 0x022fa0b3;
+    let decoded = RiscVInstruction::decode(mulhsu_x1_x31_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::Mulhsu { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X31);
+            assert_eq!(rs2, Register::X2);
+        }
+        _ => panic!("Expected MULHSU instruction"),
+    }
+}
+
+#[test]
+fn min_rs2() {
+    let mulhsu_x1_x2_x0 = 0x020120b3;
+    let decoded = RiscVInstruction::decode(mulhsu_x1_x2_x0).unwrap();
+
+    match decoded {
+        RiscVInstruction::Mulhsu { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X0);
+        }
+        _ => panic!("Expected MULHSU instruction"),
+    }
+}
+
+#[test]
+fn max_rs2() {
+    let mulhsu_x1_x2_x31 = 0x03f120b3;
+    let decoded = RiscVInstruction::decode(mulhsu_x1_x2_x31).unwrap();
+
+    match decoded {
+        RiscVInstruction::Mulhsu { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X31);
+        }
+        _ => panic!("Expected MULHSU instruction"),
+    }
+}
+
+#[test]
+fn all_max_values() {
+    let mulhsu_x31_x31_x31 = 0x03ffafb3;
+    let decoded = RiscVInstruction::decode(mulhsu_x31_x31_x31).unwrap();
+
+    match decoded {
+        RiscVInstruction::Mulhsu { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X31);
+            assert_eq!(rs1, Register::X31);
+            assert_eq!(rs2, Register::X31);
+        }
+        _ => panic!("Expected MULHSU instruction"),
+    }
+}
+
+#[test]
+fn invalid_funct7_should_be_unsupported() {
+    // MULHSU with invalid funct7 (0x03 instead of 0x01)
+    let invalid_mulhsu = 0x063120b3;
+    let decoded = RiscVInstruction::decode(invalid_mulhsu);
+
+    match decoded {
+        Err(DecodeError { word, kind }) => {
+            assert_eq!(word, 0x063120b3);
+            assert_eq!(kind, DecodeErrorKind::ReservedFunct7);
+        }
+        _ => panic!("Expected decode error"),
+    }
+}
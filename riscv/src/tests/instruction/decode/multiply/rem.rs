@@ -0,0 +1,138 @@
+use crate::error::{DecodeError, DecodeErrorKind};
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn basic() {
+    let rem_x1_x2_x3 = 0x023160b3;
+    let decoded = RiscVInstruction::decode(rem_x1_x2_x3).unwrap();
+
+    match decoded {
+        RiscVInstruction::Rem { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X3);
+        }
+        _ => panic!("Expected REM instruction"),
+    }
+}
+
+#[test]
+fn min_rd() {
+    let rem_x0_x1_x2 = 0x0220e033;
+    let decoded = RiscVInstruction::decode(rem_x0_x1_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::Rem { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X0);
+            assert_eq!(rs1, Register::X1);
+            assert_eq!(rs2, Register::X2);
+        }
+        _ => panic!("Expected REM instruction"),
+    }
+}
+
+#[test]
+fn max_rd() {
+    let rem_x31_x1_x2 = 0x0220e033 | (31 << 7);
+    let decoded = RiscVInstruction::decode(rem_x31_x1_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::Rem { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X31);
+            assert_eq!(rs1, Register::X1);
+            assert_eq!(rs2, Register::X2);
+        }
+        _ => panic!("Expected REM instruction"),
+    }
+}
+
+#[test]
+fn min_rs1() {
+    let rem_x1_x0_x2 = 0x022060b3;
+    let decoded = RiscVInstruction::decode(rem_x1_x0_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::Rem { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X0);
+            assert_eq!(rs2, Register::X2);
+        }
+        _ => panic!("Expected REM instruction"),
+    }
+}
+
+#[test]
+fn max_rs1() {
+    let rem_x1_x31_x2 = 0x022fe0b3;
+    let decoded = RiscVInstruction::decode(rem_x1_x31_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::Rem { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X31);
+            assert_eq!(rs2, Register::X2);
+        }
+        _ => panic!("Expected REM instruction"),
+    }
+}
+
+#[test]
+fn min_rs2() {
+    let rem_x1_x2_x0 = 0x020160b3;
+    let decoded = RiscVInstruction::decode(rem_x1_x2_x0).unwrap();
+
+    match decoded {
+        RiscVInstruction::Rem { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X0);
+        }
+        _ => panic!("Expected REM instruction"),
+    }
+}
+
+#[test]
+fn max_rs2() {
+    let rem_x1_x2_x31 = 0x03f160b3;
+    let decoded = RiscVInstruction::decode(rem_x1_x2_x31).unwrap();
+
+    match decoded {
+        RiscVInstruction::Rem { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X31);
+        }
+        _ => panic!("Expected REM instruction"),
+    }
+}
+
+#[test]
+fn all_max_values() {
+    let rem_x31_x31_x31 = 0x03ffefb3;
+    let decoded = RiscVInstruction::decode(rem_x31_x31_x31).unwrap();
+
+    match decoded {
+        RiscVInstruction::Rem { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X31);
+            assert_eq!(rs1, Register::X31);
+            assert_eq!(rs2, Register::X31);
+        }
+        _ => panic!("Expected REM instruction"),
+    }
+}
+
+#[test]
+fn invalid_funct7_should_be_unsupported() {
+    // REM with invalid funct7 (0x03 instead of 0x01)
+    let invalid_rem = 0x063160b3;
+    let decoded = RiscVInstruction::decode(invalid_rem);
+
+    match decoded {
+        Err(DecodeError { word, kind }) => {
+            assert_eq!(word, 0x063160b3);
+            assert_eq!(kind, DecodeErrorKind::ReservedFunct7);
+        }
+        _ => panic!("Expected decode error"),
+    }
+}
@@ -0,0 +1,138 @@
+use crate::error::{DecodeError, DecodeErrorKind};
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn basic() {
+    let remu_x1_x2_x3 = 0x023170b3;
+    let decoded = RiscVInstruction::decode(remu_x1_x2_x3).unwrap();
+
+    match decoded {
+        RiscVInstruction::Remu { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X3);
+        }
+        _ => panic!("Expected REMU instruction"),
+    }
+}
+
+#[test]
+fn min_rd() {
+    let remu_x0_x1_x2 = 0x0220f033;
+    let decoded = RiscVInstruction::decode(remu_x0_x1_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::Remu { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X0);
+            assert_eq!(rs1, Register::X1);
+            assert_eq!(rs2, Register::X2);
+        }
+        _ => panic!("Expected REMU instruction"),
+    }
+}
+
+#[test]
+fn max_rd() {
+    let remu_x31_x1_x2 = 0x0220f033 | (31 << 7);
+    let decoded = RiscVInstruction::decode(remu_x31_x1_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::Remu { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X31);
+            assert_eq!(rs1, Register::X1);
+            assert_eq!(rs2, Register::X2);
+        }
+        _ => panic!("Expected REMU instruction"),
+    }
+}
+
+#[test]
+fn min_rs1() {
+    let remu_x1_x0_x2 = 0x022070b3;
+    let decoded = RiscVInstruction::decode(remu_x1_x0_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::Remu { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X0);
+            assert_eq!(rs2, Register::X2);
+        }
+        _ => panic!("Expected REMU instruction"),
+    }
+}
+
+#[test]
+fn max_rs1() {
+    let remu_x1_x31_x2 = 0x022ff0b3;
+    let decoded = RiscVInstruction::decode(remu_x1_x31_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::Remu { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X31);
+            assert_eq!(rs2, Register::X2);
+        }
+        _ => panic!("Expected REMU instruction"),
+    }
+}
+
+#[test]
+fn min_rs2() {
+    let remu_x1_x2_x0 = 0x020170b3;
+    let decoded = RiscVInstruction::decode(remu_x1_x2_x0).unwrap();
+
+    match decoded {
+        RiscVInstruction::Remu { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X0);
+        }
+        _ => panic!("Expected REMU instruction"),
+    }
+}
+
+#[test]
+fn max_rs2() {
+    let remu_x1_x2_x31 = 0x03f170b3;
+    let decoded = RiscVInstruction::decode(remu_x1_x2_x31).unwrap();
+
+    match decoded {
+        RiscVInstruction::Remu { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X31);
+        }
+        _ => panic!("Expected REMU instruction"),
+    }
+}
+
+#[test]
+fn all_max_values() {
+    let remu_x31_x31_x31 = 0x03ffffb3;
+    let decoded = RiscVInstruction::decode(remu_x31_x31_x31).unwrap();
+
+    match decoded {
+        RiscVInstruction::Remu { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X31);
+            assert_eq!(rs1, Register::X31);
+            assert_eq!(rs2, Register::X31);
+        }
+        _ => panic!("Expected REMU instruction"),
+    }
+}
+
+#[test]
+fn invalid_funct7_should_be_unsupported() {
+    // REMU with invalid funct7 (0x03 instead of 0x01)
+    let invalid_remu = 0x063170b3;
+    let decoded = RiscVInstruction::decode(invalid_remu);
+
+    match decoded {
+        Err(DecodeError { word, kind }) => {
+            assert_eq!(word, 0x063170b3);
+            assert_eq!(kind, DecodeErrorKind::ReservedFunct7);
+        }
+        _ => panic!("Expected decode error"),
+    }
+}
@@ -0,0 +1,102 @@
+use crate::error::{DecodeError, DecodeErrorKind};
+use crate::extension::Extensions;
+use crate::instruction::RiscVInstruction;
+use crate::profile::Profile;
+use crate::register::Register;
+
+/// Asserts that `word` is rejected in RV32E for naming `register`.
+fn assert_outside_rv32e(word: u32, register: Register) {
+    let decoded = RiscVInstruction::decode_for(word, Profile::Rv32e, Extensions::NONE);
+
+    match decoded {
+        Err(DecodeError {
+            word: error_word,
+            kind,
+        }) => {
+            assert_eq!(error_word, word);
+            assert_eq!(
+                kind,
+                DecodeErrorKind::UnsupportedRegister {
+                    register,
+                    profile: Profile::Rv32e,
+                }
+            );
+        }
+        _ => panic!("Expected decode error for 0x{:08x}", word),
+    }
+}
+
+#[test]
+fn rv32e_rejects_upper_registers() {
+    assert_outside_rv32e(0x00208833, Register::X16); // add x16, x1, x2
+    assert_outside_rv32e(0x010100b3, Register::X16); // add x1, x2, x16
+    assert_outside_rv32e(0x000f8093, Register::X31); // addi x1, x31, 0
+    assert_outside_rv32e(0x01f12023, Register::X31); // sw x31, 0(x2)
+}
+
+#[test]
+fn rv32e_accepts_lower_registers() {
+    // add x15, x14, x13
+    let add = 0x00d707b3;
+    let decoded = RiscVInstruction::decode_for(add, Profile::Rv32e, Extensions::NONE).unwrap();
+
+    match decoded {
+        RiscVInstruction::Add { rd, rs1, rs2 } => {
+            assert_eq!(rd, Register::X15);
+            assert_eq!(rs1, Register::X14);
+            assert_eq!(rs2, Register::X13);
+        }
+        _ => panic!("Expected ADD instruction"),
+    }
+}
+
+#[test]
+fn rv32i_accepts_every_register() {
+    let add = 0x00208833; // add x16, x1, x2
+    assert_eq!(
+        RiscVInstruction::decode_for(add, Profile::Rv32i, Extensions::NONE),
+        RiscVInstruction::decode(add)
+    );
+}
+
+#[test]
+fn compressed() {
+    let c_mv_x16_x1 = 0x8806;
+    let decoded =
+        RiscVInstruction::decode_compressed_for(c_mv_x16_x1, Profile::Rv32e, Extensions::NONE);
+
+    match decoded {
+        Err(DecodeError { word, kind }) => {
+            assert_eq!(word, 0x8806);
+            assert_eq!(
+                kind,
+                DecodeErrorKind::UnsupportedRegister {
+                    register: Register::X16,
+                    profile: Profile::Rv32e,
+                }
+            );
+        }
+        _ => panic!("Expected decode error"),
+    }
+
+    let c_mv_x15_x1 = 0x8786;
+    assert!(
+        RiscVInstruction::decode_compressed_for(c_mv_x15_x1, Profile::Rv32e, Extensions::NONE)
+            .is_ok()
+    );
+}
+
+#[test]
+fn other_errors_take_precedence() {
+    // An add with a reserved funct7 and rd = x16.
+    let reserved = 0x20208833;
+    let decoded = RiscVInstruction::decode_for(reserved, Profile::Rv32e, Extensions::NONE);
+
+    assert_eq!(
+        decoded,
+        Err(DecodeError {
+            word: reserved,
+            kind: DecodeErrorKind::ReservedFunct7,
+        })
+    );
+}
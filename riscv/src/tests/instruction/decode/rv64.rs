@@ -0,0 +1,271 @@
+use crate::error::{DecodeError, DecodeErrorKind};
+use crate::extension::{Extension, Extensions};
+use crate::instruction::RiscVInstruction;
+use crate::profile::Profile;
+use crate::register::Register;
+
+const RD: Register = Register::X10;
+const RS1: Register = Register::X11;
+const RS2: Register = Register::X12;
+
+fn decode_rv64(word: u32) -> Result<RiscVInstruction, DecodeError> {
+    RiscVInstruction::decode_for(word, Profile::Rv64i, Extensions::NONE)
+}
+
+/// Every RV64I and RV64M instruction, as encoded by LLVM.
+fn samples() -> Vec<(u32, RiscVInstruction)> {
+    vec![
+        (
+            0x800fb083,
+            RiscVInstruction::Ld {
+                rd: Register::X1,
+                rs1: Register::X31,
+                imm: -2048,
+            },
+        ),
+        (
+            0x7ff5e503,
+            RiscVInstruction::Lwu {
+                rd: RD,
+                rs1: RS1,
+                imm: 2047,
+            },
+        ),
+        (
+            0xfea13c23,
+            RiscVInstruction::Sd {
+                rs1: Register::X2,
+                rs2: RD,
+                imm: -8,
+            },
+        ),
+        (
+            0x7ff0bfa3,
+            RiscVInstruction::Sd {
+                rs1: Register::X1,
+                rs2: Register::X31,
+                imm: 2047,
+            },
+        ),
+        (
+            0xfff5851b,
+            RiscVInstruction::Addiw {
+                rd: RD,
+                rs1: RS1,
+                imm: -1,
+            },
+        ),
+        (
+            0x01f5951b,
+            RiscVInstruction::Slliw {
+                rd: RD,
+                rs1: RS1,
+                imm: 31,
+            },
+        ),
+        (
+            0x0015d51b,
+            RiscVInstruction::Srliw {
+                rd: RD,
+                rs1: RS1,
+                imm: 1,
+            },
+        ),
+        (
+            0x41f5d51b,
+            RiscVInstruction::Sraiw {
+                rd: RD,
+                rs1: RS1,
+                imm: 31,
+            },
+        ),
+        (
+            0x00c5853b,
+            RiscVInstruction::Addw {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+        ),
+        (
+            0x40c5853b,
+            RiscVInstruction::Subw {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+        ),
+        (
+            0x00c5953b,
+            RiscVInstruction::Sllw {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+        ),
+        (
+            0x00c5d53b,
+            RiscVInstruction::Srlw {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+        ),
+        (
+            0x40c5d53b,
+            RiscVInstruction::Sraw {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+        ),
+        (
+            0x02c5853b,
+            RiscVInstruction::Mulw {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+        ),
+        (
+            0x02c5c53b,
+            RiscVInstruction::Divw {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+        ),
+        (
+            0x02c5d53b,
+            RiscVInstruction::Divuw {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+        ),
+        (
+            0x02c5e53b,
+            RiscVInstruction::Remw {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+        ),
+        (
+            0x02c5f53b,
+            RiscVInstruction::Remuw {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+        ),
+    ]
+}
+
+#[test]
+fn every_instruction() {
+    for (word, instruction) in samples() {
+        assert_eq!(decode_rv64(word), Ok(instruction), "0x{:08x}", word);
+    }
+}
+
+#[test]
+fn not_decoded_for_rv32() {
+    for (word, _) in samples() {
+        assert!(RiscVInstruction::decode(word).is_err(), "0x{:08x}", word);
+    }
+}
+
+#[test]
+fn wide_shift_amounts() {
+    let cases = [
+        (
+            0x03f59513,
+            RiscVInstruction::Slli {
+                rd: RD,
+                rs1: RS1,
+                imm: 63,
+            },
+        ),
+        (
+            0x0205d513,
+            RiscVInstruction::Srli {
+                rd: RD,
+                rs1: RS1,
+                imm: 32,
+            },
+        ),
+        (
+            0x43f5d513,
+            RiscVInstruction::Srai {
+                rd: RD,
+                rs1: RS1,
+                imm: 63,
+            },
+        ),
+    ];
+
+    for (word, instruction) in cases {
+        assert_eq!(decode_rv64(word), Ok(instruction));
+        // shamt[5] lands in funct7, which RV32I reserves.
+        assert_eq!(
+            RiscVInstruction::decode(word),
+            Err(DecodeError::new(word, DecodeErrorKind::ReservedFunct7))
+        );
+    }
+}
+
+#[test]
+fn shared_instructions() {
+    // add x10, x11, x12, lw x10, 8(x2) and slli x10, x11, 5
+    for word in [0x00c58533, 0x00812503, 0x00559513] {
+        assert_eq!(decode_rv64(word), RiscVInstruction::decode(word));
+    }
+}
+
+#[test]
+fn reserved_word_encodings() {
+    let cases = [
+        // OP-32 with funct7 = 0x01 and funct3 = 1
+        (0x02c5953b, DecodeErrorKind::ReservedFunct3),
+        // OP-32 with funct7 = 0x10
+        (0x20c5853b, DecodeErrorKind::ReservedFunct7),
+        // OP-IMM-32 with funct3 = 2
+        (0x0005a51b, DecodeErrorKind::ReservedFunct3),
+        // slliw with shamt[5] set
+        (0x0205951b, DecodeErrorKind::ReservedFunct7),
+        // The 128-bit load ldu, funct3 = 7
+        (0x0005f503, DecodeErrorKind::ReservedFunct3),
+    ];
+
+    for (word, kind) in cases {
+        assert_eq!(decode_rv64(word), Err(DecodeError::new(word, kind)));
+    }
+}
+
+#[test]
+fn extensions_are_unimplemented() {
+    // clz x10, x11
+    let clz = 0x60059513;
+    assert_eq!(
+        RiscVInstruction::decode_for(clz, Profile::Rv64i, Extensions::ALL),
+        Err(DecodeError::new(clz, DecodeErrorKind::Unimplemented))
+    );
+    assert_eq!(
+        decode_rv64(clz),
+        Err(DecodeError::new(
+            clz,
+            DecodeErrorKind::UnsupportedExtension(Extension::Zbb)
+        ))
+    );
+}
+
+#[test]
+fn fence_i() {
+    let fence_i = 0x0000100f;
+    let zifencei = Extensions::NONE.with(Extension::Zifencei);
+    assert_eq!(
+        RiscVInstruction::decode_for(fence_i, Profile::Rv64i, zifencei),
+        Ok(RiscVInstruction::FenceI)
+    );
+}
@@ -0,0 +1,184 @@
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn basic() {
+    let sh_x1_100_x2 = 0x06111223;
+    let decoded = RiscVInstruction::decode(sh_x1_100_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::Sh { rs1, rs2, imm } => {
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X1);
+            assert_eq!(imm, 100);
+        }
+        _ => panic!("Expected SH instruction"),
+    }
+}
+
+#[test]
+fn min_rs1() {
+    let sh_x1_0_x0 = 0x00101023;
+    let decoded = RiscVInstruction::decode(sh_x1_0_x0).unwrap();
+
+    match decoded {
+        RiscVInstruction::Sh { rs1, rs2, imm } => {
+            assert_eq!(rs1, Register::X0);
+            assert_eq!(rs2, Register::X1);
+            assert_eq!(imm, 0);
+        }
+        _ => panic!("Expected SH instruction"),
+    }
+}
+
+#[test]
+fn max_rs1() {
+    let sh_x1_0_x31 = 0x001f9023;
+    let decoded = RiscVInstruction::decode(sh_x1_0_x31).unwrap();
+
+    match decoded {
+        RiscVInstruction::Sh { rs1, rs2, imm } => {
+            assert_eq!(rs1, Register::X31);
+            assert_eq!(rs2, Register::X1);
+            assert_eq!(imm, 0);
+        }
+        _ => panic!("Expected SH instruction"),
+    }
+}
+
+#[test]
+fn min_rs2() {
+    let sh_x0_0_x1 = 0x00009023;
+    let decoded = RiscVInstruction::decode(sh_x0_0_x1).unwrap();
+
+    match decoded {
+        RiscVInstruction::Sh { rs1, rs2, imm } => {
+            assert_eq!(rs1, Register::X1);
+            assert_eq!(rs2, Register::X0);
+            assert_eq!(imm, 0);
+        }
+        _ => panic!("Expected SH instruction"),
+    }
+}
+
+#[test]
+fn max_rs2() {
+    let sh_x31_0_x1 = 0x01f09023;
+    let decoded = RiscVInstruction::decode(sh_x31_0_x1).unwrap();
+
+    match decoded {
+        RiscVInstruction::Sh { rs1, rs2, imm } => {
+            assert_eq!(rs1, Register::X1);
+            assert_eq!(rs2, Register::X31);
+            assert_eq!(imm, 0);
+        }
+        _ => panic!("Expected SH instruction"),
+    }
+}
+
+#[test]
+fn negative_imm() {
+    let sh_x1_neg4_x2 = 0xfe111e23;
+    let decoded = RiscVInstruction::decode(sh_x1_neg4_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::Sh { rs1, rs2, imm } => {
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X1);
+            assert_eq!(imm, -4);
+        }
+        _ => panic!("Expected SH instruction"),
+    }
+}
+
+#[test]
+fn zero_imm() {
+    let sh_x1_0_x2 = 0x00111023;
+    let decoded = RiscVInstruction::decode(sh_x1_0_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::Sh { rs1, rs2, imm } => {
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X1);
+            assert_eq!(imm, 0);
+        }
+        _ => panic!("Expected SH instruction"),
+    }
+}
+
+#[test]
+fn max_positive_imm() {
+    let sh_x1_2047_x0 = 0x7e101fa3;
+    let decoded = RiscVInstruction::decode(sh_x1_2047_x0).unwrap();
+
+    match decoded {
+        RiscVInstruction::Sh { rs1, rs2, imm } => {
+            assert_eq!(rs1, Register::X0);
+            assert_eq!(rs2, Register::X1);
+            assert_eq!(imm, 2047);
+        }
+        _ => panic!("Expected SH instruction"),
+    }
+}
+
+#[test]
+fn min_negative_imm() {
+    let sh_x1_neg2048_x0 = 0x80101023;
+    let decoded = RiscVInstruction::decode(sh_x1_neg2048_x0).unwrap();
+
+    match decoded {
+        RiscVInstruction::Sh { rs1, rs2, imm } => {
+            assert_eq!(rs1, Register::X0);
+            assert_eq!(rs2, Register::X1);
+            assert_eq!(imm, -2048);
+        }
+        _ => panic!("Expected SH instruction"),
+    }
+}
+
+#[test]
+fn neg_one_imm() {
+    let sh_x1_neg1_x2 = 0xfe111fa3;
+    let decoded = RiscVInstruction::decode(sh_x1_neg1_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::Sh { rs1, rs2, imm } => {
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X1);
+            assert_eq!(imm, -1);
+        }
+        _ => panic!("Expected SH instruction"),
+    }
+}
+
+#[test]
+fn low_imm_bits_only() {
+    // imm[4:0] is encoded in bits 11:7
+    let sh_x1_31_x2 = 0x00111fa3;
+    let decoded = RiscVInstruction::decode(sh_x1_31_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::Sh { rs1, rs2, imm } => {
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X1);
+            assert_eq!(imm, 31);
+        }
+        _ => panic!("Expected SH instruction"),
+    }
+}
+
+#[test]
+fn high_imm_bits_only() {
+    // imm[11:5] is encoded in bits 31:25
+    let sh_x1_32_x2 = 0x02111023;
+    let decoded = RiscVInstruction::decode(sh_x1_32_x2).unwrap();
+
+    match decoded {
+        RiscVInstruction::Sh { rs1, rs2, imm } => {
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(rs2, Register::X1);
+            assert_eq!(imm, 32);
+        }
+        _ => panic!("Expected SH instruction"),
+    }
+}
@@ -0,0 +1,126 @@
+use crate::error::{DecodeError, DecodeErrorKind};
+use crate::extension::{Extension, Extensions};
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+const ZICSR: Extensions = Extensions::NONE.with(Extension::Zicsr);
+
+#[test]
+fn csrrw() {
+    let csrrw_x1_0x340_x2 = 0x340110f3;
+    let decoded = RiscVInstruction::decode_with(csrrw_x1_0x340_x2, ZICSR).unwrap();
+
+    match decoded {
+        RiscVInstruction::Csrrw { rd, rs1, csr } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X2);
+            assert_eq!(csr, 0x340);
+        }
+        _ => panic!("Expected CSRRW instruction"),
+    }
+}
+
+#[test]
+fn csrrs() {
+    let csrrs_x10_cycle_x0 = 0xc0002573;
+    let decoded = RiscVInstruction::decode_with(csrrs_x10_cycle_x0, ZICSR).unwrap();
+
+    match decoded {
+        RiscVInstruction::Csrrs { rd, rs1, csr } => {
+            assert_eq!(rd, Register::X10);
+            assert_eq!(rs1, Register::X0);
+            assert_eq!(csr, 0xc00);
+        }
+        _ => panic!("Expected CSRRS instruction"),
+    }
+}
+
+#[test]
+fn csrrc_max_values() {
+    let csrrc_x1_0xfff_x31 = 0xffffb0f3;
+    let decoded = RiscVInstruction::decode_with(csrrc_x1_0xfff_x31, ZICSR).unwrap();
+
+    match decoded {
+        RiscVInstruction::Csrrc { rd, rs1, csr } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(rs1, Register::X31);
+            assert_eq!(csr, 0xfff);
+        }
+        _ => panic!("Expected CSRRC instruction"),
+    }
+}
+
+#[test]
+fn csrrwi_max_immediate() {
+    let csrrwi_x1_0x340_31 = 0x340fd0f3;
+    let decoded = RiscVInstruction::decode_with(csrrwi_x1_0x340_31, ZICSR).unwrap();
+
+    match decoded {
+        RiscVInstruction::Csrrwi { rd, imm, csr } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(imm, 31);
+            assert_eq!(csr, 0x340);
+        }
+        _ => panic!("Expected CSRRWI instruction"),
+    }
+}
+
+#[test]
+fn csrrsi() {
+    let csrrsi_x1_instreth_1 = 0xc820e0f3;
+    let decoded = RiscVInstruction::decode_with(csrrsi_x1_instreth_1, ZICSR).unwrap();
+
+    match decoded {
+        RiscVInstruction::Csrrsi { rd, imm, csr } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(imm, 1);
+            assert_eq!(csr, 0xc82);
+        }
+        _ => panic!("Expected CSRRSI instruction"),
+    }
+}
+
+#[test]
+fn csrrci_min_values() {
+    let csrrci_x0_0x001_0 = 0x00107073;
+    let decoded = RiscVInstruction::decode_with(csrrci_x0_0x001_0, ZICSR).unwrap();
+
+    match decoded {
+        RiscVInstruction::Csrrci { rd, imm, csr } => {
+            assert_eq!(rd, Register::X0);
+            assert_eq!(imm, 0);
+            assert_eq!(csr, 0x001);
+        }
+        _ => panic!("Expected CSRRCI instruction"),
+    }
+}
+
+#[test]
+fn disabled_should_be_unsupported() {
+    for word in [0x340110f3, 0xc0002573, 0x340fd0f3, 0x00107073] {
+        let decoded = RiscVInstruction::decode_with(word, Extensions::NONE);
+
+        assert_eq!(
+            decoded,
+            Err(DecodeError {
+                word,
+                kind: DecodeErrorKind::UnsupportedExtension(Extension::Zicsr),
+            })
+        );
+    }
+}
+
+#[test]
+fn reserved_funct3() {
+    // SYSTEM with funct3 4, which is not assigned to a CSR instruction.
+    let reserved = 0xc0004573;
+    let decoded = RiscVInstruction::decode_with(reserved, ZICSR);
+
+    match decoded {
+        Err(DecodeError { word, kind }) => {
+            assert_eq!(word, 0xc0004573);
+            assert_eq!(kind, DecodeErrorKind::ReservedFunct3);
+        }
+        _ => panic!("Expected decode error"),
+    }
+}
@@ -1,9 +1,10 @@
+use crate::error::{DecodeError, DecodeErrorKind};
 use crate::instruction::RiscVInstruction;
 
 #[test]
 fn basic() {
     let ebreak = 0x00100073;
-    let decoded = RiscVInstruction::decode(ebreak);
+    let decoded = RiscVInstruction::decode(ebreak).unwrap();
 
     match decoded {
         RiscVInstruction::Ebreak => {}
@@ -15,7 +16,7 @@ fn basic() {
 fn with_correct_opcode_and_fields() {
     // EBREAK: opcode=0x73, funct3=0x0, rd=0, rs1=0, imm=1
     let ebreak = 0x00100073;
-    let decoded = RiscVInstruction::decode(ebreak);
+    let decoded = RiscVInstruction::decode(ebreak).unwrap();
 
     match decoded {
         RiscVInstruction::Ebreak => {}
@@ -30,10 +31,11 @@ fn with_non_zero_rd_should_be_unsupported() {
     let decoded = RiscVInstruction::decode(invalid_ebreak);
 
     match decoded {
-        RiscVInstruction::Unsupported(word) => {
+        Err(DecodeError { word, kind }) => {
             assert_eq!(word, invalid_ebreak);
+            assert_eq!(kind, DecodeErrorKind::InvalidOperands);
         }
-        _ => panic!("Expected unsupported instruction"),
+        _ => panic!("Expected decode error"),
     }
 }
 
@@ -44,9 +46,10 @@ fn with_non_zero_rs1_should_be_unsupported() {
     let decoded = RiscVInstruction::decode(invalid_ebreak);
 
     match decoded {
-        RiscVInstruction::Unsupported(word) => {
+        Err(DecodeError { word, kind }) => {
             assert_eq!(word, invalid_ebreak);
+            assert_eq!(kind, DecodeErrorKind::InvalidOperands);
         }
-        _ => panic!("Expected unsupported instruction"),
+        _ => panic!("Expected decode error"),
     }
 }
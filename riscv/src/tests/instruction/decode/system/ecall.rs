@@ -1,9 +1,10 @@
+use crate::error::{DecodeError, DecodeErrorKind};
 use crate::instruction::RiscVInstruction;
 
 #[test]
 fn basic() {
     let ecall = 0x00000073;
-    let decoded = RiscVInstruction::decode(ecall);
+    let decoded = RiscVInstruction::decode(ecall).unwrap();
 
     match decoded {
         RiscVInstruction::Ecall => {}
@@ -15,7 +16,7 @@ fn basic() {
 fn with_correct_opcode_and_fields() {
     // ECALL: opcode=0x73, funct3=0x0, rd=0, rs1=0, imm=0
     let ecall = 0x00000073;
-    let decoded = RiscVInstruction::decode(ecall);
+    let decoded = RiscVInstruction::decode(ecall).unwrap();
 
     match decoded {
         RiscVInstruction::Ecall => {}
@@ -30,10 +31,11 @@ fn with_non_zero_rd_should_be_unsupported() {
     let decoded = RiscVInstruction::decode(invalid_ecall);
 
     match decoded {
-        RiscVInstruction::Unsupported(word) => {
+        Err(DecodeError { word, kind }) => {
             assert_eq!(word, invalid_ecall);
+            assert_eq!(kind, DecodeErrorKind::InvalidOperands);
         }
-        _ => panic!("Expected unsupported instruction"),
+        _ => panic!("Expected decode error"),
     }
 }
 
@@ -44,9 +46,10 @@ fn with_non_zero_rs1_should_be_unsupported() {
     let decoded = RiscVInstruction::decode(invalid_ecall);
 
     match decoded {
-        RiscVInstruction::Unsupported(word) => {
+        Err(DecodeError { word, kind }) => {
             assert_eq!(word, invalid_ecall);
+            assert_eq!(kind, DecodeErrorKind::InvalidOperands);
         }
-        _ => panic!("Expected unsupported instruction"),
+        _ => panic!("Expected decode error"),
     }
 }
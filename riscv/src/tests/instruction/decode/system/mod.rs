@@ -1,2 +1,3 @@
+mod csr;
 mod ebreak;
 mod ecall;
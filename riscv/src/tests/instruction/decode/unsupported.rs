@@ -1,3 +1,5 @@
+use crate::error::{DecodeError, DecodeErrorKind};
+use crate::extension::Extension;
 use crate::instruction::RiscVInstruction;
 
 #[test]
@@ -6,10 +8,11 @@ fn opcode() {
     let decoded = RiscVInstruction::decode(unsupported);
 
     match decoded {
-        RiscVInstruction::Unsupported(word) => {
+        Err(DecodeError { word, kind }) => {
             assert_eq!(word, 0x12345678);
+            assert_eq!(kind, DecodeErrorKind::UnknownOpcode);
         }
-        _ => panic!("Expected unsupported instruction"),
+        _ => panic!("Expected decode error"),
     }
 }
 
@@ -19,10 +22,11 @@ fn slli_invalid_funct7() {
     let decoded = RiscVInstruction::decode(slli_with_invalid_funct7);
 
     match decoded {
-        RiscVInstruction::Unsupported(word) => {
+        Err(DecodeError { word, kind }) => {
             assert_eq!(word, 0x02109093);
+            assert_eq!(kind, DecodeErrorKind::ReservedFunct7);
         }
-        _ => panic!("Expected unsupported instruction for SLLI with invalid funct7"),
+        _ => panic!("Expected decode error for SLLI with invalid funct7"),
     }
 }
 
@@ -32,10 +36,11 @@ fn jalr_funct3() {
     let decoded = RiscVInstruction::decode(jalr_with_invalid_funct3);
 
     match decoded {
-        RiscVInstruction::Unsupported(word) => {
+        Err(DecodeError { word, kind }) => {
             assert_eq!(word, 0x004110e7);
+            assert_eq!(kind, DecodeErrorKind::ReservedFunct3);
         }
-        _ => panic!("Expected unsupported instruction for JALR with invalid funct3"),
+        _ => panic!("Expected decode error for JALR with invalid funct3"),
     }
 }
 
@@ -45,10 +50,11 @@ fn load_invalid_funct3() {
     let decoded = RiscVInstruction::decode(load_with_invalid_funct3);
 
     match decoded {
-        RiscVInstruction::Unsupported(word) => {
+        Err(DecodeError { word, kind }) => {
             assert_eq!(word, 0x0031b083);
+            assert_eq!(kind, DecodeErrorKind::ReservedFunct3);
         }
-        _ => panic!("Expected unsupported instruction for LOAD with invalid funct3"),
+        _ => panic!("Expected decode error for LOAD with invalid funct3"),
     }
 }
 
@@ -59,10 +65,11 @@ fn system_invalid_imm() {
     let decoded = RiscVInstruction::decode(system_invalid_imm);
 
     match decoded {
-        RiscVInstruction::Unsupported(word) => {
+        Err(DecodeError { word, kind }) => {
             assert_eq!(word, 0x00200073);
+            assert_eq!(kind, DecodeErrorKind::ReservedFunct12);
         }
-        _ => panic!("Expected unsupported instruction for SYSTEM with invalid imm"),
+        _ => panic!("Expected decode error for SYSTEM with invalid imm"),
     }
 }
 
@@ -73,9 +80,41 @@ fn system_invalid_funct3() {
     let decoded = RiscVInstruction::decode(system_invalid_funct3);
 
     match decoded {
-        RiscVInstruction::Unsupported(word) => {
+        Err(DecodeError { word, kind }) => {
             assert_eq!(word, 0x00001073);
+            assert_eq!(
+                kind,
+                DecodeErrorKind::UnsupportedExtension(Extension::Zicsr)
+            );
         }
-        _ => panic!("Expected unsupported instruction for SYSTEM with invalid funct3"),
+        _ => panic!("Expected decode error for SYSTEM with invalid funct3"),
+    }
+}
+
+#[test]
+fn store_invalid_funct3() {
+    let store_with_invalid_funct3 = 0x0011b223;
+    let decoded = RiscVInstruction::decode(store_with_invalid_funct3);
+
+    match decoded {
+        Err(DecodeError { word, kind }) => {
+            assert_eq!(word, 0x0011b223);
+            assert_eq!(kind, DecodeErrorKind::ReservedFunct3);
+        }
+        _ => panic!("Expected decode error for STORE with invalid funct3"),
+    }
+}
+
+#[test]
+fn branch_invalid_funct3() {
+    let branch_with_invalid_funct3 = 0x0020a463;
+    let decoded = RiscVInstruction::decode(branch_with_invalid_funct3);
+
+    match decoded {
+        Err(DecodeError { word, kind }) => {
+            assert_eq!(word, 0x0020a463);
+            assert_eq!(kind, DecodeErrorKind::ReservedFunct3);
+        }
+        _ => panic!("Expected decode error for BRANCH with invalid funct3"),
     }
 }
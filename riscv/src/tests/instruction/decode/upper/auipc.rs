@@ -0,0 +1,101 @@
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn basic() {
+    let auipc_x1_0x12345 = 0x12345097;
+    let decoded = RiscVInstruction::decode(auipc_x1_0x12345).unwrap();
+
+    match decoded {
+        RiscVInstruction::Auipc { rd, imm } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(imm, 0x12345000);
+        }
+        _ => panic!("Expected AUIPC instruction"),
+    }
+}
+
+#[test]
+fn min_rd() {
+    let auipc_x0_0x1 = 0x00001017;
+    let decoded = RiscVInstruction::decode(auipc_x0_0x1).unwrap();
+
+    match decoded {
+        RiscVInstruction::Auipc { rd, imm } => {
+            assert_eq!(rd, Register::X0);
+            assert_eq!(imm, 4096);
+        }
+        _ => panic!("Expected AUIPC instruction"),
+    }
+}
+
+#[test]
+fn max_rd() {
+    let auipc_x31_0x1 = 0x00001f97;
+    let decoded = RiscVInstruction::decode(auipc_x31_0x1).unwrap();
+
+    match decoded {
+        RiscVInstruction::Auipc { rd, imm } => {
+            assert_eq!(rd, Register::X31);
+            assert_eq!(imm, 4096);
+        }
+        _ => panic!("Expected AUIPC instruction"),
+    }
+}
+
+#[test]
+fn zero_imm() {
+    let auipc_x1_0x0 = 0x00000097;
+    let decoded = RiscVInstruction::decode(auipc_x1_0x0).unwrap();
+
+    match decoded {
+        RiscVInstruction::Auipc { rd, imm } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(imm, 0);
+        }
+        _ => panic!("Expected AUIPC instruction"),
+    }
+}
+
+#[test]
+fn max_positive_imm() {
+    let auipc_x1_0x7ffff = 0x7ffff097;
+    let decoded = RiscVInstruction::decode(auipc_x1_0x7ffff).unwrap();
+
+    match decoded {
+        RiscVInstruction::Auipc { rd, imm } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(imm, 0x7ffff000);
+        }
+        _ => panic!("Expected AUIPC instruction"),
+    }
+}
+
+#[test]
+fn min_negative_imm() {
+    // imm[31] set: the value is negative as a 32-bit integer
+    let auipc_x1_0x80000 = 0x80000097;
+    let decoded = RiscVInstruction::decode(auipc_x1_0x80000).unwrap();
+
+    match decoded {
+        RiscVInstruction::Auipc { rd, imm } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(imm, 0x80000000_u32 as i32);
+        }
+        _ => panic!("Expected AUIPC instruction"),
+    }
+}
+
+#[test]
+fn all_ones_imm() {
+    let auipc_x1_0xfffff = 0xfffff097;
+    let decoded = RiscVInstruction::decode(auipc_x1_0xfffff).unwrap();
+
+    match decoded {
+        RiscVInstruction::Auipc { rd, imm } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(imm, 0xfffff000_u32 as i32);
+        }
+        _ => panic!("Expected AUIPC instruction"),
+    }
+}
@@ -0,0 +1,101 @@
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn basic() {
+    let lui_x1_0x12345 = 0x123450b7;
+    let decoded = RiscVInstruction::decode(lui_x1_0x12345).unwrap();
+
+    match decoded {
+        RiscVInstruction::Lui { rd, imm } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(imm, 0x12345000);
+        }
+        _ => panic!("Expected LUI instruction"),
+    }
+}
+
+#[test]
+fn min_rd() {
+    let lui_x0_0x1 = 0x00001037;
+    let decoded = RiscVInstruction::decode(lui_x0_0x1).unwrap();
+
+    match decoded {
+        RiscVInstruction::Lui { rd, imm } => {
+            assert_eq!(rd, Register::X0);
+            assert_eq!(imm, 4096);
+        }
+        _ => panic!("Expected LUI instruction"),
+    }
+}
+
+#[test]
+fn max_rd() {
+    let lui_x31_0x1 = 0x00001fb7;
+    let decoded = RiscVInstruction::decode(lui_x31_0x1).unwrap();
+
+    match decoded {
+        RiscVInstruction::Lui { rd, imm } => {
+            assert_eq!(rd, Register::X31);
+            assert_eq!(imm, 4096);
+        }
+        _ => panic!("Expected LUI instruction"),
+    }
+}
+
+#[test]
+fn zero_imm() {
+    let lui_x1_0x0 = 0x000000b7;
+    let decoded = RiscVInstruction::decode(lui_x1_0x0).unwrap();
+
+    match decoded {
+        RiscVInstruction::Lui { rd, imm } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(imm, 0);
+        }
+        _ => panic!("Expected LUI instruction"),
+    }
+}
+
+#[test]
+fn max_positive_imm() {
+    let lui_x1_0x7ffff = 0x7ffff0b7;
+    let decoded = RiscVInstruction::decode(lui_x1_0x7ffff).unwrap();
+
+    match decoded {
+        RiscVInstruction::Lui { rd, imm } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(imm, 0x7ffff000);
+        }
+        _ => panic!("Expected LUI instruction"),
+    }
+}
+
+#[test]
+fn min_negative_imm() {
+    // imm[31] set: the value is negative as a 32-bit integer
+    let lui_x1_0x80000 = 0x800000b7;
+    let decoded = RiscVInstruction::decode(lui_x1_0x80000).unwrap();
+
+    match decoded {
+        RiscVInstruction::Lui { rd, imm } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(imm, 0x80000000_u32 as i32);
+        }
+        _ => panic!("Expected LUI instruction"),
+    }
+}
+
+#[test]
+fn all_ones_imm() {
+    let lui_x1_0xfffff = 0xfffff0b7;
+    let decoded = RiscVInstruction::decode(lui_x1_0xfffff).unwrap();
+
+    match decoded {
+        RiscVInstruction::Lui { rd, imm } => {
+            assert_eq!(rd, Register::X1);
+            assert_eq!(imm, 0xfffff000_u32 as i32);
+        }
+        _ => panic!("Expected LUI instruction"),
+    }
+}
@@ -0,0 +1,2 @@
+mod auipc;
+mod lui;
@@ -0,0 +1,52 @@
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn basic() {
+    let and = RiscVInstruction::And {
+        rd: Register::X1,
+        rs1: Register::X2,
+        rs2: Register::X3,
+    };
+    assert_eq!(format!("{}", and), "and x1, x2, x3");
+}
+
+#[test]
+fn min_values() {
+    let and_min = RiscVInstruction::And {
+        rd: Register::X0,
+        rs1: Register::X0,
+        rs2: Register::X0,
+    };
+    assert_eq!(format!("{}", and_min), "and x0, x0, x0");
+}
+
+#[test]
+fn max_values() {
+    let and_max = RiscVInstruction::And {
+        rd: Register::X31,
+        rs1: Register::X31,
+        rs2: Register::X31,
+    };
+    assert_eq!(format!("{}", and_max), "and x31, x31, x31");
+}
+
+#[test]
+fn mixed_registers() {
+    let and_mixed = RiscVInstruction::And {
+        rd: Register::X5,
+        rs1: Register::X10,
+        rs2: Register::X15,
+    };
+    assert_eq!(format!("{}", and_mixed), "and x5, x10, x15");
+}
+
+#[test]
+fn same_registers() {
+    let and_same = RiscVInstruction::And {
+        rd: Register::X7,
+        rs1: Register::X7,
+        rs2: Register::X7,
+    };
+    assert_eq!(format!("{}", and_same), "and x7, x7, x7");
+}
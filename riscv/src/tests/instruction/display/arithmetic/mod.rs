@@ -1,4 +1,10 @@
 mod add;
+mod and;
 mod or;
+mod sll;
+mod slt;
+mod sltu;
+mod sra;
+mod srl;
 mod sub;
 mod xor;
@@ -0,0 +1,52 @@
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn basic() {
+    let sll = RiscVInstruction::Sll {
+        rd: Register::X1,
+        rs1: Register::X2,
+        rs2: Register::X3,
+    };
+    assert_eq!(format!("{}", sll), "sll x1, x2, x3");
+}
+
+#[test]
+fn min_values() {
+    let sll_min = RiscVInstruction::Sll {
+        rd: Register::X0,
+        rs1: Register::X0,
+        rs2: Register::X0,
+    };
+    assert_eq!(format!("{}", sll_min), "sll x0, x0, x0");
+}
+
+#[test]
+fn max_values() {
+    let sll_max = RiscVInstruction::Sll {
+        rd: Register::X31,
+        rs1: Register::X31,
+        rs2: Register::X31,
+    };
+    assert_eq!(format!("{}", sll_max), "sll x31, x31, x31");
+}
+
+#[test]
+fn mixed_registers() {
+    let sll_mixed = RiscVInstruction::Sll {
+        rd: Register::X5,
+        rs1: Register::X10,
+        rs2: Register::X15,
+    };
+    assert_eq!(format!("{}", sll_mixed), "sll x5, x10, x15");
+}
+
+#[test]
+fn same_registers() {
+    let sll_same = RiscVInstruction::Sll {
+        rd: Register::X7,
+        rs1: Register::X7,
+        rs2: Register::X7,
+    };
+    assert_eq!(format!("{}", sll_same), "sll x7, x7, x7");
+}
@@ -0,0 +1,52 @@
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn basic() {
+    let slt = RiscVInstruction::Slt {
+        rd: Register::X1,
+        rs1: Register::X2,
+        rs2: Register::X3,
+    };
+    assert_eq!(format!("{}", slt), "slt x1, x2, x3");
+}
+
+#[test]
+fn min_values() {
+    let slt_min = RiscVInstruction::Slt {
+        rd: Register::X0,
+        rs1: Register::X0,
+        rs2: Register::X0,
+    };
+    assert_eq!(format!("{}", slt_min), "slt x0, x0, x0");
+}
+
+#[test]
+fn max_values() {
+    let slt_max = RiscVInstruction::Slt {
+        rd: Register::X31,
+        rs1: Register::X31,
+        rs2: Register::X31,
+    };
+    assert_eq!(format!("{}", slt_max), "slt x31, x31, x31");
+}
+
+#[test]
+fn mixed_registers() {
+    let slt_mixed = RiscVInstruction::Slt {
+        rd: Register::X5,
+        rs1: Register::X10,
+        rs2: Register::X15,
+    };
+    assert_eq!(format!("{}", slt_mixed), "slt x5, x10, x15");
+}
+
+#[test]
+fn same_registers() {
+    let slt_same = RiscVInstruction::Slt {
+        rd: Register::X7,
+        rs1: Register::X7,
+        rs2: Register::X7,
+    };
+    assert_eq!(format!("{}", slt_same), "slt x7, x7, x7");
+}
@@ -0,0 +1,52 @@
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn basic() {
+    let sltu = RiscVInstruction::Sltu {
+        rd: Register::X1,
+        rs1: Register::X2,
+        rs2: Register::X3,
+    };
+    assert_eq!(format!("{}", sltu), "sltu x1, x2, x3");
+}
+
+#[test]
+fn min_values() {
+    let sltu_min = RiscVInstruction::Sltu {
+        rd: Register::X0,
+        rs1: Register::X0,
+        rs2: Register::X0,
+    };
+    assert_eq!(format!("{}", sltu_min), "sltu x0, x0, x0");
+}
+
+#[test]
+fn max_values() {
+    let sltu_max = RiscVInstruction::Sltu {
+        rd: Register::X31,
+        rs1: Register::X31,
+        rs2: Register::X31,
+    };
+    assert_eq!(format!("{}", sltu_max), "sltu x31, x31, x31");
+}
+
+#[test]
+fn mixed_registers() {
+    let sltu_mixed = RiscVInstruction::Sltu {
+        rd: Register::X5,
+        rs1: Register::X10,
+        rs2: Register::X15,
+    };
+    assert_eq!(format!("{}", sltu_mixed), "sltu x5, x10, x15");
+}
+
+#[test]
+fn same_registers() {
+    let sltu_same = RiscVInstruction::Sltu {
+        rd: Register::X7,
+        rs1: Register::X7,
+        rs2: Register::X7,
+    };
+    assert_eq!(format!("{}", sltu_same), "sltu x7, x7, x7");
+}
@@ -0,0 +1,52 @@
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn basic() {
+    let sra = RiscVInstruction::Sra {
+        rd: Register::X1,
+        rs1: Register::X2,
+        rs2: Register::X3,
+    };
+    assert_eq!(format!("{}", sra), "sra x1, x2, x3");
+}
+
+#[test]
+fn min_values() {
+    let sra_min = RiscVInstruction::Sra {
+        rd: Register::X0,
+        rs1: Register::X0,
+        rs2: Register::X0,
+    };
+    assert_eq!(format!("{}", sra_min), "sra x0, x0, x0");
+}
+
+#[test]
+fn max_values() {
+    let sra_max = RiscVInstruction::Sra {
+        rd: Register::X31,
+        rs1: Register::X31,
+        rs2: Register::X31,
+    };
+    assert_eq!(format!("{}", sra_max), "sra x31, x31, x31");
+}
+
+#[test]
+fn mixed_registers() {
+    let sra_mixed = RiscVInstruction::Sra {
+        rd: Register::X5,
+        rs1: Register::X10,
+        rs2: Register::X15,
+    };
+    assert_eq!(format!("{}", sra_mixed), "sra x5, x10, x15");
+}
+
+#[test]
+fn same_registers() {
+    let sra_same = RiscVInstruction::Sra {
+        rd: Register::X7,
+        rs1: Register::X7,
+        rs2: Register::X7,
+    };
+    assert_eq!(format!("{}", sra_same), "sra x7, x7, x7");
+}
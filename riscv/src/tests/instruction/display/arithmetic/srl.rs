@@ -0,0 +1,52 @@
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn basic() {
+    let srl = RiscVInstruction::Srl {
+        rd: Register::X1,
+        rs1: Register::X2,
+        rs2: Register::X3,
+    };
+    assert_eq!(format!("{}", srl), "srl x1, x2, x3");
+}
+
+#[test]
+fn min_values() {
+    let srl_min = RiscVInstruction::Srl {
+        rd: Register::X0,
+        rs1: Register::X0,
+        rs2: Register::X0,
+    };
+    assert_eq!(format!("{}", srl_min), "srl x0, x0, x0");
+}
+
+#[test]
+fn max_values() {
+    let srl_max = RiscVInstruction::Srl {
+        rd: Register::X31,
+        rs1: Register::X31,
+        rs2: Register::X31,
+    };
+    assert_eq!(format!("{}", srl_max), "srl x31, x31, x31");
+}
+
+#[test]
+fn mixed_registers() {
+    let srl_mixed = RiscVInstruction::Srl {
+        rd: Register::X5,
+        rs1: Register::X10,
+        rs2: Register::X15,
+    };
+    assert_eq!(format!("{}", srl_mixed), "srl x5, x10, x15");
+}
+
+#[test]
+fn same_registers() {
+    let srl_same = RiscVInstruction::Srl {
+        rd: Register::X7,
+        rs1: Register::X7,
+        rs2: Register::X7,
+    };
+    assert_eq!(format!("{}", srl_same), "srl x7, x7, x7");
+}
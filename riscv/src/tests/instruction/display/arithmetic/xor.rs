@@ -1,11 +1,12 @@
 use crate::instruction::RiscVInstruction;
+use crate::register::Register;
 
 #[test]
 fn basic() {
     let xor = RiscVInstruction::Xor {
-        rd: 1,
-        rs1: 2,
-        rs2: 3,
+        rd: Register::X1,
+        rs1: Register::X2,
+        rs2: Register::X3,
     };
     assert_eq!(format!("{}", xor), "xor x1, x2, x3");
 }
@@ -13,9 +14,9 @@ fn basic() {
 #[test]
 fn min_values() {
     let xor_min = RiscVInstruction::Xor {
-        rd: 0,
-        rs1: 0,
-        rs2: 0,
+        rd: Register::X0,
+        rs1: Register::X0,
+        rs2: Register::X0,
     };
     assert_eq!(format!("{}", xor_min), "xor x0, x0, x0");
 }
@@ -23,9 +24,9 @@ fn min_values() {
 #[test]
 fn max_values() {
     let xor_max = RiscVInstruction::Xor {
-        rd: 31,
-        rs1: 31,
-        rs2: 31,
+        rd: Register::X31,
+        rs1: Register::X31,
+        rs2: Register::X31,
     };
     assert_eq!(format!("{}", xor_max), "xor x31, x31, x31");
 }
@@ -33,9 +34,9 @@ fn max_values() {
 #[test]
 fn mixed_registers() {
     let xor_mixed = RiscVInstruction::Xor {
-        rd: 5,
-        rs1: 10,
-        rs2: 15,
+        rd: Register::X5,
+        rs1: Register::X10,
+        rs2: Register::X15,
     };
     assert_eq!(format!("{}", xor_mixed), "xor x5, x10, x15");
 }
@@ -43,9 +44,9 @@ fn mixed_registers() {
 #[test]
 fn same_registers() {
     let xor_same = RiscVInstruction::Xor {
-        rd: 7,
-        rs1: 7,
-        rs2: 7,
+        rd: Register::X7,
+        rs1: Register::X7,
+        rs2: Register::X7,
     };
     assert_eq!(format!("{}", xor_same), "xor x7, x7, x7");
 }
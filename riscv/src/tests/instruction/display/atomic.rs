@@ -0,0 +1,166 @@
+use crate::instruction::RiscVInstruction;
+use crate::register::{Register, Syntax};
+
+const RD: Register = Register::X10;
+const RS1: Register = Register::X11;
+const RS2: Register = Register::X12;
+
+#[test]
+fn load_reserved() {
+    let lr = RiscVInstruction::LrW {
+        rd: RD,
+        rs1: RS1,
+        aq: false,
+        rl: false,
+    };
+    assert_eq!(format!("{}", lr), "lr.w x10, (x11)");
+}
+
+#[test]
+fn memory_operations() {
+    let cases = [
+        (
+            RiscVInstruction::ScW {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+                aq: false,
+                rl: false,
+            },
+            "sc.w x10, x12, (x11)",
+        ),
+        (
+            RiscVInstruction::AmoswapW {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+                aq: false,
+                rl: false,
+            },
+            "amoswap.w x10, x12, (x11)",
+        ),
+        (
+            RiscVInstruction::AmoaddW {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+                aq: false,
+                rl: false,
+            },
+            "amoadd.w x10, x12, (x11)",
+        ),
+        (
+            RiscVInstruction::AmoxorW {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+                aq: false,
+                rl: false,
+            },
+            "amoxor.w x10, x12, (x11)",
+        ),
+        (
+            RiscVInstruction::AmoandW {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+                aq: false,
+                rl: false,
+            },
+            "amoand.w x10, x12, (x11)",
+        ),
+        (
+            RiscVInstruction::AmoorW {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+                aq: false,
+                rl: false,
+            },
+            "amoor.w x10, x12, (x11)",
+        ),
+        (
+            RiscVInstruction::AmominW {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+                aq: false,
+                rl: false,
+            },
+            "amomin.w x10, x12, (x11)",
+        ),
+        (
+            RiscVInstruction::AmomaxW {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+                aq: false,
+                rl: false,
+            },
+            "amomax.w x10, x12, (x11)",
+        ),
+        (
+            RiscVInstruction::AmominuW {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+                aq: false,
+                rl: false,
+            },
+            "amominu.w x10, x12, (x11)",
+        ),
+        (
+            RiscVInstruction::AmomaxuW {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+                aq: false,
+                rl: false,
+            },
+            "amomaxu.w x10, x12, (x11)",
+        ),
+    ];
+
+    for (instruction, text) in cases {
+        assert_eq!(format!("{}", instruction), text);
+    }
+}
+
+#[test]
+fn ordering_suffixes() {
+    let amoadd = |aq, rl| RiscVInstruction::AmoaddW {
+        rd: RD,
+        rs1: RS1,
+        rs2: RS2,
+        aq,
+        rl,
+    };
+
+    assert_eq!(
+        format!("{}", amoadd(true, false)),
+        "amoadd.w.aq x10, x12, (x11)"
+    );
+    assert_eq!(
+        format!("{}", amoadd(false, true)),
+        "amoadd.w.rl x10, x12, (x11)"
+    );
+    assert_eq!(
+        format!("{}", amoadd(true, true)),
+        "amoadd.w.aqrl x10, x12, (x11)"
+    );
+}
+
+#[test]
+fn abi_syntax() {
+    let sc = RiscVInstruction::ScW {
+        rd: RD,
+        rs1: RS1,
+        rs2: RS2,
+        aq: true,
+        rl: true,
+    };
+    assert_eq!(
+        format!("{}", sc.display(Syntax::Abi)),
+        "sc.w.aqrl a0, a2, (a1)"
+    );
+}
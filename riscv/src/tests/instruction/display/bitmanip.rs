@@ -0,0 +1,240 @@
+use crate::instruction::RiscVInstruction;
+use crate::register::{Register, Syntax};
+
+const RD: Register = Register::X10;
+const RS1: Register = Register::X11;
+const RS2: Register = Register::X12;
+
+#[test]
+fn zba() {
+    let cases = [
+        (
+            RiscVInstruction::Sh1add {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            "sh1add x10, x11, x12",
+        ),
+        (
+            RiscVInstruction::Sh2add {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            "sh2add x10, x11, x12",
+        ),
+        (
+            RiscVInstruction::Sh3add {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            "sh3add x10, x11, x12",
+        ),
+    ];
+
+    for (instruction, text) in cases {
+        assert_eq!(format!("{}", instruction), text);
+    }
+}
+
+#[test]
+fn zbb() {
+    let cases = [
+        (
+            RiscVInstruction::Andn {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            "andn x10, x11, x12",
+        ),
+        (
+            RiscVInstruction::Orn {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            "orn x10, x11, x12",
+        ),
+        (
+            RiscVInstruction::Xnor {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            "xnor x10, x11, x12",
+        ),
+        (RiscVInstruction::Clz { rd: RD, rs1: RS1 }, "clz x10, x11"),
+        (RiscVInstruction::Ctz { rd: RD, rs1: RS1 }, "ctz x10, x11"),
+        (RiscVInstruction::Cpop { rd: RD, rs1: RS1 }, "cpop x10, x11"),
+        (
+            RiscVInstruction::Max {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            "max x10, x11, x12",
+        ),
+        (
+            RiscVInstruction::Maxu {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            "maxu x10, x11, x12",
+        ),
+        (
+            RiscVInstruction::Min {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            "min x10, x11, x12",
+        ),
+        (
+            RiscVInstruction::Minu {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            "minu x10, x11, x12",
+        ),
+        (
+            RiscVInstruction::SextB { rd: RD, rs1: RS1 },
+            "sext.b x10, x11",
+        ),
+        (
+            RiscVInstruction::SextH { rd: RD, rs1: RS1 },
+            "sext.h x10, x11",
+        ),
+        (
+            RiscVInstruction::ZextH { rd: RD, rs1: RS1 },
+            "zext.h x10, x11",
+        ),
+        (
+            RiscVInstruction::Rol {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            "rol x10, x11, x12",
+        ),
+        (
+            RiscVInstruction::Ror {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            "ror x10, x11, x12",
+        ),
+        (
+            RiscVInstruction::Rori {
+                rd: RD,
+                rs1: RS1,
+                imm: 31,
+            },
+            "rori x10, x11, 31",
+        ),
+        (
+            RiscVInstruction::OrcB { rd: RD, rs1: RS1 },
+            "orc.b x10, x11",
+        ),
+        (RiscVInstruction::Rev8 { rd: RD, rs1: RS1 }, "rev8 x10, x11"),
+    ];
+
+    for (instruction, text) in cases {
+        assert_eq!(format!("{}", instruction), text);
+    }
+}
+
+#[test]
+fn zbs() {
+    let cases = [
+        (
+            RiscVInstruction::Bclr {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            "bclr x10, x11, x12",
+        ),
+        (
+            RiscVInstruction::Bclri {
+                rd: RD,
+                rs1: RS1,
+                imm: 0,
+            },
+            "bclri x10, x11, 0",
+        ),
+        (
+            RiscVInstruction::Bext {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            "bext x10, x11, x12",
+        ),
+        (
+            RiscVInstruction::Bexti {
+                rd: RD,
+                rs1: RS1,
+                imm: 1,
+            },
+            "bexti x10, x11, 1",
+        ),
+        (
+            RiscVInstruction::Binv {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            "binv x10, x11, x12",
+        ),
+        (
+            RiscVInstruction::Binvi {
+                rd: RD,
+                rs1: RS1,
+                imm: 16,
+            },
+            "binvi x10, x11, 16",
+        ),
+        (
+            RiscVInstruction::Bset {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            "bset x10, x11, x12",
+        ),
+        (
+            RiscVInstruction::Bseti {
+                rd: RD,
+                rs1: RS1,
+                imm: 31,
+            },
+            "bseti x10, x11, 31",
+        ),
+    ];
+
+    for (instruction, text) in cases {
+        assert_eq!(format!("{}", instruction), text);
+    }
+}
+
+#[test]
+fn abi_syntax() {
+    let andn = RiscVInstruction::Andn {
+        rd: RD,
+        rs1: RS1,
+        rs2: RS2,
+    };
+    assert_eq!(andn.display(Syntax::Abi).to_string(), "andn a0, a1, a2");
+
+    let rev8 = RiscVInstruction::Rev8 {
+        rd: Register::X1,
+        rs1: Register::X2,
+    };
+    assert_eq!(rev8.display(Syntax::Abi).to_string(), "rev8 ra, sp");
+}
@@ -0,0 +1,52 @@
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn positive_offset() {
+    let beq = RiscVInstruction::Beq {
+        rs1: Register::X1,
+        rs2: Register::X2,
+        imm: 8,
+    };
+    assert_eq!(format!("{}", beq), "beq x1, x2, 8");
+}
+
+#[test]
+fn negative_offset() {
+    let beq = RiscVInstruction::Beq {
+        rs1: Register::X1,
+        rs2: Register::X2,
+        imm: -8,
+    };
+    assert_eq!(format!("{}", beq), "beq x1, x2, -8");
+}
+
+#[test]
+fn zero_offset() {
+    let beq = RiscVInstruction::Beq {
+        rs1: Register::X31,
+        rs2: Register::X0,
+        imm: 0,
+    };
+    assert_eq!(format!("{}", beq), "beq x31, x0, 0");
+}
+
+#[test]
+fn min_values() {
+    let beq_min = RiscVInstruction::Beq {
+        rs1: Register::X0,
+        rs2: Register::X0,
+        imm: -4096,
+    };
+    assert_eq!(format!("{}", beq_min), "beq x0, x0, -4096");
+}
+
+#[test]
+fn max_values() {
+    let beq_max = RiscVInstruction::Beq {
+        rs1: Register::X31,
+        rs2: Register::X31,
+        imm: 4094,
+    };
+    assert_eq!(format!("{}", beq_max), "beq x31, x31, 4094");
+}
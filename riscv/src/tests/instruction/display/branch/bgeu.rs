@@ -0,0 +1,52 @@
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn positive_offset() {
+    let bgeu = RiscVInstruction::Bgeu {
+        rs1: Register::X1,
+        rs2: Register::X2,
+        imm: 8,
+    };
+    assert_eq!(format!("{}", bgeu), "bgeu x1, x2, 8");
+}
+
+#[test]
+fn negative_offset() {
+    let bgeu = RiscVInstruction::Bgeu {
+        rs1: Register::X1,
+        rs2: Register::X2,
+        imm: -8,
+    };
+    assert_eq!(format!("{}", bgeu), "bgeu x1, x2, -8");
+}
+
+#[test]
+fn zero_offset() {
+    let bgeu = RiscVInstruction::Bgeu {
+        rs1: Register::X31,
+        rs2: Register::X0,
+        imm: 0,
+    };
+    assert_eq!(format!("{}", bgeu), "bgeu x31, x0, 0");
+}
+
+#[test]
+fn min_values() {
+    let bgeu_min = RiscVInstruction::Bgeu {
+        rs1: Register::X0,
+        rs2: Register::X0,
+        imm: -4096,
+    };
+    assert_eq!(format!("{}", bgeu_min), "bgeu x0, x0, -4096");
+}
+
+#[test]
+fn max_values() {
+    let bgeu_max = RiscVInstruction::Bgeu {
+        rs1: Register::X31,
+        rs2: Register::X31,
+        imm: 4094,
+    };
+    assert_eq!(format!("{}", bgeu_max), "bgeu x31, x31, 4094");
+}
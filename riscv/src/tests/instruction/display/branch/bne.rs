@@ -0,0 +1,52 @@
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn positive_offset() {
+    let bne = RiscVInstruction::Bne {
+        rs1: Register::X1,
+        rs2: Register::X2,
+        imm: 8,
+    };
+    assert_eq!(format!("{}", bne), "bne x1, x2, 8");
+}
+
+#[test]
+fn negative_offset() {
+    let bne = RiscVInstruction::Bne {
+        rs1: Register::X1,
+        rs2: Register::X2,
+        imm: -8,
+    };
+    assert_eq!(format!("{}", bne), "bne x1, x2, -8");
+}
+
+#[test]
+fn zero_offset() {
+    let bne = RiscVInstruction::Bne {
+        rs1: Register::X31,
+        rs2: Register::X0,
+        imm: 0,
+    };
+    assert_eq!(format!("{}", bne), "bne x31, x0, 0");
+}
+
+#[test]
+fn min_values() {
+    let bne_min = RiscVInstruction::Bne {
+        rs1: Register::X0,
+        rs2: Register::X0,
+        imm: -4096,
+    };
+    assert_eq!(format!("{}", bne_min), "bne x0, x0, -4096");
+}
+
+#[test]
+fn max_values() {
+    let bne_max = RiscVInstruction::Bne {
+        rs1: Register::X31,
+        rs2: Register::X31,
+        imm: 4094,
+    };
+    assert_eq!(format!("{}", bne_max), "bne x31, x31, 4094");
+}
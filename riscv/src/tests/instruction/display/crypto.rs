@@ -0,0 +1,96 @@
+use crate::instruction::RiscVInstruction;
+use crate::register::{Register, Syntax};
+
+const RD: Register = Register::X10;
+const RS1: Register = Register::X11;
+const RS2: Register = Register::X12;
+
+#[test]
+fn sha256() {
+    let cases = [
+        (
+            RiscVInstruction::Sha256sig0 { rd: RD, rs1: RS1 },
+            "sha256sig0 x10, x11",
+        ),
+        (
+            RiscVInstruction::Sha256sig1 { rd: RD, rs1: RS1 },
+            "sha256sig1 x10, x11",
+        ),
+        (
+            RiscVInstruction::Sha256sum0 { rd: RD, rs1: RS1 },
+            "sha256sum0 x10, x11",
+        ),
+        (
+            RiscVInstruction::Sha256sum1 { rd: RD, rs1: RS1 },
+            "sha256sum1 x10, x11",
+        ),
+    ];
+
+    for (instruction, text) in cases {
+        assert_eq!(format!("{}", instruction), text);
+    }
+}
+
+#[test]
+fn sha512() {
+    let cases = [
+        (
+            RiscVInstruction::Sha512sum0r {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            "sha512sum0r x10, x11, x12",
+        ),
+        (
+            RiscVInstruction::Sha512sum1r {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            "sha512sum1r x10, x11, x12",
+        ),
+        (
+            RiscVInstruction::Sha512sig0l {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            "sha512sig0l x10, x11, x12",
+        ),
+        (
+            RiscVInstruction::Sha512sig0h {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            "sha512sig0h x10, x11, x12",
+        ),
+        (
+            RiscVInstruction::Sha512sig1l {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            "sha512sig1l x10, x11, x12",
+        ),
+        (
+            RiscVInstruction::Sha512sig1h {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            "sha512sig1h x10, x11, x12",
+        ),
+    ];
+
+    for (instruction, text) in cases {
+        assert_eq!(format!("{}", instruction), text);
+    }
+}
+
+#[test]
+fn abi_syntax() {
+    let sum0 = RiscVInstruction::Sha256sum0 { rd: RD, rs1: RS1 };
+    assert_eq!(sum0.display(Syntax::Abi).to_string(), "sha256sum0 a0, a1");
+}
@@ -0,0 +1,34 @@
+use crate::instruction::RiscVInstruction;
+
+#[test]
+fn fence_sets() {
+    let fence = RiscVInstruction::Fence {
+        pred: 0xf,
+        succ: 0xf,
+    };
+    assert_eq!(format!("{}", fence), "fence iorw, iorw");
+
+    let fence = RiscVInstruction::Fence {
+        pred: 0x3,
+        succ: 0x1,
+    };
+    assert_eq!(format!("{}", fence), "fence rw, w");
+
+    let fence = RiscVInstruction::Fence {
+        pred: 0xa,
+        succ: 0x4,
+    };
+    assert_eq!(format!("{}", fence), "fence ir, o");
+}
+
+#[test]
+fn empty_set() {
+    let fence = RiscVInstruction::Fence { pred: 0, succ: 0x2 };
+    assert_eq!(format!("{}", fence), "fence 0, r");
+}
+
+#[test]
+fn fence_tso_and_fence_i() {
+    assert_eq!(format!("{}", RiscVInstruction::FenceTso), "fence.tso");
+    assert_eq!(format!("{}", RiscVInstruction::FenceI), "fence.i");
+}
@@ -0,0 +1,483 @@
+use crate::float::RoundingMode;
+use crate::instruction::RiscVInstruction;
+use crate::register::{FloatRegister, Register, Syntax};
+
+const RD: Register = Register::X10;
+const RS1: Register = Register::X11;
+const FD: FloatRegister = FloatRegister::F10;
+const FS1: FloatRegister = FloatRegister::F11;
+const FS2: FloatRegister = FloatRegister::F12;
+const FS3: FloatRegister = FloatRegister::F13;
+
+#[test]
+fn every_instruction() {
+    let cases = [
+        (
+            RiscVInstruction::Flw {
+                rd: FD,
+                rs1: RS1,
+                imm: 8,
+            },
+            "flw f10, 8(x11)",
+        ),
+        (
+            RiscVInstruction::Fsw {
+                rs1: RS1,
+                rs2: FS2,
+                imm: 8,
+            },
+            "fsw f12, 8(x11)",
+        ),
+        (
+            RiscVInstruction::Fld {
+                rd: FD,
+                rs1: RS1,
+                imm: 8,
+            },
+            "fld f10, 8(x11)",
+        ),
+        (
+            RiscVInstruction::Fsd {
+                rs1: RS1,
+                rs2: FS2,
+                imm: 8,
+            },
+            "fsd f12, 8(x11)",
+        ),
+        (
+            RiscVInstruction::FmaddS {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+                rs3: FS3,
+                rm: RoundingMode::Dynamic,
+            },
+            "fmadd.s f10, f11, f12, f13",
+        ),
+        (
+            RiscVInstruction::FmsubS {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+                rs3: FS3,
+                rm: RoundingMode::Dynamic,
+            },
+            "fmsub.s f10, f11, f12, f13",
+        ),
+        (
+            RiscVInstruction::FnmsubS {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+                rs3: FS3,
+                rm: RoundingMode::Dynamic,
+            },
+            "fnmsub.s f10, f11, f12, f13",
+        ),
+        (
+            RiscVInstruction::FnmaddS {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+                rs3: FS3,
+                rm: RoundingMode::Dynamic,
+            },
+            "fnmadd.s f10, f11, f12, f13",
+        ),
+        (
+            RiscVInstruction::FaddS {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+                rm: RoundingMode::Dynamic,
+            },
+            "fadd.s f10, f11, f12",
+        ),
+        (
+            RiscVInstruction::FsubS {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+                rm: RoundingMode::Dynamic,
+            },
+            "fsub.s f10, f11, f12",
+        ),
+        (
+            RiscVInstruction::FmulS {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+                rm: RoundingMode::Dynamic,
+            },
+            "fmul.s f10, f11, f12",
+        ),
+        (
+            RiscVInstruction::FdivS {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+                rm: RoundingMode::Dynamic,
+            },
+            "fdiv.s f10, f11, f12",
+        ),
+        (
+            RiscVInstruction::FsqrtS {
+                rd: FD,
+                rs1: FS1,
+                rm: RoundingMode::Dynamic,
+            },
+            "fsqrt.s f10, f11",
+        ),
+        (
+            RiscVInstruction::FsgnjS {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+            },
+            "fsgnj.s f10, f11, f12",
+        ),
+        (
+            RiscVInstruction::FsgnjnS {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+            },
+            "fsgnjn.s f10, f11, f12",
+        ),
+        (
+            RiscVInstruction::FsgnjxS {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+            },
+            "fsgnjx.s f10, f11, f12",
+        ),
+        (
+            RiscVInstruction::FminS {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+            },
+            "fmin.s f10, f11, f12",
+        ),
+        (
+            RiscVInstruction::FmaxS {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+            },
+            "fmax.s f10, f11, f12",
+        ),
+        (
+            RiscVInstruction::FeqS {
+                rd: RD,
+                rs1: FS1,
+                rs2: FS2,
+            },
+            "feq.s x10, f11, f12",
+        ),
+        (
+            RiscVInstruction::FltS {
+                rd: RD,
+                rs1: FS1,
+                rs2: FS2,
+            },
+            "flt.s x10, f11, f12",
+        ),
+        (
+            RiscVInstruction::FleS {
+                rd: RD,
+                rs1: FS1,
+                rs2: FS2,
+            },
+            "fle.s x10, f11, f12",
+        ),
+        (
+            RiscVInstruction::FcvtWS {
+                rd: RD,
+                rs1: FS1,
+                rm: RoundingMode::Dynamic,
+            },
+            "fcvt.w.s x10, f11",
+        ),
+        (
+            RiscVInstruction::FcvtWuS {
+                rd: RD,
+                rs1: FS1,
+                rm: RoundingMode::Dynamic,
+            },
+            "fcvt.wu.s x10, f11",
+        ),
+        (
+            RiscVInstruction::FmvXW { rd: RD, rs1: FS1 },
+            "fmv.x.w x10, f11",
+        ),
+        (
+            RiscVInstruction::FclassS { rd: RD, rs1: FS1 },
+            "fclass.s x10, f11",
+        ),
+        (
+            RiscVInstruction::FcvtSW {
+                rd: FD,
+                rs1: RS1,
+                rm: RoundingMode::Dynamic,
+            },
+            "fcvt.s.w f10, x11",
+        ),
+        (
+            RiscVInstruction::FcvtSWu {
+                rd: FD,
+                rs1: RS1,
+                rm: RoundingMode::Dynamic,
+            },
+            "fcvt.s.wu f10, x11",
+        ),
+        (
+            RiscVInstruction::FmvWX { rd: FD, rs1: RS1 },
+            "fmv.w.x f10, x11",
+        ),
+        (
+            RiscVInstruction::FmaddD {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+                rs3: FS3,
+                rm: RoundingMode::Dynamic,
+            },
+            "fmadd.d f10, f11, f12, f13",
+        ),
+        (
+            RiscVInstruction::FmsubD {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+                rs3: FS3,
+                rm: RoundingMode::Dynamic,
+            },
+            "fmsub.d f10, f11, f12, f13",
+        ),
+        (
+            RiscVInstruction::FnmsubD {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+                rs3: FS3,
+                rm: RoundingMode::Dynamic,
+            },
+            "fnmsub.d f10, f11, f12, f13",
+        ),
+        (
+            RiscVInstruction::FnmaddD {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+                rs3: FS3,
+                rm: RoundingMode::Dynamic,
+            },
+            "fnmadd.d f10, f11, f12, f13",
+        ),
+        (
+            RiscVInstruction::FaddD {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+                rm: RoundingMode::Dynamic,
+            },
+            "fadd.d f10, f11, f12",
+        ),
+        (
+            RiscVInstruction::FsubD {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+                rm: RoundingMode::Dynamic,
+            },
+            "fsub.d f10, f11, f12",
+        ),
+        (
+            RiscVInstruction::FmulD {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+                rm: RoundingMode::Dynamic,
+            },
+            "fmul.d f10, f11, f12",
+        ),
+        (
+            RiscVInstruction::FdivD {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+                rm: RoundingMode::Dynamic,
+            },
+            "fdiv.d f10, f11, f12",
+        ),
+        (
+            RiscVInstruction::FsqrtD {
+                rd: FD,
+                rs1: FS1,
+                rm: RoundingMode::Dynamic,
+            },
+            "fsqrt.d f10, f11",
+        ),
+        (
+            RiscVInstruction::FsgnjD {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+            },
+            "fsgnj.d f10, f11, f12",
+        ),
+        (
+            RiscVInstruction::FsgnjnD {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+            },
+            "fsgnjn.d f10, f11, f12",
+        ),
+        (
+            RiscVInstruction::FsgnjxD {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+            },
+            "fsgnjx.d f10, f11, f12",
+        ),
+        (
+            RiscVInstruction::FminD {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+            },
+            "fmin.d f10, f11, f12",
+        ),
+        (
+            RiscVInstruction::FmaxD {
+                rd: FD,
+                rs1: FS1,
+                rs2: FS2,
+            },
+            "fmax.d f10, f11, f12",
+        ),
+        (
+            RiscVInstruction::FeqD {
+                rd: RD,
+                rs1: FS1,
+                rs2: FS2,
+            },
+            "feq.d x10, f11, f12",
+        ),
+        (
+            RiscVInstruction::FltD {
+                rd: RD,
+                rs1: FS1,
+                rs2: FS2,
+            },
+            "flt.d x10, f11, f12",
+        ),
+        (
+            RiscVInstruction::FleD {
+                rd: RD,
+                rs1: FS1,
+                rs2: FS2,
+            },
+            "fle.d x10, f11, f12",
+        ),
+        (
+            RiscVInstruction::FcvtWD {
+                rd: RD,
+                rs1: FS1,
+                rm: RoundingMode::Dynamic,
+            },
+            "fcvt.w.d x10, f11",
+        ),
+        (
+            RiscVInstruction::FcvtWuD {
+                rd: RD,
+                rs1: FS1,
+                rm: RoundingMode::Dynamic,
+            },
+            "fcvt.wu.d x10, f11",
+        ),
+        (
+            RiscVInstruction::FclassD { rd: RD, rs1: FS1 },
+            "fclass.d x10, f11",
+        ),
+        (
+            RiscVInstruction::FcvtDW {
+                rd: FD,
+                rs1: RS1,
+                rm: RoundingMode::Dynamic,
+            },
+            "fcvt.d.w f10, x11",
+        ),
+        (
+            RiscVInstruction::FcvtDWu {
+                rd: FD,
+                rs1: RS1,
+                rm: RoundingMode::Dynamic,
+            },
+            "fcvt.d.wu f10, x11",
+        ),
+        (
+            RiscVInstruction::FcvtSD {
+                rd: FD,
+                rs1: FS1,
+                rm: RoundingMode::Dynamic,
+            },
+            "fcvt.s.d f10, f11",
+        ),
+        (
+            RiscVInstruction::FcvtDS {
+                rd: FD,
+                rs1: FS1,
+                rm: RoundingMode::Dynamic,
+            },
+            "fcvt.d.s f10, f11",
+        ),
+    ];
+
+    for (instruction, expected) in cases {
+        assert_eq!(format!("{}", instruction), expected);
+    }
+}
+
+#[test]
+fn rounding_mode() {
+    let fcvt_w_s = RiscVInstruction::FcvtWS {
+        rd: RD,
+        rs1: FS1,
+        rm: RoundingMode::TowardZero,
+    };
+    assert_eq!(format!("{}", fcvt_w_s), "fcvt.w.s x10, f11, rtz");
+
+    let fmadd_d = RiscVInstruction::FmaddD {
+        rd: FD,
+        rs1: FS1,
+        rs2: FS2,
+        rs3: FS3,
+        rm: RoundingMode::NearestMaxMagnitude,
+    };
+    assert_eq!(format!("{}", fmadd_d), "fmadd.d f10, f11, f12, f13, rmm");
+}
+
+#[test]
+fn abi_names() {
+    let fsw = RiscVInstruction::Fsw {
+        rs1: Register::SP,
+        rs2: FloatRegister::FS0,
+        imm: -4,
+    };
+    assert_eq!(fsw.display(Syntax::Abi).to_string(), "fsw fs0, -4(sp)");
+
+    let feq_d = RiscVInstruction::FeqD {
+        rd: RD,
+        rs1: FloatRegister::FA0,
+        rs2: FloatRegister::FT11,
+    };
+    assert_eq!(
+        feq_d.display(Syntax::Abi).to_string(),
+        "feq.d a0, fa0, ft11"
+    );
+}
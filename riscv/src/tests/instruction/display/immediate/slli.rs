@@ -1,10 +1,11 @@
 use crate::instruction::RiscVInstruction;
+use crate::register::Register;
 
 #[test]
 fn positive_immediate() {
     let slli = RiscVInstruction::Slli {
-        rd: 1,
-        rs1: 2,
+        rd: Register::X1,
+        rs1: Register::X2,
         imm: 5,
     };
     assert_eq!(format!("{}", slli), "slli x1, x2, 5");
@@ -13,8 +14,8 @@ fn positive_immediate() {
 #[test]
 fn zero_immediate() {
     let slli = RiscVInstruction::Slli {
-        rd: 31,
-        rs1: 0,
+        rd: Register::X31,
+        rs1: Register::X0,
         imm: 0,
     };
     assert_eq!(format!("{}", slli), "slli x31, x0, 0");
@@ -23,8 +24,8 @@ fn zero_immediate() {
 #[test]
 fn min_values() {
     let slli_min = RiscVInstruction::Slli {
-        rd: 0,
-        rs1: 0,
+        rd: Register::X0,
+        rs1: Register::X0,
         imm: 0,
     };
     assert_eq!(format!("{}", slli_min), "slli x0, x0, 0");
@@ -33,8 +34,8 @@ fn min_values() {
 #[test]
 fn max_values() {
     let slli_max = RiscVInstruction::Slli {
-        rd: 31,
-        rs1: 31,
+        rd: Register::X31,
+        rs1: Register::X31,
         imm: 31,
     };
     assert_eq!(format!("{}", slli_max), "slli x31, x31, 31");
@@ -1,10 +1,11 @@
 use crate::instruction::RiscVInstruction;
+use crate::register::Register;
 
 #[test]
 fn positive_immediate() {
     let srai = RiscVInstruction::Srai {
-        rd: 1,
-        rs1: 2,
+        rd: Register::X1,
+        rs1: Register::X2,
         imm: 5,
     };
     assert_eq!(format!("{}", srai), "srai x1, x2, 5");
@@ -13,8 +14,8 @@ fn positive_immediate() {
 #[test]
 fn zero_immediate() {
     let srai = RiscVInstruction::Srai {
-        rd: 31,
-        rs1: 0,
+        rd: Register::X31,
+        rs1: Register::X0,
         imm: 0,
     };
     assert_eq!(format!("{}", srai), "srai x31, x0, 0");
@@ -23,8 +24,8 @@ fn zero_immediate() {
 #[test]
 fn min_values() {
     let srai_min = RiscVInstruction::Srai {
-        rd: 0,
-        rs1: 0,
+        rd: Register::X0,
+        rs1: Register::X0,
         imm: 0,
     };
     assert_eq!(format!("{}", srai_min), "srai x0, x0, 0");
@@ -33,8 +34,8 @@ fn min_values() {
 #[test]
 fn max_values() {
     let srai_max = RiscVInstruction::Srai {
-        rd: 31,
-        rs1: 31,
+        rd: Register::X31,
+        rs1: Register::X31,
         imm: 31,
     };
     assert_eq!(format!("{}", srai_max), "srai x31, x31, 31");
@@ -1,10 +1,11 @@
 use crate::instruction::RiscVInstruction;
+use crate::register::Register;
 
 #[test]
 fn positive_immediate() {
     let xori = RiscVInstruction::Xori {
-        rd: 1,
-        rs1: 2,
+        rd: Register::X1,
+        rs1: Register::X2,
         imm: 100,
     };
     assert_eq!(format!("{}", xori), "xori x1, x2, 100");
@@ -13,8 +14,8 @@ fn positive_immediate() {
 #[test]
 fn negative_immediate() {
     let xori = RiscVInstruction::Xori {
-        rd: 0,
-        rs1: 1,
+        rd: Register::X0,
+        rs1: Register::X1,
         imm: -4,
     };
     assert_eq!(format!("{}", xori), "xori x0, x1, -4");
@@ -23,8 +24,8 @@ fn negative_immediate() {
 #[test]
 fn zero_immediate() {
     let xori = RiscVInstruction::Xori {
-        rd: 31,
-        rs1: 0,
+        rd: Register::X31,
+        rs1: Register::X0,
         imm: 0,
     };
     assert_eq!(format!("{}", xori), "xori x31, x0, 0");
@@ -33,8 +34,8 @@ fn zero_immediate() {
 #[test]
 fn min_values() {
     let xori_min = RiscVInstruction::Xori {
-        rd: 0,
-        rs1: 0,
+        rd: Register::X0,
+        rs1: Register::X0,
         imm: -2048,
     };
     assert_eq!(format!("{}", xori_min), "xori x0, x0, -2048");
@@ -43,8 +44,8 @@ fn min_values() {
 #[test]
 fn max_values() {
     let xori_max = RiscVInstruction::Xori {
-        rd: 31,
-        rs1: 31,
+        rd: Register::X31,
+        rs1: Register::X31,
         imm: 2047,
     };
     assert_eq!(format!("{}", xori_max), "xori x31, x31, 2047");
@@ -0,0 +1,47 @@
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn positive_offset() {
+    let jal = RiscVInstruction::Jal {
+        rd: Register::X1,
+        imm: 2048,
+    };
+    assert_eq!(format!("{}", jal), "jal x1, 2048");
+}
+
+#[test]
+fn negative_offset() {
+    let jal = RiscVInstruction::Jal {
+        rd: Register::X0,
+        imm: -8,
+    };
+    assert_eq!(format!("{}", jal), "jal x0, -8");
+}
+
+#[test]
+fn zero_offset() {
+    let jal = RiscVInstruction::Jal {
+        rd: Register::X31,
+        imm: 0,
+    };
+    assert_eq!(format!("{}", jal), "jal x31, 0");
+}
+
+#[test]
+fn min_values() {
+    let jal_min = RiscVInstruction::Jal {
+        rd: Register::X0,
+        imm: -1048576,
+    };
+    assert_eq!(format!("{}", jal_min), "jal x0, -1048576");
+}
+
+#[test]
+fn max_values() {
+    let jal_max = RiscVInstruction::Jal {
+        rd: Register::X31,
+        imm: 1048574,
+    };
+    assert_eq!(format!("{}", jal_max), "jal x31, 1048574");
+}
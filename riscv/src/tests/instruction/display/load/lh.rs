@@ -1,10 +1,11 @@
 use crate::instruction::RiscVInstruction;
+use crate::register::Register;
 
 #[test]
 fn positive_immediate() {
     let lh = RiscVInstruction::Lh {
-        rd: 1,
-        rs1: 2,
+        rd: Register::X1,
+        rs1: Register::X2,
         imm: 100,
     };
     assert_eq!(format!("{}", lh), "lh x1, 100(x2)");
@@ -13,8 +14,8 @@ fn positive_immediate() {
 #[test]
 fn negative_immediate() {
     let lh = RiscVInstruction::Lh {
-        rd: 0,
-        rs1: 1,
+        rd: Register::X0,
+        rs1: Register::X1,
         imm: -4,
     };
     assert_eq!(format!("{}", lh), "lh x0, -4(x1)");
@@ -23,8 +24,8 @@ fn negative_immediate() {
 #[test]
 fn zero_immediate() {
     let lh = RiscVInstruction::Lh {
-        rd: 31,
-        rs1: 0,
+        rd: Register::X31,
+        rs1: Register::X0,
         imm: 0,
     };
     assert_eq!(format!("{}", lh), "lh x31, 0(x0)");
@@ -33,8 +34,8 @@ fn zero_immediate() {
 #[test]
 fn min_values() {
     let lh_min = RiscVInstruction::Lh {
-        rd: 0,
-        rs1: 0,
+        rd: Register::X0,
+        rs1: Register::X0,
         imm: -2048,
     };
     assert_eq!(format!("{}", lh_min), "lh x0, -2048(x0)");
@@ -43,8 +44,8 @@ fn min_values() {
 #[test]
 fn max_values() {
     let lh_max = RiscVInstruction::Lh {
-        rd: 31,
-        rs1: 31,
+        rd: Register::X31,
+        rs1: Register::X31,
         imm: 2047,
     };
     assert_eq!(format!("{}", lh_max), "lh x31, 2047(x31)");
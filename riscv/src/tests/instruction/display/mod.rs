@@ -1,6 +1,17 @@
 mod arithmetic;
+mod atomic;
+mod bitmanip;
+mod branch;
+mod crypto;
+mod fence;
+mod float;
 mod immediate;
 mod jump;
 mod load;
+mod multiply;
+mod rv64;
+mod store;
+mod syntax;
 mod system;
 mod unsupported;
+mod upper;
@@ -0,0 +1,52 @@
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn basic() {
+    let div = RiscVInstruction::Div {
+        rd: Register::X1,
+        rs1: Register::X2,
+        rs2: Register::X3,
+    };
+    assert_eq!(format!("{}", div), "div x1, x2, x3");
+}
+
+#[test]
+fn min_values() {
+    let div_min = RiscVInstruction::Div {
+        rd: Register::X0,
+        rs1: Register::X0,
+        rs2: Register::X0,
+    };
+    assert_eq!(format!("{}", div_min), "div x0, x0, x0");
+}
+
+#[test]
+fn max_values() {
+    let div_max = RiscVInstruction::Div {
+        rd: Register::X31,
+        rs1: Register::X31,
+        rs2: Register::X31,
+    };
+    assert_eq!(format!("{}", div_max), "div x31, x31, x31");
+}
+
+#[test]
+fn mixed_registers() {
+    let div_mixed = RiscVInstruction::Div {
+        rd: Register::X5,
+        rs1: Register::X10,
+        rs2: Register::X15,
+    };
+    assert_eq!(format!("{}", div_mixed), "div x5, x10, x15");
+}
+
+#[test]
+fn same_registers() {
+    let div_same = RiscVInstruction::Div {
+        rd: Register::X7,
+        rs1: Register::X7,
+        rs2: Register::X7,
+    };
+    assert_eq!(format!("{}", div_same), "div x7, x7, x7");
+}
@@ -0,0 +1,52 @@
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn basic() {
+    let divu = RiscVInstruction::Divu {
+        rd: Register::X1,
+        rs1: Register::X2,
+        rs2: Register::X3,
+    };
+    assert_eq!(format!("{}", divu), "divu x1, x2, x3");
+}
+
+#[test]
+fn min_values() {
+    let divu_min = RiscVInstruction::Divu {
+        rd: Register::X0,
+        rs1: Register::X0,
+        rs2: Register::X0,
+    };
+    assert_eq!(format!("{}", divu_min), "divu x0, x0, x0");
+}
+
+#[test]
+fn max_values() {
+    let divu_max = RiscVInstruction::Divu {
+        rd: Register::X31,
+        rs1: Register::X31,
+        rs2: Register::X31,
+    };
+    assert_eq!(format!("{}", divu_max), "divu x31, x31, x31");
+}
+
+#[test]
+fn mixed_registers() {
+    let divu_mixed = RiscVInstruction::Divu {
+        rd: Register::X5,
+        rs1: Register::X10,
+        rs2: Register::X15,
+    };
+    assert_eq!(format!("{}", divu_mixed), "divu x5, x10, x15");
+}
+
+#[test]
+fn same_registers() {
+    let divu_same = RiscVInstruction::Divu {
+        rd: Register::X7,
+        rs1: Register::X7,
+        rs2: Register::X7,
+    };
+    assert_eq!(format!("{}", divu_same), "divu x7, x7, x7");
+}
@@ -0,0 +1,52 @@
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn basic() {
+    let mul = RiscVInstruction::Mul {
+        rd: Register::X1,
+        rs1: Register::X2,
+        rs2: Register::X3,
+    };
+    assert_eq!(format!("{}", mul), "mul x1, x2, x3");
+}
+
+#[test]
+fn min_values() {
+    let mul_min = RiscVInstruction::Mul {
+        rd: Register::X0,
+        rs1: Register::X0,
+        rs2: Register::X0,
+    };
+    assert_eq!(format!("{}", mul_min), "mul x0, x0, x0");
+}
+
+#[test]
+fn max_values() {
+    let mul_max = RiscVInstruction::Mul {
+        rd: Register::X31,
+        rs1: Register::X31,
+        rs2: Register::X31,
+    };
+    assert_eq!(format!("{}", mul_max), "mul x31, x31, x31");
+}
+
+#[test]
+fn mixed_registers() {
+    let mul_mixed = RiscVInstruction::Mul {
+        rd: Register::X5,
+        rs1: Register::X10,
+        rs2: Register::X15,
+    };
+    assert_eq!(format!("{}", mul_mixed), "mul x5, x10, x15");
+}
+
+#[test]
+fn same_registers() {
+    let mul_same = RiscVInstruction::Mul {
+        rd: Register::X7,
+        rs1: Register::X7,
+        rs2: Register::X7,
+    };
+    assert_eq!(format!("{}", mul_same), "mul x7, x7, x7");
+}
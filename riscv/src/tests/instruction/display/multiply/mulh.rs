@@ -0,0 +1,52 @@
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn basic() {
+    let mulh = RiscVInstruction::Mulh {
+        rd: Register::X1,
+        rs1: Register::X2,
+        rs2: Register::X3,
+    };
+    assert_eq!(format!("{}", mulh), "mulh x1, x2, x3");
+}
+
+#[test]
+fn min_values() {
+    let mulh_min = RiscVInstruction::Mulh {
+        rd: Register::X0,
+        rs1: Register::X0,
+        rs2: Register::X0,
+    };
+    assert_eq!(format!("{}", mulh_min), "mulh x0, x0, x0");
+}
+
+#[test]
+fn max_values() {
+    let mulh_max = RiscVInstruction::Mulh {
+        rd: Register::X31,
+        rs1: Register::X31,
+        rs2: Register::X31,
+    };
+    assert_eq!(format!("{}", mulh_max), "mulh x31, x31, x31");
+}
+
+#[test]
+fn mixed_registers() {
+    let mulh_mixed = RiscVInstruction::Mulh {
+        rd: Register::X5,
+        rs1: Register::X10,
+        rs2: Register::X15,
+    };
+    assert_eq!(format!("{}", mulh_mixed), "mulh x5, x10, x15");
+}
+
+#[test]
+fn same_registers() {
+    let mulh_same = RiscVInstruction::Mulh {
+        rd: Register::X7,
+        rs1: Register::X7,
+        rs2: Register::X7,
+    };
+    assert_eq!(format!("{}", mulh_same), "mulh x7, x7, x7");
+}
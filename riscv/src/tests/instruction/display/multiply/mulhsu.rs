@@ -0,0 +1,52 @@
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn basic() {
+    let mulhsu = RiscVInstruction::Mulhsu {
+        rd: Register::X1,
+        rs1: Register::X2,
+        rs2: Register::X3,
+    };
+    assert_eq!(format!("{}", mulhsu), "mulhsu x1, x2, x3");
+}
+
+#[test]
+fn min_values() {
+    let mulhsu_min = RiscVInstruction::Mulhsu {
+        rd: Register::X0,
+        rs1: Register::X0,
+        rs2: Register::X0,
+    };
+    assert_eq!(format!("{}", mulhsu_min), "mulhsu x0, x0, x0");
+}
+
+#[test]
+fn max_values() {
+    let mulhsu_max = RiscVInstruction::Mulhsu {
+        rd: Register::X31,
+        rs1: Register::X31,
+        rs2: Register::X31,
+    };
+    assert_eq!(format!("{}", mulhsu_max), "mulhsu x31, x31, x31");
+}
+
+#[test]
+fn mixed_registers() {
+    let mulhsu_mixed = RiscVInstruction::Mulhsu {
+        rd: Register::X5,
+        rs1: Register::X10,
+        rs2: Register::X15,
+    };
+    assert_eq!(format!("{}", mulhsu_mixed), "mulhsu x5, x10, x15");
+}
+
+#[test]
+fn same_registers() {
+    let mulhsu_same = RiscVInstruction::Mulhsu {
+        rd: Register::X7,
+        rs1: Register::X7,
+        rs2: Register::X7,
+    };
+    assert_eq!(format!("{}", mulhsu_same), "mulhsu x7, x7, x7");
+}
@@ -0,0 +1,52 @@
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn basic() {
+    let mulhu = RiscVInstruction::Mulhu {
+        rd: Register::X1,
+        rs1: Register::X2,
+        rs2: Register::X3,
+    };
+    assert_eq!(format!("{}", mulhu), "mulhu x1, x2, x3");
+}
+
+#[test]
+fn min_values() {
+    let mulhu_min = RiscVInstruction::Mulhu {
+        rd: Register::X0,
+        rs1: Register::X0,
+        rs2: Register::X0,
+    };
+    assert_eq!(format!("{}", mulhu_min), "mulhu x0, x0, x0");
+}
+
+#[test]
+fn max_values() {
+    let mulhu_max = RiscVInstruction::Mulhu {
+        rd: Register::X31,
+        rs1: Register::X31,
+        rs2: Register::X31,
+    };
+    assert_eq!(format!("{}", mulhu_max), "mulhu x31, x31, x31");
+}
+
+#[test]
+fn mixed_registers() {
+    let mulhu_mixed = RiscVInstruction::Mulhu {
+        rd: Register::X5,
+        rs1: Register::X10,
+        rs2: Register::X15,
+    };
+    assert_eq!(format!("{}", mulhu_mixed), "mulhu x5, x10, x15");
+}
+
+#[test]
+fn same_registers() {
+    let mulhu_same = RiscVInstruction::Mulhu {
+        rd: Register::X7,
+        rs1: Register::X7,
+        rs2: Register::X7,
+    };
+    assert_eq!(format!("{}", mulhu_same), "mulhu x7, x7, x7");
+}
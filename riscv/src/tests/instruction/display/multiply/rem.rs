@@ -0,0 +1,52 @@
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn basic() {
+    let rem = RiscVInstruction::Rem {
+        rd: Register::X1,
+        rs1: Register::X2,
+        rs2: Register::X3,
+    };
+    assert_eq!(format!("{}", rem), "rem x1, x2, x3");
+}
+
+#[test]
+fn min_values() {
+    let rem_min = RiscVInstruction::Rem {
+        rd: Register::X0,
+        rs1: Register::X0,
+        rs2: Register::X0,
+    };
+    assert_eq!(format!("{}", rem_min), "rem x0, x0, x0");
+}
+
+#[test]
+fn max_values() {
+    let rem_max = RiscVInstruction::Rem {
+        rd: Register::X31,
+        rs1: Register::X31,
+        rs2: Register::X31,
+    };
+    assert_eq!(format!("{}", rem_max), "rem x31, x31, x31");
+}
+
+#[test]
+fn mixed_registers() {
+    let rem_mixed = RiscVInstruction::Rem {
+        rd: Register::X5,
+        rs1: Register::X10,
+        rs2: Register::X15,
+    };
+    assert_eq!(format!("{}", rem_mixed), "rem x5, x10, x15");
+}
+
+#[test]
+fn same_registers() {
+    let rem_same = RiscVInstruction::Rem {
+        rd: Register::X7,
+        rs1: Register::X7,
+        rs2: Register::X7,
+    };
+    assert_eq!(format!("{}", rem_same), "rem x7, x7, x7");
+}
@@ -0,0 +1,52 @@
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn basic() {
+    let remu = RiscVInstruction::Remu {
+        rd: Register::X1,
+        rs1: Register::X2,
+        rs2: Register::X3,
+    };
+    assert_eq!(format!("{}", remu), "remu x1, x2, x3");
+}
+
+#[test]
+fn min_values() {
+    let remu_min = RiscVInstruction::Remu {
+        rd: Register::X0,
+        rs1: Register::X0,
+        rs2: Register::X0,
+    };
+    assert_eq!(format!("{}", remu_min), "remu x0, x0, x0");
+}
+
+#[test]
+fn max_values() {
+    let remu_max = RiscVInstruction::Remu {
+        rd: Register::X31,
+        rs1: Register::X31,
+        rs2: Register::X31,
+    };
+    assert_eq!(format!("{}", remu_max), "remu x31, x31, x31");
+}
+
+#[test]
+fn mixed_registers() {
+    let remu_mixed = RiscVInstruction::Remu {
+        rd: Register::X5,
+        rs1: Register::X10,
+        rs2: Register::X15,
+    };
+    assert_eq!(format!("{}", remu_mixed), "remu x5, x10, x15");
+}
+
+#[test]
+fn same_registers() {
+    let remu_same = RiscVInstruction::Remu {
+        rd: Register::X7,
+        rs1: Register::X7,
+        rs2: Register::X7,
+    };
+    assert_eq!(format!("{}", remu_same), "remu x7, x7, x7");
+}
@@ -0,0 +1,169 @@
+use crate::instruction::RiscVInstruction;
+use crate::register::{Register, Syntax};
+
+const RD: Register = Register::X10;
+const RS1: Register = Register::X11;
+const RS2: Register = Register::X12;
+
+#[test]
+fn every_instruction() {
+    let cases = [
+        (
+            RiscVInstruction::Ld {
+                rd: RD,
+                rs1: RS1,
+                imm: -8,
+            },
+            "ld x10, -8(x11)",
+        ),
+        (
+            RiscVInstruction::Lwu {
+                rd: RD,
+                rs1: RS1,
+                imm: 4,
+            },
+            "lwu x10, 4(x11)",
+        ),
+        (
+            RiscVInstruction::Sd {
+                rs1: RS1,
+                rs2: RS2,
+                imm: 16,
+            },
+            "sd x12, 16(x11)",
+        ),
+        (
+            RiscVInstruction::Addiw {
+                rd: RD,
+                rs1: RS1,
+                imm: -1,
+            },
+            "addiw x10, x11, -1",
+        ),
+        (
+            RiscVInstruction::Slliw {
+                rd: RD,
+                rs1: RS1,
+                imm: 31,
+            },
+            "slliw x10, x11, 31",
+        ),
+        (
+            RiscVInstruction::Srliw {
+                rd: RD,
+                rs1: RS1,
+                imm: 1,
+            },
+            "srliw x10, x11, 1",
+        ),
+        (
+            RiscVInstruction::Sraiw {
+                rd: RD,
+                rs1: RS1,
+                imm: 4,
+            },
+            "sraiw x10, x11, 4",
+        ),
+        (
+            RiscVInstruction::Addw {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            "addw x10, x11, x12",
+        ),
+        (
+            RiscVInstruction::Subw {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            "subw x10, x11, x12",
+        ),
+        (
+            RiscVInstruction::Sllw {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            "sllw x10, x11, x12",
+        ),
+        (
+            RiscVInstruction::Srlw {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            "srlw x10, x11, x12",
+        ),
+        (
+            RiscVInstruction::Sraw {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            "sraw x10, x11, x12",
+        ),
+        (
+            RiscVInstruction::Mulw {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            "mulw x10, x11, x12",
+        ),
+        (
+            RiscVInstruction::Divw {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            "divw x10, x11, x12",
+        ),
+        (
+            RiscVInstruction::Divuw {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            "divuw x10, x11, x12",
+        ),
+        (
+            RiscVInstruction::Remw {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            "remw x10, x11, x12",
+        ),
+        (
+            RiscVInstruction::Remuw {
+                rd: RD,
+                rs1: RS1,
+                rs2: RS2,
+            },
+            "remuw x10, x11, x12",
+        ),
+    ];
+
+    for (instruction, expected) in cases {
+        assert_eq!(format!("{}", instruction), expected);
+    }
+}
+
+#[test]
+fn abi_names() {
+    let sd = RiscVInstruction::Sd {
+        rs1: Register::SP,
+        rs2: Register::RA,
+        imm: 8,
+    };
+    assert_eq!(format!("{}", sd.display(Syntax::Abi)), "sd ra, 8(sp)");
+
+    let slli = RiscVInstruction::Slli {
+        rd: RD,
+        rs1: RS1,
+        imm: 63,
+    };
+    assert_eq!(format!("{}", slli.display(Syntax::Abi)), "slli a0, a1, 63");
+}
@@ -0,0 +1,3 @@
+mod sb;
+mod sh;
+mod sw;
@@ -0,0 +1,52 @@
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn positive_immediate() {
+    let sb = RiscVInstruction::Sb {
+        rs1: Register::X2,
+        rs2: Register::X1,
+        imm: 100,
+    };
+    assert_eq!(format!("{}", sb), "sb x1, 100(x2)");
+}
+
+#[test]
+fn negative_immediate() {
+    let sb = RiscVInstruction::Sb {
+        rs1: Register::X2,
+        rs2: Register::X1,
+        imm: -4,
+    };
+    assert_eq!(format!("{}", sb), "sb x1, -4(x2)");
+}
+
+#[test]
+fn zero_immediate() {
+    let sb = RiscVInstruction::Sb {
+        rs1: Register::X0,
+        rs2: Register::X31,
+        imm: 0,
+    };
+    assert_eq!(format!("{}", sb), "sb x31, 0(x0)");
+}
+
+#[test]
+fn min_values() {
+    let sb_min = RiscVInstruction::Sb {
+        rs1: Register::X0,
+        rs2: Register::X0,
+        imm: -2048,
+    };
+    assert_eq!(format!("{}", sb_min), "sb x0, -2048(x0)");
+}
+
+#[test]
+fn max_values() {
+    let sb_max = RiscVInstruction::Sb {
+        rs1: Register::X31,
+        rs2: Register::X31,
+        imm: 2047,
+    };
+    assert_eq!(format!("{}", sb_max), "sb x31, 2047(x31)");
+}
@@ -0,0 +1,52 @@
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn positive_immediate() {
+    let sh = RiscVInstruction::Sh {
+        rs1: Register::X2,
+        rs2: Register::X1,
+        imm: 100,
+    };
+    assert_eq!(format!("{}", sh), "sh x1, 100(x2)");
+}
+
+#[test]
+fn negative_immediate() {
+    let sh = RiscVInstruction::Sh {
+        rs1: Register::X2,
+        rs2: Register::X1,
+        imm: -4,
+    };
+    assert_eq!(format!("{}", sh), "sh x1, -4(x2)");
+}
+
+#[test]
+fn zero_immediate() {
+    let sh = RiscVInstruction::Sh {
+        rs1: Register::X0,
+        rs2: Register::X31,
+        imm: 0,
+    };
+    assert_eq!(format!("{}", sh), "sh x31, 0(x0)");
+}
+
+#[test]
+fn min_values() {
+    let sh_min = RiscVInstruction::Sh {
+        rs1: Register::X0,
+        rs2: Register::X0,
+        imm: -2048,
+    };
+    assert_eq!(format!("{}", sh_min), "sh x0, -2048(x0)");
+}
+
+#[test]
+fn max_values() {
+    let sh_max = RiscVInstruction::Sh {
+        rs1: Register::X31,
+        rs2: Register::X31,
+        imm: 2047,
+    };
+    assert_eq!(format!("{}", sh_max), "sh x31, 2047(x31)");
+}
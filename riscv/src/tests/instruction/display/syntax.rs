@@ -0,0 +1,100 @@
+use crate::instruction::RiscVInstruction;
+use crate::register::{Register, Syntax};
+
+#[test]
+fn default_is_numeric() {
+    let addi = RiscVInstruction::Addi {
+        rd: Register::SP,
+        rs1: Register::SP,
+        imm: -16,
+    };
+    assert_eq!(format!("{}", addi), "addi x2, x2, -16");
+    assert_eq!(
+        format!("{}", addi.display(Syntax::Numeric)),
+        "addi x2, x2, -16"
+    );
+}
+
+#[test]
+fn abi_register_type() {
+    let add = RiscVInstruction::Add {
+        rd: Register::A0,
+        rs1: Register::A1,
+        rs2: Register::T0,
+    };
+    assert_eq!(format!("{}", add.display(Syntax::Abi)), "add a0, a1, t0");
+}
+
+#[test]
+fn abi_immediate_type() {
+    let addi = RiscVInstruction::Addi {
+        rd: Register::SP,
+        rs1: Register::SP,
+        imm: -16,
+    };
+    assert_eq!(format!("{}", addi.display(Syntax::Abi)), "addi sp, sp, -16");
+}
+
+#[test]
+fn abi_load_and_store() {
+    let lw = RiscVInstruction::Lw {
+        rd: Register::RA,
+        rs1: Register::SP,
+        imm: 12,
+    };
+    assert_eq!(format!("{}", lw.display(Syntax::Abi)), "lw ra, 12(sp)");
+
+    let sw = RiscVInstruction::Sw {
+        rs1: Register::S0,
+        rs2: Register::A0,
+        imm: -20,
+    };
+    assert_eq!(format!("{}", sw.display(Syntax::Abi)), "sw a0, -20(s0)");
+}
+
+#[test]
+fn abi_branch() {
+    let bne = RiscVInstruction::Bne {
+        rs1: Register::A5,
+        rs2: Register::ZERO,
+        imm: -8,
+    };
+    assert_eq!(format!("{}", bne.display(Syntax::Abi)), "bne a5, zero, -8");
+}
+
+#[test]
+fn abi_upper_and_jumps() {
+    let lui = RiscVInstruction::Lui {
+        rd: Register::A0,
+        imm: 0x12345000,
+    };
+    assert_eq!(format!("{}", lui.display(Syntax::Abi)), "lui a0, 0x12345");
+
+    let jal = RiscVInstruction::Jal {
+        rd: Register::RA,
+        imm: 16,
+    };
+    assert_eq!(format!("{}", jal.display(Syntax::Abi)), "jal ra, 16");
+
+    let jalr = RiscVInstruction::Jalr {
+        rd: Register::ZERO,
+        rs1: Register::RA,
+        imm: 0,
+    };
+    assert_eq!(format!("{}", jalr.display(Syntax::Abi)), "jalr zero, ra, 0");
+}
+
+#[test]
+fn abi_without_registers() {
+    assert_eq!(
+        format!("{}", RiscVInstruction::Ecall.display(Syntax::Abi)),
+        "ecall"
+    );
+}
+
+#[test]
+fn decoded_abi() {
+    // addi sp, sp, -32
+    let addi = RiscVInstruction::decode(0xfe010113).unwrap();
+    assert_eq!(format!("{}", addi.display(Syntax::Abi)), "addi sp, sp, -32");
+}
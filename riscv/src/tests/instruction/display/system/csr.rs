@@ -0,0 +1,66 @@
+use crate::instruction::RiscVInstruction;
+use crate::register::{Register, Syntax};
+
+#[test]
+fn counter_names() {
+    let csrrs = RiscVInstruction::Csrrs {
+        rd: Register::X10,
+        rs1: Register::X0,
+        csr: 0xc00,
+    };
+    assert_eq!(format!("{}", csrrs), "csrrs x10, cycle, x0");
+
+    let csrrc = RiscVInstruction::Csrrc {
+        rd: Register::X10,
+        rs1: Register::X11,
+        csr: 0xc82,
+    };
+    assert_eq!(format!("{}", csrrc), "csrrc x10, instreth, x11");
+}
+
+#[test]
+fn other_csrs_are_numbers() {
+    let csrrw = RiscVInstruction::Csrrw {
+        rd: Register::X1,
+        rs1: Register::X2,
+        csr: 0x340,
+    };
+    assert_eq!(format!("{}", csrrw), "csrrw x1, 0x340, x2");
+}
+
+#[test]
+fn immediates() {
+    let csrrwi = RiscVInstruction::Csrrwi {
+        rd: Register::X1,
+        imm: 31,
+        csr: 0xc01,
+    };
+    assert_eq!(format!("{}", csrrwi), "csrrwi x1, time, 31");
+
+    let csrrsi = RiscVInstruction::Csrrsi {
+        rd: Register::X1,
+        imm: 0,
+        csr: 0x7,
+    };
+    assert_eq!(format!("{}", csrrsi), "csrrsi x1, 0x7, 0");
+
+    let csrrci = RiscVInstruction::Csrrci {
+        rd: Register::X0,
+        imm: 4,
+        csr: 0xc80,
+    };
+    assert_eq!(format!("{}", csrrci), "csrrci x0, cycleh, 4");
+}
+
+#[test]
+fn abi_syntax() {
+    let csrrs = RiscVInstruction::Csrrs {
+        rd: Register::X10,
+        rs1: Register::X0,
+        csr: 0xc02,
+    };
+    assert_eq!(
+        csrrs.display(Syntax::Abi).to_string(),
+        "csrrs a0, instret, zero"
+    );
+}
@@ -1,13 +1,78 @@
+use crate::error::{DecodeError, DecodeErrorKind, Error};
+use crate::extension::Extension;
 use crate::instruction::RiscVInstruction;
+use crate::profile::Profile;
+use crate::register::Register;
 
 #[test]
-fn unsupported() {
-    let unsupported = RiscVInstruction::Unsupported(0x12345678);
-    assert_eq!(format!("{}", unsupported), "unsupported(0x12345678)");
+fn unknown_opcode() {
+    let error = RiscVInstruction::decode(0x12345678).unwrap_err();
+    assert_eq!(format!("{}", error), "unknown opcode (0x12345678)");
+}
+
+#[test]
+fn reserved_funct7() {
+    let error = RiscVInstruction::decode(0x203100b3).unwrap_err();
+    assert_eq!(format!("{}", error), "reserved funct7 (0x203100b3)");
+}
+
+#[test]
+fn unsupported_extension() {
+    // csrrw x0, 0, x0
+    let error = RiscVInstruction::decode(0x00001073).unwrap_err();
+    assert_eq!(
+        format!("{}", error),
+        "unsupported extension Zicsr (0x00001073)"
+    );
+}
 
-    let unsupported_zero = RiscVInstruction::Unsupported(0x00000000);
-    assert_eq!(format!("{}", unsupported_zero), "unsupported(0x00000000)");
+#[test]
+fn compressed() {
+    let error = RiscVInstruction::decode_compressed(0x0000).unwrap_err();
+    assert_eq!(
+        format!("{}", error),
+        "reserved compressed encoding (0x00000000)"
+    );
+}
 
-    let unsupported_max = RiscVInstruction::Unsupported(0xffffffff);
-    assert_eq!(format!("{}", unsupported_max), "unsupported(0xffffffff)");
+#[test]
+fn invalid_instruction() {
+    let error = Error::InvalidInstruction {
+        offset: 0x10,
+        error: DecodeError {
+            word: 0x00000057,
+            kind: DecodeErrorKind::UnsupportedExtension(Extension::V),
+        },
+    };
+    assert_eq!(
+        format!("{}", error),
+        "invalid instruction at offset 0x10: unsupported extension V (0x00000057)"
+    );
+}
+
+#[test]
+fn invalid_csr() {
+    let error = Error::InvalidCsr { csr: 0x340 };
+    assert_eq!(format!("{}", error), "invalid access to csr 0x340");
+}
+
+#[test]
+fn misaligned_atomic() {
+    let error = Error::MisalignedAtomic { address: 0x102 };
+    assert_eq!(format!("{}", error), "misaligned atomic access at 0x102");
+}
+
+#[test]
+fn unsupported_register() {
+    let error = DecodeError {
+        word: 0x00208833,
+        kind: DecodeErrorKind::UnsupportedRegister {
+            register: Register::X16,
+            profile: Profile::Rv32e,
+        },
+    };
+    assert_eq!(
+        format!("{}", error),
+        "register x16 is not available in RV32E (0x00208833)"
+    );
 }
@@ -0,0 +1,47 @@
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn basic() {
+    let auipc = RiscVInstruction::Auipc {
+        rd: Register::X1,
+        imm: 0x12345000,
+    };
+    assert_eq!(format!("{}", auipc), "auipc x1, 0x12345");
+}
+
+#[test]
+fn zero_immediate() {
+    let auipc = RiscVInstruction::Auipc {
+        rd: Register::X31,
+        imm: 0,
+    };
+    assert_eq!(format!("{}", auipc), "auipc x31, 0x0");
+}
+
+#[test]
+fn negative_immediate() {
+    let auipc = RiscVInstruction::Auipc {
+        rd: Register::X5,
+        imm: 0xfffff000_u32 as i32,
+    };
+    assert_eq!(format!("{}", auipc), "auipc x5, 0xfffff");
+}
+
+#[test]
+fn min_values() {
+    let auipc_min = RiscVInstruction::Auipc {
+        rd: Register::X0,
+        imm: 0x80000000_u32 as i32,
+    };
+    assert_eq!(format!("{}", auipc_min), "auipc x0, 0x80000");
+}
+
+#[test]
+fn max_values() {
+    let auipc_max = RiscVInstruction::Auipc {
+        rd: Register::X31,
+        imm: 0x7ffff000,
+    };
+    assert_eq!(format!("{}", auipc_max), "auipc x31, 0x7ffff");
+}
@@ -0,0 +1,47 @@
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn basic() {
+    let lui = RiscVInstruction::Lui {
+        rd: Register::X1,
+        imm: 0x12345000,
+    };
+    assert_eq!(format!("{}", lui), "lui x1, 0x12345");
+}
+
+#[test]
+fn zero_immediate() {
+    let lui = RiscVInstruction::Lui {
+        rd: Register::X31,
+        imm: 0,
+    };
+    assert_eq!(format!("{}", lui), "lui x31, 0x0");
+}
+
+#[test]
+fn negative_immediate() {
+    let lui = RiscVInstruction::Lui {
+        rd: Register::X5,
+        imm: 0xfffff000_u32 as i32,
+    };
+    assert_eq!(format!("{}", lui), "lui x5, 0xfffff");
+}
+
+#[test]
+fn min_values() {
+    let lui_min = RiscVInstruction::Lui {
+        rd: Register::X0,
+        imm: 0x80000000_u32 as i32,
+    };
+    assert_eq!(format!("{}", lui_min), "lui x0, 0x80000");
+}
+
+#[test]
+fn max_values() {
+    let lui_max = RiscVInstruction::Lui {
+        rd: Register::X31,
+        imm: 0x7ffff000,
+    };
+    assert_eq!(format!("{}", lui_max), "lui x31, 0x7ffff");
+}
@@ -0,0 +1,121 @@
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn known_encodings() {
+    let nop = RiscVInstruction::Addi {
+        rd: Register::X0,
+        rs1: Register::X0,
+        imm: 0,
+    };
+    assert_eq!(nop.encode_compressed(), Some(0x0001));
+
+    let ret = RiscVInstruction::Jalr {
+        rd: Register::X0,
+        rs1: Register::X1,
+        imm: 0,
+    };
+    assert_eq!(ret.encode_compressed(), Some(0x8082));
+
+    let mv = RiscVInstruction::Add {
+        rd: Register::X10,
+        rs1: Register::X0,
+        rs2: Register::X11,
+    };
+    assert_eq!(mv.encode_compressed(), Some(0x852e));
+
+    let addi16sp = RiscVInstruction::Addi {
+        rd: Register::X2,
+        rs1: Register::X2,
+        imm: -48,
+    };
+    assert_eq!(addi16sp.encode_compressed(), Some(0x7179));
+
+    let lwsp = RiscVInstruction::Lw {
+        rd: Register::X1,
+        rs1: Register::X2,
+        imm: 12,
+    };
+    assert_eq!(lwsp.encode_compressed(), Some(0x40b2));
+
+    let swsp = RiscVInstruction::Sw {
+        rs1: Register::X2,
+        rs2: Register::X1,
+        imm: 12,
+    };
+    assert_eq!(swsp.encode_compressed(), Some(0xc606));
+
+    assert_eq!(RiscVInstruction::Ebreak.encode_compressed(), Some(0x9002));
+}
+
+#[test]
+fn prefers_c_addi_for_small_stack_adjustments() {
+    let addi = RiscVInstruction::Addi {
+        rd: Register::X2,
+        rs1: Register::X2,
+        imm: -16,
+    };
+    assert_eq!(addi.encode_compressed(), Some(0x1141));
+}
+
+#[test]
+fn no_compressed_form() {
+    let registers_outside_x8_x15 = RiscVInstruction::Sub {
+        rd: Register::X1,
+        rs1: Register::X1,
+        rs2: Register::X2,
+    };
+    assert_eq!(registers_outside_x8_x15.encode_compressed(), None);
+
+    let three_operands = RiscVInstruction::Add {
+        rd: Register::X1,
+        rs1: Register::X2,
+        rs2: Register::X3,
+    };
+    assert_eq!(three_operands.encode_compressed(), None);
+
+    let immediate_too_large = RiscVInstruction::Addi {
+        rd: Register::X1,
+        rs1: Register::X1,
+        imm: 32,
+    };
+    assert_eq!(immediate_too_large.encode_compressed(), None);
+
+    let misaligned_load = RiscVInstruction::Lw {
+        rd: Register::X8,
+        rs1: Register::X9,
+        imm: 2,
+    };
+    assert_eq!(misaligned_load.encode_compressed(), None);
+
+    let jalr_with_offset = RiscVInstruction::Jalr {
+        rd: Register::X0,
+        rs1: Register::X1,
+        imm: 4,
+    };
+    assert_eq!(jalr_with_offset.encode_compressed(), None);
+
+    assert_eq!(RiscVInstruction::Ecall.encode_compressed(), None);
+}
+
+#[test]
+fn every_compressed_parcel() {
+    for half in 0..=u16::MAX {
+        if half & 0x3 == 0x3 {
+            continue;
+        }
+
+        let Ok(decoded) = RiscVInstruction::decode_compressed(half) else {
+            continue;
+        };
+
+        let encoded = decoded
+            .encode_compressed()
+            .unwrap_or_else(|| panic!("{} from 0x{:04x} has no compressed form", decoded, half));
+        assert_eq!(RiscVInstruction::decode_compressed(encoded), Ok(decoded));
+
+        // The expanded form must also be encodable as a regular 32-bit instruction.
+        let word = decoded.encode().unwrap();
+        assert_eq!(RiscVInstruction::decode(word), Ok(decoded));
+    }
+}
@@ -0,0 +1,138 @@
+use crate::error::Error;
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn i_type_immediate_out_of_range() {
+    let too_large = RiscVInstruction::Addi {
+        rd: Register::X1,
+        rs1: Register::X1,
+        imm: 2048,
+    };
+    assert_eq!(too_large.encode(), Err(Error::InvalidImmediate));
+
+    let too_small = RiscVInstruction::Lw {
+        rd: Register::X1,
+        rs1: Register::X1,
+        imm: -2049,
+    };
+    assert_eq!(too_small.encode(), Err(Error::InvalidImmediate));
+}
+
+#[test]
+fn shift_amount_out_of_range() {
+    let too_large = RiscVInstruction::Slli {
+        rd: Register::X1,
+        rs1: Register::X1,
+        imm: 64,
+    };
+    assert_eq!(too_large.encode(), Err(Error::InvalidImmediate));
+
+    // The word shifts keep the five-bit shift amount on RV64I.
+    let word = RiscVInstruction::Slliw {
+        rd: Register::X1,
+        rs1: Register::X1,
+        imm: 32,
+    };
+    assert_eq!(word.encode(), Err(Error::InvalidImmediate));
+
+    let negative = RiscVInstruction::Srai {
+        rd: Register::X1,
+        rs1: Register::X1,
+        imm: -1,
+    };
+    assert_eq!(negative.encode(), Err(Error::InvalidImmediate));
+}
+
+#[test]
+fn store_immediate_out_of_range() {
+    let sb = RiscVInstruction::Sb {
+        rs1: Register::X1,
+        rs2: Register::X2,
+        imm: 2048,
+    };
+    assert_eq!(sb.encode(), Err(Error::InvalidImmediate));
+}
+
+#[test]
+fn branch_offset_out_of_range() {
+    let too_large = RiscVInstruction::Beq {
+        rs1: Register::X1,
+        rs2: Register::X2,
+        imm: 4096,
+    };
+    assert_eq!(too_large.encode(), Err(Error::InvalidImmediate));
+
+    let too_small = RiscVInstruction::Bne {
+        rs1: Register::X1,
+        rs2: Register::X2,
+        imm: -4098,
+    };
+    assert_eq!(too_small.encode(), Err(Error::InvalidImmediate));
+}
+
+#[test]
+fn branch_offset_odd() {
+    let blt = RiscVInstruction::Blt {
+        rs1: Register::X1,
+        rs2: Register::X2,
+        imm: 3,
+    };
+    assert_eq!(blt.encode(), Err(Error::InvalidImmediate));
+}
+
+#[test]
+fn jump_offset_out_of_range() {
+    let too_large = RiscVInstruction::Jal {
+        rd: Register::X1,
+        imm: 1048576,
+    };
+    assert_eq!(too_large.encode(), Err(Error::InvalidImmediate));
+
+    let odd = RiscVInstruction::Jal {
+        rd: Register::X1,
+        imm: -1,
+    };
+    assert_eq!(odd.encode(), Err(Error::InvalidImmediate));
+}
+
+#[test]
+fn upper_immediate_low_bits() {
+    let lui = RiscVInstruction::Lui {
+        rd: Register::X1,
+        imm: 0x1001,
+    };
+    assert_eq!(lui.encode(), Err(Error::InvalidImmediate));
+
+    let auipc = RiscVInstruction::Auipc {
+        rd: Register::X1,
+        imm: -1,
+    };
+    assert_eq!(auipc.encode(), Err(Error::InvalidImmediate));
+}
+
+#[test]
+fn fence_set_out_of_range() {
+    let fence = RiscVInstruction::Fence {
+        pred: 0x10,
+        succ: 0xf,
+    };
+    assert_eq!(fence.encode(), Err(Error::InvalidImmediate));
+}
+
+#[test]
+fn csr_out_of_range() {
+    let csrrw = RiscVInstruction::Csrrw {
+        rd: Register::X1,
+        rs1: Register::X2,
+        csr: 0x1000,
+    };
+    assert_eq!(csrrw.encode(), Err(Error::InvalidImmediate));
+
+    let csrrsi = RiscVInstruction::Csrrsi {
+        rd: Register::X1,
+        imm: 32,
+        csr: 0xc00,
+    };
+    assert_eq!(csrrsi.encode(), Err(Error::InvalidImmediate));
+}
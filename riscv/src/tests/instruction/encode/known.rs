@@ -0,0 +1,118 @@
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn register_register() {
+    let add = RiscVInstruction::Add {
+        rd: Register::X1,
+        rs1: Register::X2,
+        rs2: Register::X3,
+    };
+    assert_eq!(add.encode(), Ok(0x003100b3));
+
+    let sub = RiscVInstruction::Sub {
+        rd: Register::X1,
+        rs1: Register::X2,
+        rs2: Register::X3,
+    };
+    assert_eq!(sub.encode(), Ok(0x403100b3));
+
+    let mul = RiscVInstruction::Mul {
+        rd: Register::X10,
+        rs1: Register::X10,
+        rs2: Register::X11,
+    };
+    assert_eq!(mul.encode(), Ok(0x02b50533));
+}
+
+#[test]
+fn immediate() {
+    let addi = RiscVInstruction::Addi {
+        rd: Register::X10,
+        rs1: Register::X0,
+        imm: 100,
+    };
+    assert_eq!(addi.encode(), Ok(0x06400513));
+
+    let addi_negative = RiscVInstruction::Addi {
+        rd: Register::X2,
+        rs1: Register::X2,
+        imm: -16,
+    };
+    assert_eq!(addi_negative.encode(), Ok(0xff010113));
+
+    let srai = RiscVInstruction::Srai {
+        rd: Register::X1,
+        rs1: Register::X2,
+        imm: 5,
+    };
+    assert_eq!(srai.encode(), Ok(0x40515093));
+}
+
+#[test]
+fn load_store() {
+    let lw = RiscVInstruction::Lw {
+        rd: Register::X1,
+        rs1: Register::X2,
+        imm: 100,
+    };
+    assert_eq!(lw.encode(), Ok(0x06412083));
+
+    let sw = RiscVInstruction::Sw {
+        rs1: Register::X2,
+        rs2: Register::X1,
+        imm: 100,
+    };
+    assert_eq!(sw.encode(), Ok(0x06112223));
+
+    let sw_negative = RiscVInstruction::Sw {
+        rs1: Register::X2,
+        rs2: Register::X1,
+        imm: -4,
+    };
+    assert_eq!(sw_negative.encode(), Ok(0xfe112e23));
+}
+
+#[test]
+fn control_flow() {
+    let bgeu = RiscVInstruction::Bgeu {
+        rs1: Register::X1,
+        rs2: Register::X2,
+        imm: -8,
+    };
+    assert_eq!(bgeu.encode(), Ok(0xfe20fce3));
+
+    let jal = RiscVInstruction::Jal {
+        rd: Register::X1,
+        imm: -2,
+    };
+    assert_eq!(jal.encode(), Ok(0xfffff0ef));
+
+    let jalr = RiscVInstruction::Jalr {
+        rd: Register::X1,
+        rs1: Register::X2,
+        imm: 4,
+    };
+    assert_eq!(jalr.encode(), Ok(0x004100e7));
+}
+
+#[test]
+fn upper_immediate() {
+    let lui = RiscVInstruction::Lui {
+        rd: Register::X1,
+        imm: 0x12345000,
+    };
+    assert_eq!(lui.encode(), Ok(0x123450b7));
+
+    let auipc = RiscVInstruction::Auipc {
+        rd: Register::X1,
+        imm: -0x1000,
+    };
+    assert_eq!(auipc.encode(), Ok(0xfffff097));
+}
+
+#[test]
+fn system() {
+    assert_eq!(RiscVInstruction::Ecall.encode(), Ok(0x00000073));
+    assert_eq!(RiscVInstruction::Ebreak.encode(), Ok(0x00100073));
+}
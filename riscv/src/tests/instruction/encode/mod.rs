@@ -0,0 +1,4 @@
+mod compressed;
+mod errors;
+mod known;
+mod round_trip;
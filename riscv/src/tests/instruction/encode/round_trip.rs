@@ -0,0 +1,298 @@
+use crate::extension::Extensions;
+use crate::instruction::RiscVInstruction;
+use crate::profile::Profile;
+use crate::register::Register;
+
+/// Returns the encoding of the instruction `word` decodes to. Decoding drops the fields
+/// FENCE and FENCE.I ignore: rd, rs1, reserved fence modes and the FENCE.I immediate.
+fn canonical(word: u32) -> u32 {
+    if word & 0x7f != 0x0f {
+        return word;
+    }
+
+    match (word >> 12) & 0x7 {
+        0 if word & 0xfff0_0000 == 0x8330_0000 => 0x8330000f,
+        0 => word & 0x0ff0_0000 | 0x0f,
+        _ => 0x0000100f,
+    }
+}
+
+/// Asserts that a word which decodes to a supported instruction encodes back to itself,
+/// up to the fields decoding ignores.
+fn assert_round_trip(word: u32) {
+    assert_round_trip_with(word, Extensions::NONE);
+}
+
+/// Like [`assert_round_trip`], accepting the given optional extensions.
+fn assert_round_trip_with(word: u32, extensions: Extensions) {
+    let Ok(decoded) = RiscVInstruction::decode_with(word, extensions) else {
+        return;
+    };
+
+    assert_eq!(
+        decoded.encode(),
+        Ok(canonical(word)),
+        "{} did not round-trip from 0x{:08x}",
+        decoded,
+        word
+    );
+}
+
+/// Like [`assert_round_trip`], decoding for RV64I.
+fn assert_round_trip_rv64(word: u32) {
+    let Ok(decoded) = RiscVInstruction::decode_for(word, Profile::Rv64i, Extensions::NONE) else {
+        return;
+    };
+
+    assert_eq!(
+        decoded.encode(),
+        Ok(canonical(word)),
+        "{} did not round-trip from 0x{:08x}",
+        decoded,
+        word
+    );
+}
+
+/// A small deterministic generator so that sampled words are the same on every run.
+fn next_random(state: &mut u64) -> u32 {
+    *state = state
+        .wrapping_mul(6364136223846793005)
+        .wrapping_add(1442695040888963407);
+    (*state >> 32) as u32
+}
+
+#[test]
+fn every_opcode_and_function() {
+    // Every combination of opcode, funct3 and funct7 with a few operand patterns.
+    for opcode in 0..0x80 {
+        for funct3 in 0..8 {
+            for funct7 in 0..0x80 {
+                for operands in [0x00000, 0x1f0fff, 0x0a5a5, 0x15a5a] {
+                    let rd_rs1 = operands & 0xfff;
+                    let rs2 = operands >> 12;
+                    let word = (funct7 << 25)
+                        | (rs2 << 20)
+                        | ((rd_rs1 >> 5) << 15)
+                        | (funct3 << 12)
+                        | ((rd_rs1 & 0x1f) << 7)
+                        | opcode;
+                    assert_round_trip(word);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn every_extension_function() {
+    // Every funct3 and funct7 of the register, immediate, AMO and SYSTEM opcodes with
+    // every optional extension enabled, which also covers each shift amount, unary rs2
+    // and CSR number.
+    for opcode in [0x13, 0x2f, 0x33, 0x73] {
+        for funct3 in 0..8 {
+            for funct7 in 0..0x80 {
+                for rs2 in 0..32 {
+                    let word = (funct7 << 25) | (rs2 << 20) | (2 << 15) | (funct3 << 12) | (1 << 7);
+                    assert_round_trip_with(word | opcode, Extensions::ALL);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn every_floating_point_function() {
+    // Every funct3, funct7 and rs2 of the F and D opcodes, which also covers each
+    // rounding mode, fmt and rs3.
+    for opcode in [0x07, 0x27, 0x43, 0x47, 0x4b, 0x4f, 0x53] {
+        for funct3 in 0..8 {
+            for funct7 in 0..0x80 {
+                for rs2 in 0..32 {
+                    let word = (funct7 << 25) | (rs2 << 20) | (2 << 15) | (funct3 << 12) | (1 << 7);
+                    assert_round_trip_with(word | opcode, Extensions::ALL);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn every_fence() {
+    // Every fm, predecessor and successor set of FENCE and every immediate of FENCE.I.
+    for funct3 in 0..2 {
+        for imm in 0..0x1000 {
+            assert_round_trip_with((imm << 20) | (funct3 << 12) | 0x0f, Extensions::ALL);
+        }
+    }
+}
+
+#[test]
+fn every_register() {
+    let bases = [
+        0x00000033, // add
+        0x40000033, // sub
+        0x02007033, // remu
+        0x00000013, // addi
+        0x40005013, // srai
+        0x00004003, // lbu
+        0x00002023, // sw
+        0x00007063, // bgeu
+        0x00000037, // lui
+        0x00000017, // auipc
+        0x0000006f, // jal
+        0x00000067, // jalr
+    ];
+
+    for base in bases {
+        for rd in 0..32 {
+            for rs1 in 0..32 {
+                for rs2 in 0..32 {
+                    assert_round_trip(base | (rs2 << 20) | (rs1 << 15) | (rd << 7));
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn every_i_type_immediate() {
+    let bases = [
+        0x00010093, // addi x1, x2
+        0x00012093, // slti x1, x2
+        0x00013093, // sltiu x1, x2
+        0x00014093, // xori x1, x2
+        0x00016093, // ori x1, x2
+        0x00017093, // andi x1, x2
+        0x00010083, // lb x1, x2
+        0x00012083, // lw x1, x2
+        0x000100e7, // jalr x1, x2
+    ];
+
+    for base in bases {
+        for imm in 0..0x1000 {
+            assert_round_trip(base | (imm << 20));
+        }
+    }
+}
+
+#[test]
+fn every_shift_amount() {
+    let bases = [
+        0x00011093, // slli x1, x2
+        0x00015093, // srli x1, x2
+        0x40015093, // srai x1, x2
+    ];
+
+    for base in bases {
+        for shamt in 0..32 {
+            assert_round_trip(base | (shamt << 20));
+        }
+    }
+}
+
+#[test]
+fn every_rv64_function() {
+    // Every funct3 and funct7 of the opcodes that RV64I adds or changes, which also
+    // covers each six-bit shift amount.
+    for opcode in [0x03, 0x13, 0x1b, 0x23, 0x3b] {
+        for funct3 in 0..8 {
+            for funct7 in 0..0x80 {
+                for rs2 in 0..32 {
+                    let word = (funct7 << 25) | (rs2 << 20) | (2 << 15) | (funct3 << 12) | (1 << 7);
+                    assert_round_trip_rv64(word | opcode);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn every_store_and_branch_immediate() {
+    let bases = [
+        0x00000023, // sb
+        0x00001023, // sh
+        0x00002023, // sw
+        0x00000063, // beq
+        0x00001063, // bne
+        0x00004063, // blt
+        0x00005063, // bge
+        0x00006063, // bltu
+        0x00007063, // bgeu
+    ];
+
+    for base in bases {
+        for imm in 0..0x1000 {
+            let high = imm >> 5;
+            let low = imm & 0x1f;
+            assert_round_trip(base | (high << 25) | (2 << 20) | (1 << 15) | (low << 7));
+        }
+    }
+}
+
+#[test]
+fn every_upper_and_jump_immediate() {
+    for imm in 0..0x100000 {
+        assert_round_trip((imm << 12) | (1 << 7) | 0x37); // lui x1
+        assert_round_trip((imm << 12) | (1 << 7) | 0x17); // auipc x1
+        assert_round_trip((imm << 12) | (1 << 7) | 0x6f); // jal x1
+    }
+}
+
+#[test]
+fn sampled_words() {
+    let mut state = 0x5eed;
+    for _ in 0..1_000_000 {
+        assert_round_trip(next_random(&mut state));
+    }
+}
+
+#[test]
+fn encode_then_decode() {
+    let instructions = [
+        RiscVInstruction::Sltu {
+            rd: Register::X31,
+            rs1: Register::X0,
+            rs2: Register::X17,
+        },
+        RiscVInstruction::Mulhsu {
+            rd: Register::X5,
+            rs1: Register::X6,
+            rs2: Register::X7,
+        },
+        RiscVInstruction::Sltiu {
+            rd: Register::X3,
+            rs1: Register::X4,
+            imm: -2048,
+        },
+        RiscVInstruction::Lhu {
+            rd: Register::X8,
+            rs1: Register::X9,
+            imm: 2047,
+        },
+        RiscVInstruction::Sh {
+            rs1: Register::X10,
+            rs2: Register::X11,
+            imm: -1,
+        },
+        RiscVInstruction::Blt {
+            rs1: Register::X12,
+            rs2: Register::X13,
+            imm: -4096,
+        },
+        RiscVInstruction::Auipc {
+            rd: Register::X14,
+            imm: i32::MIN,
+        },
+        RiscVInstruction::Jal {
+            rd: Register::X15,
+            imm: 1048574,
+        },
+        RiscVInstruction::Ecall,
+        RiscVInstruction::Ebreak,
+    ];
+
+    for instruction in instructions {
+        let word = instruction.encode().unwrap();
+        assert_eq!(RiscVInstruction::decode(word), Ok(instruction));
+    }
+}
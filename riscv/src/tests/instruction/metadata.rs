@@ -0,0 +1,290 @@
+use crate::assembler::assemble;
+use crate::instruction::RiscVInstruction;
+use crate::metadata::{AccessKind, ControlFlow, MemoryAccess};
+use crate::register::{FloatRegister, Register};
+
+/// Assembles a single line of source into its only instruction.
+fn instruction(source: &str) -> RiscVInstruction {
+    let assembly = assemble(source).unwrap();
+    assert_eq!(assembly.instructions.len(), 1);
+    assembly.instructions[0].instruction
+}
+
+fn reads(source: &str) -> Vec<Register> {
+    instruction(source).reads().collect()
+}
+
+fn writes(source: &str) -> Option<Register> {
+    instruction(source).writes()
+}
+
+fn float_reads(source: &str) -> Vec<FloatRegister> {
+    instruction(source).float_reads().collect()
+}
+
+fn control_flow(source: &str) -> ControlFlow {
+    instruction(source).control_flow()
+}
+
+fn memory_access(source: &str) -> Option<MemoryAccess> {
+    instruction(source).memory_access()
+}
+
+#[test]
+fn register_register_reads_and_writes() {
+    assert_eq!(reads("add a0, a1, a2"), [Register::A1, Register::A2]);
+    assert_eq!(writes("add a0, a1, a2"), Some(Register::A0));
+
+    assert_eq!(reads("divu t0, s1, s2"), [Register::S1, Register::S2]);
+    assert_eq!(writes("divu t0, s1, s2"), Some(Register::T0));
+}
+
+#[test]
+fn same_register_read_twice() {
+    assert_eq!(reads("mul a0, a1, a1"), [Register::A1, Register::A1]);
+}
+
+#[test]
+fn immediate_reads_and_writes() {
+    assert_eq!(reads("addi sp, sp, -16"), [Register::SP]);
+    assert_eq!(writes("addi sp, sp, -16"), Some(Register::SP));
+
+    assert_eq!(reads("srai a0, a1, 3"), [Register::A1]);
+    assert_eq!(writes("srai a0, a1, 3"), Some(Register::A0));
+}
+
+#[test]
+fn zero_register_is_never_reported() {
+    assert_eq!(reads("add a0, zero, a1"), [Register::A1]);
+    assert_eq!(reads("addi a0, zero, 5"), []);
+    assert_eq!(writes("add zero, a0, a1"), None);
+    assert_eq!(reads("beq zero, zero, 8"), []);
+}
+
+#[test]
+fn loads_and_stores() {
+    assert_eq!(reads("lw a0, 8(sp)"), [Register::SP]);
+    assert_eq!(writes("lw a0, 8(sp)"), Some(Register::A0));
+
+    assert_eq!(reads("sw a0, 8(sp)"), [Register::SP, Register::A0]);
+    assert_eq!(writes("sw a0, 8(sp)"), None);
+}
+
+#[test]
+fn word_instructions() {
+    assert_eq!(reads("addw a0, a1, a2"), [Register::A1, Register::A2]);
+    assert_eq!(writes("addw a0, a1, a2"), Some(Register::A0));
+    assert_eq!(reads("sraiw a0, a1, 3"), [Register::A1]);
+    assert_eq!(writes("sraiw a0, a1, 3"), Some(Register::A0));
+    assert_eq!(reads("sd a0, 8(sp)"), [Register::SP, Register::A0]);
+    assert_eq!(writes("sd a0, 8(sp)"), None);
+}
+
+#[test]
+fn upper_immediates() {
+    assert_eq!(reads("lui a0, 0x12345"), []);
+    assert_eq!(writes("lui a0, 0x12345"), Some(Register::A0));
+    assert_eq!(reads("auipc t1, 0x1"), []);
+    assert_eq!(writes("auipc t1, 0x1"), Some(Register::T1));
+}
+
+#[test]
+fn branches_read_both_operands() {
+    assert_eq!(reads("blt a0, a1, 8"), [Register::A0, Register::A1]);
+    assert_eq!(writes("blt a0, a1, 8"), None);
+}
+
+#[test]
+fn jumps_write_the_link_register() {
+    assert_eq!(reads("jal ra, 8"), []);
+    assert_eq!(writes("jal ra, 8"), Some(Register::RA));
+    assert_eq!(reads("jalr ra, a0, 0"), [Register::A0]);
+    assert_eq!(writes("jalr ra, a0, 0"), Some(Register::RA));
+    assert_eq!(writes("j 8"), None);
+}
+
+#[test]
+fn system_instructions() {
+    assert_eq!(reads("ecall"), []);
+    assert_eq!(writes("ecall"), None);
+    assert_eq!(reads("ebreak"), []);
+    assert_eq!(writes("ebreak"), None);
+}
+
+#[test]
+fn floating_point_registers() {
+    assert_eq!(
+        float_reads("fmadd.s fa0, fa1, fa2, fa3"),
+        [FloatRegister::FA1, FloatRegister::FA2, FloatRegister::FA3]
+    );
+    assert_eq!(
+        instruction("fmadd.s fa0, fa1, fa2, fa3").float_writes(),
+        Some(FloatRegister::FA0)
+    );
+    // f0 is an ordinary register, unlike x0.
+    assert_eq!(float_reads("fsqrt.d ft0, ft0"), [FloatRegister::FT0]);
+    assert_eq!(reads("fsqrt.d ft0, ft0"), []);
+    assert_eq!(writes("fsqrt.d ft0, ft0"), None);
+}
+
+#[test]
+fn floating_point_moves_between_register_files() {
+    assert_eq!(reads("fcvt.s.w fa0, a1"), [Register::A1]);
+    assert_eq!(float_reads("fcvt.s.w fa0, a1"), []);
+    assert_eq!(
+        instruction("fcvt.s.w fa0, a1").float_writes(),
+        Some(FloatRegister::FA0)
+    );
+
+    assert_eq!(writes("feq.d a0, fa1, fa2"), Some(Register::A0));
+    assert_eq!(
+        float_reads("feq.d a0, fa1, fa2"),
+        [FloatRegister::FA1, FloatRegister::FA2]
+    );
+    assert_eq!(instruction("feq.d a0, fa1, fa2").float_writes(), None);
+
+    assert_eq!(reads("fsw fa0, 4(sp)"), [Register::SP]);
+    assert_eq!(float_reads("fsw fa0, 4(sp)"), [FloatRegister::FA0]);
+    assert_eq!(reads("fld fa0, 4(sp)"), [Register::SP]);
+    assert_eq!(
+        instruction("fld fa0, 4(sp)").float_writes(),
+        Some(FloatRegister::FA0)
+    );
+}
+
+#[test]
+fn integer_instructions_use_no_floating_point_registers() {
+    assert_eq!(float_reads("add a0, a1, a2"), []);
+    assert_eq!(instruction("add a0, a1, a2").float_writes(), None);
+}
+
+#[test]
+fn sequential_control_flow() {
+    assert_eq!(control_flow("add a0, a1, a2"), ControlFlow::Sequential);
+    assert_eq!(control_flow("lw a0, 0(sp)"), ControlFlow::Sequential);
+    assert_eq!(control_flow("auipc a0, 0x1"), ControlFlow::Sequential);
+    assert!(!ControlFlow::Sequential.is_control_transfer());
+}
+
+#[test]
+fn branch_control_flow() {
+    for source in [
+        "beq a0, a1, 8",
+        "bne a0, a1, 8",
+        "blt a0, a1, 8",
+        "bge a0, a1, 8",
+        "bltu a0, a1, 8",
+        "bgeu a0, a1, 8",
+    ] {
+        assert_eq!(control_flow(source), ControlFlow::Branch, "{}", source);
+    }
+    assert!(ControlFlow::Branch.is_control_transfer());
+}
+
+#[test]
+fn jump_control_flow() {
+    assert_eq!(control_flow("jal zero, 8"), ControlFlow::Jump);
+    assert_eq!(control_flow("jal a0, 8"), ControlFlow::Jump);
+    assert_eq!(control_flow("jalr zero, a0, 0"), ControlFlow::IndirectJump);
+    assert_eq!(control_flow("jalr a1, a0, 0"), ControlFlow::IndirectJump);
+}
+
+#[test]
+fn call_control_flow() {
+    assert_eq!(control_flow("jal ra, 8"), ControlFlow::Call);
+    assert_eq!(control_flow("jal t0, 8"), ControlFlow::Call);
+    assert_eq!(control_flow("jalr ra, a0, 0"), ControlFlow::IndirectCall);
+    assert_eq!(control_flow("jalr t0, a0, 0"), ControlFlow::IndirectCall);
+}
+
+#[test]
+fn return_control_flow() {
+    assert_eq!(control_flow("ret"), ControlFlow::Return);
+    assert_eq!(control_flow("jalr zero, t0, 0"), ControlFlow::Return);
+}
+
+#[test]
+fn trap_control_flow() {
+    assert_eq!(control_flow("ecall"), ControlFlow::Trap);
+    assert_eq!(control_flow("ebreak"), ControlFlow::Trap);
+}
+
+#[test]
+fn load_access() {
+    let load = |width, signed| {
+        Some(MemoryAccess {
+            kind: AccessKind::Load,
+            width,
+            signed,
+        })
+    };
+
+    assert_eq!(memory_access("lb a0, 0(sp)"), load(1, true));
+    assert_eq!(memory_access("lbu a0, 0(sp)"), load(1, false));
+    assert_eq!(memory_access("lh a0, 0(sp)"), load(2, true));
+    assert_eq!(memory_access("lhu a0, 0(sp)"), load(2, false));
+    assert_eq!(memory_access("lw a0, 0(sp)"), load(4, true));
+    assert_eq!(memory_access("lwu a0, 0(sp)"), load(4, false));
+    assert_eq!(memory_access("ld a0, 0(sp)"), load(8, false));
+    assert_eq!(memory_access("flw fa0, 0(sp)"), load(4, false));
+    assert_eq!(memory_access("fld fa0, 0(sp)"), load(8, false));
+}
+
+#[test]
+fn store_access() {
+    let store = |width| {
+        Some(MemoryAccess {
+            kind: AccessKind::Store,
+            width,
+            signed: false,
+        })
+    };
+
+    assert_eq!(memory_access("sb a0, 0(sp)"), store(1));
+    assert_eq!(memory_access("sh a0, 0(sp)"), store(2));
+    assert_eq!(memory_access("sw a0, 0(sp)"), store(4));
+    assert_eq!(memory_access("sd a0, 0(sp)"), store(8));
+    assert_eq!(memory_access("fsw fa0, 0(sp)"), store(4));
+    assert_eq!(memory_access("fsd fa0, 0(sp)"), store(8));
+}
+
+#[test]
+fn atomic_access() {
+    let access = |kind| {
+        Some(MemoryAccess {
+            kind,
+            width: 4,
+            signed: false,
+        })
+    };
+
+    assert_eq!(memory_access("lr.w a0, (a1)"), access(AccessKind::Load));
+    assert_eq!(
+        memory_access("sc.w a0, a2, (a1)"),
+        access(AccessKind::Store)
+    );
+    assert_eq!(
+        memory_access("amoadd.w a0, a2, (a1)"),
+        access(AccessKind::ReadModifyWrite)
+    );
+}
+
+#[test]
+fn atomic_reads_and_writes() {
+    assert_eq!(reads("lr.w a0, (a1)"), [Register::A1]);
+    assert_eq!(writes("lr.w a0, (a1)"), Some(Register::A0));
+    assert_eq!(reads("sc.w a0, a2, (a1)"), [Register::A1, Register::A2]);
+    assert_eq!(writes("sc.w a0, a2, (a1)"), Some(Register::A0));
+    assert_eq!(
+        reads("amoswap.w zero, a2, (a1)"),
+        [Register::A1, Register::A2]
+    );
+    assert_eq!(writes("amoswap.w zero, a2, (a1)"), None);
+}
+
+#[test]
+fn no_memory_access() {
+    assert_eq!(memory_access("add a0, a1, a2"), None);
+    assert_eq!(memory_access("jalr ra, a0, 0"), None);
+    assert_eq!(memory_access("ecall"), None);
+}
@@ -1,2 +1,6 @@
 mod decode;
 mod display;
+mod encode;
+mod metadata;
+mod semantics;
+mod target;
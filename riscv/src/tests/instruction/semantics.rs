@@ -0,0 +1,651 @@
+use crate::assembler::assemble;
+use crate::csr::Counters;
+use crate::error::Error;
+use crate::float::{ExceptionFlags, Fcsr};
+use crate::instruction::RiscVInstruction;
+
+/// Assembles a single instruction.
+fn instruction(source: &str) -> RiscVInstruction {
+    assemble(source).unwrap().instructions[0].instruction
+}
+
+/// Evaluates `source` with the given source register values.
+fn evaluate(source: &str, rs1: u32, rs2: u32) -> u32 {
+    instruction(source).evaluate(rs1, rs2).unwrap()
+}
+
+/// Evaluates `source` on RV64I with the given source register values.
+fn evaluate_rv64(source: &str, rs1: u64, rs2: u64) -> u64 {
+    instruction(source).evaluate_rv64(rs1, rs2).unwrap()
+}
+
+/// Computes the value stored by the atomic memory operation `source`.
+fn atomic(source: &str, loaded: u32, rs2: u32) -> u32 {
+    instruction(source).atomic(loaded, rs2).unwrap()
+}
+
+/// Evaluates the floating-point instruction `source` with the given source register
+/// values and a zeroed `fcsr`, returning the result and the raised flags.
+fn evaluate_float(source: &str, rs1: u64, rs2: u64, rs3: u64) -> (u64, ExceptionFlags) {
+    let mut fcsr = Fcsr::default();
+    let result = instruction(source)
+        .evaluate_float(rs1, rs2, rs3, &mut fcsr)
+        .unwrap()
+        .unwrap();
+    (result, fcsr.fflags)
+}
+
+/// A NaN-boxed single-precision register value.
+fn single(value: f32) -> u64 {
+    0xffff_ffff_0000_0000 | value.to_bits() as u64
+}
+
+fn double(value: f64) -> u64 {
+    value.to_bits()
+}
+
+#[test]
+fn base_arithmetic() {
+    assert_eq!(evaluate("add a0, a1, a2", u32::MAX, 2), 1);
+    assert_eq!(evaluate("sub a0, a1, a2", 0, 1), u32::MAX);
+    assert_eq!(evaluate("sra a0, a1, a2", 0x80000000, 33), 0xc0000000);
+    assert_eq!(evaluate("srl a0, a1, a2", 0x80000000, 33), 0x40000000);
+    assert_eq!(evaluate("slt a0, a1, a2", -1_i32 as u32, 0), 1);
+    assert_eq!(evaluate("sltu a0, a1, a2", -1_i32 as u32, 0), 0);
+    assert_eq!(evaluate("addi a0, a1, -1", 0, 0), u32::MAX);
+    assert_eq!(evaluate("sltiu a0, a1, -1", 5, 0), 1);
+    assert_eq!(evaluate("srai a0, a1, 4", 0xf0000000, 0), 0xff000000);
+    assert_eq!(evaluate("lui a0, 0x12345", 0, 0), 0x12345000);
+}
+
+#[test]
+fn multiply() {
+    assert_eq!(evaluate("mul a0, a1, a2", 0x10000, 0x10000), 0);
+    assert_eq!(evaluate("mulh a0, a1, a2", -1_i32 as u32, -1_i32 as u32), 0);
+    assert_eq!(evaluate("mulhu a0, a1, a2", u32::MAX, u32::MAX), 0xfffffffe);
+    assert_eq!(
+        evaluate("mulhsu a0, a1, a2", -1_i32 as u32, u32::MAX),
+        u32::MAX
+    );
+}
+
+#[test]
+fn division_by_zero() {
+    assert_eq!(evaluate("div a0, a1, a2", 7, 0), u32::MAX);
+    assert_eq!(evaluate("divu a0, a1, a2", 7, 0), u32::MAX);
+    assert_eq!(evaluate("rem a0, a1, a2", 7, 0), 7);
+    assert_eq!(evaluate("remu a0, a1, a2", 7, 0), 7);
+}
+
+#[test]
+fn division_overflow() {
+    let min = i32::MIN as u32;
+    assert_eq!(evaluate("div a0, a1, a2", min, -1_i32 as u32), min);
+    assert_eq!(evaluate("rem a0, a1, a2", min, -1_i32 as u32), 0);
+    assert_eq!(evaluate("div a0, a1, a2", -7_i32 as u32, 2), -3_i32 as u32);
+    assert_eq!(evaluate("rem a0, a1, a2", -7_i32 as u32, 2), -1_i32 as u32);
+}
+
+#[test]
+fn rv64_base_arithmetic() {
+    assert_eq!(evaluate_rv64("add a0, a1, a2", u64::MAX, 2), 1);
+    assert_eq!(evaluate_rv64("addi a0, a1, -1", 0, 0), u64::MAX);
+    assert_eq!(evaluate_rv64("sll a0, a1, a2", 1, 63), 1 << 63);
+    // Only the lower six bits of the shift amount are used.
+    assert_eq!(evaluate_rv64("srl a0, a1, a2", 1 << 63, 64 + 63), 1);
+    assert_eq!(evaluate_rv64("sra a0, a1, a2", 1 << 63, 63), u64::MAX);
+    assert_eq!(evaluate_rv64("slli a0, a1, 40", 1, 0), 1 << 40);
+    assert_eq!(
+        evaluate_rv64("srai a0, a1, 32", 0x8000_0000_0000_0000, 0),
+        0xffff_ffff_8000_0000
+    );
+    assert_eq!(evaluate_rv64("slt a0, a1, a2", u64::MAX, 0), 1);
+    assert_eq!(evaluate_rv64("sltu a0, a1, a2", u64::MAX, 0), 0);
+    assert_eq!(evaluate_rv64("sltiu a0, a1, -1", 0xffff_ffff, 0), 1);
+    // lui sign-extends its 32-bit result.
+    assert_eq!(
+        evaluate_rv64("lui a0, 0x80000", 0, 0),
+        0xffff_ffff_8000_0000
+    );
+}
+
+#[test]
+fn rv64_multiply_and_divide() {
+    assert_eq!(evaluate_rv64("mul a0, a1, a2", 1 << 32, 1 << 32), 0);
+    assert_eq!(evaluate_rv64("mulhu a0, a1, a2", 1 << 32, 1 << 32), 1);
+    assert_eq!(evaluate_rv64("mulh a0, a1, a2", u64::MAX, u64::MAX), 0);
+    assert_eq!(
+        evaluate_rv64("mulhsu a0, a1, a2", u64::MAX, u64::MAX),
+        u64::MAX
+    );
+    assert_eq!(evaluate_rv64("div a0, a1, a2", 7, 0), u64::MAX);
+    assert_eq!(evaluate_rv64("divu a0, a1, a2", 7, 0), u64::MAX);
+    assert_eq!(evaluate_rv64("rem a0, a1, a2", 7, 0), 7);
+    let min = i64::MIN as u64;
+    assert_eq!(evaluate_rv64("div a0, a1, a2", min, u64::MAX), min);
+    assert_eq!(evaluate_rv64("rem a0, a1, a2", min, u64::MAX), 0);
+}
+
+#[test]
+fn rv64_word_instructions_sign_extend() {
+    assert_eq!(
+        evaluate_rv64("addw a0, a1, a2", 0x7fff_ffff, 1),
+        0xffff_ffff_8000_0000
+    );
+    // The upper halves of the operands are ignored.
+    assert_eq!(
+        evaluate_rv64("addw a0, a1, a2", 0x1234_5678_0000_0001, 1),
+        2
+    );
+    assert_eq!(evaluate_rv64("subw a0, a1, a2", 0, 1), u64::MAX);
+    assert_eq!(evaluate_rv64("addiw a0, a1, 0", 0xffff_ffff, 0), u64::MAX);
+    assert_eq!(
+        evaluate_rv64("slliw a0, a1, 31", 1, 0),
+        0xffff_ffff_8000_0000
+    );
+    assert_eq!(evaluate_rv64("srliw a0, a1, 1", u64::MAX, 0), 0x7fff_ffff);
+    assert_eq!(
+        evaluate_rv64("sraiw a0, a1, 4", 0x8000_0000, 0),
+        0xffff_ffff_f800_0000
+    );
+    // Word register shifts use the lower five bits of the shift amount.
+    assert_eq!(evaluate_rv64("sllw a0, a1, a2", 1, 32 + 4), 16);
+    assert_eq!(evaluate_rv64("srlw a0, a1, a2", 0x8000_0000, 31), 1);
+    assert_eq!(evaluate_rv64("sraw a0, a1, a2", 0x8000_0000, 31), u64::MAX);
+    assert_eq!(evaluate_rv64("mulw a0, a1, a2", 0x1_0000, 0x1_0000), 0);
+    assert_eq!(
+        evaluate_rv64("mulw a0, a1, a2", 0x4000_0000, 2),
+        0xffff_ffff_8000_0000
+    );
+}
+
+#[test]
+fn rv64_word_division() {
+    assert_eq!(evaluate_rv64("divw a0, a1, a2", 7, 0), u64::MAX);
+    assert_eq!(evaluate_rv64("divuw a0, a1, a2", 7, 0), u64::MAX);
+    assert_eq!(evaluate_rv64("remw a0, a1, a2", 7, 0), 7);
+    // remuw of a dividend with bit 31 set sign-extends the 32-bit remainder.
+    assert_eq!(
+        evaluate_rv64("remuw a0, a1, a2", 0x8000_0000, 0),
+        0xffff_ffff_8000_0000
+    );
+    assert_eq!(
+        evaluate_rv64("divw a0, a1, a2", 0x8000_0000, u64::MAX),
+        0xffff_ffff_8000_0000
+    );
+    assert_eq!(evaluate_rv64("remw a0, a1, a2", 0x8000_0000, u64::MAX), 0);
+    assert_eq!(
+        evaluate_rv64("divuw a0, a1, a2", 0xffff_fffe, 2),
+        0x7fff_ffff
+    );
+}
+
+#[test]
+fn rv64_extensions_are_not_evaluated() {
+    assert_eq!(instruction("clz a0, a1").evaluate_rv64(1, 0), None);
+    assert_eq!(instruction("ld a0, 0(a1)").evaluate_rv64(0, 0), None);
+    // The word instructions have no RV32I semantics.
+    assert_eq!(instruction("addw a0, a1, a2").evaluate(1, 2), None);
+}
+
+#[test]
+fn zba() {
+    assert_eq!(evaluate("sh1add a0, a1, a2", 3, 100), 106);
+    assert_eq!(evaluate("sh2add a0, a1, a2", 3, 100), 112);
+    assert_eq!(evaluate("sh3add a0, a1, a2", 3, 100), 124);
+    assert_eq!(evaluate("sh3add a0, a1, a2", 0x20000000, 1), 1);
+}
+
+#[test]
+fn zbb_logic() {
+    assert_eq!(
+        evaluate("andn a0, a1, a2", 0xff00ff00, 0x0ff00ff0),
+        0xf000f000
+    );
+    assert_eq!(
+        evaluate("orn a0, a1, a2", 0x00000000, 0x0000ffff),
+        0xffff0000
+    );
+    assert_eq!(
+        evaluate("xnor a0, a1, a2", 0x12345678, 0x12345678),
+        u32::MAX
+    );
+}
+
+#[test]
+fn zbb_count() {
+    assert_eq!(evaluate("clz a0, a1", 0, 0), 32);
+    assert_eq!(evaluate("clz a0, a1", 0x00010000, 0), 15);
+    assert_eq!(evaluate("ctz a0, a1", 0, 0), 32);
+    assert_eq!(evaluate("ctz a0, a1", 0x00010000, 0), 16);
+    assert_eq!(evaluate("cpop a0, a1", 0xf0f0f0f0, 0), 16);
+}
+
+#[test]
+fn zbb_min_max() {
+    let minus_one = -1_i32 as u32;
+    assert_eq!(evaluate("max a0, a1, a2", minus_one, 1), 1);
+    assert_eq!(evaluate("maxu a0, a1, a2", minus_one, 1), minus_one);
+    assert_eq!(evaluate("min a0, a1, a2", minus_one, 1), minus_one);
+    assert_eq!(evaluate("minu a0, a1, a2", minus_one, 1), 1);
+}
+
+#[test]
+fn zbb_extend() {
+    assert_eq!(evaluate("sext.b a0, a1", 0x1234_5680, 0), 0xffffff80);
+    assert_eq!(evaluate("sext.b a0, a1", 0x1234_567f, 0), 0x7f);
+    assert_eq!(evaluate("sext.h a0, a1", 0x1234_8000, 0), 0xffff8000);
+    assert_eq!(evaluate("zext.h a0, a1", 0xffff_8000, 0), 0x8000);
+}
+
+#[test]
+fn zbb_rotate() {
+    assert_eq!(evaluate("rol a0, a1, a2", 0x80000001, 1), 0x00000003);
+    assert_eq!(evaluate("rol a0, a1, a2", 0x80000001, 33), 0x00000003);
+    assert_eq!(evaluate("ror a0, a1, a2", 0x80000001, 1), 0xc0000000);
+    assert_eq!(evaluate("rori a0, a1, 8", 0x12345678, 0), 0x78123456);
+    assert_eq!(evaluate("rori a0, a1, 0", 0x12345678, 0), 0x12345678);
+}
+
+#[test]
+fn zbb_bytes() {
+    assert_eq!(evaluate("orc.b a0, a1", 0x00100801, 0), 0x00ffffff);
+    assert_eq!(evaluate("orc.b a0, a1", 0, 0), 0);
+    assert_eq!(evaluate("rev8 a0, a1", 0x12345678, 0), 0x78563412);
+}
+
+#[test]
+fn zbs() {
+    assert_eq!(evaluate("bclr a0, a1, a2", u32::MAX, 35), 0xfffffff7);
+    assert_eq!(evaluate("bclri a0, a1, 31", u32::MAX, 0), 0x7fffffff);
+    assert_eq!(evaluate("bext a0, a1, a2", 0x00000100, 8), 1);
+    assert_eq!(evaluate("bexti a0, a1, 9", 0x00000100, 0), 0);
+    assert_eq!(evaluate("binv a0, a1, a2", 0x00000001, 0), 0);
+    assert_eq!(evaluate("binvi a0, a1, 4", 0, 0), 0x10);
+    assert_eq!(evaluate("bset a0, a1, a2", 0, 31), 0x80000000);
+    assert_eq!(evaluate("bseti a0, a1, 0", 0x10, 0), 0x11);
+}
+
+#[test]
+fn not_evaluable() {
+    for source in [
+        "lw a0, 0(a1)",
+        "sw a0, 0(a1)",
+        "beq a0, a1, 8",
+        "jal ra, 8",
+        "auipc a0, 1",
+        "ecall",
+    ] {
+        assert_eq!(instruction(source).evaluate(1, 2), None, "{}", source);
+    }
+}
+
+#[test]
+fn sha256() {
+    // The first word of the SHA-256 initial hash value.
+    let h0 = 0x6a09e667;
+    assert_eq!(evaluate("sha256sig0 a0, a1", h0, 0), 0xba0cf582);
+    assert_eq!(evaluate("sha256sig1 a0, a1", h0, 0), 0xcfe5da3c);
+    assert_eq!(evaluate("sha256sum0 a0, a1", h0, 0), 0xce20b47e);
+    assert_eq!(evaluate("sha256sum1 a0, a1", h0, 0), 0x55b65510);
+}
+
+/// A 64-bit SHA-512 function, computed directly.
+type Sha512Function = fn(u64) -> u64;
+
+#[test]
+fn sha512_halves() {
+    // Each pair of instructions computes the low and high halves of a 64-bit function.
+    let cases: [(&str, &str, Sha512Function); 4] = [
+        ("sha512sum0r", "sha512sum0r", |x| {
+            x.rotate_right(28) ^ x.rotate_right(34) ^ x.rotate_right(39)
+        }),
+        ("sha512sum1r", "sha512sum1r", |x| {
+            x.rotate_right(14) ^ x.rotate_right(18) ^ x.rotate_right(41)
+        }),
+        ("sha512sig0l", "sha512sig0h", |x| {
+            x.rotate_right(1) ^ x.rotate_right(8) ^ (x >> 7)
+        }),
+        ("sha512sig1l", "sha512sig1h", |x| {
+            x.rotate_right(19) ^ x.rotate_right(61) ^ (x >> 6)
+        }),
+    ];
+
+    for x in [0x6a09e667f3bcc908_u64, 0x0123456789abcdef, u64::MAX, 1] {
+        let low = x as u32;
+        let high = (x >> 32) as u32;
+
+        for (low_mnemonic, high_mnemonic, function) in cases {
+            let expected = function(x);
+            let source = format!("{} a0, a1, a2", low_mnemonic);
+            assert_eq!(evaluate(&source, low, high), expected as u32, "{}", source);
+            let source = format!("{} a0, a1, a2", high_mnemonic);
+            assert_eq!(
+                evaluate(&source, high, low),
+                (expected >> 32) as u32,
+                "{}",
+                source
+            );
+        }
+    }
+}
+
+#[test]
+fn counter_reads() {
+    let counters = Counters {
+        gas_consumed: 0x0000_0002_0000_0010,
+        time: 1_700_000_000,
+        instret: 7,
+    };
+
+    let read = |source| instruction(source).read_csr(&counters);
+    assert_eq!(read("rdcycle a0"), Some(Ok(0x10)));
+    assert_eq!(read("rdcycleh a0"), Some(Ok(2)));
+    assert_eq!(read("rdtime a0"), Some(Ok(1_700_000_000)));
+    assert_eq!(read("rdinstret a0"), Some(Ok(7)));
+    assert_eq!(read("csrrsi a0, instreth, 0"), Some(Ok(0)));
+    assert_eq!(read("csrrc zero, cycle, zero"), Some(Ok(0x10)));
+}
+
+#[test]
+fn counter_writes_trap() {
+    let counters = Counters::default();
+    let read = |source| instruction(source).read_csr(&counters);
+    let trap = Some(Err(Error::InvalidCsr { csr: 0xc00 }));
+
+    assert_eq!(read("csrrw a0, cycle, zero"), trap);
+    assert_eq!(read("csrrs a0, cycle, a1"), trap);
+    assert_eq!(read("csrrc a0, cycle, a1"), trap);
+    assert_eq!(read("csrrwi a0, cycle, 0"), trap);
+    assert_eq!(read("csrrsi a0, cycle, 1"), trap);
+    assert_eq!(read("csrrci a0, cycle, 1"), trap);
+}
+
+#[test]
+fn other_csrs_trap() {
+    let counters = Counters::default();
+
+    assert_eq!(
+        instruction("csrr a0, 0x340").read_csr(&counters),
+        Some(Err(Error::InvalidCsr { csr: 0x340 }))
+    );
+    assert_eq!(
+        instruction("csrr a0, 0xc03").read_csr(&counters),
+        Some(Err(Error::InvalidCsr { csr: 0xc03 }))
+    );
+}
+
+#[test]
+fn csr_reads_are_not_evaluated() {
+    assert_eq!(instruction("rdcycle a0").evaluate(1, 2), None);
+    assert_eq!(
+        instruction("add a0, a1, a2").read_csr(&Counters::default()),
+        None
+    );
+}
+
+#[test]
+fn atomic_memory_operations() {
+    assert_eq!(atomic("amoswap.w a0, a2, (a1)", 1, 2), 2);
+    assert_eq!(atomic("amoadd.w a0, a2, (a1)", u32::MAX, 2), 1);
+    assert_eq!(atomic("amoxor.w a0, a2, (a1)", 0b1100, 0b1010), 0b0110);
+    assert_eq!(atomic("amoand.w a0, a2, (a1)", 0b1100, 0b1010), 0b1000);
+    assert_eq!(atomic("amoor.w a0, a2, (a1)", 0b1100, 0b1010), 0b1110);
+    assert_eq!(
+        atomic("amomin.w a0, a2, (a1)", -1_i32 as u32, 1),
+        -1_i32 as u32
+    );
+    assert_eq!(atomic("amomax.w a0, a2, (a1)", -1_i32 as u32, 1), 1);
+    assert_eq!(atomic("amominu.w a0, a2, (a1)", -1_i32 as u32, 1), 1);
+    assert_eq!(
+        atomic("amomaxu.w.aqrl a0, a2, (a1)", -1_i32 as u32, 1),
+        u32::MAX
+    );
+}
+
+#[test]
+fn reservations_are_not_memory_operations() {
+    assert_eq!(instruction("lr.w a0, (a1)").atomic(1, 2), None);
+    assert_eq!(instruction("sc.w a0, a2, (a1)").atomic(1, 2), None);
+    assert_eq!(instruction("add a0, a1, a2").atomic(1, 2), None);
+    assert_eq!(instruction("amoadd.w a0, a2, (a1)").evaluate(1, 2), None);
+}
+
+#[test]
+fn floating_point_arithmetic() {
+    let none = ExceptionFlags::NONE;
+    let inexact = ExceptionFlags::INEXACT;
+
+    assert_eq!(
+        evaluate_float("fadd.s fa0, fa1, fa2", single(1.5), single(2.25), 0),
+        (single(3.75), none)
+    );
+    assert_eq!(
+        evaluate_float("fdiv.d fa0, fa1, fa2", double(1.0), double(3.0), 0),
+        (double(1.0 / 3.0), inexact)
+    );
+    assert_eq!(
+        evaluate_float("fsqrt.d fa0, fa1", double(2.0), 0, 0),
+        (double(2.0_f64.sqrt()), inexact)
+    );
+    assert_eq!(
+        evaluate_float("fcvt.d.s fa0, fa1", single(0.1), 0, 0),
+        (double(0.1_f32 as f64), none)
+    );
+    assert_eq!(
+        evaluate_float("fcvt.s.d fa0, fa1", double(0.1), 0, 0),
+        (single(0.1), inexact)
+    );
+}
+
+#[test]
+fn fused_multiply_add_signs() {
+    let (a, b, c) = (double(2.0), double(3.0), double(1.0));
+    let fused = |source| evaluate_float(source, a, b, c).0;
+
+    assert_eq!(fused("fmadd.d fa0, fa1, fa2, fa3"), double(7.0));
+    assert_eq!(fused("fmsub.d fa0, fa1, fa2, fa3"), double(5.0));
+    assert_eq!(fused("fnmsub.d fa0, fa1, fa2, fa3"), double(-5.0));
+    assert_eq!(fused("fnmadd.d fa0, fa1, fa2, fa3"), double(-7.0));
+
+    // A single rounding: 0.1 * 10 - 1 is the error of rounding 0.1.
+    let (a, b, c) = (double(0.1), double(10.0), double(1.0));
+    let fused = evaluate_float("fmsub.d fa0, fa1, fa2, fa3", a, b, c).0;
+    assert_eq!(fused, double(0.1_f64.mul_add(10.0, -1.0)));
+    assert_ne!(fused, double(0.0));
+}
+
+#[test]
+fn rounding_mode_operand() {
+    let third = |source| evaluate_float(source, single(1.0), single(3.0), 0).0;
+
+    assert_eq!(third("fdiv.s fa0, fa1, fa2"), single(1.0 / 3.0));
+    assert_eq!(third("fdiv.s fa0, fa1, fa2, rup"), single(1.0 / 3.0));
+    assert_eq!(
+        third("fdiv.s fa0, fa1, fa2, rtz"),
+        single(f32::from_bits((1.0_f32 / 3.0).to_bits() - 1))
+    );
+}
+
+#[test]
+fn dynamic_rounding_mode() {
+    let fdiv = instruction("fdiv.s fa0, fa1, fa2");
+    let (one, three) = (single(1.0), single(3.0));
+
+    let mut fcsr = Fcsr {
+        frm: 2,
+        ..Fcsr::default()
+    };
+    let down = fdiv.evaluate_float(one, three, 0, &mut fcsr).unwrap();
+    assert_eq!(
+        down,
+        Ok(single(f32::from_bits((1.0_f32 / 3.0).to_bits() - 1)))
+    );
+
+    // A reserved frm traps without raising any flags.
+    let mut fcsr = Fcsr {
+        frm: 5,
+        ..Fcsr::default()
+    };
+    assert_eq!(
+        fdiv.evaluate_float(one, three, 0, &mut fcsr),
+        Some(Err(Error::InvalidRoundingMode { frm: 5 }))
+    );
+    assert_eq!(fcsr.fflags, ExceptionFlags::NONE);
+}
+
+#[test]
+fn flags_accrue() {
+    let mut fcsr = Fcsr::default();
+    let fdiv = instruction("fdiv.s fa0, fa1, fa2");
+
+    fdiv.evaluate_float(single(1.0), single(0.0), 0, &mut fcsr);
+    fdiv.evaluate_float(single(1.0), single(3.0), 0, &mut fcsr);
+    assert_eq!(
+        fcsr.fflags,
+        ExceptionFlags::DIVIDE_BY_ZERO.with(ExceptionFlags::INEXACT)
+    );
+}
+
+#[test]
+fn canonical_nans() {
+    let canonical_single = single(f32::from_bits(0x7fc0_0000));
+    let canonical_double = 0x7ff8_0000_0000_0000;
+    let invalid = ExceptionFlags::INVALID;
+
+    assert_eq!(
+        evaluate_float(
+            "fsub.d fa0, fa1, fa2",
+            double(f64::INFINITY),
+            double(f64::INFINITY),
+            0
+        ),
+        (canonical_double, invalid)
+    );
+    // NaN payloads are not propagated.
+    let payload = 0x7ff8_0000_dead_beef;
+    assert_eq!(
+        evaluate_float("fmul.d fa0, fa1, fa2", payload, double(1.0), 0),
+        (canonical_double, ExceptionFlags::NONE)
+    );
+    assert_eq!(
+        evaluate_float("fsqrt.s fa0, fa1", single(-1.0), 0, 0),
+        (canonical_single, invalid)
+    );
+    // A single-precision operand that is not NaN-boxed is the canonical NaN.
+    assert_eq!(
+        evaluate_float(
+            "fadd.s fa0, fa1, fa2",
+            1.0_f32.to_bits() as u64,
+            single(1.0),
+            0
+        ),
+        (canonical_single, ExceptionFlags::NONE)
+    );
+}
+
+#[test]
+fn sign_injection() {
+    let sign = |source| evaluate_float(source, double(2.0), double(-1.0), 0).0;
+
+    assert_eq!(sign("fsgnj.d fa0, fa1, fa2"), double(-2.0));
+    assert_eq!(sign("fsgnjn.d fa0, fa1, fa2"), double(2.0));
+    assert_eq!(sign("fsgnjx.d fa0, fa1, fa2"), double(-2.0));
+
+    // Sign injection does not canonicalize NaNs.
+    let nan = single(f32::from_bits(0xffc0_1234));
+    assert_eq!(
+        evaluate_float("fsgnj.s fa0, fa1, fa2", nan, single(1.0), 0),
+        (single(f32::from_bits(0x7fc0_1234)), ExceptionFlags::NONE)
+    );
+}
+
+#[test]
+fn comparisons_and_classification() {
+    assert_eq!(
+        evaluate_float("flt.s a0, fa1, fa2", single(1.0), single(2.0), 0).0,
+        1
+    );
+    assert_eq!(
+        evaluate_float("fle.d a0, fa1, fa2", double(f64::NAN), double(2.0), 0),
+        (0, ExceptionFlags::INVALID)
+    );
+    assert_eq!(
+        evaluate_float("fclass.s a0, fa1", single(-0.0), 0, 0).0,
+        1 << 3
+    );
+    assert_eq!(
+        evaluate_float("fmax.d fa0, fa1, fa2", double(-0.0), double(0.0), 0).0,
+        double(0.0)
+    );
+}
+
+#[test]
+fn integer_conversions() {
+    assert_eq!(
+        evaluate_float("fcvt.w.d a0, fa1, rtz", double(-2.9), 0, 0),
+        (-2_i32 as u32 as u64, ExceptionFlags::INEXACT)
+    );
+    assert_eq!(
+        evaluate_float("fcvt.wu.s a0, fa1", single(5e9), 0, 0),
+        (u32::MAX as u64, ExceptionFlags::INVALID)
+    );
+    assert_eq!(
+        evaluate_float("fcvt.s.w fa0, a1", -7_i32 as u32 as u64, 0, 0).0,
+        single(-7.0)
+    );
+    assert_eq!(
+        evaluate_float("fcvt.d.wu fa0, a1", u32::MAX as u64, 0, 0).0,
+        double(u32::MAX as f64)
+    );
+}
+
+#[test]
+fn bit_moves() {
+    // fmv.x.w copies the low 32 bits, whether or not they are NaN-boxed.
+    assert_eq!(
+        evaluate_float("fmv.x.w a0, fa1", 0x1234_5678_9abc_def0, 0, 0).0,
+        0x9abc_def0
+    );
+    assert_eq!(
+        evaluate_float("fmv.w.x fa0, a1", 0x7f80_0001, 0, 0),
+        (0xffff_ffff_7f80_0001, ExceptionFlags::NONE)
+    );
+}
+
+#[test]
+fn floating_point_csrs() {
+    let mut fcsr = Fcsr::default();
+    let access = |source, fcsr: &mut Fcsr, value| instruction(source).access_fcsr(fcsr, value);
+
+    assert_eq!(access("csrrw a0, frm, a1", &mut fcsr, 3), Some(0));
+    assert_eq!(access("csrrsi a0, fflags, 0x11", &mut fcsr, 0x11), Some(0));
+    assert_eq!(access("csrrs a0, fcsr, zero", &mut fcsr, 0xff), Some(0x71));
+    assert_eq!(
+        access("csrrci a0, fflags, 0x01", &mut fcsr, 0x01),
+        Some(0x11)
+    );
+    assert_eq!(access("csrrc a0, fcsr, zero", &mut fcsr, 0xff), Some(0x70));
+    assert_eq!(fcsr.frm, 3);
+    assert_eq!(fcsr.fflags, ExceptionFlags::INVALID);
+
+    // Other CSRs and instructions are not floating-point CSR accesses.
+    assert_eq!(access("rdcycle a0", &mut fcsr, 0), None);
+    assert_eq!(access("add a0, a1, a2", &mut fcsr, 0), None);
+}
+
+#[test]
+fn integer_instructions_are_not_floating_point() {
+    let mut fcsr = Fcsr::default();
+
+    assert_eq!(
+        instruction("add a0, a1, a2").evaluate_float(1, 2, 3, &mut fcsr),
+        None
+    );
+    assert_eq!(instruction("fadd.s fa0, fa1, fa2").evaluate(1, 2), None);
+    // Loads and stores move bits through memory rather than being evaluated.
+    assert_eq!(
+        instruction("flw fa0, 0(a1)").evaluate_float(1, 2, 3, &mut fcsr),
+        None
+    );
+}
@@ -0,0 +1,110 @@
+use crate::instruction::RiscVInstruction;
+use crate::register::Register;
+
+#[test]
+fn forward_branch() {
+    let beq = RiscVInstruction::Beq {
+        rs1: Register::X1,
+        rs2: Register::X2,
+        imm: 8,
+    };
+    assert_eq!(beq.branch_target(0x100), Some(0x108));
+}
+
+#[test]
+fn backward_branch() {
+    let bne = RiscVInstruction::Bne {
+        rs1: Register::X1,
+        rs2: Register::X0,
+        imm: -4,
+    };
+    assert_eq!(bne.branch_target(0x100), Some(0xfc));
+}
+
+#[test]
+fn branch_to_self() {
+    let blt = RiscVInstruction::Blt {
+        rs1: Register::X1,
+        rs2: Register::X2,
+        imm: 0,
+    };
+    assert_eq!(blt.branch_target(0x40), Some(0x40));
+}
+
+#[test]
+fn wraps_below_zero() {
+    let bge = RiscVInstruction::Bge {
+        rs1: Register::X1,
+        rs2: Register::X2,
+        imm: -4096,
+    };
+    assert_eq!(bge.branch_target(0), Some(0xfffff000));
+}
+
+#[test]
+fn wraps_above_max() {
+    let bltu = RiscVInstruction::Bltu {
+        rs1: Register::X1,
+        rs2: Register::X2,
+        imm: 4094,
+    };
+    assert_eq!(bltu.branch_target(0xffffffff), Some(4093));
+}
+
+#[test]
+fn decoded_branch() {
+    // bgeu x1, x2, -8
+    let bgeu = RiscVInstruction::decode(0xfe20fce3).unwrap();
+    assert_eq!(bgeu.branch_target(0x1000), Some(0xff8));
+}
+
+#[test]
+fn not_a_branch() {
+    let add = RiscVInstruction::Add {
+        rd: Register::X1,
+        rs1: Register::X2,
+        rs2: Register::X3,
+    };
+    assert_eq!(add.branch_target(0x100), None);
+
+    let jalr = RiscVInstruction::Jalr {
+        rd: Register::X0,
+        rs1: Register::X1,
+        imm: 8,
+    };
+    assert_eq!(jalr.branch_target(0x100), None);
+}
+
+#[test]
+fn forward_jal() {
+    let jal = RiscVInstruction::Jal {
+        rd: Register::X1,
+        imm: 2048,
+    };
+    assert_eq!(jal.branch_target(0x100), Some(0x900));
+}
+
+#[test]
+fn backward_jal() {
+    let jal = RiscVInstruction::Jal {
+        rd: Register::X0,
+        imm: -8,
+    };
+    assert_eq!(jal.branch_target(0x100), Some(0xf8));
+}
+
+#[test]
+fn jal_wraps_below_zero() {
+    let jal = RiscVInstruction::Jal {
+        rd: Register::X0,
+        imm: -1048576,
+    };
+    assert_eq!(jal.branch_target(0), Some(0xfff00000));
+}
+
+#[test]
+fn decoded_jal() {
+    // jal x1, -2
+    let jal = RiscVInstruction::decode(0xfffff0ef).unwrap();
+    assert_eq!(jal.branch_target(0x1000), Some(0xffe));
+}
@@ -1 +1,18 @@
+mod aarch64;
+mod assembler;
+mod atomic;
+mod csr;
+mod custom;
+mod disassembler;
+mod float;
+mod hart;
+mod instance;
 mod instruction;
+mod module;
+#[cfg(any(target_arch = "aarch64", target_arch = "x86_64"))]
+mod native;
+mod profile;
+mod pseudo;
+mod register;
+mod stream;
+mod x86_64;
@@ -0,0 +1,255 @@
+use crate::assembler::assemble;
+use crate::custom::CustomInstructions;
+use crate::error::{DecodeError, DecodeErrorKind, Error};
+use crate::extension::{Extension, Extensions};
+use crate::profile::Profile;
+use crate::register::Register;
+use crate::{Config, Engine, Module};
+
+fn syscall(_args: &[u32], _context: u64) -> u32 {
+    0
+}
+
+fn time(_context: u64) -> u64 {
+    0
+}
+
+fn module() -> Box<Module> {
+    module_for(Profile::Rv32i)
+}
+
+fn module_for(profile: Profile) -> Box<Module> {
+    module_with(profile, Extensions::NONE)
+}
+
+fn module_with(profile: Profile, extensions: Extensions) -> Box<Module> {
+    let engine = Engine::new(Config {
+        syscall,
+        max_instance_memory: 0x10000,
+        max_code_size: 0x100,
+        profile,
+        extensions,
+        time,
+        custom: CustomInstructions::NONE,
+    });
+    Module::new(engine).unwrap()
+}
+
+#[test]
+fn code_too_large() {
+    let mut module = module();
+    let code = vec![0; 0x104];
+
+    assert_eq!(module.set_riscv_code(&code), Err(Error::InvalidCodeSize));
+}
+
+#[test]
+fn invalid_instruction_offset() {
+    let mut module = module();
+    let mut code = vec![];
+    code.extend(0x003100b3_u32.to_le_bytes()); // add x1, x2, x3
+    code.extend(0x0001_u16.to_le_bytes()); // c.nop
+    code.extend(0x203100b3_u32.to_le_bytes()); // add with a reserved funct7
+
+    assert_eq!(
+        module.set_riscv_code(&code),
+        Err(Error::InvalidInstruction {
+            offset: 6,
+            error: DecodeError {
+                word: 0x203100b3,
+                kind: DecodeErrorKind::ReservedFunct7,
+            },
+        })
+    );
+}
+
+#[test]
+fn truncated_instruction() {
+    let mut module = module();
+    let mut code = vec![];
+    code.extend(0x0001_u16.to_le_bytes()); // c.nop
+    code.extend(0x0013_u16.to_le_bytes()); // first half of addi x0, x0, 0
+
+    assert_eq!(
+        module.set_riscv_code(&code),
+        Err(Error::InvalidInstruction {
+            offset: 2,
+            error: DecodeError {
+                word: 0x0013,
+                kind: DecodeErrorKind::Truncated,
+            },
+        })
+    );
+}
+
+#[test]
+fn fence_i_rejected_without_zifencei() {
+    let mut module = module();
+    let mut code = vec![];
+    code.extend(0x0ff0000f_u32.to_le_bytes()); // fence iorw, iorw
+    code.extend(0x0000100f_u32.to_le_bytes()); // fence.i
+
+    assert_eq!(
+        module.set_riscv_code(&code),
+        Err(Error::InvalidInstruction {
+            offset: 4,
+            error: DecodeError {
+                word: 0x0000100f,
+                kind: DecodeErrorKind::UnsupportedExtension(Extension::Zifencei),
+            },
+        })
+    );
+}
+
+#[test]
+fn register_outside_profile() {
+    let mut module = module_for(Profile::Rv32e);
+    let mut code = vec![];
+    code.extend(0x00178793_u32.to_le_bytes()); // addi x15, x15, 1
+    code.extend(0x8806_u16.to_le_bytes()); // c.mv x16, x1
+
+    assert_eq!(
+        module.set_riscv_code(&code),
+        Err(Error::InvalidInstruction {
+            offset: 4,
+            error: DecodeError {
+                word: 0x8806,
+                kind: DecodeErrorKind::UnsupportedRegister {
+                    register: Register::X16,
+                    profile: Profile::Rv32e,
+                },
+            },
+        })
+    );
+}
+
+#[test]
+fn extension_not_enabled() {
+    let mut module = module();
+    let mut code = vec![];
+    code.extend(0x0001_u16.to_le_bytes()); // c.nop
+    code.extend(0x403170b3_u32.to_le_bytes()); // andn x1, x2, x3
+
+    assert_eq!(
+        module.set_riscv_code(&code),
+        Err(Error::InvalidInstruction {
+            offset: 2,
+            error: DecodeError {
+                word: 0x403170b3,
+                kind: DecodeErrorKind::UnsupportedExtension(Extension::Zbb),
+            },
+        })
+    );
+}
+
+#[test]
+fn rv64_instructions_rejected_for_rv32() {
+    let mut module = module();
+    let code = 0x00c5853b_u32.to_le_bytes(); // addw x10, x11, x12
+
+    assert_eq!(
+        module.set_riscv_code(&code),
+        Err(Error::InvalidInstruction {
+            offset: 0,
+            error: DecodeError {
+                word: 0x00c5853b,
+                kind: DecodeErrorKind::UnknownOpcode,
+            },
+        })
+    );
+}
+
+#[test]
+fn rv64_invalid_instruction_offset() {
+    let mut module = module_for(Profile::Rv64i);
+    let mut code = vec![];
+    code.extend(0x7de8_u16.to_le_bytes()); // c.ld x10, 248(x11)
+    code.extend(0x00c5853b_u32.to_le_bytes()); // addw x10, x11, x12
+    code.extend(0x60059513_u32.to_le_bytes()); // clz x10, x11
+
+    assert_eq!(
+        module.set_riscv_code(&code),
+        Err(Error::InvalidInstruction {
+            offset: 6,
+            error: DecodeError {
+                word: 0x60059513,
+                kind: DecodeErrorKind::UnsupportedExtension(Extension::Zbb),
+            },
+        })
+    );
+}
+
+#[test]
+fn unregistered_custom_instruction() {
+    let mut module = module();
+    let mut code = vec![];
+    code.extend(0x003100b3_u32.to_le_bytes()); // add x1, x2, x3
+    code.extend(0x04c5950b_u32.to_le_bytes()); // .insn r 0x0b, 1, 2, x10, x11, x12
+
+    assert_eq!(
+        module.set_riscv_code(&code),
+        Err(Error::InvalidInstruction {
+            offset: 4,
+            error: DecodeError {
+                word: 0x04c5950b,
+                kind: DecodeErrorKind::UnregisteredCustom,
+            },
+        })
+    );
+}
+
+#[test]
+fn extension_instructions_are_loaded() {
+    let mut module = module_with(Profile::Rv32i, Extensions::ALL);
+    let mut code = vec![];
+    code.extend(0x003100b3_u32.to_le_bytes()); // add x1, x2, x3
+    code.extend(0xc0002573_u32.to_le_bytes()); // rdcycle x10
+
+    assert_eq!(module.set_riscv_code(&code), Ok(()));
+}
+
+#[cfg(any(target_arch = "aarch64", target_arch = "x86_64"))]
+#[test]
+fn riscv_code_is_translated() {
+    let mut module = module();
+    let code = assemble("addi a0, a0, 1\nret").unwrap().bytes;
+
+    assert_eq!(module.set_riscv_code(&code), Ok(()));
+    assert!(!module.native_code().is_empty());
+    assert!(module.native_offset(0).is_some());
+    assert_eq!(module.native_offset(2), None);
+    assert!(module.native_offset(4).is_some());
+    assert_eq!(module.native_offset(8), None);
+}
+
+#[cfg(not(any(target_arch = "aarch64", target_arch = "x86_64")))]
+#[test]
+fn riscv_code_is_interpreted() {
+    let mut module = module();
+    let code = assemble("addi a0, a0, 1\nret").unwrap().bytes;
+
+    assert_eq!(module.set_riscv_code(&code), Ok(()));
+    assert!(module.native_code().is_empty());
+    assert!(module.program.is_some());
+    assert_eq!(module.native_offset(0), None);
+}
+
+#[test]
+fn largest_code_fits_in_native_code() {
+    let mut module = module();
+    // `c.jalr a0` translates to the most native code per byte.
+    let code = [0x9502_u16.to_le_bytes(); 0x80].concat();
+
+    assert_eq!(module.set_riscv_code(&code), Ok(()));
+}
+
+#[test]
+fn native_code_replaces_riscv_code() {
+    let mut module = module();
+    let code = assemble("ret").unwrap().bytes;
+    module.set_riscv_code(&code).unwrap();
+    module.set_native_code(&[0; 4]).unwrap();
+
+    assert!(module.program.is_none());
+    assert_eq!(module.native_offset(0), None);
+}
@@ -0,0 +1,298 @@
+use crate::assembler::assemble;
+use crate::custom::CustomInstructions;
+use crate::error::Error;
+use crate::extension::Extensions;
+use crate::hart::{Hart, RETURN_ADDRESS};
+use crate::profile::Profile;
+use crate::program::Program;
+use crate::register::Register;
+use crate::{Config, Engine, Instance, Memory, Module};
+
+fn syscall(args: &[u32], context: u64) -> u32 {
+    args.iter().sum::<u32>() + context as u32
+}
+
+fn config(profile: Profile) -> Config {
+    Config {
+        syscall,
+        max_instance_memory: 0x1000,
+        max_code_size: 0x1000,
+        profile,
+        extensions: Extensions::NONE,
+        time: |_context| 0,
+        custom: CustomInstructions::NONE,
+    }
+}
+
+/// The address the operands of the tested programs are loaded from.
+const OPERANDS: usize = 0x700;
+
+/// The result, gas and memory of a call.
+type Outcome = (Result<u64, Error>, u64, Vec<u8>);
+
+/// Calls the function at the start of `source` through the native backend of the host.
+fn native(profile: Profile, source: &str, memory: &[u8], arg: u64, gas: u64) -> Outcome {
+    let engine = Engine::new(config(profile));
+    let mut module = Module::new(engine.clone()).unwrap();
+    module
+        .set_riscv_code(&assemble(source).unwrap().bytes)
+        .unwrap();
+    assert!(module.native_offset(0).is_some());
+
+    let mut instance = Instance::new(module, Memory::new(engine)).unwrap();
+    instance.memory_mut().data_mut().copy_from_slice(memory);
+    instance.set_gas(gas);
+    instance.set_context(7);
+    let result = match profile.xlen() {
+        64 => instance.call64(0, arg),
+        _ => instance.call(0, arg as u32).map(u64::from),
+    };
+
+    (result, instance.gas(), instance.memory().data().to_vec())
+}
+
+/// Calls the function at the start of `source` on the interpreter alone.
+fn interpreted(profile: Profile, source: &str, memory: &[u8], arg: u64, gas: u64) -> Outcome {
+    let config = config(profile);
+    let program = Program::decode(&assemble(source).unwrap().bytes, &config).unwrap();
+    let mut memory = memory.to_vec();
+    let mut hart = Hart::new(profile, &mut memory, 0, arg, gas, 7);
+
+    let result = loop {
+        if hart.pc == RETURN_ADDRESS {
+            break Ok(hart.read(Register::A0));
+        }
+        let Some((instruction, length)) = program.get(hart.pc) else {
+            break Err(Error::InvalidJump { target: hart.pc });
+        };
+        if let Err(error) = hart.execute(instruction, length, &config) {
+            break Err(error);
+        }
+    };
+
+    let gas = hart.gas;
+    (result, gas, memory)
+}
+
+/// Asserts that native code and the interpreter agree on every call of `source`
+/// with each pair of `operands`, which it finds at `OPERANDS`.
+fn assert_agree(profile: Profile, source: &str, operands: &[u64], gas: u64) {
+    let width = profile.xlen() as usize / 8;
+    for &a in operands {
+        for &b in operands {
+            let mut memory = vec![0; 0x1000];
+            memory[OPERANDS..OPERANDS + width].copy_from_slice(&a.to_le_bytes()[..width]);
+            memory[OPERANDS + width..OPERANDS + 2 * width]
+                .copy_from_slice(&b.to_le_bytes()[..width]);
+
+            assert_eq!(
+                native(profile, source, &memory, a, gas),
+                interpreted(profile, source, &memory, a, gas),
+                "{source} with {a:#x} and {b:#x}"
+            );
+        }
+    }
+}
+
+/// A function that loads two operands into `a0` and `a1`, and stores the result of
+/// every instruction of `instructions`, which compute `t0`, to memory in turn.
+fn results(profile: Profile, instructions: &[&str]) -> String {
+    let (load, store, width) = match profile.xlen() {
+        64 => ("ld", "sd", 8),
+        _ => ("lw", "sw", 4),
+    };
+    let second = OPERANDS + width;
+    let mut source = format!("{load} a0, {OPERANDS}(zero)\n{load} a1, {second}(zero)\n");
+    for (index, instruction) in instructions.iter().enumerate() {
+        source += &format!("{instruction}\n{store} t0, {}(zero)\n", index * width);
+    }
+    source + "ret"
+}
+
+const RV32_OPERANDS: [u64; 10] = [
+    0,
+    1,
+    7,
+    31,
+    33,
+    0x1234_5678,
+    0x7fff_ffff,
+    0x8000_0000,
+    0xffff_fff9,
+    0xffff_ffff,
+];
+
+const RV64_OPERANDS: [u64; 10] = [
+    0,
+    1,
+    63,
+    65,
+    0x7fff_ffff,
+    0x8000_0000,
+    0x1234_5678_9abc_def0,
+    0x7fff_ffff_ffff_ffff,
+    0x8000_0000_0000_0000,
+    u64::MAX,
+];
+
+const BASE: [&str; 23] = [
+    "add t0, a0, a1",
+    "sub t0, a0, a1",
+    "xor t0, a0, a1",
+    "or t0, a0, a1",
+    "and t0, a0, a1",
+    "sll t0, a0, a1",
+    "srl t0, a0, a1",
+    "sra t0, a0, a1",
+    "slt t0, a0, a1",
+    "sltu t0, a0, a1",
+    "addi t0, a0, -2048",
+    "addi t0, a0, 2047",
+    "xori t0, a0, -1",
+    "ori t0, a0, 0x7f0",
+    "andi t0, a0, -16",
+    "slti t0, a0, -1",
+    "sltiu t0, a0, -1",
+    "slli t0, a0, 31",
+    "srli t0, a0, 1",
+    "srai t0, a0, 31",
+    "lui t0, 0xfffff",
+    "auipc t0, 0x80000",
+    "li t0, 5",
+];
+
+const MULTIPLY: [&str; 8] = [
+    "mul t0, a0, a1",
+    "mulh t0, a0, a1",
+    "mulhu t0, a0, a1",
+    "mulhsu t0, a0, a1",
+    "div t0, a0, a1",
+    "divu t0, a0, a1",
+    "rem t0, a0, a1",
+    "remu t0, a0, a1",
+];
+
+const WORD: [&str; 15] = [
+    "addw t0, a0, a1",
+    "subw t0, a0, a1",
+    "sllw t0, a0, a1",
+    "srlw t0, a0, a1",
+    "sraw t0, a0, a1",
+    "mulw t0, a0, a1",
+    "divw t0, a0, a1",
+    "divuw t0, a0, a1",
+    "remw t0, a0, a1",
+    "remuw t0, a0, a1",
+    "addiw t0, a0, -1",
+    "slliw t0, a0, 31",
+    "srliw t0, a0, 1",
+    "sraiw t0, a0, 31",
+    "slli t0, a0, 63",
+];
+
+#[test]
+fn arithmetic_agrees_with_the_interpreter() {
+    let rv32 = results(Profile::Rv32i, &[&BASE[..], &MULTIPLY].concat());
+    assert_agree(Profile::Rv32i, &rv32, &RV32_OPERANDS, 1000);
+
+    let rv64 = results(Profile::Rv64i, &[&BASE[..], &MULTIPLY, &WORD].concat());
+    assert_agree(Profile::Rv64i, &rv64, &RV64_OPERANDS, 1000);
+}
+
+#[test]
+fn branches_agree_with_the_interpreter() {
+    for branch in ["beq", "bne", "blt", "bge", "bltu", "bgeu"] {
+        let source = format!(
+            "
+            lw a0, {OPERANDS}(zero)
+            lw a1, {}(zero)
+            {branch} a0, a1, taken
+            li a0, 1
+            ret
+        taken:
+            li a0, 2
+            ret
+            ",
+            OPERANDS + 4
+        );
+        assert_agree(Profile::Rv32i, &source, &RV32_OPERANDS, 1000);
+    }
+}
+
+#[test]
+fn memory_accesses_agree_with_the_interpreter() {
+    let source = results(
+        Profile::Rv64i,
+        &[
+            "sd a1, 0x100(zero)\nlb t0, 0x107(zero)",
+            "lbu t0, 0x107(zero)",
+            "lh t0, 0x106(zero)",
+            "lhu t0, 0x106(zero)",
+            "lw t0, 0x104(zero)",
+            "lwu t0, 0x104(zero)",
+            "ld t0, 0x100(zero)",
+            "sb a0, 0x110(zero)\nsh a0, 0x112(zero)\nsw a0, 0x114(zero)\nld t0, 0x110(zero)",
+        ],
+    );
+    assert_agree(Profile::Rv64i, &source, &RV64_OPERANDS, 1000);
+
+    // Accesses at the edges of memory, and beyond them.
+    for offset in [-8, -4, -3, -1, 0, 1] {
+        let source = format!("addi a1, a0, {offset}\nsw a0, 0(a1)\nlb a0, 3(a1)\nret");
+        assert_agree(
+            Profile::Rv32i,
+            &source,
+            &[0x1000, 0xfff, 0, 0xffff_ffff],
+            1000,
+        );
+    }
+}
+
+#[test]
+fn jumps_agree_with_the_interpreter() {
+    let source = "
+        mv s0, ra
+        la a1, double
+        add a1, a1, a0
+        jalr a1
+        mv ra, s0
+        jr ra
+    double:
+        add a0, a0, a0
+        ret
+        ret
+    ";
+    // Jumps to `double`, past it, into the middle of an instruction, and beyond
+    // the code.
+    assert_agree(Profile::Rv32i, source, &[0, 1, 2, 4, 5, 0x1000], 1000);
+}
+
+#[test]
+fn gas_agrees_with_the_interpreter() {
+    let source = "
+        li a1, 0
+    loop:
+        add a1, a1, a0
+        addi a0, a0, -1
+        bnez a0, loop
+        lw a2, 0(zero)
+        ecall
+        mv a0, a1
+        ret
+    ";
+    for gas in 0..40 {
+        assert_agree(Profile::Rv32i, source, &[5], gas);
+    }
+}
+
+#[test]
+fn syscalls_agree_with_the_interpreter() {
+    let source = "
+        mv a1, a0
+        li a7, 3
+        ecall
+        addi a0, a0, 1
+        ebreak
+    ";
+    assert_agree(Profile::Rv32i, source, &[0, 40], 1000);
+}
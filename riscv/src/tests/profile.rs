@@ -0,0 +1,38 @@
+use crate::profile::Profile;
+use crate::register::Register;
+
+#[test]
+fn full_register_file() {
+    for number in 0..32 {
+        assert!(Profile::Rv32i.contains(Register::new(number).unwrap()));
+    }
+}
+
+#[test]
+fn embedded_register_file() {
+    for number in 0..16 {
+        assert!(Profile::Rv32e.contains(Register::new(number).unwrap()));
+    }
+    for number in 16..32 {
+        assert!(!Profile::Rv32e.contains(Register::new(number).unwrap()));
+    }
+}
+
+#[test]
+fn rv64i_register_file() {
+    for number in 0..32 {
+        assert!(Profile::Rv64i.contains(Register::new(number).unwrap()));
+    }
+}
+
+#[test]
+fn register_width() {
+    assert_eq!(Profile::Rv32i.xlen(), 32);
+    assert_eq!(Profile::Rv32e.xlen(), 32);
+    assert_eq!(Profile::Rv64i.xlen(), 64);
+}
+
+#[test]
+fn default_is_rv32i() {
+    assert_eq!(Profile::default(), Profile::Rv32i);
+}
@@ -0,0 +1,380 @@
+use crate::error::Error;
+use crate::extension::Extensions;
+use crate::instruction::RiscVInstruction;
+use crate::pseudo::{PrintedInstruction, Pseudo, PseudoPrinter};
+use crate::register::{Register, Syntax};
+
+/// Decodes a sequence of instruction words.
+fn decode_all(words: &[u32]) -> Vec<RiscVInstruction> {
+    words
+        .iter()
+        .map(|word| RiscVInstruction::decode(*word).unwrap())
+        .collect()
+}
+
+/// Prints a sequence of instruction words with ABI register names.
+fn print_abi(words: &[u32]) -> Vec<String> {
+    let instructions = decode_all(words);
+    PseudoPrinter::new(&instructions, Syntax::Abi)
+        .map(|printed| printed.text)
+        .collect()
+}
+
+#[test]
+fn nop() {
+    // addi x0, x0, 0
+    assert_eq!(print_abi(&[0x00000013]), ["nop"]);
+}
+
+#[test]
+fn li_small() {
+    // addi a0, x0, 5
+    assert_eq!(print_abi(&[0x00500513]), ["li a0, 5"]);
+
+    // addi a0, x0, -1
+    assert_eq!(print_abi(&[0xfff00513]), ["li a0, -1"]);
+}
+
+#[test]
+fn li_lui_addi() {
+    // lui a0, 0x12345; addi a0, a0, 0x678
+    assert_eq!(print_abi(&[0x12345537, 0x67850513]), ["li a0, 305419896"]);
+}
+
+#[test]
+fn li_lui_addi_negative_low() {
+    // lui a0, 0x12346; addi a0, a0, -0x800
+    assert_eq!(
+        print_abi(&[0x12346537, 0x80050513]),
+        [format!("li a0, {}", 0x12345800)]
+    );
+}
+
+#[test]
+fn lui_addi_different_registers() {
+    // lui a0, 0x12345; addi a1, a0, 1
+    assert_eq!(
+        print_abi(&[0x12345537, 0x00150593]),
+        ["lui a0, 0x12345", "addi a1, a0, 1"]
+    );
+}
+
+#[test]
+fn lui_alone() {
+    // lui a0, 0x12345
+    assert_eq!(print_abi(&[0x12345537]), ["lui a0, 0x12345"]);
+}
+
+#[test]
+fn mv() {
+    // addi a0, a1, 0
+    assert_eq!(print_abi(&[0x00058513]), ["mv a0, a1"]);
+}
+
+#[test]
+fn not_and_neg() {
+    // xori a0, a1, -1
+    assert_eq!(print_abi(&[0xfff5c513]), ["not a0, a1"]);
+
+    // sub a0, x0, a1
+    assert_eq!(print_abi(&[0x40b00533]), ["neg a0, a1"]);
+}
+
+#[test]
+fn set_comparisons() {
+    // sltiu a0, a1, 1
+    assert_eq!(print_abi(&[0x0015b513]), ["seqz a0, a1"]);
+
+    // sltu a0, x0, a1
+    assert_eq!(print_abi(&[0x00b03533]), ["snez a0, a1"]);
+
+    // slt a0, a1, x0
+    assert_eq!(print_abi(&[0x0005a533]), ["sltz a0, a1"]);
+
+    // slt a0, x0, a1
+    assert_eq!(print_abi(&[0x00b02533]), ["sgtz a0, a1"]);
+}
+
+#[test]
+fn branches_against_zero() {
+    // beq a0, x0, 8
+    assert_eq!(print_abi(&[0x00050463]), ["beqz a0, 8"]);
+
+    // bne a0, x0, -8
+    assert_eq!(print_abi(&[0xfe051ce3]), ["bnez a0, -8"]);
+
+    // bge x0, a0, 8
+    assert_eq!(print_abi(&[0x00a05463]), ["blez a0, 8"]);
+
+    // bge a0, x0, 8
+    assert_eq!(print_abi(&[0x00055463]), ["bgez a0, 8"]);
+
+    // blt a0, x0, 8
+    assert_eq!(print_abi(&[0x00054463]), ["bltz a0, 8"]);
+
+    // blt x0, a0, 8
+    assert_eq!(print_abi(&[0x00a04463]), ["bgtz a0, 8"]);
+}
+
+#[test]
+fn jumps() {
+    // jal x0, -4
+    assert_eq!(print_abi(&[0xffdff06f]), ["j -4"]);
+
+    // jal ra, 16
+    assert_eq!(print_abi(&[0x010000ef]), ["jal 16"]);
+
+    // jalr x0, a0, 0
+    assert_eq!(print_abi(&[0x00050067]), ["jr a0"]);
+
+    // jalr ra, a0, 0
+    assert_eq!(print_abi(&[0x000500e7]), ["jalr a0"]);
+
+    // jalr x0, ra, 0
+    assert_eq!(print_abi(&[0x00008067]), ["ret"]);
+}
+
+#[test]
+fn jal_other_register() {
+    // jal t0, 16
+    assert_eq!(print_abi(&[0x010002ef]), ["jal t0, 16"]);
+}
+
+#[test]
+fn call() {
+    // auipc ra, 0x1; jalr ra, ra, -4
+    assert_eq!(print_abi(&[0x00001097, 0xffc080e7]), ["call 4092"]);
+}
+
+#[test]
+fn tail() {
+    // auipc t1, 0x0; jalr x0, t1, 24
+    assert_eq!(print_abi(&[0x00000317, 0x01830067]), ["tail 24"]);
+}
+
+#[test]
+fn auipc_jalr_different_registers() {
+    // auipc ra, 0x1; jalr ra, t0, 0
+    assert_eq!(
+        print_abi(&[0x00001097, 0x000280e7]),
+        ["auipc ra, 0x1", "jalr t0"]
+    );
+}
+
+#[test]
+fn counter_reads() {
+    let words = [
+        0xc0002573, // csrrs a0, cycle, zero
+        0xc80025f3, // csrrs a1, cycleh, zero
+        0xc0102573, // csrrs a0, time, zero
+        0xc8102573, // csrrs a0, timeh, zero
+        0xc0202573, // csrrs a0, instret, zero
+        0xc8202573, // csrrs a0, instreth, zero
+    ];
+    let instructions: Vec<_> = words
+        .iter()
+        .map(|word| RiscVInstruction::decode_with(*word, Extensions::ALL).unwrap())
+        .collect();
+
+    let printed: Vec<_> = PseudoPrinter::new(&instructions, Syntax::Abi)
+        .map(|printed| printed.text)
+        .collect();
+
+    assert_eq!(
+        printed,
+        [
+            "rdcycle a0",
+            "rdcycleh a1",
+            "rdtime a0",
+            "rdtimeh a0",
+            "rdinstret a0",
+            "rdinstreth a0"
+        ]
+    );
+}
+
+#[test]
+fn csr_read_and_write() {
+    let instructions = [
+        RiscVInstruction::Csrrs {
+            rd: Register::A0,
+            rs1: Register::ZERO,
+            csr: 0x340,
+        },
+        RiscVInstruction::Csrrw {
+            rd: Register::ZERO,
+            rs1: Register::A1,
+            csr: 0xc00,
+        },
+        RiscVInstruction::Csrrs {
+            rd: Register::A0,
+            rs1: Register::A1,
+            csr: 0xc00,
+        },
+    ];
+
+    let printed: Vec<_> = PseudoPrinter::new(&instructions, Syntax::Abi)
+        .map(|printed| printed.text)
+        .collect();
+
+    assert_eq!(
+        printed,
+        ["csrr a0, 0x340", "csrw cycle, a1", "csrrs a0, cycle, a1"]
+    );
+}
+
+#[test]
+fn raw_instructions_are_kept() {
+    // add a0, a1, a2
+    assert_eq!(print_abi(&[0x00c58533]), ["add a0, a1, a2"]);
+
+    // beq a0, a1, 8
+    assert_eq!(print_abi(&[0x00b50463]), ["beq a0, a1, 8"]);
+}
+
+#[test]
+fn numeric_syntax() {
+    let instructions = decode_all(&[0x00058513, 0x00008067]);
+    let printed: Vec<_> = PseudoPrinter::new(&instructions, Syntax::Numeric)
+        .map(|printed| printed.text)
+        .collect();
+
+    assert_eq!(printed, ["mv x10, x11", "ret"]);
+}
+
+#[test]
+fn indices_and_counts() {
+    // li a0, 0x12345678; call 8; nop
+    let instructions = decode_all(&[0x12345537, 0x67850513, 0x00000097, 0x008080e7, 0x00000013]);
+    let printed: Vec<_> = PseudoPrinter::new(&instructions, Syntax::Abi).collect();
+
+    assert_eq!(
+        printed,
+        vec![
+            PrintedInstruction {
+                index: 0,
+                count: 2,
+                text: String::from("li a0, 305419896"),
+            },
+            PrintedInstruction {
+                index: 2,
+                count: 2,
+                text: String::from("call 8"),
+            },
+            PrintedInstruction {
+                index: 4,
+                count: 1,
+                text: String::from("nop"),
+            },
+        ]
+    );
+}
+
+#[test]
+fn empty() {
+    assert_eq!(PseudoPrinter::new(&[], Syntax::Abi).next(), None);
+}
+
+#[test]
+fn recognize() {
+    let ret = RiscVInstruction::Jalr {
+        rd: Register::ZERO,
+        rs1: Register::RA,
+        imm: 0,
+    };
+    assert_eq!(Pseudo::recognize(&[ret]), Some(Pseudo::Ret));
+
+    let add = RiscVInstruction::Add {
+        rd: Register::A0,
+        rs1: Register::A1,
+        rs2: Register::A2,
+    };
+    assert_eq!(Pseudo::recognize(&[add, ret]), None);
+    assert_eq!(Pseudo::recognize(&[]), None);
+}
+
+#[test]
+fn display_defaults_to_numeric() {
+    let mv = Pseudo::Mv {
+        rd: Register::A0,
+        rs: Register::SP,
+    };
+    assert_eq!(format!("{}", mv), "mv x10, x2");
+    assert_eq!(format!("{}", mv.display(Syntax::Abi)), "mv a0, sp");
+}
+
+#[test]
+fn instruction_display_is_unchanged() {
+    // The plain Display implementation never substitutes pseudo-instructions.
+    let ret = RiscVInstruction::decode(0x00008067).unwrap();
+    assert_eq!(format!("{}", ret), "jalr x0, x1, 0");
+}
+
+#[test]
+fn expand_round_trips() {
+    let pseudos = [
+        Pseudo::Nop,
+        Pseudo::Li {
+            rd: Register::A0,
+            imm: -2048,
+        },
+        Pseudo::Li {
+            rd: Register::A0,
+            imm: 0x12345678,
+        },
+        Pseudo::Li {
+            rd: Register::A0,
+            imm: 0x10000,
+        },
+        Pseudo::Li {
+            rd: Register::A0,
+            imm: i32::MIN,
+        },
+        Pseudo::Mv {
+            rd: Register::A0,
+            rs: Register::A1,
+        },
+        Pseudo::Not {
+            rd: Register::A0,
+            rs: Register::A1,
+        },
+        Pseudo::Bgtz {
+            rs: Register::A0,
+            offset: -4096,
+        },
+        Pseudo::J { offset: 0xffffe },
+        Pseudo::Jalr { rs: Register::T0 },
+        Pseudo::Ret,
+        Pseudo::Call { offset: 0x12345800 },
+        Pseudo::Tail { offset: -4 },
+        Pseudo::Rdcycle { rd: Register::A0 },
+        Pseudo::Rdtimeh { rd: Register::A0 },
+        Pseudo::Rdinstret { rd: Register::A0 },
+        Pseudo::Csrr {
+            rd: Register::A0,
+            csr: 0x340,
+        },
+        Pseudo::Csrw {
+            csr: 0xfff,
+            rs: Register::A1,
+        },
+    ];
+
+    for pseudo in pseudos {
+        let instructions = pseudo.expand().unwrap();
+        assert_eq!(instructions.len(), pseudo.instruction_count(), "{}", pseudo);
+        assert_eq!(Pseudo::recognize(&instructions), Some(pseudo));
+    }
+}
+
+#[test]
+fn expand_out_of_range() {
+    let beqz = Pseudo::Beqz {
+        rs: Register::A0,
+        offset: 4096,
+    };
+    assert_eq!(beqz.expand(), Err(Error::InvalidImmediate));
+
+    let odd = Pseudo::J { offset: 3 };
+    assert_eq!(odd.expand(), Err(Error::InvalidImmediate));
+}
@@ -0,0 +1,125 @@
+use crate::error::Error;
+use crate::register::{FloatRegister, Register, Syntax};
+
+#[test]
+fn new_in_range() {
+    assert_eq!(Register::new(0), Some(Register::X0));
+    assert_eq!(Register::new(31), Some(Register::X31));
+    assert_eq!(Register::new(10).unwrap().number(), 10);
+}
+
+#[test]
+fn new_out_of_range() {
+    assert_eq!(Register::new(32), None);
+    assert_eq!(Register::new(255), None);
+}
+
+#[test]
+fn try_from_u8() {
+    assert_eq!(Register::try_from(2), Ok(Register::SP));
+    assert_eq!(Register::try_from(32), Err(Error::InvalidRegister));
+}
+
+#[test]
+fn abi_names() {
+    assert_eq!(Register::X0.abi_name(), "zero");
+    assert_eq!(Register::X1.abi_name(), "ra");
+    assert_eq!(Register::X2.abi_name(), "sp");
+    assert_eq!(Register::X3.abi_name(), "gp");
+    assert_eq!(Register::X4.abi_name(), "tp");
+    assert_eq!(Register::X5.abi_name(), "t0");
+    assert_eq!(Register::X8.abi_name(), "s0");
+    assert_eq!(Register::X9.abi_name(), "s1");
+    assert_eq!(Register::X10.abi_name(), "a0");
+    assert_eq!(Register::X17.abi_name(), "a7");
+    assert_eq!(Register::X18.abi_name(), "s2");
+    assert_eq!(Register::X27.abi_name(), "s11");
+    assert_eq!(Register::X28.abi_name(), "t3");
+    assert_eq!(Register::X31.abi_name(), "t6");
+}
+
+#[test]
+fn abi_constants() {
+    assert_eq!(Register::ZERO, Register::X0);
+    assert_eq!(Register::RA, Register::X1);
+    assert_eq!(Register::FP, Register::S0);
+    assert_eq!(Register::A0, Register::X10);
+    assert_eq!(Register::T6, Register::X31);
+}
+
+#[test]
+fn name_by_syntax() {
+    assert_eq!(Register::A0.name(Syntax::Numeric), "x10");
+    assert_eq!(Register::A0.name(Syntax::Abi), "a0");
+    assert_eq!(format!("{}", Register::A0), "x10");
+}
+
+#[test]
+fn from_name() {
+    assert_eq!(Register::from_name("x0"), Some(Register::X0));
+    assert_eq!(Register::from_name("x31"), Some(Register::X31));
+    assert_eq!(Register::from_name("zero"), Some(Register::X0));
+    assert_eq!(Register::from_name("a5"), Some(Register::X15));
+    assert_eq!(Register::from_name("s0"), Some(Register::X8));
+    assert_eq!(Register::from_name("fp"), Some(Register::X8));
+    assert_eq!(Register::from_name("x32"), None);
+    assert_eq!(Register::from_name("a8"), None);
+    assert_eq!(Register::from_name("X1"), None);
+}
+
+#[test]
+fn every_register_round_trips_by_name() {
+    for number in 0..32 {
+        let register = Register::new(number).unwrap();
+        assert_eq!(
+            Register::from_name(register.name(Syntax::Numeric)),
+            Some(register)
+        );
+        assert_eq!(
+            Register::from_name(register.name(Syntax::Abi)),
+            Some(register)
+        );
+    }
+}
+
+#[test]
+fn float_registers() {
+    assert_eq!(FloatRegister::new(31), Some(FloatRegister::F31));
+    assert_eq!(FloatRegister::new(32), None);
+    assert_eq!(FloatRegister::try_from(10), Ok(FloatRegister::FA0));
+    assert_eq!(FloatRegister::try_from(32), Err(Error::InvalidRegister));
+    assert_eq!(FloatRegister::FA0.name(Syntax::Numeric), "f10");
+    assert_eq!(FloatRegister::FA0.name(Syntax::Abi), "fa0");
+    assert_eq!(format!("{}", FloatRegister::FS11), "f27");
+}
+
+#[test]
+fn float_abi_names() {
+    assert_eq!(FloatRegister::F0.name(Syntax::Abi), "ft0");
+    assert_eq!(FloatRegister::F7.name(Syntax::Abi), "ft7");
+    assert_eq!(FloatRegister::F8.name(Syntax::Abi), "fs0");
+    assert_eq!(FloatRegister::F9.name(Syntax::Abi), "fs1");
+    assert_eq!(FloatRegister::F17.name(Syntax::Abi), "fa7");
+    assert_eq!(FloatRegister::F18.name(Syntax::Abi), "fs2");
+    assert_eq!(FloatRegister::F28.name(Syntax::Abi), "ft8");
+    assert_eq!(FloatRegister::F31.name(Syntax::Abi), "ft11");
+}
+
+#[test]
+fn every_float_register_round_trips_by_name() {
+    for number in 0..32 {
+        let register = FloatRegister::new(number).unwrap();
+        assert_eq!(
+            FloatRegister::from_name(register.name(Syntax::Numeric)),
+            Some(register)
+        );
+        assert_eq!(
+            FloatRegister::from_name(register.name(Syntax::Abi)),
+            Some(register)
+        );
+    }
+
+    assert_eq!(FloatRegister::from_name("x10"), None);
+    assert_eq!(FloatRegister::from_name("a0"), None);
+    assert_eq!(FloatRegister::from_name("f32"), None);
+}
@@ -0,0 +1,262 @@
+use crate::error::{DecodeError, DecodeErrorKind};
+use crate::extension::{Extension, Extensions};
+use crate::instruction::RiscVInstruction;
+use crate::profile::Profile;
+use crate::register::{FloatRegister, Register};
+use crate::stream::{DecodedInstruction, InstructionStream};
+
+#[test]
+fn empty() {
+    let mut stream = InstructionStream::new(&[]);
+    assert_eq!(stream.next(), None);
+}
+
+#[test]
+fn uncompressed_only() {
+    let mut code = vec![];
+    code.extend(0x003100b3_u32.to_le_bytes()); // add x1, x2, x3
+    code.extend(0x00008067_u32.to_le_bytes()); // jalr x0, x1, 0
+
+    let decoded: Vec<_> = InstructionStream::new(&code).collect();
+
+    assert_eq!(
+        decoded,
+        vec![
+            DecodedInstruction {
+                offset: 0,
+                length: 4,
+                instruction: Ok(RiscVInstruction::Add {
+                    rd: Register::X1,
+                    rs1: Register::X2,
+                    rs2: Register::X3
+                }),
+            },
+            DecodedInstruction {
+                offset: 4,
+                length: 4,
+                instruction: Ok(RiscVInstruction::Jalr {
+                    rd: Register::X0,
+                    rs1: Register::X1,
+                    imm: 0
+                }),
+            },
+        ]
+    );
+}
+
+#[test]
+fn compressed_only() {
+    let mut code = vec![];
+    code.extend(0x1141_u16.to_le_bytes()); // c.addi x2, -16
+    code.extend(0x8082_u16.to_le_bytes()); // c.jr x1
+
+    let decoded: Vec<_> = InstructionStream::new(&code).collect();
+
+    assert_eq!(
+        decoded,
+        vec![
+            DecodedInstruction {
+                offset: 0,
+                length: 2,
+                instruction: Ok(RiscVInstruction::Addi {
+                    rd: Register::X2,
+                    rs1: Register::X2,
+                    imm: -16
+                }),
+            },
+            DecodedInstruction {
+                offset: 2,
+                length: 2,
+                instruction: Ok(RiscVInstruction::Jalr {
+                    rd: Register::X0,
+                    rs1: Register::X1,
+                    imm: 0
+                }),
+            },
+        ]
+    );
+}
+
+#[test]
+fn mixed_lengths() {
+    let mut code = vec![];
+    code.extend(0x0001_u16.to_le_bytes()); // c.nop
+    code.extend(0x06400513_u32.to_le_bytes()); // addi x10, x0, 100
+    code.extend(0x952e_u16.to_le_bytes()); // c.add x10, x11
+    code.extend(0x00000073_u32.to_le_bytes()); // ecall
+
+    let decoded: Vec<_> = InstructionStream::new(&code)
+        .map(|decoded| (decoded.offset, decoded.length))
+        .collect();
+
+    assert_eq!(decoded, vec![(0, 2), (2, 4), (6, 2), (8, 4)]);
+}
+
+#[test]
+fn unaligned_uncompressed() {
+    // A 32-bit instruction following a compressed one starts on a 2-byte boundary.
+    let mut code = vec![];
+    code.extend(0x0001_u16.to_le_bytes()); // c.nop
+    code.extend(0x003100b3_u32.to_le_bytes()); // add x1, x2, x3
+
+    let decoded: Vec<_> = InstructionStream::new(&code).collect();
+
+    assert_eq!(decoded.len(), 2);
+    assert_eq!(decoded[1].offset, 2);
+    assert_eq!(
+        decoded[1].instruction,
+        Ok(RiscVInstruction::Add {
+            rd: Register::X1,
+            rs1: Register::X2,
+            rs2: Register::X3
+        })
+    );
+}
+
+#[test]
+fn unsupported_instructions_are_reported() {
+    let mut code = vec![];
+    code.extend(0x0000_u16.to_le_bytes()); // illegal
+    code.extend(0x00000057_u32.to_le_bytes()); // vector extension
+
+    let decoded: Vec<_> = InstructionStream::new(&code).collect();
+
+    assert_eq!(
+        decoded,
+        vec![
+            DecodedInstruction {
+                offset: 0,
+                length: 2,
+                instruction: Err(DecodeError {
+                    word: 0x0000,
+                    kind: DecodeErrorKind::ReservedCompressed,
+                }),
+            },
+            DecodedInstruction {
+                offset: 2,
+                length: 4,
+                instruction: Err(DecodeError {
+                    word: 0x00000057,
+                    kind: DecodeErrorKind::UnsupportedExtension(Extension::V),
+                }),
+            },
+        ]
+    );
+}
+
+#[test]
+fn truncated_uncompressed() {
+    let mut code = vec![];
+    code.extend(0x0001_u16.to_le_bytes()); // c.nop
+    code.extend(0x00b3_u16.to_le_bytes()); // first half of add x1, x2, x3
+
+    let decoded: Vec<_> = InstructionStream::new(&code).collect();
+
+    assert_eq!(
+        decoded[1],
+        DecodedInstruction {
+            offset: 2,
+            length: 2,
+            instruction: Err(DecodeError {
+                word: 0x00b3,
+                kind: DecodeErrorKind::Truncated,
+            }),
+        }
+    );
+    assert_eq!(decoded.len(), 2);
+}
+
+#[test]
+fn trailing_byte() {
+    let code = [0x01, 0x00, 0x73];
+
+    let decoded: Vec<_> = InstructionStream::new(&code).collect();
+
+    assert_eq!(
+        decoded[1],
+        DecodedInstruction {
+            offset: 2,
+            length: 1,
+            instruction: Err(DecodeError {
+                word: 0x73,
+                kind: DecodeErrorKind::Truncated,
+            }),
+        }
+    );
+    assert_eq!(decoded.len(), 2);
+}
+
+#[test]
+fn with_profile() {
+    let mut code = vec![];
+    code.extend(0x010100b3_u32.to_le_bytes()); // add x1, x2, x16
+    code.extend(0x8786_u16.to_le_bytes()); // c.mv x15, x1
+
+    let full: Vec<_> = InstructionStream::new(&code).collect();
+    let embedded: Vec<_> =
+        InstructionStream::with_profile(&code, Profile::Rv32e, Extensions::NONE).collect();
+
+    assert!(full.iter().all(|decoded| decoded.instruction.is_ok()));
+    assert_eq!(
+        embedded[0].instruction,
+        Err(DecodeError {
+            word: 0x010100b3,
+            kind: DecodeErrorKind::UnsupportedRegister {
+                register: Register::X16,
+                profile: Profile::Rv32e,
+            },
+        })
+    );
+    assert_eq!(embedded[1].instruction, full[1].instruction);
+}
+
+#[test]
+fn with_extensions() {
+    let code = 0x403170b3_u32.to_le_bytes(); // andn x1, x2, x3
+
+    let base: Vec<_> = InstructionStream::new(&code).collect();
+    let extended: Vec<_> =
+        InstructionStream::with_extensions(&code, Extensions::NONE.with(Extension::Zbb)).collect();
+
+    assert_eq!(
+        base[0].instruction,
+        Err(DecodeError {
+            word: 0x403170b3,
+            kind: DecodeErrorKind::UnsupportedExtension(Extension::Zbb),
+        })
+    );
+    assert_eq!(
+        extended[0].instruction,
+        Ok(RiscVInstruction::Andn {
+            rd: Register::X1,
+            rs1: Register::X2,
+            rs2: Register::X3,
+        })
+    );
+}
+
+#[test]
+fn compressed_with_extensions() {
+    let code = 0x61c8_u16.to_le_bytes(); // c.flw f10, 4(x11)
+
+    let base: Vec<_> = InstructionStream::new(&code).collect();
+    let extended: Vec<_> =
+        InstructionStream::with_extensions(&code, Extensions::NONE.with(Extension::F)).collect();
+
+    assert_eq!(
+        base[0].instruction,
+        Err(DecodeError {
+            word: 0x61c8,
+            kind: DecodeErrorKind::UnsupportedExtension(Extension::F),
+        })
+    );
+    assert_eq!(
+        extended[0].instruction,
+        Ok(RiscVInstruction::Flw {
+            rd: FloatRegister::F10,
+            rs1: Register::X11,
+            imm: 4,
+        })
+    );
+    assert_eq!(extended[0].length, 2);
+}
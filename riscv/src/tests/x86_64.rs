@@ -0,0 +1,322 @@
+use crate::Config;
+use crate::assembler::assemble;
+use crate::custom::CustomInstructions;
+use crate::extension::Extensions;
+use crate::profile::Profile;
+use crate::program::{NO_ENTRY, Program};
+use crate::x86_64::{
+    Alu, Condition, Emitter, R, Shift, Unary, load_encoding, store_encoding, translate,
+};
+
+fn program(profile: Profile, source: &str) -> Program {
+    let config = Config {
+        syscall: |_args, _context| 0,
+        max_instance_memory: 0x1000,
+        max_code_size: 0x1000,
+        profile,
+        extensions: Extensions::NONE,
+        time: |_context| 0,
+        custom: CustomInstructions::NONE,
+    };
+    Program::decode(&assemble(source).unwrap().bytes, &config).unwrap()
+}
+
+fn encode(emit: impl FnOnce(&mut Emitter)) -> Vec<u8> {
+    let mut e = Emitter::default();
+    emit(&mut e);
+    e.code
+}
+
+/// The expected bytes disassemble to the commented instructions with
+/// `llvm-mc -triple=x86_64 -disassemble -output-asm-variant=1`.
+#[test]
+fn encodings() {
+    let (rax, rcx, rdx) = (R(0), R(1), R(2));
+
+    // add eax, ecx
+    assert_eq!(encode(|e| e.alu(Alu::Add, false, rax, rcx)), [0x01, 0xc8]);
+    // add rax, rcx
+    assert_eq!(
+        encode(|e| e.alu(Alu::Add, true, rax, rcx)),
+        [0x48, 0x01, 0xc8]
+    );
+    // cmp rax, r13
+    assert_eq!(
+        encode(|e| e.alu(Alu::Cmp, true, rax, R(13))),
+        [0x4c, 0x39, 0xe8]
+    );
+    // xor eax, eax
+    assert_eq!(encode(|e| e.alu(Alu::Xor, false, rax, rax)), [0x31, 0xc0]);
+    // sub r14, 1
+    assert_eq!(
+        encode(|e| e.alu_imm(Alu::Sub, true, R(14), 1)),
+        [0x49, 0x83, 0xee, 0x01]
+    );
+    // and rax, -2
+    assert_eq!(
+        encode(|e| e.alu_imm(Alu::And, true, rax, -2)),
+        [0x48, 0x83, 0xe0, 0xfe]
+    );
+    // or eax, 0xfffff800
+    assert_eq!(
+        encode(|e| e.alu_imm(Alu::Or, false, rax, -2048)),
+        [0x81, 0xc8, 0x00, 0xf8, 0xff, 0xff]
+    );
+    // add r8, 2047
+    assert_eq!(
+        encode(|e| e.alu_imm(Alu::Add, true, R(8), 2047)),
+        [0x49, 0x81, 0xc0, 0xff, 0x07, 0x00, 0x00]
+    );
+    // test r14, r14
+    assert_eq!(encode(|e| e.test(true, R(14), R(14))), [0x4d, 0x85, 0xf6]);
+    // shl eax, cl
+    assert_eq!(encode(|e| e.shift(Shift::Shl, false, rax)), [0xd3, 0xe0]);
+    // sar rax, cl
+    assert_eq!(
+        encode(|e| e.shift(Shift::Sar, true, rax)),
+        [0x48, 0xd3, 0xf8]
+    );
+    // shr rax, 32
+    assert_eq!(
+        encode(|e| e.shift_imm(Shift::Shr, true, rax, 32)),
+        [0x48, 0xc1, 0xe8, 0x20]
+    );
+    // shl r9d, 3
+    assert_eq!(
+        encode(|e| e.shift_imm(Shift::Shl, false, R(9), 3)),
+        [0x41, 0xc1, 0xe1, 0x03]
+    );
+    // imul rax, rcx
+    assert_eq!(encode(|e| e.imul(true, rax, rcx)), [0x48, 0x0f, 0xaf, 0xc1]);
+    // imul ecx, eax
+    assert_eq!(encode(|e| e.imul(false, rcx, rax)), [0x0f, 0xaf, 0xc8]);
+    // imul rcx
+    assert_eq!(
+        encode(|e| e.unary(Unary::Imul, true, rcx)),
+        [0x48, 0xf7, 0xe9]
+    );
+    // mul rcx
+    assert_eq!(
+        encode(|e| e.unary(Unary::Mul, true, rcx)),
+        [0x48, 0xf7, 0xe1]
+    );
+    // div ecx
+    assert_eq!(encode(|e| e.unary(Unary::Div, false, rcx)), [0xf7, 0xf1]);
+    // idiv rcx
+    assert_eq!(
+        encode(|e| e.unary(Unary::Idiv, true, rcx)),
+        [0x48, 0xf7, 0xf9]
+    );
+    // neg eax
+    assert_eq!(encode(|e| e.unary(Unary::Neg, false, rax)), [0xf7, 0xd8]);
+    // cdq; cqo
+    assert_eq!(
+        encode(|e| {
+            e.sign_extend_rax(false);
+            e.sign_extend_rax(true);
+        }),
+        [0x99, 0x48, 0x99]
+    );
+    // mov rax, rdx
+    assert_eq!(encode(|e| e.mov(true, rax, rdx)), [0x48, 0x89, 0xd0]);
+    // mov rbx, rdi
+    assert_eq!(encode(|e| e.mov(true, R(3), R(7))), [0x48, 0x89, 0xfb]);
+    // movsxd rax, eax
+    assert_eq!(encode(|e| e.movsxd(rax, rax)), [0x48, 0x63, 0xc0]);
+    // setl al; movzx eax, al
+    assert_eq!(
+        encode(|e| e.set(Condition::L, rax)),
+        [0x0f, 0x9c, 0xc0, 0x0f, 0xb6, 0xc0]
+    );
+    // setb cl; movzx ecx, cl
+    assert_eq!(
+        encode(|e| e.set(Condition::B, rcx)),
+        [0x0f, 0x92, 0xc1, 0x0f, 0xb6, 0xc9]
+    );
+}
+
+#[test]
+fn memory_encodings() {
+    let (rax, rcx, rbx) = (R(0), R(1), R(3));
+
+    // mov r12, qword ptr [rbx + 272]
+    assert_eq!(
+        encode(|e| e.load(R(12), rbx, 272)),
+        [0x4c, 0x8b, 0xa3, 0x10, 0x01, 0x00, 0x00]
+    );
+    // mov qword ptr [rbx + 264], r9
+    assert_eq!(
+        encode(|e| e.store(R(9), rbx, 264)),
+        [0x4c, 0x89, 0x8b, 0x08, 0x01, 0x00, 0x00]
+    );
+    // mov rax, qword ptr [rbx]
+    assert_eq!(encode(|e| e.load(rax, rbx, 0)), [0x48, 0x8b, 0x03]);
+    // mov qword ptr [rbx + 80], rdx
+    assert_eq!(encode(|e| e.store(R(2), rbx, 80)), [0x48, 0x89, 0x53, 0x50]);
+    // lea rcx, [rax + 4]
+    assert_eq!(encode(|e| e.lea(rcx, rax, 4)), [0x48, 0x8d, 0x48, 0x04]);
+    // mov ecx, dword ptr [r15 + 2*rax]
+    assert_eq!(
+        encode(|e| e.indexed(load_encoding(4, false, false), rcx, R(15), rax, 1)),
+        [0x41, 0x8b, 0x0c, 0x47]
+    );
+    // mov rcx, qword ptr [r13 + rax]
+    assert_eq!(
+        encode(|e| e.indexed(load_encoding(8, false, true), rcx, R(13), rax, 0)),
+        [0x49, 0x8b, 0x4c, 0x05, 0x00]
+    );
+
+    let loads = [
+        (1, false, false, &[0x41, 0x0f, 0xb6][..]), // movzx ecx, byte ptr [r12 + rax]
+        (1, true, false, &[0x41, 0x0f, 0xbe]),      // movsx ecx, byte ptr [r12 + rax]
+        (1, true, true, &[0x49, 0x0f, 0xbe]),       // movsx rcx, byte ptr [r12 + rax]
+        (2, false, true, &[0x41, 0x0f, 0xb7]),      // movzx ecx, word ptr [r12 + rax]
+        (2, true, false, &[0x41, 0x0f, 0xbf]),      // movsx ecx, word ptr [r12 + rax]
+        (2, true, true, &[0x49, 0x0f, 0xbf]),       // movsx rcx, word ptr [r12 + rax]
+        (4, false, true, &[0x41, 0x8b]),            // mov ecx, dword ptr [r12 + rax]
+        (4, true, false, &[0x41, 0x8b]),            // mov ecx, dword ptr [r12 + rax]
+        (4, true, true, &[0x49, 0x63]),             // movsxd rcx, dword ptr [r12 + rax]
+        (8, false, true, &[0x49, 0x8b]),            // mov rcx, qword ptr [r12 + rax]
+    ];
+    for (width, signed, wide, opcode) in loads {
+        let encoding = load_encoding(width, signed, wide);
+        let code = encode(|e| e.indexed(encoding, rcx, R(12), rax, 0));
+        assert_eq!(code, [opcode, &[0x0c, 0x04]].concat());
+    }
+
+    let stores = [
+        (1, &[0x41, 0x88][..]),   // mov byte ptr [r12 + rax], cl
+        (2, &[0x66, 0x41, 0x89]), // mov word ptr [r12 + rax], cx
+        (4, &[0x41, 0x89]),       // mov dword ptr [r12 + rax], ecx
+        (8, &[0x49, 0x89]),       // mov qword ptr [r12 + rax], rcx
+    ];
+    for (width, opcode) in stores {
+        let code = encode(|e| e.indexed(store_encoding(width), rcx, R(12), rax, 0));
+        assert_eq!(code, [opcode, &[0x0c, 0x04]].concat());
+    }
+}
+
+#[test]
+fn move_immediate() {
+    let moves = |wide, dst, value| encode(|e| e.mov_imm(wide, dst, value));
+
+    // mov r9d, 0x1234
+    assert_eq!(
+        moves(false, R(9), 0x1234),
+        [0x41, 0xb9, 0x34, 0x12, 0x00, 0x00]
+    );
+    // mov edx, 0xffffffff
+    assert_eq!(
+        moves(true, R(2), 0xffff_ffff),
+        [0xba, 0xff, 0xff, 0xff, 0xff]
+    );
+    // mov rdx, -2048
+    assert_eq!(
+        moves(true, R(2), -2048_i64 as u64),
+        [0x48, 0xc7, 0xc2, 0x00, 0xf8, 0xff, 0xff]
+    );
+    // movabs r10, 0x123456789abc
+    assert_eq!(
+        moves(true, R(10), 0x1234_5678_9abc),
+        [0x49, 0xba, 0xbc, 0x9a, 0x78, 0x56, 0x34, 0x12, 0x00, 0x00]
+    );
+}
+
+#[test]
+fn control_encodings() {
+    // jmp rsi; jmp rcx; push rbp; push r12; pop r15; pop rbx; ret
+    assert_eq!(
+        encode(|e| {
+            e.jmp_register(R(6));
+            e.jmp_register(R(1));
+            e.push(R(5));
+            e.push(R(12));
+            e.pop(R(15));
+            e.pop(R(3));
+            e.ret();
+        }),
+        [
+            0xff, 0xe6, 0xff, 0xe1, 0x55, 0x41, 0x54, 0x41, 0x5f, 0x5b, 0xc3
+        ]
+    );
+    // ret; lea rbp, [rip - 8]; jmp -13; jae -19
+    assert_eq!(
+        encode(|e| {
+            e.ret();
+            e.lea_rip(R(5), 0);
+            e.jmp(0);
+            e.jcc(Condition::Ae, 0);
+        }),
+        [
+            0xc3, 0x48, 0x8d, 0x2d, 0xf8, 0xff, 0xff, 0xff, 0xe9, 0xf3, 0xff, 0xff, 0xff, 0x0f,
+            0x83, 0xed, 0xff, 0xff, 0xff
+        ]
+    );
+    // jne 1; ret; jmp 0
+    assert_eq!(
+        encode(|e| {
+            let label = e.jcc_short(Condition::Ne);
+            e.ret();
+            e.bind(label);
+            let label = e.jmp_short();
+            e.bind(label);
+        }),
+        [0x75, 0x01, 0xc3, 0xeb, 0x00]
+    );
+}
+
+#[test]
+fn offsets() {
+    let program = program(Profile::Rv32i, "addi a0, a0, 1\n.half 0x0001\nret");
+    let translation = translate(&program, Profile::Rv32i).unwrap();
+
+    let offsets = &translation.offsets;
+    assert_eq!(offsets.len(), 5);
+    assert_ne!(offsets[0], NO_ENTRY);
+    assert_eq!(offsets[1], NO_ENTRY);
+    assert!(offsets[2] > offsets[0]);
+    assert!(offsets[3] > offsets[2]);
+    assert_eq!(offsets[4], NO_ENTRY);
+
+    // The code starts with the entry point.
+    let code = &translation.code;
+    assert_eq!(code[0], 0x55); // push rbp
+    // Every instruction starts by checking for gas, jumping over its stub.
+    for offset in [offsets[0], offsets[2], offsets[3]] {
+        let offset = offset as usize;
+        // test r14, r14; jne 11
+        assert_eq!(code[offset..offset + 5], [0x4d, 0x85, 0xf6, 0x75, 0x0b]);
+    }
+}
+
+#[test]
+fn instructions_executed_by_the_engine() {
+    let program = program(Profile::Rv32i, "ecall\nebreak\nj 0x100");
+    let translation = translate(&program, Profile::Rv32i).unwrap();
+    let code = &translation.code;
+
+    for (index, pc) in [(0, 0_u8), (2, 4), (4, 8)] {
+        let offset = translation.offsets[index] as usize;
+        // mov r9d, pc; jmp exit
+        assert_eq!(code[offset..offset + 7], [0x41, 0xb9, pc, 0, 0, 0, 0xe9]);
+    }
+}
+
+#[test]
+fn branches_link_to_their_targets() {
+    let program = program(Profile::Rv32i, "loop:\naddi a0, a0, -1\nbnez a0, loop\nret");
+    let translation = translate(&program, Profile::Rv32i).unwrap();
+    let code = &translation.code;
+
+    let (target, branch) = (translation.offsets[0], translation.offsets[2]);
+    let end = translation.offsets[4] as usize;
+    // jne rel32
+    let jump = code[..end]
+        .windows(2)
+        .rposition(|bytes| bytes == [0x0f, 0x85])
+        .unwrap();
+    assert!(jump > branch as usize);
+
+    let distance = i32::from_le_bytes(code[jump + 2..jump + 6].try_into().unwrap());
+    assert_eq!(jump as i64 + 6 + i64::from(distance), i64::from(target));
+}
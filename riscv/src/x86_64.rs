@@ -0,0 +1,883 @@
+use crate::error::Error;
+use crate::hart::offsets::{GAS, MEMORY, MEMORY_SIZE, NATIVE_OFFSETS, PC, REGISTERS};
+use crate::instruction::RiscVInstruction;
+use crate::profile::Profile;
+use crate::program::{NO_ENTRY, Program, Translation};
+use crate::register::Register;
+
+/// An x86-64 general-purpose register, by number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct R(pub(crate) u8);
+
+/// Scratch registers, which native code does not preserve between instructions.
+/// Division takes its dividend in `T0` and `T2`, and shifts take their count in `T1`.
+const T0: R = R(0);
+const T1: R = R(1);
+const T2: R = R(2);
+/// The [`Hart`](crate::hart::Hart) of the call, for as long as native code runs.
+const HART: R = R(3);
+/// The start of the native code.
+const CODE: R = R(5);
+/// The second argument register.
+const RSI: R = R(6);
+/// The first argument register.
+const RDI: R = R(7);
+/// The pc of the instruction native code exits at.
+const EXIT_PC: R = R(9);
+/// The start of guest memory.
+const MEMORY_BASE: R = R(12);
+/// The size of guest memory.
+const MEMORY_END: R = R(13);
+/// The gas remaining.
+const GAS_LEFT: R = R(14);
+/// The native offsets of the instructions.
+const OFFSETS: R = R(15);
+
+/// A condition code of a conditional jump or `setcc`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Condition {
+    B = 0x2,
+    Ae = 0x3,
+    E = 0x4,
+    Ne = 0x5,
+    A = 0x7,
+    L = 0xc,
+    Ge = 0xd,
+}
+
+/// An arithmetic or logic instruction, by the `/digit` of its immediate form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Alu {
+    Add = 0,
+    Or = 1,
+    And = 4,
+    Sub = 5,
+    Xor = 6,
+    Cmp = 7,
+}
+
+/// A shift instruction, by its `/digit`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Shift {
+    Shl = 4,
+    Shr = 5,
+    Sar = 7,
+}
+
+/// A unary instruction of the `F7` group, by its `/digit`. The multiplications and
+/// divisions operate on `rdx:rax`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Unary {
+    Neg = 3,
+    Mul = 4,
+    Imul = 5,
+    Div = 6,
+    Idiv = 7,
+}
+
+/// The operand an instruction encodes in its ModRM byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Operand {
+    Register(R),
+    /// `[base + index * 2^scale + disp]`
+    Memory {
+        base: R,
+        index: Option<(R, u8)>,
+        disp: i32,
+    },
+}
+
+/// The largest distance of a `rel32` jump, in either direction.
+const BRANCH_RANGE: usize = 1 << 31;
+
+/// An encoder of x86-64 instructions.
+///
+/// Every method appends one instruction. `wide` selects the 64-bit operand size; the
+/// 32-bit operand size zero-extends its result to 64 bits.
+#[derive(Debug, Default)]
+pub(crate) struct Emitter {
+    pub(crate) code: Vec<u8>,
+}
+
+impl Emitter {
+    /// Returns the offset of the next instruction.
+    pub(crate) fn position(&self) -> usize {
+        self.code.len()
+    }
+
+    /// Appends raw bytes.
+    pub(crate) fn emit(&mut self, bytes: &[u8]) {
+        self.code.extend(bytes);
+    }
+
+    /// Replaces the `rel32` at `position` with the distance from its end to `target`.
+    fn patch(&mut self, position: usize, target: usize) {
+        let distance = (target as i64 - (position as i64 + 4)) as i32;
+        self.code[position..position + 4].copy_from_slice(&distance.to_le_bytes());
+    }
+
+    /// Encodes an instruction with an optional legacy `prefix`, a REX prefix if
+    /// needed, `opcode`, and a ModRM byte of `reg`, a register or `/digit`, and `rm`.
+    fn instruction(&mut self, prefix: Option<u8>, wide: bool, opcode: &[u8], reg: u8, rm: Operand) {
+        let (base, index) = match rm {
+            Operand::Register(r) => (r.0, 0),
+            Operand::Memory { base, index, .. } => (base.0, index.map_or(0, |(r, _)| r.0)),
+        };
+        let rex = (wide as u8) << 3 | (reg >> 3) << 2 | (index >> 3) << 1 | base >> 3;
+
+        self.code.extend(prefix);
+        if rex != 0 {
+            self.code.push(0x40 | rex);
+        }
+        self.code.extend(opcode);
+
+        let Operand::Memory { index, disp, .. } = rm else {
+            self.code.push(0xc0 | (reg & 7) << 3 | base & 7);
+            return;
+        };
+        // `rbp` and `r13` as a base without displacement encode `rip` or no base.
+        let mode = match disp {
+            0 if base & 7 != 5 => 0x00,
+            -0x80..0x80 => 0x40,
+            _ => 0x80,
+        };
+        match index {
+            Some((index, scale)) => {
+                self.code.push(mode | (reg & 7) << 3 | 4);
+                self.code.push(scale << 6 | (index.0 & 7) << 3 | base & 7);
+            }
+            None if base & 7 == 4 => {
+                self.code.push(mode | (reg & 7) << 3 | 4);
+                self.code.push(0x24);
+            }
+            None => self.code.push(mode | (reg & 7) << 3 | base & 7),
+        }
+        match mode {
+            0x40 => self.code.push(disp as u8),
+            0x80 => self.code.extend(disp.to_le_bytes()),
+            _ => {}
+        }
+    }
+
+    /// Encodes an instruction whose opcode holds the low bits of `r`, with a REX
+    /// prefix if needed.
+    fn short(&mut self, wide: bool, opcode: u8, r: R) {
+        let rex = (wide as u8) << 3 | r.0 >> 3;
+        if rex != 0 {
+            self.code.push(0x40 | rex);
+        }
+        self.code.push(opcode | r.0 & 7);
+    }
+
+    /// `op dst, src`
+    pub(crate) fn alu(&mut self, op: Alu, wide: bool, dst: R, src: R) {
+        let opcode = (op as u8) << 3 | 1;
+        self.instruction(None, wide, &[opcode], src.0, Operand::Register(dst));
+    }
+
+    /// `op dst, imm`
+    pub(crate) fn alu_imm(&mut self, op: Alu, wide: bool, dst: R, imm: i32) {
+        match i8::try_from(imm) {
+            Ok(imm) => {
+                self.instruction(None, wide, &[0x83], op as u8, Operand::Register(dst));
+                self.code.push(imm as u8);
+            }
+            Err(_) => {
+                self.instruction(None, wide, &[0x81], op as u8, Operand::Register(dst));
+                self.code.extend(imm.to_le_bytes());
+            }
+        }
+    }
+
+    /// `test a, b`
+    pub(crate) fn test(&mut self, wide: bool, a: R, b: R) {
+        self.instruction(None, wide, &[0x85], b.0, Operand::Register(a));
+    }
+
+    /// `op dst, cl`
+    pub(crate) fn shift(&mut self, op: Shift, wide: bool, dst: R) {
+        self.instruction(None, wide, &[0xd3], op as u8, Operand::Register(dst));
+    }
+
+    /// `op dst, imm`
+    pub(crate) fn shift_imm(&mut self, op: Shift, wide: bool, dst: R, imm: u8) {
+        self.instruction(None, wide, &[0xc1], op as u8, Operand::Register(dst));
+        self.code.push(imm);
+    }
+
+    /// `op src`
+    pub(crate) fn unary(&mut self, op: Unary, wide: bool, src: R) {
+        self.instruction(None, wide, &[0xf7], op as u8, Operand::Register(src));
+    }
+
+    /// `imul dst, src`, the low half of the product.
+    pub(crate) fn imul(&mut self, wide: bool, dst: R, src: R) {
+        self.instruction(None, wide, &[0x0f, 0xaf], dst.0, Operand::Register(src));
+    }
+
+    /// `cdq` or `cqo`, sign-extending `rax` into `rdx`.
+    pub(crate) fn sign_extend_rax(&mut self, wide: bool) {
+        match wide {
+            true => self.emit(&[0x48, 0x99]),
+            false => self.emit(&[0x99]),
+        }
+    }
+
+    /// `mov dst, src`
+    pub(crate) fn mov(&mut self, wide: bool, dst: R, src: R) {
+        self.instruction(None, wide, &[0x89], src.0, Operand::Register(dst));
+    }
+
+    /// `movsxd dst, src32`
+    pub(crate) fn movsxd(&mut self, dst: R, src: R) {
+        self.instruction(None, true, &[0x63], dst.0, Operand::Register(src));
+    }
+
+    /// `setcc dst8; movzx dst32, dst8`, for one of the first four registers.
+    pub(crate) fn set(&mut self, cond: Condition, dst: R) {
+        debug_assert!(dst.0 < 4);
+        let opcode = 0x90 | cond as u8;
+        self.instruction(None, false, &[0x0f, opcode], 0, Operand::Register(dst));
+        self.instruction(None, false, &[0x0f, 0xb6], dst.0, Operand::Register(dst));
+    }
+
+    /// Moves `value` into `dst` with the shortest form of `mov`.
+    pub(crate) fn mov_imm(&mut self, wide: bool, dst: R, value: u64) {
+        if !wide || value <= u64::from(u32::MAX) {
+            self.short(false, 0xb8, dst);
+            self.code.extend((value as u32).to_le_bytes());
+        } else if let Ok(value) = i32::try_from(value as i64) {
+            self.instruction(None, true, &[0xc7], 0, Operand::Register(dst));
+            self.code.extend(value.to_le_bytes());
+        } else {
+            self.short(true, 0xb8, dst);
+            self.code.extend(value.to_le_bytes());
+        }
+    }
+
+    /// `mov dst, [base + disp]`
+    pub(crate) fn load(&mut self, dst: R, base: R, disp: usize) {
+        let rm = Operand::Memory {
+            base,
+            index: None,
+            disp: disp as i32,
+        };
+        self.instruction(None, true, &[0x8b], dst.0, rm);
+    }
+
+    /// `mov [base + disp], src`
+    pub(crate) fn store(&mut self, src: R, base: R, disp: usize) {
+        let rm = Operand::Memory {
+            base,
+            index: None,
+            disp: disp as i32,
+        };
+        self.instruction(None, true, &[0x89], src.0, rm);
+    }
+
+    /// `lea dst, [base + disp]`
+    pub(crate) fn lea(&mut self, dst: R, base: R, disp: i32) {
+        let rm = Operand::Memory {
+            base,
+            index: None,
+            disp,
+        };
+        self.instruction(None, true, &[0x8d], dst.0, rm);
+    }
+
+    /// `lea dst, [rip + target]`
+    pub(crate) fn lea_rip(&mut self, dst: R, target: usize) {
+        // Mode 0 with `rm` = 5 addresses relative to the next instruction.
+        self.emit(&[
+            0x48 | (dst.0 >> 3) << 2,
+            0x8d,
+            (dst.0 & 7) << 3 | 5,
+            0,
+            0,
+            0,
+            0,
+        ]);
+        self.patch(self.position() - 4, target);
+    }
+
+    /// A load or store of `reg` at `[base + index * 2^scale]`, with `encoding`
+    /// selecting the width, direction and extension.
+    pub(crate) fn indexed(&mut self, encoding: Encoding, reg: R, base: R, index: R, scale: u8) {
+        let rm = Operand::Memory {
+            base,
+            index: Some((index, scale)),
+            disp: 0,
+        };
+        self.instruction(encoding.prefix, encoding.wide, encoding.opcode, reg.0, rm);
+    }
+
+    /// `jmp target`
+    pub(crate) fn jmp(&mut self, target: usize) {
+        self.emit(&[0xe9, 0, 0, 0, 0]);
+        self.patch(self.position() - 4, target);
+    }
+
+    /// `jcc target`
+    pub(crate) fn jcc(&mut self, cond: Condition, target: usize) {
+        self.emit(&[0x0f, 0x80 | cond as u8, 0, 0, 0, 0]);
+        self.patch(self.position() - 4, target);
+    }
+
+    /// A short `jcc` forward, to be resolved by [`Emitter::bind`]. Returns its label.
+    pub(crate) fn jcc_short(&mut self, cond: Condition) -> usize {
+        self.emit(&[0x70 | cond as u8, 0]);
+        self.position()
+    }
+
+    /// A short `jmp` forward, to be resolved by [`Emitter::bind`]. Returns its label.
+    pub(crate) fn jmp_short(&mut self) -> usize {
+        self.emit(&[0xeb, 0]);
+        self.position()
+    }
+
+    /// Resolves the short jump of `label` to the next instruction.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the next instruction is out of the range of a `rel8`.
+    pub(crate) fn bind(&mut self, label: usize) {
+        let distance = self.position() - label;
+        assert!(distance < 0x80, "short jump of {distance} bytes");
+        self.code[label - 1] = distance as u8;
+    }
+
+    /// `jmp src`
+    pub(crate) fn jmp_register(&mut self, src: R) {
+        self.instruction(None, false, &[0xff], 4, Operand::Register(src));
+    }
+
+    pub(crate) fn push(&mut self, src: R) {
+        self.short(false, 0x50, src);
+    }
+
+    pub(crate) fn pop(&mut self, dst: R) {
+        self.short(false, 0x58, dst);
+    }
+
+    pub(crate) fn ret(&mut self) {
+        self.emit(&[0xc3]);
+    }
+}
+
+/// The prefix, opcode and operand size of a load or store.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Encoding {
+    prefix: Option<u8>,
+    opcode: &'static [u8],
+    wide: bool,
+}
+
+impl Encoding {
+    const fn new(prefix: Option<u8>, opcode: &'static [u8], wide: bool) -> Encoding {
+        Encoding {
+            prefix,
+            opcode,
+            wide,
+        }
+    }
+}
+
+/// The encodings of the loads, by width in bytes and whether they sign-extend to 32
+/// or 64 bits.
+pub(crate) fn load_encoding(width: u8, signed: bool, wide: bool) -> Encoding {
+    match (width, signed) {
+        (1, false) => Encoding::new(None, &[0x0f, 0xb6], false),
+        (1, true) => Encoding::new(None, &[0x0f, 0xbe], wide),
+        (2, false) => Encoding::new(None, &[0x0f, 0xb7], false),
+        (2, true) => Encoding::new(None, &[0x0f, 0xbf], wide),
+        (4, true) if wide => Encoding::new(None, &[0x63], true),
+        (4, _) => Encoding::new(None, &[0x8b], false),
+        _ => Encoding::new(None, &[0x8b], true),
+    }
+}
+
+/// The encodings of the stores, by width in bytes.
+pub(crate) fn store_encoding(width: u8) -> Encoding {
+    match width {
+        1 => Encoding::new(None, &[0x88], false),
+        2 => Encoding::new(Some(0x66), &[0x89], false),
+        4 => Encoding::new(None, &[0x89], false),
+        _ => Encoding::new(None, &[0x89], true),
+    }
+}
+
+/// The callee-saved registers the entry point preserves, in the order it pushes them.
+const SAVED: [R; 6] = [CODE, HART, MEMORY_BASE, MEMORY_END, GAS_LEFT, OFFSETS];
+
+/// A translator of RISC-V programs to x86-64 code.
+///
+/// Guest registers live in the hart, and each instruction loads its operands into
+/// `T0` to `T2`. While native code runs, the callee-saved registers of `SAVED` hold
+/// `HART`, `CODE`, `MEMORY_BASE`, `MEMORY_END`, `GAS_LEFT` and `OFFSETS`, and stubs
+/// pass the pc in `EXIT_PC`. The entry point pushes `SAVED` onto the stack of the
+/// caller, with no other frame, and the shared exit sequence pops them.
+struct Translator<'a> {
+    program: &'a Program,
+    emitter: Emitter,
+    /// Whether the guest has 64-bit registers.
+    wide: bool,
+    /// The offset of the shared exit sequence.
+    exit: usize,
+    /// The native offset of every instruction, indexed by address / 2.
+    offsets: Vec<u32>,
+    /// The `rel32` of jumps to patch once every instruction has an offset, with the
+    /// guest address they jump to.
+    fixups: Vec<(usize, u64)>,
+}
+
+/// Translates `program` to x86-64 code following the protocol of
+/// [`Entry`](crate::hart::Entry).
+///
+/// # Errors
+///
+/// - `Error::InvalidCodeSize` if the native code is too large to jump across.
+pub(crate) fn translate(program: &Program, profile: Profile) -> Result<Translation, Error> {
+    let mut translator = Translator {
+        program,
+        emitter: Emitter::default(),
+        wide: profile.xlen() == 64,
+        exit: 0,
+        offsets: vec![NO_ENTRY; program.len() as usize / 2],
+        fixups: vec![],
+    };
+
+    translator.entry();
+    for (pc, instruction, length) in program.iter() {
+        translator.offsets[pc as usize / 2] = translator.emitter.position() as u32;
+        translator.instruction(pc, instruction, length);
+    }
+    // Falling off the end of the code exits with the pc past its last instruction.
+    translator.stub(program.len());
+
+    if translator.emitter.position() >= BRANCH_RANGE {
+        return Err(Error::InvalidCodeSize);
+    }
+    for (position, target) in std::mem::take(&mut translator.fixups) {
+        let target = translator.offsets[target as usize / 2] as usize;
+        translator.emitter.patch(position, target);
+    }
+
+    Ok(Translation {
+        code: translator.emitter.code,
+        offsets: translator.offsets,
+    })
+}
+
+impl Translator<'_> {
+    /// Emits the entry point and the exit sequence it shares with every stub.
+    fn entry(&mut self) {
+        let e = &mut self.emitter;
+        for register in SAVED {
+            e.push(register);
+        }
+        e.mov(true, HART, RDI);
+        e.load(MEMORY_BASE, HART, MEMORY);
+        e.load(MEMORY_END, HART, MEMORY_SIZE);
+        e.load(GAS_LEFT, HART, GAS);
+        e.load(OFFSETS, HART, NATIVE_OFFSETS);
+        e.lea_rip(CODE, 0);
+        e.jmp_register(RSI);
+
+        self.exit = e.position();
+        e.store(EXIT_PC, HART, PC);
+        e.store(GAS_LEFT, HART, GAS);
+        for register in SAVED.into_iter().rev() {
+            e.pop(register);
+        }
+        e.ret();
+    }
+
+    /// Emits a stub that exits to Rust at `pc`.
+    fn stub(&mut self, pc: u64) {
+        self.emitter.mov_imm(false, EXIT_PC, pc);
+        self.emitter.jmp(self.exit);
+    }
+
+    /// Emits the native code of `instruction`, located at `pc`.
+    fn instruction(&mut self, pc: u64, instruction: RiscVInstruction, length: u8) {
+        let start = self.emitter.position();
+
+        self.emitter.test(true, GAS_LEFT, GAS_LEFT);
+        let body = self.emitter.jcc_short(Condition::Ne);
+        let stub = self.emitter.position();
+        self.stub(pc);
+        self.emitter.bind(body);
+        if !self.body(pc, instruction, length, stub) {
+            self.emitter.code.truncate(start);
+            self.stub(pc);
+        }
+    }
+
+    /// Emits the body of `instruction`, which exits through `stub` to trap. Returns
+    /// `false` if native code does not implement the instruction.
+    fn body(&mut self, pc: u64, instruction: RiscVInstruction, length: u8, stub: usize) -> bool {
+        use RiscVInstruction::*;
+
+        let wide = self.wide;
+        let next = self.wrap(pc + u64::from(length));
+
+        match instruction {
+            Add { rd, rs1, rs2 } => self.alu(rd, rs1, rs2, Alu::Add),
+            Sub { rd, rs1, rs2 } => self.alu(rd, rs1, rs2, Alu::Sub),
+            Xor { rd, rs1, rs2 } => self.alu(rd, rs1, rs2, Alu::Xor),
+            Or { rd, rs1, rs2 } => self.alu(rd, rs1, rs2, Alu::Or),
+            And { rd, rs1, rs2 } => self.alu(rd, rs1, rs2, Alu::And),
+            Sll { rd, rs1, rs2 } => self.register(rd, rs1, rs2, |e| e.shift(Shift::Shl, wide, T0)),
+            Srl { rd, rs1, rs2 } => self.register(rd, rs1, rs2, |e| e.shift(Shift::Shr, wide, T0)),
+            Sra { rd, rs1, rs2 } => self.register(rd, rs1, rs2, |e| e.shift(Shift::Sar, wide, T0)),
+            Slt { rd, rs1, rs2 } => self.register(rd, rs1, rs2, |e| {
+                e.alu(Alu::Cmp, wide, T0, T1);
+                e.set(Condition::L, T0);
+            }),
+            Sltu { rd, rs1, rs2 } => self.register(rd, rs1, rs2, |e| {
+                e.alu(Alu::Cmp, wide, T0, T1);
+                e.set(Condition::B, T0);
+            }),
+            Mul { rd, rs1, rs2 } => self.register(rd, rs1, rs2, |e| e.imul(wide, T0, T1)),
+            Mulh { rd, rs1, rs2 } if wide => {
+                self.register(rd, rs1, rs2, |e| high(e, Unary::Imul));
+            }
+            Mulhu { rd, rs1, rs2 } if wide => {
+                self.register(rd, rs1, rs2, |e| high(e, Unary::Mul));
+            }
+            // The products of 32-bit values, signed or not, fit in 64 bits.
+            Mulh { rd, rs1, rs2 } => self.register(rd, rs1, rs2, |e| {
+                e.movsxd(T0, T0);
+                e.movsxd(T1, T1);
+                high_word(e);
+            }),
+            Mulhu { rd, rs1, rs2 } => self.register(rd, rs1, rs2, high_word),
+            Mulhsu { rd, rs1, rs2 } if !wide => self.register(rd, rs1, rs2, |e| {
+                e.movsxd(T0, T0);
+                high_word(e);
+            }),
+            Div { rd, rs1, rs2 } => self.register(rd, rs1, rs2, |e| divide(e, wide, true, false)),
+            Divu { rd, rs1, rs2 } => {
+                self.register(rd, rs1, rs2, |e| divide(e, wide, false, false));
+            }
+            Rem { rd, rs1, rs2 } => self.register(rd, rs1, rs2, |e| divide(e, wide, true, true)),
+            Remu { rd, rs1, rs2 } => {
+                self.register(rd, rs1, rs2, |e| divide(e, wide, false, true));
+            }
+            Addw { rd, rs1, rs2 } => self.word(rd, rs1, rs2, |e| e.alu(Alu::Add, false, T0, T1)),
+            Subw { rd, rs1, rs2 } => self.word(rd, rs1, rs2, |e| e.alu(Alu::Sub, false, T0, T1)),
+            Sllw { rd, rs1, rs2 } => self.word(rd, rs1, rs2, |e| e.shift(Shift::Shl, false, T0)),
+            Srlw { rd, rs1, rs2 } => self.word(rd, rs1, rs2, |e| e.shift(Shift::Shr, false, T0)),
+            Sraw { rd, rs1, rs2 } => self.word(rd, rs1, rs2, |e| e.shift(Shift::Sar, false, T0)),
+            Mulw { rd, rs1, rs2 } => self.word(rd, rs1, rs2, |e| e.imul(false, T0, T1)),
+            Divw { rd, rs1, rs2 } => self.word(rd, rs1, rs2, |e| divide(e, false, true, false)),
+            Divuw { rd, rs1, rs2 } => {
+                self.word(rd, rs1, rs2, |e| divide(e, false, false, false));
+            }
+            Remw { rd, rs1, rs2 } => self.word(rd, rs1, rs2, |e| divide(e, false, true, true)),
+            Remuw { rd, rs1, rs2 } => {
+                self.word(rd, rs1, rs2, |e| divide(e, false, false, true));
+            }
+            Addi { rd, rs1, imm } => self.immediate(rd, rs1, imm, Alu::Add),
+            Xori { rd, rs1, imm } => self.immediate(rd, rs1, imm, Alu::Xor),
+            Ori { rd, rs1, imm } => self.immediate(rd, rs1, imm, Alu::Or),
+            Andi { rd, rs1, imm } => self.immediate(rd, rs1, imm, Alu::And),
+            Slti { rd, rs1, imm } => self.unary(rd, rs1, |e| {
+                e.alu_imm(Alu::Cmp, wide, T0, imm.into());
+                e.set(Condition::L, T0);
+            }),
+            Sltiu { rd, rs1, imm } => self.unary(rd, rs1, |e| {
+                e.alu_imm(Alu::Cmp, wide, T0, imm.into());
+                e.set(Condition::B, T0);
+            }),
+            Slli { rd, rs1, imm } => {
+                let shift = self.shamt(imm);
+                self.unary(rd, rs1, |e| e.shift_imm(Shift::Shl, wide, T0, shift));
+            }
+            Srli { rd, rs1, imm } => {
+                let shift = self.shamt(imm);
+                self.unary(rd, rs1, |e| e.shift_imm(Shift::Shr, wide, T0, shift));
+            }
+            Srai { rd, rs1, imm } => {
+                let shift = self.shamt(imm);
+                self.unary(rd, rs1, |e| e.shift_imm(Shift::Sar, wide, T0, shift));
+            }
+            Addiw { rd, rs1, imm } => self.unary(rd, rs1, |e| {
+                e.alu_imm(Alu::Add, false, T0, imm.into());
+                e.movsxd(T0, T0);
+            }),
+            Slliw { rd, rs1, imm } => self.unary(rd, rs1, |e| {
+                e.shift_imm(Shift::Shl, false, T0, imm as u8 & 0x1f);
+                e.movsxd(T0, T0);
+            }),
+            Srliw { rd, rs1, imm } => self.unary(rd, rs1, |e| {
+                e.shift_imm(Shift::Shr, false, T0, imm as u8 & 0x1f);
+                e.movsxd(T0, T0);
+            }),
+            Sraiw { rd, rs1, imm } => self.unary(rd, rs1, |e| {
+                e.shift_imm(Shift::Sar, false, T0, imm as u8 & 0x1f);
+                e.movsxd(T0, T0);
+            }),
+            Lui { rd, imm } => self.constant(rd, imm as i64 as u64),
+            Auipc { rd, imm } => self.constant(rd, pc.wrapping_add(imm as i64 as u64)),
+            Jal { rd, imm } => {
+                let target = self.wrap(pc.wrapping_add(imm as i64 as u64));
+                if self.program.get(target).is_none() {
+                    return false;
+                }
+
+                self.charge();
+                self.link(rd, next);
+                self.emitter.jmp(0);
+                self.fixup(target);
+            }
+            Jalr { rd, rs1, imm } => self.jalr(rd, rs1, imm, next, stub),
+            Beq { rs1, rs2, imm } => return self.branch(pc, rs1, rs2, imm, Condition::E),
+            Bne { rs1, rs2, imm } => return self.branch(pc, rs1, rs2, imm, Condition::Ne),
+            Blt { rs1, rs2, imm } => return self.branch(pc, rs1, rs2, imm, Condition::L),
+            Bge { rs1, rs2, imm } => return self.branch(pc, rs1, rs2, imm, Condition::Ge),
+            Bltu { rs1, rs2, imm } => return self.branch(pc, rs1, rs2, imm, Condition::B),
+            Bgeu { rs1, rs2, imm } => return self.branch(pc, rs1, rs2, imm, Condition::Ae),
+            Lb { rd, rs1, imm }
+            | Lh { rd, rs1, imm }
+            | Lw { rd, rs1, imm }
+            | Lbu { rd, rs1, imm }
+            | Lhu { rd, rs1, imm }
+            | Ld { rd, rs1, imm }
+            | Lwu { rd, rs1, imm } => {
+                let access = instruction.memory_access().expect("loads access memory");
+                self.address(rs1, imm, access.width, stub);
+                self.charge();
+                let encoding = load_encoding(access.width, access.signed, wide);
+                self.emitter.indexed(encoding, T1, MEMORY_BASE, T0, 0);
+                self.store(rd, T1);
+            }
+            Sb { rs1, rs2, imm }
+            | Sh { rs1, rs2, imm }
+            | Sw { rs1, rs2, imm }
+            | Sd { rs1, rs2, imm } => {
+                let access = instruction.memory_access().expect("stores access memory");
+                self.address(rs1, imm, access.width, stub);
+                self.load(T1, rs2);
+                self.charge();
+                let encoding = store_encoding(access.width);
+                self.emitter.indexed(encoding, T1, MEMORY_BASE, T0, 0);
+            }
+            Fence { .. } | FenceTso | FenceI => self.charge(),
+            _ => return false,
+        }
+
+        true
+    }
+
+    /// Loads the value of `register` into `r`.
+    fn load(&mut self, r: R, register: Register) {
+        let offset = REGISTERS + 8 * register.number() as usize;
+        self.emitter.load(r, HART, offset);
+    }
+
+    /// Stores `r` to `register`, unless it is `x0`.
+    fn store(&mut self, register: Register, r: R) {
+        if register != Register::ZERO {
+            let offset = REGISTERS + 8 * register.number() as usize;
+            self.emitter.store(r, HART, offset);
+        }
+    }
+
+    /// Charges the gas of the instruction.
+    fn charge(&mut self) {
+        self.emitter.alu_imm(Alu::Sub, true, GAS_LEFT, 1);
+    }
+
+    /// Emits an instruction of two source registers, loaded into `T0` and `T1`, and
+    /// computed by `op` into `T0`.
+    fn register(
+        &mut self,
+        rd: Register,
+        rs1: Register,
+        rs2: Register,
+        op: impl FnOnce(&mut Emitter),
+    ) {
+        self.charge();
+        self.load(T0, rs1);
+        self.load(T1, rs2);
+        op(&mut self.emitter);
+        self.store(rd, T0);
+    }
+
+    /// Emits an arithmetic or logic instruction of two source registers.
+    fn alu(&mut self, rd: Register, rs1: Register, rs2: Register, op: Alu) {
+        let wide = self.wide;
+        self.register(rd, rs1, rs2, |e| e.alu(op, wide, T0, T1));
+    }
+
+    /// Emits a word instruction, which sign-extends the 32-bit result of `op`.
+    fn word(&mut self, rd: Register, rs1: Register, rs2: Register, op: impl FnOnce(&mut Emitter)) {
+        self.register(rd, rs1, rs2, |e| {
+            op(e);
+            e.movsxd(T0, T0);
+        });
+    }
+
+    /// Emits an instruction of one source register, loaded into `T0` and computed by
+    /// `op` in place.
+    fn unary(&mut self, rd: Register, rs1: Register, op: impl FnOnce(&mut Emitter)) {
+        self.charge();
+        self.load(T0, rs1);
+        op(&mut self.emitter);
+        self.store(rd, T0);
+    }
+
+    /// Emits an arithmetic or logic instruction of a source register and an
+    /// immediate, which x86-64 sign-extends as RISC-V does.
+    fn immediate(&mut self, rd: Register, rs1: Register, imm: i16, op: Alu) {
+        let wide = self.wide;
+        self.unary(rd, rs1, |e| e.alu_imm(op, wide, T0, imm.into()));
+    }
+
+    /// Emits an instruction that writes a value known at translation time.
+    fn constant(&mut self, rd: Register, value: u64) {
+        self.charge();
+        self.link(rd, self.wrap(value));
+    }
+
+    /// Writes `value` to `rd`, as the link of a jump does.
+    fn link(&mut self, rd: Register, value: u64) {
+        if rd != Register::ZERO {
+            self.emitter.mov_imm(self.wide, T2, value);
+            self.store(rd, T2);
+        }
+    }
+
+    /// Records that the jump just emitted goes to the native code of the instruction
+    /// at `target`.
+    fn fixup(&mut self, target: u64) {
+        self.fixups.push((self.emitter.position() - 4, target));
+    }
+
+    /// Emits a conditional jump that is taken if `rs1` and `rs2` satisfy `cond`.
+    /// Returns `false` if the target is not an instruction.
+    fn branch(&mut self, pc: u64, rs1: Register, rs2: Register, imm: i16, cond: Condition) -> bool {
+        let target = self.wrap(pc.wrapping_add(imm as i64 as u64));
+        if self.program.get(target).is_none() {
+            return false;
+        }
+
+        self.charge();
+        self.load(T0, rs1);
+        self.load(T1, rs2);
+        self.emitter.alu(Alu::Cmp, self.wide, T0, T1);
+        self.emitter.jcc(cond, 0);
+        self.fixup(target);
+
+        true
+    }
+
+    /// Emits `jalr`, which looks up the native code of its target in the offsets.
+    /// Targets outside of the code, such as the return address of the call, and in
+    /// the middle of an instruction exit through `stub`.
+    fn jalr(&mut self, rd: Register, rs1: Register, imm: i16, next: u64, stub: usize) {
+        let wide = self.wide;
+        self.load(T0, rs1);
+        let e = &mut self.emitter;
+        e.alu_imm(Alu::Add, wide, T0, imm.into());
+        e.alu_imm(Alu::And, wide, T0, !1);
+        e.mov_imm(true, T1, self.program.len());
+        e.alu(Alu::Cmp, true, T0, T1);
+        e.jcc(Condition::Ae, stub);
+        e.indexed(load_encoding(4, false, false), T1, OFFSETS, T0, 1);
+        e.alu_imm(Alu::Cmp, false, T1, -1);
+        e.jcc(Condition::E, stub);
+
+        self.link(rd, next);
+        self.charge();
+        self.emitter.alu(Alu::Add, true, T1, CODE);
+        self.emitter.jmp_register(T1);
+    }
+
+    /// Computes the address `rs1 + imm` of a memory access into `T0`, and exits
+    /// through `stub` unless all `width` bytes from it are within memory.
+    fn address(&mut self, rs1: Register, imm: i16, width: u8, stub: usize) {
+        let wide = self.wide;
+        self.load(T0, rs1);
+        let e = &mut self.emitter;
+        e.alu_imm(Alu::Add, wide, T0, imm.into());
+        e.alu(Alu::Cmp, true, T0, MEMORY_END);
+        e.jcc(Condition::Ae, stub);
+        e.lea(T1, T0, width.into());
+        e.alu(Alu::Cmp, true, T1, MEMORY_END);
+        e.jcc(Condition::A, stub);
+    }
+
+    /// The shift amount of an immediate shift.
+    fn shamt(&self, imm: i16) -> u8 {
+        match self.wide {
+            true => imm as u8 & 0x3f,
+            false => imm as u8 & 0x1f,
+        }
+    }
+
+    /// Truncates `value` to the register width of the guest.
+    fn wrap(&self, value: u64) -> u64 {
+        match self.wide {
+            true => value,
+            false => value as u32 as u64,
+        }
+    }
+}
+
+/// Emits the high half of the 128-bit product of `T0` and `T1` into `T0`.
+fn high(e: &mut Emitter, op: Unary) {
+    e.unary(op, true, T1);
+    e.mov(true, T0, T2);
+}
+
+/// Emits the high word of the 64-bit product of `T0` and `T1` into `T0`.
+fn high_word(e: &mut Emitter) {
+    e.imul(true, T0, T1);
+    e.shift_imm(Shift::Shr, true, T0, 32);
+}
+
+/// Emits `div`, `divu`, `rem` or `remu` of `T0` by `T1` into `T0`.
+///
+/// x86-64 division faults on a zero divisor and on the overflow of the most negative
+/// value divided by -1, which RISC-V defines instead, so both are handled before
+/// dividing.
+fn divide(e: &mut Emitter, wide: bool, signed: bool, remainder: bool) {
+    e.test(wide, T1, T1);
+    let by_zero = e.jcc_short(Condition::E);
+    let mut by_minus_one = None;
+    match signed {
+        true => {
+            e.alu_imm(Alu::Cmp, wide, T1, -1);
+            let divide = e.jcc_short(Condition::Ne);
+            // Dividing by -1 negates, which wraps, and leaves no remainder.
+            match remainder {
+                true => e.alu(Alu::Xor, false, T0, T0),
+                false => e.unary(Unary::Neg, wide, T0),
+            }
+            by_minus_one = Some(e.jmp_short());
+            e.bind(divide);
+            e.sign_extend_rax(wide);
+            e.unary(Unary::Idiv, wide, T1);
+        }
+        false => {
+            e.alu(Alu::Xor, false, T2, T2);
+            e.unary(Unary::Div, wide, T1);
+        }
+    }
+    if remainder {
+        e.mov(wide, T0, T2);
+    }
+    let done = e.jmp_short();
+
+    // Division by zero yields all ones, and the remainder is the dividend.
+    e.bind(by_zero);
+    if !remainder {
+        e.alu_imm(Alu::Or, wide, T0, -1);
+    }
+    e.bind(done);
+    if let Some(label) = by_minus_one {
+        e.bind(label);
+    }
+}